@@ -47,6 +47,12 @@ fn create_test_workflow(project_id: &str) -> Workflow {
         created_at: Utc::now(),
         updated_at: Utc::now(),
         pause_reason: None,
+        budget_max_tokens: None,
+        budget_max_cost_usd: None,
+        budget_max_duration_secs: None,
+        activity_stall_timeout_secs: None,
+        activity_loop_threshold: None,
+        activity_escalation_interval_secs: None,
     }
 }
 
@@ -87,7 +93,13 @@ fn bench_find_by_id(c: &mut Criterion) {
                 completed_at TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                pause_reason TEXT
+                pause_reason TEXT,
+                budget_max_tokens INTEGER,
+                budget_max_cost_usd REAL,
+                budget_max_duration_secs INTEGER,
+                activity_stall_timeout_secs INTEGER,
+                activity_loop_threshold INTEGER,
+                activity_escalation_interval_secs INTEGER
             )
             "#
         )
@@ -316,7 +328,13 @@ fn bench_find_by_project_with_status(c: &mut Criterion) {
                 completed_at TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                pause_reason TEXT
+                pause_reason TEXT,
+                budget_max_tokens INTEGER,
+                budget_max_cost_usd REAL,
+                budget_max_duration_secs INTEGER,
+                activity_stall_timeout_secs INTEGER,
+                activity_loop_threshold INTEGER,
+                activity_escalation_interval_secs INTEGER
             )
             "#
         )
@@ -0,0 +1,3 @@
+ALTER TABLE workflow DROP COLUMN budget_max_duration_secs;
+ALTER TABLE workflow DROP COLUMN budget_max_cost_usd;
+ALTER TABLE workflow DROP COLUMN budget_max_tokens;
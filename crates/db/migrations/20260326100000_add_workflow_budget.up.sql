@@ -0,0 +1,4 @@
+-- Per-workflow LLM budget; NULL means unlimited
+ALTER TABLE workflow ADD COLUMN budget_max_tokens INTEGER;
+ALTER TABLE workflow ADD COLUMN budget_max_cost_usd REAL;
+ALTER TABLE workflow ADD COLUMN budget_max_duration_secs INTEGER;
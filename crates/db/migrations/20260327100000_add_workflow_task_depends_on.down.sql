@@ -0,0 +1 @@
+ALTER TABLE workflow_task DROP COLUMN depends_on;
@@ -0,0 +1,2 @@
+-- Task dependencies: JSON array of workflow_task ids that must complete first
+ALTER TABLE workflow_task ADD COLUMN depends_on TEXT NOT NULL DEFAULT '[]';
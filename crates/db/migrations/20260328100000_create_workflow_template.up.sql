@@ -0,0 +1,12 @@
+-- Reusable workflow templates (task/terminal graph + parameters)
+CREATE TABLE IF NOT EXISTS workflow_template (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    schema_version INTEGER NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    content TEXT NOT NULL,
+    source_workflow_id TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
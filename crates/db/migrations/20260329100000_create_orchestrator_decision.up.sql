@@ -0,0 +1,27 @@
+-- Audit trail of orchestrator decisions (instructions, prompt decisions, merges)
+CREATE TABLE IF NOT EXISTS orchestrator_decision (
+    id                TEXT PRIMARY KEY,
+    workflow_id       TEXT NOT NULL REFERENCES workflow(id) ON DELETE CASCADE,
+    workflow_event_id TEXT REFERENCES workflow_event(id) ON DELETE SET NULL,
+    decision_type     TEXT NOT NULL,
+    action            TEXT NOT NULL,
+    status            TEXT NOT NULL,
+    terminal_id       TEXT,
+    task_id           TEXT,
+    reasoning         TEXT,
+    detail            TEXT,
+    error             TEXT,
+    provider          TEXT,
+    prompt_tokens     INTEGER,
+    completion_tokens INTEGER,
+    trigger_type      TEXT,
+    trigger_message   TEXT,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_orchestrator_decision_workflow
+    ON orchestrator_decision(workflow_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_orchestrator_decision_terminal
+    ON orchestrator_decision(workflow_id, terminal_id);
+CREATE INDEX IF NOT EXISTS idx_orchestrator_decision_type
+    ON orchestrator_decision(workflow_id, decision_type);
@@ -0,0 +1,3 @@
+ALTER TABLE workflow DROP COLUMN activity_escalation_interval_secs;
+ALTER TABLE workflow DROP COLUMN activity_loop_threshold;
+ALTER TABLE workflow DROP COLUMN activity_stall_timeout_secs;
@@ -0,0 +1,4 @@
+-- Per-workflow terminal activity monitor thresholds; NULL means the default, 0 disables the check
+ALTER TABLE workflow ADD COLUMN activity_stall_timeout_secs INTEGER;
+ALTER TABLE workflow ADD COLUMN activity_loop_threshold INTEGER;
+ALTER TABLE workflow ADD COLUMN activity_escalation_interval_secs INTEGER;
@@ -0,0 +1,2 @@
+ALTER TABLE terminal DROP COLUMN resource_report;
+ALTER TABLE terminal DROP COLUMN resource_limits;
@@ -0,0 +1,4 @@
+-- Per-terminal resource policy (JSON TerminalResourceLimits) and how it was enforced
+-- for the latest PTY session (JSON TerminalResourceReport)
+ALTER TABLE terminal ADD COLUMN resource_limits TEXT;
+ALTER TABLE terminal ADD COLUMN resource_report TEXT;
@@ -0,0 +1,8 @@
+DROP TRIGGER IF EXISTS execution_log_fts_delete;
+DROP TRIGGER IF EXISTS execution_log_fts_insert;
+DROP TABLE IF EXISTS execution_log_fts;
+DROP TRIGGER IF EXISTS terminal_log_fts_update;
+DROP TRIGGER IF EXISTS terminal_log_fts_delete;
+DROP TRIGGER IF EXISTS terminal_log_fts_insert;
+DROP TABLE IF EXISTS terminal_log_fts;
+ALTER TABLE terminal_log DROP COLUMN seq;
@@ -0,0 +1,64 @@
+-- Full-text search over terminal and execution process logs.
+
+-- Output sequence number of the PTY chunk a terminal log row was written from
+-- (NULL for system lines and rows written before this migration)
+ALTER TABLE terminal_log ADD COLUMN seq INTEGER;
+
+-- Both indexes use the trigram tokenizer: matches are case-insensitive substrings, so
+-- words glued to ANSI escape sequences in raw PTY output are still found.
+
+-- terminal_log is indexed in place (external content keyed by terminal_log.rowid)
+CREATE VIRTUAL TABLE IF NOT EXISTS terminal_log_fts USING fts5(
+    content,
+    content = 'terminal_log',
+    content_rowid = 'rowid',
+    tokenize = 'trigram'
+);
+
+CREATE TRIGGER IF NOT EXISTS terminal_log_fts_insert AFTER INSERT ON terminal_log BEGIN
+    INSERT INTO terminal_log_fts (rowid, content) VALUES (NEW.rowid, NEW.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS terminal_log_fts_delete AFTER DELETE ON terminal_log BEGIN
+    INSERT INTO terminal_log_fts (terminal_log_fts, rowid, content)
+    VALUES ('delete', OLD.rowid, OLD.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS terminal_log_fts_update AFTER UPDATE OF content ON terminal_log BEGIN
+    INSERT INTO terminal_log_fts (terminal_log_fts, rowid, content)
+    VALUES ('delete', OLD.rowid, OLD.content);
+    INSERT INTO terminal_log_fts (rowid, content) VALUES (NEW.rowid, NEW.content);
+END;
+
+INSERT INTO terminal_log_fts (terminal_log_fts) VALUES ('rebuild');
+
+-- execution_process_logs rows are JSONL LogMsg records; only the text of Stdout/Stderr
+-- messages is indexed, keyed by execution_process_logs.rowid
+CREATE VIRTUAL TABLE IF NOT EXISTS execution_log_fts USING fts5(content, tokenize = 'trigram');
+
+CREATE TRIGGER IF NOT EXISTS execution_log_fts_insert AFTER INSERT ON execution_process_logs
+WHEN CASE WHEN json_valid(NEW.logs)
+          THEN COALESCE(json_extract(NEW.logs, '$.Stdout'), json_extract(NEW.logs, '$.Stderr'))
+     END IS NOT NULL
+BEGIN
+    INSERT INTO execution_log_fts (rowid, content)
+    VALUES (
+        NEW.rowid,
+        COALESCE(json_extract(NEW.logs, '$.Stdout'), json_extract(NEW.logs, '$.Stderr'))
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS execution_log_fts_delete AFTER DELETE ON execution_process_logs BEGIN
+    DELETE FROM execution_log_fts WHERE rowid = OLD.rowid;
+END;
+
+INSERT INTO execution_log_fts (rowid, content)
+SELECT rowid, text
+FROM (
+    SELECT rowid,
+           CASE WHEN json_valid(logs)
+                THEN COALESCE(json_extract(logs, '$.Stdout'), json_extract(logs, '$.Stderr'))
+           END AS text
+    FROM execution_process_logs
+)
+WHERE text IS NOT NULL;
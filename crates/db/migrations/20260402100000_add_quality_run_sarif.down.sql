@@ -0,0 +1 @@
+ALTER TABLE quality_run DROP COLUMN sarif_json;
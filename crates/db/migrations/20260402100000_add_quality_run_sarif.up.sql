@@ -0,0 +1,2 @@
+-- SARIF 2.1.0 export of the quality report, stored next to report_json
+ALTER TABLE quality_run ADD COLUMN sarif_json TEXT;
@@ -0,0 +1,2 @@
+-- Runner labels (JSON array of strings) a remote runner must carry to host the terminal
+ALTER TABLE terminal ADD COLUMN runner_labels TEXT;
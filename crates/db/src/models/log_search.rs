@@ -0,0 +1,230 @@
+//! Full-text search over terminal and execution process logs
+//!
+//! Backed by the `terminal_log_fts` and `execution_log_fts` FTS5 tables, which
+//! triggers keep in sync with `terminal_log` and `execution_process_logs`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+pub const LOG_SOURCE_TERMINAL: &str = "terminal";
+pub const LOG_SOURCE_EXECUTION: &str = "execution";
+
+/// Shortest search term the trigram index can match
+pub const MIN_SEARCH_TERM_CHARS: usize = 3;
+
+/// Log search filters; unset fields match everything
+///
+/// Execution process logs are not tied to a workflow or terminal, so they are
+/// only searched when neither `workflow_id` nor `terminal_id` is set.
+#[derive(Debug, Clone, Default)]
+pub struct LogSearchFilter {
+    /// Free text; every whitespace-separated term must occur as a case-insensitive
+    /// substring. Terms shorter than [`MIN_SEARCH_TERM_CHARS`] never match.
+    pub text: Option<String>,
+    pub workflow_id: Option<String>,
+    pub terminal_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// One matching log row
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct LogSearchHit {
+    /// terminal | execution
+    pub source: String,
+    pub terminal_id: Option<String>,
+    pub workflow_id: Option<String>,
+    pub execution_id: Option<Uuid>,
+    /// Position to jump to: the PTY output chunk seq for terminal logs, the
+    /// zero-based message index for execution logs
+    pub seq: Option<i64>,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    /// Row id in the source table
+    #[serde(skip)]
+    #[ts(skip)]
+    pub source_row: i64,
+}
+
+impl LogSearchHit {
+    /// Matching log rows across terminal and execution logs, oldest first.
+    pub async fn search(
+        pool: &SqlitePool,
+        filter: &LogSearchFilter,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        let match_expr = filter.text.as_deref().and_then(fts_match_expr);
+        // Message indexes are only numbered within executions that have a match
+        let (terminal_from, text_clause, execution_scope) = if match_expr.is_some() {
+            (
+                "terminal_log_fts f JOIN terminal_log l ON l.rowid = f.rowid",
+                ("terminal_log_fts MATCH ?1", "execution_log_fts MATCH ?1"),
+                "execution_id IN (
+                        SELECT p.execution_id
+                        FROM execution_log_fts m
+                        JOIN execution_process_logs p ON p.rowid = m.rowid
+                        WHERE execution_log_fts MATCH ?1
+                    )",
+            )
+        } else {
+            ("terminal_log l", ("?1 IS NULL", "?1 IS NULL"), "1")
+        };
+        let sql = format!(
+            r"SELECT * FROM (
+                SELECT 'terminal' AS source, l.terminal_id AS terminal_id,
+                       wt.workflow_id AS workflow_id, NULL AS execution_id, l.seq AS seq,
+                       l.content AS content, l.created_at AS created_at, l.rowid AS source_row
+                FROM {terminal_from}
+                LEFT JOIN terminal t ON t.id = l.terminal_id
+                LEFT JOIN workflow_task wt ON wt.id = t.workflow_task_id
+                WHERE {terminal_text}
+                  AND (?2 IS NULL OR wt.workflow_id = ?2)
+                  AND (?3 IS NULL OR l.terminal_id = ?3)
+                  AND (?4 IS NULL OR julianday(l.created_at) >= julianday(?4))
+                  AND (?5 IS NULL OR julianday(l.created_at) <= julianday(?5))
+                UNION ALL
+                SELECT 'execution', NULL, NULL, e.execution_id, e.message_index,
+                       x.content, e.inserted_at, e.row_id
+                FROM execution_log_fts x
+                JOIN (
+                    SELECT rowid AS row_id, execution_id, inserted_at,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY execution_id ORDER BY rowid
+                           ) - 1 AS message_index
+                    FROM execution_process_logs
+                    WHERE ?2 IS NULL AND ?3 IS NULL AND {execution_scope}
+                ) e ON e.row_id = x.rowid
+                WHERE {execution_text}
+                  AND ?2 IS NULL AND ?3 IS NULL
+                  AND (?4 IS NULL OR julianday(e.inserted_at) >= julianday(?4))
+                  AND (?5 IS NULL OR julianday(e.inserted_at) <= julianday(?5))
+              )
+              ORDER BY julianday(created_at) ASC, source ASC, source_row ASC
+              LIMIT ?6 OFFSET ?7",
+            terminal_text = text_clause.0,
+            execution_text = text_clause.1,
+        );
+
+        sqlx::query_as::<_, Self>(&sql)
+            .bind(&match_expr)
+            .bind(&filter.workflow_id)
+            .bind(&filter.terminal_id)
+            .bind(filter.since)
+            .bind(filter.until)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await
+    }
+}
+
+/// Quote every term so user input is matched literally rather than parsed as
+/// FTS5 query syntax. `None` when the text has no terms.
+pub fn fts_match_expr(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        // Log rows reference execution processes the test does not create
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    async fn insert_execution_log(pool: &SqlitePool, execution_id: Uuid, line: &str) {
+        sqlx::query(
+            "INSERT INTO execution_process_logs (execution_id, logs, byte_size) VALUES (?1, ?2, ?3)",
+        )
+        .bind(execution_id)
+        .bind(line)
+        .bind(line.len() as i64)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn execution_hits_carry_their_message_index() {
+        let pool = setup_pool().await;
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        for (execution_id, line) in [
+            (first, r#"{"Stdout":"compiling"}"#),
+            (second, r#"{"Stderr":"error: linker failed"}"#),
+            (first, r#"{"JsonPatch":[]}"#),
+            (first, r#"{"Stderr":"error[E0308]: mismatched types"}"#),
+            (second, r#"{"Stdout":"retrying"}"#),
+            (first, r#"{"Stdout":"second error shown"}"#),
+        ] {
+            insert_execution_log(&pool, execution_id, line).await;
+        }
+
+        let filter = LogSearchFilter {
+            text: Some("error".to_string()),
+            ..Default::default()
+        };
+        let hits = LogSearchHit::search(&pool, &filter, 50, 0).await.unwrap();
+        let positions = hits
+            .iter()
+            .map(|hit| (hit.source.as_str(), hit.execution_id, hit.seq))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                (LOG_SOURCE_EXECUTION, Some(second), Some(0)),
+                (LOG_SOURCE_EXECUTION, Some(first), Some(2)),
+                (LOG_SOURCE_EXECUTION, Some(first), Some(3)),
+            ]
+        );
+
+        // Paging does not shift the indexes
+        let page = LogSearchHit::search(&pool, &filter, 1, 2).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].seq, Some(3));
+
+        // Terminal filters exclude execution logs
+        let scoped = LogSearchFilter {
+            terminal_id: Some("term-1".to_string()),
+            ..filter
+        };
+        assert!(
+            LogSearchHit::search(&pool, &scoped, 50, 0)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn fts_match_expr_quotes_terms() {
+        assert_eq!(fts_match_expr("  "), None);
+        assert_eq!(
+            fts_match_expr("error: OR \"x\""),
+            Some(r#""error:" "OR" """x""""#.to_string())
+        );
+    }
+}
@@ -20,6 +20,8 @@ pub mod concierge;
 pub mod cli_type;
 pub mod feishu_config;
 pub mod git_event;
+pub mod log_search;
+pub mod orchestrator_decision;
 pub mod orchestrator_message;
 pub mod planning_draft;
 pub mod quality_issue;
@@ -29,10 +31,12 @@ pub mod system_settings;
 pub mod terminal;
 pub mod workflow;
 pub mod workflow_event;
+pub mod workflow_template;
 
 pub use cli_type::*;
 pub use concierge::*;
 pub use git_event::*;
+pub use orchestrator_decision::OrchestratorDecision;
 pub use orchestrator_message::*;
 pub use quality_issue::*;
 pub use quality_policy_snapshot::*;
@@ -40,3 +44,4 @@ pub use quality_run::*;
 pub use system_settings::SystemSetting;
 pub use terminal::*;
 pub use workflow::*;
+pub use workflow_template::WorkflowTemplate;
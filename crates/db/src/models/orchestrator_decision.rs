@@ -0,0 +1,356 @@
+//! Orchestrator Decision Model
+//!
+//! Audit trail of orchestrator decisions: executed instructions, terminal
+//! prompt decisions and merges. Every decision is linked to a `workflow_event`
+//! row so it also shows up in the workflow timeline.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::workflow_event::WorkflowEvent;
+
+/// `workflow_event.event_type` of the timeline entry linked to a decision
+pub const DECISION_EVENT_TYPE: &str = "orchestrator_decision";
+
+/// Orchestrator instruction executed from an LLM response
+pub const DECISION_TYPE_INSTRUCTION: &str = "instruction";
+/// Decision on an interactive terminal prompt
+pub const DECISION_TYPE_PROMPT: &str = "prompt_decision";
+/// Merge triggered by the orchestrator
+pub const DECISION_TYPE_MERGE: &str = "merge";
+/// Recovery step taken for a stalled or looping terminal
+pub const DECISION_TYPE_RECOVERY: &str = "recovery";
+
+/// Orchestrator decision
+///
+/// Corresponds to database table: orchestrator_decision
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct OrchestratorDecision {
+    pub id: String,
+    pub workflow_id: String,
+    /// Linked timeline entry
+    pub workflow_event_id: Option<String>,
+    /// instruction | prompt_decision | merge
+    pub decision_type: String,
+    /// Instruction type (`send_to_terminal`, ...), prompt decision action
+    /// (`auto_confirm`, `llm_decision`, ...) or merge kind
+    pub action: String,
+    /// applied | failed | timed_out
+    pub status: String,
+    pub terminal_id: Option<String>,
+    pub task_id: Option<String>,
+    /// Why the decision was made (LLM reply text, prompt decision reason)
+    pub reasoning: Option<String>,
+    /// Full decision payload as JSON
+    pub detail: Option<String>,
+    pub error: Option<String>,
+    /// LLM provider that produced the decision
+    pub provider: Option<String>,
+    pub prompt_tokens: Option<i64>,
+    pub completion_tokens: Option<i64>,
+    /// Bus message variant that triggered the decision (or `user_chat`)
+    pub trigger_type: Option<String>,
+    /// Triggering bus message as JSON
+    pub trigger_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filters for [`OrchestratorDecision::find_page`]
+#[derive(Debug, Clone, Default)]
+pub struct DecisionFilter {
+    pub terminal_id: Option<String>,
+    pub decision_type: Option<String>,
+}
+
+impl OrchestratorDecision {
+    /// Create a decision with the required fields; the rest default to `None`.
+    pub fn new(workflow_id: &str, decision_type: &str, action: &str, status: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: workflow_id.to_string(),
+            workflow_event_id: None,
+            decision_type: decision_type.to_string(),
+            action: action.to_string(),
+            status: status.to_string(),
+            terminal_id: None,
+            task_id: None,
+            reasoning: None,
+            detail: None,
+            error: None,
+            provider: None,
+            prompt_tokens: None,
+            completion_tokens: None,
+            trigger_type: None,
+            trigger_message: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Insert the decision together with its timeline entry in one transaction.
+    ///
+    /// Sets `workflow_event_id` to the id of the inserted event.
+    pub async fn insert_with_event(
+        pool: &SqlitePool,
+        decision: &mut Self,
+        summary: &str,
+    ) -> sqlx::Result<()> {
+        let event = WorkflowEvent {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: decision.workflow_id.clone(),
+            event_type: DECISION_EVENT_TYPE.to_string(),
+            summary: summary.to_string(),
+            metadata: Some(
+                serde_json::json!({
+                    "decisionId": decision.id,
+                    "decisionType": decision.decision_type,
+                    "action": decision.action,
+                })
+                .to_string(),
+            ),
+            created_at: decision.created_at,
+        };
+        decision.workflow_event_id = Some(event.id.clone());
+
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            r"INSERT INTO workflow_event (id, workflow_id, event_type, summary, metadata, created_at)
+              VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .bind(&event.id)
+        .bind(&event.workflow_id)
+        .bind(&event.event_type)
+        .bind(&event.summary)
+        .bind(&event.metadata)
+        .bind(event.created_at)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r"INSERT INTO orchestrator_decision (
+                id, workflow_id, workflow_event_id, decision_type, action, status,
+                terminal_id, task_id, reasoning, detail, error, provider,
+                prompt_tokens, completion_tokens, trigger_type, trigger_message, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+        )
+        .bind(&decision.id)
+        .bind(&decision.workflow_id)
+        .bind(&decision.workflow_event_id)
+        .bind(&decision.decision_type)
+        .bind(&decision.action)
+        .bind(&decision.status)
+        .bind(&decision.terminal_id)
+        .bind(&decision.task_id)
+        .bind(&decision.reasoning)
+        .bind(&decision.detail)
+        .bind(&decision.error)
+        .bind(&decision.provider)
+        .bind(decision.prompt_tokens)
+        .bind(decision.completion_tokens)
+        .bind(&decision.trigger_type)
+        .bind(&decision.trigger_message)
+        .bind(decision.created_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// One page of a workflow's decisions, oldest first.
+    pub async fn find_page(
+        pool: &SqlitePool,
+        workflow_id: &str,
+        filter: &DecisionFilter,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM orchestrator_decision
+              WHERE workflow_id = ?1
+                AND (?2 IS NULL OR terminal_id = ?2)
+                AND (?3 IS NULL OR decision_type = ?3)
+              ORDER BY created_at ASC, id ASC
+              LIMIT ?4 OFFSET ?5",
+        )
+        .bind(workflow_id)
+        .bind(&filter.terminal_id)
+        .bind(&filter.decision_type)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Number of decisions matching `filter`.
+    pub async fn count(
+        pool: &SqlitePool,
+        workflow_id: &str,
+        filter: &DecisionFilter,
+    ) -> sqlx::Result<i64> {
+        sqlx::query_scalar(
+            r"SELECT COUNT(*) FROM orchestrator_decision
+              WHERE workflow_id = ?1
+                AND (?2 IS NULL OR terminal_id = ?2)
+                AND (?3 IS NULL OR decision_type = ?3)",
+        )
+        .bind(workflow_id)
+        .bind(&filter.terminal_id)
+        .bind(&filter.decision_type)
+        .fetch_one(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let project_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO projects (id, name, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(project_id)
+        .bind("decision-project")
+        .bind(Utc::now())
+        .bind(Utc::now())
+        .execute(&pool)
+        .await
+        .unwrap();
+        for workflow_id in ["wf-1", "wf-2"] {
+            sqlx::query(
+                r"INSERT INTO workflow (
+                    id, project_id, name, target_branch,
+                    merge_terminal_cli_id, merge_terminal_model_id, created_at, updated_at
+                ) VALUES (?1, ?2, ?3, 'main', 'cli-claude-code', 'model-claude-sonnet', ?4, ?5)",
+            )
+            .bind(workflow_id)
+            .bind(project_id)
+            .bind(workflow_id)
+            .bind(Utc::now())
+            .bind(Utc::now())
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        pool
+    }
+
+    /// Inserts five `wf-1` decisions one second apart (terminal `t1` for the first
+    /// three, alternating instruction/prompt types) and one `wf-2` decision.
+    async fn insert_decisions(pool: &SqlitePool) -> Vec<String> {
+        let base = Utc::now();
+        let mut ids = Vec::new();
+        for i in 0..5 {
+            let decision_type = if i % 2 == 0 {
+                DECISION_TYPE_INSTRUCTION
+            } else {
+                DECISION_TYPE_PROMPT
+            };
+            let mut decision = OrchestratorDecision::new("wf-1", decision_type, "act", "applied");
+            decision.terminal_id = Some(if i < 3 { "t1" } else { "t2" }.to_string());
+            decision.created_at = base + Duration::seconds(i);
+            OrchestratorDecision::insert_with_event(pool, &mut decision, "summary")
+                .await
+                .unwrap();
+            ids.push(decision.id);
+        }
+        let mut other = OrchestratorDecision::new("wf-2", DECISION_TYPE_MERGE, "merge", "applied");
+        other.terminal_id = Some("t1".to_string());
+        OrchestratorDecision::insert_with_event(pool, &mut other, "summary")
+            .await
+            .unwrap();
+        ids
+    }
+
+    #[tokio::test]
+    async fn find_page_pages_oldest_first() {
+        let pool = setup_pool().await;
+        let ids = insert_decisions(&pool).await;
+        let all = DecisionFilter::default();
+
+        let first = OrchestratorDecision::find_page(&pool, "wf-1", &all, 2, 0)
+            .await
+            .unwrap();
+        let first: Vec<_> = first.into_iter().map(|d| d.id).collect();
+        assert_eq!(first, ids[..2]);
+
+        let last = OrchestratorDecision::find_page(&pool, "wf-1", &all, 2, 4)
+            .await
+            .unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].id, ids[4]);
+        assert!(last[0].workflow_event_id.is_some());
+
+        let past_end = OrchestratorDecision::find_page(&pool, "wf-1", &all, 2, 10)
+            .await
+            .unwrap();
+        assert!(past_end.is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_page_and_count_apply_filters() {
+        let pool = setup_pool().await;
+        let ids = insert_decisions(&pool).await;
+
+        let all = DecisionFilter::default();
+        assert_eq!(
+            OrchestratorDecision::count(&pool, "wf-1", &all)
+                .await
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            OrchestratorDecision::count(&pool, "wf-2", &all)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            OrchestratorDecision::count(&pool, "wf-3", &all)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let by_terminal = DecisionFilter {
+            terminal_id: Some("t1".to_string()),
+            decision_type: None,
+        };
+        assert_eq!(
+            OrchestratorDecision::count(&pool, "wf-1", &by_terminal)
+                .await
+                .unwrap(),
+            3
+        );
+
+        let prompts_on_t1 = DecisionFilter {
+            terminal_id: Some("t1".to_string()),
+            decision_type: Some(DECISION_TYPE_PROMPT.to_string()),
+        };
+        assert_eq!(
+            OrchestratorDecision::count(&pool, "wf-1", &prompts_on_t1)
+                .await
+                .unwrap(),
+            1
+        );
+        let page = OrchestratorDecision::find_page(&pool, "wf-1", &prompts_on_t1, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].id, ids[1]);
+    }
+}
@@ -37,6 +37,8 @@ pub struct QualityRun {
     pub report_json: Option<String>,
     /// Serialized QualityGateDecision
     pub decision_json: Option<String>,
+    /// SARIF 2.1.0 export of the report
+    pub sarif_json: Option<String>,
     /// Error message if the run itself failed
     pub error_message: Option<String>,
     pub created_at: DateTime<Utc>,
@@ -69,6 +71,7 @@ impl QualityRun {
             providers_run: None,
             report_json: None,
             decision_json: None,
+            sarif_json: None,
             error_message: None,
             created_at: Utc::now(),
             completed_at: None,
@@ -82,9 +85,9 @@ impl QualityRun {
                 id, workflow_id, task_id, terminal_id, commit_hash,
                 gate_level, gate_status, mode,
                 total_issues, blocking_issues, new_issues, duration_ms,
-                providers_run, report_json, decision_json, error_message,
+                providers_run, report_json, decision_json, sarif_json, error_message,
                 created_at, completed_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
         )
         .bind(&run.id)
         .bind(&run.workflow_id)
@@ -101,6 +104,7 @@ impl QualityRun {
         .bind(&run.providers_run)
         .bind(&run.report_json)
         .bind(&run.decision_json)
+        .bind(&run.sarif_json)
         .bind(&run.error_message)
         .bind(run.created_at)
         .bind(run.completed_at)
@@ -131,13 +135,14 @@ impl QualityRun {
         providers_run: Option<&str>,
         report_json: Option<&str>,
         decision_json: Option<&str>,
+        sarif_json: Option<&str>,
     ) -> sqlx::Result<()> {
         sqlx::query(
             r"UPDATE quality_run
             SET gate_status = ?1, total_issues = ?2, blocking_issues = ?3,
                 new_issues = ?4, duration_ms = ?5, providers_run = ?6,
-                report_json = ?7, decision_json = ?8, completed_at = ?9
-            WHERE id = ?10",
+                report_json = ?7, decision_json = ?8, sarif_json = ?9, completed_at = ?10
+            WHERE id = ?11",
         )
         .bind(gate_status)
         .bind(total_issues)
@@ -147,6 +152,7 @@ impl QualityRun {
         .bind(providers_run)
         .bind(report_json)
         .bind(decision_json)
+        .bind(sarif_json)
         .bind(Utc::now())
         .bind(id)
         .execute(pool)
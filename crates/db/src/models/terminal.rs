@@ -119,6 +119,18 @@ pub struct Terminal {
     /// - Gemini: --yolo
     pub auto_confirm: bool,
 
+    /// Resource limits applied whenever the terminal process is spawned
+    #[sqlx(json(nullable))]
+    pub resource_limits: Option<TerminalResourceLimits>,
+
+    /// How `resource_limits` were enforced for the latest PTY session
+    #[sqlx(json(nullable))]
+    pub resource_report: Option<TerminalResourceReport>,
+
+    /// Labels a remote runner must carry to host this terminal
+    #[sqlx(json(nullable))]
+    pub runner_labels: Option<Vec<String>>,
+
     /// Last Git commit hash
     pub last_commit_hash: Option<String>,
 
@@ -138,6 +150,107 @@ pub struct Terminal {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Terminal resource limits
+///
+/// Every limit is optional; limits are enforced on Linux (see
+/// `services::terminal::sandbox`), other platforms only get the wall-clock timeout.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TerminalResourceLimits {
+    /// CPU cap in percent of one core (200 = two cores)
+    pub cpu_percent: Option<u32>,
+    /// Memory cap in MiB
+    pub memory_mb: Option<u32>,
+    /// Maximum number of processes (threads included)
+    pub max_processes: Option<u32>,
+    /// Kill the terminal process after this many seconds
+    pub wall_clock_timeout_secs: Option<u32>,
+    /// Run without network access
+    #[serde(default)]
+    pub network_disabled: bool,
+}
+
+impl TerminalResourceLimits {
+    /// True when no limit is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Rejects zero-valued limits.
+    pub fn validate(&self) -> Result<(), String> {
+        let limits = [
+            ("cpuPercent", self.cpu_percent),
+            ("memoryMb", self.memory_mb),
+            ("maxProcesses", self.max_processes),
+            ("wallClockTimeoutSecs", self.wall_clock_timeout_secs),
+        ];
+        match limits.iter().find(|(_, value)| *value == Some(0)) {
+            Some((name, _)) => Err(format!("{name} must be greater than 0")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Limited resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum TerminalResource {
+    Cpu,
+    Memory,
+    Processes,
+    WallClock,
+    Network,
+}
+
+/// Mechanism enforcing a resource limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ResourceEnforcement {
+    /// cgroup v2 controller
+    Cgroup,
+    /// setrlimit fallback
+    Rlimit,
+    /// Linux namespace
+    Namespace,
+    /// Killed by the process manager
+    Supervisor,
+    /// Requested but not supported on this host
+    Unenforced,
+}
+
+/// Enforcement of one requested limit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AppliedResourceLimit {
+    pub resource: TerminalResource,
+    pub enforcement: ResourceEnforcement,
+}
+
+/// Resource limits in effect for a PTY session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TerminalResourceReport {
+    pub applied: Vec<AppliedResourceLimit>,
+    /// cgroup v2 directory of the session, when cgroups are used
+    pub cgroup_path: Option<String>,
+}
+
+impl TerminalResourceReport {
+    /// Requested limits the host could not enforce.
+    pub fn unenforced(&self) -> Vec<TerminalResource> {
+        self.applied
+            .iter()
+            .filter(|limit| limit.enforcement == ResourceEnforcement::Unenforced)
+            .map(|limit| limit.resource)
+            .collect()
+    }
+}
+
 /// Terminal Log Type
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, EnumString, Display,
@@ -171,6 +284,9 @@ pub struct TerminalLog {
     /// Log content
     pub content: String,
 
+    /// Output sequence number of the PTY chunk this line came from
+    pub seq: Option<i64>,
+
     /// Created timestamp
     pub created_at: DateTime<Utc>,
 }
@@ -216,8 +332,9 @@ impl Terminal {
             INSERT INTO terminal (
                 id, workflow_task_id, cli_type_id, model_config_id,
                 custom_base_url, custom_api_key, role, role_description,
-                order_index, status, auto_confirm, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+                order_index, status, auto_confirm, resource_limits, runner_labels,
+                created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
             RETURNING *
             ",
         )
@@ -232,6 +349,8 @@ impl Terminal {
         .bind(terminal.order_index)
         .bind(&terminal.status)
         .bind(terminal.auto_confirm)
+        .bind(terminal.resource_limits.as_ref().map(sqlx::types::Json))
+        .bind(terminal.runner_labels.as_ref().map(sqlx::types::Json))
         .bind(terminal.created_at)
         .bind(terminal.updated_at)
         .fetch_one(pool)
@@ -395,6 +514,69 @@ impl Terminal {
         Ok(())
     }
 
+    /// Replace the terminal's resource limits (applied from the next spawn on)
+    pub async fn update_resource_limits(
+        pool: &SqlitePool,
+        id: &str,
+        limits: Option<&TerminalResourceLimits>,
+    ) -> sqlx::Result<()> {
+        sqlx::query(
+            r"
+            UPDATE terminal
+            SET resource_limits = ?, updated_at = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(limits.map(sqlx::types::Json))
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Replace the runner labels required to host the terminal (applied from the next spawn on)
+    pub async fn update_runner_labels(
+        pool: &SqlitePool,
+        id: &str,
+        labels: Option<&[String]>,
+    ) -> sqlx::Result<()> {
+        sqlx::query(
+            r"
+            UPDATE terminal
+            SET runner_labels = ?, updated_at = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(labels.map(sqlx::types::Json))
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record how resource limits were enforced for the current PTY session
+    pub async fn update_resource_report(
+        pool: &SqlitePool,
+        id: &str,
+        report: Option<&TerminalResourceReport>,
+    ) -> sqlx::Result<()> {
+        sqlx::query(
+            r"
+            UPDATE terminal
+            SET resource_report = ?, updated_at = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(report.map(sqlx::types::Json))
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     /// Update terminal session binding
     pub async fn update_session(
         pool: &SqlitePool,
@@ -715,6 +897,9 @@ mod tests {
                     execution_process_id: None,
                     vk_session_id: None,
                     auto_confirm: false,
+                    resource_limits: None,
+                    resource_report: None,
+                    runner_labels: None,
                     last_commit_hash: None,
                     last_commit_message: None,
                     started_at: None,
@@ -767,6 +952,9 @@ mod tests {
                 execution_process_id: None,
                 vk_session_id: None,
                 auto_confirm: false,
+                resource_limits: None,
+                resource_report: None,
+                runner_labels: None,
                 last_commit_hash: None,
                 last_commit_message: None,
                 started_at: None,
@@ -808,6 +996,9 @@ mod tests {
                 execution_process_id: None,
                 vk_session_id: None,
                 auto_confirm: false,
+                resource_limits: None,
+                resource_report: None,
+                runner_labels: None,
                 last_commit_hash: None,
                 last_commit_message: None,
                 started_at: None,
@@ -846,6 +1037,9 @@ mod tests {
                     execution_process_id: None,
                     vk_session_id: None,
                     auto_confirm: false,
+                    resource_limits: None,
+                    resource_report: None,
+                    runner_labels: None,
                     last_commit_hash: None,
                     last_commit_message: None,
                     started_at: None,
@@ -884,6 +1078,9 @@ mod tests {
                     execution_process_id: None,
                     vk_session_id: None,
                     auto_confirm: false,
+                    resource_limits: None,
+                    resource_report: None,
+                    runner_labels: None,
                     last_commit_hash: None,
                     last_commit_message: None,
                     started_at: None,
@@ -932,6 +1129,9 @@ mod tests {
                     execution_process_id: None,
                     vk_session_id: None,
                     auto_confirm: false,
+                    resource_limits: None,
+                    resource_report: None,
+                    runner_labels: None,
                     last_commit_hash: None,
                     last_commit_message: None,
                     started_at: None,
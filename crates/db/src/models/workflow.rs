@@ -11,7 +11,7 @@ use ts_rs::TS;
 use uuid::Uuid;
 
 // Import Terminal type for batch operations
-use super::terminal::Terminal;
+use super::terminal::{Terminal, TerminalResourceLimits};
 
 /// Workflow Status Enum
 #[derive(
@@ -189,6 +189,25 @@ pub struct Workflow {
 
     /// Reason for pause (e.g., "api_exhausted", "user_requested")
     pub pause_reason: Option<String>,
+
+    /// Orchestrator token budget (NULL = unlimited)
+    pub budget_max_tokens: Option<i64>,
+
+    /// Orchestrator spend budget in USD (NULL = unlimited)
+    pub budget_max_cost_usd: Option<f64>,
+
+    /// Orchestrator wall-clock budget in seconds (NULL = unlimited)
+    pub budget_max_duration_secs: Option<i64>,
+
+    /// Terminal idle time without output or commits before escalating
+    /// (NULL = default, 0 = disabled)
+    pub activity_stall_timeout_secs: Option<i64>,
+
+    /// Repeats of the same tool call that count as a loop (NULL = default, 0 = disabled)
+    pub activity_loop_threshold: Option<i64>,
+
+    /// Minimum time between escalation steps (NULL = default)
+    pub activity_escalation_interval_secs: Option<i64>,
 }
 
 impl Workflow {
@@ -244,6 +263,11 @@ pub struct WorkflowTask {
     /// Task order
     pub order_index: i32,
 
+    /// IDs of tasks (same workflow) that must complete before this task starts
+    #[sqlx(json)]
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+
     /// Started timestamp
     pub started_at: Option<DateTime<Utc>>,
 
@@ -333,6 +357,9 @@ pub struct CreateWorkflowTaskRequest {
     pub branch: Option<String>,
     /// Task order index
     pub order_index: i32,
+    /// `id`s of other tasks in this request that must complete first
+    #[serde(default)]
+    pub depends_on: Vec<String>,
     /// Terminals for this task
     pub terminals: Vec<CreateTerminalRequest>,
 }
@@ -390,6 +417,12 @@ pub struct CreateTerminalRequest {
     /// Auto-confirm mode: skip CLI permission prompts (defaults to true)
     #[serde(default = "default_auto_confirm_true")]
     pub auto_confirm: bool,
+    /// Resource limits for the terminal process (optional)
+    #[serde(default)]
+    pub resource_limits: Option<TerminalResourceLimits>,
+    /// Labels a remote runner must carry to host the terminal (optional)
+    #[serde(default)]
+    pub runner_labels: Option<Vec<String>>,
 }
 
 /// Create Workflow Request
@@ -422,6 +455,12 @@ pub struct CreateWorkflowRequest {
     pub target_branch: Option<String>,
     /// Enable git watcher (default true)
     pub git_watcher_enabled: Option<bool>,
+    /// Orchestrator budget (omit for unlimited)
+    #[serde(default)]
+    pub budget: Option<WorkflowBudgetRequest>,
+    /// Terminal activity monitor thresholds (omit for defaults)
+    #[serde(default)]
+    pub activity_policy: Option<WorkflowActivityPolicyRequest>,
 
     // ========== 新增字段 ==========
     /// Workflow tasks with terminals
@@ -429,6 +468,26 @@ pub struct CreateWorkflowRequest {
     pub tasks: Vec<CreateWorkflowTaskRequest>,
 }
 
+/// Orchestrator budget limits; `None` fields mean unlimited
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WorkflowBudgetRequest {
+    pub max_tokens: Option<i64>,
+    pub max_cost_usd: Option<f64>,
+    pub max_duration_secs: Option<i64>,
+}
+
+/// Terminal activity monitor thresholds; `None` fields use the defaults, 0 disables
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WorkflowActivityPolicyRequest {
+    pub stall_timeout_secs: Option<i64>,
+    pub loop_threshold: Option<i64>,
+    pub escalation_interval_secs: Option<i64>,
+}
+
 /// Workflow command request for creating workflow
 #[derive(Debug, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -447,6 +506,8 @@ pub struct WorkflowCommandRequest {
 pub struct OrchestratorConfig {
     pub api_type: String,
     pub base_url: String,
+    /// May be empty for the `local` API type
+    #[serde(default)]
     pub api_key: String,
     pub model: String,
 }
@@ -477,8 +538,11 @@ impl Workflow {
                 orchestrator_api_key, orchestrator_model,
                 error_terminal_enabled, error_terminal_cli_id, error_terminal_model_id,
                 merge_terminal_cli_id, merge_terminal_model_id,
-                target_branch, git_watcher_enabled, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)
+                target_branch, git_watcher_enabled, created_at, updated_at,
+                budget_max_tokens, budget_max_cost_usd, budget_max_duration_secs,
+                activity_stall_timeout_secs, activity_loop_threshold,
+                activity_escalation_interval_secs
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28)
             RETURNING *
             "
         )
@@ -504,6 +568,12 @@ impl Workflow {
         .bind(workflow.git_watcher_enabled)
         .bind(workflow.created_at)
         .bind(workflow.updated_at)
+        .bind(workflow.budget_max_tokens)
+        .bind(workflow.budget_max_cost_usd)
+        .bind(workflow.budget_max_duration_secs)
+        .bind(workflow.activity_stall_timeout_secs)
+        .bind(workflow.activity_loop_threshold)
+        .bind(workflow.activity_escalation_interval_secs)
         .fetch_one(pool)
         .await
     }
@@ -692,7 +762,7 @@ impl Workflow {
         let result = sqlx::query(
             r"
             UPDATE workflow
-            SET status = 'running', started_at = ?, updated_at = ?
+            SET status = 'running', started_at = ?, updated_at = ?, pause_reason = NULL
             WHERE id = ? AND status IN ('ready', 'paused')
             ",
         )
@@ -710,6 +780,79 @@ impl Workflow {
         Ok(())
     }
 
+    /// Atomically transition workflow from 'running' to 'paused' and record why (CAS).
+    ///
+    /// Returns `true` if the transition succeeded, `false` if the workflow
+    /// was no longer running.
+    pub async fn set_paused(pool: &SqlitePool, id: &str, reason: &str) -> sqlx::Result<bool> {
+        let result = sqlx::query(
+            r"
+            UPDATE workflow
+            SET status = 'paused', pause_reason = ?, updated_at = ?
+            WHERE id = ? AND status = 'running'
+            ",
+        )
+        .bind(reason)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Update the orchestrator budget. `None` removes the corresponding limit.
+    pub async fn update_budget(
+        pool: &SqlitePool,
+        id: &str,
+        max_tokens: Option<i64>,
+        max_cost_usd: Option<f64>,
+        max_duration_secs: Option<i64>,
+    ) -> sqlx::Result<()> {
+        sqlx::query(
+            r"
+            UPDATE workflow
+            SET budget_max_tokens = ?, budget_max_cost_usd = ?, budget_max_duration_secs = ?,
+                updated_at = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(max_tokens)
+        .bind(max_cost_usd)
+        .bind(max_duration_secs)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Update the terminal activity monitor thresholds. `None` restores the default.
+    pub async fn update_activity_policy(
+        pool: &SqlitePool,
+        id: &str,
+        stall_timeout_secs: Option<i64>,
+        loop_threshold: Option<i64>,
+        escalation_interval_secs: Option<i64>,
+    ) -> sqlx::Result<()> {
+        sqlx::query(
+            r"
+            UPDATE workflow
+            SET activity_stall_timeout_secs = ?, activity_loop_threshold = ?,
+                activity_escalation_interval_secs = ?, updated_at = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(stall_timeout_secs)
+        .bind(loop_threshold)
+        .bind(escalation_interval_secs)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     /// Atomically transition workflow from 'completed' to 'merging' (CAS).
     ///
     /// Returns `true` if the transition succeeded (exactly one row updated),
@@ -849,8 +992,8 @@ impl Workflow {
                 r"
                 INSERT INTO workflow_task (
                     id, workflow_id, vk_task_id, name, description,
-                    branch, status, order_index, created_at, updated_at
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                    branch, status, order_index, created_at, updated_at, depends_on
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
                 ",
             )
             .bind(&task.id)
@@ -863,6 +1006,7 @@ impl Workflow {
             .bind(task.order_index)
             .bind(task.created_at)
             .bind(task.updated_at)
+            .bind(sqlx::types::Json(&task.depends_on))
             .execute(&mut *tx)
             .await?;
 
@@ -907,8 +1051,8 @@ impl WorkflowTask {
             r"
             INSERT INTO workflow_task (
                 id, workflow_id, vk_task_id, name, description,
-                branch, status, order_index, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                branch, status, order_index, created_at, updated_at, depends_on
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
             RETURNING *
             ",
         )
@@ -922,6 +1066,7 @@ impl WorkflowTask {
         .bind(task.order_index)
         .bind(task.created_at)
         .bind(task.updated_at)
+        .bind(sqlx::types::Json(&task.depends_on))
         .fetch_one(pool)
         .await
     }
@@ -1194,6 +1339,12 @@ mod encryption_tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             pause_reason: None,
+            budget_max_tokens: None,
+            budget_max_cost_usd: None,
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
         }
     }
 
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+
+/// Stored workflow template
+///
+/// Corresponds to database table: workflow_template. `content` holds the
+/// template document as JSON; `version` is bumped on every content update.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WorkflowTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Schema version of the template document
+    pub schema_version: i32,
+    /// Revision of this template, starting at 1
+    pub version: i32,
+    pub content: String,
+    /// Workflow the template was exported from (if any)
+    pub source_workflow_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WorkflowTemplate {
+    pub async fn find_all(pool: &SqlitePool) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as::<_, Self>("SELECT * FROM workflow_template ORDER BY name ASC")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: &str) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as::<_, Self>("SELECT * FROM workflow_template WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn create(pool: &SqlitePool, template: &Self) -> sqlx::Result<Self> {
+        sqlx::query_as::<_, Self>(
+            r"
+            INSERT INTO workflow_template (
+                id, name, description, schema_version, version,
+                content, source_workflow_id, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            RETURNING *
+            ",
+        )
+        .bind(&template.id)
+        .bind(&template.name)
+        .bind(&template.description)
+        .bind(template.schema_version)
+        .bind(template.version)
+        .bind(&template.content)
+        .bind(&template.source_workflow_id)
+        .bind(template.created_at)
+        .bind(template.updated_at)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Replaces the template content and bumps `version`.
+    pub async fn update_content(
+        pool: &SqlitePool,
+        id: &str,
+        name: &str,
+        description: Option<&str>,
+        schema_version: i32,
+        content: &str,
+    ) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as::<_, Self>(
+            r"
+            UPDATE workflow_template
+            SET name = ?2, description = ?3, schema_version = ?4, content = ?5,
+                version = version + 1, updated_at = ?6
+            WHERE id = ?1
+            RETURNING *
+            ",
+        )
+        .bind(id)
+        .bind(name)
+        .bind(description)
+        .bind(schema_version)
+        .bind(content)
+        .bind(Utc::now())
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Returns false if no template matched.
+    pub async fn delete(pool: &SqlitePool, id: &str) -> sqlx::Result<bool> {
+        let result = sqlx::query("DELETE FROM workflow_template WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
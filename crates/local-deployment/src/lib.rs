@@ -9,15 +9,17 @@
     clippy::too_many_lines
 )]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use db::{
     DBService,
-    models::{WorkflowOrchestratorCommand, terminal::Terminal},
+    models::{WorkflowOrchestratorCommand, WorkflowTask, terminal::Terminal},
 };
 use deployment::{Deployment, DeploymentError};
 use executors::profile::ExecutorConfigs;
+#[cfg(unix)]
+use services::services::terminal::SupervisorConfig;
 use services::services::{
     analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
     approvals::Approvals,
@@ -35,8 +37,11 @@ use services::services::{
     project::ProjectService,
     queued_message::QueuedMessageService,
     repo::RepoService,
-    runner_client::RunnerClientImpl,
-    terminal::{PromptWatcher, process::ProcessManager},
+    runner_client::{LocalRunner, RunnerClientImpl},
+    terminal::{
+        ActivityMonitor, PromptRuleStore, PromptWatcher, TerminalBridge, process::ProcessManager,
+        recording::RecordingConfig,
+    },
 };
 use tokio::sync::RwLock;
 use utils::{
@@ -51,6 +56,9 @@ mod command;
 pub mod container;
 mod copy;
 
+/// Poll interval for prompt rule pack changes
+const PROMPT_RULES_RELOAD_INTERVAL_SECS: u64 = 5;
+
 #[derive(Clone)]
 pub struct LocalDeployment {
     config: Arc<RwLock<Config>>,
@@ -183,8 +191,32 @@ impl Deployment for LocalDeployment {
         let message_bus = Arc::new(MessageBus::new(1000));
         let orchestrator_runtime =
             OrchestratorRuntime::new(Arc::new(db.clone()), message_bus.clone());
-        let process_manager = Arc::new(ProcessManager::new());
-        let prompt_watcher = PromptWatcher::new(message_bus.clone(), process_manager.clone());
+        let mut process_manager = ProcessManager::new();
+        if let Some(config) = RecordingConfig::from_env() {
+            process_manager = process_manager.with_recording(config);
+        }
+        #[cfg(unix)]
+        if let Some(supervisor) = SupervisorConfig::from_env() {
+            process_manager = process_manager.with_supervisor(supervisor);
+        }
+        // In remote mode terminals run on the configured runners
+        let remote_runner = RunnerClientImpl::remote_from_env()
+            .expect("Failed to initialize RunnerClient")
+            .map(Arc::new);
+        if let Some(runner) = &remote_runner {
+            process_manager = process_manager.with_remote_runner(Arc::clone(runner));
+        }
+        let process_manager = Arc::new(process_manager);
+        let prompt_rules = Arc::new(
+            PromptRuleStore::default_dir()
+                .map(PromptRuleStore::load)
+                .unwrap_or_else(PromptRuleStore::builtin),
+        );
+        prompt_rules
+            .clone()
+            .spawn_hot_reload(Duration::from_secs(PROMPT_RULES_RELOAD_INTERVAL_SECS));
+        let prompt_watcher = PromptWatcher::new(message_bus.clone(), process_manager.clone())
+            .with_rule_store(prompt_rules);
         orchestrator_runtime
             .set_runtime_actions(Arc::new(RuntimeActionService::new(
                 Arc::new(db.clone()),
@@ -194,6 +226,11 @@ impl Deployment for LocalDeployment {
             )))
             .await;
 
+        // Pick up terminals whose PTY supervisors outlived the previous server
+        #[cfg(unix)]
+        Self::reattach_supervised_terminals(&db, &message_bus, &process_manager, &prompt_watcher)
+            .await;
+
         // Reconcile terminal statuses on startup
         // Reset any terminals that are marked as running but have no actual process
         if let Err(e) = Self::reconcile_terminal_statuses(&db, &process_manager).await {
@@ -233,6 +270,13 @@ impl Deployment for LocalDeployment {
         let cli_health_monitor: SharedCliHealthMonitor = Arc::new(CliHealthMonitor::new(0));
         cli_health_monitor.start(Arc::new(db.clone()));
 
+        // Stall/loop watchdog for agent terminals of orchestrated workflows
+        let activity_monitor = Arc::new(ActivityMonitor::new(
+            message_bus.clone(),
+            process_manager.clone(),
+        ));
+        activity_monitor.start(Arc::new(db.clone()));
+
         let deployment = Self {
             config,
             user_id,
@@ -251,8 +295,9 @@ impl Deployment for LocalDeployment {
             auth_context,
             oauth_handoffs,
             orchestrator_runtime,
-            runner_client: RunnerClientImpl::from_env(process_manager.clone())
-                .expect("Failed to initialize RunnerClient"),
+            runner_client: remote_runner.as_deref().cloned().unwrap_or_else(|| {
+                RunnerClientImpl::Local(LocalRunner::new(process_manager.clone()))
+            }),
             process_manager,
             message_bus,
             prompt_watcher,
@@ -351,6 +396,94 @@ impl LocalDeployment {
         &self.cli_health_monitor
     }
 
+    /// Resume log persistence, stdin bridging and prompt watching for terminals
+    /// reattached from their PTY supervisors.
+    #[cfg(unix)]
+    async fn reattach_supervised_terminals(
+        db: &DBService,
+        message_bus: &SharedMessageBus,
+        process_manager: &Arc<ProcessManager>,
+        prompt_watcher: &PromptWatcher,
+    ) {
+        for handle in process_manager.reattach_supervised().await {
+            let terminal_id = handle.terminal_id.as_str();
+            let terminal = match Terminal::find_by_id(&db.pool, terminal_id).await {
+                Ok(Some(terminal)) => terminal,
+                Ok(None) => {
+                    tracing::warn!(
+                        terminal_id = %terminal_id,
+                        "Killing supervised terminal that no longer exists"
+                    );
+                    let _ = process_manager.kill_terminal(terminal_id).await;
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        terminal_id = %terminal_id,
+                        error = %e,
+                        "Failed to load reattached terminal"
+                    );
+                    continue;
+                }
+            };
+
+            if let Err(e) = process_manager
+                .attach_terminal_logger(Arc::new(db.clone()), terminal_id, "stdout", 1)
+                .await
+            {
+                tracing::warn!(
+                    terminal_id = %terminal_id,
+                    error = %e,
+                    "Failed to attach logger to reattached terminal"
+                );
+            }
+            if let Err(e) = TerminalBridge::new(message_bus.clone(), process_manager.clone())
+                .register(terminal_id, &handle.session_id)
+                .await
+            {
+                tracing::warn!(
+                    terminal_id = %terminal_id,
+                    error = %e,
+                    "Failed to register bridge for reattached terminal"
+                );
+            }
+            match WorkflowTask::find_by_id(&db.pool, &terminal.workflow_task_id).await {
+                Ok(Some(task)) => {
+                    if let Err(e) = prompt_watcher
+                        .register(
+                            terminal_id,
+                            &task.workflow_id,
+                            &terminal.workflow_task_id,
+                            &handle.session_id,
+                            &terminal.cli_type_id,
+                            terminal.auto_confirm,
+                        )
+                        .await
+                    {
+                        tracing::warn!(
+                            terminal_id = %terminal_id,
+                            error = %e,
+                            "Failed to register PromptWatcher for reattached terminal"
+                        );
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        terminal_id = %terminal_id,
+                        error = %e,
+                        "Failed to load workflow task for reattached terminal"
+                    );
+                }
+            }
+            tracing::info!(
+                terminal_id = %terminal_id,
+                pid = handle.pid,
+                "Reattached terminal after restart"
+            );
+        }
+    }
+
     /// Reconcile terminal statuses on startup
     ///
     /// Resets any terminals that are marked as running/waiting in the database
@@ -18,6 +18,11 @@ pub fn is_ts_file(path: &Path) -> bool {
     })
 }
 
+/// Check if a file path is a Python source file
+pub fn is_python_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "py")
+}
+
 /// Check if a file path should be excluded from analysis
 pub fn is_excluded(path: &Path) -> bool {
     let path_str = path.to_string_lossy();
@@ -28,6 +33,8 @@ pub fn is_excluded(path: &Path) -> bool {
         || path_str.contains("vendor/")
         || path_str.contains(".next/")
         || path_str.contains("build/")
+        || path_str.contains("__pycache__")
+        || path_str.contains("venv/")
 }
 
 /// Collect all source files from a directory recursively
@@ -0,0 +1,381 @@
+//! 质量基线
+//!
+//! 记录某一时刻已存在问题的指纹，后续运行与之比对，只有基线之外的问题
+//! 才被标记为新增（`is_new`），从而让存量仓库也能开启 `enforce` 模式。
+//!
+//! 指纹 = 规则 ID + 归一化文件路径 + 问题所在行的归一化代码哈希。
+//! 代码哈希忽略空白且不含行号，上方插入或删除代码不会改变指纹。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::gate::result::MeasureValue;
+use crate::issue::QualityIssue;
+use crate::metrics::MetricKey;
+use crate::rule::RuleType;
+
+/// 基线目录（相对于项目根）
+pub const BASELINE_DIR: &str = "quality/baselines";
+/// 基线文件格式版本
+pub const BASELINE_VERSION: u32 = 1;
+
+/// 问题指纹
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct IssueFingerprint {
+    /// 规则 ID
+    pub rule_id: String,
+    /// 相对项目根的路径（`/` 分隔），无位置信息时为空
+    pub file_path: String,
+    /// 问题所在行的归一化代码哈希；读不到源码时退化为消息哈希
+    pub code_hash: String,
+}
+
+/// 基线条目（同一指纹可出现多次）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    #[serde(flatten)]
+    pub fingerprint: IssueFingerprint,
+    pub count: usize,
+}
+
+/// 质量基线快照，对应 `quality/baselines/baseline-YYYY-MM-DD.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityBaseline {
+    /// 文件格式版本
+    pub version: u32,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+    /// 基线内问题总数
+    pub total_issues: usize,
+    /// 指纹条目（按指纹排序，便于 diff）
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl QualityBaseline {
+    /// 从当前问题列表生成基线
+    pub fn from_issues<'a>(
+        project_root: &Path,
+        issues: impl IntoIterator<Item = &'a QualityIssue>,
+    ) -> Self {
+        let mut fingerprinter = Fingerprinter::new(project_root);
+        let mut counts: HashMap<IssueFingerprint, usize> = HashMap::new();
+        for issue in issues {
+            *counts.entry(fingerprinter.fingerprint(issue)).or_default() += 1;
+        }
+
+        let mut entries: Vec<BaselineEntry> = counts
+            .into_iter()
+            .map(|(fingerprint, count)| BaselineEntry { fingerprint, count })
+            .collect();
+        entries.sort_by(|a, b| a.fingerprint.cmp(&b.fingerprint));
+
+        Self {
+            version: BASELINE_VERSION,
+            created_at: Utc::now(),
+            total_issues: entries.iter().map(|e| e.count).sum(),
+            entries,
+        }
+    }
+
+    /// 与基线比对并设置 `is_new`，返回命中基线的（历史债务）问题数
+    ///
+    /// 同一指纹在基线中出现 N 次，则最多有 N 个当前问题被视为历史债务。
+    pub fn apply<'a>(
+        &self,
+        project_root: &Path,
+        issues: impl IntoIterator<Item = &'a mut QualityIssue>,
+    ) -> usize {
+        let mut remaining: HashMap<&IssueFingerprint, usize> = self
+            .entries
+            .iter()
+            .map(|e| (&e.fingerprint, e.count))
+            .collect();
+        let mut fingerprinter = Fingerprinter::new(project_root);
+        let mut legacy = 0;
+
+        for issue in issues {
+            let fingerprint = fingerprinter.fingerprint(issue);
+            match remaining.get_mut(&fingerprint) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    issue.is_new = false;
+                    legacy += 1;
+                }
+                _ => issue.is_new = true,
+            }
+        }
+        legacy
+    }
+
+    /// 基线目录
+    pub fn dir(project_root: &Path) -> PathBuf {
+        project_root.join(BASELINE_DIR)
+    }
+
+    /// 最新的基线文件（按文件名中的日期排序）
+    pub fn latest_path(project_root: &Path) -> Option<PathBuf> {
+        std::fs::read_dir(Self::dir(project_root))
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("baseline-") && n.ends_with(".json"))
+            })
+            .max()
+    }
+
+    /// 从文件加载
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read quality baseline {}", path.display()))?;
+        let baseline: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse quality baseline {}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            anyhow::bail!(
+                "Unsupported quality baseline version {} in {}",
+                baseline.version,
+                path.display()
+            );
+        }
+        Ok(baseline)
+    }
+
+    /// 加载最新基线，不存在时返回 `None`
+    pub fn load_latest(project_root: &Path) -> anyhow::Result<Option<Self>> {
+        Self::latest_path(project_root)
+            .map(|path| Self::load(&path))
+            .transpose()
+    }
+
+    /// 保存为 `baseline-<创建日期>.json`，同一天内重复保存会覆盖
+    pub fn save(&self, project_root: &Path) -> anyhow::Result<PathBuf> {
+        let dir = Self::dir(project_root);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(format!(
+            "baseline-{}.json",
+            self.created_at.format("%Y-%m-%d")
+        ));
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, content + "\n")
+            .with_context(|| format!("Failed to write quality baseline {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// 基于 `is_new` 计算 `new_*` 度量
+pub fn new_issue_metrics(issues: &[QualityIssue]) -> HashMap<MetricKey, MeasureValue> {
+    let new_issues: Vec<&QualityIssue> = issues.iter().filter(|i| i.is_new).collect();
+    let count_type = |rule_type: RuleType| {
+        new_issues
+            .iter()
+            .filter(|i| i.rule_type == rule_type)
+            .count() as i64
+    };
+
+    HashMap::from([
+        (
+            MetricKey::NewIssues,
+            MeasureValue::Int(new_issues.len() as i64),
+        ),
+        (
+            MetricKey::NewBlockingIssues,
+            MeasureValue::Int(new_issues.iter().filter(|i| i.is_blocking()).count() as i64),
+        ),
+        (
+            MetricKey::NewBugs,
+            MeasureValue::Int(count_type(RuleType::Bug)),
+        ),
+        (
+            MetricKey::NewVulnerabilities,
+            MeasureValue::Int(count_type(RuleType::Vulnerability)),
+        ),
+        (
+            MetricKey::NewCodeSmells,
+            MeasureValue::Int(count_type(RuleType::CodeSmell)),
+        ),
+    ])
+}
+
+/// 问题指纹计算器，缓存已读取的源文件
+pub struct Fingerprinter<'a> {
+    project_root: &'a Path,
+    sources: HashMap<String, Option<Vec<String>>>,
+}
+
+impl<'a> Fingerprinter<'a> {
+    /// 创建计算器
+    pub fn new(project_root: &'a Path) -> Self {
+        Self {
+            project_root,
+            sources: HashMap::new(),
+        }
+    }
+
+    /// 计算问题指纹
+    pub fn fingerprint(&mut self, issue: &QualityIssue) -> IssueFingerprint {
+        let file_path = issue
+            .file_path
+            .as_deref()
+            .map(|path| normalize_path(self.project_root, path))
+            .unwrap_or_default();
+
+        let code_hash = issue
+            .line
+            .filter(|_| !file_path.is_empty())
+            .and_then(|line| self.normalized_line(&file_path, line))
+            .map(|code| short_hash(&code))
+            .unwrap_or_else(|| short_hash(&normalize_message(&issue.message)));
+
+        IssueFingerprint {
+            rule_id: issue.rule_id.clone(),
+            file_path,
+            code_hash,
+        }
+    }
+
+    /// 读取指定行并去掉所有空白；空行返回 `None`
+    fn normalized_line(&mut self, file_path: &str, line: u32) -> Option<String> {
+        let root = self.project_root;
+        let lines = self
+            .sources
+            .entry(file_path.to_string())
+            .or_insert_with(|| {
+                std::fs::read_to_string(root.join(file_path))
+                    .ok()
+                    .map(|content| content.lines().map(str::to_string).collect())
+            })
+            .as_ref()?;
+
+        let text = lines.get((line as usize).checked_sub(1)?)?;
+        let normalized: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        (!normalized.is_empty()).then_some(normalized)
+    }
+}
+
+/// 统一为相对项目根、`/` 分隔的路径
+fn normalize_path(project_root: &Path, path: &str) -> String {
+    let path = Path::new(path);
+    let relative = path.strip_prefix(project_root).unwrap_or(path);
+    let normalized = relative.to_string_lossy().replace('\\', "/");
+    normalized.trim_start_matches("./").to_string()
+}
+
+/// 消息中的数字（行号、计数等）替换为 `#` 并压缩空白
+fn normalize_message(message: &str) -> String {
+    let mut normalized = String::with_capacity(message.len());
+    let mut last = ' ';
+    for c in message.trim().chars() {
+        let c = if c.is_ascii_digit() {
+            '#'
+        } else if c.is_whitespace() {
+            ' '
+        } else {
+            c
+        };
+        if !((c == '#' || c == ' ') && c == last) {
+            normalized.push(c);
+        }
+        last = c;
+    }
+    normalized
+}
+
+fn short_hash(text: &str) -> String {
+    let digest = Sha256::digest(text.as_bytes());
+    digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::{AnalyzerSource, Severity};
+
+    fn issue(rule_id: &str, file: &str, line: u32) -> QualityIssue {
+        QualityIssue::new(
+            rule_id,
+            RuleType::Bug,
+            Severity::Critical,
+            AnalyzerSource::Clippy,
+            format!("problem at line {}", line),
+        )
+        .with_location(file, line)
+    }
+
+    #[test]
+    fn test_baseline_survives_line_shifts() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("src")).unwrap();
+        std::fs::write(
+            root.path().join("src/lib.rs"),
+            "fn a() {\n    x.unwrap();\n}\n",
+        )
+        .unwrap();
+
+        let baseline =
+            QualityBaseline::from_issues(root.path(), &[issue("unwrap", "src/lib.rs", 2)]);
+        assert_eq!(baseline.total_issues, 1);
+
+        // 上方插入代码并重新缩进：旧问题下移，同时新增一个问题
+        std::fs::write(
+            root.path().join("src/lib.rs"),
+            "use foo;\n\nfn a() {\n        x.unwrap();\n    y.unwrap();\n}\n",
+        )
+        .unwrap();
+        let mut issues = vec![
+            issue("unwrap", "./src/lib.rs", 4),
+            issue("unwrap", "src/lib.rs", 5),
+        ];
+        let legacy = baseline.apply(root.path(), issues.iter_mut());
+
+        assert_eq!(legacy, 1);
+        assert!(!issues[0].is_new);
+        assert!(issues[1].is_new);
+
+        let metrics = new_issue_metrics(&issues);
+        assert_eq!(metrics[&MetricKey::NewIssues], MeasureValue::Int(1));
+        assert_eq!(metrics[&MetricKey::NewBugs], MeasureValue::Int(1));
+        assert_eq!(metrics[&MetricKey::NewCodeSmells], MeasureValue::Int(0));
+    }
+
+    #[test]
+    fn test_baseline_counts_duplicates_and_round_trips() {
+        let root = tempfile::tempdir().unwrap();
+        let unlocated = |message: &str| {
+            QualityIssue::new(
+                "deps",
+                RuleType::Vulnerability,
+                Severity::Major,
+                AnalyzerSource::SecurityAudit,
+                message,
+            )
+        };
+
+        let baseline = QualityBaseline::from_issues(
+            root.path(),
+            &[
+                unlocated("2 vulnerable crates"),
+                unlocated("2 vulnerable crates"),
+            ],
+        );
+        assert_eq!(baseline.entries.len(), 1);
+        let path = baseline.save(root.path()).unwrap();
+        assert_eq!(QualityBaseline::latest_path(root.path()), Some(path));
+
+        let loaded = QualityBaseline::load_latest(root.path()).unwrap().unwrap();
+        let mut issues = [
+            unlocated("7 vulnerable crates"),
+            unlocated("7 vulnerable crates"),
+            unlocated("7 vulnerable crates"),
+        ];
+        assert_eq!(loaded.apply(root.path(), issues.iter_mut()), 2);
+        assert!(issues[2].is_new);
+    }
+}
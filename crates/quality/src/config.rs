@@ -47,6 +47,23 @@ pub enum QualityGateMode {
     Enforce,
 }
 
+impl std::str::FromStr for QualityGateMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "shadow" => Ok(Self::Shadow),
+            "warn" => Ok(Self::Warn),
+            "enforce" => Ok(Self::Enforce),
+            other => anyhow::bail!(
+                "Unknown quality gate mode '{}' (expected off, shadow, warn or enforce)",
+                other
+            ),
+        }
+    }
+}
+
 /// 单个质量门定义
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GateDefinition {
@@ -94,6 +111,9 @@ pub struct ProvidersConfig {
     /// Built-in TypeScript/JavaScript static analysis rules
     #[serde(default = "default_true")]
     pub builtin_frontend: bool,
+    /// Built-in Python static analysis rules
+    #[serde(default = "default_true")]
+    pub builtin_python: bool,
     /// Built-in language-agnostic rules (duplication, secrets, etc.)
     #[serde(default = "default_true")]
     pub builtin_common: bool,
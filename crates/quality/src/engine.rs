@@ -4,17 +4,18 @@
 //! 这是质量门的顶层入口
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{info, warn};
 
+use crate::baseline::{self, QualityBaseline};
 use crate::config::{QualityGateConfig, QualityGateMode};
 use crate::gate::evaluator::ConditionEvaluator;
 use crate::gate::result::MeasureValue;
 use crate::gate::QualityGateLevel;
 use crate::issue::QualityIssue;
 use crate::metrics::MetricKey;
-use crate::provider::QualityProvider;
+use crate::provider::{ProviderReport, QualityProvider};
 use crate::report::QualityReport;
 use crate::rule::AnalyzerSource;
 use crate::sarif;
@@ -41,8 +42,11 @@ impl QualityEngine {
     /// 从项目目录自动创建引擎
     pub fn from_project(project_root: &Path) -> anyhow::Result<Self> {
         let config = QualityGateConfig::load_from_project(project_root)?;
+        Ok(Self::from_config(config))
+    }
 
-        // 根据配置创建启用的 providers
+    /// 根据配置创建启用的 providers
+    pub fn from_config(config: QualityGateConfig) -> Self {
         let mut providers: Vec<Arc<dyn QualityProvider>> = Vec::new();
 
         if config.providers.rust {
@@ -83,6 +87,11 @@ impl QualityEngine {
                 crate::provider::builtin_frontend::BuiltinFrontendProvider::default(),
             ));
         }
+        if config.providers.builtin_python {
+            providers.push(Arc::new(
+                crate::provider::builtin_python::BuiltinPythonProvider,
+            ));
+        }
         if config.providers.builtin_common {
             providers.push(Arc::new(
                 crate::provider::builtin_common::BuiltinCommonProvider,
@@ -94,7 +103,7 @@ impl QualityEngine {
             ));
         }
 
-        Ok(Self::new(config, providers))
+        Self::new(config, providers)
     }
 
     /// 执行质量门分析
@@ -117,6 +126,108 @@ impl QualityEngine {
 
         info!("Starting quality gate analysis: {} (mode={:?})", level, self.config.mode);
 
+        let mut reports = self.collect_reports(project_root, changed_files).await;
+
+        // 与最新基线比对：命中基线的问题视为历史债务，其余为新增
+        let baseline_applied = match QualityBaseline::load_latest(project_root) {
+            Ok(Some(baseline)) => {
+                let legacy = baseline.apply(
+                    project_root,
+                    reports.iter_mut().flat_map(|r| r.issues.iter_mut()),
+                );
+                info!("Quality baseline matched {} existing issues", legacy);
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                warn!(
+                    "Ignoring quality baseline: {:#} — all issues are treated as new",
+                    e
+                );
+                false
+            }
+        };
+
+        // 聚合报告
+        let mut quality_report = QualityReport::aggregate(reports);
+
+        // 获取质量门定义并求值
+        let gate = self.config.get_gate(level)?;
+
+        // 收集所有度量值
+        let mut all_metrics: HashMap<MetricKey, MeasureValue> = HashMap::new();
+        for provider_report in &quality_report.provider_reports {
+            all_metrics.extend(provider_report.metrics.clone());
+        }
+        // 有基线时以基线比对结果为准，覆盖 provider 自报的 new_* 度量
+        for (key, value) in baseline::new_issue_metrics(&quality_report.all_issues) {
+            if baseline_applied {
+                all_metrics.insert(key, value);
+            } else {
+                all_metrics.entry(key).or_insert(value);
+            }
+        }
+        all_metrics.insert(
+            MetricKey::SuppressedIssues,
+            MeasureValue::Int(quality_report.suppressed_issues.len() as i64),
+        );
+
+        // 条件求值
+        let eval_results = ConditionEvaluator::evaluate_all(&gate.conditions, &all_metrics);
+
+        // 生成质量门决策
+        let decision = gate.evaluate(&eval_results);
+
+        info!("{}", quality_report.status_line());
+
+        quality_report = quality_report.with_decision(decision);
+
+        Ok(quality_report)
+    }
+
+    /// 全量分析并把当前所有问题写为基线
+    ///
+    /// 已存在基线时需 `refresh = true`，否则报错，避免误把新问题吞进基线。
+    pub async fn create_baseline(
+        &self,
+        project_root: &Path,
+        refresh: bool,
+    ) -> anyhow::Result<(PathBuf, QualityBaseline)> {
+        if !refresh && let Some(existing) = QualityBaseline::latest_path(project_root) {
+            anyhow::bail!(
+                "A quality baseline already exists at {}; refresh it instead",
+                existing.display()
+            );
+        }
+
+        let reports = self.collect_reports(project_root, None).await;
+        for report in reports.iter().filter(|r| !r.success) {
+            warn!(
+                "Provider '{}' failed while creating the baseline, its issues are not covered: {}",
+                report.provider_name,
+                report.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+
+        let baseline = QualityBaseline::from_issues(
+            project_root,
+            reports.iter().flat_map(|r| r.issues.iter()),
+        );
+        let path = baseline.save(project_root)?;
+        info!(
+            "Wrote quality baseline with {} issues to {}",
+            baseline.total_issues,
+            path.display()
+        );
+        Ok((path, baseline))
+    }
+
+    /// 并发运行启用的 providers 并导入 SARIF 结果
+    async fn collect_reports(
+        &self,
+        project_root: &Path,
+        changed_files: Option<&[String]>,
+    ) -> Vec<ProviderReport> {
         // 并发运行所有启用的 providers
         let mut handles = Vec::new();
         for provider in &self.providers {
@@ -144,7 +255,7 @@ impl QualityEngine {
                 Ok(Err(e)) => {
                     warn!("Provider analysis failed: {} — metrics from this provider will be missing, which may cause quality gate conditions to WARN", e);
                     // Include a failed provider report so the evaluator sees the gap
-                    reports.push(crate::provider::ProviderReport::failure(
+                    reports.push(ProviderReport::failure(
                         "unknown-provider",
                         0,
                         format!("Provider failed: {}", e),
@@ -152,7 +263,7 @@ impl QualityEngine {
                 }
                 Err(e) => {
                     warn!("Provider task panicked: {} — metrics from this provider will be missing, which may cause quality gate conditions to WARN", e);
-                    reports.push(crate::provider::ProviderReport::failure(
+                    reports.push(ProviderReport::failure(
                         "unknown-provider",
                         0,
                         format!("Provider panicked: {}", e),
@@ -165,34 +276,12 @@ impl QualityEngine {
         let sarif_issues = Self::collect_sarif_issues(project_root).await;
         if !sarif_issues.is_empty() {
             info!("Imported {} issues from SARIF files", sarif_issues.len());
-            let sarif_report = crate::provider::ProviderReport::success("sarif-import", 0)
+            let sarif_report = ProviderReport::success("sarif-import", 0)
                 .with_issues(sarif_issues);
             reports.push(sarif_report);
         }
 
-        // 聚合报告
-        let mut quality_report = QualityReport::aggregate(reports);
-
-        // 获取质量门定义并求值
-        let gate = self.config.get_gate(level)?;
-
-        // 收集所有度量值
-        let mut all_metrics: HashMap<MetricKey, MeasureValue> = HashMap::new();
-        for provider_report in &quality_report.provider_reports {
-            all_metrics.extend(provider_report.metrics.clone());
-        }
-
-        // 条件求值
-        let eval_results = ConditionEvaluator::evaluate_all(&gate.conditions, &all_metrics);
-
-        // 生成质量门决策
-        let decision = gate.evaluate(&eval_results);
-
-        info!("{}", quality_report.status_line());
-
-        quality_report = quality_report.with_decision(decision);
-
-        Ok(quality_report)
+        reports
     }
 
     /// 获取当前配置
@@ -129,6 +129,22 @@ impl std::fmt::Display for QualityGateLevel {
     }
 }
 
+impl std::str::FromStr for QualityGateLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "terminal" => Ok(Self::Terminal),
+            "branch" => Ok(Self::Branch),
+            "repo" => Ok(Self::Repo),
+            other => anyhow::bail!(
+                "Unknown quality gate level '{}' (expected terminal, branch or repo)",
+                other
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
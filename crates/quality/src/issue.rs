@@ -160,6 +160,9 @@ pub struct IssueSummary {
     pub info: usize,
     pub new_issues: usize,
     pub blocking_issues: usize,
+    /// 被行内注释抑制的问题数（不计入 total）
+    #[serde(default)]
+    pub suppressed: usize,
 }
 
 impl IssueSummary {
@@ -186,10 +189,14 @@ impl IssueSummary {
 
     /// 生成一行摘要文本
     pub fn one_line_summary(&self) -> String {
-        format!(
+        let mut line = format!(
             "{} issues ({} blocker, {} critical, {} major, {} minor, {} info) | {} new | {} blocking",
             self.total, self.blocker, self.critical, self.major, self.minor, self.info,
             self.new_issues, self.blocking_issues
-        )
+        );
+        if self.suppressed > 0 {
+            line.push_str(&format!(" | {} suppressed", self.suppressed));
+        }
+        line
     }
 }
@@ -19,10 +19,12 @@
 //! - `rule` — 规则类型与严重级别
 //! - `sarif` — SARIF 2.1.0 报告解析
 //! - `report` — 报告聚合器
+//! - `baseline` — 质量基线：问题指纹快照，区分新增问题与历史债务
 //! - `metrics` — 度量指标定义
 //! - `config` — 配置加载（quality-gate.yaml）
 
 pub mod analysis;
+pub mod baseline;
 pub mod config;
 pub mod engine;
 pub mod gate;
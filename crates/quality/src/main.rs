@@ -0,0 +1,123 @@
+//! 质量门命令行入口
+//!
+//! - `quality --tier <terminal|branch|repo> [--mode ..]` — 运行质量门，enforce 模式下未通过则退出码为 1
+//! - `quality baseline create|refresh` — 生成/刷新 `quality/baselines/` 下的基线
+//! - `--sarif <path>` — 额外导出 SARIF 2.1.0 报告
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use quality::config::{QualityGateConfig, QualityGateMode};
+use quality::engine::QualityEngine;
+use quality::gate::QualityGateLevel;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser)]
+#[command(name = "quality", about = "SoloDawn quality gate")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+    /// 项目根目录
+    #[arg(long, global = true, default_value = ".")]
+    project_root: PathBuf,
+    /// 实际分析的目录（如终端 worktree），默认为项目根
+    #[arg(long, global = true)]
+    working_dir: Option<PathBuf>,
+    /// 质量门配置文件，默认在项目根下查找
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// 质量门层级
+    #[arg(long, default_value = "repo")]
+    tier: QualityGateLevel,
+    /// 覆盖配置中的运行模式
+    #[arg(long)]
+    mode: Option<QualityGateMode>,
+    /// 逗号分隔的变更文件列表
+    #[arg(long, value_delimiter = ',')]
+    changed_files: Vec<String>,
+    /// 同时把报告导出为 SARIF 2.1.0 文件
+    #[arg(long)]
+    sarif: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// 管理质量基线
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum BaselineAction {
+    /// 生成首个基线（已存在时失败）
+    Create,
+    /// 用当前全部问题重新生成基线
+    Refresh,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .with_writer(std::io::stderr)
+        .init();
+
+    match run(Cli::parse()).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
+    let mut config = match &cli.config {
+        Some(path) => QualityGateConfig::load_from_file(path)?,
+        None => QualityGateConfig::load_from_project(&cli.project_root)?,
+    };
+    if let Some(mode) = cli.mode {
+        config.mode = mode;
+    }
+    let root = cli.working_dir.unwrap_or(cli.project_root);
+    let engine = QualityEngine::from_config(config);
+
+    if let Some(Commands::Baseline { action }) = cli.command {
+        let refresh = matches!(action, BaselineAction::Refresh);
+        let (path, baseline) = engine.create_baseline(&root, refresh).await?;
+        println!(
+            "Quality baseline: {} issues ({} fingerprints) -> {}",
+            baseline.total_issues,
+            baseline.entries.len(),
+            path.display()
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let changed_files: Vec<String> = cli
+        .changed_files
+        .into_iter()
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty())
+        .collect();
+    let changed = (!changed_files.is_empty()).then_some(changed_files.as_slice());
+
+    let report = engine.run(&root, cli.tier, changed).await?;
+    if let Some(path) = &cli.sarif {
+        let sarif = serde_json::to_string_pretty(&quality::sarif::report_to_sarif(&report))?;
+        std::fs::write(path, sarif)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    }
+    println!("{}", report.status_line());
+    if !report.is_passed() {
+        print!("{}", report.to_fix_instructions());
+    }
+
+    if engine.config().is_enforcing() && !report.is_passed() {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
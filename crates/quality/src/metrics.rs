@@ -54,6 +54,18 @@ pub enum MetricKey {
     /// 新增 Bug 数（变化量）
     #[serde(rename = "new_bugs")]
     NewBugs,
+    /// 新增漏洞数（相对基线）
+    #[serde(rename = "new_vulnerabilities")]
+    NewVulnerabilities,
+    /// 新增代码异味数（相对基线）
+    #[serde(rename = "new_code_smells")]
+    NewCodeSmells,
+    /// 新增问题总数（相对基线）
+    #[serde(rename = "new_issues")]
+    NewIssues,
+    /// 新增阻断级别问题数（相对基线）
+    #[serde(rename = "new_blocking_issues")]
+    NewBlockingIssues,
     /// 代码异味数
     #[serde(rename = "code_smells")]
     CodeSmells,
@@ -66,6 +78,9 @@ pub enum MetricKey {
     /// 安全审计问题数
     #[serde(rename = "security_issues")]
     SecurityIssues,
+    /// 被行内注释抑制的问题数
+    #[serde(rename = "suppressed_issues")]
+    SuppressedIssues,
 
     // ── Repo/Infra 指标 ──
     /// 类型生成检查失败
@@ -111,6 +126,20 @@ pub enum MetricKey {
     #[serde(rename = "builtin_frontend_critical")]
     BuiltinFrontendCritical,
 
+    // ── Built-in Python 分析指标 ──
+    /// 内置 Python 规则发现的问题总数
+    #[serde(rename = "builtin_python_issues")]
+    BuiltinPythonIssues,
+    /// 内置 Python 规则发现的 Critical+ 问题数
+    #[serde(rename = "builtin_python_critical")]
+    BuiltinPythonCritical,
+    /// Python 函数最高圈复杂度
+    #[serde(rename = "python_cyclomatic_complexity")]
+    PythonCyclomaticComplexity,
+    /// Python 函数最高认知复杂度
+    #[serde(rename = "python_cognitive_complexity")]
+    PythonCognitiveComplexity,
+
     // ── Built-in Common 分析指标 ──
     /// 内置通用规则发现的问题总数
     #[serde(rename = "builtin_common_issues")]
@@ -148,10 +177,15 @@ impl MetricKey {
             Self::TestCoverage => "test_coverage",
             Self::Bugs => "bugs",
             Self::NewBugs => "new_bugs",
+            Self::NewVulnerabilities => "new_vulnerabilities",
+            Self::NewCodeSmells => "new_code_smells",
+            Self::NewIssues => "new_issues",
+            Self::NewBlockingIssues => "new_blocking_issues",
             Self::CodeSmells => "code_smells",
             Self::Vulnerabilities => "vulnerabilities",
             Self::DuplicatedLinesDensity => "duplicated_lines_density",
             Self::SecurityIssues => "security_issues",
+            Self::SuppressedIssues => "suppressed_issues",
             Self::GenerateTypesCheckFailures => "generate_types_check_failures",
             Self::PrepareDbCheckFailures => "prepare_db_check_failures",
             Self::SonarQualityGateStatus => "sonar_quality_gate_status",
@@ -164,6 +198,10 @@ impl MetricKey {
             Self::RustCognitiveComplexity => "rust_cognitive_complexity",
             Self::BuiltinFrontendIssues => "builtin_frontend_issues",
             Self::BuiltinFrontendCritical => "builtin_frontend_critical",
+            Self::BuiltinPythonIssues => "builtin_python_issues",
+            Self::BuiltinPythonCritical => "builtin_python_critical",
+            Self::PythonCyclomaticComplexity => "python_cyclomatic_complexity",
+            Self::PythonCognitiveComplexity => "python_cognitive_complexity",
             Self::BuiltinCommonIssues => "builtin_common_issues",
             Self::DuplicatedBlocks => "duplicated_blocks",
             Self::SecretsDetected => "secrets_detected",
@@ -188,10 +226,15 @@ impl MetricKey {
             Self::TestCoverage => "Test Coverage",
             Self::Bugs => "Bugs",
             Self::NewBugs => "New Bugs",
+            Self::NewVulnerabilities => "New Vulnerabilities",
+            Self::NewCodeSmells => "New Code Smells",
+            Self::NewIssues => "New Issues",
+            Self::NewBlockingIssues => "New Blocking Issues",
             Self::CodeSmells => "Code Smells",
             Self::Vulnerabilities => "Vulnerabilities",
             Self::DuplicatedLinesDensity => "Duplicated Lines (%)",
             Self::SecurityIssues => "Security Issues",
+            Self::SuppressedIssues => "Suppressed Issues",
             Self::GenerateTypesCheckFailures => "Type Generation Failures",
             Self::PrepareDbCheckFailures => "DB Preparation Failures",
             Self::SonarQualityGateStatus => "Sonar Quality Gate",
@@ -204,6 +247,10 @@ impl MetricKey {
             Self::RustCognitiveComplexity => "Rust Cognitive Complexity",
             Self::BuiltinFrontendIssues => "Built-in Frontend Issues",
             Self::BuiltinFrontendCritical => "Built-in Frontend Critical",
+            Self::BuiltinPythonIssues => "Built-in Python Issues",
+            Self::BuiltinPythonCritical => "Built-in Python Critical",
+            Self::PythonCyclomaticComplexity => "Python Cyclomatic Complexity",
+            Self::PythonCognitiveComplexity => "Python Cognitive Complexity",
             Self::BuiltinCommonIssues => "Built-in Common Issues",
             Self::DuplicatedBlocks => "Duplicated Blocks",
             Self::SecretsDetected => "Secrets Detected",
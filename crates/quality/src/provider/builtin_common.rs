@@ -12,18 +12,19 @@ use crate::gate::result::MeasureValue;
 use crate::metrics::MetricKey;
 use crate::provider::{ProviderReport, QualityProvider};
 use crate::rules::common::all_common_rules;
+use crate::rules::suppression::Suppressions;
 use crate::rules::{CommonAnalysisContext, RuleConfig};
 
 /// Built-in common (language-agnostic) quality provider
 ///
 /// Runs all common rules from `crate::rules::common` against every
-/// Rust and TypeScript/JavaScript source file in the project.
+/// Rust, TypeScript/JavaScript and Python source file in the project.
 #[derive(Default)]
 pub struct BuiltinCommonProvider;
 
-/// Combined filter: accepts Rust and TS/JS source files.
-fn is_rust_or_ts_file(p: &Path) -> bool {
-    analysis::is_rust_file(p) || analysis::is_ts_file(p)
+/// Combined filter: accepts Rust, TS/JS and Python source files.
+fn is_source_file(p: &Path) -> bool {
+    analysis::is_rust_file(p) || analysis::is_ts_file(p) || analysis::is_python_file(p)
 }
 
 #[async_trait]
@@ -48,12 +49,13 @@ impl QualityProvider for BuiltinCommonProvider {
         let start = Instant::now();
         debug!("builtin-common: starting analysis");
 
-        let files = analysis::collect_files(project_root, is_rust_or_ts_file);
+        let files = analysis::collect_files(project_root, is_source_file);
         debug!("builtin-common: collected {} source files", files.len());
 
         let rules = all_common_rules();
         let config = RuleConfig::default();
         let mut all_issues = Vec::new();
+        let mut all_suppressed = Vec::new();
 
         for file_path in &files {
             let bytes = match std::fs::read(file_path) {
@@ -89,13 +91,22 @@ impl QualityProvider for BuiltinCommonProvider {
                 config: &config,
             };
 
+            let mut file_issues = Vec::new();
             for rule in &rules {
                 if !rule.default_config().enabled {
                     continue;
                 }
-                let issues = rule.analyze(&ctx);
-                all_issues.extend(issues);
+                file_issues.extend(rule.analyze(&ctx));
             }
+
+            // Binary files cannot carry suppression comments
+            let suppressions = text_owned
+                .as_deref()
+                .map(Suppressions::parse)
+                .unwrap_or_default();
+            let (file_issues, suppressed) = suppressions.apply(file_issues);
+            all_issues.extend(file_issues);
+            all_suppressed.extend(suppressed);
         }
 
         let total_issues = all_issues.len() as i64;
@@ -119,7 +130,8 @@ impl QualityProvider for BuiltinCommonProvider {
             .with_metric(MetricKey::BuiltinCommonIssues, MeasureValue::Int(total_issues))
             .with_metric(MetricKey::DuplicatedBlocks, MeasureValue::Int(duplicated_blocks))
             .with_metric(MetricKey::SecretsDetected, MeasureValue::Int(secrets_detected))
-            .with_issues(all_issues);
+            .with_issues(all_issues)
+            .with_suppressed(all_suppressed);
 
         Ok(report)
     }
@@ -1,8 +1,10 @@
 //! Built-in Frontend Provider
 //!
 //! Runs all built-in TypeScript/JavaScript quality rules without external tools.
+//! Parses each file with `oxc_parser` and applies every rule from `crate::rules::typescript`.
 
 use async_trait::async_trait;
+use oxc_allocator::Allocator;
 use std::path::Path;
 use std::time::Instant;
 use tracing::{debug, warn};
@@ -12,8 +14,9 @@ use crate::gate::result::MeasureValue;
 use crate::metrics::MetricKey;
 use crate::provider::{ProviderReport, QualityProvider};
 use crate::rule::Severity;
-use crate::rules::typescript::all_ts_rules;
-use crate::rules::{RuleConfig, TsAnalysisContext};
+use crate::rules::suppression::Suppressions;
+use crate::rules::typescript::{all_ts_rules, parse_typescript};
+use crate::rules::{RuleConfig, TsAnalysisContext, line_starts};
 
 /// Built-in frontend quality provider.
 ///
@@ -63,8 +66,13 @@ impl QualityProvider for BuiltinFrontendProvider {
         let rules = all_ts_rules();
         let config = RuleConfig::default();
         let mut all_issues = Vec::new();
+        let mut all_suppressed = Vec::new();
+        let mut allocator = Allocator::default();
 
         for file_path in &files {
+            // The previous file's AST is dropped by now; reuse its arena.
+            allocator.reset();
+
             let content = match std::fs::read_to_string(file_path) {
                 Ok(c) => c,
                 Err(e) => {
@@ -78,22 +86,37 @@ impl QualityProvider for BuiltinFrontendProvider {
                 .unwrap_or(file_path)
                 .to_string_lossy();
 
+            let program = match parse_typescript(&allocator, &content, &relative) {
+                Ok(p) => p,
+                Err(e) => {
+                    warn!("builtin-frontend: failed to parse {}: {}", relative, e);
+                    continue;
+                }
+            };
+
             let lines: Vec<&str> = content.lines().collect();
+            let line_starts = line_starts(&content);
 
             let ctx = TsAnalysisContext {
                 file_path: &relative,
                 content: &content,
                 lines: &lines,
+                line_starts: &line_starts,
+                program: &program,
                 config: &config,
             };
 
+            let mut file_issues = Vec::new();
             for rule in &rules {
                 if !rule.default_config().enabled {
                     continue;
                 }
-                let issues = rule.analyze(&ctx);
-                all_issues.extend(issues);
+                file_issues.extend(rule.analyze(&ctx));
             }
+
+            let (file_issues, suppressed) = Suppressions::parse(&content).apply(file_issues);
+            all_issues.extend(file_issues);
+            all_suppressed.extend(suppressed);
         }
 
         let total_issues = all_issues.len() as i64;
@@ -114,7 +137,8 @@ impl QualityProvider for BuiltinFrontendProvider {
         let report = ProviderReport::success("builtin-frontend", duration_ms)
             .with_metric(MetricKey::BuiltinFrontendIssues, MeasureValue::Int(total_issues))
             .with_metric(MetricKey::BuiltinFrontendCritical, MeasureValue::Int(critical_count))
-            .with_issues(all_issues);
+            .with_issues(all_issues)
+            .with_suppressed(all_suppressed);
 
         Ok(report)
     }
@@ -0,0 +1,157 @@
+//! Built-in Python quality provider
+//!
+//! Runs all built-in Python quality rules without external tools.
+//! Parses each `.py` file with `rustpython-parser` and applies every rule from `crate::rules::python`.
+
+use std::path::Path;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::analysis;
+use crate::gate::result::MeasureValue;
+use crate::metrics::MetricKey;
+use crate::provider::{ProviderReport, QualityProvider};
+use crate::rule::Severity;
+use crate::rules::python::{all_python_rules, parse_python};
+use crate::rules::suppression::Suppressions;
+use crate::rules::{PythonAnalysisContext, RuleConfig};
+
+/// Built-in Python quality provider
+///
+/// Analyses all `.py` files using the built-in rule set (complexity, function length,
+/// bare `except`, mutable defaults, etc.) without requiring ruff, pylint or a Python
+/// interpreter.
+#[derive(Default)]
+pub struct BuiltinPythonProvider;
+
+#[async_trait]
+impl QualityProvider for BuiltinPythonProvider {
+    fn name(&self) -> &str {
+        "builtin-python"
+    }
+
+    fn supported_metrics(&self) -> Vec<MetricKey> {
+        vec![
+            MetricKey::BuiltinPythonIssues,
+            MetricKey::BuiltinPythonCritical,
+            MetricKey::PythonCyclomaticComplexity,
+            MetricKey::PythonCognitiveComplexity,
+        ]
+    }
+
+    async fn analyze(
+        &self,
+        project_root: &Path,
+        _changed_files: Option<&[String]>,
+    ) -> anyhow::Result<ProviderReport> {
+        let start = Instant::now();
+
+        let python_files = analysis::collect_files(project_root, analysis::is_python_file);
+        debug!(
+            "builtin-python: found {} Python files to analyze",
+            python_files.len()
+        );
+
+        let rules = all_python_rules();
+        let config = RuleConfig::default();
+        let mut all_issues = Vec::new();
+        let mut all_suppressed = Vec::new();
+        let mut max_cyclomatic: i64 = 0;
+        let mut max_cognitive: i64 = 0;
+
+        for file_path in &python_files {
+            let relative = file_path
+                .strip_prefix(project_root)
+                .unwrap_or(file_path)
+                .to_string_lossy();
+
+            let content = match std::fs::read_to_string(file_path) {
+                Ok(c) => c,
+                Err(e) => {
+                    warn!("builtin-python: failed to read {}: {}", relative, e);
+                    continue;
+                }
+            };
+
+            let suite = match parse_python(&content, &relative) {
+                Ok(s) => s,
+                Err(e) => {
+                    warn!("builtin-python: failed to parse {}: {}", relative, e);
+                    continue;
+                }
+            };
+
+            let ctx = PythonAnalysisContext {
+                file_path: &relative,
+                content: &content,
+                suite: &suite,
+                config: &config,
+            };
+
+            let mut file_issues = Vec::new();
+            for rule in &rules {
+                if !rule.default_config().enabled {
+                    continue;
+                }
+                file_issues.extend(rule.analyze(&ctx));
+            }
+
+            let (file_issues, suppressed) = Suppressions::parse(&content).apply(file_issues);
+            all_suppressed.extend(suppressed);
+
+            for issue in &file_issues {
+                let complexity = complexity_from_message(&issue.message);
+                if issue.rule_id == "python:cyclomatic-complexity" {
+                    max_cyclomatic = max_cyclomatic.max(complexity.unwrap_or(1));
+                }
+                if issue.rule_id == "python:cognitive-complexity" {
+                    max_cognitive = max_cognitive.max(complexity.unwrap_or(1));
+                }
+            }
+            all_issues.extend(file_issues);
+        }
+
+        let total_issues = all_issues.len() as i64;
+        let critical_count = all_issues
+            .iter()
+            .filter(|i| matches!(i.severity, Severity::Critical | Severity::Blocker))
+            .count() as i64;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        debug!(
+            "builtin-python: finished in {}ms — {} issues ({} critical), max cyclomatic={}, max cognitive={}",
+            duration_ms, total_issues, critical_count, max_cyclomatic, max_cognitive
+        );
+
+        let report = ProviderReport::success("builtin-python", duration_ms)
+            .with_metric(
+                MetricKey::BuiltinPythonIssues,
+                MeasureValue::Int(total_issues),
+            )
+            .with_metric(
+                MetricKey::BuiltinPythonCritical,
+                MeasureValue::Int(critical_count),
+            )
+            .with_metric(
+                MetricKey::PythonCyclomaticComplexity,
+                MeasureValue::Int(max_cyclomatic),
+            )
+            .with_metric(
+                MetricKey::PythonCognitiveComplexity,
+                MeasureValue::Int(max_cognitive),
+            )
+            .with_issues(all_issues)
+            .with_suppressed(all_suppressed);
+
+        Ok(report)
+    }
+}
+
+/// Pull the score out of "Function `f` has a ... complexity of 17 (threshold: 15)".
+fn complexity_from_message(message: &str) -> Option<i64> {
+    let (_, rest) = message.split_once("complexity of ")?;
+    rest.split_whitespace().next()?.parse().ok()
+}
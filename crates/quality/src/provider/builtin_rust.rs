@@ -15,6 +15,7 @@ use crate::metrics::MetricKey;
 use crate::provider::{ProviderReport, QualityProvider};
 use crate::rule::Severity;
 use crate::rules::rust::all_rust_rules;
+use crate::rules::suppression::Suppressions;
 use crate::rules::{RuleConfig, RustAnalysisContext};
 
 /// Built-in Rust quality provider
@@ -56,6 +57,7 @@ impl QualityProvider for BuiltinRustProvider {
         let rules = all_rust_rules();
         let config = RuleConfig::default();
         let mut all_issues = Vec::new();
+        let mut all_suppressed = Vec::new();
         let mut max_cyclomatic: i64 = 0;
         let mut max_cognitive: i64 = 0;
 
@@ -88,29 +90,35 @@ impl QualityProvider for BuiltinRustProvider {
                 config: &config,
             };
 
+            let mut file_issues = Vec::new();
             for rule in &rules {
                 if !rule.default_config().enabled {
                     continue;
                 }
-                let issues = rule.analyze(&ctx);
-                for issue in &issues {
-                    if issue.rule_id.contains("cyclomatic") {
-                        // Extract complexity value from the issue message if present,
-                        // otherwise count each issue as complexity 1.
-                        let complexity = extract_number_from_message(&issue.message).unwrap_or(1);
-                        if complexity > max_cyclomatic {
-                            max_cyclomatic = complexity;
-                        }
+                file_issues.extend(rule.analyze(&ctx));
+            }
+
+            // Suppressed findings count neither as issues nor towards the complexity maxima
+            let (file_issues, suppressed) = Suppressions::parse(&content).apply(file_issues);
+            all_suppressed.extend(suppressed);
+
+            for issue in &file_issues {
+                if issue.rule_id.contains("cyclomatic") {
+                    // Extract complexity value from the issue message if present,
+                    // otherwise count each issue as complexity 1.
+                    let complexity = extract_number_from_message(&issue.message).unwrap_or(1);
+                    if complexity > max_cyclomatic {
+                        max_cyclomatic = complexity;
                     }
-                    if issue.rule_id.contains("cognitive") {
-                        let complexity = extract_number_from_message(&issue.message).unwrap_or(1);
-                        if complexity > max_cognitive {
-                            max_cognitive = complexity;
-                        }
+                }
+                if issue.rule_id.contains("cognitive") {
+                    let complexity = extract_number_from_message(&issue.message).unwrap_or(1);
+                    if complexity > max_cognitive {
+                        max_cognitive = complexity;
                     }
                 }
-                all_issues.extend(issues);
             }
+            all_issues.extend(file_issues);
         }
 
         let total_issues = all_issues.len() as i64;
@@ -140,7 +148,8 @@ impl QualityProvider for BuiltinRustProvider {
                 MetricKey::RustCognitiveComplexity,
                 MeasureValue::Int(max_cognitive),
             )
-            .with_issues(all_issues);
+            .with_issues(all_issues)
+            .with_suppressed(all_suppressed);
 
         Ok(report)
     }
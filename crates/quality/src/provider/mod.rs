@@ -4,6 +4,7 @@
 
 pub mod builtin_common;
 pub mod builtin_frontend;
+pub mod builtin_python;
 pub mod builtin_rust;
 pub mod coverage;
 pub mod frontend;
@@ -20,6 +21,7 @@ use std::path::Path;
 use crate::gate::result::MeasureValue;
 use crate::issue::QualityIssue;
 use crate::metrics::MetricKey;
+use crate::rules::suppression::SuppressedIssue;
 
 /// Provider 分析报告
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +36,9 @@ pub struct ProviderReport {
     pub metrics: HashMap<MetricKey, MeasureValue>,
     /// 发现的问题
     pub issues: Vec<QualityIssue>,
+    /// 被行内注释抑制的问题
+    #[serde(default)]
+    pub suppressed: Vec<SuppressedIssue>,
     /// 原始输出（截断保留）
     pub raw_output: Option<String>,
     /// 错误消息
@@ -49,6 +54,7 @@ impl ProviderReport {
             duration_ms,
             metrics: HashMap::new(),
             issues: Vec::new(),
+            suppressed: Vec::new(),
             raw_output: None,
             error: None,
         }
@@ -62,6 +68,7 @@ impl ProviderReport {
             duration_ms,
             metrics: HashMap::new(),
             issues: Vec::new(),
+            suppressed: Vec::new(),
             raw_output: None,
             error: Some(error.into()),
         }
@@ -79,6 +86,12 @@ impl ProviderReport {
         self
     }
 
+    /// 添加被抑制的问题列表
+    pub fn with_suppressed(mut self, suppressed: Vec<SuppressedIssue>) -> Self {
+        self.suppressed = suppressed;
+        self
+    }
+
     /// 设置原始输出
     pub fn with_raw_output(mut self, output: impl Into<String>) -> Self {
         self.raw_output = Some(output.into());
@@ -10,6 +10,7 @@ use crate::gate::status::QualityGateStatus;
 use crate::gate::QualityGateDecision;
 use crate::issue::{IssueSummary, QualityIssue};
 use crate::provider::ProviderReport;
+use crate::rules::suppression::SuppressedIssue;
 
 /// 聚合质量报告
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +23,9 @@ pub struct QualityReport {
     pub provider_reports: Vec<ProviderReport>,
     /// 所有问题（聚合）
     pub all_issues: Vec<QualityIssue>,
+    /// 被行内注释抑制的问题（含原因）
+    #[serde(default)]
+    pub suppressed_issues: Vec<SuppressedIssue>,
     /// 问题摘要
     pub summary: IssueSummary,
     /// 总耗时（毫秒）
@@ -34,20 +38,24 @@ impl QualityReport {
     /// 从多个 Provider 报告构建聚合报告
     pub fn aggregate(reports: Vec<ProviderReport>) -> Self {
         let mut all_issues = Vec::new();
+        let mut suppressed_issues = Vec::new();
         let mut total_duration_ms = 0u64;
 
         for report in &reports {
             all_issues.extend(report.issues.clone());
+            suppressed_issues.extend(report.suppressed.clone());
             total_duration_ms += report.duration_ms;
         }
 
-        let summary = IssueSummary::from_issues(&all_issues);
+        let mut summary = IssueSummary::from_issues(&all_issues);
+        summary.suppressed = suppressed_issues.len();
 
         Self {
             id: Uuid::new_v4().to_string(),
             decision: None,
             provider_reports: reports,
             all_issues,
+            suppressed_issues,
             summary,
             total_duration_ms,
             created_at: Utc::now(),
@@ -1,10 +1,13 @@
 //! Built-in quality rules
 //!
 //! Provides fully self-contained static analysis rules that run without external services.
-//! Rules are organized by language: Rust, TypeScript, and language-agnostic common rules.
+//! Rules are organized by language: Rust, TypeScript, Python, and language-agnostic common rules.
+//! Findings can be silenced with inline comments, see [`suppression`].
 
 pub mod common;
+pub mod python;
 pub mod rust;
+pub mod suppression;
 pub mod typescript;
 
 use std::collections::HashMap;
@@ -30,18 +33,72 @@ pub trait Rule: Send + Sync {
     }
 }
 
+/// Static description of a built-in rule, used when exporting reports
+#[derive(Debug, Clone)]
+pub struct RuleMetadata {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub rule_type: RuleType,
+    pub default_severity: Severity,
+}
+
+impl RuleMetadata {
+    fn of(rule: &dyn Rule) -> Self {
+        Self {
+            id: rule.id().to_string(),
+            name: rule.name().to_string(),
+            description: rule.description().to_string(),
+            rule_type: rule.rule_type(),
+            default_severity: rule.default_severity(),
+        }
+    }
+}
+
+/// Metadata of every built-in rule across all languages
+pub fn builtin_rule_metadata() -> Vec<RuleMetadata> {
+    let mut metadata = Vec::new();
+    metadata.extend(
+        rust::all_rust_rules()
+            .iter()
+            .map(|r| RuleMetadata::of(r.as_ref())),
+    );
+    metadata.extend(
+        typescript::all_ts_rules()
+            .iter()
+            .map(|r| RuleMetadata::of(r.as_ref())),
+    );
+    metadata.extend(
+        python::all_python_rules()
+            .iter()
+            .map(|r| RuleMetadata::of(r.as_ref())),
+    );
+    metadata.extend(
+        common::all_common_rules()
+            .iter()
+            .map(|r| RuleMetadata::of(r.as_ref())),
+    );
+    metadata
+}
+
 /// Rust-specific rule (operates on syn AST)
 pub trait RustRule: Rule {
     /// Analyze a parsed Rust source file
     fn analyze(&self, ctx: &RustAnalysisContext) -> Vec<QualityIssue>;
 }
 
-/// TypeScript/JavaScript-specific rule (operates on source text with line-based analysis)
+/// TypeScript/JavaScript-specific rule (operates on oxc AST)
 pub trait TsRule: Rule {
     /// Analyze a TypeScript/JavaScript source file
     fn analyze(&self, ctx: &TsAnalysisContext) -> Vec<QualityIssue>;
 }
 
+/// Python-specific rule (operates on rustpython AST)
+pub trait PythonRule: Rule {
+    /// Analyze a parsed Python source file
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue>;
+}
+
 /// Language-agnostic rule (operates on raw file content)
 pub trait CommonRule: Rule {
     /// Analyze any file
@@ -68,6 +125,38 @@ pub struct TsAnalysisContext<'a> {
     pub content: &'a str,
     /// Lines of source (pre-split for convenience)
     pub lines: &'a [&'a str],
+    /// Byte offset of every line start, from [`line_starts`]
+    pub line_starts: &'a [usize],
+    /// Parsed AST
+    pub program: &'a oxc_ast::ast::Program<'a>,
+    /// Per-rule configuration
+    pub config: &'a RuleConfig,
+}
+
+impl TsAnalysisContext<'_> {
+    /// 1-based line number of a span's start
+    pub fn line_of(&self, span: oxc_span::Span) -> u32 {
+        let start = span.start as usize;
+        self.line_starts.partition_point(|&line_start| line_start <= start) as u32
+    }
+}
+
+/// Byte offsets at which the lines of `content` start, computed once per file
+/// so that [`TsAnalysisContext::line_of`] can binary-search them.
+pub fn line_starts(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// Analysis context for Python files
+pub struct PythonAnalysisContext<'a> {
+    /// Relative file path from project root
+    pub file_path: &'a str,
+    /// Raw source content
+    pub content: &'a str,
+    /// Parsed module body with line locations
+    pub suite: &'a [rustpython_parser::ast::located::Stmt],
     /// Per-rule configuration
     pub config: &'a RuleConfig,
 }
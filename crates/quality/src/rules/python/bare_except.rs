@@ -0,0 +1,130 @@
+//! Bare `except` rule for Python.
+//!
+//! A bare `except:` also catches `SystemExit`, `KeyboardInterrupt` and `GeneratorExit`,
+//! which hides shutdown requests and real bugs. Catch `Exception` (or something narrower).
+
+use rustpython_parser::ast::located::{ExceptHandler, Stmt};
+
+use super::{child_blocks, line_of};
+use crate::issue::QualityIssue;
+use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::{PythonAnalysisContext, PythonRule, Rule};
+
+/// Detects `except:` clauses without an exception type.
+#[derive(Debug)]
+pub struct BareExceptRule;
+
+impl Rule for BareExceptRule {
+    fn id(&self) -> &str {
+        "python:bare-except"
+    }
+
+    fn name(&self) -> &str {
+        "Bare Except"
+    }
+
+    fn description(&self) -> &str {
+        "Detects bare `except:` clauses that also swallow SystemExit and KeyboardInterrupt"
+    }
+
+    fn rule_type(&self) -> RuleType {
+        RuleType::CodeSmell
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Major
+    }
+}
+
+impl PythonRule for BareExceptRule {
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue> {
+        let severity = ctx
+            .config
+            .severity_override
+            .unwrap_or_else(|| self.default_severity());
+
+        let mut lines = Vec::new();
+        collect_bare_handlers(ctx.suite, &mut lines);
+
+        lines
+            .into_iter()
+            .map(|line| {
+                QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    AnalyzerSource::Other("builtin".to_string()),
+                    "Bare `except:` catches SystemExit and KeyboardInterrupt; catch `Exception` or a specific type",
+                )
+                .with_location(ctx.file_path, line)
+                .with_effort(5)
+            })
+            .collect()
+    }
+}
+
+fn collect_bare_handlers(stmts: &[Stmt], lines: &mut Vec<u32>) {
+    for stmt in stmts {
+        let handlers = match stmt {
+            Stmt::Try(s) => s.handlers.as_slice(),
+            Stmt::TryStar(s) => s.handlers.as_slice(),
+            _ => &[],
+        };
+        for handler in handlers {
+            let ExceptHandler::ExceptHandler(h) = handler;
+            if h.type_.is_none() {
+                lines.push(line_of(handler));
+            }
+        }
+        for block in child_blocks(stmt) {
+            collect_bare_handlers(block, lines);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::RuleConfig;
+    use crate::rules::python::parse_python;
+
+    fn analyze(source: &str) -> Vec<QualityIssue> {
+        let suite = parse_python(source, "test.py").expect("failed to parse test source");
+        let config = RuleConfig::default();
+        let ctx = PythonAnalysisContext {
+            file_path: "app.py",
+            content: source,
+            suite: &suite,
+            config: &config,
+        };
+        BareExceptRule.analyze(&ctx)
+    }
+
+    #[test]
+    fn detects_bare_except_at_any_depth() {
+        let source = r#"
+try:
+    import json
+except:
+    json = None
+
+class Loader:
+    def load(self):
+        try:
+            return 1
+        except ValueError:
+            return 2
+        except:
+            raise
+"#;
+        let issues = analyze(source);
+        let lines: Vec<_> = issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![Some(4), Some(13)]);
+    }
+
+    #[test]
+    fn typed_handlers_are_fine() {
+        let source = "try:\n    pass\nexcept (OSError, ValueError) as e:\n    pass\n";
+        assert!(analyze(source).is_empty());
+    }
+}
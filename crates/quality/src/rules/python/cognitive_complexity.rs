@@ -0,0 +1,277 @@
+//! Cognitive Complexity rule for Python source files.
+//!
+//! Calculates cognitive complexity for each function/method. Breaks in linear flow
+//! (`if`, loops, `except`, `match`, conditional expressions) cost 1 plus the current nesting
+//! level; `elif`/`else` and each boolean operator sequence cost a flat 1.
+
+use rustpython_parser::ast::located::{ExceptHandler, Expr, Stmt};
+
+use super::{collect_functions, is_scope, stmt_exprs, walk_expr};
+use crate::issue::QualityIssue;
+use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::{PythonAnalysisContext, PythonRule, Rule, RuleConfig};
+
+/// Rule that checks cognitive complexity of Python functions/methods.
+pub struct CognitiveComplexityRule;
+
+impl Rule for CognitiveComplexityRule {
+    fn id(&self) -> &str {
+        "python:cognitive-complexity"
+    }
+
+    fn name(&self) -> &str {
+        "Cognitive Complexity"
+    }
+
+    fn description(&self) -> &str {
+        "Checks that cognitive complexity of functions does not exceed a configurable threshold"
+    }
+
+    fn rule_type(&self) -> RuleType {
+        RuleType::CodeSmell
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Major
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::default();
+        config
+            .params
+            .insert("threshold".to_string(), "20".to_string());
+        config
+    }
+}
+
+impl PythonRule for CognitiveComplexityRule {
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue> {
+        let threshold = ctx.config.get_param_usize("threshold", 20);
+        let severity = ctx
+            .config
+            .severity_override
+            .unwrap_or_else(|| self.default_severity());
+
+        let mut issues = Vec::new();
+        for func in collect_functions(ctx.suite) {
+            let mut visitor = CognitiveVisitor::default();
+            visitor.visit_block(func.body);
+            let complexity = visitor.complexity;
+
+            if complexity > threshold {
+                let issue = QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    AnalyzerSource::Other("built-in".to_string()),
+                    format!(
+                        "Function `{}` has a cognitive complexity of {} (threshold: {})",
+                        func.name, complexity, threshold
+                    ),
+                )
+                .with_location(ctx.file_path, func.line)
+                .with_effort(((complexity - threshold) as i32) * 5);
+                issues.push(issue);
+            }
+        }
+
+        issues
+    }
+}
+
+#[derive(Default)]
+struct CognitiveVisitor {
+    complexity: usize,
+    nesting: usize,
+}
+
+impl CognitiveVisitor {
+    /// Add an increment with nesting penalty: base 1 + current nesting depth.
+    fn increment_with_nesting(&mut self) {
+        self.complexity += 1 + self.nesting;
+    }
+
+    /// Add a flat increment of 1 (no nesting penalty).
+    fn increment_flat(&mut self) {
+        self.complexity += 1;
+    }
+
+    fn visit_nested(&mut self, stmts: &[Stmt]) {
+        self.nesting += 1;
+        self.visit_block(stmts);
+        self.nesting -= 1;
+    }
+
+    fn visit_block(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.visit_stmt(stmt);
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        // Nested functions and classes get their own score
+        if is_scope(stmt) {
+            return;
+        }
+        for expr in stmt_exprs(stmt) {
+            self.visit_expr(expr);
+        }
+
+        match stmt {
+            Stmt::If(s) => {
+                self.increment_with_nesting();
+                self.visit_nested(&s.body);
+                self.visit_else(&s.orelse);
+            }
+            Stmt::For(s) => self.visit_loop(&s.body, &s.orelse),
+            Stmt::AsyncFor(s) => self.visit_loop(&s.body, &s.orelse),
+            Stmt::While(s) => self.visit_loop(&s.body, &s.orelse),
+            Stmt::Match(s) => {
+                self.increment_with_nesting();
+                for case in &s.cases {
+                    self.visit_nested(&case.body);
+                }
+            }
+            Stmt::Try(s) => self.visit_try(&s.body, &s.handlers, &s.orelse, &s.finalbody),
+            Stmt::TryStar(s) => self.visit_try(&s.body, &s.handlers, &s.orelse, &s.finalbody),
+            // `with` blocks do not break linear flow
+            Stmt::With(s) => self.visit_block(&s.body),
+            Stmt::AsyncWith(s) => self.visit_block(&s.body),
+            _ => {}
+        }
+    }
+
+    /// `orelse` of an `if`: a lone nested `if` is an `elif` chain.
+    fn visit_else(&mut self, orelse: &[Stmt]) {
+        match orelse {
+            [] => {}
+            [Stmt::If(elif)] => {
+                self.increment_flat();
+                for expr in stmt_exprs(&orelse[0]) {
+                    self.visit_expr(expr);
+                }
+                self.visit_nested(&elif.body);
+                self.visit_else(&elif.orelse);
+            }
+            _ => {
+                self.increment_flat();
+                self.visit_nested(orelse);
+            }
+        }
+    }
+
+    fn visit_loop(&mut self, body: &[Stmt], orelse: &[Stmt]) {
+        self.increment_with_nesting();
+        self.visit_nested(body);
+        if !orelse.is_empty() {
+            self.increment_flat();
+            self.visit_nested(orelse);
+        }
+    }
+
+    fn visit_try(
+        &mut self,
+        body: &[Stmt],
+        handlers: &[ExceptHandler],
+        orelse: &[Stmt],
+        finalbody: &[Stmt],
+    ) {
+        // The `try` itself is free; each `except` is a branch
+        self.visit_block(body);
+        for ExceptHandler::ExceptHandler(handler) in handlers {
+            self.increment_with_nesting();
+            self.visit_nested(&handler.body);
+        }
+        if !orelse.is_empty() {
+            self.increment_flat();
+            self.visit_nested(orelse);
+        }
+        self.visit_block(finalbody);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        let nesting = self.nesting;
+        let mut complexity = 0;
+        walk_expr(expr, &mut |e| match e {
+            // Each sequence of `and`/`or` counts once; mixing operators nests a new BoolOp
+            Expr::BoolOp(_) => complexity += 1,
+            Expr::IfExp(_) => complexity += 1 + nesting,
+            _ => {}
+        });
+        self.complexity += complexity;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::python::parse_python;
+
+    fn complexity_of(source: &str) -> usize {
+        let suite = parse_python(source, "test.py").expect("failed to parse source");
+        let func = collect_functions(&suite).remove(0);
+        let mut visitor = CognitiveVisitor::default();
+        visitor.visit_block(func.body);
+        visitor.complexity
+    }
+
+    #[test]
+    fn nesting_increases_cost() {
+        let source = r#"
+def f(items):
+    for item in items:          # +1
+        if item:                # +2 (nesting 1)
+            while item > 0:     # +3 (nesting 2)
+                item -= 1
+"#;
+        assert_eq!(complexity_of(source), 6);
+    }
+
+    #[test]
+    fn elif_else_and_boolean_sequences_are_flat() {
+        let source = r#"
+def f(a, b, c):
+    if a and b:                 # +1 if, +1 and
+        return 1
+    elif b or c:                # +1 elif, +1 or
+        return 2
+    else:                       # +1
+        return 3
+"#;
+        assert_eq!(complexity_of(source), 5);
+    }
+
+    #[test]
+    fn except_clauses_are_nested_branches() {
+        let source = r#"
+def f():
+    try:
+        pass
+    except ValueError:          # +1
+        if True:                # +2
+            pass
+    except KeyError:            # +1
+        pass
+"#;
+        assert_eq!(complexity_of(source), 4);
+    }
+
+    #[test]
+    fn reports_over_threshold() {
+        let source = "def f(x):\n    if x:\n        return 1\n    return 0\n";
+        let suite = parse_python(source, "test.py").unwrap();
+        let mut config = RuleConfig::default();
+        config
+            .params
+            .insert("threshold".to_string(), "0".to_string());
+        let ctx = PythonAnalysisContext {
+            file_path: "test.py",
+            content: source,
+            suite: &suite,
+            config: &config,
+        };
+        let issues = CognitiveComplexityRule.analyze(&ctx);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(1));
+    }
+}
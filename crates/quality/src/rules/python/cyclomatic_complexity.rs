@@ -0,0 +1,187 @@
+//! Cyclomatic Complexity rule for Python source files.
+//!
+//! Counts the independent paths through each function: 1 plus every `if`/`elif`, loop,
+//! `except` clause, `match` case, conditional expression, comprehension clause and
+//! additional boolean operand. Nested functions and classes are measured on their own.
+
+use rustpython_parser::ast::located::{Expr, Pattern, Stmt};
+
+use super::{child_blocks, collect_functions, is_scope, stmt_exprs, walk_expr};
+use crate::issue::QualityIssue;
+use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::{PythonAnalysisContext, PythonRule, Rule, RuleConfig};
+
+/// Rule that checks cyclomatic complexity of Python functions/methods.
+pub struct CyclomaticComplexityRule;
+
+impl Rule for CyclomaticComplexityRule {
+    fn id(&self) -> &str {
+        "python:cyclomatic-complexity"
+    }
+
+    fn name(&self) -> &str {
+        "Cyclomatic Complexity"
+    }
+
+    fn description(&self) -> &str {
+        "Checks that cyclomatic complexity of functions does not exceed a configurable threshold"
+    }
+
+    fn rule_type(&self) -> RuleType {
+        RuleType::CodeSmell
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Major
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        let mut config = RuleConfig::default();
+        config
+            .params
+            .insert("threshold".to_string(), "15".to_string());
+        config
+    }
+}
+
+impl PythonRule for CyclomaticComplexityRule {
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue> {
+        let threshold = ctx.config.get_param_usize("threshold", 15);
+        let severity = ctx
+            .config
+            .severity_override
+            .unwrap_or_else(|| self.default_severity());
+
+        let mut issues = Vec::new();
+        for func in collect_functions(ctx.suite) {
+            let complexity = 1 + block_complexity(func.body);
+            if complexity > threshold {
+                let issue = QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    AnalyzerSource::Other("built-in".to_string()),
+                    format!(
+                        "Function `{}` has a cyclomatic complexity of {} (threshold: {})",
+                        func.name, complexity, threshold
+                    ),
+                )
+                .with_location(ctx.file_path, func.line)
+                .with_effort(((complexity - threshold) as i32) * 5);
+                issues.push(issue);
+            }
+        }
+
+        issues
+    }
+}
+
+/// Decision points in a block, not counting nested scopes.
+fn block_complexity(stmts: &[Stmt]) -> usize {
+    let mut complexity = 0;
+    for stmt in stmts {
+        if is_scope(stmt) {
+            continue;
+        }
+        complexity += match stmt {
+            Stmt::If(_) | Stmt::For(_) | Stmt::AsyncFor(_) | Stmt::While(_) => 1,
+            Stmt::Try(s) => s.handlers.len(),
+            Stmt::TryStar(s) => s.handlers.len(),
+            Stmt::Match(s) => s
+                .cases
+                .iter()
+                .filter(|case| !is_wildcard(&case.pattern) || case.guard.is_some())
+                .count(),
+            _ => 0,
+        };
+        for expr in stmt_exprs(stmt) {
+            walk_expr(expr, &mut |e| complexity += expr_decisions(e));
+        }
+        for block in child_blocks(stmt) {
+            complexity += block_complexity(block);
+        }
+    }
+    complexity
+}
+
+fn expr_decisions(expr: &Expr) -> usize {
+    match expr {
+        Expr::BoolOp(e) => e.values.len().saturating_sub(1),
+        Expr::IfExp(_) => 1,
+        Expr::ListComp(e) => e.generators.iter().map(|g| 1 + g.ifs.len()).sum(),
+        Expr::SetComp(e) => e.generators.iter().map(|g| 1 + g.ifs.len()).sum(),
+        Expr::DictComp(e) => e.generators.iter().map(|g| 1 + g.ifs.len()).sum(),
+        Expr::GeneratorExp(e) => e.generators.iter().map(|g| 1 + g.ifs.len()).sum(),
+        _ => 0,
+    }
+}
+
+/// `case _:` — the fall-through arm adds no extra path.
+fn is_wildcard(pattern: &Pattern) -> bool {
+    matches!(pattern, Pattern::MatchAs(p) if p.pattern.is_none() && p.name.is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::python::parse_python;
+
+    fn analyze_source_with_threshold(source: &str, threshold: usize) -> Vec<QualityIssue> {
+        let suite = parse_python(source, "test.py").expect("failed to parse source");
+        let mut config = RuleConfig::default();
+        config
+            .params
+            .insert("threshold".to_string(), threshold.to_string());
+        let ctx = PythonAnalysisContext {
+            file_path: "test.py",
+            content: source,
+            suite: &suite,
+            config: &config,
+        };
+        CyclomaticComplexityRule.analyze(&ctx)
+    }
+
+    #[test]
+    fn simple_function_passes() {
+        let source = "def add(a, b):\n    return a + b\n";
+        assert!(analyze_source_with_threshold(source, 1).is_empty());
+    }
+
+    #[test]
+    fn counts_branches_loops_and_boolean_operands() {
+        let source = r#"
+def classify(items, strict):
+    for item in items:
+        if item > 10 and strict:
+            return "big"
+        elif item < 0 or item is None:
+            return "bad"
+    try:
+        return [x for x in items if x]
+    except ValueError:
+        return None
+"#;
+        // 1 + for + if + and + elif + or + comprehension + comprehension-if + except = 9
+        let issues = analyze_source_with_threshold(source, 8);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("complexity of 9"));
+        assert!(analyze_source_with_threshold(source, 9).is_empty());
+    }
+
+    #[test]
+    fn nested_functions_are_measured_separately() {
+        let source = r#"
+def outer(x):
+    def inner(y):
+        if y:
+            return 1
+        if not y:
+            return 2
+        return 3
+    return inner(x)
+"#;
+        let issues = analyze_source_with_threshold(source, 2);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("`inner`"));
+    }
+}
@@ -0,0 +1,114 @@
+//! Rule: Function Length
+//!
+//! Checks that Python functions and methods do not exceed a configurable maximum line count.
+
+use super::collect_functions;
+use crate::issue::QualityIssue;
+use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::{PythonAnalysisContext, PythonRule, Rule, RuleConfig};
+
+/// Rule that flags functions/methods exceeding a maximum line count.
+pub struct FunctionLengthRule;
+
+impl Rule for FunctionLengthRule {
+    fn id(&self) -> &str {
+        "python:function-length"
+    }
+
+    fn name(&self) -> &str {
+        "Function Length"
+    }
+
+    fn description(&self) -> &str {
+        "Checks that functions and methods do not exceed a maximum number of lines"
+    }
+
+    fn rule_type(&self) -> RuleType {
+        RuleType::CodeSmell
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Major
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        RuleConfig::default()
+    }
+}
+
+impl PythonRule for FunctionLengthRule {
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue> {
+        let max_lines = ctx.config.get_param_usize("max_lines", 60);
+        let severity = ctx
+            .config
+            .severity_override
+            .unwrap_or_else(|| self.default_severity());
+
+        let mut issues = Vec::new();
+        for func in collect_functions(ctx.suite) {
+            // Lines after the `def` line, matching the body-only count of the Rust rule
+            let line_count = func.end_line.saturating_sub(func.line) as usize;
+            if line_count > max_lines {
+                let issue = QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    AnalyzerSource::Other("built-in".to_string()),
+                    format!(
+                        "Function `{}` has {} lines, which exceeds the maximum of {} lines",
+                        func.name, line_count, max_lines
+                    ),
+                )
+                .with_location(ctx.file_path, func.line);
+                issues.push(issue);
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::python::parse_python;
+
+    fn analyze_code(source: &str, max_lines: usize) -> Vec<QualityIssue> {
+        let suite = parse_python(source, "test.py").expect("failed to parse test source");
+        let config = RuleConfig {
+            enabled: true,
+            severity_override: None,
+            params: [("max_lines".to_string(), max_lines.to_string())]
+                .into_iter()
+                .collect(),
+        };
+        let ctx = PythonAnalysisContext {
+            file_path: "test.py",
+            content: source,
+            suite: &suite,
+            config: &config,
+        };
+        FunctionLengthRule.analyze(&ctx)
+    }
+
+    #[test]
+    fn short_function_no_issue() {
+        let source = "def short():\n    x = 1\n    return x\n";
+        assert!(analyze_code(source, 60).is_empty());
+    }
+
+    #[test]
+    fn long_method_triggers_issue() {
+        let mut body_lines = String::new();
+        for i in 0..70 {
+            body_lines.push_str(&format!("        v{} = {}\n", i, i));
+        }
+        let source = format!("class Service:\n    def long_method(self):\n{}", body_lines);
+
+        let issues = analyze_code(&source, 60);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("long_method"));
+        assert!(issues[0].message.contains("70 lines"));
+        assert_eq!(issues[0].line, Some(2));
+    }
+}
@@ -0,0 +1,306 @@
+//! Built-in Python quality rules
+//!
+//! Each rule operates on a located `rustpython_parser` AST and produces `QualityIssue` results.
+//! The helpers below give rules a borrowed view over statement blocks and sub-expressions,
+//! since the upstream visitor consumes nodes by value.
+
+pub mod bare_except;
+pub mod cognitive_complexity;
+pub mod cyclomatic_complexity;
+pub mod function_length;
+pub mod mutable_default_args;
+pub mod nesting_depth;
+pub mod print_usage;
+pub mod todo_comments;
+
+use rustpython_parser::Parse;
+use rustpython_parser::ast::Fold;
+use rustpython_parser::ast::located::{Arguments, ExceptHandler, Expr, Located, Stmt, Suite};
+use rustpython_parser::source_code::RandomLocator;
+
+use super::PythonRule;
+
+/// Parse Python source into a located AST (1-based line numbers on every node).
+pub fn parse_python(source: &str, file_path: &str) -> anyhow::Result<Suite> {
+    let suite = rustpython_parser::ast::Suite::parse(source, file_path)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let mut locator = RandomLocator::new(source);
+    let located = locator.fold(suite).unwrap_or_else(|never| match never {});
+    Ok(located)
+}
+
+/// A `def` or `async def` found anywhere in a module.
+pub struct FunctionDef<'a> {
+    pub name: &'a str,
+    /// Line of the `def` keyword
+    pub line: u32,
+    /// Last line of the function body
+    pub end_line: u32,
+    pub args: &'a Arguments,
+    pub body: &'a [Stmt],
+}
+
+/// Collect every function and method, including nested ones, in source order.
+pub fn collect_functions(suite: &[Stmt]) -> Vec<FunctionDef<'_>> {
+    let mut functions = Vec::new();
+    collect_functions_in(suite, &mut functions);
+    functions
+}
+
+fn collect_functions_in<'a>(stmts: &'a [Stmt], functions: &mut Vec<FunctionDef<'a>>) {
+    for stmt in stmts {
+        let def = match stmt {
+            Stmt::FunctionDef(f) => Some((f.name.as_str(), &*f.args, f.body.as_slice())),
+            Stmt::AsyncFunctionDef(f) => Some((f.name.as_str(), &*f.args, f.body.as_slice())),
+            _ => None,
+        };
+        if let Some((name, args, body)) = def {
+            let range = stmt.range();
+            functions.push(FunctionDef {
+                name,
+                line: range.start.row.get(),
+                end_line: range.end.map_or(range.start.row, |end| end.row).get(),
+                args,
+                body,
+            });
+        }
+        for block in child_blocks(stmt) {
+            collect_functions_in(block, functions);
+        }
+    }
+}
+
+/// Line of a node.
+pub fn line_of(node: &impl Located) -> u32 {
+    node.location().row.get()
+}
+
+/// Whether `stmt` opens a new scope whose body is analysed on its own.
+pub fn is_scope(stmt: &Stmt) -> bool {
+    matches!(
+        stmt,
+        Stmt::FunctionDef(_) | Stmt::AsyncFunctionDef(_) | Stmt::ClassDef(_)
+    )
+}
+
+/// Statement blocks directly nested in `stmt` (bodies, branches, handlers, match cases).
+pub fn child_blocks(stmt: &Stmt) -> Vec<&[Stmt]> {
+    match stmt {
+        Stmt::FunctionDef(s) => vec![&s.body],
+        Stmt::AsyncFunctionDef(s) => vec![&s.body],
+        Stmt::ClassDef(s) => vec![&s.body],
+        Stmt::For(s) => vec![&s.body, &s.orelse],
+        Stmt::AsyncFor(s) => vec![&s.body, &s.orelse],
+        Stmt::While(s) => vec![&s.body, &s.orelse],
+        Stmt::If(s) => vec![&s.body, &s.orelse],
+        Stmt::With(s) => vec![&s.body],
+        Stmt::AsyncWith(s) => vec![&s.body],
+        Stmt::Match(s) => s.cases.iter().map(|case| case.body.as_slice()).collect(),
+        Stmt::Try(s) => try_blocks(&s.body, &s.handlers, &s.orelse, &s.finalbody),
+        Stmt::TryStar(s) => try_blocks(&s.body, &s.handlers, &s.orelse, &s.finalbody),
+        _ => Vec::new(),
+    }
+}
+
+fn try_blocks<'a>(
+    body: &'a [Stmt],
+    handlers: &'a [ExceptHandler],
+    orelse: &'a [Stmt],
+    finalbody: &'a [Stmt],
+) -> Vec<&'a [Stmt]> {
+    let mut blocks = vec![body];
+    blocks.extend(
+        handlers
+            .iter()
+            .map(|ExceptHandler::ExceptHandler(h)| h.body.as_slice()),
+    );
+    blocks.push(orelse);
+    blocks.push(finalbody);
+    blocks
+}
+
+/// Expressions owned by `stmt` itself, excluding those inside its nested blocks.
+pub fn stmt_exprs(stmt: &Stmt) -> Vec<&Expr> {
+    let mut exprs: Vec<&Expr> = Vec::new();
+    match stmt {
+        Stmt::FunctionDef(s) => {
+            exprs.extend(&s.decorator_list);
+            exprs.extend(argument_defaults(&s.args));
+        }
+        Stmt::AsyncFunctionDef(s) => {
+            exprs.extend(&s.decorator_list);
+            exprs.extend(argument_defaults(&s.args));
+        }
+        Stmt::ClassDef(s) => {
+            exprs.extend(&s.decorator_list);
+            exprs.extend(&s.bases);
+            exprs.extend(s.keywords.iter().map(|k| &k.value));
+        }
+        Stmt::Return(s) => exprs.extend(s.value.as_deref()),
+        Stmt::Delete(s) => exprs.extend(&s.targets),
+        Stmt::Assign(s) => {
+            exprs.extend(&s.targets);
+            exprs.push(&s.value);
+        }
+        Stmt::TypeAlias(s) => exprs.push(&s.value),
+        Stmt::AugAssign(s) => exprs.extend([&*s.target, &*s.value]),
+        Stmt::AnnAssign(s) => {
+            exprs.extend([&*s.target, &*s.annotation]);
+            exprs.extend(s.value.as_deref());
+        }
+        Stmt::For(s) => exprs.extend([&*s.target, &*s.iter]),
+        Stmt::AsyncFor(s) => exprs.extend([&*s.target, &*s.iter]),
+        Stmt::While(s) => exprs.push(&s.test),
+        Stmt::If(s) => exprs.push(&s.test),
+        Stmt::With(s) => {
+            for item in &s.items {
+                exprs.push(&item.context_expr);
+                exprs.extend(item.optional_vars.as_deref());
+            }
+        }
+        Stmt::AsyncWith(s) => {
+            for item in &s.items {
+                exprs.push(&item.context_expr);
+                exprs.extend(item.optional_vars.as_deref());
+            }
+        }
+        Stmt::Match(s) => {
+            exprs.push(&s.subject);
+            exprs.extend(s.cases.iter().filter_map(|case| case.guard.as_deref()));
+        }
+        Stmt::Raise(s) => {
+            exprs.extend(s.exc.as_deref());
+            exprs.extend(s.cause.as_deref());
+        }
+        Stmt::Try(s) => exprs.extend(handler_types(&s.handlers)),
+        Stmt::TryStar(s) => exprs.extend(handler_types(&s.handlers)),
+        Stmt::Assert(s) => {
+            exprs.push(&s.test);
+            exprs.extend(s.msg.as_deref());
+        }
+        Stmt::Expr(s) => exprs.push(&s.value),
+        _ => {}
+    }
+    exprs
+}
+
+fn argument_defaults(args: &Arguments) -> impl Iterator<Item = &Expr> {
+    args.posonlyargs
+        .iter()
+        .chain(&args.args)
+        .chain(&args.kwonlyargs)
+        .filter_map(|arg| arg.default.as_deref())
+}
+
+fn handler_types(handlers: &[ExceptHandler]) -> impl Iterator<Item = &Expr> {
+    handlers
+        .iter()
+        .filter_map(|ExceptHandler::ExceptHandler(h)| h.type_.as_deref())
+}
+
+/// Direct sub-expressions of `expr`.
+pub fn child_exprs(expr: &Expr) -> Vec<&Expr> {
+    let mut exprs: Vec<&Expr> = Vec::new();
+    match expr {
+        Expr::BoolOp(e) => exprs.extend(&e.values),
+        Expr::NamedExpr(e) => exprs.extend([&*e.target, &*e.value]),
+        Expr::BinOp(e) => exprs.extend([&*e.left, &*e.right]),
+        Expr::UnaryOp(e) => exprs.push(&e.operand),
+        Expr::Lambda(e) => {
+            exprs.extend(argument_defaults(&e.args));
+            exprs.push(&e.body);
+        }
+        Expr::IfExp(e) => exprs.extend([&*e.test, &*e.body, &*e.orelse]),
+        Expr::Dict(e) => {
+            exprs.extend(e.keys.iter().flatten());
+            exprs.extend(&e.values);
+        }
+        Expr::Set(e) => exprs.extend(&e.elts),
+        Expr::ListComp(e) => {
+            exprs.push(&e.elt);
+            exprs.extend(e.generators.iter().flat_map(comprehension_exprs));
+        }
+        Expr::SetComp(e) => {
+            exprs.push(&e.elt);
+            exprs.extend(e.generators.iter().flat_map(comprehension_exprs));
+        }
+        Expr::DictComp(e) => {
+            exprs.extend([&*e.key, &*e.value]);
+            exprs.extend(e.generators.iter().flat_map(comprehension_exprs));
+        }
+        Expr::GeneratorExp(e) => {
+            exprs.push(&e.elt);
+            exprs.extend(e.generators.iter().flat_map(comprehension_exprs));
+        }
+        Expr::Await(e) => exprs.push(&e.value),
+        Expr::Yield(e) => exprs.extend(e.value.as_deref()),
+        Expr::YieldFrom(e) => exprs.push(&e.value),
+        Expr::Compare(e) => {
+            exprs.push(&e.left);
+            exprs.extend(&e.comparators);
+        }
+        Expr::Call(e) => {
+            exprs.push(&e.func);
+            exprs.extend(&e.args);
+            exprs.extend(e.keywords.iter().map(|k| &k.value));
+        }
+        Expr::FormattedValue(e) => {
+            exprs.push(&e.value);
+            exprs.extend(e.format_spec.as_deref());
+        }
+        Expr::JoinedStr(e) => exprs.extend(&e.values),
+        Expr::Attribute(e) => exprs.push(&e.value),
+        Expr::Subscript(e) => exprs.extend([&*e.value, &*e.slice]),
+        Expr::Starred(e) => exprs.push(&e.value),
+        Expr::List(e) => exprs.extend(&e.elts),
+        Expr::Tuple(e) => exprs.extend(&e.elts),
+        Expr::Slice(e) => {
+            exprs.extend(e.lower.as_deref());
+            exprs.extend(e.upper.as_deref());
+            exprs.extend(e.step.as_deref());
+        }
+        Expr::Constant(_) | Expr::Name(_) => {}
+    }
+    exprs
+}
+
+fn comprehension_exprs(
+    generator: &rustpython_parser::ast::located::Comprehension,
+) -> impl Iterator<Item = &Expr> {
+    [&generator.target, &generator.iter]
+        .into_iter()
+        .chain(&generator.ifs)
+}
+
+/// Visit `expr` and all of its sub-expressions, depth first.
+pub fn walk_expr<'a>(expr: &'a Expr, f: &mut impl FnMut(&'a Expr)) {
+    f(expr);
+    for child in child_exprs(expr) {
+        walk_expr(child, f);
+    }
+}
+
+/// Whether `path` looks like a test module (`test_*.py`, `*_test.py`, `tests/`, `conftest.py`).
+pub fn is_test_file(path: &str) -> bool {
+    let normalized = path.replace('\\', "/");
+    let file_name = normalized.rsplit('/').next().unwrap_or(&normalized);
+    file_name.starts_with("test_")
+        || file_name.ends_with("_test.py")
+        || file_name == "conftest.py"
+        || normalized.starts_with("tests/")
+        || normalized.contains("/tests/")
+}
+
+/// Collect all built-in Python rules
+pub fn all_python_rules() -> Vec<Box<dyn PythonRule>> {
+    vec![
+        Box::new(cyclomatic_complexity::CyclomaticComplexityRule),
+        Box::new(cognitive_complexity::CognitiveComplexityRule),
+        Box::new(function_length::FunctionLengthRule),
+        Box::new(nesting_depth::NestingDepthRule),
+        Box::new(bare_except::BareExceptRule),
+        Box::new(mutable_default_args::MutableDefaultArgsRule),
+        Box::new(print_usage::PrintUsageRule),
+        Box::new(todo_comments::TodoCommentsRule::default()),
+    ]
+}
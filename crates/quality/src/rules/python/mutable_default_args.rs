@@ -0,0 +1,152 @@
+//! Mutable default argument rule for Python.
+//!
+//! Default values are evaluated once, at `def` time, so a list/dict/set default is shared
+//! between calls and leaks state. Use `None` and create the container inside the function.
+
+use rustpython_parser::ast::located::Expr;
+
+use super::{collect_functions, line_of};
+use crate::issue::QualityIssue;
+use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::{PythonAnalysisContext, PythonRule, Rule};
+
+/// Constructors whose result is mutable and should never be used as a default value.
+const MUTABLE_CONSTRUCTORS: &[&str] = &[
+    "list",
+    "dict",
+    "set",
+    "bytearray",
+    "defaultdict",
+    "OrderedDict",
+    "Counter",
+    "deque",
+];
+
+/// Detects list/dict/set literals, comprehensions and constructor calls used as defaults.
+#[derive(Debug)]
+pub struct MutableDefaultArgsRule;
+
+impl Rule for MutableDefaultArgsRule {
+    fn id(&self) -> &str {
+        "python:mutable-default-args"
+    }
+
+    fn name(&self) -> &str {
+        "Mutable Default Arguments"
+    }
+
+    fn description(&self) -> &str {
+        "Detects mutable default argument values, which are shared across calls"
+    }
+
+    fn rule_type(&self) -> RuleType {
+        RuleType::Bug
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Major
+    }
+}
+
+impl PythonRule for MutableDefaultArgsRule {
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue> {
+        let severity = ctx
+            .config
+            .severity_override
+            .unwrap_or_else(|| self.default_severity());
+
+        let mut issues = Vec::new();
+        for func in collect_functions(ctx.suite) {
+            let args = func
+                .args
+                .posonlyargs
+                .iter()
+                .chain(&func.args.args)
+                .chain(&func.args.kwonlyargs);
+            for arg in args {
+                let Some(default) = arg.default.as_deref() else {
+                    continue;
+                };
+                if !is_mutable(default) {
+                    continue;
+                }
+                let issue = QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    AnalyzerSource::Other("builtin".to_string()),
+                    format!(
+                        "Parameter `{}` of `{}` has a mutable default value; default to `None` and create it inside the function",
+                        arg.def.arg, func.name
+                    ),
+                )
+                .with_location(ctx.file_path, line_of(default))
+                .with_effort(5);
+                issues.push(issue);
+            }
+        }
+
+        issues
+    }
+}
+
+fn is_mutable(expr: &Expr) -> bool {
+    match expr {
+        Expr::List(_)
+        | Expr::Dict(_)
+        | Expr::Set(_)
+        | Expr::ListComp(_)
+        | Expr::DictComp(_)
+        | Expr::SetComp(_) => true,
+        Expr::Call(call) => {
+            let callee = match call.func.as_ref() {
+                Expr::Name(name) => name.id.as_str(),
+                Expr::Attribute(attr) => attr.attr.as_str(),
+                _ => return false,
+            };
+            MUTABLE_CONSTRUCTORS.contains(&callee)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::RuleConfig;
+    use crate::rules::python::parse_python;
+
+    fn analyze(source: &str) -> Vec<QualityIssue> {
+        let suite = parse_python(source, "test.py").expect("failed to parse test source");
+        let config = RuleConfig::default();
+        let ctx = PythonAnalysisContext {
+            file_path: "app.py",
+            content: source,
+            suite: &suite,
+            config: &config,
+        };
+        MutableDefaultArgsRule.analyze(&ctx)
+    }
+
+    #[test]
+    fn detects_mutable_defaults() {
+        let source = r#"
+def a(items=[], *, opts={}):
+    pass
+
+async def b(seen=set(), cache=collections.defaultdict(list)):
+    pass
+"#;
+        let issues = analyze(source);
+        assert_eq!(issues.len(), 4);
+        assert!(issues[0].message.contains("`items` of `a`"));
+        assert!(issues[1].message.contains("`opts`"));
+        assert_eq!(issues[2].line, Some(5));
+    }
+
+    #[test]
+    fn immutable_defaults_are_fine() {
+        let source = "def f(a=None, b=(), c=0, d=\"x\", e=frozenset()):\n    pass\n";
+        assert!(analyze(source).is_empty());
+    }
+}
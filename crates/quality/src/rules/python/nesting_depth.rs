@@ -0,0 +1,155 @@
+//! Rule: Nesting Depth
+//!
+//! Checks that control structure nesting within Python functions does not exceed a configurable
+//! maximum depth. Deeply nested code is hard to read and maintain.
+
+use rustpython_parser::ast::located::Stmt;
+
+use super::{child_blocks, collect_functions, is_scope};
+use crate::issue::QualityIssue;
+use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::{PythonAnalysisContext, PythonRule, Rule, RuleConfig};
+
+/// Rule that flags functions whose control-flow nesting depth exceeds a threshold.
+pub struct NestingDepthRule;
+
+impl Rule for NestingDepthRule {
+    fn id(&self) -> &str {
+        "python:nesting-depth"
+    }
+
+    fn name(&self) -> &str {
+        "Nesting Depth"
+    }
+
+    fn description(&self) -> &str {
+        "Checks that control structure nesting depth within functions does not exceed a maximum"
+    }
+
+    fn rule_type(&self) -> RuleType {
+        RuleType::CodeSmell
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Major
+    }
+
+    fn default_config(&self) -> RuleConfig {
+        RuleConfig::default()
+    }
+}
+
+impl PythonRule for NestingDepthRule {
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue> {
+        let max_depth = ctx.config.get_param_usize("max_depth", 5);
+        let severity = ctx
+            .config
+            .severity_override
+            .unwrap_or_else(|| self.default_severity());
+
+        let mut issues = Vec::new();
+        for func in collect_functions(ctx.suite) {
+            let depth = max_depth_of(func.body);
+            if depth > max_depth {
+                let issue = QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    AnalyzerSource::Other("built-in".to_string()),
+                    format!(
+                        "Function `{}` has a nesting depth of {}, which exceeds the maximum of {}",
+                        func.name, depth, max_depth
+                    ),
+                )
+                .with_location(ctx.file_path, func.line);
+                issues.push(issue);
+            }
+        }
+
+        issues
+    }
+}
+
+/// Deepest control-flow nesting in `stmts`. Nested functions and classes get their own check.
+fn max_depth_of(stmts: &[Stmt]) -> usize {
+    stmts
+        .iter()
+        .filter(|stmt| !is_scope(stmt))
+        .map(|stmt| match stmt {
+            // `elif` continues the chain at the same depth as its `if`
+            Stmt::If(s) => match s.orelse.as_slice() {
+                elif @ [Stmt::If(_)] => (1 + max_depth_of(&s.body)).max(max_depth_of(elif)),
+                orelse => 1 + max_depth_of(&s.body).max(max_depth_of(orelse)),
+            },
+            _ => {
+                let blocks = child_blocks(stmt);
+                if blocks.is_empty() {
+                    0
+                } else {
+                    1 + blocks.into_iter().map(max_depth_of).max().unwrap_or(0)
+                }
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::python::parse_python;
+
+    fn analyze_code(source: &str, max_depth: usize) -> Vec<QualityIssue> {
+        let suite = parse_python(source, "test.py").expect("failed to parse test source");
+        let config = RuleConfig {
+            enabled: true,
+            severity_override: None,
+            params: [("max_depth".to_string(), max_depth.to_string())]
+                .into_iter()
+                .collect(),
+        };
+        let ctx = PythonAnalysisContext {
+            file_path: "test.py",
+            content: source,
+            suite: &suite,
+            config: &config,
+        };
+        NestingDepthRule.analyze(&ctx)
+    }
+
+    #[test]
+    fn elif_chain_does_not_deepen() {
+        let source = r#"
+def dispatch(x):
+    if x == 1:
+        return "a"
+    elif x == 2:
+        return "b"
+    elif x == 3:
+        return "c"
+    else:
+        return "d"
+"#;
+        assert!(analyze_code(source, 1).is_empty());
+    }
+
+    #[test]
+    fn deep_nesting_triggers_issue() {
+        let source = r#"
+def deep(items):
+    for item in items:
+        with open(item) as f:
+            try:
+                while True:
+                    if f.readline():
+                        match item:
+                            case "x":
+                                pass
+            except OSError:
+                pass
+"#;
+        let issues = analyze_code(source, 5);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("nesting depth of 6"));
+    }
+}
@@ -0,0 +1,154 @@
+//! `print` usage detection rule for Python.
+//!
+//! Flags `print(...)` calls in production modules; use `logging` instead. Test modules and
+//! code under `if __name__ == "__main__":` are skipped.
+
+use rustpython_parser::ast::located::{Constant, Expr, Stmt};
+
+use super::{child_blocks, is_test_file, line_of, stmt_exprs, walk_expr};
+use crate::issue::QualityIssue;
+use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::{PythonAnalysisContext, PythonRule, Rule};
+
+/// Detects `print()` calls in production Python code.
+#[derive(Debug)]
+pub struct PrintUsageRule;
+
+impl Rule for PrintUsageRule {
+    fn id(&self) -> &str {
+        "python:print-usage"
+    }
+
+    fn name(&self) -> &str {
+        "Print Usage"
+    }
+
+    fn description(&self) -> &str {
+        "Detects print() calls that should be replaced by logging in production code"
+    }
+
+    fn rule_type(&self) -> RuleType {
+        RuleType::CodeSmell
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Minor
+    }
+}
+
+impl PythonRule for PrintUsageRule {
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue> {
+        if is_test_file(ctx.file_path) {
+            return Vec::new();
+        }
+
+        let severity = ctx
+            .config
+            .severity_override
+            .unwrap_or_else(|| self.default_severity());
+
+        let mut lines = Vec::new();
+        collect_print_calls(ctx.suite, &mut lines);
+
+        lines
+            .into_iter()
+            .map(|line| {
+                QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    AnalyzerSource::Other("builtin".to_string()),
+                    format!(
+                        "`print()` call on line {}; use the `logging` module instead",
+                        line
+                    ),
+                )
+                .with_location(ctx.file_path, line)
+                .with_effort(2)
+            })
+            .collect()
+    }
+}
+
+fn collect_print_calls(stmts: &[Stmt], lines: &mut Vec<u32>) {
+    for stmt in stmts {
+        if is_main_guard(stmt) {
+            continue;
+        }
+        for expr in stmt_exprs(stmt) {
+            walk_expr(expr, &mut |e| {
+                if let Expr::Call(call) = e
+                    && matches!(call.func.as_ref(), Expr::Name(name) if name.id.as_str() == "print")
+                {
+                    lines.push(line_of(e));
+                }
+            });
+        }
+        for block in child_blocks(stmt) {
+            collect_print_calls(block, lines);
+        }
+    }
+}
+
+/// `if __name__ == "__main__":`
+fn is_main_guard(stmt: &Stmt) -> bool {
+    let Stmt::If(s) = stmt else {
+        return false;
+    };
+    let Expr::Compare(cmp) = s.test.as_ref() else {
+        return false;
+    };
+    let is_dunder_name = |e: &Expr| matches!(e, Expr::Name(n) if n.id.as_str() == "__name__");
+    let is_main = |e: &Expr| match e {
+        Expr::Constant(c) => matches!(&c.value, Constant::Str(v) if v == "__main__"),
+        _ => false,
+    };
+    match (cmp.left.as_ref(), cmp.comparators.as_slice()) {
+        (left, [right]) => {
+            (is_dunder_name(left) && is_main(right)) || (is_main(left) && is_dunder_name(right))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::RuleConfig;
+    use crate::rules::python::parse_python;
+
+    fn analyze(path: &str, source: &str) -> Vec<QualityIssue> {
+        let suite = parse_python(source, path).expect("failed to parse test source");
+        let config = RuleConfig::default();
+        let ctx = PythonAnalysisContext {
+            file_path: path,
+            content: source,
+            suite: &suite,
+            config: &config,
+        };
+        PrintUsageRule.analyze(&ctx)
+    }
+
+    #[test]
+    fn detects_print_calls() {
+        let source = r#"
+def run(x):
+    print("running", x)
+    value = compute(lambda: print(x))
+    self.print(x)
+"#;
+        let issues = analyze("app/service.py", source);
+        let lines: Vec<_> = issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![Some(3), Some(4)]);
+    }
+
+    #[test]
+    fn skips_main_guard_and_tests() {
+        let source = "if __name__ == \"__main__\":\n    print(main())\n";
+        assert!(analyze("app/cli.py", source).is_empty());
+
+        let source = "def test_x():\n    print('debug')\n";
+        assert!(analyze("tests/test_app.py", source).is_empty());
+        assert!(analyze("app/test_models.py", source).is_empty());
+    }
+}
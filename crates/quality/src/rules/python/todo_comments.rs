@@ -0,0 +1,114 @@
+//! TODO/FIXME comments rule — detects TODO, FIXME, HACK, XXX comments in Python source files.
+
+use regex::Regex;
+use rustpython_parser::source_code::LinearLocator;
+use rustpython_parser::{Mode, Tok, lexer};
+
+use crate::issue::QualityIssue;
+use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::{PythonAnalysisContext, PythonRule, Rule};
+
+/// Detects TODO, FIXME, HACK, and XXX `#` comments in Python source files.
+///
+/// Comments are not part of the AST, so the rule scans the lexer's comment tokens;
+/// markers inside string literals are not reported.
+#[derive(Debug)]
+pub struct TodoCommentsRule {
+    marker_pattern: Regex,
+}
+
+impl Default for TodoCommentsRule {
+    fn default() -> Self {
+        Self {
+            marker_pattern: Regex::new(r"(?i)^#\s*(TODO|FIXME|HACK|XXX)\b")
+                .expect("invalid TODO marker regex"),
+        }
+    }
+}
+
+impl Rule for TodoCommentsRule {
+    fn id(&self) -> &str {
+        "python:todo-comments"
+    }
+
+    fn name(&self) -> &str {
+        "TODO/FIXME Comments"
+    }
+
+    fn description(&self) -> &str {
+        "Detects TODO, FIXME, HACK, and XXX comments that indicate unfinished work"
+    }
+
+    fn rule_type(&self) -> RuleType {
+        RuleType::CodeSmell
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+}
+
+impl PythonRule for TodoCommentsRule {
+    fn analyze(&self, ctx: &PythonAnalysisContext) -> Vec<QualityIssue> {
+        let severity = ctx
+            .config
+            .severity_override
+            .unwrap_or_else(|| self.default_severity());
+
+        let mut issues = Vec::new();
+        let mut locator = LinearLocator::new(ctx.content);
+
+        let tokens = lexer::lex(ctx.content, Mode::Module).map_while(Result::ok);
+        for (tok, range) in tokens {
+            let Tok::Comment(comment) = tok else {
+                continue;
+            };
+            if self.marker_pattern.is_match(&comment) {
+                let comment_text = comment.trim().to_string();
+                let line_number = locator.locate(range.start()).row.get();
+
+                let issue = QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    AnalyzerSource::Other("builtin".to_string()),
+                    format!(
+                        "Found comment marker on line {}: {}",
+                        line_number, comment_text
+                    ),
+                )
+                .with_location(ctx.file_path, line_number)
+                .with_effort(5);
+
+                issues.push(issue);
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::RuleConfig;
+    use crate::rules::python::parse_python;
+
+    #[test]
+    fn detects_comment_markers() {
+        let source = "x = 1  # TODO: remove\n# fixme later\ny = \"todo\"\n# HACKY is not a marker\n\
+                      z = \"# TODO: inside a string\"\nw = \"\"\"\n# FIXME: docstring\n\"\"\"  # XXX\n";
+        let suite = parse_python(source, "test.py").unwrap();
+        let config = RuleConfig::default();
+        let ctx = PythonAnalysisContext {
+            file_path: "test.py",
+            content: source,
+            suite: &suite,
+            config: &config,
+        };
+        let issues = TodoCommentsRule::default().analyze(&ctx);
+        let lines: Vec<_> = issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![Some(1), Some(2), Some(8)]);
+        assert!(issues[0].message.contains("# TODO: remove"));
+    }
+}
@@ -0,0 +1,298 @@
+//! Inline suppression comments for built-in rules
+//!
+//! Markers work in any comment style (`//`, `/* */`, `{/* */}`, `#`, `<!-- -->`):
+//!
+//! - `solodawn-ignore-line <rules> -- <reason>` — the line carrying the comment
+//! - `solodawn-ignore-next-line <rules> -- <reason>` — the next line that is not just another marker comment
+//! - `solodawn-ignore-start <rules> -- <reason>` … `solodawn-ignore-end` — a block of lines
+//! - `solodawn-ignore-file <rules> -- <reason>` — the whole file
+//!
+//! `<rules>` is a comma or space separated list of rule IDs, or `*` for every rule.
+
+use serde::{Deserialize, Serialize};
+
+use crate::issue::QualityIssue;
+
+const MARKER: &str = "solodawn-ignore-";
+
+/// An issue dropped by a suppression comment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuppressedIssue {
+    /// The issue that would have been reported
+    pub issue: QualityIssue,
+    /// Line of the suppression comment
+    pub suppressed_at: u32,
+    /// Reason given after `--`, if any
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    /// Inclusive line range
+    Lines(u32, u32),
+    File,
+}
+
+#[derive(Debug, Clone)]
+struct Suppression {
+    rules: Vec<String>,
+    reason: Option<String>,
+    line: u32,
+    scope: Scope,
+}
+
+impl Suppression {
+    fn matches(&self, issue: &QualityIssue) -> bool {
+        let rule_matches = self
+            .rules
+            .iter()
+            .any(|rule| rule == "*" || *rule == issue.rule_id);
+        let in_scope = match (self.scope, issue.line) {
+            (Scope::File, _) => true,
+            (Scope::Lines(start, end), Some(line)) => (start..=end).contains(&line),
+            (Scope::Lines(..), None) => false,
+        };
+        rule_matches && in_scope
+    }
+}
+
+/// Suppression comments parsed from one source file
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    entries: Vec<Suppression>,
+}
+
+impl Suppressions {
+    /// Parse every suppression marker in `text`
+    pub fn parse(text: &str) -> Self {
+        let mut entries = Vec::new();
+        let mut pending_next_line: Vec<(u32, Vec<String>, Option<String>)> = Vec::new();
+        let mut open_blocks: Vec<(u32, Vec<String>, Option<String>)> = Vec::new();
+
+        for (index, line_text) in text.lines().enumerate() {
+            let line = index as u32 + 1;
+            let marker = parse_marker(line_text);
+            // Markers stack: `next-line` applies to the first line with code on it
+            if marker.is_none() || !is_marker_only(line_text) {
+                for (at, rules, reason) in pending_next_line.drain(..) {
+                    entries.push(Suppression {
+                        rules,
+                        reason,
+                        line: at,
+                        scope: Scope::Lines(line, line),
+                    });
+                }
+            }
+            let Some((directive, rules, reason)) = marker else {
+                continue;
+            };
+
+            match directive {
+                "end" => {
+                    if let Some((at, rules, reason)) = open_blocks.pop() {
+                        entries.push(Suppression {
+                            rules,
+                            reason,
+                            line: at,
+                            scope: Scope::Lines(at, line),
+                        });
+                    }
+                }
+                _ if rules.is_empty() => {}
+                "line" => entries.push(Suppression {
+                    rules,
+                    reason,
+                    line,
+                    scope: Scope::Lines(line, line),
+                }),
+                "next-line" => pending_next_line.push((line, rules, reason)),
+                "start" => open_blocks.push((line, rules, reason)),
+                "file" => entries.push(Suppression {
+                    rules,
+                    reason,
+                    line,
+                    scope: Scope::File,
+                }),
+                _ => {}
+            }
+        }
+
+        // An unterminated block runs to the end of the file
+        for (at, rules, reason) in open_blocks {
+            entries.push(Suppression {
+                rules,
+                reason,
+                line: at,
+                scope: Scope::Lines(at, u32::MAX),
+            });
+        }
+
+        Self { entries }
+    }
+
+    /// Whether the file has no suppression markers
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Split `issues` into reported and suppressed ones
+    pub fn apply(&self, issues: Vec<QualityIssue>) -> (Vec<QualityIssue>, Vec<SuppressedIssue>) {
+        if self.is_empty() {
+            return (issues, Vec::new());
+        }
+
+        let mut kept = Vec::with_capacity(issues.len());
+        let mut suppressed = Vec::new();
+        for issue in issues {
+            match self.entries.iter().find(|s| s.matches(&issue)) {
+                Some(suppression) => suppressed.push(SuppressedIssue {
+                    issue,
+                    suppressed_at: suppression.line,
+                    reason: suppression.reason.clone(),
+                }),
+                None => kept.push(issue),
+            }
+        }
+        (kept, suppressed)
+    }
+}
+
+/// Parse `solodawn-ignore-<directive> <rules> -- <reason>` from a comment on this line.
+fn parse_marker(line: &str) -> Option<(&str, Vec<String>, Option<String>)> {
+    let pos = line.find(MARKER)?;
+    let prefix = line[..pos].trim_end();
+    let in_comment = prefix.ends_with("//")
+        || prefix.ends_with("//!")
+        || prefix.ends_with('*')
+        || prefix.ends_with('#')
+        || prefix.ends_with("<!--");
+    if !in_comment {
+        return None;
+    }
+
+    let rest = &line[pos + MARKER.len()..];
+    let (directive, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let directive = strip_comment_end(directive);
+    let rest = strip_comment_end(rest);
+
+    let (rules, reason) = match rest.split_once("--") {
+        Some((rules, reason)) => (rules, Some(reason.trim())),
+        None => (rest, None),
+    };
+    let rules = rules
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|rule| !rule.is_empty())
+        .map(str::to_string)
+        .collect();
+    let reason = reason.filter(|r| !r.is_empty()).map(str::to_string);
+
+    Some((directive, rules, reason))
+}
+
+/// Whether nothing but comment openers precedes the marker on this line.
+fn is_marker_only(line: &str) -> bool {
+    line.find(MARKER).is_some_and(|pos| {
+        line[..pos]
+            .chars()
+            .all(|c| c.is_whitespace() || matches!(c, '/' | '*' | '!' | '#' | '{' | '<' | '-'))
+    })
+}
+
+/// Drop trailing block-comment closers (`*/`, `*/}`, `-->`).
+fn strip_comment_end(text: &str) -> &str {
+    let mut text = text.trim();
+    loop {
+        let stripped = text
+            .strip_suffix('}')
+            .or_else(|| text.strip_suffix("*/"))
+            .or_else(|| text.strip_suffix("-->"))
+            .map(str::trim_end);
+        match stripped {
+            Some(s) => text = s,
+            None => return text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::{AnalyzerSource, RuleType, Severity};
+
+    fn issue(rule_id: &str, line: Option<u32>) -> QualityIssue {
+        let issue = QualityIssue::new(
+            rule_id,
+            RuleType::CodeSmell,
+            Severity::Major,
+            AnalyzerSource::Other("builtin".to_string()),
+            "problem",
+        );
+        match line {
+            Some(line) => issue.with_location("src/lib.rs", line),
+            None => issue,
+        }
+    }
+
+    #[test]
+    fn next_line_and_same_line_markers() {
+        let text = "\
+// solodawn-ignore-next-line rust:magic-numbers -- protocol constant
+// solodawn-ignore-next-line rust:naming
+let x = 42;
+let key = \"AKIA\"; // solodawn-ignore-line common:secret-detection -- test fixture
+let y = 7;
+// solodawn-ignore-next-line rust:magic-numbers
+let k = 42; // solodawn-ignore-line common:secret-detection
+let z = 9;
+";
+        let suppressions = Suppressions::parse(text);
+        let (kept, suppressed) = suppressions.apply(vec![
+            issue("rust:magic-numbers", Some(3)),
+            issue("rust:naming", Some(3)),
+            issue("common:secret-detection", Some(4)),
+            issue("rust:magic-numbers", Some(5)),
+            issue("rust:magic-numbers", Some(7)),
+            issue("common:secret-detection", Some(7)),
+            issue("rust:magic-numbers", Some(8)),
+        ]);
+
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].line, Some(5));
+        assert_eq!(kept[1].line, Some(8));
+        assert_eq!(suppressed.len(), 5);
+        assert_eq!(suppressed[3].suppressed_at, 6);
+        assert_eq!(suppressed[0].suppressed_at, 1);
+        assert_eq!(suppressed[0].reason.as_deref(), Some("protocol constant"));
+        assert_eq!(suppressed[1].reason, None);
+        assert_eq!(suppressed[2].reason.as_deref(), Some("test fixture"));
+    }
+
+    #[test]
+    fn block_and_file_markers() {
+        let text = "\
+# solodawn-ignore-file common:line-length -- generated
+/* solodawn-ignore-start * -- vendored */
+a
+{/* solodawn-ignore-end */}
+b
+";
+        let suppressions = Suppressions::parse(text);
+        let (kept, suppressed) = suppressions.apply(vec![
+            issue("common:line-length", None),
+            issue("ts:any-usage", Some(3)),
+            issue("ts:any-usage", Some(5)),
+        ]);
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].line, Some(5));
+        assert_eq!(suppressed[0].reason.as_deref(), Some("generated"));
+        assert_eq!(suppressed[1].reason.as_deref(), Some("vendored"));
+    }
+
+    #[test]
+    fn markers_outside_comments_are_ignored() {
+        let text = "let s = \"solodawn-ignore-line *\";\n";
+        let (kept, _) = Suppressions::parse(text).apply(vec![issue("rust:magic-numbers", Some(1))]);
+        assert_eq!(kept.len(), 1);
+    }
+}
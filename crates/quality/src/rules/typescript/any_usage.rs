@@ -1,13 +1,21 @@
 //! Any type usage rule — detects usage of the `any` type in TypeScript code.
 
+use std::collections::HashMap;
+
+use oxc_ast::ast::{
+    TSAnyKeyword, TSArrayType, TSAsExpression, TSType, TSTypeAnnotation, TSTypeAssertion,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::Span;
+
 use crate::issue::QualityIssue;
 use crate::rule::{AnalyzerSource, RuleType, Severity};
 use crate::rules::{Rule, TsAnalysisContext, TsRule};
-use regex::Regex;
 
 /// Detects usage of the `any` type in TypeScript source files.
 ///
-/// Matches the following patterns:
+/// Every `any` type node is reported, wherever it appears; the message names the
+/// surrounding form when it is one of:
 /// - `: any` (type annotation)
 /// - `as any` (type assertion)
 /// - `<any>` (generic type assertion)
@@ -43,35 +51,50 @@ impl Rule for AnyUsageRule {
     }
 }
 
-/// Returns true if the line is a single-line comment or if the relevant portion
-/// appears inside a block comment.
-fn is_comment_line(line: &str, in_block_comment: &mut bool) -> bool {
-    let trimmed = line.trim();
+/// Collects `any` keywords, labelled by the syntax that directly contains them.
+#[derive(Default)]
+struct AnyCollector {
+    found: Vec<Span>,
+    /// `any` span start -> surrounding form, e.g. `as any`
+    labels: HashMap<u32, &'static str>,
+}
 
-    // If we are inside a block comment, check for the end marker.
-    if *in_block_comment {
-        if let Some(_pos) = trimmed.find("*/") {
-            *in_block_comment = false;
+impl AnyCollector {
+    fn label(&mut self, ty: &TSType, label: &'static str) {
+        if let TSType::TSAnyKeyword(any) = ty {
+            self.labels.insert(any.span.start, label);
         }
-        return true;
     }
 
-    // Single-line comment
-    if trimmed.starts_with("//") {
-        return true;
+    fn label_of(&self, span: Span) -> &'static str {
+        self.labels.get(&span.start).copied().unwrap_or("any")
     }
+}
 
-    // Block comment that opens and closes on the same line
-    if trimmed.starts_with("/*") {
-        if trimmed.contains("*/") {
-            // Entire comment on one line
-            return true;
-        }
-        *in_block_comment = true;
-        return true;
+impl<'a> Visit<'a> for AnyCollector {
+    fn visit_ts_any_keyword(&mut self, it: &TSAnyKeyword) {
+        self.found.push(it.span);
+    }
+
+    fn visit_ts_type_annotation(&mut self, it: &TSTypeAnnotation<'a>) {
+        self.label(&it.type_annotation, ": any");
+        walk::walk_ts_type_annotation(self, it);
+    }
+
+    fn visit_ts_as_expression(&mut self, it: &TSAsExpression<'a>) {
+        self.label(&it.type_annotation, "as any");
+        walk::walk_ts_as_expression(self, it);
+    }
+
+    fn visit_ts_type_assertion(&mut self, it: &TSTypeAssertion<'a>) {
+        self.label(&it.type_annotation, "<any>");
+        walk::walk_ts_type_assertion(self, it);
     }
 
-    false
+    fn visit_ts_array_type(&mut self, it: &TSArrayType<'a>) {
+        self.label(&it.element_type, "any[]");
+        walk::walk_ts_array_type(self, it);
+    }
 }
 
 impl TsRule for AnyUsageRule {
@@ -81,37 +104,30 @@ impl TsRule for AnyUsageRule {
             .severity_override
             .unwrap_or_else(|| self.default_severity());
 
-        let pattern = Regex::new(r"(:\s*any\b|as\s+any\b|<any>|any\[\])").expect("valid regex");
+        let mut collector = AnyCollector::default();
+        collector.visit_program(ctx.program);
 
         let mut issues = Vec::new();
-        let mut in_block_comment = false;
-
-        for (idx, line) in ctx.lines.iter().enumerate() {
-            if is_comment_line(line, &mut in_block_comment) {
-                continue;
-            }
-
-            for mat in pattern.find_iter(line) {
-                let matched_text = mat.as_str().trim();
-                let line_number = (idx + 1) as u32;
-
-                let message = format!(
-                    "Avoid using `any` type (found `{}`). Use a more specific type instead.",
-                    matched_text,
-                );
-
-                let issue = QualityIssue::new(
-                    self.id(),
-                    self.rule_type(),
-                    severity,
-                    AnalyzerSource::Other("builtin".to_string()),
-                    message,
-                )
-                .with_location(ctx.file_path, line_number)
-                .with_effort(10);
-
-                issues.push(issue);
-            }
+
+        for span in &collector.found {
+            let line_number = ctx.line_of(*span);
+
+            let message = format!(
+                "Avoid using `any` type (found `{}`). Use a more specific type instead.",
+                collector.label_of(*span),
+            );
+
+            let issue = QualityIssue::new(
+                self.id(),
+                self.rule_type(),
+                severity,
+                AnalyzerSource::Other("builtin".to_string()),
+                message,
+            )
+            .with_location(ctx.file_path, line_number)
+            .with_effort(10);
+
+            issues.push(issue);
         }
 
         issues
@@ -123,17 +139,11 @@ mod tests {
     use super::*;
     use crate::rules::RuleConfig;
 
+    use crate::rules::typescript::analyze_source;
+
     fn run_rule(source: &str) -> Vec<QualityIssue> {
-        let rule = AnyUsageRule;
-        let lines: Vec<&str> = source.lines().collect();
         let config = RuleConfig::default();
-        let ctx = TsAnalysisContext {
-            file_path: "src/example.ts",
-            content: source,
-            lines: &lines,
-            config: &config,
-        };
-        rule.analyze(&ctx)
+        analyze_source(&AnyUsageRule, "src/example.ts", source, &config)
     }
 
     #[test]
@@ -160,6 +170,26 @@ const d: any[] = [];
         assert_eq!(issues[1].line, Some(3));
         assert_eq!(issues[2].line, Some(4));
         assert_eq!(issues[3].line, Some(5));
+        assert!(issues[0].message.contains("found `: any`"));
+        assert!(issues[1].message.contains("found `as any`"));
+        assert!(issues[2].message.contains("found `<any>`"));
+        assert!(issues[3].message.contains("found `any[]`"));
+    }
+
+    #[test]
+    fn detects_any_in_nested_types_but_not_in_strings() {
+        let source = r#"
+const cache: Record<string, any> = {};
+function parse(input: unknown): Promise<Array<any>> {
+    const note = "cast it as any: any[]";
+    return Promise.resolve([]);
+}
+const company = { anyone: 1, many: [] as string[] };
+"#;
+        let issues = run_rule(source);
+        let lines: Vec<_> = issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![Some(2), Some(3)]);
+        assert!(issues[0].message.contains("found `any`"));
     }
 
     #[test]
@@ -1,34 +1,23 @@
 //! Cyclomatic complexity rule for TypeScript/JavaScript functions.
 //!
-//! Uses regex-based analysis to find function boundaries and count decision points.
+//! Walks each function body in the AST and counts decision points. Nested functions are
+//! scored separately.
 
-use regex::Regex;
+use oxc_ast::AstKind;
+use oxc_ast::ast::{ArrowFunctionExpression, Function, LogicalOperator};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::Span;
+use oxc_syntax::scope::ScopeFlags;
 
+use super::FunctionNames;
 use crate::issue::QualityIssue;
 use crate::rule::{RuleType, Severity};
-use crate::rules::{Rule, TsRule, TsAnalysisContext, RuleConfig};
-use super::count_structural_braces;
+use crate::rules::{Rule, RuleConfig, TsAnalysisContext, TsRule};
 
 /// Calculates cyclomatic complexity for TypeScript/JavaScript functions
 /// and reports functions that exceed a configurable threshold.
-#[derive(Debug)]
-pub struct ComplexityRule {
-    fn_pattern: Regex,
-    decision_pattern: Regex,
-}
-
-impl Default for ComplexityRule {
-    fn default() -> Self {
-        Self {
-            fn_pattern: Regex::new(
-                r"(?:function\s+\w+|=>\s*\{|(\w+)\s*\(.*\)\s*\{)"
-            ).expect("invalid function pattern regex"),
-            decision_pattern: Regex::new(
-                r"\b(?:if|else\s+if|for|while|do|case|catch)\b|\&\&|\|\||\?\?"
-            ).expect("invalid decision pattern regex"),
-        }
-    }
-}
+#[derive(Debug, Default)]
+pub struct ComplexityRule;
 
 impl Rule for ComplexityRule {
     fn id(&self) -> &str {
@@ -59,122 +48,93 @@ impl Rule for ComplexityRule {
 impl TsRule for ComplexityRule {
     fn analyze(&self, ctx: &TsAnalysisContext) -> Vec<QualityIssue> {
         let threshold = ctx.config.get_param_usize("threshold", 15);
-        let mut issues = Vec::new();
+        let mut visitor = ComplexityVisitor::default();
+        visitor.visit_program(ctx.program);
 
-        // Find function start lines and their names
-        let mut functions: Vec<(usize, String)> = Vec::new();
-        for (i, line) in ctx.lines.iter().enumerate() {
-            if let Some(m) = self.fn_pattern.find(line) {
-                let matched = m.as_str();
-                let name = extract_function_name(matched, line);
-                functions.push((i, name));
+        let names = FunctionNames::collect(ctx.program);
+        let mut issues = Vec::new();
+        for (span, complexity) in visitor.functions {
+            if complexity > threshold {
+                let issue = QualityIssue::new(
+                    "ts:complexity",
+                    RuleType::CodeSmell,
+                    Severity::Major,
+                    crate::rule::AnalyzerSource::Other("built-in".into()),
+                    format!(
+                        "Function '{}' has a cyclomatic complexity of {} (threshold: {})",
+                        names.get(span),
+                        complexity,
+                        threshold
+                    ),
+                )
+                .with_location(ctx.file_path.to_string(), ctx.line_of(span));
+                issues.push(issue);
             }
         }
 
-        // For each function, find its body boundaries and count decision points
-        for (start_line, fn_name) in &functions {
-            if let Some(end_line) = find_closing_brace(ctx.lines, *start_line) {
-                // Base complexity is 1
-                let mut complexity: usize = 1;
-                for line_idx in *start_line..=end_line {
-                    let line = ctx.lines[line_idx];
-                    complexity += self.decision_pattern.find_iter(line).count();
-                }
+        issues
+    }
+}
 
-                if complexity > threshold {
-                    let issue = QualityIssue::new(
-                        "ts:complexity",
-                        RuleType::CodeSmell,
-                        Severity::Major,
-                        crate::rule::AnalyzerSource::Other("built-in".into()),
-                        format!(
-                            "Function '{}' has a cyclomatic complexity of {} (threshold: {})",
-                            fn_name, complexity, threshold
-                        ),
-                    )
-                    .with_location(ctx.file_path.to_string(), (*start_line as u32) + 1);
-                    issues.push(issue);
-                }
-            }
-        }
+/// Scores every function in source order; `open` indexes the functions being walked.
+#[derive(Default)]
+struct ComplexityVisitor {
+    functions: Vec<(Span, usize)>,
+    open: Vec<usize>,
+}
 
-        issues
+impl ComplexityVisitor {
+    fn score_function(&mut self, span: Span, walk_body: impl FnOnce(&mut Self)) {
+        // Base complexity is 1
+        self.functions.push((span, 1));
+        self.open.push(self.functions.len() - 1);
+        walk_body(self);
+        self.open.pop();
     }
 }
 
-/// Extract a human-readable function name from the matched text and line.
-fn extract_function_name(matched: &str, line: &str) -> String {
-    // "function foo" pattern
-    if matched.starts_with("function") {
-        return matched
-            .strip_prefix("function")
-            .unwrap_or("")
-            .trim()
-            .to_string();
-    }
-
-    // Arrow function: look for identifier before `=>`
-    if matched.contains("=>") {
-        let trimmed = line.trim();
-        // Try to find `const name =` or `let name =` or `name =`
-        let re = Regex::new(r"(?:const|let|var)\s+(\w+)").ok();
-        if let Some(re) = re {
-            if let Some(caps) = re.captures(trimmed) {
-                return caps.get(1).map(|m| m.as_str().to_string()).unwrap_or_else(|| "<arrow>".into());
-            }
+impl<'a> Visit<'a> for ComplexityVisitor {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        let is_decision = match kind {
+            AstKind::IfStatement(_)
+            | AstKind::ConditionalExpression(_)
+            | AstKind::ForStatement(_)
+            | AstKind::ForInStatement(_)
+            | AstKind::ForOfStatement(_)
+            | AstKind::WhileStatement(_)
+            | AstKind::DoWhileStatement(_)
+            | AstKind::CatchClause(_) => true,
+            // `default:` is not a branch of its own
+            AstKind::SwitchCase(case) => case.test.is_some(),
+            AstKind::LogicalExpression(expr) => matches!(
+                expr.operator,
+                LogicalOperator::And | LogicalOperator::Or | LogicalOperator::Coalesce
+            ),
+            _ => false,
+        };
+        if is_decision && let Some(&index) = self.open.last() {
+            self.functions[index].1 += 1;
         }
-        return "<arrow>".into();
     }
 
-    // Method pattern: `name(...)  {`
-    let re = Regex::new(r"(\w+)\s*\(").ok();
-    if let Some(re) = re {
-        if let Some(caps) = re.captures(matched) {
-            return caps.get(1).map(|m| m.as_str().to_string()).unwrap_or_else(|| "<anonymous>".into());
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        if it.body.is_none() {
+            // Overload signatures and `declare function` have nothing to score
+            return;
         }
+        self.score_function(it.span, |v| walk::walk_function(v, it, flags));
     }
 
-    "<anonymous>".into()
-}
-
-/// Find the closing brace that matches the opening brace on or after `start_line`.
-fn find_closing_brace(lines: &[&str], start_line: usize) -> Option<usize> {
-    let mut depth: i32 = 0;
-    let mut found_open = false;
-
-    for (i, line) in lines.iter().enumerate().skip(start_line) {
-        let (opens, closes) = count_structural_braces(line);
-        for _ in 0..opens {
-            depth += 1;
-            found_open = true;
-        }
-        for _ in 0..closes {
-            depth -= 1;
-            if found_open && depth == 0 {
-                return Some(i);
-            }
-        }
+    fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+        self.score_function(it.span, |v| walk::walk_arrow_function_expression(v, it));
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::rules::RuleConfig;
-
-    fn make_context<'a>(
-        content: &'a str,
-        lines: &'a [&'a str],
-        config: &'a RuleConfig,
-    ) -> TsAnalysisContext<'a> {
-        TsAnalysisContext {
-            file_path: "test.ts",
-            content,
-            lines,
-            config,
-        }
-    }
+    use crate::rules::typescript::analyze_source;
 
     #[test]
     fn simple_function_below_threshold_produces_no_issues() {
@@ -186,11 +146,8 @@ function simple(x) {
     return -x;
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let config = RuleConfig::default();
-        let ctx = make_context(src, &lines, &config);
-        let rule = ComplexityRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&ComplexityRule, "test.ts", src, &config);
         assert!(issues.is_empty(), "expected no issues for simple function");
     }
 
@@ -223,16 +180,52 @@ function complex(x) {
     }
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let mut config = RuleConfig::default();
         config.params.insert("threshold".into(), "2".into());
-        let ctx = make_context(src, &lines, &config);
-        let rule = ComplexityRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&ComplexityRule, "test.ts", src, &config);
         assert!(
             !issues.is_empty(),
             "expected at least one issue for complex function"
         );
         assert!(issues[0].message.contains("complex"));
     }
+
+    #[test]
+    fn keywords_in_strings_and_comments_are_not_decisions() {
+        let src = r#"
+const render = (x) => {
+    // if (a && b) { for (;;) {} }
+    const label = "if && || ?? while case";
+    return x ? label : `${label} catch`;
+};
+"#;
+        let mut config = RuleConfig::default();
+        config.params.insert("threshold".into(), "1".into());
+        let issues = analyze_source(&ComplexityRule, "test.ts", src, &config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].message,
+            "Function 'render' has a cyclomatic complexity of 2 (threshold: 1)"
+        );
+        assert_eq!(issues[0].line, Some(2));
+    }
+
+    #[test]
+    fn nested_functions_are_scored_separately() {
+        let src = r#"
+function outer(items) {
+    return items.map((item) => {
+        if (item.a) { return 1; }
+        if (item.b) { return 2; }
+        return 3;
+    });
+}
+"#;
+        let mut config = RuleConfig::default();
+        config.params.insert("threshold".into(), "2".into());
+        let issues = analyze_source(&ComplexityRule, "test.ts", src, &config);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("'<anonymous>'"));
+        assert_eq!(issues[0].line, Some(3));
+    }
 }
@@ -3,11 +3,18 @@
 //! Detects `console.log`, `console.warn`, `console.error`, and other console
 //! methods that should not appear in production code.
 
-use regex::Regex;
+use oxc_ast::ast::{CallExpression, Expression};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::Span;
 
 use crate::issue::QualityIssue;
 use crate::rule::{RuleType, Severity};
-use crate::rules::{Rule, TsRule, TsAnalysisContext, RuleConfig};
+use crate::rules::{Rule, RuleConfig, TsAnalysisContext, TsRule};
+
+/// Console methods that are flagged.
+const CONSOLE_METHODS: &[&str] = &[
+    "log", "warn", "error", "debug", "info", "trace", "dir", "table",
+];
 
 /// Detects `console.*()` calls in production TypeScript/JavaScript code.
 ///
@@ -15,21 +22,8 @@ use crate::rules::{Rule, TsRule, TsAnalysisContext, RuleConfig};
 /// before merging to production. This rule flags calls to `console.log`,
 /// `console.warn`, `console.error`, `console.debug`, `console.info`,
 /// `console.trace`, `console.dir`, and `console.table`.
-#[derive(Debug)]
-pub struct ConsoleUsageRule {
-    console_pattern: Regex,
-}
-
-impl Default for ConsoleUsageRule {
-    fn default() -> Self {
-        Self {
-            console_pattern: Regex::new(
-                r"console\.(?:log|warn|error|debug|info|trace|dir|table)\s*\("
-            )
-            .expect("invalid console pattern regex"),
-        }
-    }
-}
+#[derive(Debug, Default)]
+pub struct ConsoleUsageRule;
 
 impl Rule for ConsoleUsageRule {
     fn id(&self) -> &str {
@@ -62,10 +56,21 @@ fn is_test_file(path: &str) -> bool {
     path.contains("__tests__") || path.contains(".test.") || path.contains(".spec.")
 }
 
-/// Check whether a line is a single-line comment (// or leading /*)
-fn is_comment_line(line: &str) -> bool {
-    let trimmed = line.trim();
-    trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*')
+/// Collects `console.<method>(...)` calls with their method names.
+#[derive(Default)]
+struct ConsoleCalls<'a>(Vec<(Span, &'a str)>);
+
+impl<'a> Visit<'a> for ConsoleCalls<'a> {
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        if let Expression::StaticMemberExpression(member) = &it.callee
+            && let Expression::Identifier(object) = &member.object
+            && object.name == "console"
+            && CONSOLE_METHODS.contains(&member.property.name.as_str())
+        {
+            self.0.push((it.span, member.property.name.as_str()));
+        }
+        walk::walk_call_expression(self, it);
+    }
 }
 
 impl TsRule for ConsoleUsageRule {
@@ -75,24 +80,14 @@ impl TsRule for ConsoleUsageRule {
             return Vec::new();
         }
 
-        let mut issues = Vec::new();
-
-        for (i, line) in ctx.lines.iter().enumerate() {
-            // Skip comment lines
-            if is_comment_line(line) {
-                continue;
-            }
+        let mut calls = ConsoleCalls::default();
+        calls.visit_program(ctx.program);
 
-            for mat in self.console_pattern.find_iter(line) {
-                let matched = mat.as_str();
-                // Extract the method name from "console.<method>("
-                let method = matched
-                    .strip_prefix("console.")
-                    .and_then(|rest| rest.split_once('(').or_else(|| rest.split_once(' ')))
-                    .map(|(name, _)| name.trim())
-                    .unwrap_or("log");
-
-                let issue = QualityIssue::new(
+        calls
+            .0
+            .into_iter()
+            .map(|(span, method)| {
+                QualityIssue::new(
                     "ts:console-usage",
                     RuleType::CodeSmell,
                     Severity::Minor,
@@ -102,14 +97,10 @@ impl TsRule for ConsoleUsageRule {
                         method
                     ),
                 )
-                .with_location(ctx.file_path.to_string(), (i as u32) + 1)
-                .with_effort(1);
-
-                issues.push(issue);
-            }
-        }
-
-        issues
+                .with_location(ctx.file_path.to_string(), ctx.line_of(span))
+                .with_effort(1)
+            })
+            .collect()
     }
 }
 
@@ -117,19 +108,11 @@ impl TsRule for ConsoleUsageRule {
 mod tests {
     use super::*;
     use crate::rules::RuleConfig;
+    use crate::rules::typescript::analyze_source;
 
-    fn make_context<'a>(
-        file_path: &'a str,
-        content: &'a str,
-        lines: &'a [&'a str],
-        config: &'a RuleConfig,
-    ) -> TsAnalysisContext<'a> {
-        TsAnalysisContext {
-            file_path,
-            content,
-            lines,
-            config,
-        }
+    fn analyze(file_path: &str, src: &str) -> Vec<QualityIssue> {
+        let config = RuleConfig::default();
+        analyze_source(&ConsoleUsageRule, file_path, src, &config)
     }
 
     #[test]
@@ -141,11 +124,7 @@ function init() {
     console.error("something failed");
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = make_context("src/app.ts", src, &lines, &config);
-        let rule = ConsoleUsageRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze("src/app.ts", src);
         assert_eq!(issues.len(), 3, "expected 3 console usage issues");
         assert!(issues[0].message.contains("console.log"));
         assert!(issues[1].message.contains("console.warn"));
@@ -155,35 +134,40 @@ function init() {
     #[test]
     fn skips_test_files() {
         let src = "console.log('test output');\n";
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-
-        let ctx = make_context("src/__tests__/app.test.ts", src, &lines, &config);
-        let rule = ConsoleUsageRule::default();
-        assert!(rule.analyze(&ctx).is_empty(), "should skip __tests__ directory");
-
-        let ctx2 = make_context("src/utils.spec.ts", src, &lines, &config);
-        assert!(rule.analyze(&ctx2).is_empty(), "should skip .spec. files");
-
-        let ctx3 = make_context("src/utils.test.ts", src, &lines, &config);
-        assert!(rule.analyze(&ctx3).is_empty(), "should skip .test. files");
+        assert!(
+            analyze("src/__tests__/app.test.ts", src).is_empty(),
+            "should skip __tests__ directory"
+        );
+        assert!(
+            analyze("src/utils.spec.ts", src).is_empty(),
+            "should skip .spec. files"
+        );
+        assert!(
+            analyze("src/utils.test.ts", src).is_empty(),
+            "should skip .test. files"
+        );
     }
 
     #[test]
-    fn skips_comment_lines() {
+    fn skips_comments_and_strings() {
         let src = r#"
 // console.log("commented out");
 /* console.warn("block comment"); */
-* console.error("inside block comment");
+/*
+ * console.error("inside block comment");
+ */
+const hint = "call console.info(x) to debug";
+logger.console.log("not the global console");
 console.debug("this one is real");
 "#;
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = make_context("src/app.ts", src, &lines, &config);
-        let rule = ConsoleUsageRule::default();
-        let issues = rule.analyze(&ctx);
-        assert_eq!(issues.len(), 1, "expected only the non-commented console call");
+        let issues = analyze("src/app.ts", src);
+        assert_eq!(
+            issues.len(),
+            1,
+            "expected only the non-commented console call"
+        );
         assert!(issues[0].message.contains("console.debug"));
+        assert_eq!(issues[0].line, Some(9));
     }
 
     #[test]
@@ -193,11 +177,10 @@ function add(a: number, b: number): number {
     return a + b;
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = make_context("src/math.ts", src, &lines, &config);
-        let rule = ConsoleUsageRule::default();
-        let issues = rule.analyze(&ctx);
-        assert!(issues.is_empty(), "expected no issues for code without console calls");
+        let issues = analyze("src/math.ts", src);
+        assert!(
+            issues.is_empty(),
+            "expected no issues for code without console calls"
+        );
     }
 }
@@ -71,6 +71,7 @@ impl TsRule for FileLengthRule {
 mod tests {
     use super::*;
     use crate::rules::RuleConfig;
+    use crate::rules::typescript::analyze_source;
 
     fn make_content(line_count: usize) -> String {
         (0..line_count)
@@ -85,37 +86,19 @@ mod tests {
 
     #[test]
     fn file_within_limit_produces_no_issues() {
-        let rule = FileLengthRule;
         let content = make_content(100);
-        let lines: Vec<&str> = content.lines().collect();
         let config = default_config();
 
-        let ctx = TsAnalysisContext {
-            file_path: "src/app.ts",
-            content: &content,
-            lines: &lines,
-            config: &config,
-        };
-
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&FileLengthRule, "src/app.ts", &content, &config);
         assert!(issues.is_empty(), "Expected no issues for a short file");
     }
 
     #[test]
     fn file_exceeding_limit_produces_issue() {
-        let rule = FileLengthRule;
         let content = make_content(401);
-        let lines: Vec<&str> = content.lines().collect();
         let config = default_config();
 
-        let ctx = TsAnalysisContext {
-            file_path: "src/large.ts",
-            content: &content,
-            lines: &lines,
-            config: &config,
-        };
-
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&FileLengthRule, "src/large.ts", &content, &config);
         assert_eq!(issues.len(), 1);
         assert_eq!(issues[0].rule_id, "ts:file-length");
         assert_eq!(issues[0].severity, Severity::Minor);
@@ -125,22 +108,13 @@ mod tests {
 
     #[test]
     fn custom_max_lines_is_respected() {
-        let rule = FileLengthRule;
         let content = make_content(250);
-        let lines: Vec<&str> = content.lines().collect();
         let mut config = default_config();
         config
             .params
             .insert("max_lines".to_string(), "200".to_string());
 
-        let ctx = TsAnalysisContext {
-            file_path: "src/medium.tsx",
-            content: &content,
-            lines: &lines,
-            config: &config,
-        };
-
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&FileLengthRule, "src/medium.tsx", &content, &config);
         assert_eq!(issues.len(), 1);
         assert!(issues[0].message.contains("250"));
         assert!(issues[0].message.contains("200"));
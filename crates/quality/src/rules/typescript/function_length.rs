@@ -1,31 +1,20 @@
 //! Function Length rule — flags TypeScript/JavaScript functions that exceed a configurable line limit.
 
-use regex::Regex;
+use oxc_ast::ast::{ArrowFunctionExpression, Function};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::Span;
+use oxc_syntax::scope::ScopeFlags;
 
+use super::FunctionNames;
 use crate::issue::QualityIssue;
 use crate::rule::{RuleType, Severity};
-use crate::rules::{Rule, TsRule, TsAnalysisContext, RuleConfig};
-use super::count_structural_braces;
+use crate::rules::{Rule, RuleConfig, TsAnalysisContext, TsRule};
 
 /// Checks that individual functions do not exceed a maximum number of lines.
 ///
 /// Defaults to 50 lines. Configurable via the `max_lines` parameter.
-pub struct FunctionLengthRule {
-    fn_decl_re: Regex,
-    arrow_fn_re: Regex,
-    method_re: Regex,
-}
-
-impl Default for FunctionLengthRule {
-    fn default() -> Self {
-        Self {
-            fn_decl_re: Regex::new(r"function\s+(\w+)").unwrap(),
-            arrow_fn_re: Regex::new(r"(\w+)\s*=\s*(?:async\s+)?(?:\([^)]*\)|[^=])\s*=>")
-                .unwrap(),
-            method_re: Regex::new(r"(?:async\s+)?(\w+)\s*\([^)]*\)\s*\{").unwrap(),
-        }
-    }
-}
+#[derive(Debug, Default)]
+pub struct FunctionLengthRule;
 
 impl Rule for FunctionLengthRule {
     fn id(&self) -> &str {
@@ -55,98 +44,52 @@ impl Rule for FunctionLengthRule {
     }
 }
 
-/// Information about a detected function declaration.
-struct FunctionInfo {
-    name: String,
-    /// 0-based line index where the function starts.
-    start_line: usize,
+/// Spans of every function that has a body, in source order.
+#[derive(Default)]
+struct FunctionSpans(Vec<Span>);
+
+impl<'a> Visit<'a> for FunctionSpans {
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        // Overload signatures and `declare function` have no body
+        if it.body.is_some() {
+            self.0.push(it.span);
+        }
+        walk::walk_function(self, it, flags);
+    }
+
+    fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+        self.0.push(it.span);
+        walk::walk_arrow_function_expression(self, it);
+    }
 }
 
 impl TsRule for FunctionLengthRule {
     fn analyze(&self, ctx: &TsAnalysisContext) -> Vec<QualityIssue> {
         let max_lines = ctx.config.get_param_usize("max_lines", 50);
-        let mut issues = Vec::new();
-
-        // Collect function start positions (line index, name).
-        let mut functions: Vec<FunctionInfo> = Vec::new();
-
-        for (i, line) in ctx.lines.iter().enumerate() {
-            let trimmed = line.trim();
-
-            // Skip comments and imports
-            if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") || trimmed.starts_with("import ") {
-                continue;
-            }
-
-            // Check for function declarations: `function foo(`
-            if let Some(caps) = self.fn_decl_re.captures(line) {
-                let name = caps.get(1).map_or("anonymous", |m| m.as_str()).to_string();
-                functions.push(FunctionInfo { name, start_line: i });
-                continue;
-            }
-
-            // Check for arrow functions: `const foo = (...) =>`
-            if let Some(caps) = self.arrow_fn_re.captures(line) {
-                let name = caps.get(1).map_or("anonymous", |m| m.as_str()).to_string();
-                // Only treat as top-level arrow if it looks like a const/let/var assignment
-                if trimmed.starts_with("const ")
-                    || trimmed.starts_with("let ")
-                    || trimmed.starts_with("var ")
-                    || trimmed.starts_with("export ")
-                {
-                    functions.push(FunctionInfo { name, start_line: i });
-                    continue;
-                }
-            }
-
-            // Check for method definitions: `methodName(...) {`
-            if let Some(caps) = self.method_re.captures(line) {
-                let name = caps.get(1).map_or("anonymous", |m| m.as_str()).to_string();
-                // Avoid matching control-flow keywords
-                if !matches!(
-                    name.as_str(),
-                    "if" | "else" | "for" | "while" | "switch" | "catch" | "return" | "function"
-                ) {
-                    functions.push(FunctionInfo { name, start_line: i });
-                }
-            }
-        }
+        let mut functions = FunctionSpans::default();
+        functions.visit_program(ctx.program);
 
-        // For each function, track brace depth to find the closing brace, then count lines.
-        for func in &functions {
-            let mut depth: i32 = 0;
-            let mut found_open = false;
-            let mut end_line: Option<usize> = None;
-
-            for (i, line) in ctx.lines.iter().enumerate().skip(func.start_line) {
-                let (opens, closes) = count_structural_braces(line);
-                for _ in 0..opens {
-                    depth += 1;
-                    found_open = true;
-                }
-                depth -= closes as i32;
-                if found_open && depth <= 0 {
-                    end_line = Some(i);
-                    break;
-                }
-            }
-
-            if let Some(end) = end_line {
-                let line_count = end - func.start_line + 1;
-                if line_count > max_lines {
-                    let issue = QualityIssue::new(
-                        self.id(),
-                        self.rule_type(),
-                        self.default_severity(),
-                        crate::rule::AnalyzerSource::TypeScript,
-                        format!(
-                            "Function '{}' has {} lines (maximum allowed is {})",
-                            func.name, line_count, max_lines
-                        ),
-                    )
-                    .with_location(ctx.file_path, (func.start_line + 1) as u32);
-                    issues.push(issue);
-                }
+        let names = FunctionNames::collect(ctx.program);
+        let mut issues = Vec::new();
+        for span in functions.0 {
+            let start_line = ctx.line_of(span);
+            let end_line = ctx.line_of(Span::empty(span.end.saturating_sub(1)));
+            let line_count = (end_line - start_line + 1) as usize;
+            if line_count > max_lines {
+                let issue = QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    self.default_severity(),
+                    crate::rule::AnalyzerSource::TypeScript,
+                    format!(
+                        "Function '{}' has {} lines (maximum allowed is {})",
+                        names.get(span),
+                        line_count,
+                        max_lines
+                    ),
+                )
+                .with_location(ctx.file_path, start_line);
+                issues.push(issue);
             }
         }
 
@@ -158,19 +101,7 @@ impl TsRule for FunctionLengthRule {
 mod tests {
     use super::*;
     use crate::rules::RuleConfig;
-
-    fn make_ctx<'a>(
-        content: &'a str,
-        lines: &'a [&'a str],
-        config: &'a RuleConfig,
-    ) -> TsAnalysisContext<'a> {
-        TsAnalysisContext {
-            file_path: "test.ts",
-            content,
-            lines,
-            config,
-        }
-    }
+    use crate::rules::typescript::analyze_source;
 
     #[test]
     fn short_function_no_issue() {
@@ -179,12 +110,9 @@ mod tests {
     console.log(name);
     return true;
 }"#;
-        let lines: Vec<&str> = src.lines().collect();
         let mut config = RuleConfig::default();
         config.params.insert("max_lines".to_string(), "10".to_string());
-        let rule = FunctionLengthRule::default();
-        let ctx = make_ctx(src, &lines, &config);
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&FunctionLengthRule, "test.ts", src, &config);
         assert!(issues.is_empty(), "Short function should produce no issues");
     }
 
@@ -198,14 +126,11 @@ mod tests {
         }
         body_lines.push("}".to_string());
         let src = body_lines.join("\n");
-        let lines: Vec<&str> = src.lines().collect();
 
         let mut config = RuleConfig::default();
         config.params.insert("max_lines".to_string(), "10".to_string());
 
-        let rule = FunctionLengthRule::default();
-        let ctx = make_ctx(&src, &lines, &config);
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&FunctionLengthRule, "test.ts", &src, &config);
 
         assert_eq!(issues.len(), 1);
         assert!(issues[0].message.contains("longFunction"));
@@ -222,16 +147,37 @@ mod tests {
         }
         body_lines.push("}".to_string());
         let src = body_lines.join("\n");
-        let lines: Vec<&str> = src.lines().collect();
 
         let mut config = RuleConfig::default();
         config.params.insert("max_lines".to_string(), "10".to_string());
 
-        let rule = FunctionLengthRule::default();
-        let ctx = make_ctx(&src, &lines, &config);
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&FunctionLengthRule, "test.ts", &src, &config);
 
         assert_eq!(issues.len(), 1);
         assert!(issues[0].message.contains("handler"));
     }
+
+    #[test]
+    fn methods_and_callbacks_are_measured_separately() {
+        let mut body_lines: Vec<String> = Vec::new();
+        body_lines.push("class Loader {".to_string());
+        body_lines.push("    load(items: string[]) {".to_string());
+        body_lines.push("        items.forEach((item) => {".to_string());
+        for i in 0..12 {
+            body_lines.push(format!("            fetch{}(item);", i));
+        }
+        body_lines.push("        });".to_string());
+        body_lines.push("    }".to_string());
+        body_lines.push("}".to_string());
+        let src = body_lines.join("\n");
+
+        let mut config = RuleConfig::default();
+        config.params.insert("max_lines".to_string(), "14".to_string());
+
+        let issues = analyze_source(&FunctionLengthRule, "test.ts", &src, &config);
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("'load' has 16 lines"));
+        assert_eq!(issues[0].line, Some(2));
+    }
 }
@@ -4,7 +4,7 @@
 use crate::issue::QualityIssue;
 use crate::rule::{AnalyzerSource, RuleType, Severity};
 use crate::rules::{Rule, TsAnalysisContext, TsRule};
-use regex::Regex;
+use oxc_ast::ast::Statement;
 
 /// Node.js built-in module names (without the `node:` prefix).
 const NODE_BUILTINS: &[&str] = &[
@@ -115,31 +115,20 @@ impl TsRule for ImportOrderRule {
             .severity_override
             .unwrap_or_else(|| self.default_severity());
 
-        // Match `import ... from '...'` and side-effect `import '...'`
-        let re_from =
-            Regex::new(r#"import\s+.*\s+from\s+['"]([^'"]+)['"]"#).expect("valid regex");
-        let re_side = Regex::new(r#"import\s+['"]([^'"]+)['"]"#).expect("valid regex");
-
-        // Collect (line_number, group) for each import
-        let mut imports: Vec<(u32, ImportGroup, String)> = Vec::new();
-
-        for (idx, line) in ctx.lines.iter().enumerate() {
-            let trimmed = line.trim();
-            if !trimmed.starts_with("import") {
-                continue;
-            }
-
-            let specifier = re_from
-                .captures(trimmed)
-                .or_else(|| re_side.captures(trimmed))
-                .and_then(|caps| caps.get(1))
-                .map(|m| m.as_str().to_string());
-
-            if let Some(spec) = specifier {
-                let group = classify_import(&spec);
-                imports.push(((idx + 1) as u32, group, spec));
-            }
-        }
+        // Collect (line_number, group) for each top-level import, including
+        // side-effect `import '...'` and `import type`
+        let imports: Vec<(u32, ImportGroup, String)> = ctx
+            .program
+            .body
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::ImportDeclaration(decl) => {
+                    let spec = decl.source.value.to_string();
+                    Some((ctx.line_of(decl.span), classify_import(&spec), spec))
+                }
+                _ => None,
+            })
+            .collect();
 
         let mut issues = Vec::new();
         let mut max_group = None::<ImportGroup>;
@@ -181,17 +170,11 @@ mod tests {
     use super::*;
     use crate::rules::RuleConfig;
 
+    use crate::rules::typescript::analyze_source;
+
     fn run_rule(source: &str) -> Vec<QualityIssue> {
-        let rule = ImportOrderRule;
-        let lines: Vec<&str> = source.lines().collect();
         let config = RuleConfig::default();
-        let ctx = TsAnalysisContext {
-            file_path: "src/example.ts",
-            content: source,
-            lines: &lines,
-            config: &config,
-        };
-        rule.analyze(&ctx)
+        analyze_source(&ImportOrderRule, "src/example.ts", source, &config)
     }
 
     #[test]
@@ -234,6 +217,21 @@ import { util } from '@/utils';
         assert_eq!(issues[0].line, Some(2));
     }
 
+    #[test]
+    fn multiline_and_side_effect_imports_are_classified() {
+        let source = r#"import {
+    foo,
+    bar,
+} from './local';
+import 'reflect-metadata';
+const text = "import x from 'fs'";
+"#;
+        let issues = run_rule(source);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(5));
+        assert!(issues[0].message.contains("`reflect-metadata`"));
+    }
+
     #[test]
     fn rule_metadata_is_correct() {
         let rule = ImportOrderRule;
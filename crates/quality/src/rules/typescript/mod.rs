@@ -1,6 +1,7 @@
 //! Built-in TypeScript/JavaScript quality rules
 //!
-//! Each rule operates on source text with regex-based analysis and produces `QualityIssue` results.
+//! Each file is parsed once with `oxc_parser`; rules walk the resulting AST (see
+//! [`TsAnalysisContext::program`](super::TsAnalysisContext)) and produce `QualityIssue` results.
 
 pub mod any_usage;
 pub mod complexity;
@@ -14,53 +15,244 @@ pub mod react_hooks;
 pub mod todo_comments;
 pub mod type_assertion;
 
+use std::collections::HashMap;
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Expression, Function, MethodDefinition, ObjectPropertyKind, Program, PropertyDefinition,
+    PropertyKey, VariableDeclarator,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_parser::Parser;
+use oxc_span::{SourceType, Span};
+use oxc_syntax::scope::ScopeFlags;
+
 use super::TsRule;
 
-/// Count structural (non-string/template-literal) braces in a line.
+/// Parse a TypeScript/JavaScript source file, picking the dialect (TS/TSX/JS/JSX) from its extension.
+///
+/// The parser recovers from most syntax errors, so only an unrecoverable parse is an error.
+pub fn parse_typescript<'a>(
+    allocator: &'a Allocator,
+    source: &'a str,
+    file_path: &str,
+) -> anyhow::Result<Program<'a>> {
+    let source_type = SourceType::from_path(file_path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let ret = Parser::new(allocator, source, source_type).parse();
+    if ret.panicked {
+        let message = ret
+            .errors
+            .first()
+            .map(|e| e.to_string())
+            .unwrap_or_else(|| "unrecoverable syntax error".to_string());
+        anyhow::bail!(message);
+    }
+    Ok(ret.program)
+}
+
+/// Names of functions that are not named in their own syntax.
 ///
-/// Returns `(opens, closes)` ignoring braces inside `'…'`, `"…"`, and `` `…` `` literals.
-pub fn count_structural_braces(line: &str) -> (usize, usize) {
-    let mut opens: usize = 0;
-    let mut closes: usize = 0;
-    let mut in_single_quote = false;
-    let mut in_double_quote = false;
-    let mut in_template = false;
-    let mut escaped = false;
-
-    for ch in line.chars() {
-        if escaped {
-            escaped = false;
-            continue;
+/// Arrow functions and function expressions take the name of the variable, class field,
+/// object property or method they are assigned to; `const Foo = memo(() => ...)` names the
+/// wrapped function `Foo`.
+pub struct FunctionNames<'a> {
+    names: HashMap<u32, &'a str>,
+}
+
+impl<'a> FunctionNames<'a> {
+    /// Collect function names for a whole program.
+    pub fn collect(program: &Program<'a>) -> Self {
+        let mut collector = FunctionNames {
+            names: HashMap::new(),
+        };
+        collector.visit_program(program);
+        collector
+    }
+
+    /// Name of the function starting at `span`, or `<anonymous>`.
+    pub fn get(&self, span: Span) -> &'a str {
+        self.names
+            .get(&span.start)
+            .copied()
+            .unwrap_or("<anonymous>")
+    }
+
+    fn assign(&mut self, name: &'a str, value: &Expression<'a>) {
+        let value = match value.without_parentheses() {
+            // `memo(() => ...)`, `forwardRef(function (props, ref) { ... })`, `useCallback(...)`
+            Expression::CallExpression(call) => match call.arguments.first() {
+                Some(arg) => match arg.as_expression() {
+                    Some(expr) => expr.without_parentheses(),
+                    None => return,
+                },
+                None => return,
+            },
+            other => other,
+        };
+        match value {
+            Expression::ArrowFunctionExpression(f) => {
+                self.names.entry(f.span.start).or_insert(name);
+            }
+            Expression::FunctionExpression(f) => {
+                self.names.entry(f.span.start).or_insert(name);
+            }
+            _ => {}
         }
-        if ch == '\\' {
-            escaped = true;
-            continue;
+    }
+}
+
+impl<'a> Visit<'a> for FunctionNames<'a> {
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        if let Some(id) = &it.id {
+            self.names.insert(it.span.start, id.name.as_str());
         }
-        match ch {
-            '\'' if !in_double_quote && !in_template => in_single_quote = !in_single_quote,
-            '"' if !in_single_quote && !in_template => in_double_quote = !in_double_quote,
-            '`' if !in_single_quote && !in_double_quote => in_template = !in_template,
-            '{' if !in_single_quote && !in_double_quote && !in_template => opens += 1,
-            '}' if !in_single_quote && !in_double_quote && !in_template => closes += 1,
-            _ => {}
+        walk::walk_function(self, it, flags);
+    }
+
+    fn visit_variable_declarator(&mut self, it: &VariableDeclarator<'a>) {
+        if let (Some(name), Some(init)) = (it.id.get_identifier_name(), &it.init) {
+            self.assign(name.as_str(), init);
+        }
+        walk::walk_variable_declarator(self, it);
+    }
+
+    fn visit_method_definition(&mut self, it: &MethodDefinition<'a>) {
+        if let Some(name) = static_key_name(&it.key) {
+            self.names.insert(it.value.span.start, name);
         }
+        walk::walk_method_definition(self, it);
+    }
+
+    fn visit_property_definition(&mut self, it: &PropertyDefinition<'a>) {
+        if let (Some(name), Some(value)) = (static_key_name(&it.key), &it.value) {
+            self.assign(name, value);
+        }
+        walk::walk_property_definition(self, it);
+    }
+
+    fn visit_object_property_kind(&mut self, it: &ObjectPropertyKind<'a>) {
+        if let ObjectPropertyKind::ObjectProperty(prop) = it
+            && let Some(name) = static_key_name(&prop.key)
+        {
+            self.assign(name, &prop.value);
+        }
+        walk::walk_object_property_kind(self, it);
+    }
+}
+
+/// Identifier or string-literal property key.
+fn static_key_name<'a>(key: &PropertyKey<'a>) -> Option<&'a str> {
+    match key {
+        PropertyKey::StaticIdentifier(ident) => Some(ident.name.as_str()),
+        PropertyKey::StringLiteral(lit) => Some(lit.value.as_str()),
+        _ => None,
     }
-    (opens, closes)
 }
 
 /// Collect all built-in TypeScript/JavaScript rules
 pub fn all_ts_rules() -> Vec<Box<dyn TsRule>> {
     vec![
-        Box::new(complexity::ComplexityRule::default()),
-        Box::new(function_length::FunctionLengthRule::default()),
+        Box::new(complexity::ComplexityRule),
+        Box::new(function_length::FunctionLengthRule),
         Box::new(file_length::FileLengthRule),
-        Box::new(nesting_depth::NestingDepthRule::default()),
+        Box::new(nesting_depth::NestingDepthRule),
         Box::new(any_usage::AnyUsageRule),
-        Box::new(type_assertion::TypeAssertionRule::default()),
-        Box::new(console_usage::ConsoleUsageRule::default()),
-        Box::new(naming::NamingConventionRule::default()),
+        Box::new(type_assertion::TypeAssertionRule),
+        Box::new(console_usage::ConsoleUsageRule),
+        Box::new(naming::NamingConventionRule),
         Box::new(react_hooks::ReactHooksRule),
         Box::new(import_order::ImportOrderRule),
         Box::new(todo_comments::TodoCommentsRule),
     ]
 }
+
+/// Parse `source` as `file_path` and run a single rule over it.
+#[cfg(test)]
+pub(crate) fn analyze_source(
+    rule: &dyn TsRule,
+    file_path: &str,
+    source: &str,
+    config: &super::RuleConfig,
+) -> Vec<crate::issue::QualityIssue> {
+    let allocator = Allocator::default();
+    let program =
+        parse_typescript(&allocator, source, file_path).expect("failed to parse test source");
+    let lines: Vec<&str> = source.lines().collect();
+    let line_starts = super::line_starts(source);
+    let ctx = super::TsAnalysisContext {
+        file_path,
+        content: source,
+        lines: &lines,
+        line_starts: &line_starts,
+        program: &program,
+        config,
+    };
+    rule.analyze(&ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names_of(source: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let program = parse_typescript(&allocator, source, "test.tsx").unwrap();
+        let mut found: Vec<_> = FunctionNames::collect(&program).names.into_iter().collect();
+        found.sort_by_key(|(start, _)| *start);
+        found
+            .into_iter()
+            .map(|(_, name)| name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn names_follow_assignment_targets() {
+        let source = r#"
+function declared() {}
+const arrow = () => {};
+const Wrapped = memo((props) => <div />);
+class Store {
+    load() {}
+    handle = () => {};
+}
+const handlers = { onClick: function () {}, 'on-key': () => {} };
+"#;
+        assert_eq!(
+            names_of(source),
+            vec![
+                "declared", "arrow", "Wrapped", "load", "handle", "onClick", "on-key"
+            ]
+        );
+    }
+
+    #[test]
+    fn line_of_maps_offsets_to_lines() {
+        let source = "const a = 1;\n\nconst b = 2;\n";
+        let allocator = Allocator::default();
+        let program = parse_typescript(&allocator, source, "test.ts").unwrap();
+        let lines: Vec<&str> = source.lines().collect();
+        let line_starts = crate::rules::line_starts(source);
+        let config = crate::rules::RuleConfig::default();
+        let ctx = crate::rules::TsAnalysisContext {
+            file_path: "test.ts",
+            content: source,
+            lines: &lines,
+            line_starts: &line_starts,
+            program: &program,
+            config: &config,
+        };
+        let line = |offset| ctx.line_of(oxc_span::Span::new(offset, offset));
+        assert_eq!(line(0), 1);
+        assert_eq!(line(12), 1);
+        assert_eq!(line(13), 2);
+        assert_eq!(line(14), 3);
+        assert_eq!(line(100), 4);
+    }
+
+    #[test]
+    fn unrecoverable_source_is_an_error() {
+        let allocator = Allocator::default();
+        assert!(parse_typescript(&allocator, "function (", "broken.ts").is_err());
+        assert!(parse_typescript(&allocator, "const x = 1;", "file.unknown").is_err());
+    }
+}
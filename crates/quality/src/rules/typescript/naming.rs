@@ -3,50 +3,26 @@
 //! Checks that functions use camelCase, classes/interfaces/types/enums use PascalCase,
 //! and React components use PascalCase.
 
-use regex::Regex;
+use oxc_ast::ast::{
+    BindingIdentifier, Class, Function, TSEnumDeclaration, TSInterfaceDeclaration,
+    TSTypeAliasDeclaration,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::Span;
+use oxc_syntax::scope::ScopeFlags;
 
 use crate::issue::QualityIssue;
 use crate::rule::{RuleType, Severity};
-use crate::rules::{Rule, TsRule, TsAnalysisContext, RuleConfig};
+use crate::rules::{Rule, RuleConfig, TsAnalysisContext, TsRule};
 
 /// Checks TypeScript/JavaScript naming conventions.
 ///
 /// - Function names must be camelCase
 /// - Class, interface, type alias, and enum names must be PascalCase
 /// - Interface names may optionally be prefixed with `I`
-/// - UPPER_SNAKE_CASE constants are accepted
-/// - React components (PascalCase functions returning JSX) must be PascalCase
-#[derive(Debug)]
-pub struct NamingConventionRule {
-    fn_pattern: Regex,
-    class_pattern: Regex,
-    interface_pattern: Regex,
-    type_pattern: Regex,
-    enum_pattern: Regex,
-    const_upper_pattern: Regex,
-    react_component_pattern: Regex,
-}
-
-impl Default for NamingConventionRule {
-    fn default() -> Self {
-        Self {
-            fn_pattern: Regex::new(r"function\s+(\w+)")
-                .expect("invalid fn_pattern regex"),
-            class_pattern: Regex::new(r"class\s+(\w+)")
-                .expect("invalid class_pattern regex"),
-            interface_pattern: Regex::new(r"interface\s+(\w+)")
-                .expect("invalid interface_pattern regex"),
-            type_pattern: Regex::new(r"\btype\s+(\w+)\s*[=<{]")
-                .expect("invalid type_pattern regex"),
-            enum_pattern: Regex::new(r"enum\s+(\w+)")
-                .expect("invalid enum_pattern regex"),
-            const_upper_pattern: Regex::new(r"const\s+([A-Z_]+)\s*=")
-                .expect("invalid const_upper_pattern regex"),
-            react_component_pattern: Regex::new(r"function\s+([A-Z]\w*)\s*\(")
-                .expect("invalid react_component_pattern regex"),
-        }
-    }
-}
+/// - React components (PascalCase functions in `.tsx`/`.jsx` files) may be PascalCase
+#[derive(Debug, Default)]
+pub struct NamingConventionRule;
 
 /// Returns `true` if `name` is camelCase: starts with a lowercase ASCII letter
 /// and contains no underscores (except for the rare case of a single-word name).
@@ -114,160 +90,117 @@ impl Rule for NamingConventionRule {
     }
 }
 
+/// Kind of declaration a name belongs to.
+#[derive(Debug, Clone, Copy)]
+enum Declared {
+    Function,
+    Class,
+    Interface,
+    TypeAlias,
+    Enum,
+}
+
+/// Declared names in source order.
+#[derive(Default)]
+struct Declarations<'a>(Vec<(Declared, Span, &'a str)>);
+
+impl<'a> Declarations<'a> {
+    fn push(&mut self, kind: Declared, id: &BindingIdentifier<'a>) {
+        self.0.push((kind, id.span, id.name.as_str()));
+    }
+}
+
+impl<'a> Visit<'a> for Declarations<'a> {
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        if let Some(id) = &it.id {
+            self.push(Declared::Function, id);
+        }
+        walk::walk_function(self, it, flags);
+    }
+
+    fn visit_class(&mut self, it: &Class<'a>) {
+        if let Some(id) = &it.id {
+            self.push(Declared::Class, id);
+        }
+        walk::walk_class(self, it);
+    }
+
+    fn visit_ts_interface_declaration(&mut self, it: &TSInterfaceDeclaration<'a>) {
+        self.push(Declared::Interface, &it.id);
+        walk::walk_ts_interface_declaration(self, it);
+    }
+
+    fn visit_ts_type_alias_declaration(&mut self, it: &TSTypeAliasDeclaration<'a>) {
+        self.push(Declared::TypeAlias, &it.id);
+        walk::walk_ts_type_alias_declaration(self, it);
+    }
+
+    fn visit_ts_enum_declaration(&mut self, it: &TSEnumDeclaration<'a>) {
+        self.push(Declared::Enum, &it.id);
+        walk::walk_ts_enum_declaration(self, it);
+    }
+}
+
 impl TsRule for NamingConventionRule {
     fn analyze(&self, ctx: &TsAnalysisContext) -> Vec<QualityIssue> {
-        let mut issues = Vec::new();
         let is_jsx_file = ctx.file_path.ends_with(".tsx") || ctx.file_path.ends_with(".jsx");
 
-        for (i, line) in ctx.lines.iter().enumerate() {
-            let line_number = (i as u32) + 1;
-            let trimmed = line.trim();
-
-            // Skip comments
-            if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
-                continue;
-            }
-
-            // UPPER_SNAKE_CASE constants are OK — check before function pattern
-            if self.const_upper_pattern.is_match(line) {
-                continue;
-            }
-
-            // React component: PascalCase function returning JSX is fine,
-            // but only in .tsx/.jsx files where React components are expected.
-            let is_react_component = is_jsx_file && self.react_component_pattern.is_match(line);
-
-            // Function names should be camelCase (unless it is a React component)
-            if let Some(caps) = self.fn_pattern.captures(line) {
-                let name = caps.get(1).unwrap().as_str();
-                if !is_react_component && !is_camel_case(name) {
-                    issues.push(
-                        QualityIssue::new(
-                            "ts:naming",
-                            RuleType::CodeSmell,
-                            Severity::Minor,
-                            crate::rule::AnalyzerSource::Other("built-in".into()),
-                            format!(
-                                "Function '{}' should use camelCase naming",
-                                name
-                            ),
-                        )
-                        .with_location(ctx.file_path.to_string(), line_number),
-                    );
+        let mut declarations = Declarations::default();
+        declarations.visit_program(ctx.program);
+
+        let mut issues = Vec::new();
+        for (kind, span, name) in declarations.0 {
+            let message = match kind {
+                // React components are PascalCase functions, but only in .tsx/.jsx files
+                Declared::Function
+                    if !(is_camel_case(name) || is_jsx_file && is_pascal_case(name)) =>
+                {
+                    format!("Function '{}' should use camelCase naming", name)
                 }
-            }
-
-            // Class names should be PascalCase
-            if let Some(caps) = self.class_pattern.captures(line) {
-                let name = caps.get(1).unwrap().as_str();
-                if !is_pascal_case(name) {
-                    issues.push(
-                        QualityIssue::new(
-                            "ts:naming",
-                            RuleType::CodeSmell,
-                            Severity::Minor,
-                            crate::rule::AnalyzerSource::Other("built-in".into()),
-                            format!(
-                                "Class '{}' should use PascalCase naming",
-                                name
-                            ),
-                        )
-                        .with_location(ctx.file_path.to_string(), line_number),
-                    );
+                Declared::Class if !is_pascal_case(name) => {
+                    format!("Class '{}' should use PascalCase naming", name)
                 }
-            }
-
-            // Interface names should be PascalCase (optionally prefixed with I)
-            if let Some(caps) = self.interface_pattern.captures(line) {
-                let name = caps.get(1).unwrap().as_str();
-                // Strip leading 'I' prefix for PascalCase check
-                let name_to_check = if name.len() > 1
-                    && name.starts_with('I')
-                    && name.chars().nth(1).unwrap().is_ascii_uppercase()
-                {
-                    &name[1..]
-                } else {
+                Declared::Interface if !is_pascal_case(strip_interface_prefix(name)) => format!(
+                    "Interface '{}' should use PascalCase naming (optionally prefixed with 'I')",
                     name
-                };
-                if !is_pascal_case(name_to_check) {
-                    issues.push(
-                        QualityIssue::new(
-                            "ts:naming",
-                            RuleType::CodeSmell,
-                            Severity::Minor,
-                            crate::rule::AnalyzerSource::Other("built-in".into()),
-                            format!(
-                                "Interface '{}' should use PascalCase naming (optionally prefixed with 'I')",
-                                name
-                            ),
-                        )
-                        .with_location(ctx.file_path.to_string(), line_number),
-                    );
+                ),
+                Declared::TypeAlias if !is_pascal_case(name) => {
+                    format!("Type alias '{}' should use PascalCase naming", name)
                 }
-            }
-
-            // Type alias names should be PascalCase
-            if let Some(caps) = self.type_pattern.captures(line) {
-                let name = caps.get(1).unwrap().as_str();
-                if !is_pascal_case(name) {
-                    issues.push(
-                        QualityIssue::new(
-                            "ts:naming",
-                            RuleType::CodeSmell,
-                            Severity::Minor,
-                            crate::rule::AnalyzerSource::Other("built-in".into()),
-                            format!(
-                                "Type alias '{}' should use PascalCase naming",
-                                name
-                            ),
-                        )
-                        .with_location(ctx.file_path.to_string(), line_number),
-                    );
+                Declared::Enum if !is_pascal_case(name) => {
+                    format!("Enum '{}' should use PascalCase naming", name)
                 }
-            }
-
-            // Enum names should be PascalCase
-            if let Some(caps) = self.enum_pattern.captures(line) {
-                let name = caps.get(1).unwrap().as_str();
-                if !is_pascal_case(name) {
-                    issues.push(
-                        QualityIssue::new(
-                            "ts:naming",
-                            RuleType::CodeSmell,
-                            Severity::Minor,
-                            crate::rule::AnalyzerSource::Other("built-in".into()),
-                            format!(
-                                "Enum '{}' should use PascalCase naming",
-                                name
-                            ),
-                        )
-                        .with_location(ctx.file_path.to_string(), line_number),
-                    );
-                }
-            }
+                _ => continue,
+            };
+            issues.push(
+                QualityIssue::new(
+                    "ts:naming",
+                    RuleType::CodeSmell,
+                    Severity::Minor,
+                    crate::rule::AnalyzerSource::Other("built-in".into()),
+                    message,
+                )
+                .with_location(ctx.file_path.to_string(), ctx.line_of(span)),
+            );
         }
 
         issues
     }
 }
 
+/// `IUserRepository` is checked as `UserRepository`.
+fn strip_interface_prefix(name: &str) -> &str {
+    match name.strip_prefix('I') {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_uppercase()) => rest,
+        _ => name,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::rules::RuleConfig;
-
-    fn make_context<'a>(
-        content: &'a str,
-        lines: &'a [&'a str],
-        config: &'a RuleConfig,
-    ) -> TsAnalysisContext<'a> {
-        TsAnalysisContext {
-            file_path: "test.ts",
-            content,
-            lines,
-            config,
-        }
-    }
+    use crate::rules::typescript::analyze_source;
 
     #[test]
     fn valid_names_produce_no_issues() {
@@ -294,11 +227,8 @@ enum Color {
 
 const MAX_RETRIES = 5;
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let config = RuleConfig::default();
-        let ctx = make_context(src, &lines, &config);
-        let rule = NamingConventionRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&NamingConventionRule, "test.ts", src, &config);
         assert!(
             issues.is_empty(),
             "expected no issues for valid naming, got: {:?}",
@@ -325,16 +255,14 @@ enum status_code {
     Ok,
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let config = RuleConfig::default();
-        let ctx = make_context(src, &lines, &config);
-        let rule = NamingConventionRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&NamingConventionRule, "test.ts", src, &config);
         // We expect issues for: GetData (function not camelCase), user_service (class),
         // bad_interface (interface), my_type (type alias), status_code (enum)
-        assert!(
-            issues.len() >= 5,
-            "expected at least 5 issues for invalid naming, got {}: {:?}",
+        assert_eq!(
+            issues.len(),
+            5,
+            "expected 5 issues for invalid naming, got {}: {:?}",
             issues.len(),
             issues.iter().map(|i| &i.message).collect::<Vec<_>>()
         );
@@ -347,16 +275,8 @@ function MyComponent(props) {
     return <div />;
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let config = RuleConfig::default();
-        let ctx = TsAnalysisContext {
-            file_path: "Component.tsx",
-            content: src,
-            lines: &lines,
-            config: &config,
-        };
-        let rule = NamingConventionRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&NamingConventionRule, "Component.tsx", src, &config);
         assert!(
             issues.is_empty(),
             "expected no issues for PascalCase React component, got: {:?}",
@@ -372,11 +292,8 @@ if (typeof value === "string") {}
 const t = typeof obj !== "undefined";
 type MyAlias = string;
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let config = RuleConfig::default();
-        let ctx = make_context(src, &lines, &config);
-        let rule = NamingConventionRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&NamingConventionRule, "test.ts", src, &config);
         // Only the type alias `MyAlias` should be checked (and pass), `typeof` should not match
         assert!(
             issues.is_empty(),
@@ -385,6 +302,23 @@ type MyAlias = string;
         );
     }
 
+    #[test]
+    fn keywords_in_strings_and_comments_are_not_declarations() {
+        let src = r#"
+// function Bad_Name() {}
+const doc = "class user_service implements bad_interface";
+const build = function make_thing() {};
+"#;
+        let config = RuleConfig::default();
+        let issues = analyze_source(&NamingConventionRule, "test.ts", src, &config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].message,
+            "Function 'make_thing' should use camelCase naming"
+        );
+        assert_eq!(issues[0].line, Some(4));
+    }
+
     #[test]
     fn helper_is_camel_case() {
         assert!(is_camel_case("getData"));
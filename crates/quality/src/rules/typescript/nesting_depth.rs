@@ -2,30 +2,23 @@
 //!
 //! Checks that the nesting depth of control-flow structures does not exceed a configurable
 //! maximum (default: 4). Deeply nested code is hard to read and maintain.
+//! Depth is measured per function; `else if` continues its chain at the same depth.
 
-use regex::Regex;
+use std::collections::BTreeMap;
+
+use oxc_ast::AstKind;
+use oxc_ast::ast::{ArrowFunctionExpression, Function, IfStatement, Statement};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::Span;
+use oxc_syntax::scope::ScopeFlags;
 
 use crate::issue::QualityIssue;
 use crate::rule::{RuleType, Severity};
-use crate::rules::{Rule, TsRule, TsAnalysisContext, RuleConfig};
-use super::count_structural_braces;
-
-/// Reports lines where control-flow nesting depth exceeds a configurable maximum.
-#[derive(Debug)]
-pub struct NestingDepthRule {
-    /// Pattern that detects the start of a nesting construct.
-    nesting_pattern: Regex,
-}
+use crate::rules::{Rule, RuleConfig, TsAnalysisContext, TsRule};
 
-impl Default for NestingDepthRule {
-    fn default() -> Self {
-        Self {
-            nesting_pattern: Regex::new(
-                r"\b(?:if\s*\(|else\s*\{|for\s*\(|while\s*\(|switch\s*\(|try\s*\{|catch\s*\()"
-            ).expect("invalid nesting pattern regex"),
-        }
-    }
-}
+/// Reports control-flow statements whose nesting depth exceeds a configurable maximum.
+#[derive(Debug, Default)]
+pub struct NestingDepthRule;
 
 impl Rule for NestingDepthRule {
     fn id(&self) -> &str {
@@ -56,51 +49,113 @@ impl Rule for NestingDepthRule {
 impl TsRule for NestingDepthRule {
     fn analyze(&self, ctx: &TsAnalysisContext) -> Vec<QualityIssue> {
         let max_depth = ctx.config.get_param_usize("max_depth", 4);
-        let mut issues = Vec::new();
-        let mut depth: usize = 0;
-        // Track which lines already had an issue reported so we report at most once per line.
-        let mut reported = std::collections::HashSet::new();
+        let mut visitor = NestingVisitor {
+            max_depth,
+            depth: 0,
+            too_deep: Vec::new(),
+        };
+        visitor.visit_program(ctx.program);
 
-        for (i, line) in ctx.lines.iter().enumerate() {
-            let trimmed = line.trim();
+        // Report at most once per line, with the deepest level seen there.
+        let mut per_line = BTreeMap::new();
+        for (span, depth) in visitor.too_deep {
+            let line = per_line.entry(ctx.line_of(span)).or_insert(depth);
+            *line = (*line).max(depth);
+        }
 
-            // Skip blank lines and pure comment lines.
-            if trimmed.is_empty() || trimmed.starts_with("//") || trimmed.starts_with('*') {
-                continue;
-            }
+        per_line
+            .into_iter()
+            .map(|(line_number, depth)| {
+                QualityIssue::new(
+                    "ts:nesting-depth",
+                    RuleType::CodeSmell,
+                    Severity::Major,
+                    crate::rule::AnalyzerSource::Other("built-in".into()),
+                    format!(
+                        "Nesting depth {} exceeds maximum allowed depth of {} at line {}",
+                        depth, max_depth, line_number
+                    ),
+                )
+                .with_location(ctx.file_path.to_string(), line_number)
+            })
+            .collect()
+    }
+}
 
-            // Determine if this line contains a nesting keyword.
-            let is_nesting_line = self.nesting_pattern.is_match(trimmed);
-
-            // Process braces to track depth changes (ignoring braces inside strings/templates).
-            let (opens, closes) = count_structural_braces(trimmed);
-            for _ in 0..opens {
-                depth += 1;
-                // Check right after incrementing — if this brace is from a nesting
-                // construct and we just exceeded the max, report it.
-                if is_nesting_line && depth > max_depth && !reported.contains(&i) {
-                    let line_number = (i as u32) + 1;
-                    let issue = QualityIssue::new(
-                        "ts:nesting-depth",
-                        RuleType::CodeSmell,
-                        Severity::Major,
-                        crate::rule::AnalyzerSource::Other("built-in".into()),
-                        format!(
-                            "Nesting depth {} exceeds maximum allowed depth of {} at line {}",
-                            depth, max_depth, line_number
-                        ),
-                    )
-                    .with_location(ctx.file_path.to_string(), line_number);
-                    issues.push(issue);
-                    reported.insert(i);
-                }
-            }
-            for _ in 0..closes {
-                depth = depth.saturating_sub(1);
+struct NestingVisitor {
+    max_depth: usize,
+    /// Control-flow depth inside the current function
+    depth: usize,
+    too_deep: Vec<(Span, usize)>,
+}
+
+impl NestingVisitor {
+    fn enter(&mut self, span: Span) {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            self.too_deep.push((span, self.depth));
+        }
+    }
+
+    /// Nested functions start again from depth 0.
+    fn in_function(&mut self, walk_body: impl FnOnce(&mut Self)) {
+        let outer = std::mem::take(&mut self.depth);
+        walk_body(self);
+        self.depth = outer;
+    }
+}
+
+impl<'a> Visit<'a> for NestingVisitor {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        match kind {
+            AstKind::ForStatement(s) => self.enter(s.span),
+            AstKind::ForInStatement(s) => self.enter(s.span),
+            AstKind::ForOfStatement(s) => self.enter(s.span),
+            AstKind::WhileStatement(s) => self.enter(s.span),
+            AstKind::DoWhileStatement(s) => self.enter(s.span),
+            AstKind::SwitchStatement(s) => self.enter(s.span),
+            AstKind::TryStatement(s) => self.enter(s.span),
+            _ => {}
+        }
+    }
+
+    fn leave_node(&mut self, kind: AstKind<'a>) {
+        if matches!(
+            kind,
+            AstKind::ForStatement(_)
+                | AstKind::ForInStatement(_)
+                | AstKind::ForOfStatement(_)
+                | AstKind::WhileStatement(_)
+                | AstKind::DoWhileStatement(_)
+                | AstKind::SwitchStatement(_)
+                | AstKind::TryStatement(_)
+        ) {
+            self.depth -= 1;
+        }
+    }
+
+    fn visit_if_statement(&mut self, it: &IfStatement<'a>) {
+        self.enter(it.span);
+        self.visit_expression(&it.test);
+        self.visit_statement(&it.consequent);
+        self.depth -= 1;
+        match &it.alternate {
+            Some(Statement::IfStatement(else_if)) => self.visit_if_statement(else_if),
+            Some(alternate) => {
+                self.depth += 1;
+                self.visit_statement(alternate);
+                self.depth -= 1;
             }
+            None => {}
         }
+    }
+
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        self.in_function(|v| walk::walk_function(v, it, flags));
+    }
 
-        issues
+    fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+        self.in_function(|v| walk::walk_arrow_function_expression(v, it));
     }
 }
 
@@ -108,19 +163,7 @@ impl TsRule for NestingDepthRule {
 mod tests {
     use super::*;
     use crate::rules::RuleConfig;
-
-    fn make_context<'a>(
-        content: &'a str,
-        lines: &'a [&'a str],
-        config: &'a RuleConfig,
-    ) -> TsAnalysisContext<'a> {
-        TsAnalysisContext {
-            file_path: "test.ts",
-            content,
-            lines,
-            config,
-        }
-    }
+    use crate::rules::typescript::analyze_source;
 
     #[test]
     fn shallow_nesting_produces_no_issues() {
@@ -133,11 +176,8 @@ function foo(x: number) {
     }
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let config = RuleConfig::default();
-        let ctx = make_context(src, &lines, &config);
-        let rule = NestingDepthRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&NestingDepthRule, "test.ts", src, &config);
         assert!(
             issues.is_empty(),
             "expected no issues for shallow nesting, got {}: {:?}",
@@ -160,12 +200,9 @@ function deep() {
     }
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let mut config = RuleConfig::default();
         config.params.insert("max_depth".into(), "2".into());
-        let ctx = make_context(src, &lines, &config);
-        let rule = NestingDepthRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&NestingDepthRule, "test.ts", src, &config);
         assert!(
             !issues.is_empty(),
             "expected at least one nesting depth issue"
@@ -195,15 +232,67 @@ function deep() {
     }
 }
 "#;
-        let lines: Vec<&str> = src.lines().collect();
         let mut config = RuleConfig::default();
         config.params.insert("max_depth".into(), "20".into());
-        let ctx = make_context(src, &lines, &config);
-        let rule = NestingDepthRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze_source(&NestingDepthRule, "test.ts", src, &config);
         assert!(
             issues.is_empty(),
             "expected no issues with high max_depth threshold"
         );
     }
+
+    #[test]
+    fn else_if_chains_and_callbacks_do_not_deepen() {
+        let src = r#"
+function dispatch(kind: string, items: number[]) {
+    if (kind === "a") {
+        return 1;
+    } else if (kind === "b") {
+        return 2;
+    } else if (kind === "c") {
+        items.forEach((item) => {
+            if (item > 0) {
+                console.log(item);
+            }
+        });
+    } else {
+        return 4;
+    }
+}
+"#;
+        let mut config = RuleConfig::default();
+        config.params.insert("max_depth".into(), "1".into());
+        let issues = analyze_source(&NestingDepthRule, "test.ts", src, &config);
+        assert!(
+            issues.is_empty(),
+            "expected no issues, got: {:?}",
+            issues.iter().map(|i| &i.message).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn reports_the_statement_that_is_too_deep() {
+        let src = r#"
+function load(paths: string[]) {
+    for (const path of paths) {
+        try {
+            while (pending(path)) {
+                switch (state(path)) {
+                    case 1:
+                        break;
+                }
+            }
+        } catch (e) {
+            retry(path);
+        }
+    }
+}
+"#;
+        let mut config = RuleConfig::default();
+        config.params.insert("max_depth".into(), "3".into());
+        let issues = analyze_source(&NestingDepthRule, "test.ts", src, &config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(6));
+        assert!(issues[0].message.starts_with("Nesting depth 4 exceeds"));
+    }
 }
@@ -1,15 +1,27 @@
 //! React Hooks usage rules for TypeScript/JSX files.
 //!
 //! Detects violations of the Rules of Hooks:
-//! - Hooks called inside conditionals (if/else/for/while)
+//! - Hooks called inside conditionals (`if`/`else`, `switch`, `?:`, `&&`/`||`/`??`)
 //! - Hooks called inside loops
 //! - Hooks called after early returns
+//! - Hooks called inside callbacks nested in a component or custom hook
+//!
+//! Every function is its own scope: a hook called inside a callback is judged against the
+//! callback, not against the conditionals of the component around it.
+
+use oxc_ast::ast::{
+    ArrowFunctionExpression, CallExpression, ConditionalExpression, DoWhileStatement, Expression,
+    ForInStatement, ForOfStatement, ForStatement, Function, IfStatement, LogicalExpression,
+    ReturnStatement, SwitchStatement, WhileStatement,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::Span;
+use oxc_syntax::scope::ScopeFlags;
 
+use super::FunctionNames;
 use crate::issue::QualityIssue;
 use crate::rule::{RuleType, Severity};
-use crate::rules::{Rule, TsRule, TsAnalysisContext};
-use super::count_structural_braces;
-use regex::Regex;
+use crate::rules::{Rule, TsAnalysisContext, TsRule};
 
 /// Built-in rule that checks React hooks usage rules in TypeScript/JSX files.
 #[derive(Debug, Default)]
@@ -26,7 +38,7 @@ impl Rule for ReactHooksRule {
 
     fn description(&self) -> &str {
         "Checks that React hooks are not called conditionally, inside loops, \
-         or after early returns. Hooks must be called in the same order on every render."
+         after early returns, or inside nested callbacks. Hooks must be called in the same order on every render."
     }
 
     fn rule_type(&self) -> RuleType {
@@ -50,153 +62,255 @@ impl TsRule for ReactHooksRule {
             .severity_override
             .unwrap_or_else(|| self.default_severity());
 
-        let hook_re = Regex::new(r"use[A-Z]\w*\(").expect("invalid hook regex");
-        let conditional_kw_re =
-            Regex::new(r"\b(if|else\s+if|else)\b").expect("invalid conditional keyword regex");
-        let loop_kw_re =
-            Regex::new(r"\b(for|while)\s*\(").expect("invalid loop keyword regex");
-        let return_re = Regex::new(r"\breturn\b").expect("invalid return regex");
-
-        let mut issues = Vec::new();
-
-        // Track state while scanning lines
-        let mut conditional_depth: i32 = 0; // depth of braces inside conditionals
-        let mut in_conditional_header = false;
-        let mut loop_depth: i32 = 0;
-        let mut in_loop_header = false;
-        let mut had_early_return = false;
-        let mut brace_depth: i32 = 0;
-        // Track the brace depth at which we entered a conditional or loop
-        let mut conditional_start_depths: Vec<i32> = Vec::new();
-        let mut loop_start_depths: Vec<i32> = Vec::new();
-        // Component/function scope depth to reset early return tracking
-        let mut function_scope_depth: Option<i32> = None;
-
-        for (line_idx, line) in ctx.lines.iter().enumerate() {
-            let line_num = (line_idx + 1) as u32;
-            let trimmed = line.trim();
-
-            // Skip comments and empty lines
-            if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.is_empty() {
-                continue;
+        let mut visitor = HooksVisitor {
+            names: FunctionNames::collect(ctx.program),
+            scopes: vec![Scope::new(ScopeKind::Module)],
+            violations: Vec::new(),
+        };
+        visitor.visit_program(ctx.program);
+
+        visitor
+            .violations
+            .into_iter()
+            .map(|(span, hook_name, violation)| {
+                let message = match violation {
+                    Violation::Conditional => format!(
+                        "React hook `{}` is called inside a conditional block. \
+                         Hooks must be called in the same order on every render.",
+                        hook_name
+                    ),
+                    Violation::Loop => format!(
+                        "React hook `{}` is called inside a loop. \
+                         Hooks must be called in the same order on every render.",
+                        hook_name
+                    ),
+                    Violation::EarlyReturn => format!(
+                        "React hook `{}` is called after an early return. \
+                         Hooks must be called in the same order on every render.",
+                        hook_name
+                    ),
+                    Violation::NestedFunction => format!(
+                        "React hook `{}` is called inside a nested function. \
+                         Hooks must be called at the top level of a component or custom hook.",
+                        hook_name
+                    ),
+                };
+                QualityIssue::new(
+                    self.id(),
+                    self.rule_type(),
+                    severity,
+                    crate::rule::AnalyzerSource::Other("built-in".to_string()),
+                    message,
+                )
+                .with_location(ctx.file_path, ctx.line_of(span))
+                .with_effort(15)
+            })
+            .collect()
+    }
+}
+
+/// `useState`, `useMyThing` — but not `use` or `user`.
+fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_uppercase())
+}
+
+fn is_component_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+/// Hook name of a call such as `useState(0)` or `React.useState(0)`.
+fn hook_callee<'a>(call: &CallExpression<'a>) -> Option<&'a str> {
+    let name = match &call.callee {
+        Expression::Identifier(ident) => ident.name.as_str(),
+        Expression::StaticMemberExpression(member) => match &member.object {
+            Expression::Identifier(object) if is_component_name(&object.name) => {
+                member.property.name.as_str()
             }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    is_hook_name(name).then_some(name)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScopeKind {
+    /// Top-level module code
+    Module,
+    /// A function that is not nested inside a component or custom hook
+    Function,
+    /// A component or custom hook body
+    Render,
+    /// A function nested inside a component or custom hook
+    Callback,
+}
 
-            // Detect function/component boundaries to scope early return tracking
-            if (trimmed.contains("function ") || trimmed.contains("=> {") || trimmed.contains("=>{"))
-                && function_scope_depth.is_none() {
-                    function_scope_depth = Some(brace_depth);
-                    had_early_return = false;
-                }
+struct Scope {
+    kind: ScopeKind,
+    conditionals: usize,
+    loops: usize,
+    returned_early: bool,
+}
 
-            // Detect conditional keywords
-            if conditional_kw_re.is_match(trimmed) && !trimmed.starts_with("//") {
-                in_conditional_header = true;
+impl Scope {
+    fn new(kind: ScopeKind) -> Self {
+        Self {
+            kind,
+            conditionals: 0,
+            loops: 0,
+            returned_early: false,
+        }
+    }
+}
+
+enum Violation {
+    Conditional,
+    Loop,
+    EarlyReturn,
+    NestedFunction,
+}
+
+struct HooksVisitor<'a> {
+    names: FunctionNames<'a>,
+    /// Innermost scope last; the module scope is never popped.
+    scopes: Vec<Scope>,
+    violations: Vec<(Span, &'a str, Violation)>,
+}
+
+impl HooksVisitor<'_> {
+    fn scope(&mut self) -> &mut Scope {
+        self.scopes
+            .last_mut()
+            .expect("module scope is always present")
+    }
+
+    fn in_function(&mut self, span: Span, walk_body: impl FnOnce(&mut Self)) {
+        let inside_render = self
+            .scopes
+            .iter()
+            .any(|s| matches!(s.kind, ScopeKind::Render | ScopeKind::Callback));
+        let name = self.names.get(span);
+        let kind = if inside_render {
+            ScopeKind::Callback
+        } else if is_component_name(name) || is_hook_name(name) {
+            ScopeKind::Render
+        } else {
+            ScopeKind::Function
+        };
+        self.scopes.push(Scope::new(kind));
+        walk_body(self);
+        self.scopes.pop();
+    }
+
+    fn conditionally(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.scope().conditionals += 1;
+        visit(self);
+        self.scope().conditionals -= 1;
+    }
+
+    fn repeatedly(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.scope().loops += 1;
+        visit(self);
+        self.scope().loops -= 1;
+    }
+}
+
+impl<'a> Visit<'a> for HooksVisitor<'a> {
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        self.in_function(it.span, |v| walk::walk_function(v, it, flags));
+    }
+
+    fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+        self.in_function(it.span, |v| walk::walk_arrow_function_expression(v, it));
+    }
+
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        if let Some(hook_name) = hook_callee(it) {
+            let scope = self.scope();
+            let violation = match scope.kind {
+                ScopeKind::Module => None,
+                ScopeKind::Callback => Some(Violation::NestedFunction),
+                _ if scope.conditionals > 0 => Some(Violation::Conditional),
+                _ if scope.loops > 0 => Some(Violation::Loop),
+                _ if scope.returned_early => Some(Violation::EarlyReturn),
+                _ => None,
+            };
+            if let Some(violation) = violation {
+                self.violations.push((it.span, hook_name, violation));
             }
+        }
+        walk::walk_call_expression(self, it);
+    }
 
-            // Detect loop keywords
-            if loop_kw_re.is_match(trimmed) && !trimmed.starts_with("//") {
-                in_loop_header = true;
+    fn visit_return_statement(&mut self, it: &ReturnStatement<'a>) {
+        walk::walk_return_statement(self, it);
+        let scope = self.scope();
+        if scope.conditionals > 0 || scope.loops > 0 {
+            scope.returned_early = true;
+        }
+    }
+
+    fn visit_if_statement(&mut self, it: &IfStatement<'a>) {
+        self.visit_expression(&it.test);
+        self.conditionally(|v| {
+            v.visit_statement(&it.consequent);
+            if let Some(alternate) = &it.alternate {
+                v.visit_statement(alternate);
             }
+        });
+    }
+
+    fn visit_switch_statement(&mut self, it: &SwitchStatement<'a>) {
+        self.visit_expression(&it.discriminant);
+        self.conditionally(|v| v.visit_switch_cases(&it.cases));
+    }
 
-            // Count braces to track depth (ignoring braces inside strings/templates)
-            let (opens, closes) = count_structural_braces(trimmed);
-            for _ in 0..opens {
-                brace_depth += 1;
-                if in_conditional_header {
-                    in_conditional_header = false;
-                    conditional_depth += 1;
-                    conditional_start_depths.push(brace_depth);
-                }
-                if in_loop_header {
-                    in_loop_header = false;
-                    loop_depth += 1;
-                    loop_start_depths.push(brace_depth);
-                }
+    fn visit_conditional_expression(&mut self, it: &ConditionalExpression<'a>) {
+        self.visit_expression(&it.test);
+        self.conditionally(|v| {
+            v.visit_expression(&it.consequent);
+            v.visit_expression(&it.alternate);
+        });
+    }
+
+    fn visit_logical_expression(&mut self, it: &LogicalExpression<'a>) {
+        self.visit_expression(&it.left);
+        self.conditionally(|v| v.visit_expression(&it.right));
+    }
+
+    fn visit_for_statement(&mut self, it: &ForStatement<'a>) {
+        if let Some(init) = &it.init {
+            self.visit_for_statement_init(init);
+        }
+        self.repeatedly(|v| {
+            if let Some(test) = &it.test {
+                v.visit_expression(test);
             }
-            for _ in 0..closes {
-                // Check if we're leaving a conditional block
-                if let Some(&start) = conditional_start_depths.last() {
-                    if brace_depth == start {
-                        conditional_start_depths.pop();
-                        conditional_depth -= 1;
-                    }
-                }
-                // Check if we're leaving a loop block
-                if let Some(&start) = loop_start_depths.last() {
-                    if brace_depth == start {
-                        loop_start_depths.pop();
-                        loop_depth -= 1;
-                    }
-                }
-                // Check if we're leaving the function scope
-                if let Some(fn_depth) = function_scope_depth {
-                    if brace_depth == fn_depth + 1 {
-                        function_scope_depth = None;
-                        had_early_return = false;
-                    }
-                }
-                brace_depth -= 1;
+            if let Some(update) = &it.update {
+                v.visit_expression(update);
             }
+            v.visit_statement(&it.body);
+        });
+    }
 
-            // Detect early returns (return statements not at the end of a function)
-            if return_re.is_match(trimmed) && conditional_depth > 0 {
-                had_early_return = true;
-            }
+    fn visit_for_in_statement(&mut self, it: &ForInStatement<'a>) {
+        self.visit_expression(&it.right);
+        self.repeatedly(|v| v.visit_statement(&it.body));
+    }
 
-            // Check for hook calls
-            if let Some(mat) = hook_re.find(trimmed) {
-                let hook_name = &trimmed[mat.start()..mat.end() - 1]; // exclude '('
-
-                if conditional_depth > 0 {
-                    let issue = QualityIssue::new(
-                        self.id(),
-                        self.rule_type(),
-                        severity,
-                        crate::rule::AnalyzerSource::Other("built-in".to_string()),
-                        format!(
-                            "React hook `{}` is called inside a conditional block. \
-                             Hooks must be called in the same order on every render.",
-                            hook_name
-                        ),
-                    )
-                    .with_location(ctx.file_path, line_num)
-                    .with_effort(15);
-                    issues.push(issue);
-                } else if loop_depth > 0 {
-                    let issue = QualityIssue::new(
-                        self.id(),
-                        self.rule_type(),
-                        severity,
-                        crate::rule::AnalyzerSource::Other("built-in".to_string()),
-                        format!(
-                            "React hook `{}` is called inside a loop. \
-                             Hooks must be called in the same order on every render.",
-                            hook_name
-                        ),
-                    )
-                    .with_location(ctx.file_path, line_num)
-                    .with_effort(15);
-                    issues.push(issue);
-                } else if had_early_return {
-                    let issue = QualityIssue::new(
-                        self.id(),
-                        self.rule_type(),
-                        severity,
-                        crate::rule::AnalyzerSource::Other("built-in".to_string()),
-                        format!(
-                            "React hook `{}` is called after an early return. \
-                             Hooks must be called in the same order on every render.",
-                            hook_name
-                        ),
-                    )
-                    .with_location(ctx.file_path, line_num)
-                    .with_effort(15);
-                    issues.push(issue);
-                }
-            }
-        }
+    fn visit_for_of_statement(&mut self, it: &ForOfStatement<'a>) {
+        self.visit_expression(&it.right);
+        self.repeatedly(|v| v.visit_statement(&it.body));
+    }
+
+    fn visit_while_statement(&mut self, it: &WhileStatement<'a>) {
+        self.repeatedly(|v| walk::walk_while_statement(v, it));
+    }
 
-        issues
+    fn visit_do_while_statement(&mut self, it: &DoWhileStatement<'a>) {
+        self.repeatedly(|v| walk::walk_do_while_statement(v, it));
     }
 }
 
@@ -205,30 +319,15 @@ mod tests {
     use super::*;
     use crate::rules::RuleConfig;
 
+    use crate::rules::typescript::analyze_source;
+
     fn analyze_tsx(source: &str) -> Vec<QualityIssue> {
-        let lines: Vec<&str> = source.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = TsAnalysisContext {
-            file_path: "component.tsx",
-            content: source,
-            lines: &lines,
-            config: &config,
-        };
-        let rule = ReactHooksRule;
-        rule.analyze(&ctx)
+        analyze_with_path(source, "component.tsx")
     }
 
     fn analyze_with_path(source: &str, path: &str) -> Vec<QualityIssue> {
-        let lines: Vec<&str> = source.lines().collect();
         let config = RuleConfig::default();
-        let ctx = TsAnalysisContext {
-            file_path: path,
-            content: source,
-            lines: &lines,
-            config: &config,
-        };
-        let rule = ReactHooksRule;
-        rule.analyze(&ctx)
+        analyze_source(&ReactHooksRule, path, source, &config)
     }
 
     #[test]
@@ -276,7 +375,10 @@ function MyComponent() {
 }
 "#;
         let issues = analyze_tsx(source);
-        assert!(issues.is_empty(), "Top-level hooks should produce no issues");
+        assert!(
+            issues.is_empty(),
+            "Top-level hooks should produce no issues"
+        );
     }
 
     #[test]
@@ -304,4 +406,96 @@ function MyComponent() {
         let issues = analyze_with_path(source, "component.jsx");
         assert_eq!(issues.len(), 1);
     }
+
+    #[test]
+    fn hook_after_early_return_is_flagged() {
+        let source = r#"
+function Profile({ user }) {
+    if (!user) {
+        return null;
+    }
+    const [name, setName] = useState(user.name);
+    return <div>{name}</div>;
+}
+"#;
+        let issues = analyze_tsx(source);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("after an early return"));
+        assert_eq!(issues[0].line, Some(6));
+    }
+
+    #[test]
+    fn conditional_expressions_are_conditional() {
+        let source = r#"
+const Panel = ({ open, id }) => {
+    const data = open ? useQuery(id) : null;
+    const extra = open && React.useMemo(() => id * 2, [id]);
+    return <div>{data}{extra}</div>;
+};
+"#;
+        let issues = analyze_tsx(source);
+        let hooks: Vec<_> = issues
+            .iter()
+            .map(|i| (i.line, i.message.contains("conditional")))
+            .collect();
+        assert_eq!(hooks, vec![(Some(3), true), (Some(4), true)]);
+        assert!(issues[1].message.contains("`useMemo`"));
+    }
+
+    #[test]
+    fn hook_inside_callback_is_flagged() {
+        let source = r#"
+function List({ items }) {
+    const [selected, setSelected] = useState(null);
+    useEffect(() => {
+        const theme = useContext(ThemeContext);
+    }, []);
+    return items.map((item) => <Row key={item.id} ref={useRef(null)} />);
+}
+"#;
+        let issues = analyze_tsx(source);
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|i| i.message.contains("nested function")));
+        assert_eq!(issues[0].line, Some(5));
+        assert_eq!(issues[1].line, Some(7));
+    }
+
+    #[test]
+    fn branches_inside_callbacks_do_not_leak_into_the_component() {
+        let source = r#"
+function Search({ query }) {
+    const onChange = useCallback((value) => {
+        if (!value) {
+            return;
+        }
+        setQuery(value);
+    }, []);
+    const results = useMemo(() => filter(query), [query]);
+    // if (query) { useState(0) }
+    const label = "if (x) { useState(0) }";
+    return <Results items={results} label={label} />;
+}
+"#;
+        let issues = analyze_tsx(source);
+        assert!(
+            issues.is_empty(),
+            "expected no issues, got: {:?}",
+            issues.iter().map(|i| &i.message).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn custom_hooks_are_checked_and_non_hooks_ignored() {
+        let source = r#"
+export function useToggle(initial: boolean) {
+    for (const _ of [1]) {
+        useState(initial);
+    }
+    return user(initial) || useless();
+}
+"#;
+        let issues = analyze_tsx(source);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("inside a loop"));
+    }
 }
@@ -53,24 +53,24 @@ impl TsRule for TodoCommentsRule {
             .severity_override
             .unwrap_or_else(|| self.default_severity());
 
+        // Applied to each line of a comment: the marker must open the comment or a
+        // `*`-prefixed continuation line of a block comment.
         let pattern =
-            Regex::new(r"(?i)(//|/\*)\s*(TODO|FIXME|HACK|XXX)\b").expect("valid regex");
+            Regex::new(r"(?i)^\s*(//|/\*+|\*)\s*(TODO|FIXME|HACK|XXX)\b").expect("valid regex");
 
         let mut issues = Vec::new();
 
-        for (idx, line) in ctx.lines.iter().enumerate() {
-            if let Some(mat) = pattern.find(line) {
-                // Skip matches inside string literals: if the text before the match
-                // contains an odd number of quote characters, the match is inside a string.
-                let before_match = &line[..mat.start()];
-                let double_quotes = before_match.chars().filter(|&c| c == '"').count();
-                let single_quotes = before_match.chars().filter(|&c| c == '\'').count();
-                let backticks = before_match.chars().filter(|&c| c == '`').count();
-                if double_quotes % 2 != 0 || single_quotes % 2 != 0 || backticks % 2 != 0 {
+        // Comments come from the parser, so markers inside string literals never match.
+        for comment in ctx.program.comments.iter() {
+            let first_line = ctx.line_of(comment.span);
+            let text = comment.span.source_text(ctx.content);
+
+            for (offset, line) in text.lines().enumerate() {
+                let Some(mat) = pattern.find(line) else {
                     continue;
-                }
+                };
 
-                let line_number = (idx + 1) as u32;
+                let line_number = first_line + offset as u32;
 
                 // Extract the rest of the comment text after the match for context.
                 let comment_text = line[mat.start()..].trim();
@@ -103,17 +103,11 @@ mod tests {
     use super::*;
     use crate::rules::RuleConfig;
 
+    use crate::rules::typescript::analyze_source;
+
     fn run_rule(source: &str) -> Vec<QualityIssue> {
-        let rule = TodoCommentsRule;
-        let lines: Vec<&str> = source.lines().collect();
         let config = RuleConfig::default();
-        let ctx = TsAnalysisContext {
-            file_path: "src/example.ts",
-            content: source,
-            lines: &lines,
-            config: &config,
-        };
-        rule.analyze(&ctx)
+        analyze_source(&TodoCommentsRule, "src/example.ts", source, &config)
     }
 
     #[test]
@@ -181,6 +175,23 @@ const tpl = `// HACK: template string`;
         assert_eq!(issues[0].line, Some(4));
     }
 
+    #[test]
+    fn detects_markers_in_block_comment_continuation_lines() {
+        let source = r#"/**
+ * Loads the user.
+ * TODO: cache the result
+ */
+const url = "https://example.com/*TODO*/"; // FIXME: move to config
+"#;
+        let issues = run_rule(source);
+        let lines: Vec<_> = issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![Some(3), Some(5)]);
+        assert_eq!(
+            issues[0].message,
+            "Found comment marker: * TODO: cache the result"
+        );
+    }
+
     #[test]
     fn rule_metadata_is_correct() {
         let rule = TodoCommentsRule;
@@ -2,38 +2,20 @@
 //!
 //! Detects `as Type` and `<Type>` assertions that bypass type safety.
 
-use regex::Regex;
+use oxc_ast::ast::{TSAsExpression, TSTypeAssertion};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::{GetSpan, Span};
 
 use crate::issue::QualityIssue;
 use crate::rule::{RuleType, Severity};
-use crate::rules::{Rule, TsRule, TsAnalysisContext, RuleConfig};
+use crate::rules::{Rule, RuleConfig, TsAnalysisContext, TsRule};
 
 /// Detects type assertions (`as Type` and `<Type>value`) in TypeScript code.
 ///
 /// Type assertions bypass the compiler's type checking and can hide real bugs.
 /// The safe `as const` pattern is excluded from detection.
-#[derive(Debug)]
-pub struct TypeAssertionRule {
-    as_pattern: Regex,
-    angle_pattern: Regex,
-    comment_pattern: Regex,
-}
-
-impl Default for TypeAssertionRule {
-    fn default() -> Self {
-        Self {
-            // Match `as SomeType` — we filter out `as const` in the analyze method
-            as_pattern: Regex::new(r"\bas\s+\w+")
-                .expect("invalid as-assertion regex"),
-            // Match `<SomeType>` followed by a word char or paren (value expression),
-            // but not common JSX/HTML-like patterns
-            angle_pattern: Regex::new(r#"<(\w+)>\s*[\w\(\[\{"']"#)
-                .expect("invalid angle-bracket assertion regex"),
-            comment_pattern: Regex::new(r"^\s*(?://|/\*|\*)")
-                .expect("invalid comment regex"),
-        }
-    }
-}
+#[derive(Debug, Default)]
+pub struct TypeAssertionRule;
 
 impl Rule for TypeAssertionRule {
     fn id(&self) -> &str {
@@ -61,75 +43,73 @@ impl Rule for TypeAssertionRule {
     }
 }
 
+/// A type assertion and the span of its asserted type.
+enum Assertion {
+    As { at: Span, ty: Span },
+    AngleBracket { at: Span, ty: Span },
+}
+
+#[derive(Default)]
+struct Assertions(Vec<Assertion>);
+
+impl<'a> Visit<'a> for Assertions {
+    fn visit_ts_as_expression(&mut self, it: &TSAsExpression<'a>) {
+        // `as const` narrows rather than overrides the inferred type
+        if !it.type_annotation.is_const_type_reference() {
+            self.0.push(Assertion::As {
+                at: it.span,
+                ty: it.type_annotation.span(),
+            });
+        }
+        walk::walk_ts_as_expression(self, it);
+    }
+
+    fn visit_ts_type_assertion(&mut self, it: &TSTypeAssertion<'a>) {
+        if !it.type_annotation.is_const_type_reference() {
+            self.0.push(Assertion::AngleBracket {
+                at: it.span,
+                ty: it.type_annotation.span(),
+            });
+        }
+        walk::walk_ts_type_assertion(self, it);
+    }
+}
+
 impl TsRule for TypeAssertionRule {
     fn analyze(&self, ctx: &TsAnalysisContext) -> Vec<QualityIssue> {
-        let mut issues = Vec::new();
-        let is_tsx = ctx.file_path.ends_with(".tsx");
-
-        for (i, line) in ctx.lines.iter().enumerate() {
-            let trimmed = line.trim();
-
-            // Skip comment lines
-            if self.comment_pattern.is_match(line) {
-                continue;
-            }
-
-            // Skip import/export rename lines (`import { X as Y }`, `export { X as Y }`)
-            if trimmed.starts_with("import ") || trimmed.starts_with("export ") {
-                continue;
-            }
-
-            // Detect `as Type` assertions (excluding `as const`)
-            for m in self.as_pattern.find_iter(line) {
-                let matched = m.as_str();
-                // Skip safe `as const` pattern
-                if matched.trim().ends_with("const") {
-                    continue;
-                }
-                let issue = QualityIssue::new(
+        let mut assertions = Assertions::default();
+        assertions.visit_program(ctx.program);
+
+        assertions
+            .0
+            .into_iter()
+            .map(|assertion| {
+                let (at, message) = match assertion {
+                    Assertion::As { at, ty } => (
+                        at,
+                        format!(
+                            "Type assertion 'as {}' bypasses type safety; consider using type guards instead",
+                            ty.source_text(ctx.content)
+                        ),
+                    ),
+                    Assertion::AngleBracket { at, ty } => (
+                        at,
+                        format!(
+                            "Angle-bracket type assertion '<{}>' bypasses type safety; prefer 'as' syntax or type guards",
+                            ty.source_text(ctx.content)
+                        ),
+                    ),
+                };
+                QualityIssue::new(
                     "ts:type-assertion",
                     RuleType::CodeSmell,
                     Severity::Minor,
                     crate::rule::AnalyzerSource::Other("built-in".into()),
-                    format!(
-                        "Type assertion '{}' bypasses type safety; consider using type guards instead",
-                        matched.trim()
-                    ),
+                    message,
                 )
-                .with_location(ctx.file_path.to_string(), (i as u32) + 1);
-                issues.push(issue);
-            }
-
-            // Detect angle-bracket assertions `<Type>value`, but skip .tsx files
-            // because `<Tag>` in TSX is JSX, not a type assertion.
-            if !is_tsx {
-                for caps in self.angle_pattern.captures_iter(line) {
-                    let tag = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-                    // Skip common HTML/JSX tag names that are unlikely to be type assertions
-                    // (extra safety even for .ts files)
-                    if matches!(
-                        tag,
-                        "div" | "span" | "p" | "a" | "br" | "hr" | "img" | "input"
-                    ) {
-                        continue;
-                    }
-                    let issue = QualityIssue::new(
-                        "ts:type-assertion",
-                        RuleType::CodeSmell,
-                        Severity::Minor,
-                        crate::rule::AnalyzerSource::Other("built-in".into()),
-                        format!(
-                            "Angle-bracket type assertion '<{}>' bypasses type safety; prefer 'as' syntax or type guards",
-                            tag
-                        ),
-                    )
-                    .with_location(ctx.file_path.to_string(), (i as u32) + 1);
-                    issues.push(issue);
-                }
-            }
-        }
-
-        issues
+                .with_location(ctx.file_path.to_string(), ctx.line_of(at))
+            })
+            .collect()
     }
 }
 
@@ -137,19 +117,11 @@ impl TsRule for TypeAssertionRule {
 mod tests {
     use super::*;
     use crate::rules::RuleConfig;
+    use crate::rules::typescript::analyze_source;
 
-    fn make_context<'a>(
-        file_path: &'a str,
-        content: &'a str,
-        lines: &'a [&'a str],
-        config: &'a RuleConfig,
-    ) -> TsAnalysisContext<'a> {
-        TsAnalysisContext {
-            file_path,
-            content,
-            lines,
-            config,
-        }
+    fn analyze(file_path: &str, src: &str) -> Vec<QualityIssue> {
+        let config = RuleConfig::default();
+        analyze_source(&TypeAssertionRule, file_path, src, &config)
     }
 
     #[test]
@@ -159,11 +131,7 @@ const x = someValue as string;
 const y = foo as number;
 const z = bar as const;
 "#;
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = make_context("test.ts", src, &lines, &config);
-        let rule = TypeAssertionRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze("test.ts", src);
         // Should detect `as string` and `as number`, but NOT `as const`
         assert_eq!(issues.len(), 2, "expected 2 issues, got {}: {:?}",
             issues.len(), issues.iter().map(|i| &i.message).collect::<Vec<_>>());
@@ -177,28 +145,27 @@ const z = bar as const;
 const x = <string>someValue;
 const y = <number>otherValue;
 "#;
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = make_context("test.ts", src, &lines, &config);
-        let rule = TypeAssertionRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze("test.ts", src);
         assert_eq!(issues.len(), 2, "expected 2 angle-bracket assertion issues");
         assert!(issues[0].message.contains("<string>"));
         assert!(issues[1].message.contains("<number>"));
     }
 
     #[test]
-    fn skips_angle_bracket_assertions_in_tsx_files() {
+    fn jsx_elements_are_not_assertions() {
         let src = r#"
-const x = <string>someValue;
-const y = <number>otherValue;
+const label = <span>{title}</span>;
+const row = <Row<Item> item={item} />;
+const value = input as HTMLInputElement;
 "#;
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = make_context("component.tsx", src, &lines, &config);
-        let rule = TypeAssertionRule::default();
-        let issues = rule.analyze(&ctx);
-        assert!(issues.is_empty(), "should skip angle-bracket patterns in .tsx files");
+        let issues = analyze("component.tsx", src);
+        assert_eq!(
+            issues.len(),
+            1,
+            "only the `as` assertion is a type assertion in TSX"
+        );
+        assert!(issues[0].message.contains("as HTMLInputElement"));
+        assert_eq!(issues[0].line, Some(4));
     }
 
     #[test]
@@ -210,11 +177,7 @@ import type { Foo as Bar } from 'baz';
 export { Something as Other };
 const x = value as string;
 "#;
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = make_context("test.ts", src, &lines, &config);
-        let rule = TypeAssertionRule::default();
-        let issues = rule.analyze(&ctx);
+        let issues = analyze("test.ts", src);
         // Should only detect the real assertion on `value as string`, not import/export renames
         assert_eq!(issues.len(), 1, "expected 1 issue, got {}: {:?}",
             issues.len(), issues.iter().map(|i| &i.message).collect::<Vec<_>>());
@@ -222,19 +185,16 @@ const x = value as string;
     }
 
     #[test]
-    fn skips_comment_lines() {
+    fn skips_comments_and_strings() {
         let src = r#"
 // const x = value as string;
 /* const y = value as number; */
-* value as any
-const z = real as unknown;
+const note = "treat it as string";
+const z = real as unknown as Record<string, number>;
 "#;
-        let lines: Vec<&str> = src.lines().collect();
-        let config = RuleConfig::default();
-        let ctx = make_context("test.ts", src, &lines, &config);
-        let rule = TypeAssertionRule::default();
-        let issues = rule.analyze(&ctx);
-        assert_eq!(issues.len(), 1, "should only detect the non-comment assertion");
-        assert!(issues[0].message.contains("as unknown"));
+        let issues = analyze("test.ts", src);
+        assert_eq!(issues.len(), 2, "should only detect the non-comment assertions");
+        assert!(issues[0].message.contains("as Record<string, number>"));
+        assert!(issues[1].message.contains("as unknown"));
     }
 }
@@ -1,19 +1,34 @@
-//! SARIF 2.1.0 报告解析
+//! SARIF 2.1.0 报告解析与导出
 //!
-//! 支持解析 SARIF (Static Analysis Results Interchange Format) 标准报告。
+//! 支持解析 SARIF (Static Analysis Results Interchange Format) 标准报告，
+//! 并可将 `QualityReport` 导出为 SARIF，供其他 SARIF 查看器和代码扫描面板使用。
 //! SARIF schema 复用自 SonarQube `sonar-sarif/src/main/resources/sarif/sarif-schema-2.1.0.json`
 //!
 //! 参考: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
 
 use crate::issue::QualityIssue;
+use crate::provider::ProviderReport;
+use crate::report::QualityReport;
 use crate::rule::{AnalyzerSource, RuleType, Severity};
+use crate::rules::suppression::SuppressedIssue;
+use crate::rules::{RuleMetadata, builtin_rule_metadata};
+
+/// SARIF 版本
+pub const SARIF_VERSION: &str = "2.1.0";
+/// SARIF 2.1.0 JSON schema
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+/// 导出结果中相对路径的基准目录
+pub const SARIF_SRCROOT: &str = "%SRCROOT%";
 
 /// SARIF 2.1.0 顶层结构（简化版）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SarifReport {
-    #[serde(rename = "$schema", default)]
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
     pub schema: Option<String>,
     pub version: String,
     #[serde(default)]
@@ -26,6 +41,29 @@ pub struct SarifRun {
     pub tool: SarifTool,
     #[serde(default)]
     pub results: Vec<SarifResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invocations: Vec<SarifInvocation>,
+}
+
+/// SARIF Invocation — 工具执行情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifInvocation {
+    #[serde(rename = "executionSuccessful")]
+    pub execution_successful: bool,
+    #[serde(
+        rename = "toolExecutionNotifications",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub tool_execution_notifications: Vec<SarifNotification>,
+}
+
+/// SARIF Notification — 工具执行期间的消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifNotification {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    pub message: SarifMessage,
 }
 
 /// SARIF Tool 描述
@@ -38,9 +76,13 @@ pub struct SarifTool {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SarifDriver {
     pub name: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
-    #[serde(rename = "informationUri", default)]
+    #[serde(
+        rename = "informationUri",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub information_uri: Option<String>,
     #[serde(default)]
     pub rules: Vec<SarifRule>,
@@ -50,18 +92,35 @@ pub struct SarifDriver {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SarifRule {
     pub id: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
-    #[serde(rename = "shortDescription", default)]
+    #[serde(
+        rename = "shortDescription",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub short_description: Option<SarifMessage>,
-    #[serde(rename = "defaultConfiguration", default)]
+    #[serde(
+        rename = "fullDescription",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub full_description: Option<SarifMessage>,
+    #[serde(
+        rename = "defaultConfiguration",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub default_configuration: Option<SarifRuleConfig>,
+    /// 属性包（规则类型、严重级别、tags）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Map<String, Value>>,
 }
 
 /// SARIF Rule 默认配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SarifRuleConfig {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub level: Option<String>,
 }
 
@@ -76,26 +135,67 @@ pub struct SarifMessage {
 pub struct SarifResult {
     #[serde(rename = "ruleId")]
     pub rule_id: String,
-    #[serde(default)]
+    #[serde(rename = "ruleIndex", default, skip_serializing_if = "Option::is_none")]
+    pub rule_index: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub level: Option<String>,
     pub message: SarifMessage,
     #[serde(default)]
     pub locations: Vec<SarifLocation>,
+    /// new / unchanged（相对质量基线）
+    #[serde(
+        rename = "baselineState",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub baseline_state: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<SarifSuppression>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Map<String, Value>>,
+}
+
+impl SarifResult {
+    /// 是否被抑制（无 status 或 status 为 accepted 的 suppression）
+    pub fn is_suppressed(&self) -> bool {
+        self.suppressions
+            .iter()
+            .any(|s| s.status.as_deref().unwrap_or("accepted") == "accepted")
+    }
+}
+
+/// SARIF Suppression
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifSuppression {
+    /// inSource / external
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<String>,
 }
 
 /// SARIF Location
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SarifLocation {
-    #[serde(rename = "physicalLocation", default)]
+    #[serde(
+        rename = "physicalLocation",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub physical_location: Option<SarifPhysicalLocation>,
 }
 
 /// SARIF Physical Location
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SarifPhysicalLocation {
-    #[serde(rename = "artifactLocation", default)]
+    #[serde(
+        rename = "artifactLocation",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub artifact_location: Option<SarifArtifactLocation>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub region: Option<SarifRegion>,
 }
 
@@ -103,18 +203,24 @@ pub struct SarifPhysicalLocation {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SarifArtifactLocation {
     pub uri: String,
+    #[serde(rename = "uriBaseId", default, skip_serializing_if = "Option::is_none")]
+    pub uri_base_id: Option<String>,
 }
 
 /// SARIF Region (行/列范围)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SarifRegion {
-    #[serde(rename = "startLine", default)]
+    #[serde(rename = "startLine", default, skip_serializing_if = "Option::is_none")]
     pub start_line: Option<u32>,
-    #[serde(rename = "startColumn", default)]
+    #[serde(
+        rename = "startColumn",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub start_column: Option<u32>,
-    #[serde(rename = "endLine", default)]
+    #[serde(rename = "endLine", default, skip_serializing_if = "Option::is_none")]
     pub end_line: Option<u32>,
-    #[serde(rename = "endColumn", default)]
+    #[serde(rename = "endColumn", default, skip_serializing_if = "Option::is_none")]
     pub end_column: Option<u32>,
 }
 
@@ -123,7 +229,7 @@ pub fn sarif_to_issues(report: &SarifReport, source: AnalyzerSource) -> Vec<Qual
     let mut issues = Vec::new();
 
     for run in &report.runs {
-        for result in &run.results {
+        for result in run.results.iter().filter(|r| !r.is_suppressed()) {
             let severity = sarif_level_to_severity(result.level.as_deref().unwrap_or("warning"));
             let rule_type = severity_to_rule_type(severity);
 
@@ -162,6 +268,178 @@ pub fn sarif_to_issues(report: &SarifReport, source: AnalyzerSource) -> Vec<Qual
     issues
 }
 
+/// 将质量报告导出为 SARIF 2.1.0
+///
+/// 每个 provider 对应一个 run；内置规则附带 `Rule` 元数据，
+/// 被行内注释抑制的问题以 `suppressions` 形式保留。
+pub fn report_to_sarif(report: &QualityReport) -> SarifReport {
+    let builtin: HashMap<String, RuleMetadata> = builtin_rule_metadata()
+        .into_iter()
+        .map(|m| (m.id.clone(), m))
+        .collect();
+
+    SarifReport {
+        schema: Some(SARIF_SCHEMA.to_string()),
+        version: SARIF_VERSION.to_string(),
+        runs: report
+            .provider_reports
+            .iter()
+            .map(|provider| provider_to_run(provider, &builtin))
+            .collect(),
+    }
+}
+
+fn provider_to_run(provider: &ProviderReport, builtin: &HashMap<String, RuleMetadata>) -> SarifRun {
+    let mut rules: Vec<SarifRule> = Vec::new();
+    let mut rule_indices: HashMap<&str, usize> = HashMap::new();
+    let mut results = Vec::new();
+
+    let reported = provider.issues.iter().map(|issue| (issue, None));
+    let suppressed = provider.suppressed.iter().map(|s| (&s.issue, Some(s)));
+    for (issue, suppression) in reported.chain(suppressed) {
+        let rule_index = *rule_indices
+            .entry(issue.rule_id.as_str())
+            .or_insert_with(|| {
+                rules.push(issue_rule(issue, builtin.get(&issue.rule_id)));
+                rules.len() - 1
+            });
+        results.push(issue_to_result(issue, rule_index, suppression));
+    }
+
+    SarifRun {
+        tool: SarifTool {
+            driver: SarifDriver {
+                name: provider.provider_name.clone(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                information_uri: None,
+                rules,
+            },
+        },
+        results,
+        invocations: vec![SarifInvocation {
+            execution_successful: provider.success,
+            tool_execution_notifications: provider
+                .error
+                .iter()
+                .map(|error| SarifNotification {
+                    level: Some("error".to_string()),
+                    message: SarifMessage {
+                        text: error.clone(),
+                    },
+                })
+                .collect(),
+        }],
+    }
+}
+
+/// 规则描述：内置规则取 `Rule` 元数据，其余规则取首个问题的类型与级别
+fn issue_rule(issue: &QualityIssue, metadata: Option<&RuleMetadata>) -> SarifRule {
+    let (rule_type, severity) = metadata
+        .map(|m| (m.rule_type, m.default_severity))
+        .unwrap_or((issue.rule_type, issue.severity));
+
+    SarifRule {
+        id: issue.rule_id.clone(),
+        name: metadata.map(|m| m.name.clone()),
+        short_description: metadata.map(|m| SarifMessage {
+            text: m.name.clone(),
+        }),
+        full_description: metadata.map(|m| SarifMessage {
+            text: m.description.clone(),
+        }),
+        default_configuration: Some(SarifRuleConfig {
+            level: Some(severity_to_sarif_level(severity).to_string()),
+        }),
+        properties: Some(quality_properties(rule_type, severity, true)),
+    }
+}
+
+fn issue_to_result(
+    issue: &QualityIssue,
+    rule_index: usize,
+    suppression: Option<&SuppressedIssue>,
+) -> SarifResult {
+    let locations = issue
+        .file_path
+        .as_deref()
+        .map(|path| {
+            let path = path.replace('\\', "/");
+            let artifact_location = if path.starts_with('/') {
+                SarifArtifactLocation {
+                    uri: format!("file://{}", path),
+                    uri_base_id: None,
+                }
+            } else {
+                SarifArtifactLocation {
+                    uri: path,
+                    uri_base_id: Some(SARIF_SRCROOT.to_string()),
+                }
+            };
+            // SARIF 行列号从 1 开始
+            let region = issue.line.filter(|line| *line > 0).map(|line| SarifRegion {
+                start_line: Some(line),
+                start_column: issue.column.filter(|c| *c > 0),
+                end_line: issue.end_line.filter(|end| *end >= line),
+                end_column: issue.end_column.filter(|c| *c > 0),
+            });
+            vec![SarifLocation {
+                physical_location: Some(SarifPhysicalLocation {
+                    artifact_location: Some(artifact_location),
+                    region,
+                }),
+            }]
+        })
+        .unwrap_or_default();
+
+    SarifResult {
+        rule_id: issue.rule_id.clone(),
+        rule_index: Some(rule_index),
+        level: Some(severity_to_sarif_level(issue.severity).to_string()),
+        message: SarifMessage {
+            text: issue.message.clone(),
+        },
+        locations,
+        baseline_state: Some(if issue.is_new { "new" } else { "unchanged" }.to_string()),
+        suppressions: suppression
+            .map(|s| {
+                vec![SarifSuppression {
+                    kind: "inSource".to_string(),
+                    status: None,
+                    justification: s.reason.clone(),
+                }]
+            })
+            .unwrap_or_default(),
+        properties: Some(quality_properties(issue.rule_type, issue.severity, false)),
+    }
+}
+
+fn quality_properties(
+    rule_type: RuleType,
+    severity: Severity,
+    with_tags: bool,
+) -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert("type".to_string(), json!(rule_type.as_str()));
+    properties.insert("severity".to_string(), json!(severity.as_str()));
+    if with_tags {
+        properties.insert(
+            "tags".to_string(),
+            json!([rule_type.as_str().to_lowercase().replace('_', "-")]),
+        );
+    }
+    properties
+}
+
+/// Severity → SARIF level 映射（与 `sarif_level_to_severity` 互逆）
+fn severity_to_sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Blocker | Severity::Critical => "error",
+        Severity::Major => "warning",
+        Severity::Minor => "note",
+        Severity::Info => "none",
+    }
+}
+
 /// SARIF level → Severity 映射
 fn sarif_level_to_severity(level: &str) -> Severity {
     match level {
@@ -226,4 +504,72 @@ mod tests {
         assert_eq!(issues[0].line, Some(42));
         assert_eq!(issues[0].severity, Severity::Major);
     }
+
+    #[test]
+    fn test_export_report_to_sarif() {
+        let reported = QualityIssue::new(
+            "rust:magic-numbers",
+            RuleType::CodeSmell,
+            Severity::Minor,
+            AnalyzerSource::Other("builtin-rust".to_string()),
+            "Magic number 42",
+        )
+        .with_location("src/lib.rs", 3)
+        .as_legacy();
+        let suppressed = SuppressedIssue {
+            issue: QualityIssue::new(
+                "rust:magic-numbers",
+                RuleType::CodeSmell,
+                Severity::Minor,
+                AnalyzerSource::Other("builtin-rust".to_string()),
+                "Magic number 7",
+            )
+            .with_location("src/lib.rs", 9),
+            suppressed_at: 8,
+            reason: Some("protocol constant".to_string()),
+        };
+        let report = QualityReport::aggregate(vec![
+            ProviderReport::success("builtin-rust", 5)
+                .with_issues(vec![reported])
+                .with_suppressed(vec![suppressed]),
+            ProviderReport::failure("clippy", 0, "cargo not found"),
+        ]);
+
+        let sarif = report_to_sarif(&report);
+        let json = serde_json::to_value(&sarif).unwrap();
+        assert_eq!(json["$schema"], SARIF_SCHEMA);
+        assert_eq!(json["version"], "2.1.0");
+        assert!(!json.to_string().contains("null"));
+
+        let run = &json["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["id"], "rust:magic-numbers");
+        assert!(rules[0]["fullDescription"]["text"].is_string());
+        assert_eq!(rules[0]["properties"]["type"], "CODE_SMELL");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[0]["ruleIndex"], 0);
+        assert_eq!(results[0]["level"], "note");
+        assert_eq!(results[0]["baselineState"], "unchanged");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uriBaseId"],
+            SARIF_SRCROOT
+        );
+        assert_eq!(results[1]["suppressions"][0]["kind"], "inSource");
+        assert_eq!(
+            results[1]["suppressions"][0]["justification"],
+            "protocol constant"
+        );
+        assert_eq!(
+            json["runs"][1]["invocations"][0]["executionSuccessful"],
+            false
+        );
+
+        // 再导入时跳过被抑制的结果
+        let parsed = parse_sarif(&json.to_string()).unwrap();
+        let issues = sarif_to_issues(&parsed, AnalyzerSource::Other("sarif".to_string()));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(3));
+    }
 }
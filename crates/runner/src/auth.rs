@@ -0,0 +1,225 @@
+//! Runner authentication
+//!
+//! The runner accepts a shared bearer token (`SOLODAWN_RUNNER_TOKEN`), TLS with
+//! client certificate verification (mTLS), or both. Serving without either on a
+//! non-loopback address is refused unless `SOLODAWN_RUNNER_INSECURE=1`.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::{Context, Result, bail};
+use tonic::{
+    Request, Status,
+    metadata::MetadataMap,
+    service::Interceptor,
+    transport::{Certificate, Identity, ServerTlsConfig},
+};
+
+/// Shared bearer token clients must present
+pub const TOKEN_ENV: &str = "SOLODAWN_RUNNER_TOKEN";
+/// PEM certificate chain served by the runner
+pub const TLS_CERT_ENV: &str = "SOLODAWN_RUNNER_SERVER_TLS_CERT";
+/// PEM private key for `TLS_CERT_ENV`
+pub const TLS_KEY_ENV: &str = "SOLODAWN_RUNNER_SERVER_TLS_KEY";
+/// PEM CA bundle; when set, clients must present a certificate signed by it
+pub const TLS_CLIENT_CA_ENV: &str = "SOLODAWN_RUNNER_TLS_CLIENT_CA";
+/// Allow serving without authentication on a non-loopback address
+pub const INSECURE_ENV: &str = "SOLODAWN_RUNNER_INSECURE";
+
+/// Authentication settings for the runner gRPC server
+#[derive(Default)]
+pub struct RunnerSecurity {
+    token: Option<Arc<str>>,
+    tls: Option<ServerTlsConfig>,
+    client_certs_required: bool,
+    allow_insecure: bool,
+}
+
+impl std::fmt::Debug for RunnerSecurity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunnerSecurity")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("tls", &self.tls.is_some())
+            .field("client_certs_required", &self.client_certs_required)
+            .field("allow_insecure", &self.allow_insecure)
+            .finish()
+    }
+}
+
+impl RunnerSecurity {
+    /// Load token and TLS material from the environment.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let mut security = Self {
+            token: var(TOKEN_ENV).map(Arc::from),
+            allow_insecure: var(INSECURE_ENV).is_some_and(|value| value == "1" || value == "true"),
+            ..Self::default()
+        };
+
+        match (var(TLS_CERT_ENV), var(TLS_KEY_ENV)) {
+            (Some(cert_path), Some(key_path)) => {
+                let cert = std::fs::read(&cert_path)
+                    .with_context(|| format!("Failed to read {TLS_CERT_ENV} at {cert_path}"))?;
+                let key = std::fs::read(&key_path)
+                    .with_context(|| format!("Failed to read {TLS_KEY_ENV} at {key_path}"))?;
+                let mut tls = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+                if let Some(ca_path) = var(TLS_CLIENT_CA_ENV) {
+                    let ca = std::fs::read(&ca_path).with_context(|| {
+                        format!("Failed to read {TLS_CLIENT_CA_ENV} at {ca_path}")
+                    })?;
+                    tls = tls.client_ca_root(Certificate::from_pem(ca));
+                    security.client_certs_required = true;
+                }
+                security.tls = Some(tls);
+            }
+            (None, None) => {
+                if var(TLS_CLIENT_CA_ENV).is_some() {
+                    bail!("{TLS_CLIENT_CA_ENV} requires {TLS_CERT_ENV} and {TLS_KEY_ENV}");
+                }
+            }
+            _ => bail!("{TLS_CERT_ENV} and {TLS_KEY_ENV} must be set together"),
+        }
+
+        Ok(security)
+    }
+
+    /// With a bearer token check.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(Arc::from(token.into()));
+        self
+    }
+
+    /// True when callers must prove their identity (token or client certificate).
+    pub fn is_authenticated(&self) -> bool {
+        self.token.is_some() || self.client_certs_required
+    }
+
+    /// Refuse to serve unauthenticated on an address reachable from other hosts.
+    pub fn check_bind_addr(&self, addr: SocketAddr) -> Result<()> {
+        if self.is_authenticated() || addr.ip().is_loopback() {
+            return Ok(());
+        }
+        if self.allow_insecure {
+            tracing::warn!(
+                %addr,
+                "Runner is serving without authentication ({INSECURE_ENV} is set)"
+            );
+            return Ok(());
+        }
+        bail!(
+            "Refusing to serve the runner on {addr} without authentication: set {TOKEN_ENV}, \
+             or {TLS_CERT_ENV}/{TLS_KEY_ENV}/{TLS_CLIENT_CA_ENV} for mTLS \
+             ({INSECURE_ENV}=1 overrides)"
+        )
+    }
+
+    /// TLS configuration for the server, if any.
+    pub fn tls_config(&self) -> Option<ServerTlsConfig> {
+        self.tls.clone()
+    }
+
+    /// Interceptor enforcing the bearer token on every call.
+    pub fn interceptor(&self) -> TokenInterceptor {
+        TokenInterceptor {
+            expected: self.token.clone(),
+        }
+    }
+}
+
+/// Rejects calls without the expected `authorization: Bearer <token>` metadata
+#[derive(Clone)]
+pub struct TokenInterceptor {
+    expected: Option<Arc<str>>,
+}
+
+impl Interceptor for TokenInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(expected) = &self.expected else {
+            return Ok(request);
+        };
+        if bearer_token(request.metadata())
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes()))
+        {
+            Ok(request)
+        } else {
+            tracing::warn!("Rejected runner request: invalid or missing token");
+            Err(Status::unauthenticated("Invalid or missing runner token"))
+        }
+    }
+}
+
+fn bearer_token(metadata: &MetadataMap) -> Option<&str> {
+    metadata
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Constant-time byte comparison to prevent timing attacks.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with(authorization: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(value) = authorization {
+            request
+                .metadata_mut()
+                .insert("authorization", value.parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn token_interceptor_requires_matching_bearer_token() {
+        let mut interceptor = RunnerSecurity::default().with_token("s3cret").interceptor();
+        assert!(
+            interceptor
+                .call(request_with(Some("Bearer s3cret")))
+                .is_ok()
+        );
+        for authorization in [None, Some("Bearer wrong"), Some("s3cret")] {
+            let status = interceptor.call(request_with(authorization)).unwrap_err();
+            assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        }
+
+        let mut open = RunnerSecurity::default().interceptor();
+        assert!(open.call(request_with(None)).is_ok());
+    }
+
+    #[test]
+    fn unauthenticated_runner_only_binds_loopback() {
+        let open = RunnerSecurity::default();
+        assert!(
+            open.check_bind_addr("127.0.0.1:50051".parse().unwrap())
+                .is_ok()
+        );
+        assert!(
+            open.check_bind_addr("0.0.0.0:50051".parse().unwrap())
+                .is_err()
+        );
+
+        let secured = RunnerSecurity::default().with_token("s3cret");
+        assert!(
+            secured
+                .check_bind_addr("0.0.0.0:50051".parse().unwrap())
+                .is_ok()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod service;
 
 pub mod proto {
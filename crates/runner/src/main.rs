@@ -3,6 +3,7 @@ use std::sync::Arc;
 use tonic::transport::Server;
 use tracing_subscriber::EnvFilter;
 
+use runner::auth::RunnerSecurity;
 use runner::proto::runner_service_server::RunnerServiceServer;
 use runner::service::RunnerGrpcService;
 use services::terminal::process::ProcessManager;
@@ -21,13 +22,28 @@ async fn main() -> anyhow::Result<()> {
         .unwrap_or_else(|_| "0.0.0.0:50051".to_string())
         .parse()?;
 
+    let security = RunnerSecurity::from_env()?;
+    security.check_bind_addr(addr)?;
+
     let process_manager = Arc::new(ProcessManager::new());
     let service = RunnerGrpcService::new(process_manager);
 
-    tracing::info!(%addr, "Runner gRPC server starting");
+    tracing::info!(
+        %addr,
+        tls = security.tls_config().is_some(),
+        authenticated = security.is_authenticated(),
+        "Runner gRPC server starting"
+    );
 
-    Server::builder()
-        .add_service(RunnerServiceServer::new(service))
+    let mut server = Server::builder();
+    if let Some(tls) = security.tls_config() {
+        server = server.tls_config(tls)?;
+    }
+    server
+        .add_service(RunnerServiceServer::with_interceptor(
+            service,
+            security.interceptor(),
+        ))
         .serve_with_shutdown(addr, shutdown_signal())
         .await?;
 
@@ -1,33 +1,47 @@
 use std::sync::Arc;
 use std::time::UNIX_EPOCH;
 
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio_stream::wrappers::ReceiverStream;
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 
 use services::terminal::process::{ProcessManager, SpawnCommand, SpawnEnv};
+use services::workspace_sync::{BUNDLE_CHUNK_SIZE, RunnerWorkspaces};
 
 use crate::proto::runner_service_server::RunnerService;
 use crate::proto::{
-    HealthRequest, HealthResponse, IsRunningRequest, IsRunningResponse, KillTerminalRequest,
-    KillTerminalResponse, ResizeRequest, ResizeResponse, SpawnTerminalRequest,
-    SpawnTerminalResponse, StreamOutputRequest, TerminalOutputChunk, WriteInputRequest,
-    WriteInputResponse,
+    FetchWorkspaceChangesRequest, HealthRequest, HealthResponse, IsRunningRequest,
+    IsRunningResponse, KillTerminalRequest, KillTerminalResponse, ProvisionWorkspaceRequest,
+    ProvisionWorkspaceResponse, ReleaseWorkspaceRequest, ReleaseWorkspaceResponse, ResizeRequest,
+    ResizeResponse, SpawnTerminalRequest, SpawnTerminalResponse, StreamOutputRequest,
+    TerminalOutputChunk, WorkspaceChangesChunk, WriteInputRequest, WriteInputResponse,
 };
 
 /// gRPC service implementation for the Runner, backed by ProcessManager.
 pub struct RunnerGrpcService {
     process_manager: Arc<ProcessManager>,
+    workspaces: RunnerWorkspaces,
 }
 
 impl RunnerGrpcService {
     pub fn new(process_manager: Arc<ProcessManager>) -> Self {
-        Self { process_manager }
+        Self {
+            process_manager,
+            workspaces: RunnerWorkspaces::from_env(),
+        }
+    }
+
+    /// Keep provisioned workspaces in `workspaces` instead of the env default.
+    pub fn with_workspaces(mut self, workspaces: RunnerWorkspaces) -> Self {
+        self.workspaces = workspaces;
+        self
     }
 }
 
 #[tonic::async_trait]
 impl RunnerService for RunnerGrpcService {
     type StreamOutputStream = ReceiverStream<Result<TerminalOutputChunk, Status>>;
+    type FetchWorkspaceChangesStream = ReceiverStream<Result<WorkspaceChangesChunk, Status>>;
 
     async fn spawn_terminal(
         &self,
@@ -44,6 +58,8 @@ impl RunnerService for RunnerGrpcService {
             args: req.args,
             working_dir: req.working_dir.into(),
             env,
+            resource_limits: None,
+            runner_labels: Vec::new(),
         };
 
         let cols = if req.cols > 0 { req.cols as u16 } else { 80 };
@@ -220,4 +236,146 @@ impl RunnerService for RunnerGrpcService {
             active_terminals: terminals.len() as u32,
         }))
     }
+
+    async fn provision_workspace(
+        &self,
+        request: Request<Streaming<ProvisionWorkspaceRequest>>,
+    ) -> Result<Response<ProvisionWorkspaceResponse>, Status> {
+        let mut stream = request.into_inner();
+        let header = stream
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("Empty provision request"))?;
+
+        // The bundle file lives until the clone finished
+        let (source, _bundle) = if header.remote_url.is_empty() {
+            let path = self
+                .workspaces
+                .temp_bundle()
+                .map_err(|e| Status::internal(e.to_string()))?;
+            let mut file = tokio::fs::File::create(&path)
+                .await
+                .map_err(|e| Status::internal(format!("Failed to create bundle file: {e}")))?;
+            let mut chunk = header.bundle_chunk;
+            loop {
+                file.write_all(&chunk)
+                    .await
+                    .map_err(|e| Status::internal(format!("Failed to write bundle: {e}")))?;
+                match stream.message().await? {
+                    Some(next) => chunk = next.bundle_chunk,
+                    None => break,
+                }
+            }
+            file.flush()
+                .await
+                .map_err(|e| Status::internal(format!("Failed to write bundle: {e}")))?;
+            (path.to_string_lossy().into_owned(), Some(path))
+        } else {
+            (header.remote_url, None)
+        };
+
+        match self
+            .workspaces
+            .provision(&header.workspace_id, &source, &header.branch)
+            .await
+        {
+            Ok(provisioned) => {
+                tracing::info!(
+                    workspace_id = %header.workspace_id,
+                    head = %provisioned.head_commit,
+                    "Workspace provisioned via gRPC"
+                );
+                Ok(Response::new(ProvisionWorkspaceResponse {
+                    working_dir: provisioned.working_dir,
+                    head_commit: provisioned.head_commit,
+                }))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    workspace_id = %header.workspace_id,
+                    error = %e,
+                    "Failed to provision workspace via gRPC"
+                );
+                Err(Status::failed_precondition(format!("{e:#}")))
+            }
+        }
+    }
+
+    async fn fetch_workspace_changes(
+        &self,
+        request: Request<FetchWorkspaceChangesRequest>,
+    ) -> Result<Response<Self::FetchWorkspaceChangesStream>, Status> {
+        let req = request.into_inner();
+        let bundle = self
+            .workspaces
+            .temp_bundle()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let changes = self
+            .workspaces
+            .collect_changes(&req.workspace_id, &req.since_commit, &bundle)
+            .await
+            .map_err(|e| Status::failed_precondition(format!("{e:#}")))?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+
+        tokio::spawn(async move {
+            let has_commits = !changes.commits.is_empty();
+            let summary = WorkspaceChangesChunk {
+                head_commit: changes.head_commit,
+                commits: changes.commits,
+                patch: changes.patch,
+                bundle_chunk: Vec::new(),
+            };
+            if tx.send(Ok(summary)).await.is_err() || !has_commits {
+                return;
+            }
+
+            let mut file = match tokio::fs::File::open(&bundle).await {
+                Ok(file) => file,
+                Err(e) => {
+                    let _ = tx
+                        .send(Err(Status::internal(format!("Failed to open bundle: {e}"))))
+                        .await;
+                    return;
+                }
+            };
+            let mut buf = vec![0; BUNDLE_CHUNK_SIZE];
+            loop {
+                let chunk = match file.read(&mut buf).await {
+                    Ok(0) => break,
+                    Ok(n) => Ok(WorkspaceChangesChunk {
+                        bundle_chunk: buf[..n].to_vec(),
+                        ..Default::default()
+                    }),
+                    Err(e) => Err(Status::internal(format!("Failed to read bundle: {e}"))),
+                };
+                let failed = chunk.is_err();
+                if tx.send(chunk).await.is_err() || failed {
+                    // Client disconnected or the bundle is unreadable
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn release_workspace(
+        &self,
+        request: Request<ReleaseWorkspaceRequest>,
+    ) -> Result<Response<ReleaseWorkspaceResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.workspaces.release(&req.workspace_id).await {
+            Ok(()) => Ok(Response::new(ReleaseWorkspaceResponse { success: true })),
+            Err(e) => {
+                tracing::warn!(
+                    workspace_id = %req.workspace_id,
+                    error = %e,
+                    "Failed to release workspace via gRPC"
+                );
+                Err(Status::internal(e.to_string()))
+            }
+        }
+    }
 }
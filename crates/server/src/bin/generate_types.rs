@@ -17,6 +17,9 @@ fn generate_types_content() -> String {
         server::routes::workflows_dto::WorkflowCommandDto::decl(),
         server::routes::workflows_dto::SlashCommandPresetDto::decl(),
         server::routes::workflows_dto::WorkflowListItemDto::decl(),
+        server::routes::workflows_dto::WorkflowBudgetDto::decl(),
+        server::routes::workflows_dto::WorkflowActivityPolicyDto::decl(),
+        server::routes::workflows_dto::WorkflowUsageDto::decl(),
         server::routes::shared_tasks_types::SharedTaskResponse::decl(),
         server::routes::shared_tasks_types::AssigneesQuery::decl(),
         server::routes::shared_tasks_types::SharedTask::decl(),
@@ -114,6 +117,7 @@ fn generate_types_content() -> String {
         server::routes::config::CheckEditorAvailabilityQuery::decl(),
         server::routes::config::CheckEditorAvailabilityResponse::decl(),
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
+        services::services::terminal::input_lease::InputOwner::decl(),
         server::routes::terminal_ws::WsMessage::decl(),
         server::routes::oauth::CurrentUserResponse::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
@@ -230,15 +234,39 @@ fn generate_types_content() -> String {
         db::models::quality_issue::SeverityCount::decl(),
         server::routes::quality::QualityRunSummary::decl(),
         server::routes::quality::QualityRunDetail::decl(),
+        server::routes::quality::CreateQualityBaselineRequest::decl(),
+        server::routes::quality::QualityBaselineInfo::decl(),
         server::routes::workflow_events::WsEvent::decl(),
         server::routes::workflow_events::WsEventType::decl(),
         // [G36-003] SoloDawn-specific DB model types with #[derive(TS)] but previously unexported
         db::models::workflow::WorkflowStatus::decl(),
         db::models::workflow::WorkflowTaskStatus::decl(),
         db::models::terminal::TerminalStatus::decl(),
+        db::models::terminal::TerminalResourceLimits::decl(),
+        db::models::terminal::TerminalResource::decl(),
+        db::models::terminal::ResourceEnforcement::decl(),
+        db::models::terminal::AppliedResourceLimit::decl(),
+        db::models::terminal::TerminalResourceReport::decl(),
         db::models::cli_type::CliType::decl(),
         db::models::cli_type::ModelConfig::decl(),
         db::models::cli_type::CliDetectionStatus::decl(),
+        db::models::workflow_template::WorkflowTemplate::decl(),
+        services::services::workflow_template::TemplateFormat::decl(),
+        services::services::workflow_template::WorkflowTemplateDocument::decl(),
+        services::services::workflow_template::TemplateParameter::decl(),
+        services::services::workflow_template::TemplateWorkflow::decl(),
+        services::services::workflow_template::TemplateOrchestrator::decl(),
+        services::services::workflow_template::TemplateTerminalConfig::decl(),
+        services::services::workflow_template::TemplateBudget::decl(),
+        services::services::workflow_template::TemplateActivityPolicy::decl(),
+        services::services::workflow_template::TemplateCommand::decl(),
+        services::services::workflow_template::TemplateTask::decl(),
+        services::services::workflow_template::TemplateTerminal::decl(),
+        db::models::orchestrator_decision::OrchestratorDecision::decl(),
+        server::routes::workflow_decisions::DecisionPage::decl(),
+        services::services::terminal::recording::RecordingInfo::decl(),
+        db::models::log_search::LogSearchHit::decl(),
+        server::routes::log_search::LogSearchPage::decl(),
     ];
 
     let body = decls
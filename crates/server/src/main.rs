@@ -52,6 +52,9 @@ enum Commands {
         #[arg(long)]
         orchestration: bool,
     },
+    /// Own a terminal PTY on behalf of the server (spawned internally)
+    #[command(hide = true)]
+    PtySupervisor,
 }
 
 const DEV_DEFAULT_ENCRYPTION_KEY: &str = "12345678901234567890123456789012";
@@ -144,6 +147,13 @@ async fn main() -> Result<(), SoloDawnError> {
             let exit_code = server::self_test::run(json, filter, orchestration).await;
             std::process::exit(exit_code);
         }
+        Some(Commands::PtySupervisor) => {
+            #[cfg(unix)]
+            let exit_code = services::services::terminal::supervisor::run_from_stdio();
+            #[cfg(not(unix))]
+            let exit_code = 1;
+            std::process::exit(exit_code);
+        }
         None => run_server().await,
     }
 }
@@ -468,6 +468,12 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             pause_reason: None,
+            budget_max_tokens: None,
+            budget_max_cost_usd: None,
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
         }
     }
 
@@ -0,0 +1,239 @@
+//! Log Search API Routes
+//!
+//! Full-text search over terminal and execution process logs, filtered by
+//! workflow, terminal, time range and regex.
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use db::models::log_search::{LogSearchFilter, LogSearchHit, MIN_SEARCH_TERM_CHARS};
+use deployment::Deployment;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_PAGE_LIMIT: i64 = 50;
+const MAX_PAGE_LIMIT: i64 = 200;
+/// Rows fetched per round while filtering by regex
+const REGEX_SCAN_BATCH: i64 = 500;
+/// Upper bound on rows inspected for one regex page
+const MAX_REGEX_SCAN_ROWS: i64 = 20_000;
+/// Compiled size limit for user-supplied patterns
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LogSearchQuery {
+    /// Free-text terms, all of which must occur (case-insensitive substrings)
+    pub q: Option<String>,
+    pub workflow_id: Option<String>,
+    pub terminal_id: Option<String>,
+    /// RFC 3339 lower bound on the log timestamp
+    pub since: Option<DateTime<Utc>>,
+    /// RFC 3339 upper bound on the log timestamp
+    pub until: Option<DateTime<Utc>>,
+    /// Regex the log text (ANSI escapes stripped) must match
+    pub regex: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// One page of log search hits, oldest first
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct LogSearchPage {
+    pub items: Vec<LogSearchHit>,
+    pub limit: i64,
+    pub offset: i64,
+    /// True when a regex search stopped at the scan limit before filling the page
+    pub truncated: bool,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET /api/logs/search?q=&workflowId=&terminalId=&since=&until=&regex=&limit=&offset=
+async fn search_logs(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<LogSearchQuery>,
+) -> Result<ResponseJson<ApiResponse<LogSearchPage>>, ApiError> {
+    let (filter, regex) = search_filter(&query)?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let pool = &deployment.db().pool;
+
+    let Some(regex) = regex else {
+        let mut items = LogSearchHit::search(pool, &filter, limit, offset).await?;
+        for hit in &mut items {
+            hit.content = strip_ansi_escapes::strip_str(&hit.content);
+        }
+        return Ok(Json(ApiResponse::success(LogSearchPage {
+            items,
+            limit,
+            offset,
+            truncated: false,
+        })));
+    };
+
+    // The index narrows candidates by text, the regex is applied on top; `offset`
+    // counts regex matches, not scanned rows.
+    let mut items = Vec::new();
+    let mut skipped = 0;
+    let mut scanned = 0;
+    let mut truncated = false;
+    'scan: loop {
+        let batch = LogSearchHit::search(pool, &filter, REGEX_SCAN_BATCH, scanned).await?;
+        let exhausted = (batch.len() as i64) < REGEX_SCAN_BATCH;
+        scanned += batch.len() as i64;
+        for mut hit in batch {
+            hit.content = strip_ansi_escapes::strip_str(&hit.content);
+            if !regex.is_match(&hit.content) {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            items.push(hit);
+            if items.len() as i64 == limit {
+                break 'scan;
+            }
+        }
+        if exhausted {
+            break;
+        }
+        if scanned >= MAX_REGEX_SCAN_ROWS {
+            truncated = true;
+            break;
+        }
+    }
+
+    Ok(Json(ApiResponse::success(LogSearchPage {
+        items,
+        limit,
+        offset,
+        truncated,
+    })))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn search_filter(query: &LogSearchQuery) -> Result<(LogSearchFilter, Option<Regex>), ApiError> {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(ToString::to_string)
+    };
+    let text = non_empty(&query.q);
+    let pattern = non_empty(&query.regex);
+    if text.is_none() && pattern.is_none() {
+        return Err(ApiError::BadRequest(
+            "Provide a search text (q) or a regex".to_string(),
+        ));
+    }
+    if let Some(term) = text.as_deref().and_then(|text| {
+        text.split_whitespace()
+            .find(|term| term.chars().count() < MIN_SEARCH_TERM_CHARS)
+    }) {
+        return Err(ApiError::BadRequest(format!(
+            "Search term '{term}' is too short: terms need at least {MIN_SEARCH_TERM_CHARS} characters, use regex for shorter patterns"
+        )));
+    }
+    if let (Some(since), Some(until)) = (query.since, query.until)
+        && since > until
+    {
+        return Err(ApiError::BadRequest(
+            "since must not be later than until".to_string(),
+        ));
+    }
+    let regex = pattern
+        .map(|pattern| {
+            RegexBuilder::new(&pattern)
+                .size_limit(REGEX_SIZE_LIMIT)
+                .build()
+                .map_err(|e| ApiError::BadRequest(format!("Invalid regex: {e}")))
+        })
+        .transpose()?;
+
+    Ok((
+        LogSearchFilter {
+            text,
+            workflow_id: non_empty(&query.workflow_id),
+            terminal_id: non_empty(&query.terminal_id),
+            since: query.since,
+            until: query.until,
+        },
+        regex,
+    ))
+}
+
+// ============================================================================
+// Route Definition
+// ============================================================================
+
+/// Create log search router (nested under /api/logs)
+pub fn log_search_routes() -> Router<DeploymentImpl> {
+    Router::new().route("/search", get(search_logs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_filter_validates_input() {
+        let (filter, regex) = search_filter(&LogSearchQuery {
+            q: Some(" error ".to_string()),
+            terminal_id: Some(" ".to_string()),
+            regex: Some(r"E\d{4}".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(filter.text.as_deref(), Some("error"));
+        assert_eq!(filter.terminal_id, None);
+        assert!(regex.unwrap().is_match("error[E0308]"));
+
+        for query in [
+            LogSearchQuery::default(),
+            LogSearchQuery {
+                q: Some("build ok".to_string()),
+                ..Default::default()
+            },
+            LogSearchQuery {
+                regex: Some("(".to_string()),
+                ..Default::default()
+            },
+            LogSearchQuery {
+                q: Some("error".to_string()),
+                since: Some("2026-01-02T00:00:00Z".parse().unwrap()),
+                until: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                search_filter(&query),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
+    }
+}
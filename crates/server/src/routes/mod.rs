@@ -81,6 +81,7 @@ pub mod frontend;
 pub mod git;
 pub mod health;
 pub mod images;
+pub mod log_search;
 pub mod models;
 pub mod oauth;
 pub mod organizations;
@@ -101,7 +102,9 @@ pub mod task_attempts;
 pub mod tasks;
 pub mod terminal_ws;
 pub mod terminals;
+pub mod workflow_decisions;
 pub mod workflow_events;
+pub mod workflow_templates;
 pub mod workflow_ws;
 pub mod workflows;
 pub mod ws_origin;
@@ -154,12 +157,15 @@ pub fn build_router(
         .merge(system_settings::router())
         .merge(setup::router())
         .nest("/images", images::routes())
+        .nest("/logs", log_search::log_search_routes())
         .nest("/models", models::router())
         .nest("/cli_types", cli_types::cli_types_routes())
         .nest("/cli_types", cli_status_sse::cli_status_sse_routes())
         .nest("/planning-drafts", planning_drafts::planning_draft_routes())
         .nest("/workflows", workflows::workflows_routes())
         .nest("/workflows", slash_commands::slash_commands_routes())
+        .nest("/workflows", workflow_templates::workflow_templates_routes())
+        .nest("/workflows", workflow_decisions::workflow_decisions_routes())
         .nest("/workflows", provider_health::provider_health_routes())
         .nest("/workflows", quality::quality_workflow_routes())
         .nest("/quality", quality::quality_routes())
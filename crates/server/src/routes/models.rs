@@ -1,6 +1,7 @@
 //! Model verification and listing API endpoints
 //!
-//! Provides `/api/models/list` and `/api/models/verify` for frontend model configuration.
+//! Provides `/api/models/list` and `/api/models/verify` for frontend model configuration,
+//! plus `/api/models/local` for discovering models on a local server (Ollama, llama.cpp).
 
 use std::time::Duration;
 
@@ -14,7 +15,10 @@ use axum::{
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-
+use services::services::orchestrator::{
+    constants::{DEFAULT_LOCAL_BASE_URL, LOCAL_API_TYPE},
+    local_llm::{self, LocalModelInfo},
+};
 use utils::url::normalize_base_url;
 
 use crate::{DeploymentImpl, error::ApiError};
@@ -28,6 +32,7 @@ pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/list", get(list_models))
         .route("/verify", post(verify_model))
+        .route("/local", get(list_local_models))
 }
 
 #[derive(Debug, Deserialize)]
@@ -43,13 +48,26 @@ struct ModelsListResponse {
     models: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct LocalModelsQuery {
+    #[serde(rename = "baseUrl")]
+    base_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalModelsResponse {
+    base_url: String,
+    models: Vec<LocalModelInfo>,
+}
+
 #[derive(Debug, Deserialize)]
 struct VerifyModelRequest {
     #[serde(rename = "apiType")]
     api_type: String,
     #[serde(rename = "baseUrl")]
     base_url: String,
-    #[serde(rename = "apiKey")]
+    #[serde(rename = "apiKey", default)]
     api_key: String,
     #[serde(rename = "modelId")]
     model_id: String,
@@ -66,8 +84,14 @@ async fn list_models(
     Query(query): Query<ModelsListQuery>,
     headers: HeaderMap,
 ) -> Result<ResponseJson<ModelsListResponse>, ApiError> {
-    let api_key = api_key_from_headers(&headers)?;
     let base_url = normalized_base_url(&query.api_type, query.base_url.as_deref())?;
+    if query.api_type == LOCAL_API_TYPE {
+        let models = discover_local(&base_url).await?;
+        return Ok(ResponseJson(ModelsListResponse {
+            models: models.into_iter().map(|model| model.id).collect(),
+        }));
+    }
+    let api_key = api_key_from_headers(&headers)?;
     let client = http_client()?;
 
     let models = match query.api_type.as_str() {
@@ -93,6 +117,16 @@ async fn list_models(
     Ok(ResponseJson(ModelsListResponse { models }))
 }
 
+/// GET /api/models/local
+/// Lists models of a local server with their context windows (no API key needed)
+async fn list_local_models(
+    Query(query): Query<LocalModelsQuery>,
+) -> Result<ResponseJson<LocalModelsResponse>, ApiError> {
+    let base_url = normalized_base_url(LOCAL_API_TYPE, query.base_url.as_deref())?;
+    let models = discover_local(&base_url).await?;
+    Ok(ResponseJson(LocalModelsResponse { base_url, models }))
+}
+
 /// POST /api/models/verify
 /// Verifies that a model configuration is valid and can connect
 async fn verify_model(
@@ -113,6 +147,7 @@ async fn verify_model(
             let url = normalize_base_url("google", &payload.base_url);
             verify_google_model(&client, &url, &payload.api_key, &payload.model_id).await
         }
+        LOCAL_API_TYPE => verify_local_model(&client, &payload).await,
         other => {
             return Err(ApiError::BadRequest(format!(
                 "Unsupported apiType: {other}"
@@ -148,6 +183,7 @@ fn normalized_base_url(api_type: &str, base_url: Option<&str>) -> Result<String,
         "openai-compatible" | "anthropic-compatible" => None,
         "anthropic" => Some(DEFAULT_ANTHROPIC_BASE_URL),
         "google" => Some(DEFAULT_GOOGLE_BASE_URL),
+        LOCAL_API_TYPE => Some(DEFAULT_LOCAL_BASE_URL),
         _ => None,
     };
 
@@ -377,6 +413,39 @@ async fn verify_google_model(
     Ok(models.iter().any(|model| model == target))
 }
 
+// ============================================================================
+// Local (Ollama / llama.cpp)
+// ============================================================================
+
+async fn discover_local(base_url: &str) -> Result<Vec<LocalModelInfo>, ApiError> {
+    local_llm::discover_local_models(base_url).await.map_err(|e| {
+        tracing::warn!("Local model discovery failed: {e}");
+        ApiError::BadRequest(e.to_string())
+    })
+}
+
+/// The model must be listed, fit the orchestrator's context needs and answer a ping.
+async fn verify_local_model(
+    client: &Client,
+    payload: &VerifyModelRequest,
+) -> Result<bool, ApiError> {
+    let models = discover_local(&payload.base_url).await?;
+    let Some(model) = models.iter().find(|model| model.id == payload.model_id.trim()) else {
+        tracing::warn!("Local model {} is not served at {}", payload.model_id, payload.base_url);
+        return Ok(false);
+    };
+    if !model.fits_orchestrator() {
+        tracing::warn!(
+            "Local model {} context window {:?} is too small",
+            model.id,
+            model.context_length
+        );
+        return Ok(false);
+    }
+    let url = normalize_base_url(LOCAL_API_TYPE, &payload.base_url);
+    verify_openai_model(client, &url, &payload.api_key, &model.id).await
+}
+
 // ============================================================================
 // Shared verification helpers
 // ============================================================================
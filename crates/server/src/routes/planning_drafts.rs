@@ -1,20 +1,26 @@
 //! Planning draft API for orchestrated workspace mode.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::Infallible, time::Duration};
 
 use axum::{
     Extension, Json, Router,
     extract::{Path, Query, State},
-    response::Json as ResponseJson,
+    response::{
+        IntoResponse, Json as ResponseJson, Response, Sse,
+        sse::{Event, KeepAlive},
+    },
     routing::{get, post, put},
 };
 use db::models::planning_draft::{PlanningDraft, PlanningDraftMessage, PLANNING_DRAFT_STATUSES};
 use deployment::Deployment;
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use services::services::orchestrator::{
-    LLMMessage, OrchestratorConfig, create_llm_client,
+    LLMMessage, LLMResponse, LLMStreamEvent, OrchestratorConfig, create_llm_client,
     config::{PromptProfile, system_prompt_for_profile},
 };
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
@@ -122,6 +128,7 @@ pub fn planning_draft_routes() -> Router<DeploymentImpl> {
             "/{draft_id}/messages",
             get(list_messages).post(send_message),
         )
+        .route("/{draft_id}/messages/stream", post(send_message_stream))
 }
 
 async fn create_draft(
@@ -283,7 +290,102 @@ async fn send_message(
     Path(draft_id): Path<String>,
     Json(req): Json<SendMessageRequest>,
 ) -> Result<ResponseJson<ApiResponse<Vec<MessageResponse>>>, ApiError> {
-    let draft = PlanningDraft::find_by_id(&deployment.db().pool, &draft_id)
+    let (draft, user_msg) = store_user_message(&deployment, &draft_id, &req).await?;
+    let mut result = vec![user_msg];
+
+    // Call LLM and store assistant reply
+    let reply = if let Some(llm_client) = build_llm_client_from_draft(&draft) {
+        let llm_messages = build_planning_messages(&deployment, &draft_id).await?;
+        let response = llm_client.chat(llm_messages).await;
+        store_assistant_reply(&deployment, &draft, response).await
+    } else {
+        store_unconfigured_reply(&deployment, &draft_id).await
+    };
+    result.extend(reply);
+
+    push_messages_to_feishu(&feishu_handle, &draft, &result).await;
+
+    Ok(Json(ApiResponse::success(result)))
+}
+
+/// POST /api/planning-drafts/{draft_id}/messages/stream
+///
+/// Same as `send_message`, but answers with Server-Sent Events:
+/// 1. `delta` events (`{"kind":"delta","text":...}`) while the LLM generates.
+/// 2. `reset` when a provider failover discards the partial reply.
+/// 3. A final `messages` event with the stored messages (same payload as `send_message`).
+///
+/// The reply is generated and persisted in a background task, so it is kept
+/// even if the client disconnects mid-stream.
+async fn send_message_stream(
+    State(deployment): State<DeploymentImpl>,
+    Extension(feishu_handle): Extension<SharedFeishuHandle>,
+    Path(draft_id): Path<String>,
+    Json(req): Json<SendMessageRequest>,
+) -> Result<Response, ApiError> {
+    let (draft, user_msg) = store_user_message(&deployment, &draft_id, &req).await?;
+    let llm_client = build_llm_client_from_draft(&draft);
+    let llm_messages = if llm_client.is_some() {
+        build_planning_messages(&deployment, &draft_id).await?
+    } else {
+        Vec::new()
+    };
+
+    let (sse_tx, sse_rx) = mpsc::unbounded_channel::<Event>();
+    tokio::spawn(async move {
+        let mut result = vec![user_msg];
+
+        let reply = if let Some(llm_client) = llm_client {
+            let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+            let forwarder = {
+                let sse_tx = sse_tx.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = events_rx.recv().await {
+                        let name = match &event {
+                            LLMStreamEvent::Delta { .. } => "delta",
+                            LLMStreamEvent::Reset => "reset",
+                            LLMStreamEvent::Done => "done",
+                        };
+                        let data = serde_json::to_string(&event)
+                            .unwrap_or_else(|_| "{}".to_string());
+                        // Client gone: keep draining so the reply is still stored.
+                        let _ = sse_tx.send(Event::default().event(name).data(data));
+                    }
+                })
+            };
+            let response = llm_client
+                .chat_stream(llm_messages, Vec::new(), events_tx)
+                .await;
+            let _ = forwarder.await;
+            store_assistant_reply(&deployment, &draft, response).await
+        } else {
+            store_unconfigured_reply(&deployment, &draft.id).await
+        };
+        result.extend(reply);
+
+        push_messages_to_feishu(&feishu_handle, &draft, &result).await;
+
+        let data = serde_json::to_string(&result).unwrap_or_else(|_| "[]".to_string());
+        let _ = sse_tx.send(Event::default().event("messages").data(data));
+    });
+
+    let stream = UnboundedReceiverStream::new(sse_rx).map(Ok::<_, Infallible>);
+    Ok(Sse::new(stream)
+        .keep_alive(
+            KeepAlive::new()
+                .interval(Duration::from_secs(30))
+                .text("keep-alive"),
+        )
+        .into_response())
+}
+
+/// Validate the draft/message and store the user message.
+async fn store_user_message(
+    deployment: &DeploymentImpl,
+    draft_id: &str,
+    req: &SendMessageRequest,
+) -> Result<(PlanningDraft, MessageResponse), ApiError> {
+    let draft = PlanningDraft::find_by_id(&deployment.db().pool, draft_id)
         .await
         .map_err(|e| ApiError::Internal(format!("Database error: {e}")))?
         .ok_or_else(|| ApiError::NotFound(format!("Planning draft {draft_id} not found")))?;
@@ -301,123 +403,147 @@ async fn send_message(
         ));
     }
 
-    // 1. Store user message
-    let user_msg = PlanningDraftMessage::new(&draft_id, "user", req.message.trim());
+    let user_msg = PlanningDraftMessage::new(draft_id, "user", req.message.trim());
     PlanningDraftMessage::insert(&deployment.db().pool, &user_msg)
         .await
         .map_err(|e| ApiError::Internal(format!("Failed to save message: {e}")))?;
 
-    let mut result = vec![MessageResponse::from(user_msg)];
+    Ok((draft, MessageResponse::from(user_msg)))
+}
 
-    // 2. Try to call LLM and store assistant reply
-    if let Some(llm_client) = build_llm_client_from_draft(&draft) {
-        let all_messages = PlanningDraftMessage::list_by_draft(&deployment.db().pool, &draft_id)
-            .await
-            .map_err(|e| ApiError::Internal(format!("Database error: {e}")))?;
-
-        let system_prompt = system_prompt_for_profile(PromptProfile::WorkspacePlanning);
-        let mut llm_messages = vec![LLMMessage {
-            role: "system".to_string(),
-            content: system_prompt,
-        }];
-        for m in &all_messages {
-            llm_messages.push(LLMMessage {
-                role: m.role.clone(),
-                content: m.content.clone(),
-            });
-        }
+/// System prompt followed by the full stored conversation.
+async fn build_planning_messages(
+    deployment: &DeploymentImpl,
+    draft_id: &str,
+) -> Result<Vec<LLMMessage>, ApiError> {
+    let all_messages = PlanningDraftMessage::list_by_draft(&deployment.db().pool, draft_id)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Database error: {e}")))?;
+
+    let system_prompt = system_prompt_for_profile(PromptProfile::WorkspacePlanning);
+    let mut llm_messages = vec![LLMMessage {
+        role: "system".to_string(),
+        content: system_prompt,
+    }];
+    for m in &all_messages {
+        llm_messages.push(LLMMessage {
+            role: m.role.clone(),
+            content: m.content.clone(),
+        });
+    }
+    Ok(llm_messages)
+}
 
-        match llm_client.chat(llm_messages).await {
-            Ok(response) => {
-                let assistant_msg =
-                    PlanningDraftMessage::new(&draft_id, "assistant", &response.content);
-                if let Err(e) =
-                    PlanningDraftMessage::insert(&deployment.db().pool, &assistant_msg).await
+/// Store the LLM reply (or the failure) as an assistant message.
+async fn store_assistant_reply(
+    deployment: &DeploymentImpl,
+    draft: &PlanningDraft,
+    response: anyhow::Result<LLMResponse>,
+) -> Option<MessageResponse> {
+    let draft_id = &draft.id;
+    match response {
+        Ok(response) => {
+            let assistant_msg =
+                PlanningDraftMessage::new(draft_id, "assistant", &response.content);
+            let stored = if let Err(e) =
+                PlanningDraftMessage::insert(&deployment.db().pool, &assistant_msg).await
+            {
+                tracing::warn!(draft_id = %draft_id, "Failed to save assistant reply: {e}");
+                None
+            } else {
+                Some(MessageResponse::from(assistant_msg))
+            };
+
+            // Auto-transition: if LLM produced a ```json PLANNING_SPEC block,
+            // move draft from gathering → spec_ready and extract spec content.
+            if draft.status == "gathering"
+                && (response.content.contains("```json\n") || response.content.contains("```\n"))
+                && response.content.contains("\"productGoal\"")
+            {
+                // Extract the JSON block from the fenced code block
+                let json_block = response.content
+                    .split("```json\n").nth(1)
+                    .or_else(|| response.content.split("```\n").nth(1))
+                    .and_then(|s| s.split("```").next())
+                    .unwrap_or("");
+
+                let (req_summary, tech_spec) = if let Ok(spec) =
+                    serde_json::from_str::<serde_json::Value>(json_block)
                 {
-                    tracing::warn!(draft_id = %draft_id, "Failed to save assistant reply: {e}");
+                    let goal = spec["productGoal"].as_str().unwrap_or("").to_string();
+                    (goal, json_block.to_string())
                 } else {
-                    result.push(MessageResponse::from(assistant_msg));
+                    (String::new(), json_block.to_string())
+                };
+
+                // Store extracted spec content
+                if let Err(e) = PlanningDraft::update_spec(
+                    &deployment.db().pool,
+                    draft_id,
+                    Some(&req_summary),
+                    Some(&tech_spec),
+                    None,
+                ).await {
+                    tracing::warn!(draft_id = %draft_id, "Failed to save extracted spec: {e}");
                 }
 
-                // Auto-transition: if LLM produced a ```json PLANNING_SPEC block,
-                // move draft from gathering → spec_ready and extract spec content.
-                if draft.status == "gathering"
-                    && (response.content.contains("```json\n") || response.content.contains("```\n"))
-                    && response.content.contains("\"productGoal\"")
+                if let Err(e) = PlanningDraft::update_status(
+                    &deployment.db().pool,
+                    draft_id,
+                    "spec_ready",
+                )
+                .await
                 {
-                    // Extract the JSON block from the fenced code block
-                    let json_block = response.content
-                        .split("```json\n").nth(1)
-                        .or_else(|| response.content.split("```\n").nth(1))
-                        .and_then(|s| s.split("```").next())
-                        .unwrap_or("");
-
-                    let (req_summary, tech_spec) = if let Ok(spec) =
-                        serde_json::from_str::<serde_json::Value>(json_block)
-                    {
-                        let goal = spec["productGoal"].as_str().unwrap_or("").to_string();
-                        (goal, json_block.to_string())
-                    } else {
-                        (String::new(), json_block.to_string())
-                    };
-
-                    // Store extracted spec content
-                    if let Err(e) = PlanningDraft::update_spec(
-                        &deployment.db().pool,
-                        &draft_id,
-                        Some(&req_summary),
-                        Some(&tech_spec),
-                        None,
-                    ).await {
-                        tracing::warn!(draft_id = %draft_id, "Failed to save extracted spec: {e}");
-                    }
-
-                    if let Err(e) = PlanningDraft::update_status(
-                        &deployment.db().pool,
-                        &draft_id,
-                        "spec_ready",
-                    )
-                    .await
-                    {
-                        tracing::warn!(draft_id = %draft_id, "Failed to auto-transition to spec_ready: {e}");
-                    } else {
-                        tracing::info!(draft_id = %draft_id, req_summary = %req_summary, "Auto-transitioned draft to spec_ready with extracted spec");
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::warn!(draft_id = %draft_id, "LLM call failed for planning draft: {e}");
-                // Surface the error as an assistant message so the user sees it
-                let error_content = format!(
-                    "LLM call failed: {e}\n\nPlease check your model configuration (API key, base URL, model name) in Settings."
-                );
-                let error_msg =
-                    PlanningDraftMessage::new(&draft_id, "assistant", &error_content);
-                if let Ok(()) =
-                    PlanningDraftMessage::insert(&deployment.db().pool, &error_msg).await
-                {
-                    result.push(MessageResponse::from(error_msg));
+                    tracing::warn!(draft_id = %draft_id, "Failed to auto-transition to spec_ready: {e}");
+                } else {
+                    tracing::info!(draft_id = %draft_id, req_summary = %req_summary, "Auto-transitioned draft to spec_ready with extracted spec");
                 }
             }
+
+            stored
         }
-    } else {
-        tracing::warn!(
-            draft_id = %draft_id,
-            "No LLM config on planning draft — model credentials may be missing"
-        );
-        // Surface as an assistant message so the user knows what's wrong
-        let error_content =
-            "Model not configured for this workspace. Please check Settings → Models and ensure the selected model has a valid API key and base URL.";
-        let error_msg =
-            PlanningDraftMessage::new(&draft_id, "assistant", error_content);
-        if let Ok(()) =
-            PlanningDraftMessage::insert(&deployment.db().pool, &error_msg).await
-        {
-            result.push(MessageResponse::from(error_msg));
+        Err(e) => {
+            tracing::warn!(draft_id = %draft_id, "LLM call failed for planning draft: {e}");
+            // Surface the error as an assistant message so the user sees it
+            let error_content = format!(
+                "LLM call failed: {e}\n\nPlease check your model configuration (API key, base URL, model name) in Settings."
+            );
+            let error_msg =
+                PlanningDraftMessage::new(draft_id, "assistant", &error_content);
+            PlanningDraftMessage::insert(&deployment.db().pool, &error_msg)
+                .await
+                .ok()
+                .map(|()| MessageResponse::from(error_msg))
         }
     }
+}
 
-    // Push new messages to Feishu if sync is enabled
+/// Store the "model not configured" notice as an assistant message.
+async fn store_unconfigured_reply(
+    deployment: &DeploymentImpl,
+    draft_id: &str,
+) -> Option<MessageResponse> {
+    tracing::warn!(
+        draft_id = %draft_id,
+        "No LLM config on planning draft — model credentials may be missing"
+    );
+    // Surface as an assistant message so the user knows what's wrong
+    let error_content =
+        "Model not configured for this workspace. Please check Settings → Models and ensure the selected model has a valid API key and base URL.";
+    let error_msg =
+        PlanningDraftMessage::new(draft_id, "assistant", error_content);
+    PlanningDraftMessage::insert(&deployment.db().pool, &error_msg)
+        .await
+        .ok()
+        .map(|()| MessageResponse::from(error_msg))
+}
+
+/// Push new messages to Feishu if sync is enabled
+async fn push_messages_to_feishu(
+    feishu_handle: &SharedFeishuHandle,
+    draft: &PlanningDraft,
+    messages: &[MessageResponse],
+) {
     if draft.feishu_sync {
         if let Some(ref chat_id) = draft.feishu_chat_id {
             let handle_guard = feishu_handle.read().await;
@@ -425,7 +551,7 @@ async fn send_message(
                 if *h.connected.read().await {
                     let messenger = h.messenger.clone();
                     let chat_id = chat_id.clone();
-                    let messages_to_push: Vec<_> = result
+                    let messages_to_push: Vec<_> = messages
                         .iter()
                         .map(|m| (m.role.clone(), m.content.clone()))
                         .collect();
@@ -450,8 +576,6 @@ async fn send_message(
             }
         }
     }
-
-    Ok(Json(ApiResponse::success(result)))
 }
 
 /// Build an LLM client from the draft's planner configuration.
@@ -754,6 +878,12 @@ async fn materialize_draft(
         created_at: now,
         updated_at: now,
         pause_reason: None,
+        budget_max_tokens: None,
+        budget_max_cost_usd: None,
+        budget_max_duration_secs: None,
+        activity_stall_timeout_secs: None,
+        activity_loop_threshold: None,
+        activity_escalation_interval_secs: None,
     };
 
     let decrypted_key = draft.get_api_key()
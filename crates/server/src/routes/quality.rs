@@ -4,17 +4,25 @@
 //! - GET /workflows/:id/quality/runs       — list quality runs for a workflow
 //! - GET /quality/runs/:run_id             — single quality run by ID
 //! - GET /quality/runs/:run_id/issues      — issues for a quality run
+//! - GET /quality/runs/:run_id/sarif       — SARIF 2.1.0 export of a quality run
 //! - GET /terminals/:id/quality/latest     — latest quality run for a terminal
+//! - GET/POST /quality/repos/:id/baseline   — show / create or refresh a repo's baseline
 
 use axum::{
     Json, Router,
     extract::{Path, State},
+    http::header,
+    response::IntoResponse,
     routing::get,
 };
 use deployment::Deployment;
-use serde::Serialize;
+use quality::{
+    baseline::QualityBaseline, engine::QualityEngine, report::QualityReport, sarif::report_to_sarif,
+};
+use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use uuid::Uuid;
 
 use crate::DeploymentImpl;
 use crate::error::ApiError;
@@ -92,6 +100,7 @@ impl From<db::models::QualityRun> for QualityRunDetail {
                 providers_run: None,
                 report_json: None,
                 decision_json: None,
+                sarif_json: None,
                 ..r
             }),
             providers_run: providers,
@@ -101,6 +110,36 @@ impl From<db::models::QualityRun> for QualityRunDetail {
     }
 }
 
+/// Request body for creating a quality baseline.
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateQualityBaselineRequest {
+    /// Replace an existing baseline instead of failing.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// Summary of a repo's latest quality baseline file.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityBaselineInfo {
+    pub path: String,
+    pub created_at: String,
+    pub total_issues: usize,
+    pub fingerprints: usize,
+}
+
+impl QualityBaselineInfo {
+    fn new(path: &std::path::Path, baseline: &QualityBaseline) -> Self {
+        Self {
+            path: path.display().to_string(),
+            created_at: baseline.created_at.to_rfc3339(),
+            total_issues: baseline.total_issues,
+            fingerprints: baseline.entries.len(),
+        }
+    }
+}
+
 /// GET /workflows/:workflow_id/quality/runs
 pub async fn list_quality_runs(
     State(deployment): State<DeploymentImpl>,
@@ -138,6 +177,38 @@ pub async fn get_quality_issues(
     Ok(Json(ApiResponse::success(issues)))
 }
 
+/// GET /quality/runs/:run_id/sarif
+///
+/// Returns the raw SARIF log (not wrapped in `ApiResponse`) so it can be fed
+/// straight into code-scanning tools. Runs recorded before SARIF export was
+/// stored are converted from their report JSON on the fly.
+pub async fn get_quality_run_sarif(
+    State(deployment): State<DeploymentImpl>,
+    Path(run_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let run = db::models::QualityRun::find_by_id(&deployment.db().pool, &run_id)
+        .await
+        .map_err(ApiError::Database)?
+        .ok_or_else(|| ApiError::NotFound(format!("Quality run {run_id} not found")))?;
+
+    let sarif = match (run.sarif_json, run.report_json) {
+        (Some(sarif), _) => sarif,
+        (None, Some(report_json)) => {
+            let report: QualityReport = serde_json::from_str(&report_json)
+                .map_err(|e| ApiError::Internal(format!("Invalid quality report: {e}")))?;
+            serde_json::to_string(&report_to_sarif(&report))
+                .map_err(|e| ApiError::Internal(format!("Failed to export SARIF: {e}")))?
+        }
+        (None, None) => {
+            return Err(ApiError::NotFound(format!(
+                "Quality run {run_id} has no report to export"
+            )));
+        }
+    };
+
+    Ok(([(header::CONTENT_TYPE, "application/sarif+json")], sarif))
+}
+
 /// GET /terminals/:terminal_id/quality/latest
 pub async fn get_terminal_latest_quality(
     State(deployment): State<DeploymentImpl>,
@@ -151,6 +222,61 @@ pub async fn get_terminal_latest_quality(
     Ok(Json(ApiResponse::success(run.map(QualityRunSummary::from))))
 }
 
+/// GET /quality/repos/:repo_id/baseline
+pub async fn get_quality_baseline(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Option<QualityBaselineInfo>>>, ApiError> {
+    let repo = deployment
+        .repo()
+        .get_by_id(&deployment.db().pool, repo_id)
+        .await?;
+
+    let Some(path) = QualityBaseline::latest_path(&repo.path) else {
+        return Ok(Json(ApiResponse::success(None)));
+    };
+    let baseline =
+        QualityBaseline::load(&path).map_err(|e| ApiError::Internal(format!("{e:#}")))?;
+    Ok(Json(ApiResponse::success(Some(QualityBaselineInfo::new(
+        &path, &baseline,
+    )))))
+}
+
+/// POST /quality/repos/:repo_id/baseline
+///
+/// Runs a full analysis and records every current issue as legacy debt, so
+/// only issues introduced afterwards count as new.
+pub async fn create_quality_baseline(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    Json(payload): Json<CreateQualityBaselineRequest>,
+) -> Result<Json<ApiResponse<QualityBaselineInfo>>, ApiError> {
+    let repo = deployment
+        .repo()
+        .get_by_id(&deployment.db().pool, repo_id)
+        .await?;
+
+    if !payload.refresh
+        && let Some(existing) = QualityBaseline::latest_path(&repo.path)
+    {
+        return Err(ApiError::Conflict(format!(
+            "A quality baseline already exists at {}; set refresh to replace it",
+            existing.display()
+        )));
+    }
+
+    let engine = QualityEngine::from_project(&repo.path)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid quality gate config: {e:#}")))?;
+    let (path, baseline) = engine
+        .create_baseline(&repo.path, payload.refresh)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to create quality baseline: {e:#}")))?;
+
+    Ok(Json(ApiResponse::success(QualityBaselineInfo::new(
+        &path, &baseline,
+    ))))
+}
+
 /// Quality routes nested under /workflows
 pub fn quality_workflow_routes() -> Router<DeploymentImpl> {
     Router::new().route("/{workflow_id}/quality/runs", get(list_quality_runs))
@@ -161,6 +287,11 @@ pub fn quality_routes() -> Router<DeploymentImpl> {
     Router::new()
         .route("/runs/{run_id}", get(get_quality_run))
         .route("/runs/{run_id}/issues", get(get_quality_issues))
+        .route("/runs/{run_id}/sarif", get(get_quality_run_sarif))
+        .route(
+            "/repos/{repo_id}/baseline",
+            get(get_quality_baseline).post(create_quality_baseline),
+        )
 }
 
 /// Quality routes nested under /terminals
@@ -21,10 +21,14 @@ use axum::{
 };
 use db::models::{Terminal, WorkflowTask};
 use deployment::Deployment;
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{SinkExt, StreamExt, stream::SplitSink};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use services::services::terminal::{
+    input_lease::InputOwner,
+    recording::{self, Recording},
+};
 use tokio::sync::mpsc;
 use ts_rs::TS;
 
@@ -47,49 +51,6 @@ fn elapsed_since_millis(start_millis: u64) -> Duration {
 use crate::{DeploymentImpl, error::ApiError};
 use super::ws_origin::validate_ws_origin;
 
-// BACKLOG-002: Runner container separation
-// ============================================================================
-// Terminal I/O Abstraction
-// ============================================================================
-
-/// Terminal I/O abstraction for future RunnerClient migration.
-/// Currently delegates to ProcessManager directly.
-/// When RunnerClient is integrated, this will route through gRPC streams
-/// instead of local ProcessHandle reader/writer pairs.
-// BACKLOG-002: Runner container separation
-#[allow(dead_code)]
-pub(crate) struct TerminalIO {
-    /// The terminal identifier this I/O handle is associated with.
-    pub terminal_id: String,
-    /// PTY writer handle, wrapped for shared access across reconnections.
-    /// When RunnerClient is integrated, this will be replaced with a gRPC
-    /// stream sender for forwarding input to the remote runner.
-    pub writer: Option<
-        std::sync::Arc<std::sync::Mutex<services::services::terminal::process::PtyWriter>>,
-    >,
-    // RUNNER_CLIENT_MIGRATION: Future fields for gRPC-based I/O:
-    //   pub input_stream: Option<tonic::Streaming<TerminalInputChunk>>,
-    //   pub output_stream: Option<tonic::Streaming<TerminalOutputChunk>>,
-}
-
-#[allow(dead_code)]
-impl TerminalIO {
-    /// Create a TerminalIO from an existing ProcessHandle.
-    /// This is the current local-runner path. When RunnerClient is integrated,
-    /// a separate constructor (e.g., `from_runner_stream`) will be added for
-    /// gRPC-based terminal I/O.
-    // BACKLOG-002: Runner container separation
-    pub fn from_process_handle(
-        terminal_id: String,
-        handle: &services::services::terminal::process::ProcessHandle,
-    ) -> Self {
-        Self {
-            terminal_id,
-            writer: handle.writer.clone(),
-        }
-    }
-}
-
 // ============================================================================
 // Constants
 // ============================================================================
@@ -100,6 +61,13 @@ const WS_IDLE_TIMEOUT_SECS: u64 = 300;
 /// Heartbeat interval for keep-alive (30 seconds)
 const WS_HEARTBEAT_INTERVAL_SECS: u64 = 30;
 
+/// Default cap for idle gaps between replayed events (2 seconds)
+const REPLAY_DEFAULT_MAX_IDLE_MS: u64 = 2000;
+
+/// Allowed replay speed multipliers
+const REPLAY_MIN_SPEED: f64 = 0.25;
+const REPLAY_MAX_SPEED: f64 = 16.0;
+
 // Compile-time sanity check for timeout ordering.
 const _: () = {
     assert!(WS_HEARTBEAT_INTERVAL_SECS < WS_IDLE_TIMEOUT_SECS);
@@ -137,12 +105,22 @@ pub enum WsMessage {
     Input { data: String },
     /// Output to client (terminal response)
     Output { data: String },
-    /// Terminal resize request
+    /// Terminal resize request (sent by the server at the start of a replay)
     Resize { cols: u16, rows: u16 },
     /// Heartbeat from client for keep-alive
     Heartbeat,
     /// Error message
     Error { message: String },
+    /// Client takes the terminal's input lease over
+    TakeInput,
+    /// Client hands the input lease back to the orchestrator
+    ReleaseInput,
+    /// Current input lease holder (sent on connect and on every change);
+    /// `client_id` identifies the receiving connection
+    InputLease {
+        owner: InputOwner,
+        client_id: String,
+    },
 }
 
 fn is_benign_ws_disconnect(error: &axum::Error) -> bool {
@@ -177,7 +155,12 @@ fn is_benign_ws_disconnect(error: &axum::Error) -> bool {
 
 /// Create terminal WebSocket routes
 pub fn terminal_ws_routes() -> Router<DeploymentImpl> {
-    Router::new().route("/{terminal_id}", get(terminal_ws_handler))
+    Router::new()
+        .route("/{terminal_id}", get(terminal_ws_handler))
+        .route(
+            "/{terminal_id}/replay/{session_id}",
+            get(terminal_replay_handler),
+        )
 }
 
 // ============================================================================
@@ -189,9 +172,33 @@ pub fn terminal_ws_routes() -> Router<DeploymentImpl> {
 /// `last_seq`: The last output sequence number the client received before disconnecting.
 /// When provided, the server replays only the chunks with `seq > last_seq`, enabling
 /// efficient reconnection without re-sending the full history. [G09-003]
+///
+/// `client_id`: Label of the viewer for input lease ownership. The server binds
+/// it to the connection (see [`bind_client_id`]) and reports the bound id in
+/// `input_lease` messages.
 #[derive(Debug, serde::Deserialize, Default)]
 struct ResumeParams {
     last_seq: Option<u64>,
+    client_id: Option<String>,
+}
+
+/// Longest accepted `client_id`
+const MAX_CLIENT_ID_LEN: usize = 128;
+
+/// Lease identity of one WebSocket connection: the requested label plus a
+/// per-connection suffix, so another socket presenting the same `client_id`
+/// cannot write under, or release, this connection's lease.
+fn bind_client_id(requested: Option<String>) -> Result<String, ApiError> {
+    let connection_id = uuid::Uuid::new_v4();
+    match requested {
+        Some(client_id) if client_id.trim().is_empty() || client_id.len() > MAX_CLIENT_ID_LEN => {
+            Err(ApiError::BadRequest(format!(
+                "client_id must be 1-{MAX_CLIENT_ID_LEN} characters"
+            )))
+        }
+        Some(client_id) => Ok(format!("{client_id}:{connection_id}")),
+        None => Ok(connection_id.to_string()),
+    }
 }
 
 /// WebSocket handler for terminal connection
@@ -213,23 +220,154 @@ async fn terminal_ws_handler(
         return ApiError::BadRequest(format!("Invalid terminal_id format: {e}")).into_response();
     }
 
+    let client_id = match bind_client_id(params.client_id) {
+        Ok(client_id) => client_id,
+        Err(e) => return e.into_response(),
+    };
+
     // SEC-017: Enforce WebSocket message size limits (256 KB)
     ws.max_message_size(256 * 1024)
         .max_frame_size(256 * 1024)
         .on_upgrade(move |socket| {
-            handle_terminal_socket(socket, terminal_id, deployment, params.last_seq)
+            handle_terminal_socket(socket, terminal_id, deployment, params.last_seq, client_id)
         })
 }
 
+/// Query parameters for the recording replay WebSocket endpoint.
+#[derive(Debug, serde::Deserialize, Default)]
+struct ReplayParams {
+    /// Playback speed multiplier (default 1.0)
+    speed: Option<f64>,
+    /// Cap for idle gaps between events in milliseconds
+    max_idle_ms: Option<u64>,
+}
+
+/// WebSocket handler replaying a recorded PTY session as output messages
+async fn terminal_replay_handler(
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+    Path((terminal_id, session_id)): Path<(String, String)>,
+    Query(params): Query<ReplayParams>,
+    State(deployment): State<DeploymentImpl>,
+) -> impl IntoResponse {
+    if let Err((status, msg)) = validate_ws_origin(&headers) {
+        return (status, msg).into_response();
+    }
+
+    if let Err(e) = validate_terminal_id(&terminal_id) {
+        return ApiError::BadRequest(format!("Invalid terminal_id format: {e}")).into_response();
+    }
+
+    let recording = match load_recording(&deployment, &terminal_id, &session_id).await {
+        Ok(recording) => recording,
+        Err(e) => return e.into_response(),
+    };
+
+    ws.on_upgrade(move |socket| replay_recording(socket, terminal_id, recording, params))
+}
+
+async fn load_recording(
+    deployment: &DeploymentImpl,
+    terminal_id: &str,
+    session_id: &str,
+) -> Result<Recording, ApiError> {
+    let dir = super::terminals::recordings_dir(deployment)?;
+    let path = recording::recording_path(&dir, terminal_id, session_id)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let content = match tokio::fs::read_to_string(&path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return Err(ApiError::NotFound(format!(
+                "Recording {session_id} not found for terminal {terminal_id}"
+            )));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    recording::parse_recording(&content)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid recording: {e}")))
+}
+
+/// Send the recording with its original timing, then close.
+///
+/// Idle gaps are capped at `max_idle_ms` and scaled by `speed`. The replay
+/// never touches the PTY, so it also works after the terminal has exited.
+async fn replay_recording(
+    socket: WebSocket,
+    terminal_id: String,
+    recording: Recording,
+    params: ReplayParams,
+) {
+    let (mut ws_sender, mut ws_receiver) = socket.split();
+    let speed = params
+        .speed
+        .filter(|speed| speed.is_finite())
+        .unwrap_or(1.0)
+        .clamp(REPLAY_MIN_SPEED, REPLAY_MAX_SPEED);
+    let max_idle = Duration::from_millis(params.max_idle_ms.unwrap_or(REPLAY_DEFAULT_MAX_IDLE_MS));
+
+    tracing::info!(
+        terminal_id = %terminal_id,
+        events = recording.events.len(),
+        speed = speed,
+        "Terminal recording replay started"
+    );
+
+    let replay = async {
+        let resize = WsMessage::Resize {
+            cols: recording.width,
+            rows: recording.height,
+        };
+        let json = serde_json::to_string(&resize).map_err(axum::Error::new)?;
+        ws_sender.send(Message::Text(json.into())).await?;
+
+        let mut previous = Duration::ZERO;
+        for event in recording.events {
+            let gap = event.offset.saturating_sub(previous).min(max_idle);
+            previous = event.offset;
+            tokio::time::sleep(gap.div_f64(speed)).await;
+
+            let json = serde_json::to_string(&WsMessage::Output { data: event.data })
+                .map_err(axum::Error::new)?;
+            ws_sender.send(Message::Text(json.into())).await?;
+        }
+        ws_sender.send(Message::Close(None)).await
+    };
+
+    tokio::select! {
+        result = replay => {
+            if let Err(e) = result {
+                tracing::debug!(
+                    terminal_id = %terminal_id,
+                    error = %e,
+                    "Terminal recording replay stopped"
+                );
+            }
+        }
+        () = async {
+            while let Some(Ok(message)) = ws_receiver.next().await {
+                if matches!(message, Message::Close(_)) {
+                    break;
+                }
+            }
+        } => {
+            tracing::debug!(terminal_id = %terminal_id, "Replay client disconnected");
+        }
+    }
+}
+
 /// Handle terminal WebSocket connection with PTY.
 ///
 /// `resume_from_seq`: when `Some(n)`, replay starts from `seq > n` (G09-003 resume).
 /// When `None`, full replay from the earliest retained chunk is provided.
+///
+/// Input is only forwarded while `client_id` holds the terminal's input lease,
+/// which it receives on connect when no orchestrator runs the terminal's workflow.
 async fn handle_terminal_socket(
     socket: WebSocket,
     terminal_id: String,
     deployment: DeploymentImpl,
     resume_from_seq: Option<u64>,
+    client_id: String,
 ) {
     if let Some(seq) = resume_from_seq {
         tracing::info!(
@@ -291,6 +429,26 @@ async fn handle_terminal_socket(
         return;
     }
 
+    let Some(input_lease) = process_manager.input_lease(&terminal_id).await else {
+        let msg = WsMessage::Error {
+            message: "Terminal process not running. Please start the terminal first.".to_string(),
+        };
+        let _ = send_ws_message(&mut ws_sender, &msg).await;
+        let _ = ws_sender.close().await;
+        return;
+    };
+    let input_owner = InputOwner::client(client_id.clone());
+    // Nothing drives a standalone terminal, so the viewer types right away
+    if !has_orchestrated_workflow(&deployment, &terminal_id).await
+        && input_lease.claim_from_orchestrator(input_owner.clone())
+    {
+        tracing::debug!(
+            terminal_id = %terminal_id,
+            client_id = %client_id,
+            "Terminal input lease handed to client: no orchestrated workflow"
+        );
+    }
+
     // Subscribe to terminal output stream with seq-based resume (G09-003).
     // On reconnect, the client passes `last_seq` as a query parameter so only
     // chunks with seq > last_seq are replayed. When last_seq is None, the full
@@ -317,6 +475,8 @@ async fn handle_terminal_socket(
     // Create channels for async communication
     // WebSocket input -> PTY
     let (ws_tx_input, mut ws_rx) = mpsc::channel::<Vec<u8>>(100);
+    // Receive task -> WebSocket (lease rejections)
+    let (ws_tx_control, mut ws_rx_control) = mpsc::channel::<WsMessage>(16);
 
     // Clone terminal_id for tasks
     let terminal_id_writer = terminal_id.clone();
@@ -349,9 +509,42 @@ async fn handle_terminal_socket(
         let idle_timeout_notifier = idle_timeout_notifier.clone();
         let idle_timeout_triggered = idle_timeout_triggered.clone();
         let process_manager_output = process_manager.clone();
+        // Announce the current lease holder first, then every change
+        let mut lease_changes = input_lease.subscribe();
+        lease_changes.mark_changed();
+        let mut lease_open = true;
         tokio::spawn(async move {
             loop {
                 tokio::select! {
+                    changed = lease_changes.changed(), if lease_open => {
+                        if changed.is_err() {
+                            // Session ended; output subscription closes next
+                            lease_open = false;
+                            continue;
+                        }
+                        let msg = WsMessage::InputLease {
+                            owner: lease_changes.borrow_and_update().clone(),
+                            client_id: client_id.clone(),
+                        };
+                        if let Err(e) = send_ws_message(&mut ws_sender, &msg).await {
+                            tracing::debug!(
+                                terminal_id = %terminal_id_output,
+                                error = %e,
+                                "Failed to send input lease update to WebSocket"
+                            );
+                            break;
+                        }
+                    }
+                    Some(msg) = ws_rx_control.recv() => {
+                        if let Err(e) = send_ws_message(&mut ws_sender, &msg).await {
+                            tracing::debug!(
+                                terminal_id = %terminal_id_output,
+                                error = %e,
+                                "Failed to send control message to WebSocket"
+                            );
+                            break;
+                        }
+                    }
                     () = idle_timeout_notifier.notified() => {
                         if idle_timeout_triggered.load(Ordering::Acquire) {
                             tracing::info!(
@@ -553,6 +746,8 @@ async fn handle_terminal_socket(
     };
 
     // Spawn WebSocket receive task (WebSocket input -> PTY)
+    let recv_lease = input_lease.clone();
+    let recv_owner = input_owner.clone();
     let recv_task = tokio::spawn(async move {
         loop {
             if let Some(result) = ws_receiver.next().await {
@@ -566,6 +761,17 @@ async fn handle_terminal_socket(
                         Message::Text(text) => {
                             if let Ok(ws_msg) = serde_json::from_str::<WsMessage>(&text) {
                                 match ws_msg {
+                                    WsMessage::Input { .. }
+                                        if !recv_lease.is_held_by(&recv_owner) =>
+                                    {
+                                        let msg = WsMessage::Error {
+                                            message: format!(
+                                                "Terminal input is held by {}; take it over to type",
+                                                recv_lease.owner()
+                                            ),
+                                        };
+                                        let _ = ws_tx_control.try_send(msg);
+                                    }
                                     WsMessage::Input { data } => {
                                         // Send to PTY writer
                                         if let Err(e) =
@@ -598,6 +804,26 @@ async fn handle_terminal_socket(
                                     WsMessage::Heartbeat => {
                                         tracing::trace!("Received client heartbeat");
                                     }
+                                    WsMessage::TakeInput => {
+                                        if recv_lease.acquire(recv_owner.clone()) {
+                                            tracing::info!(
+                                                terminal_id = %terminal_id_recv,
+                                                holder = %recv_owner,
+                                                "Terminal input lease taken over"
+                                            );
+                                        }
+                                    }
+                                    WsMessage::ReleaseInput => {
+                                        if recv_lease.release(&recv_owner) {
+                                            tracing::info!(
+                                                terminal_id = %terminal_id_recv,
+                                                "Terminal input lease returned to orchestrator"
+                                            );
+                                        }
+                                    }
+                                    WsMessage::InputLease { .. } => {
+                                        tracing::warn!("Client sent unexpected InputLease message");
+                                    }
                                     WsMessage::Output { .. } => {
                                         tracing::warn!("Client sent unexpected Output message");
                                     }
@@ -724,9 +950,60 @@ async fn handle_terminal_socket(
         task.abort();
     }
 
+    // A departing holder hands input back to the orchestrator
+    if input_lease.release(&input_owner) {
+        tracing::info!(
+            terminal_id = %terminal_id,
+            "Terminal input lease returned to orchestrator after disconnect"
+        );
+    }
+
     tracing::info!("Terminal WebSocket disconnected: {}", terminal_id);
 }
 
+async fn send_ws_message(
+    sender: &mut SplitSink<WebSocket, Message>,
+    msg: &WsMessage,
+) -> Result<(), axum::Error> {
+    let json = serde_json::to_string(msg).map_err(axum::Error::new)?;
+    sender.send(Message::Text(json.into())).await
+}
+
+/// Whether an orchestrator agent is currently running the terminal's workflow.
+async fn has_orchestrated_workflow(deployment: &DeploymentImpl, terminal_id: &str) -> bool {
+    let pool = &deployment.db().pool;
+    let task_id = match Terminal::find_by_id(pool, terminal_id).await {
+        Ok(Some(terminal)) => terminal.workflow_task_id,
+        Ok(None) => return false,
+        Err(e) => {
+            tracing::warn!(
+                terminal_id = %terminal_id,
+                error = %e,
+                "Failed to query terminal for input lease assignment"
+            );
+            // Keep the orchestrator as holder when in doubt
+            return true;
+        }
+    };
+    let workflow_id = match WorkflowTask::find_by_id(pool, &task_id).await {
+        Ok(Some(task)) => task.workflow_id,
+        Ok(None) => return false,
+        Err(e) => {
+            tracing::warn!(
+                terminal_id = %terminal_id,
+                workflow_task_id = %task_id,
+                error = %e,
+                "Failed to query workflow task for input lease assignment"
+            );
+            return true;
+        }
+    };
+    deployment
+        .orchestrator_runtime()
+        .is_running(&workflow_id)
+        .await
+}
+
 async fn sync_prompt_watcher_registration(
     terminal_id: &str,
     prompt_watcher: services::services::terminal::prompt_watcher::PromptWatcher,
@@ -806,6 +1083,7 @@ async fn sync_prompt_watcher_registration(
             &workflow_id,
             &task_id,
             &session_id,
+            &terminal.cli_type_id,
             terminal.auto_confirm,
         )
         .await
@@ -900,6 +1178,34 @@ mod tests {
         assert!(matches!(msg, WsMessage::Heartbeat));
     }
 
+    #[test]
+    fn test_ws_input_lease_messages() {
+        let msg: WsMessage = serde_json::from_str(r#"{"type":"take_input"}"#).unwrap();
+        assert!(matches!(msg, WsMessage::TakeInput));
+        let msg: WsMessage = serde_json::from_str(r#"{"type":"release_input"}"#).unwrap();
+        assert!(matches!(msg, WsMessage::ReleaseInput));
+
+        let msg = WsMessage::InputLease {
+            owner: InputOwner::client("alice"),
+            client_id: "bob".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&msg).unwrap(),
+            r#"{"type":"input_lease","owner":{"kind":"client","client_id":"alice"},"client_id":"bob"}"#
+        );
+    }
+
+    #[test]
+    fn test_client_id_is_bound_to_the_connection() {
+        let first = bind_client_id(Some("alice".to_string())).unwrap();
+        let second = bind_client_id(Some("alice".to_string())).unwrap();
+        assert!(first.starts_with("alice:"));
+        assert_ne!(first, second, "each connection gets its own lease identity");
+        assert!(bind_client_id(None).is_ok());
+        assert!(bind_client_id(Some(" ".to_string())).is_err());
+        assert!(bind_client_id(Some("x".repeat(MAX_CLIENT_ID_LEN + 1))).is_err());
+    }
+
     #[test]
     fn test_validate_terminal_id_valid() {
         assert!(validate_terminal_id("550e8400-e29b-41d4-a716-446655440000").is_ok());
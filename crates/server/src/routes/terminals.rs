@@ -5,12 +5,14 @@
 use std::sync::Arc;
 
 use axum::{
-    Router,
+    Json, Router,
+    body::Body,
     extract::{Path, Query, State},
-    response::Json as ResponseJson,
-    routing::{get, post},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+    routing::{get, post, put},
 };
-use db::models::terminal::{Terminal, TerminalLog};
+use db::models::terminal::{Terminal, TerminalLog, TerminalResourceLimits};
 use deployment::Deployment;
 use serde::Deserialize;
 use services::services::{
@@ -19,69 +21,16 @@ use services::services::{
     terminal::{
         bridge::TerminalBridge,
         process::{DEFAULT_COLS, DEFAULT_ROWS},
+        recording::{self, RecordingInfo},
     },
 };
-use tokio::process::Command;
+use tokio::{fs::File, io::AsyncReadExt, process::Command};
+use tokio_util::io::ReaderStream;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-// BACKLOG-002: Runner container separation
-// ============================================================================
-// RunnerClient Terminal Spawn Configuration
-// ============================================================================
-
-/// Terminal spawn configuration for the RunnerClient abstraction layer.
-///
-/// This struct bridges the existing `SpawnCommand` format (used by ProcessManager)
-/// to the RunnerClient interface. When RunnerClient is fully integrated, terminal
-/// spawn requests will be sent via gRPC using this configuration.
-///
-/// Import path (future): `crate::services::runner_client::TerminalSpawnConfig`
-// BACKLOG-002: Runner container separation
-#[allow(dead_code)]
-pub(crate) struct TerminalSpawnConfig {
-    /// Unique terminal identifier.
-    pub terminal_id: String,
-    /// Command to execute (e.g., "claude", "codex", "gemini").
-    pub command: String,
-    /// Command-line arguments.
-    pub args: Vec<String>,
-    /// Working directory for the child process.
-    pub working_dir: std::path::PathBuf,
-    /// Environment variables to set on the child process.
-    pub env_set: std::collections::HashMap<String, String>,
-    /// Environment variable keys to remove from the inherited environment.
-    pub env_unset: Vec<String>,
-    /// Terminal width in columns.
-    pub cols: u16,
-    /// Terminal height in rows.
-    pub rows: u16,
-}
-
-/// Build TerminalSpawnConfig from CCSwitchService output.
-/// This bridges the existing SpawnCommand format to the RunnerClient interface.
-// BACKLOG-002: Runner container separation
-#[allow(dead_code)]
-fn spawn_command_to_runner_config(
-    terminal_id: &str,
-    spawn_config: &services::services::terminal::process::SpawnCommand,
-    cols: u16,
-    rows: u16,
-) -> TerminalSpawnConfig {
-    TerminalSpawnConfig {
-        terminal_id: terminal_id.to_string(),
-        command: spawn_config.command.clone(),
-        args: spawn_config.args.clone(),
-        working_dir: spawn_config.working_dir.clone(),
-        env_set: spawn_config.env.set.clone(),
-        env_unset: spawn_config.env.unset.clone(),
-        cols,
-        rows,
-    }
-}
-
 async fn broadcast_terminal_status(
     deployment: &DeploymentImpl,
     terminal: &Terminal,
@@ -225,6 +174,95 @@ pub async fn get_terminal_logs(
     Ok(ResponseJson(ApiResponse::success(logs)))
 }
 
+pub(crate) fn recordings_dir(deployment: &DeploymentImpl) -> Result<std::path::PathBuf, ApiError> {
+    deployment
+        .process_manager()
+        .recordings_dir()
+        .map(std::path::Path::to_path_buf)
+        .ok_or_else(|| ApiError::NotFound("Terminal session recording is disabled".to_string()))
+}
+
+/// List terminal recordings endpoint
+///
+/// GET /api/terminals/:id/recordings
+///
+/// Lists asciicast recordings of the terminal's PTY sessions, newest first
+pub async fn list_terminal_recordings(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<RecordingInfo>>>, ApiError> {
+    let dir = recordings_dir(&deployment)?;
+    let recordings = recording::list_recordings(&dir, &id.to_string())
+        .map_err(|e| ApiError::Internal(format!("Failed to list recordings: {e}")))?;
+
+    Ok(ResponseJson(ApiResponse::success(recordings)))
+}
+
+/// Download terminal recording endpoint
+///
+/// GET /api/terminals/:id/recordings/:session_id
+///
+/// Streams one session recording as an asciicast v2 file
+pub async fn download_terminal_recording(
+    State(deployment): State<DeploymentImpl>,
+    Path((id, session_id)): Path<(Uuid, String)>,
+) -> Result<Response, ApiError> {
+    let dir = recordings_dir(&deployment)?;
+    let path = recording::recording_path(&dir, &id.to_string(), &session_id)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let file = match File::open(&path).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(ApiError::NotFound(format!(
+                "Recording {session_id} not found for terminal {id}"
+            )));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    // A live session keeps appending; serve the bytes present right now.
+    let len = file.metadata().await?.len();
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/x-asciicast")
+        .header(header::CONTENT_LENGTH, len)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{id}-{session_id}.cast\""),
+        )
+        .body(Body::from_stream(ReaderStream::new(file.take(len))))
+        .map_err(|e| ApiError::Internal(format!("Failed to build response: {e}")))
+}
+
+/// Update terminal resource limits endpoint
+///
+/// PUT /api/terminals/:id/resource-limits
+///
+/// Replaces the terminal's resource limits; an empty policy clears them.
+/// Takes effect the next time the terminal is started.
+pub async fn update_terminal_resource_limits(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(limits): Json<TerminalResourceLimits>,
+) -> Result<ResponseJson<ApiResponse<Terminal>>, ApiError> {
+    let id = id.to_string();
+    limits.validate().map_err(ApiError::BadRequest)?;
+
+    let pool = &deployment.db().pool;
+    if Terminal::find_by_id(pool, &id).await?.is_none() {
+        return Err(ApiError::NotFound(format!("Terminal {id} not found")));
+    }
+
+    let limits = (!limits.is_empty()).then_some(limits);
+    Terminal::update_resource_limits(pool, &id, limits.as_ref()).await?;
+    let terminal = Terminal::find_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Terminal {id} not found")))?;
+
+    Ok(ResponseJson(ApiResponse::success(terminal)))
+}
+
 /// Start terminal endpoint
 ///
 /// POST /api/terminals/:id/start
@@ -363,13 +401,7 @@ pub async fn start_terminal(
         }
     };
 
-    // Spawn PTY process with configuration
-    // RUNNER_CLIENT_MIGRATION: When RunnerClient is integrated, replace:
-    //   deployment.process_manager().spawn_pty_with_config(&id, &spawn_config, cols, rows)
-    // with:
-    //   deployment.runner_client().spawn_terminal(TerminalSpawnConfig { ... })
-    // The TerminalSpawnConfig is built from CCSwitchService::build_launch_config() output.
-    // Use spawn_command_to_runner_config() to convert SpawnCommand -> TerminalSpawnConfig.
+    // Spawn PTY process with configuration (on a remote runner in remote mode)
     let handle = match deployment
         .process_manager()
         .spawn_pty_with_config(&id, &spawn_config, DEFAULT_COLS, DEFAULT_ROWS)
@@ -437,6 +469,19 @@ pub async fn start_terminal(
             "Failed to update terminal process info: {e}"
         )));
     }
+    if let Err(e) = Terminal::update_resource_report(
+        &deployment.db().pool,
+        &id,
+        handle.resource_report.as_ref(),
+    )
+    .await
+    {
+        tracing::warn!(
+            terminal_id = %id,
+            error = %e,
+            "Failed to persist terminal resource report"
+        );
+    }
 
     // Attach terminal logger for output persistence (Phase 26)
     if let Err(e) = deployment
@@ -501,6 +546,7 @@ pub async fn start_terminal(
                     &task.workflow_id,
                     &terminal.workflow_task_id,
                     &handle.session_id,
+                    &terminal.cli_type_id,
                     terminal.auto_confirm,
                 )
                 .await
@@ -550,7 +596,8 @@ pub async fn start_terminal(
         "pid": handle.pid,
         "session_id": handle.session_id,
         "status": "waiting",
-        "auto_confirm": terminal.auto_confirm
+        "auto_confirm": terminal.auto_confirm,
+        "resource_report": handle.resource_report
     }))))
 }
 
@@ -642,7 +689,6 @@ pub async fn stop_terminal(
             ))
         })?;
     // Best-effort kill in case the process is still running
-    // RUNNER_CLIENT_MIGRATION: Replace process_manager().kill_terminal() with runner_client().kill_terminal()
     if let Err(e) = deployment.process_manager().kill_terminal(&id).await {
         tracing::warn!("Failed to kill terminal {}: {}", id, e);
     }
@@ -692,7 +738,6 @@ pub async fn close_terminal(
         )));
     }
 
-    // RUNNER_CLIENT_MIGRATION: Replace process_manager().kill_terminal() with runner_client().kill_terminal()
     if let Err(e) = deployment.process_manager().kill_terminal(&id).await {
         tracing::warn!("Failed to kill terminal {} during close: {}", id, e);
     }
@@ -724,12 +769,54 @@ pub async fn close_terminal(
     Ok(ResponseJson(ApiResponse::success(terminal)))
 }
 
+/// Update terminal runner labels endpoint
+///
+/// PUT /api/terminals/:id/runner-labels
+///
+/// Replaces the labels a remote runner must carry to host the terminal; an
+/// empty list clears them. Takes effect the next time the terminal is started.
+pub async fn update_terminal_runner_labels(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(labels): Json<Vec<String>>,
+) -> Result<ResponseJson<ApiResponse<Terminal>>, ApiError> {
+    let id = id.to_string();
+    if labels.iter().any(|label| label.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Runner labels must not be empty".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    if Terminal::find_by_id(pool, &id).await?.is_none() {
+        return Err(ApiError::NotFound(format!("Terminal {id} not found")));
+    }
+
+    let labels = (!labels.is_empty()).then_some(labels);
+    Terminal::update_runner_labels(pool, &id, labels.as_deref()).await?;
+    let terminal = Terminal::find_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Terminal {id} not found")))?;
+
+    Ok(ResponseJson(ApiResponse::success(terminal)))
+}
+
 /// Terminal routes router
 ///
 /// Mounts all terminal-related API endpoints
 pub fn terminal_routes() -> Router<DeploymentImpl> {
     Router::new()
         .route("/{id}/logs", get(get_terminal_logs))
+        .route("/{id}/recordings", get(list_terminal_recordings))
+        .route(
+            "/{id}/recordings/{session_id}",
+            get(download_terminal_recording),
+        )
+        .route(
+            "/{id}/resource-limits",
+            put(update_terminal_resource_limits),
+        )
+        .route("/{id}/runner-labels", put(update_terminal_runner_labels))
         .route("/{id}/start", post(start_terminal))
         .route("/{id}/stop", post(stop_terminal))
         .route("/{id}/close", post(close_terminal))
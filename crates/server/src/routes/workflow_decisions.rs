@@ -0,0 +1,150 @@
+//! Orchestrator Decision API Routes
+//!
+//! Paginated audit trail of what the orchestrator decided for a workflow and why.
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    Workflow,
+    orchestrator_decision::{
+        DECISION_TYPE_INSTRUCTION, DECISION_TYPE_MERGE, DECISION_TYPE_PROMPT,
+        DECISION_TYPE_RECOVERY, DecisionFilter, OrchestratorDecision,
+    },
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DECISION_TYPES: [&str; 4] = [
+    DECISION_TYPE_INSTRUCTION,
+    DECISION_TYPE_PROMPT,
+    DECISION_TYPE_MERGE,
+    DECISION_TYPE_RECOVERY,
+];
+const DEFAULT_PAGE_LIMIT: i64 = 50;
+const MAX_PAGE_LIMIT: i64 = 200;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDecisionsQuery {
+    pub terminal_id: Option<String>,
+    /// instruction | prompt_decision | merge
+    pub decision_type: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// One page of the decision timeline, oldest first
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DecisionPage {
+    pub items: Vec<OrchestratorDecision>,
+    /// Number of decisions matching the filters
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET /api/workflows/:workflow_id/decisions?terminalId=&decisionType=&limit=&offset=
+async fn list_decisions(
+    State(deployment): State<DeploymentImpl>,
+    Path(workflow_id): Path<Uuid>,
+    Query(query): Query<ListDecisionsQuery>,
+) -> Result<ResponseJson<ApiResponse<DecisionPage>>, ApiError> {
+    let workflow_id = workflow_id.to_string();
+    let pool = &deployment.db().pool;
+    Workflow::find_by_id(pool, &workflow_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Workflow not found".to_string()))?;
+
+    let filter = decision_filter(&query)?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let total = OrchestratorDecision::count(pool, &workflow_id, &filter).await?;
+    let items = OrchestratorDecision::find_page(pool, &workflow_id, &filter, limit, offset).await?;
+    Ok(Json(ApiResponse::success(DecisionPage {
+        items,
+        total,
+        limit,
+        offset,
+    })))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn decision_filter(query: &ListDecisionsQuery) -> Result<DecisionFilter, ApiError> {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(ToString::to_string)
+    };
+    let decision_type = non_empty(&query.decision_type);
+    if let Some(decision_type) = &decision_type
+        && !DECISION_TYPES.contains(&decision_type.as_str())
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown decision type '{decision_type}': expected one of {DECISION_TYPES:?}"
+        )));
+    }
+    Ok(DecisionFilter {
+        terminal_id: non_empty(&query.terminal_id),
+        decision_type,
+    })
+}
+
+// ============================================================================
+// Route Definition
+// ============================================================================
+
+/// Create orchestrator decisions router (nested under /api/workflows)
+pub fn workflow_decisions_routes() -> Router<DeploymentImpl> {
+    Router::new().route("/{workflow_id}/decisions", get(list_decisions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decision_filter_validates_type_and_drops_blanks() {
+        let filter = decision_filter(&ListDecisionsQuery {
+            terminal_id: Some("  ".to_string()),
+            decision_type: Some("prompt_decision".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(filter.terminal_id, None);
+        assert_eq!(filter.decision_type.as_deref(), Some("prompt_decision"));
+
+        let err = decision_filter(&ListDecisionsQuery {
+            decision_type: Some("review".to_string()),
+            ..Default::default()
+        });
+        assert!(matches!(err, Err(ApiError::BadRequest(_))));
+    }
+}
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use services::services::orchestrator::{
     BusMessage, ProviderEvent,
-    types::{PromptDecision, PromptKind, TerminalCompletionStatus},
+    types::{LLMStreamEvent, PromptDecision, PromptKind, TerminalCompletionStatus},
 };
 use ts_rs::TS;
 use uuid::Uuid;
@@ -94,6 +94,14 @@ pub enum WsEventType {
     /// Quality gate result for a terminal checkpoint
     #[serde(rename = "quality.gate_result")]
     QualityGateResult,
+
+    /// Incremental Orchestrator LLM output (delta / reset / done)
+    #[serde(rename = "orchestrator.stream")]
+    OrchestratorStream,
+
+    /// Activity monitor escalated a stalled or looping terminal
+    #[serde(rename = "terminal.activity_escalated")]
+    TerminalActivityEscalated,
 }
 
 // ============================================================================
@@ -491,6 +499,44 @@ impl WsEvent {
                 };
                 Some((workflow_id, Self::new(event_type, payload)))
             }
+
+            BusMessage::LLMStream {
+                workflow_id,
+                stream_id,
+                event,
+            } => {
+                let (kind, text) = match event {
+                    LLMStreamEvent::Delta { text } => ("delta", Some(text)),
+                    LLMStreamEvent::Reset => ("reset", None),
+                    LLMStreamEvent::Done => ("done", None),
+                };
+                let payload = json!({
+                    "workflowId": workflow_id,
+                    "streamId": stream_id,
+                    "kind": kind,
+                    "text": text,
+                    "workflow_id": workflow_id,
+                    "stream_id": stream_id
+                });
+                Some((workflow_id, Self::new(WsEventType::OrchestratorStream, payload)))
+            }
+
+            BusMessage::TerminalActivityEscalated(event) => {
+                let payload = json!({
+                    "workflowId": &event.workflow_id,
+                    "taskId": &event.task_id,
+                    "terminalId": &event.terminal_id,
+                    "level": event.level,
+                    "reason": &event.reason,
+                    "summary": event.reason.describe(),
+                    "lastOutputSeq": event.last_output_seq,
+                    "detectedAt": event.detected_at.to_rfc3339()
+                });
+                Some((
+                    event.workflow_id,
+                    Self::new(WsEventType::TerminalActivityEscalated, payload),
+                ))
+            }
         }
     }
 }
@@ -502,7 +548,8 @@ impl WsEvent {
 #[cfg(test)]
 mod tests {
     use services::services::orchestrator::types::{
-        ArrowSelectOption, DetectedPrompt, PromptDecision, PromptKind, TerminalCompletionEvent,
+        ActivityEscalationLevel, ActivityStallReason, ArrowSelectOption, DetectedPrompt,
+        PromptDecision, PromptKind, TerminalActivityEvent, TerminalCompletionEvent,
         TerminalCompletionStatus, TerminalPromptEvent,
     };
 
@@ -912,6 +959,56 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_bus_message_llm_stream_conversion() {
+        let bus_msg = BusMessage::LLMStream {
+            workflow_id: "wf-1".to_string(),
+            stream_id: "s-1".to_string(),
+            event: LLMStreamEvent::Delta {
+                text: "Hel".to_string(),
+            },
+        };
+        let (workflow_id, event) = WsEvent::try_from_bus_message(bus_msg).unwrap();
+        assert_eq!(workflow_id, "wf-1");
+        assert_eq!(event.event_type, WsEventType::OrchestratorStream);
+        assert_eq!(event.payload["streamId"], "s-1");
+        assert_eq!(event.payload["kind"], "delta");
+        assert_eq!(event.payload["text"], "Hel");
+
+        let done = BusMessage::LLMStream {
+            workflow_id: "wf-1".to_string(),
+            stream_id: "s-1".to_string(),
+            event: LLMStreamEvent::Done,
+        };
+        let (_, event) = WsEvent::try_from_bus_message(done).unwrap();
+        assert_eq!(event.payload["kind"], "done");
+        assert!(event.payload["text"].is_null());
+    }
+
+    #[test]
+    fn test_bus_message_terminal_activity_conversion() {
+        let bus_msg = BusMessage::TerminalActivityEscalated(TerminalActivityEvent {
+            terminal_id: "term-1".to_string(),
+            workflow_id: "wf-1".to_string(),
+            task_id: "task-1".to_string(),
+            session_id: "session-1".to_string(),
+            level: ActivityEscalationLevel::Restart,
+            reason: ActivityStallReason::Loop {
+                tool_call: "Bash(npm test)".to_string(),
+                repeats: 6,
+            },
+            last_output_seq: 42,
+            detected_at: Utc::now(),
+        });
+        let (workflow_id, event) = WsEvent::try_from_bus_message(bus_msg).unwrap();
+        assert_eq!(workflow_id, "wf-1");
+        assert_eq!(event.event_type, WsEventType::TerminalActivityEscalated);
+        assert_eq!(event.payload["level"], "restart");
+        assert_eq!(event.payload["reason"]["kind"], "loop");
+        assert_eq!(event.payload["reason"]["tool_call"], "Bash(npm test)");
+        assert_eq!(event.payload["lastOutputSeq"], 42);
+    }
+
     #[test]
     fn test_all_event_types_serialize() {
         let types = vec![
@@ -925,6 +1022,8 @@ mod tests {
             WsEventType::SystemHeartbeat,
             WsEventType::SystemLagged,
             WsEventType::SystemError,
+            WsEventType::OrchestratorStream,
+            WsEventType::TerminalActivityEscalated,
         ];
 
         for event_type in types {
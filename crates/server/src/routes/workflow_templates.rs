@@ -0,0 +1,300 @@
+//! Workflow Template API Routes
+//!
+//! Export an existing workflow as a template document, store templates, and
+//! instantiate them into new workflows.
+
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::Utc;
+use db::models::{Terminal, Workflow, WorkflowCommand, WorkflowTask, WorkflowTemplate};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::workflow_template::{TemplateFormat, WorkflowTemplateDocument};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::{workflows::create_workflow, workflows_dto::WorkflowDetailDto},
+};
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateFormatQuery {
+    #[serde(default)]
+    pub format: TemplateFormat,
+}
+
+/// Create/update body: a template document in JSON or YAML
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveWorkflowTemplateRequest {
+    pub content: String,
+}
+
+/// Create a template from an existing workflow
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTemplateFromWorkflowRequest {
+    /// Template name (defaults to the workflow name)
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateWorkflowTemplateRequest {
+    pub project_id: String,
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+    /// Required when the template configures a Main Agent
+    pub orchestrator_api_key: Option<String>,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET /api/workflows/templates
+async fn list_templates(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkflowTemplate>>>, ApiError> {
+    let templates = WorkflowTemplate::find_all(&deployment.db().pool).await?;
+    Ok(Json(ApiResponse::success(templates)))
+}
+
+/// GET /api/workflows/templates/:template_id
+async fn get_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<WorkflowTemplate>>, ApiError> {
+    let template = load_template(&deployment, &template_id).await?;
+    Ok(Json(ApiResponse::success(template)))
+}
+
+/// POST /api/workflows/templates
+async fn create_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(req): Json<SaveWorkflowTemplateRequest>,
+) -> Result<ResponseJson<ApiResponse<WorkflowTemplate>>, ApiError> {
+    let document = parse_document(&req.content)?;
+    let template = insert_template(&deployment, &document, None).await?;
+    Ok(Json(ApiResponse::success(template)))
+}
+
+/// PUT /api/workflows/templates/:template_id
+/// Replace the document; bumps the template version.
+async fn update_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<String>,
+    Json(req): Json<SaveWorkflowTemplateRequest>,
+) -> Result<ResponseJson<ApiResponse<WorkflowTemplate>>, ApiError> {
+    let document = parse_document(&req.content)?;
+    let content = serde_json::to_string(&document)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize template: {e}")))?;
+    let template = WorkflowTemplate::update_content(
+        &deployment.db().pool,
+        &template_id,
+        &document.name,
+        document.description.as_deref(),
+        schema_version_i32(&document),
+        &content,
+    )
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Workflow template not found: {template_id}")))?;
+    Ok(Json(ApiResponse::success(template)))
+}
+
+/// DELETE /api/workflows/templates/:template_id
+async fn delete_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<serde_json::Value>>, ApiError> {
+    if !WorkflowTemplate::delete(&deployment.db().pool, &template_id).await? {
+        return Err(ApiError::NotFound(format!(
+            "Workflow template not found: {template_id}"
+        )));
+    }
+    Ok(Json(ApiResponse::success(
+        serde_json::json!({"deleted": template_id}),
+    )))
+}
+
+/// GET /api/workflows/templates/:template_id/document?format=json|yaml
+async fn get_template_document(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<String>,
+    Query(query): Query<TemplateFormatQuery>,
+) -> Result<ResponseJson<ApiResponse<String>>, ApiError> {
+    let template = load_template(&deployment, &template_id).await?;
+    let document = parse_document(&template.content)?;
+    Ok(Json(ApiResponse::success(serialize_document(&document, query.format)?)))
+}
+
+/// POST /api/workflows/templates/:template_id/instantiate
+/// Render the template with parameters and create a new workflow from it.
+async fn instantiate_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<String>,
+    Json(req): Json<InstantiateWorkflowTemplateRequest>,
+) -> Result<ResponseJson<ApiResponse<WorkflowDetailDto>>, ApiError> {
+    let template = load_template(&deployment, &template_id).await?;
+    let document = parse_document(&template.content)?;
+    let create_request = document
+        .instantiate(
+            &req.project_id,
+            &req.params,
+            req.orchestrator_api_key.as_deref(),
+        )
+        .map_err(|e| ApiError::BadRequest(format!("Failed to instantiate template: {e}")))?;
+
+    tracing::info!(
+        template_id = %template.id,
+        template_version = template.version,
+        project_id = %req.project_id,
+        "Instantiating workflow template"
+    );
+    create_workflow(State(deployment), Json(create_request)).await
+}
+
+/// GET /api/workflows/:workflow_id/export?format=json|yaml
+/// Export a workflow's task/terminal graph as a template document.
+async fn export_workflow(
+    State(deployment): State<DeploymentImpl>,
+    Path(workflow_id): Path<Uuid>,
+    Query(query): Query<TemplateFormatQuery>,
+) -> Result<ResponseJson<ApiResponse<String>>, ApiError> {
+    let document = export_document(&deployment, &workflow_id.to_string()).await?;
+    Ok(Json(ApiResponse::success(serialize_document(&document, query.format)?)))
+}
+
+/// POST /api/workflows/:workflow_id/templates
+/// Export a workflow and store the result as a new template.
+async fn create_template_from_workflow(
+    State(deployment): State<DeploymentImpl>,
+    Path(workflow_id): Path<Uuid>,
+    Json(req): Json<CreateTemplateFromWorkflowRequest>,
+) -> Result<ResponseJson<ApiResponse<WorkflowTemplate>>, ApiError> {
+    let workflow_id = workflow_id.to_string();
+    let mut document = export_document(&deployment, &workflow_id).await?;
+    if let Some(name) = req.name.filter(|name| !name.trim().is_empty()) {
+        document.name = name;
+    }
+    if req.description.is_some() {
+        document.description = req.description;
+    }
+    let template = insert_template(&deployment, &document, Some(workflow_id)).await?;
+    Ok(Json(ApiResponse::success(template)))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+async fn load_template(
+    deployment: &DeploymentImpl,
+    template_id: &str,
+) -> Result<WorkflowTemplate, ApiError> {
+    WorkflowTemplate::find_by_id(&deployment.db().pool, template_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Workflow template not found: {template_id}")))
+}
+
+fn parse_document(content: &str) -> Result<WorkflowTemplateDocument, ApiError> {
+    WorkflowTemplateDocument::parse(content).map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+fn serialize_document(
+    document: &WorkflowTemplateDocument,
+    format: TemplateFormat,
+) -> Result<String, ApiError> {
+    document
+        .serialize(format)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize template: {e}")))
+}
+
+fn schema_version_i32(document: &WorkflowTemplateDocument) -> i32 {
+    i32::try_from(document.schema_version).unwrap_or(i32::MAX)
+}
+
+async fn export_document(
+    deployment: &DeploymentImpl,
+    workflow_id: &str,
+) -> Result<WorkflowTemplateDocument, ApiError> {
+    let pool = &deployment.db().pool;
+    let workflow = Workflow::find_by_id(pool, workflow_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Workflow not found".to_string()))?;
+
+    let mut tasks = Vec::new();
+    for task in WorkflowTask::find_by_workflow(pool, workflow_id).await? {
+        let terminals = Terminal::find_by_task(pool, &task.id).await?;
+        tasks.push((task, terminals));
+    }
+    let commands = WorkflowCommand::find_by_workflow(pool, workflow_id).await?;
+
+    Ok(WorkflowTemplateDocument::from_workflow(
+        &workflow, &tasks, &commands,
+    ))
+}
+
+async fn insert_template(
+    deployment: &DeploymentImpl,
+    document: &WorkflowTemplateDocument,
+    source_workflow_id: Option<String>,
+) -> Result<WorkflowTemplate, ApiError> {
+    let content = serde_json::to_string(document)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize template: {e}")))?;
+    let now = Utc::now();
+    let template = WorkflowTemplate {
+        id: Uuid::new_v4().to_string(),
+        name: document.name.clone(),
+        description: document.description.clone(),
+        schema_version: schema_version_i32(document),
+        version: 1,
+        content,
+        source_workflow_id,
+        created_at: now,
+        updated_at: now,
+    };
+    Ok(WorkflowTemplate::create(&deployment.db().pool, &template).await?)
+}
+
+// ============================================================================
+// Route Definition
+// ============================================================================
+
+/// Create workflow templates router (nested under /api/workflows)
+pub fn workflow_templates_routes() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/templates", get(list_templates).post(create_template))
+        .route(
+            "/templates/{template_id}",
+            get(get_template)
+                .put(update_template)
+                .delete(delete_template),
+        )
+        .route(
+            "/templates/{template_id}/document",
+            get(get_template_document),
+        )
+        .route(
+            "/templates/{template_id}/instantiate",
+            post(instantiate_template),
+        )
+        .route("/{workflow_id}/export", get(export_workflow))
+        .route(
+            "/{workflow_id}/templates",
+            post(create_template_from_workflow),
+        )
+}
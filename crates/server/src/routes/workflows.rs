@@ -1,7 +1,7 @@
 //! Workflow API Routes
 
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     path::PathBuf,
     sync::Arc,
     time::{Duration, Instant},
@@ -17,7 +17,7 @@ use axum::{
 use chrono::Utc;
 use db::models::{
     CliType, CreateWorkflowRequest, InlineModelConfig, ModelConfig, SlashCommandPreset, Terminal,
-    Workflow, WorkflowCommand, WorkflowOrchestratorCommand, WorkflowOrchestratorMessage,
+    TerminalResourceLimits, Workflow, WorkflowActivityPolicyRequest, WorkflowBudgetRequest, WorkflowCommand, WorkflowOrchestratorCommand, WorkflowOrchestratorMessage,
     WorkflowTask,
     project::Project,
 };
@@ -28,7 +28,12 @@ use services::services::{
     cc_switch::CCSwitchService,
     config::Config as AppConfig,
     git::GitServiceError,
-    orchestrator::{BusMessage, OrchestratorRuntime, TerminalCoordinator, constants::WORKFLOW_STATUS_PAUSED},
+    orchestrator::{
+        BusMessage, OrchestratorRuntime, TerminalCoordinator,
+        budget::is_budget_pause_reason,
+        constants::{PAUSE_REASON_USER_REQUESTED, WORKFLOW_STATUS_PAUSED},
+        task_graph,
+    },
     terminal::TerminalLauncher,
 };
 use once_cell::sync::Lazy;
@@ -39,11 +44,15 @@ use uuid::Uuid;
 
 // Import DTOs
 use crate::routes::terminals::start_terminal;
-use crate::routes::workflows_dto::{TerminalDto, WorkflowDetailDto, WorkflowListItemDto};
+use crate::routes::workflows_dto::{
+    TerminalDto, WorkflowActivityPolicyDto, WorkflowDetailDto, WorkflowListItemDto,
+    WorkflowUsageDto,
+};
 use crate::{DeploymentImpl, error::ApiError};
 
+use db::models::workflow::CreateWorkflowTaskRequest;
 #[cfg(test)]
-use db::models::workflow::{CreateTerminalRequest, CreateWorkflowTaskRequest};
+use db::models::workflow::CreateTerminalRequest;
 
 // ============================================================================
 // Request/Response Types
@@ -96,6 +105,9 @@ pub struct CreateRuntimeTaskRequest {
     pub description: Option<String>,
     pub branch: Option<String>,
     pub order_index: Option<i32>,
+    /// IDs of existing tasks in this workflow that must complete first
+    #[serde(default)]
+    pub depends_on: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -111,6 +123,10 @@ pub struct CreateRuntimeTerminalRequest {
     #[serde(default = "default_runtime_terminal_auto_confirm")]
     pub auto_confirm: bool,
     #[serde(default)]
+    pub resource_limits: Option<TerminalResourceLimits>,
+    #[serde(default)]
+    pub runner_labels: Option<Vec<String>>,
+    #[serde(default)]
     pub start_immediately: bool,
 }
 
@@ -178,6 +194,9 @@ pub fn workflows_routes() -> Router<DeploymentImpl> {
         .route("/{workflow_id}/pause", post(pause_workflow))
         .route("/{workflow_id}/resume", post(resume_workflow))
         .route("/{workflow_id}/stop", post(stop_workflow))
+        .route("/{workflow_id}/usage", get(get_workflow_usage))
+        .route("/{workflow_id}/budget", put(update_workflow_budget))
+        .route("/{workflow_id}/activity-policy", put(update_workflow_activity_policy))
         .route(
             "/{workflow_id}/prompts/respond",
             post(submit_prompt_response),
@@ -591,6 +610,14 @@ pub fn validate_create_request(req: &CreateWorkflowRequest) -> Result<(), ApiErr
                     "task[{task_index}].terminal[{terminal_index}].modelConfigId is required"
                 )));
             }
+
+            if let Some(limits) = &terminal.resource_limits {
+                limits.validate().map_err(|e| {
+                    ApiError::BadRequest(format!(
+                        "task[{task_index}].terminal[{terminal_index}].resourceLimits: {e}"
+                    ))
+                })?;
+            }
         }
     }
 
@@ -606,6 +633,16 @@ pub fn validate_create_request(req: &CreateWorkflowRequest) -> Result<(), ApiErr
         ));
     }
 
+    validate_task_dependencies(&req.tasks)?;
+
+    if let Some(ref budget) = req.budget {
+        validate_budget(budget)?;
+    }
+
+    if let Some(ref policy) = req.activity_policy {
+        validate_activity_policy(policy)?;
+    }
+
     // Validate commands if provided
     if let Some(ref commands) = req.commands {
         for (cmd_index, cmd) in commands.iter().enumerate() {
@@ -631,6 +668,89 @@ pub fn validate_create_request(req: &CreateWorkflowRequest) -> Result<(), ApiErr
     Ok(())
 }
 
+/// Budget limits must be positive when set.
+fn validate_budget(budget: &WorkflowBudgetRequest) -> Result<(), ApiError> {
+    if budget.max_tokens.is_some_and(|v| v <= 0) {
+        return Err(ApiError::BadRequest("budget.maxTokens must be positive".to_string()));
+    }
+    if budget
+        .max_cost_usd
+        .is_some_and(|v| !v.is_finite() || v <= 0.0)
+    {
+        return Err(ApiError::BadRequest("budget.maxCostUsd must be positive".to_string()));
+    }
+    if budget.max_duration_secs.is_some_and(|v| v <= 0) {
+        return Err(ApiError::BadRequest(
+            "budget.maxDurationSecs must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Activity thresholds must not be negative; 0 disables a check.
+fn validate_activity_policy(policy: &WorkflowActivityPolicyRequest) -> Result<(), ApiError> {
+    if policy.stall_timeout_secs.is_some_and(|v| v < 0) {
+        return Err(ApiError::BadRequest(
+            "activityPolicy.stallTimeoutSecs must not be negative".to_string(),
+        ));
+    }
+    if policy.loop_threshold.is_some_and(|v| v < 0 || v == 1) {
+        return Err(ApiError::BadRequest(
+            "activityPolicy.loopThreshold must be 0 (disabled) or at least 2".to_string(),
+        ));
+    }
+    if policy.escalation_interval_secs.is_some_and(|v| v <= 0) {
+        return Err(ApiError::BadRequest(
+            "activityPolicy.escalationIntervalSecs must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `dependsOn` must reference other tasks of the same request by `id`, without cycles.
+fn validate_task_dependencies(tasks: &[CreateWorkflowTaskRequest]) -> Result<(), ApiError> {
+    if tasks.iter().all(|task| task.depends_on.is_empty()) {
+        return Ok(());
+    }
+
+    let mut seen_ids = HashSet::new();
+    for (task_index, task) in tasks.iter().enumerate() {
+        if let Some(id) = task.id.as_deref()
+            && !seen_ids.insert(id)
+        {
+            return Err(ApiError::BadRequest(format!(
+                "task[{task_index}].id '{id}' is not unique"
+            )));
+        }
+    }
+
+    for (task_index, task) in tasks.iter().enumerate() {
+        for dep in &task.depends_on {
+            if task.id.as_deref() == Some(dep.as_str()) {
+                return Err(ApiError::BadRequest(format!(
+                    "task[{task_index}].dependsOn must not reference the task itself"
+                )));
+            }
+            if !seen_ids.contains(dep.as_str()) {
+                return Err(ApiError::BadRequest(format!(
+                    "task[{task_index}].dependsOn references unknown task '{dep}'"
+                )));
+            }
+        }
+    }
+
+    let nodes = tasks
+        .iter()
+        .filter_map(|task| Some((task.id.as_deref()?, task.depends_on.as_slice())));
+    if let Some(cycle) = task_graph::find_cycle(nodes) {
+        return Err(ApiError::BadRequest(format!(
+            "task dependencies contain a cycle: {}",
+            cycle.join(" -> ")
+        )));
+    }
+    Ok(())
+}
+
 /// Validate CLI types and model configs exist in database
 /// If model_config_id doesn't exist but inline model_config is provided,
 /// automatically create a new ModelConfig record.
@@ -784,7 +904,7 @@ async fn list_workflows(
 
 /// POST /api/workflows
 /// Create workflow
-async fn create_workflow(
+pub async fn create_workflow(
     State(deployment): State<DeploymentImpl>,
     Json(req): Json<CreateWorkflowRequest>,
 ) -> Result<ResponseJson<ApiResponse<WorkflowDetailDto>>, ApiError> {
@@ -824,6 +944,8 @@ async fn create_workflow(
     // is provided (the wizard always sends it for the LLM key).
     let is_diy = req.execution_mode == "diy";
     let is_orchestrator_enabled = !is_diy && req.orchestrator_config.is_some();
+    let budget = req.budget.clone().unwrap_or_default();
+    let activity_policy = req.activity_policy.clone().unwrap_or_default();
     let mut workflow = Workflow {
         id: workflow_id.clone(),
         project_id,
@@ -857,10 +979,19 @@ async fn create_workflow(
         created_at: now,
         updated_at: now,
         pause_reason: None,
+        budget_max_tokens: budget.max_tokens,
+        budget_max_cost_usd: budget.max_cost_usd,
+        budget_max_duration_secs: budget.max_duration_secs,
+        activity_stall_timeout_secs: activity_policy.stall_timeout_secs,
+        activity_loop_threshold: activity_policy.loop_threshold,
+        activity_escalation_interval_secs: activity_policy.escalation_interval_secs,
     };
 
-    // Encrypt and store API key if provided
-    if let Some(orch_config) = &req.orchestrator_config {
+    // Encrypt and store API key if provided. Local servers run without a key,
+    // and encrypting an empty one would needlessly require the encryption key.
+    if let Some(orch_config) = &req.orchestrator_config
+        && !orch_config.api_key.is_empty()
+    {
         workflow
             .set_api_key(&orch_config.api_key)
             .map_err(|e| ApiError::BadRequest(format!("Failed to encrypt API key: {e}")))?;
@@ -892,8 +1023,17 @@ async fn create_workflow(
         repo_path_str.map(std::path::PathBuf::from)
     };
 
-    for task_req in &req.tasks {
-        let task_id = Uuid::new_v4().to_string();
+    // Tasks in the request reference each other (`dependsOn`) by their request `id`.
+    let task_ids: Vec<String> = req.tasks.iter().map(|_| Uuid::new_v4().to_string()).collect();
+    let task_id_by_request_id: HashMap<&str, &str> = req
+        .tasks
+        .iter()
+        .zip(&task_ids)
+        .filter_map(|(task_req, task_id)| Some((task_req.id.as_deref()?, task_id.as_str())))
+        .collect();
+
+    for (task_req, task_id) in req.tasks.iter().zip(&task_ids) {
+        let task_id = task_id.clone();
 
         // Generate branch name using slugify with conflict detection
         let branch = if let Some(custom_branch) = &task_req.branch {
@@ -971,6 +1111,12 @@ async fn create_workflow(
             branch,
             status: "pending".to_string(),
             order_index: task_req.order_index,
+            depends_on: task_req
+                .depends_on
+                .iter()
+                .filter_map(|dep| task_id_by_request_id.get(dep.as_str()))
+                .map(|task_id| (*task_id).to_string())
+                .collect(),
             started_at: None,
             completed_at: None,
             created_at: now,
@@ -997,6 +1143,9 @@ async fn create_workflow(
                 execution_process_id: None,
                 vk_session_id: None,
                 auto_confirm: terminal_req.auto_confirm,
+                resource_limits: terminal_req.resource_limits.clone(),
+                resource_report: None,
+                runner_labels: terminal_req.runner_labels.clone(),
                 last_commit_hash: None,
                 last_commit_message: None,
                 started_at: None,
@@ -1443,6 +1592,7 @@ async fn refresh_prompt_watcher_registrations(deployment: &DeploymentImpl, workf
                 &resolved_workflow_id,
                 &terminal.workflow_task_id,
                 &session_id,
+                &terminal.cli_type_id,
                 terminal.auto_confirm,
             )
             .await
@@ -2117,15 +2267,39 @@ async fn pause_workflow(
     )
     .await?;
 
-    // Kill PTY processes and unregister prompt watchers
-    let terminals =
-        cleanup_workflow_terminals(&deployment, &workflow_id, "pausing workflow").await?;
+    // Mark workflow as paused. CAS miss means the orchestrator already paused
+    // it (e.g. budget exceeded) — keep that reason.
+    if !Workflow::set_paused(&deployment.db().pool, &workflow_id, PAUSE_REASON_USER_REQUESTED)
+        .await?
+    {
+        tracing::info!(
+            workflow_id = %workflow_id,
+            "Workflow left 'running' before manual pause; keeping existing pause state"
+        );
+    }
 
-    // Mark workflow as paused
-    Workflow::update_status(&deployment.db().pool, &workflow_id, WORKFLOW_STATUS_PAUSED).await?;
+    reset_paused_workflow_execution(&deployment, &workflow_id, "pausing workflow").await?;
+
+    tracing::info!(
+        workflow_id = %workflow_id,
+        "Workflow paused with terminal cleanup and cascaded status updates"
+    );
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Kill terminal processes and reset running tasks/terminals so a paused
+/// workflow can be re-dispatched on resume.
+async fn reset_paused_workflow_execution(
+    deployment: &DeploymentImpl,
+    workflow_id: &str,
+    action: &str,
+) -> Result<(), ApiError> {
+    // Kill PTY processes and unregister prompt watchers
+    let terminals = cleanup_workflow_terminals(deployment, workflow_id, action).await?;
 
     // Cascade: reset running tasks to pending so they can be re-dispatched on resume
-    let tasks = WorkflowTask::find_by_workflow(&deployment.db().pool, &workflow_id).await?;
+    let tasks = WorkflowTask::find_by_workflow(&deployment.db().pool, workflow_id).await?;
     for task in &tasks {
         if task.status == "running" {
             WorkflowTask::update_status(&deployment.db().pool, &task.id, "pending").await?;
@@ -2142,13 +2316,7 @@ async fn pause_workflow(
             _ => {}
         }
     }
-
-    tracing::info!(
-        workflow_id = %workflow_id,
-        "Workflow paused with terminal cleanup and cascaded status updates"
-    );
-
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(())
 }
 
 /// POST /api/workflows/:workflow_id/resume
@@ -2176,6 +2344,17 @@ async fn resume_workflow(
         )));
     }
 
+    // A budget pause only stops the orchestrator; terminals may still be running
+    // their last instruction. Reset them the same way a manual pause does.
+    if workflow
+        .pause_reason
+        .as_deref()
+        .is_some_and(is_budget_pause_reason)
+    {
+        reset_paused_workflow_execution(&deployment, &workflow_id, "resuming budget-paused workflow")
+            .await?;
+    }
+
     // Check terminal readiness before resuming (mirrors start_workflow self-heal logic)
     {
         let terminals =
@@ -2245,6 +2424,100 @@ async fn resume_workflow(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// GET /api/workflows/:workflow_id/usage
+/// Orchestrator LLM usage, spend and budget for a workflow
+async fn get_workflow_usage(
+    State(deployment): State<DeploymentImpl>,
+    Path(workflow_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<WorkflowUsageDto>>, ApiError> {
+    let workflow_id = workflow_id.to_string();
+    let workflow = Workflow::find_by_id(&deployment.db().pool, &workflow_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Workflow not found".to_string()))?;
+
+    let usage = deployment
+        .orchestrator_runtime()
+        .get_workflow_usage(&workflow_id)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to load workflow usage: {e}")))?;
+
+    Ok(ResponseJson(ApiResponse::success(WorkflowUsageDto::from_workflow(
+        &workflow,
+        usage.as_ref(),
+    ))))
+}
+
+/// PUT /api/workflows/:workflow_id/budget
+/// Replace the orchestrator budget (omitted fields become unlimited)
+///
+/// Rejected while running: the live orchestrator keeps the budget it started
+/// with. Raise the budget of a budget-paused workflow, then resume it.
+async fn update_workflow_budget(
+    State(deployment): State<DeploymentImpl>,
+    Path(workflow_id): Path<Uuid>,
+    Json(req): Json<WorkflowBudgetRequest>,
+) -> Result<ResponseJson<ApiResponse<WorkflowUsageDto>>, ApiError> {
+    let workflow_uuid = workflow_id;
+    let workflow_id = workflow_id.to_string();
+    validate_budget(&req)?;
+
+    let workflow = Workflow::find_by_id(&deployment.db().pool, &workflow_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Workflow not found".to_string()))?;
+    if workflow.status == "running" {
+        return Err(ApiError::Conflict(
+            "Pause the workflow before changing its budget".to_string(),
+        ));
+    }
+
+    Workflow::update_budget(
+        &deployment.db().pool,
+        &workflow_id,
+        req.max_tokens,
+        req.max_cost_usd,
+        req.max_duration_secs,
+    )
+    .await?;
+
+    get_workflow_usage(State(deployment), Path(workflow_uuid)).await
+}
+
+/// PUT /api/workflows/:workflow_id/activity-policy
+/// Replace the terminal activity monitor thresholds (omitted fields use defaults)
+///
+/// Applies to running workflows on the monitor's next check.
+async fn update_workflow_activity_policy(
+    State(deployment): State<DeploymentImpl>,
+    Path(workflow_id): Path<Uuid>,
+    Json(req): Json<WorkflowActivityPolicyRequest>,
+) -> Result<ResponseJson<ApiResponse<WorkflowActivityPolicyDto>>, ApiError> {
+    let workflow_id = workflow_id.to_string();
+    validate_activity_policy(&req)?;
+
+    if Workflow::find_by_id(&deployment.db().pool, &workflow_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::NotFound("Workflow not found".to_string()));
+    }
+
+    Workflow::update_activity_policy(
+        &deployment.db().pool,
+        &workflow_id,
+        req.stall_timeout_secs,
+        req.loop_threshold,
+        req.escalation_interval_secs,
+    )
+    .await?;
+
+    let workflow = Workflow::find_by_id(&deployment.db().pool, &workflow_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Workflow not found".to_string()))?;
+    Ok(ResponseJson(ApiResponse::success(
+        WorkflowActivityPolicyDto::from_workflow(&workflow),
+    )))
+}
+
 /// POST /api/workflows/:workflow_id/stop
 /// Stop a workflow and mark as cancelled
 async fn stop_workflow(
@@ -2489,6 +2762,16 @@ async fn create_runtime_task(
         candidate
     };
 
+    if let Some(unknown) = req
+        .depends_on
+        .iter()
+        .find(|dep| !existing_tasks.iter().any(|task| &task.id == *dep))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "dependsOn references unknown task {unknown}"
+        )));
+    }
+
     let now = chrono::Utc::now();
     let task = WorkflowTask {
         id: Uuid::new_v4().to_string(),
@@ -2499,6 +2782,7 @@ async fn create_runtime_task(
         branch,
         status: "pending".to_string(),
         order_index,
+        depends_on: req.depends_on,
         started_at: None,
         completed_at: None,
         created_at: now,
@@ -2548,6 +2832,11 @@ async fn create_runtime_terminal(
     if model_config_id.is_empty() {
         return Err(ApiError::BadRequest("modelConfigId is required".to_string()));
     }
+    if let Some(limits) = &req.resource_limits {
+        limits
+            .validate()
+            .map_err(|e| ApiError::BadRequest(format!("resourceLimits: {e}")))?;
+    }
 
     let cli_exists = CliType::find_by_id(&deployment.db().pool, cli_type_id)
         .await
@@ -2607,6 +2896,9 @@ async fn create_runtime_terminal(
         execution_process_id: None,
         vk_session_id: None,
         auto_confirm: req.auto_confirm,
+        resource_limits: req.resource_limits,
+        resource_report: None,
+        runner_labels: req.runner_labels,
         last_commit_hash: None,
         last_commit_message: None,
         started_at: None,
@@ -3668,6 +3960,7 @@ mod workflow_guard_tests {
             branch: "workflow/test/task".to_string(),
             status: status.to_string(),
             order_index: 0,
+            depends_on: Vec::new(),
             started_at: None,
             completed_at: None,
             created_at: Utc::now(),
@@ -3818,12 +4111,15 @@ mod create_request_validation_tests {
             merge_terminal_config: minimal_terminal_config(),
             target_branch: Some("main".to_string()),
             git_watcher_enabled: Some(true),
+            budget: None,
+            activity_policy: None,
             tasks: vec![CreateWorkflowTaskRequest {
                 id: None,
                 name: "Task 1".to_string(),
                 description: None,
                 branch: None,
                 order_index: 0,
+                depends_on: Vec::new(),
                 terminals: vec![CreateTerminalRequest {
                     id: None,
                     cli_type_id: "cli-test".to_string(),
@@ -3835,6 +4131,8 @@ mod create_request_validation_tests {
                     role_description: None,
                     order_index: 0,
                     auto_confirm: true,
+                    resource_limits: None,
+                    runner_labels: None,
                 }],
             }],
         }
@@ -3894,6 +4192,92 @@ mod create_request_validation_tests {
             .expect_err("expected missing orchestrator_config error");
         assert!(matches!(error, ApiError::BadRequest(_)));
     }
+
+    #[test]
+    fn activity_policy_rejects_negative_and_degenerate_thresholds() {
+        let mut request = minimal_diy_request();
+        request.activity_policy = Some(WorkflowActivityPolicyRequest {
+            stall_timeout_secs: Some(0),
+            loop_threshold: Some(0),
+            escalation_interval_secs: Some(60),
+        });
+        assert!(validate_create_request(&request).is_ok());
+
+        for policy in [
+            WorkflowActivityPolicyRequest {
+                stall_timeout_secs: Some(-1),
+                ..Default::default()
+            },
+            WorkflowActivityPolicyRequest {
+                loop_threshold: Some(1),
+                ..Default::default()
+            },
+            WorkflowActivityPolicyRequest {
+                escalation_interval_secs: Some(0),
+                ..Default::default()
+            },
+        ] {
+            request.activity_policy = Some(policy);
+            assert!(matches!(
+                validate_create_request(&request),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn terminal_resource_limits_reject_zero_values() {
+        let mut request = minimal_diy_request();
+        request.tasks[0].terminals[0].resource_limits = Some(TerminalResourceLimits {
+            memory_mb: Some(2048),
+            network_disabled: true,
+            ..Default::default()
+        });
+        assert!(validate_create_request(&request).is_ok());
+
+        request.tasks[0].terminals[0].resource_limits = Some(TerminalResourceLimits {
+            max_processes: Some(0),
+            ..Default::default()
+        });
+        let Err(ApiError::BadRequest(message)) = validate_create_request(&request) else {
+            panic!("zero process limit must be rejected");
+        };
+        assert!(message.contains("task[0].terminal[0].resourceLimits: maxProcesses"));
+    }
+
+    fn request_with_dependencies(edges: &[(&str, &[&str])]) -> CreateWorkflowRequest {
+        let mut request = minimal_diy_request();
+        request.tasks.clear();
+        for (id, depends_on) in edges {
+            let mut task = minimal_diy_request().tasks.remove(0);
+            task.id = Some((*id).to_string());
+            task.name = format!("Task {id}");
+            task.depends_on = depends_on.iter().map(ToString::to_string).collect();
+            request.tasks.push(task);
+        }
+        request
+    }
+
+    #[test]
+    fn task_dependencies_accept_dag() {
+        let request = request_with_dependencies(&[("a", &[]), ("b", &["a"]), ("c", &["a", "b"])]);
+        validate_create_request(&request).expect("DAG dependencies should be valid");
+    }
+
+    #[test]
+    fn task_dependencies_reject_unknown_and_cyclic_references() {
+        let unknown = request_with_dependencies(&[("a", &["missing"])]);
+        assert!(matches!(
+            validate_create_request(&unknown),
+            Err(ApiError::BadRequest(message)) if message.contains("unknown task 'missing'")
+        ));
+
+        let cyclic = request_with_dependencies(&[("a", &["c"]), ("b", &["a"]), ("c", &["b"])]);
+        assert!(matches!(
+            validate_create_request(&cyclic),
+            Err(ApiError::BadRequest(message)) if message.contains("a -> c -> b -> a")
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -3937,7 +4321,13 @@ mod recovery_response_tests {
                 completed_at TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                pause_reason TEXT
+                pause_reason TEXT,
+                budget_max_tokens INTEGER,
+                budget_max_cost_usd REAL,
+                budget_max_duration_secs INTEGER,
+                activity_stall_timeout_secs INTEGER,
+                activity_loop_threshold INTEGER,
+                activity_escalation_interval_secs INTEGER
             )
             ",
         )
@@ -3975,6 +4365,12 @@ mod recovery_response_tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             pause_reason: None,
+            budget_max_tokens: None,
+            budget_max_cost_usd: None,
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
         };
         Workflow::create(&pool, &workflow).await.unwrap();
 
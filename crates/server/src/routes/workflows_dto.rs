@@ -37,6 +37,10 @@ pub struct WorkflowDetailDto {
     pub merge_terminal_model_id: Option<String>,
     pub target_branch: String,
     pub git_watcher_enabled: bool,
+    /// Why the workflow is paused (e.g. "user_requested", "budget_cost_exceeded")
+    pub pause_reason: Option<String>,
+    pub budget: WorkflowBudgetDto,
+    pub activity_policy: WorkflowActivityPolicyDto,
 
     // Timestamps
     pub ready_at: Option<String>,
@@ -50,6 +54,46 @@ pub struct WorkflowDetailDto {
     pub commands: Vec<WorkflowCommandDto>,
 }
 
+/// Orchestrator budget limits (`None` = unlimited)
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WorkflowBudgetDto {
+    pub max_tokens: Option<i64>,
+    pub max_cost_usd: Option<f64>,
+    pub max_duration_secs: Option<i64>,
+}
+
+/// Terminal activity monitor thresholds (`None` = default, 0 = disabled)
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WorkflowActivityPolicyDto {
+    pub stall_timeout_secs: Option<i64>,
+    pub loop_threshold: Option<i64>,
+    pub escalation_interval_secs: Option<i64>,
+}
+
+/// Orchestrator LLM usage and spend for a workflow
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WorkflowUsageDto {
+    pub workflow_id: String,
+    pub status: String,
+    pub pause_reason: Option<String>,
+    pub budget: WorkflowBudgetDto,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    /// Spend in USD for models with a known price
+    pub cost_usd: f64,
+    pub llm_calls: u64,
+    /// Calls whose model has no known price (not included in `cost_usd`)
+    pub unpriced_calls: u64,
+    pub active_secs: i64,
+}
+
 /// Workflow task DTO
 #[derive(Debug, Clone, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -63,6 +107,7 @@ pub struct WorkflowTaskDto {
     pub branch: String,
     pub status: String,
     pub order_index: i32,
+    pub depends_on: Vec<String>,
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
     pub created_at: String,
@@ -90,6 +135,9 @@ pub struct TerminalDto {
     pub status: String,
     // G17-005: Key fields added to DTO for frontend visibility
     pub auto_confirm: bool,
+    pub resource_limits: Option<db::models::TerminalResourceLimits>,
+    pub resource_report: Option<db::models::TerminalResourceReport>,
+    pub runner_labels: Option<Vec<String>>,
     pub last_commit_hash: Option<String>,
     pub last_commit_message: Option<String>,
     pub started_at: Option<String>,
@@ -182,6 +230,9 @@ impl WorkflowDetailDto {
             merge_terminal_model_id: Some(workflow.merge_terminal_model_id.clone()),
             target_branch: workflow.target_branch.clone(),
             git_watcher_enabled: workflow.git_watcher_enabled,
+            pause_reason: workflow.pause_reason.clone(),
+            budget: WorkflowBudgetDto::from_workflow(workflow),
+            activity_policy: WorkflowActivityPolicyDto::from_workflow(workflow),
             ready_at: workflow.ready_at.map(|dt| dt.to_rfc3339()),
             started_at: workflow.started_at.map(|dt| dt.to_rfc3339()),
             completed_at: workflow.completed_at.map(|dt| dt.to_rfc3339()),
@@ -228,6 +279,9 @@ impl WorkflowDetailDto {
             merge_terminal_model_id: Some(workflow.merge_terminal_model_id.clone()),
             target_branch: workflow.target_branch.clone(),
             git_watcher_enabled: workflow.git_watcher_enabled,
+            pause_reason: workflow.pause_reason.clone(),
+            budget: WorkflowBudgetDto::from_workflow(workflow),
+            activity_policy: WorkflowActivityPolicyDto::from_workflow(workflow),
             ready_at: workflow.ready_at.map(|dt| dt.to_rfc3339()),
             started_at: workflow.started_at.map(|dt| dt.to_rfc3339()),
             completed_at: workflow.completed_at.map(|dt| dt.to_rfc3339()),
@@ -239,6 +293,49 @@ impl WorkflowDetailDto {
     }
 }
 
+impl WorkflowBudgetDto {
+    pub fn from_workflow(workflow: &db::models::Workflow) -> Self {
+        Self {
+            max_tokens: workflow.budget_max_tokens,
+            max_cost_usd: workflow.budget_max_cost_usd,
+            max_duration_secs: workflow.budget_max_duration_secs,
+        }
+    }
+}
+
+impl WorkflowActivityPolicyDto {
+    pub fn from_workflow(workflow: &db::models::Workflow) -> Self {
+        Self {
+            stall_timeout_secs: workflow.activity_stall_timeout_secs,
+            loop_threshold: workflow.activity_loop_threshold,
+            escalation_interval_secs: workflow.activity_escalation_interval_secs,
+        }
+    }
+}
+
+impl WorkflowUsageDto {
+    /// `usage` is `None` when the orchestrator has never run for this workflow.
+    pub fn from_workflow(
+        workflow: &db::models::Workflow,
+        usage: Option<&services::services::orchestrator::WorkflowUsage>,
+    ) -> Self {
+        let usage = usage.cloned().unwrap_or_default();
+        Self {
+            workflow_id: workflow.id.clone(),
+            status: workflow.status.clone(),
+            pause_reason: workflow.pause_reason.clone(),
+            budget: WorkflowBudgetDto::from_workflow(workflow),
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            total_tokens: usage.total_tokens,
+            cost_usd: usage.cost_usd,
+            llm_calls: usage.llm_calls,
+            unpriced_calls: usage.unpriced_calls,
+            active_secs: usage.active_secs(),
+        }
+    }
+}
+
 impl WorkflowTaskDto {
     pub fn from_workflow_task(
         task: &db::models::WorkflowTask,
@@ -255,6 +352,7 @@ impl WorkflowTaskDto {
             branch: task.branch.clone(),
             status: task.status.clone(),
             order_index: task.order_index,
+            depends_on: task.depends_on.clone(),
             started_at: task.started_at.map(|dt| dt.to_rfc3339()),
             completed_at: task.completed_at.map(|dt| dt.to_rfc3339()),
             created_at: task.created_at.to_rfc3339(),
@@ -279,6 +377,9 @@ impl TerminalDto {
             status: terminal.status.clone(),
             // G17-005: Key fields for frontend visibility
             auto_confirm: terminal.auto_confirm,
+            resource_limits: terminal.resource_limits.clone(),
+            resource_report: terminal.resource_report.clone(),
+            runner_labels: terminal.runner_labels.clone(),
             last_commit_hash: terminal.last_commit_hash.clone(),
             last_commit_message: terminal.last_commit_message.clone(),
             started_at: terminal.started_at.map(|dt| dt.to_rfc3339()),
@@ -346,6 +447,9 @@ mod tests {
             merge_terminal_model_id: Some("model-merge".to_string()),
             target_branch: "main".to_string(),
             git_watcher_enabled: true,
+            pause_reason: None,
+            budget: WorkflowBudgetDto::default(),
+            activity_policy: WorkflowActivityPolicyDto::default(),
             ready_at: None,
             started_at: None,
             completed_at: None,
@@ -407,6 +511,9 @@ mod tests {
                 merge_terminal_model_id: None,
                 target_branch: "main".to_string(),
                 git_watcher_enabled: true,
+                pause_reason: None,
+                budget: WorkflowBudgetDto::default(),
+                activity_policy: WorkflowActivityPolicyDto::default(),
                 ready_at: None,
                 started_at: None,
                 completed_at: None,
@@ -442,6 +549,7 @@ mod tests {
                 branch: "workflow/test".to_string(),
                 status: status.to_string(),
                 order_index: 0,
+                depends_on: vec![],
                 started_at: None,
                 completed_at: None,
                 created_at: "2026-01-24T10:00:00Z".to_string(),
@@ -493,6 +601,12 @@ mod conversion_tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             pause_reason: None,
+            budget_max_tokens: None,
+            budget_max_cost_usd: None,
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
         };
 
         let dto = WorkflowDetailDto::from_workflow(&workflow, &[], &[]);
@@ -503,4 +617,65 @@ mod conversion_tests {
         assert_eq!(dto.initial_goal.as_deref(), Some("Ship dual-mode orchestration"));
         assert!(dto.use_slash_commands);
     }
+
+    #[test]
+    fn test_convert_workflow_usage_to_dto() {
+        use services::services::orchestrator::WorkflowUsage;
+
+        let workflow = Workflow {
+            id: "wf-usage".to_string(),
+            project_id: Uuid::new_v4(),
+            name: "Budgeted".to_string(),
+            description: None,
+            status: "paused".to_string(),
+            execution_mode: "agent_planned".to_string(),
+            initial_goal: None,
+            use_slash_commands: false,
+            orchestrator_enabled: true,
+            orchestrator_api_type: Some("openai".to_string()),
+            orchestrator_base_url: None,
+            orchestrator_model: Some("gpt-4o".to_string()),
+            orchestrator_api_key: None,
+            error_terminal_enabled: false,
+            error_terminal_cli_id: None,
+            error_terminal_model_id: None,
+            merge_terminal_cli_id: "cli-merge".to_string(),
+            merge_terminal_model_id: "model-merge".to_string(),
+            target_branch: "main".to_string(),
+            git_watcher_enabled: true,
+            ready_at: None,
+            started_at: None,
+            completed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            pause_reason: Some("budget_cost_exceeded".to_string()),
+            budget_max_tokens: None,
+            budget_max_cost_usd: Some(1.5),
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
+        };
+        let usage = WorkflowUsage {
+            total_tokens: 1200,
+            cost_usd: 1.75,
+            llm_calls: 3,
+            active_ms: 90_500,
+            ..Default::default()
+        };
+
+        let dto = WorkflowUsageDto::from_workflow(&workflow, Some(&usage));
+        assert_eq!(dto.pause_reason.as_deref(), Some("budget_cost_exceeded"));
+        assert_eq!(dto.budget.max_cost_usd, Some(1.5));
+        assert_eq!(dto.total_tokens, 1200);
+        assert_eq!(dto.active_secs, 90);
+
+        let json = serde_json::to_value(&dto).unwrap();
+        assert_eq!(json["costUsd"], 1.75);
+        assert_eq!(json["budget"]["maxCostUsd"], 1.5);
+        assert!(json["budget"]["maxTokens"].is_null());
+
+        let empty = WorkflowUsageDto::from_workflow(&workflow, None);
+        assert_eq!(empty.llm_calls, 0);
+    }
 }
@@ -69,6 +69,12 @@ async fn create_minimal_workflow(deployment: &DeploymentImpl, project_id: Uuid)
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         pause_reason: None,
+        budget_max_tokens: None,
+        budget_max_cost_usd: None,
+        budget_max_duration_secs: None,
+        activity_stall_timeout_secs: None,
+        activity_loop_threshold: None,
+        activity_escalation_interval_secs: None,
     };
 
     Workflow::create(&deployment.db().pool, &workflow)
@@ -93,6 +99,7 @@ fn test_agent_config() -> AgentConfig {
         auto_merge_on_completion: false,
         fallback_providers: vec![],
         quality_gate_mode: "off".to_string(),
+        ..Default::default()
     }
 }
 
@@ -177,6 +184,7 @@ async fn test_terminal_status_broadcast() {
         branch: "test-branch".to_string(),
         status: "pending".to_string(),
         order_index: 0,
+        depends_on: Vec::new(),
         started_at: None,
         completed_at: None,
         created_at: chrono::Utc::now(),
@@ -205,6 +213,9 @@ async fn test_terminal_status_broadcast() {
         execution_process_id: None,
         vk_session_id: None,
         auto_confirm: true,
+        resource_limits: None,
+        resource_report: None,
+        runner_labels: None,
         last_commit_hash: None,
         last_commit_message: None,
         started_at: None,
@@ -275,6 +286,7 @@ async fn test_task_status_broadcast() {
         branch: "test-branch".to_string(),
         status: "pending".to_string(),
         order_index: 0,
+        depends_on: Vec::new(),
         started_at: None,
         completed_at: None,
         created_at: chrono::Utc::now(),
@@ -210,6 +210,12 @@ async fn test_workflow_commands_with_presets() {
         created_at: now,
         updated_at: now,
         pause_reason: None,
+        budget_max_tokens: None,
+        budget_max_cost_usd: None,
+        budget_max_duration_secs: None,
+        activity_stall_timeout_secs: None,
+        activity_loop_threshold: None,
+        activity_escalation_interval_secs: None,
     };
 
     Workflow::create(pool, &workflow)
@@ -350,6 +356,8 @@ async fn test_full_workflow_with_commands_api() {
         },
         target_branch: Some("main".to_string()),
         git_watcher_enabled: Some(true),
+        budget: None,
+        activity_policy: None,
         tasks: vec![],
     };
 
@@ -397,6 +405,12 @@ async fn test_full_workflow_with_commands_api() {
         created_at: now,
         updated_at: now,
         pause_reason: None,
+        budget_max_tokens: None,
+        budget_max_cost_usd: None,
+        budget_max_duration_secs: None,
+        activity_stall_timeout_secs: None,
+        activity_loop_threshold: None,
+        activity_escalation_interval_secs: None,
     };
 
     Workflow::create(pool, &workflow)
@@ -467,6 +481,12 @@ async fn test_workflow_without_commands() {
         created_at: now,
         updated_at: now,
         pause_reason: None,
+        budget_max_tokens: None,
+        budget_max_cost_usd: None,
+        budget_max_duration_secs: None,
+        activity_stall_timeout_secs: None,
+        activity_loop_threshold: None,
+        activity_escalation_interval_secs: None,
     };
 
     Workflow::create(pool, &workflow)
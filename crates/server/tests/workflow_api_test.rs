@@ -132,6 +132,12 @@ async fn create_minimal_workflow(
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         pause_reason: None,
+        budget_max_tokens: None,
+        budget_max_cost_usd: None,
+        budget_max_duration_secs: None,
+        activity_stall_timeout_secs: None,
+        activity_loop_threshold: None,
+        activity_escalation_interval_secs: None,
     };
 
     Workflow::create(&deployment.db().pool, &workflow)
@@ -39,4 +39,4 @@ pub mod services;
 pub mod utils;
 
 // Re-export commonly used modules for convenience
-pub use services::{git, git_watcher, merge_coordinator, orchestrator, terminal};
+pub use services::{git, git_watcher, merge_coordinator, orchestrator, terminal, workspace_sync};
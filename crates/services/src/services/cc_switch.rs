@@ -711,7 +711,8 @@ impl CCSwitchService {
     ///
     /// # Returns
     ///
-    /// Returns a `SpawnCommand` containing command, args, working_dir, and env configuration.
+    /// Returns a `SpawnCommand` containing command, args, working_dir, env configuration and
+    /// the terminal's resource limits.
     /// For unsupported CLIs, returns an empty configuration (does not fail).
     pub async fn build_launch_config(
         &self,
@@ -731,6 +732,8 @@ impl CCSwitchService {
             args: Vec::new(),
             working_dir: working_dir.to_path_buf(),
             env: SpawnEnv::default(),
+            resource_limits: terminal.resource_limits.clone(),
+            runner_labels: terminal.runner_labels.clone().unwrap_or_default(),
         };
 
         // Parse CLI type
@@ -1176,6 +1179,8 @@ impl CCSwitchService {
             args,
             working_dir: working_dir.to_path_buf(),
             env,
+            resource_limits: terminal.resource_limits.clone(),
+            runner_labels: terminal.runner_labels.clone().unwrap_or_default(),
         })
     }
 
@@ -1378,6 +1383,9 @@ mod tests {
             execution_process_id: None,
             vk_session_id: None,
             auto_confirm: true,
+            resource_limits: None,
+            resource_report: None,
+            runner_labels: None,
             last_commit_hash: None,
             last_commit_message: None,
             started_at: None,
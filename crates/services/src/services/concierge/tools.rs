@@ -478,6 +478,12 @@ async fn execute_create_workflow(
         started_at: None,
         completed_at: None,
         pause_reason: None,
+        budget_max_tokens: None,
+        budget_max_cost_usd: None,
+        budget_max_duration_secs: None,
+        activity_stall_timeout_secs: None,
+        activity_loop_threshold: None,
+        activity_escalation_interval_secs: None,
         created_at: now,
         updated_at: now,
     };
@@ -169,6 +169,9 @@ impl ErrorHandler {
                 execution_process_id: None,
                 vk_session_id: None,
                 auto_confirm: false,
+                resource_limits: None,
+                resource_report: None,
+                runner_labels: None,
                 last_commit_hash: None,
                 last_commit_message: None,
                 started_at: Some(now),
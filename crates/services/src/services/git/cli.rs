@@ -817,7 +817,7 @@ impl GitCli {
         Ok(String::from_utf8_lossy(&out).to_string())
     }
 
-    fn git_with_env<I, S>(
+    pub(crate) fn git_with_env<I, S>(
         repo_path: &Path,
         args: I,
         envs: &[(OsString, OsString)],
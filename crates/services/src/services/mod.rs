@@ -26,9 +26,12 @@ pub mod qa_repos;
 pub mod queued_message;
 pub mod repo;
 pub mod runner_client;
+pub mod runner_registry;
 pub mod template_renderer;
+pub mod workflow_template;
 pub mod terminal;
 pub mod workspace_manager;
+pub mod workspace_sync;
 pub mod worktree_manager;
 pub use cc_switch::{CCSwitch, CCSwitchService};
 pub mod chat_connector;
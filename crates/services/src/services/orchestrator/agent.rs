@@ -3,23 +3,33 @@
 use std::{
     collections::{HashMap, HashSet},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
     time::Instant,
 };
 
 use anyhow::anyhow;
-use db::DBService;
+use db::{
+    DBService,
+    models::orchestrator_decision::{
+        DECISION_TYPE_INSTRUCTION, DECISION_TYPE_MERGE, DECISION_TYPE_PROMPT,
+        DECISION_TYPE_RECOVERY, OrchestratorDecision,
+    },
+};
 use futures::future;
 #[cfg(unix)]
 use nix::unistd::Pid;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use tokio::{
-    sync::RwLock,
+    sync::{RwLock, mpsc},
     time::{Duration, MissedTickBehavior, interval, sleep},
 };
 
 use super::{
+    budget::{BudgetViolation, WorkflowUsage, price_for_model},
     config::OrchestratorConfig,
     constants::{
         COMPLETION_CONTEXT_BODY_MAX_CHARS, COMPLETION_CONTEXT_DIFF_MAX_CHARS,
@@ -33,18 +43,30 @@ use super::{
         TERMINAL_STATUS_REVIEW_REJECTED, TERMINAL_STATUS_STARTING, TERMINAL_STATUS_WAITING,
         TERMINAL_STATUS_WORKING,
         WORKFLOW_STATUS_COMPLETED, WORKFLOW_STATUS_FAILED,
-        WORKFLOW_STATUS_MERGE_PARTIAL_FAILED,
+        WORKFLOW_STATUS_MERGE_PARTIAL_FAILED, WORKFLOW_STATUS_PAUSED,
         WORKFLOW_STATUS_RUNNING, WORKFLOW_TOPIC_PREFIX,
     },
+    decision_log::{
+        self, DECISION_STATUS_APPLIED, DECISION_STATUS_FAILED, DECISION_STATUS_TIMED_OUT,
+        DECISION_TRIGGER, DecisionTrigger,
+    },
+    history,
+    instruction_tools::{
+        assistant_history_content, instruction_tool_definitions, instructions_from_tool_calls,
+    },
     persistence::StatePersistence,
     llm::{LLMClient, build_terminal_completion_prompt, create_llm_client},
+    llm_replay::{LLM_RECORD_DIR_ENV, RecordingLLMClient},
     message_bus::{BusMessage, SharedMessageBus},
     prompt_handler::PromptHandler,
+    routing::LLMRoute,
     runtime_actions::{RuntimeActionService, RuntimeTaskSpec, RuntimeTerminalSpec},
     state::{OrchestratorRunState, OrchestratorState, SharedOrchestratorState},
+    task_graph::{self, DependencyState},
     types::{
-        CodeIssue, LLMMessage, OrchestratorInstruction, PreviousTerminalContext,
-        QualityGateResultEvent, TerminalCompletionContext, TerminalCompletionEvent,
+        ActivityEscalationLevel, CodeIssue, LLMMessage, LLMResponse, LLMStreamEvent,
+        OrchestratorInstruction, PreviousTerminalContext, QualityGateResultEvent,
+        TerminalActivityEvent, TerminalCompletionContext, TerminalCompletionEvent,
         TerminalCompletionStatus, TerminalPromptEvent,
     },
 };
@@ -66,6 +88,10 @@ pub struct OrchestratorAgent {
     runtime_actions: Option<Arc<RuntimeActionService>>,
     persistence: Option<Arc<StatePersistence>>,
     last_state_save: Arc<tokio::sync::Mutex<tokio::time::Instant>>,
+    /// Last time active run time was added to `state.usage`
+    last_usage_tick: Arc<tokio::sync::Mutex<tokio::time::Instant>>,
+    /// Set once the workflow has been paused for exceeding its budget
+    budget_paused: AtomicBool,
     concierge_broadcaster: Option<Arc<ConciergeBroadcaster>>,
 }
 
@@ -154,7 +180,15 @@ impl OrchestratorAgent {
         message_bus: SharedMessageBus,
         db: Arc<DBService>,
     ) -> anyhow::Result<Self> {
-        let llm_client = create_llm_client(&config)?;
+        let mut llm_client = create_llm_client(&config)?;
+        if let Ok(record_dir) = std::env::var(LLM_RECORD_DIR_ENV)
+            && !record_dir.trim().is_empty()
+        {
+            let path =
+                std::path::Path::new(record_dir.trim()).join(format!("{workflow_id}.json"));
+            tracing::info!(path = %path.display(), "Recording orchestrator LLM exchanges");
+            llm_client = Box::new(RecordingLLMClient::open(llm_client, path)?);
+        }
         let state = Arc::new(RwLock::new(OrchestratorState::new(workflow_id)));
         let error_handler = ErrorHandler::new(db.clone(), message_bus.clone());
         let prompt_handler = PromptHandler::new(message_bus.clone());
@@ -170,6 +204,8 @@ impl OrchestratorAgent {
             runtime_actions: None,
             persistence: None,
             last_state_save: Arc::new(tokio::sync::Mutex::new(tokio::time::Instant::now())),
+            last_usage_tick: Arc::new(tokio::sync::Mutex::new(tokio::time::Instant::now())),
+            budget_paused: AtomicBool::new(false),
             concierge_broadcaster: None,
         })
     }
@@ -198,6 +234,8 @@ impl OrchestratorAgent {
             runtime_actions: None,
             persistence: None,
             last_state_save: Arc::new(tokio::sync::Mutex::new(tokio::time::Instant::now())),
+            last_usage_tick: Arc::new(tokio::sync::Mutex::new(tokio::time::Instant::now())),
+            budget_paused: AtomicBool::new(false),
             concierge_broadcaster: None,
         })
     }
@@ -272,6 +310,128 @@ impl OrchestratorAgent {
         state.conversation_history = recovered.conversation_history;
         state.total_tokens_used = recovered.total_tokens_used;
         state.error_count = recovered.error_count;
+        state.usage = recovered.usage;
+    }
+
+    /// Carry budget usage over from a previous run (e.g. after pause/resume),
+    /// so the budget applies to the workflow as a whole.
+    pub async fn restore_usage(&self, usage: WorkflowUsage) {
+        self.state.write().await.usage = usage;
+    }
+
+    /// Snapshot of accumulated LLM usage, spend and active time.
+    pub async fn usage_snapshot(&self) -> WorkflowUsage {
+        self.accrue_active_time().await;
+        self.state.read().await.usage.clone()
+    }
+
+    /// Adds the run time since the last tick to `state.usage.active_ms`.
+    async fn accrue_active_time(&self) {
+        let mut last_tick = self.last_usage_tick.lock().await;
+        let now = tokio::time::Instant::now();
+        let elapsed_ms =
+            i64::try_from(now.duration_since(*last_tick).as_millis()).unwrap_or(i64::MAX);
+        *last_tick = now;
+        drop(last_tick);
+
+        let mut state = self.state.write().await;
+        state.usage.active_ms = state.usage.active_ms.saturating_add(elapsed_ms);
+    }
+
+    /// Records one LLM call against the workflow budget.
+    async fn record_llm_usage(&self, response: &LLMResponse) {
+        // Fallback and routed providers may run a different model than the primary.
+        let model = response.model.as_deref().unwrap_or(&self.config.model);
+        let price = price_for_model(model);
+        let mut state = self.state.write().await;
+        if let Some(usage) = &response.usage {
+            state.total_tokens_used += i64::from(usage.total_tokens);
+        }
+        state.usage.record(response.usage.as_ref(), price);
+    }
+
+    /// Checks the workflow budget and pauses the workflow when a limit is reached.
+    ///
+    /// Returns the violation if the workflow is (now) paused for budget reasons.
+    /// Limits are checked between LLM calls, so the call that crosses a limit
+    /// still completes.
+    async fn enforce_budget(&self) -> Option<BudgetViolation> {
+        if self.config.budget.is_unlimited() {
+            return None;
+        }
+        self.accrue_active_time().await;
+        let violation = {
+            let state = self.state.read().await;
+            self.config.budget.exceeded_by(&state.usage)?
+        };
+        if self.budget_paused.swap(true, Ordering::SeqCst) {
+            return Some(violation);
+        }
+
+        let workflow_id = {
+            let mut state = self.state.write().await;
+            state.run_state = OrchestratorRunState::Paused;
+            state.workflow_id.clone()
+        };
+        tracing::warn!(
+            workflow_id = %workflow_id,
+            reason = violation.pause_reason,
+            "{}; pausing workflow",
+            violation.detail
+        );
+
+        match db::models::Workflow::set_paused(&self.db.pool, &workflow_id, violation.pause_reason)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                tracing::debug!(
+                    workflow_id = %workflow_id,
+                    "Workflow no longer running; budget pause skipped"
+                );
+            }
+            Err(e) => {
+                tracing::warn!(
+                    workflow_id = %workflow_id,
+                    error = %e,
+                    "Failed to pause workflow after budget exceeded"
+                );
+            }
+        }
+
+        if let Err(e) = self
+            .message_bus
+            .publish_workflow_event(
+                &workflow_id,
+                BusMessage::Error {
+                    workflow_id: workflow_id.clone(),
+                    error: format!("Workflow paused: {}", violation.detail),
+                },
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to publish budget exceeded error event");
+        }
+        if let Err(e) = self
+            .message_bus
+            .publish_workflow_event(
+                &workflow_id,
+                BusMessage::StatusUpdate {
+                    workflow_id: workflow_id.clone(),
+                    status: WORKFLOW_STATUS_PAUSED.to_string(),
+                },
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to publish workflow paused status update event");
+        }
+
+        // Persist usage right away so the paused workflow reports final spend.
+        if let Some(ref persistence) = self.persistence {
+            self.save_state_now(persistence).await;
+        }
+
+        Some(violation)
     }
 
     /// Debounced state persistence - saves at most once every STATE_SAVE_DEBOUNCE_SECS seconds.
@@ -288,6 +448,11 @@ impl OrchestratorAgent {
         *last_save = tokio::time::Instant::now();
         drop(last_save);
 
+        self.save_state_now(persistence).await;
+    }
+
+    /// Persist state immediately, bypassing the debounce.
+    async fn save_state_now(&self, persistence: &StatePersistence) {
         let state = self.state.read().await;
         if let Err(e) = persistence.save_state(&state).await {
             let workflow_id = &state.workflow_id;
@@ -351,9 +516,9 @@ impl OrchestratorAgent {
         }
 
         let prompt = self.build_initial_planning_prompt(&workflow).await?;
-        let response = self.call_llm(&prompt).await?;
+        let response = self.call_llm(&prompt, LLMRoute::Planning).await?;
         tracing::info!("Initial planning LLM response received, executing instructions");
-        if let Err(e) = self.execute_instruction(&response).await {
+        if let Err(e) = self.execute_llm_response(&response).await {
             tracing::warn!("Initial planning instruction execution had errors (continuing): {e}");
         }
         tracing::info!("Initial planning instructions processed, checking for tasks without terminals");
@@ -375,9 +540,9 @@ impl OrchestratorAgent {
                  [{{\"type\":\"create_task\",\"name\":\"Task Name\",\"branch\":\"feat/branch-name\",\"order_index\":0}}]",
                 workflow.initial_goal.as_deref().unwrap_or("(see conversation history)")
             );
-            if let Ok(retry_resp) = self.call_llm(&retry_prompt).await {
+            if let Ok(retry_resp) = self.call_llm(&retry_prompt, LLMRoute::Planning).await {
                 tracing::info!("Retry planning LLM response received");
-                if let Err(e) = self.execute_instruction(&retry_resp).await {
+                if let Err(e) = self.execute_llm_response(&retry_resp).await {
                     tracing::warn!("Retry planning instruction execution had errors: {e}");
                 }
                 let tasks_retry = db::models::WorkflowTask::find_by_workflow(&self.db.pool, &workflow.id)
@@ -407,8 +572,8 @@ impl OrchestratorAgent {
                         "The following tasks have been created but have NO terminals yet:\n{}\n\nFor each task, create at least one terminal using create_terminal (with cli_type_id and model_config_id from the available pool) and then start it with start_terminal. Return raw JSON instructions only, no markdown.",
                         task_names.join("\n")
                     );
-                    if let Ok(resp2) = self.call_llm(&follow_up).await {
-                        let _ = self.execute_instruction(&resp2).await;
+                    if let Ok(resp2) = self.call_llm(&follow_up, LLMRoute::Planning).await {
+                        let _ = self.execute_llm_response(&resp2).await;
                     }
                 }
                 return Ok(());
@@ -433,8 +598,8 @@ impl OrchestratorAgent {
                     "The following tasks have been created but have NO terminals yet:\n{}\n\nFor each task, create at least one terminal using create_terminal (with cli_type_id and model_config_id from the available pool) and then start it with start_terminal. Return raw JSON instructions only.",
                     task_names.join("\n")
                 );
-                if let Ok(resp2) = self.call_llm(&follow_up).await {
-                    let _ = self.execute_instruction(&resp2).await;
+                if let Ok(resp2) = self.call_llm(&follow_up, LLMRoute::Planning).await {
+                    let _ = self.execute_llm_response(&resp2).await;
                 }
             }
         }
@@ -641,6 +806,9 @@ impl OrchestratorAgent {
             )
             .await
             .map_err(|e| anyhow!("Failed to publish task status update: {e}"))?;
+        if let Err(e) = self.dispatch_unblocked_tasks().await {
+            tracing::warn!(task_id = %task_id, error = %e, "Failed to dispatch unblocked tasks");
+        }
         Ok(())
     }
 
@@ -686,6 +854,7 @@ impl OrchestratorAgent {
         for attempt in 1..=3u32 {
             match self.run_initial_agent_planning_if_needed().await {
                 Ok(()) => break,
+                Err(_) if self.budget_paused.load(Ordering::SeqCst) => break,
                 Err(e) => {
                     tracing::error!(attempt, "Failed to run initial agent-planned cycle: {}", e);
                     if attempt < 3 {
@@ -697,6 +866,11 @@ impl OrchestratorAgent {
             }
         }
 
+        if self.enforce_budget().await.is_some() {
+            tracing::info!("Orchestrator paused (budget exceeded) for workflow: {}", workflow_id);
+            return Ok(());
+        }
+
         // Stall recovery: detect and recover stuck workflows
         let mut stall_recovery_tracker = StallRecoveryTracker::default();
         let mut watchdog = interval(Self::STALL_WATCHDOG_TICK);
@@ -709,12 +883,18 @@ impl OrchestratorAgent {
                     let Some(message) = maybe_message else {
                         break;
                     };
-                    let should_stop = self.handle_message(message).await?;
-                    if should_stop {
+                    let trigger = DecisionTrigger::from_bus_message(&message);
+                    let should_stop = DECISION_TRIGGER
+                        .scope(trigger, self.handle_message(message))
+                        .await?;
+                    if should_stop || self.enforce_budget().await.is_some() {
                         break;
                     }
                 }
                 _ = watchdog.tick() => {
+                    if self.enforce_budget().await.is_some() {
+                        break;
+                    }
                     if let Err(error) = self
                         .recover_stalled_terminals(&mut stall_recovery_tracker)
                         .await
@@ -733,6 +913,14 @@ impl OrchestratorAgent {
                             "Failed while auto-completing stalled tasks"
                         );
                     }
+                    // Start tasks unblocked by completions outside the event path
+                    if let Err(error) = self.dispatch_unblocked_tasks().await {
+                        tracing::warn!(
+                            workflow_id = %workflow_id,
+                            error = %error,
+                            "Failed while dispatching unblocked tasks"
+                        );
+                    }
                     // Check if workflow can be marked as completed
                     if let Err(error) = self.auto_sync_workflow_completion(&workflow_id).await {
                         tracing::warn!(
@@ -745,6 +933,12 @@ impl OrchestratorAgent {
             }
         }
 
+        // Flush usage accounting so a later resume continues from it.
+        self.accrue_active_time().await;
+        if let Some(ref persistence) = self.persistence {
+            self.save_state_now(persistence).await;
+        }
+
         tracing::info!("Orchestrator stopped for workflow: {}", workflow_id);
         Ok(())
     }
@@ -761,6 +955,9 @@ impl OrchestratorAgent {
             BusMessage::TerminalPromptDetected(event) => {
                 self.handle_terminal_prompt_detected(event).await?;
             }
+            BusMessage::TerminalActivityEscalated(event) => {
+                self.handle_terminal_activity_escalated(event).await?;
+            }
             BusMessage::GitEvent {
                 workflow_id,
                 commit_hash,
@@ -781,7 +978,8 @@ impl OrchestratorAgent {
             | BusMessage::TerminalMessage { .. }
             | BusMessage::TerminalInput { .. }
             | BusMessage::TerminalPromptDecision { .. }
-            | BusMessage::ProviderStateChanged { .. } => {
+            | BusMessage::ProviderStateChanged { .. }
+            | BusMessage::LLMStream { .. } => {
                 // Outbound-only or UI-notification events — no action needed in agent loop
             }
         }
@@ -874,6 +1072,38 @@ impl OrchestratorAgent {
         }
     }
 
+    /// Decision for the current workflow, with the task-local trigger attached.
+    async fn new_decision(&self, decision_type: &str, action: &str) -> OrchestratorDecision {
+        let workflow_id = {
+            let state = self.state.read().await;
+            state.workflow_id.clone()
+        };
+        let mut decision =
+            OrchestratorDecision::new(&workflow_id, decision_type, action, DECISION_STATUS_APPLIED);
+        decision_log::apply_trigger(&mut decision);
+        decision
+    }
+
+    /// Persist a decision and its timeline entry to the audit trail.
+    async fn record_decision(&self, mut decision: OrchestratorDecision) {
+        let summary = decision_log::decision_summary(&decision);
+        if let Err(e) =
+            OrchestratorDecision::insert_with_event(&self.db.pool, &mut decision, &summary).await
+        {
+            tracing::warn!(
+                decision_id = %decision.id,
+                decision_type = %decision.decision_type,
+                error = %e,
+                "Failed to persist orchestrator decision; audit record lost"
+            );
+        }
+    }
+
+    /// Provider label for single-provider clients, which do not tag responses.
+    fn provider_label(&self) -> String {
+        format!("{}({})", self.config.api_type, self.config.model)
+    }
+
     async fn recover_stalled_terminals(
         &self,
         tracker: &mut StallRecoveryTracker,
@@ -957,6 +1187,7 @@ impl OrchestratorAgent {
                         &task,
                         terminal,
                         terminals.len(),
+                        Self::STALL_RECOVERY_SUFFIX,
                     )
                     .await
                 {
@@ -986,6 +1217,7 @@ impl OrchestratorAgent {
         task: &db::models::WorkflowTask,
         terminal: &db::models::Terminal,
         total_terminals: usize,
+        notice: &str,
     ) -> anyhow::Result<()> {
         let pty_session_id = terminal
             .pty_session_id
@@ -1004,7 +1236,7 @@ impl OrchestratorAgent {
         let instruction = format!(
             "{} | {}",
             Self::build_task_instruction(workflow_id, task, terminal, total_terminals, None),
-            Self::STALL_RECOVERY_SUFFIX
+            notice
         );
 
         if Self::needs_explicit_submit(terminal) {
@@ -1063,10 +1295,151 @@ impl OrchestratorAgent {
                 decision = ?decision,
                 "Handled terminal prompt event"
             );
+            let (action, reason) = decision_log::prompt_decision_parts(&decision);
+            let mut record = self.new_decision(DECISION_TYPE_PROMPT, action).await;
+            record.terminal_id = Some(event.terminal_id.clone());
+            record.task_id = Some(event.task_id.clone());
+            record.reasoning = Some(reason.to_string());
+            record.detail = serde_json::to_string(&decision).ok();
+            self.record_decision(record).await;
         }
         Ok(())
     }
 
+    /// Carries out one step of the activity monitor's escalation ladder.
+    async fn handle_terminal_activity_escalated(
+        &self,
+        event: TerminalActivityEvent,
+    ) -> anyhow::Result<()> {
+        let workflow_id = {
+            let state = self.state.read().await;
+            state.workflow_id.clone()
+        };
+        if event.workflow_id != workflow_id {
+            return Ok(());
+        }
+        let Some(terminal) =
+            db::models::Terminal::find_by_id(&self.db.pool, &event.terminal_id).await?
+        else {
+            return Ok(());
+        };
+        // The terminal finished or was restarted since the monitor looked at it.
+        if terminal.status != TERMINAL_STATUS_WORKING {
+            return Ok(());
+        }
+        let Some(task) =
+            db::models::WorkflowTask::find_by_id(&self.db.pool, &event.task_id).await?
+        else {
+            return Ok(());
+        };
+
+        let summary = event.reason.describe();
+        let notice = format!(
+            "Activity monitor notice: {summary}. Stop repeating the same step, resume this task from the current workspace state and commit your progress."
+        );
+        tracing::warn!(
+            workflow_id = %workflow_id,
+            task_id = %task.id,
+            terminal_id = %terminal.id,
+            level = event.level.as_str(),
+            reason = %summary,
+            "Handling terminal activity escalation"
+        );
+
+        let action = match event.level {
+            ActivityEscalationLevel::Nudge => {
+                let total_terminals = db::models::Terminal::find_by_task(&self.db.pool, &task.id)
+                    .await?
+                    .len();
+                self.redispatch_stalled_terminal_instruction(
+                    &workflow_id,
+                    &task,
+                    &terminal,
+                    total_terminals,
+                    &notice,
+                )
+                .await?;
+                "nudge_terminal"
+            }
+            ActivityEscalationLevel::Notify => {
+                let prompt = format!(
+                    "Terminal {} ({}) of task \"{}\" ({}) needs attention: {summary}. \
+                     It has already been nudged once. Decide how to proceed, e.g. send it \
+                     a more specific instruction or close it; reply with orchestrator instructions.",
+                    terminal.id, terminal.cli_type_id, task.name, task.id
+                );
+                {
+                    let mut state = self.state.write().await;
+                    state.add_message(
+                        "system",
+                        &format!("Terminal {} escalated: {summary}", terminal.id),
+                        &self.config,
+                    );
+                }
+                if let Some(response) = self.call_llm_safe(&prompt, LLMRoute::Decision).await {
+                    self.execute_llm_response(&response).await?;
+                }
+                "notify_orchestrator"
+            }
+            ActivityEscalationLevel::Restart => {
+                let actions = self.runtime_actions()?;
+                actions
+                    .close_terminal(&terminal.id, Some(TERMINAL_STATUS_CANCELLED))
+                    .await?;
+                let terminal = actions.start_terminal(&terminal.id).await?;
+                let planning_complete = self.task_planning_complete(&task.id).await;
+                self.sync_task_state_from_db(&task.id, Some(planning_complete))
+                    .await?;
+                let total_terminals = db::models::Terminal::find_by_task(&self.db.pool, &task.id)
+                    .await?
+                    .len();
+                let instruction = format!(
+                    "{} | {notice}",
+                    Self::build_task_instruction(
+                        &workflow_id,
+                        &task,
+                        &terminal,
+                        total_terminals,
+                        None
+                    )
+                );
+                match tokio::time::timeout(
+                    std::time::Duration::from_secs(30),
+                    self.dispatch_terminal(&task.id, &terminal, &instruction),
+                )
+                .await
+                {
+                    Ok(result) => result?,
+                    Err(_) => anyhow::bail!("dispatch_terminal timed out after restart"),
+                }
+                "restart_terminal"
+            }
+            ActivityEscalationLevel::Fail => {
+                self.enforce_terminal_completion_shutdown(&workflow_id, &terminal)
+                    .await;
+                self.handle_terminal_completed(TerminalCompletionEvent {
+                    terminal_id: terminal.id.clone(),
+                    task_id: task.id.clone(),
+                    workflow_id: workflow_id.clone(),
+                    status: TerminalCompletionStatus::Failed,
+                    commit_hash: None,
+                    commit_message: None,
+                    metadata: None,
+                })
+                .await?;
+                "fail_terminal"
+            }
+        };
+
+        let mut record = self.new_decision(DECISION_TYPE_RECOVERY, action).await;
+        record.terminal_id = Some(terminal.id.clone());
+        record.task_id = Some(task.id.clone());
+        record.reasoning = Some(summary);
+        record.detail = serde_json::to_string(&event).ok();
+        self.record_decision(record).await;
+        Ok(())
+    }
+
     /// Atomically marks a terminal as finalized using CAS with a fallback.
     ///
     /// Attempts `set_completed_cas` (working → final_status).  On CAS miss,
@@ -1365,14 +1738,29 @@ impl OrchestratorAgent {
             {
                 tracing::warn!(error = %e, "Failed to publish task status update event");
             }
+
+            // Start tasks that were waiting on this one (or cancel them if it failed)
+            if let Err(e) = self.dispatch_unblocked_tasks().await {
+                tracing::warn!(
+                    task_id = %event.task_id,
+                    error = %e,
+                    "Failed to dispatch unblocked tasks"
+                );
+            }
         }
 
         // 闁哄瀚紓鎾诲箵閹邦喓浠涙鐐村劶閻ㄧ喖鏁?LLM
         let should_run_completion_llm = !(success && has_next && !task_failed);
-        let mut completion_response: Option<String> = None;
+        let mut completion_response: Option<LLMResponse> = None;
         if should_run_completion_llm {
             let prompt = self.build_completion_prompt(&event).await?;
-            if let Some(response) = self.call_llm_safe(&prompt).await {
+            let terminal_role = db::models::Terminal::find_by_id(&self.db.pool, &event.terminal_id)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|terminal| terminal.role);
+            let route = LLMRoute::for_completion(event.status, terminal_role.as_deref());
+            if let Some(response) = self.call_llm_safe(&prompt, route).await {
                 completion_response = Some(response);
             } else {
                 let wf_id = {
@@ -1400,8 +1788,8 @@ impl OrchestratorAgent {
         }
 
         // Parse and execute orchestrator instructions from completion response.
-        if let Some(response) = completion_response.as_deref() {
-            self.execute_instruction(response).await?;
+        if let Some(response) = completion_response.as_ref() {
+            self.execute_llm_response(response).await?;
         }
 
         // Auto-dispatch next terminal if successful, there's more to do, and task hasn't failed
@@ -1613,14 +2001,14 @@ impl OrchestratorAgent {
                 run_id: &str,
                 reason: &str,
                 quality_run_id_for_warn: &str,
-            ) -> (&'static str, i32, i32, i32, bool, Option<String>, Option<String>) {
+            ) -> (&'static str, i32, i32, i32, bool, Option<String>, Option<String>, Option<String>) {
                 tracing::warn!(
                     quality_run_id = %quality_run_id_for_warn,
                     reason = %reason,
                     "Quality engine unavailable — gate_status set to 'skipped' (fail-open, G31-006)"
                 );
                 let _ = run_id; // silence unused warning
-                ("skipped", 0i32, 0i32, 0i32, true, None, None)
+                ("skipped", 0i32, 0i32, 0i32, true, None, None, None)
             }
 
             // Resolve the project working directory for quality analysis
@@ -1672,7 +2060,8 @@ impl OrchestratorAgent {
                                     let is_passed = report.is_passed();
                                     let fix = if is_passed { None } else { Some(report.to_fix_instructions()) };
                                     let rjson = serde_json::to_string(&report).ok();
-                                    (gate_str, total, blocking, new_i, is_passed, fix, rjson)
+                                    let sjson = serde_json::to_string(&quality::sarif::report_to_sarif(&report)).ok();
+                                    (gate_str, total, blocking, new_i, is_passed, fix, rjson, sjson)
                                 }
                                 Err(e) => {
                                     skipped_outcome(&run_id, &format!("engine.run failed: {e}"), &run_id)
@@ -1688,7 +2077,7 @@ impl OrchestratorAgent {
                 }
             };
 
-            let (gate_status, total_issues, blocking_issues, new_issues, passed, fix_instructions, report_json) =
+            let (gate_status, total_issues, blocking_issues, new_issues, passed, fix_instructions, report_json, sarif_json) =
                 match tokio::time::timeout(
                     Duration::from_secs(QUALITY_GATE_TIMEOUT_SECS),
                     engine_future,
@@ -1719,6 +2108,7 @@ impl OrchestratorAgent {
                 None, // providers_run
                 report_json.as_deref(),
                 None, // decision_json
+                sarif_json.as_deref(),
             )
             .await
             {
@@ -3136,31 +3526,136 @@ impl OrchestratorAgent {
     }
 
     /// Calls the LLM with the current conversation history.
-    async fn call_llm(&self, prompt: &str) -> anyhow::Result<String> {
+    ///
+    /// Every orchestrator instruction is offered as a native tool; providers that
+    /// ignore tools still answer in text, which `execute_llm_response` parses.
+    /// `route` tags the call site so multi-provider clients pick the model tier.
+    async fn call_llm(&self, prompt: &str, route: LLMRoute) -> anyhow::Result<LLMResponse> {
+        if let Some(violation) = self.enforce_budget().await {
+            return Err(anyhow!(violation.detail));
+        }
+
+        self.maybe_compact_history().await;
+
         let mut state = self.state.write().await;
         state.add_message("user", prompt, &self.config);
 
         let messages = state.conversation_history.clone();
+        let workflow_id = state.workflow_id.clone();
         drop(state);
 
-        let response = self.llm_client.chat(messages).await?;
+        // Stream deltas to the workflow WebSocket as they arrive. Broadcast only:
+        // publishing to the workflow topic would flood the agent's own inbox.
+        let stream_id = uuid::Uuid::new_v4().to_string();
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let forwarder = {
+            let message_bus = self.message_bus.clone();
+            let workflow_id = workflow_id.clone();
+            let stream_id = stream_id.clone();
+            tokio::spawn(async move {
+                while let Some(event) = events_rx.recv().await {
+                    let _ = message_bus.broadcast(BusMessage::LLMStream {
+                        workflow_id: workflow_id.clone(),
+                        stream_id: stream_id.clone(),
+                        event,
+                    });
+                }
+            })
+        };
+
+        let result = self
+            .llm_client
+            .chat_routed(
+                self.config.tier_for(route),
+                messages,
+                instruction_tool_definitions(),
+                Some(events_tx),
+            )
+            .await;
+        // The sender was moved into chat_routed, so the forwarder drains and exits.
+        let _ = forwarder.await;
+        let _ = self.message_bus.broadcast(BusMessage::LLMStream {
+            workflow_id,
+            stream_id,
+            event: LLMStreamEvent::Done,
+        });
+        let response = result?;
 
         // Publish any provider state-change events that occurred during the call.
         self.publish_provider_events().await;
+        self.record_llm_usage(&response).await;
 
         let mut state = self.state.write().await;
-        state.add_message("assistant", &response.content, &self.config);
-        if let Some(usage) = &response.usage {
-            state.total_tokens_used += i64::from(usage.total_tokens);
-        }
+        state.add_message(
+            "assistant",
+            &assistant_history_content(&response.content, &response.tool_calls),
+            &self.config,
+        );
+
+        Ok(response)
+    }
+
+    /// Folds older conversation turns into the pinned memory message once the
+    /// history crosses the compaction policy.
+    ///
+    /// On failure the history is left as is and `add_message` falls back to
+    /// plain truncation.
+    async fn maybe_compact_history(&self) {
+        let (batch, workflow_id) = {
+            let state = self.state.read().await;
+            let Some(batch) = state.compaction_batch(&self.config) else {
+                return;
+            };
+            (batch, state.workflow_id.clone())
+        };
+        let policy = &self.config.history_compaction;
+
+        let response = match self
+            .llm_client
+            .chat_routed(
+                self.config.tier_for(LLMRoute::HistorySummary),
+                history::summary_request(&batch, policy.summary_max_chars),
+                Vec::new(),
+                None,
+            )
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::warn!(
+                    workflow_id = %workflow_id,
+                    error = %e,
+                    "History compaction failed, falling back to truncation"
+                );
+                return;
+            }
+        };
+        self.publish_provider_events().await;
+        self.record_llm_usage(&response).await;
 
-        Ok(response.content)
+        let summary = history::clamp_summary(&response.content, policy.summary_max_chars);
+        if summary.is_empty() {
+            return;
+        }
+        let compacted = self
+            .state
+            .write()
+            .await
+            .apply_compaction(&batch, &summary);
+        if compacted {
+            tracing::info!(
+                workflow_id = %workflow_id,
+                folded_messages = batch.messages.len(),
+                summary_chars = summary.len(),
+                "Compacted orchestrator conversation history"
+            );
+        }
     }
 
     /// Wrapper around `call_llm` that catches errors instead of propagating them.
     /// Returns `None` on failure, allowing the agent event loop to continue.
-    async fn call_llm_safe(&self, prompt: &str) -> Option<String> {
-        match self.call_llm(prompt).await {
+    async fn call_llm_safe(&self, prompt: &str, route: LLMRoute) -> Option<LLMResponse> {
+        match self.call_llm(prompt, route).await {
             Ok(response) => {
                 // Reset consecutive failure count on success
                 let mut state = self.state.write().await;
@@ -3169,6 +3664,10 @@ impl OrchestratorAgent {
                 self.maybe_save_state().await;
                 Some(response)
             }
+            Err(_) if self.budget_paused.load(Ordering::SeqCst) => {
+                // Budget pause is not an LLM failure; already reported by enforce_budget.
+                None
+            }
             Err(e) => {
                 let mut state = self.state.write().await;
                 state.error_count += 1;
@@ -3253,13 +3752,54 @@ impl OrchestratorAgent {
         }
     }
 
-    /// Executes orchestrator instructions returned by the LLM.
+    /// Executes an LLM response, preferring structured tool calls.
+    ///
+    /// Falls back to parsing instructions out of the response text when the
+    /// provider returned no tool calls or none of them were valid.
+    pub async fn execute_llm_response(&self, response: &LLMResponse) -> anyhow::Result<()> {
+        if !response.tool_calls.is_empty() {
+            let instructions = instructions_from_tool_calls(&response.tool_calls);
+            if !instructions.is_empty() {
+                tracing::info!(
+                    tool_calls = response.tool_calls.len(),
+                    instructions = instructions.len(),
+                    "Executing native tool-call instructions"
+                );
+                return self.execute_instructions(instructions, Some(response)).await;
+            }
+            tracing::warn!(
+                tool_calls = response.tool_calls.len(),
+                "No valid instructions in tool calls, falling back to text parsing"
+            );
+        }
+        self.execute_instruction_text(&response.content, Some(response))
+            .await
+    }
+
+    /// Executes orchestrator instructions parsed from LLM response text.
     pub async fn execute_instruction(&self, response: &str) -> anyhow::Result<()> {
-        let Some(instructions) = Self::parse_instructions(response) else {
+        self.execute_instruction_text(response, None).await
+    }
+
+    async fn execute_instruction_text(
+        &self,
+        text: &str,
+        source: Option<&LLMResponse>,
+    ) -> anyhow::Result<()> {
+        let Some(instructions) = Self::parse_instructions(text) else {
             tracing::warn!("LLM response did not contain a valid orchestrator instruction payload");
             return Ok(());
         };
+        self.execute_instructions(instructions, source).await
+    }
 
+    /// Executes instructions in order; each one is recorded in the decision
+    /// audit trail together with the LLM reply (`source`) that produced it.
+    async fn execute_instructions(
+        &self,
+        instructions: Vec<OrchestratorInstruction>,
+        source: Option<&LLMResponse>,
+    ) -> anyhow::Result<()> {
         // Track ID remaps: LLM may use non-UUID IDs like "task-infra" that get
         // replaced with proper UUIDs.  Subsequent instructions in the same batch
         // need these mappings so their cross-references stay consistent.
@@ -3269,6 +3809,18 @@ impl OrchestratorAgent {
             Self::validate_instruction_whitelist(&instruction)?;
             Self::remap_instruction_ids(&mut instruction, &mut id_remap);
             tracing::info!(index = i, instruction = ?std::mem::discriminant(&instruction), "Executing instruction");
+            let mut decision = self
+                .new_decision(
+                    DECISION_TYPE_INSTRUCTION,
+                    Self::instruction_type_name(&instruction),
+                )
+                .await;
+            (decision.terminal_id, decision.task_id) =
+                decision_log::instruction_targets(&instruction);
+            decision.detail = decision_log::instruction_detail(&instruction);
+            if let Some(response) = source {
+                decision_log::apply_llm_source(&mut decision, response, &self.provider_label());
+            }
             match tokio::time::timeout(
                 std::time::Duration::from_secs(60),
                 self.execute_single_instruction(instruction),
@@ -3276,11 +3828,15 @@ impl OrchestratorAgent {
                 Ok(Ok(())) => tracing::info!(index = i, "Instruction completed"),
                 Ok(Err(e)) => {
                     tracing::error!(index = i, error = %e, "Instruction failed, continuing");
+                    decision.status = DECISION_STATUS_FAILED.to_string();
+                    decision.error = Some(e.to_string());
                 }
                 Err(_) => {
                     tracing::error!(index = i, "Instruction timed out after 60s, skipping");
+                    decision.status = DECISION_STATUS_TIMED_OUT.to_string();
                 }
             }
+            self.record_decision(decision).await;
         }
 
         Ok(())
@@ -3320,8 +3876,15 @@ impl OrchestratorAgent {
         }
 
         match instruction {
-            OrchestratorInstruction::CreateTask { task_id, .. } => {
+            OrchestratorInstruction::CreateTask {
+                task_id,
+                depends_on,
+                ..
+            } => {
                 ensure_uuid_opt(task_id, remap);
+                for dep in depends_on {
+                    resolve(dep, remap);
+                }
             }
             OrchestratorInstruction::CreateTerminal {
                 terminal_id,
@@ -3357,6 +3920,7 @@ impl OrchestratorAgent {
                 description,
                 branch,
                 order_index,
+                depends_on,
             } => {
                 self.ensure_agent_planned_workflow().await?;
                 let workflow_id = {
@@ -3373,6 +3937,7 @@ impl OrchestratorAgent {
                             description,
                             branch,
                             order_index,
+                            depends_on,
                         },
                     )
                     .await?;
@@ -3446,6 +4011,16 @@ impl OrchestratorAgent {
                 instruction,
             } => {
                 self.ensure_agent_planned_workflow().await?;
+                if let Some(terminal) =
+                    db::models::Terminal::find_by_id(&self.db.pool, &terminal_id).await?
+                    && let Some(task) = db::models::WorkflowTask::find_by_id(
+                        &self.db.pool,
+                        &terminal.workflow_task_id,
+                    )
+                    .await?
+                {
+                    self.ensure_task_dependencies_met(&task).await?;
+                }
                 let terminal = self.runtime_actions()?.start_terminal(&terminal_id).await?;
                 let task_id = terminal.workflow_task_id.clone();
                 let planning_complete = self.task_planning_complete(&task_id).await;
@@ -3700,6 +4275,7 @@ impl OrchestratorAgent {
                     .await
                     .map_err(|e| anyhow::anyhow!("Failed to get task: {e}"))?
                     .ok_or_else(|| anyhow::anyhow!("Task {task_id} not found"))?;
+                self.ensure_task_dependencies_met(&task).await?;
 
                 // 2. Get terminals for this task
                 let terminals = db::models::Terminal::find_by_task(&self.db.pool, &task_id)
@@ -4479,6 +5055,8 @@ impl OrchestratorAgent {
     ///
     /// This method is called after the workflow enters running state to automatically
     /// start execution of all tasks by dispatching their first terminals.
+    /// Tasks whose `depends_on` are not all completed are left pending; see
+    /// [`Self::dispatch_unblocked_tasks`].
     ///
     /// # G03-005: Parallel dispatch via join_all
     ///
@@ -4490,6 +5068,103 @@ impl OrchestratorAgent {
     /// `futures::future::join_all`. Individual dispatch failures are logged but
     /// do not abort other dispatches.
     async fn auto_dispatch_initial_tasks(&self) -> anyhow::Result<()> {
+        self.dispatch_ready_tasks(None).await
+    }
+
+    /// Starts pending tasks whose dependencies have all completed.
+    ///
+    /// Pending tasks with a failed or cancelled dependency can never start, so
+    /// they are cancelled (transitively) instead of blocking the workflow.
+    async fn dispatch_unblocked_tasks(&self) -> anyhow::Result<()> {
+        let workflow_id = {
+            let state = self.state.read().await;
+            state.workflow_id.clone()
+        };
+        let mut tasks = db::models::WorkflowTask::find_by_workflow(&self.db.pool, &workflow_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get workflow tasks: {e}"))?;
+        if tasks.iter().all(|task| task.depends_on.is_empty()) {
+            return Ok(());
+        }
+
+        loop {
+            let doomed: Vec<usize> = tasks
+                .iter()
+                .enumerate()
+                .filter(|(_, task)| {
+                    task.status == TASK_STATUS_PENDING
+                        && task_graph::dependency_state(task, &tasks)
+                            == DependencyState::Unsatisfiable
+                })
+                .map(|(index, _)| index)
+                .collect();
+            if doomed.is_empty() {
+                break;
+            }
+            for index in doomed {
+                tracing::warn!(
+                    workflow_id = %workflow_id,
+                    task_id = %tasks[index].id,
+                    "Cancelling task: a dependency failed or was cancelled"
+                );
+                self.broadcast_task_status(&tasks[index].id, TASK_STATUS_CANCELLED)
+                    .await?;
+                tasks[index].status = TASK_STATUS_CANCELLED.to_string();
+            }
+        }
+
+        let ready: HashSet<String> = tasks
+            .iter()
+            .filter(|task| {
+                task.status == TASK_STATUS_PENDING
+                    && !task.depends_on.is_empty()
+                    && task_graph::dependency_state(task, &tasks) == DependencyState::Ready
+            })
+            .map(|task| task.id.clone())
+            .collect();
+        if ready.is_empty() {
+            return Ok(());
+        }
+
+        tracing::info!(
+            workflow_id = %workflow_id,
+            count = ready.len(),
+            "Dependencies satisfied, dispatching unblocked tasks"
+        );
+        self.dispatch_ready_tasks(Some(&ready)).await
+    }
+
+    /// Refuses to start a task before all of its dependencies have completed.
+    async fn ensure_task_dependencies_met(
+        &self,
+        task: &db::models::WorkflowTask,
+    ) -> anyhow::Result<()> {
+        if task.depends_on.is_empty() {
+            return Ok(());
+        }
+        let tasks = db::models::WorkflowTask::find_by_workflow(&self.db.pool, &task.workflow_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get workflow tasks: {e}"))?;
+        match task_graph::dependency_state(task, &tasks) {
+            DependencyState::Ready => Ok(()),
+            DependencyState::Blocked => Err(anyhow::anyhow!(
+                "Task {} is waiting on dependencies: {}",
+                task.id,
+                task.depends_on.join(", ")
+            )),
+            DependencyState::Unsatisfiable => Err(anyhow::anyhow!(
+                "Task {} cannot start: a dependency failed or was cancelled",
+                task.id
+            )),
+        }
+    }
+
+    /// Dispatches the next waiting terminal of every unfinished task whose
+    /// dependencies are satisfied, optionally restricted to `only_task_ids`.
+    async fn dispatch_ready_tasks(
+        &self,
+        only_task_ids: Option<&HashSet<String>>,
+    ) -> anyhow::Result<()> {
         let workflow_id = {
             let state = self.state.read().await;
             state.workflow_id.clone()
@@ -4518,7 +5193,11 @@ impl OrchestratorAgent {
         // State initialization requires the write lock, so this must remain sequential.
         let mut dispatch_queue: Vec<(String, db::models::Terminal, String)> = Vec::new();
 
-        for task in tasks {
+        for task in &tasks {
+            if only_task_ids.is_some_and(|ids| !ids.contains(&task.id)) {
+                continue;
+            }
+
             // Skip tasks that are already completed, failed, or cancelled
             if task.status == TASK_STATUS_COMPLETED
                 || task.status == TASK_STATUS_FAILED
@@ -4528,6 +5207,16 @@ impl OrchestratorAgent {
                 continue;
             }
 
+            // Leave tasks pending until everything they depend on has completed
+            if task_graph::dependency_state(task, &tasks) != DependencyState::Ready {
+                tracing::debug!(
+                    "Skipping task {} until dependencies complete: {:?}",
+                    task.id,
+                    task.depends_on
+                );
+                continue;
+            }
+
             // Get terminals for this task
             let terminals = db::models::Terminal::find_by_task(&self.db.pool, &task.id)
                 .await
@@ -4586,7 +5275,7 @@ impl OrchestratorAgent {
 
             // Build instruction and enqueue for parallel dispatch
             let instruction =
-                Self::build_task_instruction(&workflow_id, &task, &terminal, terminals.len(), None);
+                Self::build_task_instruction(&workflow_id, task, &terminal, terminals.len(), None);
             dispatch_queue.push((task.id.clone(), terminal, instruction));
         }
 
@@ -4823,7 +5512,15 @@ impl OrchestratorAgent {
 
         // Auto-merge completed task branches
         if self.config.auto_merge_on_completion {
-            match self.execute_auto_merge().await {
+            let merge_result = self.execute_auto_merge().await;
+            let mut decision = self.new_decision(DECISION_TYPE_MERGE, "auto_merge").await;
+            decision.reasoning = Some("auto_merge_on_completion is enabled".to_string());
+            if let Err(e) = &merge_result {
+                decision.status = DECISION_STATUS_FAILED.to_string();
+                decision.error = Some(e.to_string());
+            }
+            self.record_decision(decision).await;
+            match merge_result {
                 Ok(()) => {
                     tracing::info!(
                         workflow_id = %workflow_id,
@@ -5298,11 +5995,12 @@ impl OrchestratorAgent {
             state.workflow_id.clone()
         };
 
-        let result = async {
-            let response = self.call_llm(message).await?;
-            self.execute_instruction(&response).await
-        }
-        .await;
+        let result = DECISION_TRIGGER
+            .scope(DecisionTrigger::user_chat(message), async {
+                let response = self.call_llm(message, LLMRoute::Decision).await?;
+                self.execute_llm_response(&response).await
+            })
+            .await;
 
         {
             let mut state = self.state.write().await;
@@ -5413,6 +6111,12 @@ impl OrchestratorAgent {
                 cmd.order_index
             );
 
+            if let Some(violation) = self.enforce_budget().await {
+                return Err(anyhow!(violation.detail));
+            }
+
+            self.maybe_compact_history().await;
+
             // Add rendered prompt as user message to conversation
             {
                 let mut state = self.state.write().await;
@@ -5420,25 +6124,26 @@ impl OrchestratorAgent {
             }
 
             // Send to LLM and get response
+            let messages = self.state.read().await.conversation_history.clone();
             let response = self
                 .llm_client
-                .chat({
-                    let state = self.state.read().await;
-                    state.conversation_history.clone()
-                })
+                .chat_routed(
+                    self.config.tier_for(LLMRoute::Planning),
+                    messages,
+                    Vec::new(),
+                    None,
+                )
                 .await?;
 
             // G24-002: Publish any provider state-change events that occurred
-            // during the direct chat() invocation (same as call_llm does).
+            // during the direct chat_routed() invocation (same as call_llm does).
             self.publish_provider_events().await;
+            self.record_llm_usage(&response).await;
 
             // Add assistant response to conversation
             {
                 let mut state = self.state.write().await;
                 state.add_message("assistant", &response.content, &self.config);
-                if let Some(usage) = &response.usage {
-                    state.total_tokens_used += i64::from(usage.total_tokens);
-                }
             }
 
             tracing::info!(
@@ -5673,6 +6378,7 @@ mod tests {
             branch: "workflow/workflow-1/implement-feature".to_string(),
             status: "pending".to_string(),
             order_index: 0,
+            depends_on: Vec::new(),
             started_at: None,
             completed_at: None,
             created_at: now,
@@ -5699,6 +6405,9 @@ mod tests {
             execution_process_id: None,
             vk_session_id: None,
             auto_confirm: true,
+            resource_limits: None,
+            resource_report: None,
+            runner_labels: None,
             last_commit_hash: None,
             last_commit_message: None,
             started_at: None,
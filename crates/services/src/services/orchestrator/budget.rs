@@ -0,0 +1,227 @@
+//! Per-workflow LLM budget: usage accounting, model pricing and limit checks.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    constants::{PAUSE_REASON_COST_BUDGET, PAUSE_REASON_TIME_BUDGET, PAUSE_REASON_TOKEN_BUDGET},
+    types::LLMUsage,
+};
+
+/// Whether a `workflow.pause_reason` comes from a budget limit.
+pub fn is_budget_pause_reason(reason: &str) -> bool {
+    matches!(
+        reason,
+        PAUSE_REASON_TOKEN_BUDGET | PAUSE_REASON_COST_BUDGET | PAUSE_REASON_TIME_BUDGET
+    )
+}
+
+/// Model price in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input_usd_per_mtok: f64,
+    pub output_usd_per_mtok: f64,
+}
+
+impl ModelPrice {
+    const fn new(input_usd_per_mtok: f64, output_usd_per_mtok: f64) -> Self {
+        Self {
+            input_usd_per_mtok,
+            output_usd_per_mtok,
+        }
+    }
+
+    /// Cost in USD of a single call.
+    pub fn cost_of(&self, usage: &LLMUsage) -> f64 {
+        (f64::from(usage.prompt_tokens) * self.input_usd_per_mtok
+            + f64::from(usage.completion_tokens) * self.output_usd_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// Built-in list prices, matched by model-name prefix (longest prefix wins).
+///
+/// Models missing here are counted as unpriced; their calls still count
+/// toward token and time limits.
+const MODEL_PRICES: &[(&str, ModelPrice)] = &[
+    ("gpt-4o-mini", ModelPrice::new(0.15, 0.60)),
+    ("gpt-4o", ModelPrice::new(2.50, 10.00)),
+    ("gpt-4.1-nano", ModelPrice::new(0.10, 0.40)),
+    ("gpt-4.1-mini", ModelPrice::new(0.40, 1.60)),
+    ("gpt-4.1", ModelPrice::new(2.00, 8.00)),
+    ("o3-mini", ModelPrice::new(1.10, 4.40)),
+    ("o4-mini", ModelPrice::new(1.10, 4.40)),
+    ("claude-3-5-haiku", ModelPrice::new(0.80, 4.00)),
+    ("claude-3-5-sonnet", ModelPrice::new(3.00, 15.00)),
+    ("claude-3-7-sonnet", ModelPrice::new(3.00, 15.00)),
+    ("claude-sonnet-4", ModelPrice::new(3.00, 15.00)),
+    ("claude-opus-4", ModelPrice::new(15.00, 75.00)),
+    ("deepseek-chat", ModelPrice::new(0.27, 1.10)),
+    ("deepseek-reasoner", ModelPrice::new(0.55, 2.19)),
+];
+
+/// Looks up the list price for a model name.
+pub fn price_for_model(model: &str) -> Option<ModelPrice> {
+    let model = model.trim().to_ascii_lowercase();
+    // Strip provider prefixes such as "openai/gpt-4o".
+    let model = model.rsplit('/').next().unwrap_or(&model);
+    MODEL_PRICES
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| *price)
+}
+
+/// Budget limits for one workflow. `None` means unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowBudget {
+    pub max_tokens: Option<i64>,
+    pub max_cost_usd: Option<f64>,
+    pub max_duration_secs: Option<i64>,
+}
+
+impl WorkflowBudget {
+    pub fn from_workflow(workflow: &db::models::Workflow) -> Self {
+        Self {
+            max_tokens: workflow.budget_max_tokens,
+            max_cost_usd: workflow.budget_max_cost_usd,
+            max_duration_secs: workflow.budget_max_duration_secs,
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_tokens.is_none() && self.max_cost_usd.is_none() && self.max_duration_secs.is_none()
+    }
+
+    /// Returns the first limit the usage has reached, if any.
+    pub fn exceeded_by(&self, usage: &WorkflowUsage) -> Option<BudgetViolation> {
+        if let Some(max) = self.max_tokens
+            && usage.total_tokens >= max
+        {
+            return Some(BudgetViolation {
+                pause_reason: PAUSE_REASON_TOKEN_BUDGET,
+                detail: format!("token budget exceeded ({} / {max} tokens)", usage.total_tokens),
+            });
+        }
+        if let Some(max) = self.max_cost_usd
+            && usage.cost_usd >= max
+        {
+            return Some(BudgetViolation {
+                pause_reason: PAUSE_REASON_COST_BUDGET,
+                detail: format!("cost budget exceeded (${:.4} / ${max:.4})", usage.cost_usd),
+            });
+        }
+        if let Some(max) = self.max_duration_secs
+            && usage.active_secs() >= max
+        {
+            return Some(BudgetViolation {
+                pause_reason: PAUSE_REASON_TIME_BUDGET,
+                detail: format!("time budget exceeded ({}s / {max}s)", usage.active_secs()),
+            });
+        }
+        None
+    }
+}
+
+/// A budget limit that was reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetViolation {
+    /// Value stored in `workflow.pause_reason`
+    pub pause_reason: &'static str,
+    /// Human-readable explanation for events and logs
+    pub detail: String,
+}
+
+/// Accumulated LLM usage and wall-clock time of a workflow's orchestrator.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowUsage {
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    /// Spend in USD for priced calls
+    pub cost_usd: f64,
+    pub llm_calls: u64,
+    /// Calls whose model has no known price (not included in `cost_usd`)
+    pub unpriced_calls: u64,
+    /// Time the orchestrator has been running, summed across resumes
+    pub active_ms: i64,
+}
+
+impl WorkflowUsage {
+    /// Adds one LLM call.
+    pub fn record(&mut self, usage: Option<&LLMUsage>, price: Option<ModelPrice>) {
+        self.llm_calls += 1;
+        let Some(usage) = usage else {
+            return;
+        };
+        self.prompt_tokens += i64::from(usage.prompt_tokens);
+        self.completion_tokens += i64::from(usage.completion_tokens);
+        self.total_tokens += i64::from(usage.total_tokens);
+        match price {
+            Some(price) => self.cost_usd += price.cost_of(usage),
+            None => self.unpriced_calls += 1,
+        }
+    }
+
+    pub fn active_secs(&self) -> i64 {
+        self.active_ms / 1000
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(prompt: i32, completion: i32) -> LLMUsage {
+        LLMUsage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+        }
+    }
+
+    #[test]
+    fn price_lookup_prefers_longest_prefix() {
+        assert_eq!(price_for_model("gpt-4o-mini-2024-07-18"), Some(ModelPrice::new(0.15, 0.60)));
+        assert_eq!(price_for_model("GPT-4o"), Some(ModelPrice::new(2.50, 10.00)));
+        assert_eq!(price_for_model("openai/gpt-4.1"), Some(ModelPrice::new(2.00, 8.00)));
+        assert_eq!(price_for_model("glm-4.5"), None);
+    }
+
+    #[test]
+    fn record_accumulates_tokens_and_cost() {
+        let mut total = WorkflowUsage::default();
+        let price = price_for_model("gpt-4o");
+        total.record(Some(&usage(1_000_000, 100_000)), price);
+        total.record(Some(&usage(10, 5)), None);
+        total.record(None, price);
+
+        assert_eq!(total.llm_calls, 3);
+        assert_eq!(total.unpriced_calls, 1);
+        assert_eq!(total.total_tokens, 1_100_015);
+        assert!((total.cost_usd - 3.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn budget_reports_first_exceeded_limit() {
+        let budget = WorkflowBudget {
+            max_tokens: Some(100),
+            max_cost_usd: Some(1.0),
+            max_duration_secs: Some(60),
+        };
+        let mut usage = WorkflowUsage::default();
+        assert!(budget.exceeded_by(&usage).is_none());
+
+        usage.active_ms = 61_000;
+        assert_eq!(
+            budget.exceeded_by(&usage).map(|v| v.pause_reason),
+            Some(PAUSE_REASON_TIME_BUDGET)
+        );
+
+        usage.total_tokens = 100;
+        assert_eq!(
+            budget.exceeded_by(&usage).map(|v| v.pause_reason),
+            Some(PAUSE_REASON_TOKEN_BUDGET)
+        );
+        assert!(WorkflowBudget::default().exceeded_by(&usage).is_none());
+    }
+}
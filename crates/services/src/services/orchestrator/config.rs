@@ -1,10 +1,17 @@
 //! Orchestrator 配置
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::budget::WorkflowBudget;
+use super::history::HistoryCompactionConfig;
+use super::routing::{LLMRoute, ModelTier};
+
 use super::constants::{
-    DEFAULT_LLM_RATE_LIMIT_PER_SECOND, DEFAULT_LLM_TIMEOUT_SECS, DEFAULT_MAX_CONVERSATION_HISTORY,
-    DEFAULT_MAX_RETRIES, DEFAULT_RETRY_DELAY_MS, QUALITY_GATE_DEFAULT_MODE,
+    DEFAULT_LLM_RATE_LIMIT_PER_SECOND, DEFAULT_LLM_TIMEOUT_SECS, DEFAULT_LOCAL_LLM_TIMEOUT_SECS,
+    DEFAULT_MAX_CONVERSATION_HISTORY, DEFAULT_MAX_RETRIES, DEFAULT_RETRY_DELAY_MS,
+    LOCAL_API_TYPE, QUALITY_GATE_DEFAULT_MODE,
 };
 
 /// Configuration for a fallback LLM provider
@@ -22,18 +29,21 @@ pub struct ProviderConfig {
     pub model: String,
     /// Priority (lower = higher priority); used for ordering fallbacks
     pub priority: u32,
+    /// Capability tiers this provider serves (empty = all tiers)
+    #[serde(default)]
+    pub tiers: Vec<ModelTier>,
 }
 
 /// Orchestrator 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrchestratorConfig {
-    /// API 类型: "openai", "anthropic", "custom"
+    /// API 类型: "openai", "anthropic", "custom", "local"
     pub api_type: String,
 
     /// API Base URL
     pub base_url: String,
 
-    /// API Key
+    /// API Key（`local` 可为空）
     pub api_key: String,
 
     /// 模型名称
@@ -59,6 +69,10 @@ pub struct OrchestratorConfig {
     #[serde(default = "default_max_history")]
     pub max_conversation_history: usize,
 
+    /// 历史压缩策略：旧对话由 LLM 总结为固定的记忆消息（默认开启，失败时退回截断）
+    #[serde(default)]
+    pub history_compaction: HistoryCompactionConfig,
+
     /// 系统提示词
     #[serde(default = "default_system_prompt")]
     pub system_prompt: String,
@@ -71,9 +85,21 @@ pub struct OrchestratorConfig {
     #[serde(default)]
     pub fallback_providers: Vec<ProviderConfig>,
 
+    /// 主 provider 提供的能力档位（为空表示全部）
+    #[serde(default)]
+    pub tiers: Vec<ModelTier>,
+
+    /// 调用点 → 能力档位的覆盖配置；未配置的调用点使用默认档位
+    #[serde(default)]
+    pub routing: HashMap<LLMRoute, ModelTier>,
+
     /// Quality gate mode: off | shadow | warn | enforce
     #[serde(default = "default_quality_gate_mode")]
     pub quality_gate_mode: String,
+
+    /// Per-workflow token / cost / time budget (unlimited by default)
+    #[serde(default)]
+    pub budget: WorkflowBudget,
 }
 
 fn default_max_retries() -> u32 {
@@ -178,7 +204,8 @@ Before anything else, evaluate the user's first message:
 fn default_system_prompt() -> String {
     r#"You are the SoloDawn Orchestrator Agent. You decompose projects into Tasks (Git branches) and Terminals (AI coding agents).
 
-RESPOND ONLY WITH A JSON ARRAY. No explanation text. The "type" field is REQUIRED on every object.
+When tools are provided, take every action by calling the matching tool (one call per action, in execution order).
+Otherwise RESPOND ONLY WITH A JSON ARRAY. No explanation text. The "type" field is REQUIRED on every object.
 
 ## Execution Model
 Workflow → Task (own Git branch) → Terminal (PTY AI agent)
@@ -197,7 +224,7 @@ Workflow → Task (own Git branch) → Terminal (PTY AI agent)
 4. Call set_workflow_planning_complete when initial batch is dispatched
 5. On terminal completion events: evaluate results, then decide — create new tasks/terminals, complete_task, merge_branch, or complete_workflow
 
-## Action Types
+## Action Types (tool names match the "type" values)
 create_task: {"type":"create_task","task_id":"t1","name":"...", "branch":"feat/x","order_index":0}
 create_terminal: {"type":"create_terminal","terminal_id":"tm1","task_id":"t1","cli_type_id":"...","model_config_id":"...","role":"coder","auto_confirm":true}
 start_terminal: {"type":"start_terminal","terminal_id":"tm1","instruction":"..."}
@@ -240,10 +267,14 @@ impl Default for OrchestratorConfig {
             retry_delay_ms: default_retry_delay(),
             rate_limit_requests_per_second: default_rate_limit_requests_per_second(),
             max_conversation_history: default_max_history(),
+            history_compaction: HistoryCompactionConfig::default(),
             system_prompt: default_system_prompt(),
             auto_merge_on_completion: default_auto_merge_on_completion(),
             fallback_providers: Vec::new(),
+            tiers: Vec::new(),
+            routing: HashMap::new(),
             quality_gate_mode: default_quality_gate_mode(),
+            budget: WorkflowBudget::default(),
         }
     }
 }
@@ -256,18 +287,38 @@ impl OrchestratorConfig {
         api_key: Option<&str>,
         model: Option<&str>,
     ) -> Option<Self> {
+        let api_type = api_type?.to_string();
+        let timeout_secs = if api_type == LOCAL_API_TYPE {
+            DEFAULT_LOCAL_LLM_TIMEOUT_SECS
+        } else {
+            default_timeout()
+        };
         Some(Self {
-            api_type: api_type?.to_string(),
+            api_type,
             base_url: base_url?.to_string(),
             api_key: api_key?.to_string(),
             model: model?.to_string(),
+            timeout_secs,
             ..Default::default()
         })
     }
 
+    /// Local OpenAI-compatible server (Ollama, llama.cpp): no API key needed.
+    pub fn is_local(&self) -> bool {
+        self.api_type == LOCAL_API_TYPE
+    }
+
+    /// Capability tier for an LLM call site.
+    pub fn tier_for(&self, route: LLMRoute) -> ModelTier {
+        self.routing
+            .get(&route)
+            .copied()
+            .unwrap_or_else(|| route.default_tier())
+    }
+
     /// 验证配置是否有效
     pub fn validate(&self) -> Result<(), String> {
-        if self.api_key.is_empty() {
+        if self.api_key.is_empty() && !self.is_local() {
             return Err("API key is required".to_string());
         }
         if self.base_url.is_empty() {
@@ -285,6 +336,7 @@ impl OrchestratorConfig {
             "anthropic",
             "openai-compatible",
             "anthropic-compatible",
+            LOCAL_API_TYPE,
         ];
         if !valid_api_types.contains(&self.api_type.as_str()) {
             return Err(format!(
@@ -404,6 +456,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_accepts_local_without_api_key() {
+        let config = OrchestratorConfig {
+            api_type: "local".to_string(),
+            base_url: "http://localhost:11434".to_string(),
+            api_key: String::new(),
+            model: "qwen2.5-coder:14b".to_string(),
+            ..Default::default()
+        };
+        assert!(
+            config.validate().is_ok(),
+            "Local servers do not need an API key"
+        );
+    }
+
     // ----- OrchestratorConfig::from_workflow tests -----
 
     #[test]
@@ -456,6 +523,23 @@ mod tests {
         assert_eq!(config.base_url, "https://open.bigmodel.cn/api/paas/v4");
         assert_eq!(config.api_key, "sk-test-key");
         assert_eq!(config.model, "glm-5");
+        assert!(
+            config.history_compaction.enabled,
+            "Workflow runs compact their history"
+        );
+    }
+
+    #[test]
+    fn from_workflow_local_uses_longer_timeout() {
+        let config = OrchestratorConfig::from_workflow(
+            Some("local"),
+            Some("http://localhost:11434"),
+            Some(""),
+            Some("llama3.1:8b"),
+        )
+        .unwrap();
+        assert!(config.is_local());
+        assert_eq!(config.timeout_secs, DEFAULT_LOCAL_LLM_TIMEOUT_SECS);
     }
 
     #[test]
@@ -476,4 +560,30 @@ mod tests {
             defaults.rate_limit_requests_per_second
         );
     }
+
+    #[test]
+    fn routing_overrides_default_tiers() {
+        let config: OrchestratorConfig = serde_json::from_value(serde_json::json!({
+            "api_type": "openai",
+            "base_url": "https://api.openai.com/v1",
+            "api_key": "sk-test",
+            "model": "gpt-4o",
+            "routing": {"history_summary": "strong"},
+            "fallback_providers": [{
+                "name": "mini",
+                "api_type": "openai",
+                "base_url": "https://api.openai.com/v1",
+                "api_key": "sk-test",
+                "model": "gpt-4o-mini",
+                "priority": 1,
+                "tiers": ["cheap"]
+            }]
+        }))
+        .unwrap();
+        assert_eq!(config.tier_for(LLMRoute::HistorySummary), ModelTier::Strong);
+        assert_eq!(config.tier_for(LLMRoute::CompletionSummary), ModelTier::Cheap);
+        assert_eq!(config.tier_for(LLMRoute::Planning), ModelTier::Strong);
+        assert_eq!(config.fallback_providers[0].tiers, vec![ModelTier::Cheap]);
+        assert!(config.tiers.is_empty());
+    }
 }
@@ -13,9 +13,20 @@ pub const GIT_COMMIT_METADATA_SEPARATOR: &str = "---METADATA---";
 /// Environment variable names
 pub const ENCRYPTION_KEY_ENV: &str = "SOLODAWN_ENCRYPTION_KEY";
 
+/// Local OpenAI-compatible servers (Ollama, llama.cpp)
+pub const LOCAL_API_TYPE: &str = "local";
+pub const DEFAULT_LOCAL_BASE_URL: &str = "http://localhost:11434";
+/// Smallest context window the orchestrator prompt and tools fit into.
+pub const LOCAL_MIN_CONTEXT_TOKENS: u32 = 8_192;
+
 /// Default configuration values
 pub const DEFAULT_MAX_CONVERSATION_HISTORY: usize = 50;
+pub const DEFAULT_HISTORY_COMPACTION_TRIGGER_TOKENS: usize = 24_000;
+pub const DEFAULT_HISTORY_KEEP_RECENT_MESSAGES: usize = 12;
+pub const DEFAULT_HISTORY_SUMMARY_MAX_CHARS: usize = 4_000;
 pub const DEFAULT_LLM_TIMEOUT_SECS: u64 = 300;
+/// Local models on consumer hardware answer much slower than hosted APIs.
+pub const DEFAULT_LOCAL_LLM_TIMEOUT_SECS: u64 = 900;
 pub const DEFAULT_MAX_RETRIES: u32 = 3;
 pub const DEFAULT_RETRY_DELAY_MS: u64 = 1000;
 pub const DEFAULT_LLM_RATE_LIMIT_PER_SECOND: u32 = 10;
@@ -45,6 +56,12 @@ pub const WORKFLOW_STATUS_FAILED: &str = "failed";
 pub const WORKFLOW_STATUS_CANCELLED: &str = "cancelled";
 pub const WORKFLOW_STATUS_MERGE_PARTIAL_FAILED: &str = "merge_partial_failed";
 
+/// `workflow.pause_reason` values.
+pub const PAUSE_REASON_USER_REQUESTED: &str = "user_requested";
+pub const PAUSE_REASON_TOKEN_BUDGET: &str = "budget_tokens_exceeded";
+pub const PAUSE_REASON_COST_BUDGET: &str = "budget_cost_exceeded";
+pub const PAUSE_REASON_TIME_BUDGET: &str = "budget_time_exceeded";
+
 /// Task status values — mirrors `WorkflowTaskStatus` enum in `db::models::workflow`.
 pub const TASK_STATUS_PENDING: &str = "pending";
 pub const TASK_STATUS_RUNNING: &str = "running";
@@ -0,0 +1,247 @@
+//! Orchestrator decision audit trail.
+//!
+//! Builds `orchestrator_decision` rows for executed instructions, prompt
+//! decisions and merges, annotated with the LLM reply that produced them and
+//! the bus message that triggered them.
+
+use db::models::orchestrator_decision::OrchestratorDecision;
+use serde_json::Value;
+use utils::text::truncate_to_char_boundary;
+
+use super::{
+    message_bus::BusMessage,
+    types::{LLMResponse, OrchestratorInstruction, PromptDecision},
+};
+
+/// Decision was carried out
+pub const DECISION_STATUS_APPLIED: &str = "applied";
+/// Decision failed while being carried out
+pub const DECISION_STATUS_FAILED: &str = "failed";
+/// Instruction exceeded its execution timeout
+pub const DECISION_STATUS_TIMED_OUT: &str = "timed_out";
+
+/// LLM replies can be long; the audit trail keeps the head.
+const MAX_REASONING_BYTES: usize = 4_000;
+
+/// What the orchestrator was reacting to when it made a decision.
+#[derive(Debug, Clone)]
+pub struct DecisionTrigger {
+    /// Bus message variant (`TerminalCompleted`, ...) or `user_chat`
+    pub kind: String,
+    /// Triggering message as JSON
+    pub message: Option<String>,
+}
+
+impl DecisionTrigger {
+    pub fn from_bus_message(message: &BusMessage) -> Self {
+        let value = serde_json::to_value(message).ok();
+        let kind = match &value {
+            Some(Value::Object(map)) => map.keys().next().cloned(),
+            Some(Value::String(unit)) => Some(unit.clone()),
+            _ => None,
+        }
+        .unwrap_or_else(|| "unknown".to_string());
+        Self {
+            kind,
+            message: value.map(|v| v.to_string()),
+        }
+    }
+
+    pub fn user_chat(message: &str) -> Self {
+        Self {
+            kind: "user_chat".to_string(),
+            message: Some(serde_json::json!({ "message": message }).to_string()),
+        }
+    }
+}
+
+tokio::task_local! {
+    /// Trigger of the message currently handled by the agent task.
+    pub static DECISION_TRIGGER: DecisionTrigger;
+}
+
+/// Trigger in scope for the current task, if any.
+pub fn current_trigger() -> Option<DecisionTrigger> {
+    DECISION_TRIGGER.try_with(Clone::clone).ok()
+}
+
+/// Fills trigger fields from the task-local trigger.
+pub fn apply_trigger(decision: &mut OrchestratorDecision) {
+    if let Some(trigger) = current_trigger() {
+        decision.trigger_type = Some(trigger.kind);
+        decision.trigger_message = trigger.message;
+    }
+}
+
+/// Fills provider, token and reasoning fields from the LLM reply.
+pub fn apply_llm_source(
+    decision: &mut OrchestratorDecision,
+    response: &LLMResponse,
+    default_provider: &str,
+) {
+    decision.provider = Some(
+        response
+            .provider
+            .clone()
+            .unwrap_or_else(|| default_provider.to_string()),
+    );
+    if let Some(usage) = &response.usage {
+        decision.prompt_tokens = Some(i64::from(usage.prompt_tokens));
+        decision.completion_tokens = Some(i64::from(usage.completion_tokens));
+    }
+    decision.reasoning = reasoning_text(&response.content);
+}
+
+fn reasoning_text(text: &str) -> Option<String> {
+    let trimmed = text.trim();
+    (!trimmed.is_empty())
+        .then(|| truncate_to_char_boundary(trimmed, MAX_REASONING_BYTES).to_string())
+}
+
+/// `(terminal_id, task_id)` targeted by an instruction.
+pub fn instruction_targets(
+    instruction: &OrchestratorInstruction,
+) -> (Option<String>, Option<String>) {
+    match instruction {
+        OrchestratorInstruction::StartTask { task_id, .. }
+        | OrchestratorInstruction::CompleteTask { task_id, .. } => (None, Some(task_id.clone())),
+        OrchestratorInstruction::CreateTask { task_id, .. } => (None, task_id.clone()),
+        OrchestratorInstruction::CreateTerminal {
+            terminal_id,
+            task_id,
+            ..
+        } => (terminal_id.clone(), Some(task_id.clone())),
+        OrchestratorInstruction::StartTerminal { terminal_id, .. }
+        | OrchestratorInstruction::CloseTerminal { terminal_id, .. }
+        | OrchestratorInstruction::SendToTerminal { terminal_id, .. }
+        | OrchestratorInstruction::ReviewCode { terminal_id, .. }
+        | OrchestratorInstruction::FixIssues { terminal_id, .. } => {
+            (Some(terminal_id.clone()), None)
+        }
+        OrchestratorInstruction::SetWorkflowPlanningComplete { .. }
+        | OrchestratorInstruction::MergeBranch { .. }
+        | OrchestratorInstruction::CompleteWorkflow { .. }
+        | OrchestratorInstruction::FailWorkflow { .. } => (None, None),
+    }
+}
+
+/// Instruction payload as JSON, with credentials redacted.
+pub fn instruction_detail(instruction: &OrchestratorInstruction) -> Option<String> {
+    let mut value = serde_json::to_value(instruction).ok()?;
+    if let Some(key) = value.get_mut("custom_api_key")
+        && !key.is_null()
+    {
+        *key = Value::String("***".to_string());
+    }
+    Some(value.to_string())
+}
+
+/// `(action, reason)` of a prompt decision.
+pub fn prompt_decision_parts(decision: &PromptDecision) -> (&'static str, &str) {
+    match decision {
+        PromptDecision::AutoConfirm { reason, .. } => ("auto_confirm", reason),
+        PromptDecision::LLMDecision { reasoning, .. } => ("llm_decision", reasoning),
+        PromptDecision::AskUser { reason, .. } => ("ask_user", reason),
+        PromptDecision::Skip { reason } => ("skip", reason),
+    }
+}
+
+/// Timeline summary for a decision.
+pub fn decision_summary(decision: &OrchestratorDecision) -> String {
+    let target = decision
+        .terminal_id
+        .as_deref()
+        .map(|id| format!(" on terminal {}", truncate_to_char_boundary(id, 8)))
+        .or_else(|| {
+            decision
+                .task_id
+                .as_deref()
+                .map(|id| format!(" on task {}", truncate_to_char_boundary(id, 8)))
+        })
+        .unwrap_or_default();
+    format!(
+        "{} {}{target} ({})",
+        decision.decision_type.replace('_', " "),
+        decision.action,
+        decision.status
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use db::models::orchestrator_decision::DECISION_TYPE_INSTRUCTION;
+
+    use super::*;
+    use crate::services::orchestrator::types::LLMUsage;
+
+    #[test]
+    fn trigger_kind_comes_from_bus_variant() {
+        let trigger = DecisionTrigger::from_bus_message(&BusMessage::Shutdown);
+        assert_eq!(trigger.kind, "Shutdown");
+
+        let trigger = DecisionTrigger::from_bus_message(&BusMessage::GitEvent {
+            workflow_id: "wf".to_string(),
+            commit_hash: "abc".to_string(),
+            branch: "main".to_string(),
+            message: "fix".to_string(),
+        });
+        assert_eq!(trigger.kind, "GitEvent");
+        assert!(trigger.message.unwrap().contains("\"commit_hash\":\"abc\""));
+    }
+
+    #[test]
+    fn instruction_detail_redacts_api_key() {
+        let instruction = OrchestratorInstruction::CreateTerminal {
+            terminal_id: Some("term-1".to_string()),
+            task_id: "task-1".to_string(),
+            cli_type_id: "cli-codex".to_string(),
+            model_config_id: "model-x".to_string(),
+            custom_base_url: None,
+            custom_api_key: Some("sk-secret".to_string()),
+            role: None,
+            role_description: None,
+            order_index: None,
+            auto_confirm: None,
+        };
+        let detail = instruction_detail(&instruction).unwrap();
+        assert!(!detail.contains("sk-secret"));
+        assert_eq!(
+            instruction_targets(&instruction),
+            (Some("term-1".to_string()), Some("task-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn llm_source_fills_provider_tokens_and_summary() {
+        let mut decision = OrchestratorDecision::new(
+            "wf",
+            DECISION_TYPE_INSTRUCTION,
+            "send_to_terminal",
+            "applied",
+        );
+        decision.terminal_id = Some("0123456789abcdef".to_string());
+        let response = LLMResponse {
+            content: "  Terminal is idle, nudging it.  ".to_string(),
+            usage: Some(LLMUsage {
+                prompt_tokens: 100,
+                completion_tokens: 20,
+                total_tokens: 120,
+            }),
+            tool_calls: Vec::new(),
+            provider: None,
+            model: None,
+        };
+        apply_llm_source(&mut decision, &response, "openai(gpt-4o)");
+
+        assert_eq!(decision.provider.as_deref(), Some("openai(gpt-4o)"));
+        assert_eq!(decision.prompt_tokens, Some(100));
+        assert_eq!(
+            decision.reasoning.as_deref(),
+            Some("Terminal is idle, nudging it.")
+        );
+        assert_eq!(
+            decision_summary(&decision),
+            "instruction send_to_terminal on terminal 01234567 (applied)"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Conversation-history compaction: older turns are summarized by the LLM into
+//! a pinned memory message instead of being dropped.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    constants::{
+        DEFAULT_HISTORY_COMPACTION_TRIGGER_TOKENS, DEFAULT_HISTORY_KEEP_RECENT_MESSAGES,
+        DEFAULT_HISTORY_SUMMARY_MAX_CHARS,
+    },
+    types::LLMMessage,
+};
+
+/// Marks the pinned memory message inside `conversation_history`.
+pub const MEMORY_MESSAGE_PREFIX: &str = "[Workflow memory]";
+
+/// History compaction policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryCompactionConfig {
+    /// When false, history is only capped by `max_conversation_history`
+    pub enabled: bool,
+    /// Estimated token count of the history that triggers compaction
+    pub trigger_tokens: usize,
+    /// Most recent non-system messages kept verbatim
+    pub keep_recent_messages: usize,
+    /// Upper bound on the stored summary length
+    pub summary_max_chars: usize,
+}
+
+impl Default for HistoryCompactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            trigger_tokens: DEFAULT_HISTORY_COMPACTION_TRIGGER_TOKENS,
+            keep_recent_messages: DEFAULT_HISTORY_KEEP_RECENT_MESSAGES,
+            summary_max_chars: DEFAULT_HISTORY_SUMMARY_MAX_CHARS,
+        }
+    }
+}
+
+/// Older turns selected for summarization.
+#[derive(Debug, Clone)]
+pub struct CompactionBatch {
+    /// Summary produced by the previous compaction, folded into the new one
+    pub previous_memory: Option<String>,
+    /// Oldest non-system messages, in order
+    pub messages: Vec<LLMMessage>,
+}
+
+/// Rough token estimate (~4 characters per token plus per-message overhead).
+pub fn estimate_tokens(messages: &[LLMMessage]) -> usize {
+    messages
+        .iter()
+        .map(|m| m.content.chars().count().div_ceil(4) + 4)
+        .sum()
+}
+
+pub fn is_memory_message(message: &LLMMessage) -> bool {
+    message.role == "system" && message.content.starts_with(MEMORY_MESSAGE_PREFIX)
+}
+
+/// Content of the memory message pinned into the history.
+pub fn memory_message_content(summary: &str) -> String {
+    format!("{MEMORY_MESSAGE_PREFIX}\n{summary}")
+}
+
+/// Extracts the summary text from a memory message.
+pub fn memory_summary(message: &LLMMessage) -> Option<&str> {
+    is_memory_message(message).then(|| {
+        message.content[MEMORY_MESSAGE_PREFIX.len()..].trim_start_matches('\n')
+    })
+}
+
+/// Builds the summarization request for a batch.
+pub fn summary_request(batch: &CompactionBatch, max_chars: usize) -> Vec<LLMMessage> {
+    let mut transcript = String::new();
+    if let Some(memory) = &batch.previous_memory {
+        transcript.push_str("## Existing memory\n");
+        transcript.push_str(memory);
+        transcript.push_str("\n\n");
+    }
+    transcript.push_str("## Conversation to fold in\n");
+    for message in &batch.messages {
+        transcript.push_str(&format!("[{}] {}\n", message.role, message.content));
+    }
+
+    vec![
+        LLMMessage {
+            role: "system".to_string(),
+            content: format!(
+                "You maintain the long-term memory of the SoloDawn Orchestrator. \
+                 Merge the existing memory and the conversation below into one concise summary \
+                 (at most {max_chars} characters). Keep: the overall plan, tasks and their branches, \
+                 terminal assignments, decisions made, approaches that were rejected and why, \
+                 and open problems. Drop chatter and repeated status updates. \
+                 Reply with the summary text only."
+            ),
+        },
+        LLMMessage {
+            role: "user".to_string(),
+            content: transcript,
+        },
+    ]
+}
+
+/// Normalizes the LLM summary before it is stored.
+pub fn clamp_summary(summary: &str, max_chars: usize) -> String {
+    let summary = summary.trim();
+    if summary.chars().count() <= max_chars {
+        return summary.to_string();
+    }
+    let mut clamped: String = summary.chars().take(max_chars).collect();
+    clamped.push_str("...");
+    clamped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_message_round_trip() {
+        let message = LLMMessage {
+            role: "system".to_string(),
+            content: memory_message_content("plan: A then B"),
+        };
+        assert!(is_memory_message(&message));
+        assert_eq!(memory_summary(&message), Some("plan: A then B"));
+
+        let prompt = LLMMessage {
+            role: "system".to_string(),
+            content: "You are the orchestrator".to_string(),
+        };
+        assert!(!is_memory_message(&prompt));
+        assert_eq!(memory_summary(&prompt), None);
+    }
+
+    #[test]
+    fn clamp_summary_limits_length() {
+        assert_eq!(clamp_summary("  short  ", 10), "short");
+        assert_eq!(clamp_summary("abcdefghij", 4), "abcd...");
+    }
+}
@@ -0,0 +1,293 @@
+//! Native tool-calling schema for orchestrator instructions.
+//!
+//! Every [`OrchestratorInstruction`] variant is exposed to the LLM as a function
+//! tool whose name equals the variant's serde `type` tag. A structured tool call
+//! is converted back into an instruction by re-inserting that tag into the
+//! argument object, so the tool schema and the text-fallback JSON format stay in
+//! lockstep.
+
+use serde_json::{Map, Value, json};
+
+use super::types::{LLMToolCall, LLMToolDefinition, OrchestratorInstruction};
+
+fn tool(name: &str, description: &str, properties: Value, required: &[&str]) -> LLMToolDefinition {
+    LLMToolDefinition {
+        name: name.to_string(),
+        description: description.to_string(),
+        parameters: json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        }),
+    }
+}
+
+/// Returns one tool definition per orchestrator instruction variant.
+pub fn instruction_tool_definitions() -> Vec<LLMToolDefinition> {
+    let string = json!({ "type": "string" });
+    let integer = json!({ "type": "integer" });
+
+    vec![
+        tool(
+            "start_task",
+            "Dispatch the next waiting terminal of an existing task with an instruction.",
+            json!({ "task_id": string, "instruction": string }),
+            &["task_id", "instruction"],
+        ),
+        tool(
+            "create_task",
+            "Create a runtime task (own Git branch). agent_planned workflows only. \
+             The task is not started until every task in depends_on has completed.",
+            json!({
+                "task_id": string,
+                "name": string,
+                "description": string,
+                "branch": string,
+                "order_index": integer,
+                "depends_on": { "type": "array", "items": string },
+            }),
+            &["name"],
+        ),
+        tool(
+            "create_terminal",
+            "Create a runtime terminal (AI coding agent) for a task. agent_planned workflows only.",
+            json!({
+                "terminal_id": string,
+                "task_id": string,
+                "cli_type_id": string,
+                "model_config_id": string,
+                "custom_base_url": string,
+                "custom_api_key": string,
+                "role": string,
+                "role_description": string,
+                "order_index": integer,
+                "auto_confirm": { "type": "boolean" },
+            }),
+            &["task_id", "cli_type_id", "model_config_id"],
+        ),
+        tool(
+            "start_terminal",
+            "Start a runtime terminal and send its first instruction.",
+            json!({ "terminal_id": string, "instruction": string }),
+            &["terminal_id", "instruction"],
+        ),
+        tool(
+            "close_terminal",
+            "Close a terminal, keeping its final status and history.",
+            json!({ "terminal_id": string, "final_status": string }),
+            &["terminal_id"],
+        ),
+        tool(
+            "complete_task",
+            "Mark task planning complete; the task finishes once all its terminals end.",
+            json!({ "task_id": string, "summary": string }),
+            &["task_id", "summary"],
+        ),
+        tool(
+            "set_workflow_planning_complete",
+            "Declare that no more tasks or terminals will be added to the workflow.",
+            json!({ "summary": string }),
+            &[],
+        ),
+        tool(
+            "send_to_terminal",
+            "Send a message to a working terminal.",
+            json!({ "terminal_id": string, "message": string }),
+            &["terminal_id", "message"],
+        ),
+        tool(
+            "review_code",
+            "Ask a terminal to review a commit.",
+            json!({ "terminal_id": string, "commit_hash": string }),
+            &["terminal_id", "commit_hash"],
+        ),
+        tool(
+            "fix_issues",
+            "Ask a terminal to fix a list of issues.",
+            json!({
+                "terminal_id": string,
+                "issues": { "type": "array", "items": string },
+            }),
+            &["terminal_id", "issues"],
+        ),
+        tool(
+            "merge_branch",
+            "Merge a task branch into the target branch.",
+            json!({ "source_branch": string, "target_branch": string }),
+            &["source_branch", "target_branch"],
+        ),
+        tool(
+            "complete_workflow",
+            "Mark the workflow as completed.",
+            json!({ "summary": string }),
+            &["summary"],
+        ),
+        tool(
+            "fail_workflow",
+            "Mark the workflow as failed.",
+            json!({ "reason": string }),
+            &["reason"],
+        ),
+    ]
+}
+
+/// Converts a single structured tool call into an orchestrator instruction.
+pub fn instruction_from_tool_call(call: &LLMToolCall) -> anyhow::Result<OrchestratorInstruction> {
+    let mut payload = match &call.arguments {
+        Value::Object(map) => map.clone(),
+        Value::Null => Map::new(),
+        // Some OpenAI-compatible proxies double-encode the argument object.
+        Value::String(raw) => serde_json::from_str::<Map<String, Value>>(raw).map_err(|e| {
+            anyhow::anyhow!("Tool call '{}' has unparsable arguments: {e}", call.name)
+        })?,
+        other => {
+            return Err(anyhow::anyhow!(
+                "Tool call '{}' arguments must be an object, got {other}",
+                call.name
+            ));
+        }
+    };
+    payload.insert("type".to_string(), Value::String(call.name.clone()));
+
+    serde_json::from_value(Value::Object(payload))
+        .map_err(|e| anyhow::anyhow!("Tool call '{}' is not a valid instruction: {e}", call.name))
+}
+
+/// Converts all tool calls, skipping (and logging) the ones that do not map to
+/// a valid instruction.
+pub fn instructions_from_tool_calls(calls: &[LLMToolCall]) -> Vec<OrchestratorInstruction> {
+    calls
+        .iter()
+        .filter_map(|call| match instruction_from_tool_call(call) {
+            Ok(instruction) => Some(instruction),
+            Err(e) => {
+                tracing::warn!(tool_call_id = %call.id, error = %e, "Skipping invalid tool call");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Renders an assistant turn for conversation history.
+///
+/// Tool calls are stored in the same JSON-array form the text fallback uses so
+/// later turns (and providers without tool support) see the actions taken.
+pub fn assistant_history_content(content: &str, calls: &[LLMToolCall]) -> String {
+    if calls.is_empty() {
+        return content.to_string();
+    }
+
+    let actions: Vec<Value> = calls
+        .iter()
+        .map(|call| {
+            let mut object = match &call.arguments {
+                Value::Object(map) => map.clone(),
+                _ => Map::new(),
+            };
+            object.insert("type".to_string(), Value::String(call.name.clone()));
+            Value::Object(object)
+        })
+        .collect();
+    let actions = serde_json::to_string(&actions).unwrap_or_default();
+
+    if content.trim().is_empty() {
+        actions
+    } else {
+        format!("{}\n{actions}", content.trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn call(name: &str, arguments: Value) -> LLMToolCall {
+        LLMToolCall {
+            id: format!("call-{name}"),
+            name: name.to_string(),
+            arguments,
+        }
+    }
+
+    #[test]
+    fn definitions_have_unique_names_and_object_schemas() {
+        let definitions = instruction_tool_definitions();
+        let names: HashSet<_> = definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names.len(), definitions.len());
+        assert_eq!(definitions.len(), 13);
+        for definition in &definitions {
+            assert_eq!(definition.parameters["type"], "object");
+        }
+    }
+
+    #[test]
+    fn tool_call_converts_to_instruction() {
+        let instruction = instruction_from_tool_call(&call(
+            "create_terminal",
+            json!({
+                "task_id": "t1",
+                "cli_type_id": "cli-codex",
+                "model_config_id": "model-x",
+                "auto_confirm": true,
+            }),
+        ))
+        .expect("valid tool call");
+
+        match instruction {
+            OrchestratorInstruction::CreateTerminal {
+                task_id,
+                auto_confirm,
+                terminal_id,
+                ..
+            } => {
+                assert_eq!(task_id, "t1");
+                assert_eq!(auto_confirm, Some(true));
+                assert!(terminal_id.is_none());
+            }
+            other => panic!("unexpected instruction: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn string_encoded_arguments_are_accepted() {
+        let instruction = instruction_from_tool_call(&call(
+            "fail_workflow",
+            Value::String(r#"{"reason":"boom"}"#.to_string()),
+        ))
+        .expect("double-encoded arguments");
+        assert!(matches!(
+            instruction,
+            OrchestratorInstruction::FailWorkflow { reason } if reason == "boom"
+        ));
+    }
+
+    #[test]
+    fn invalid_tool_calls_are_skipped() {
+        let calls = vec![
+            call("unknown_tool", json!({})),
+            call("start_terminal", json!({ "terminal_id": "tm1" })),
+            call("set_workflow_planning_complete", Value::Null),
+        ];
+        let instructions = instructions_from_tool_calls(&calls);
+        assert_eq!(instructions.len(), 1);
+        assert!(matches!(
+            instructions[0],
+            OrchestratorInstruction::SetWorkflowPlanningComplete { summary: None }
+        ));
+    }
+
+    #[test]
+    fn history_content_round_trips_through_text_parser() {
+        let calls = vec![call(
+            "send_to_terminal",
+            json!({ "terminal_id": "tm1", "message": "hi" }),
+        )];
+        let text = assistant_history_content("", &calls);
+        let parsed: Vec<OrchestratorInstruction> =
+            serde_json::from_str(&text).expect("history text is a JSON instruction array");
+        assert_eq!(parsed.len(), 1);
+    }
+}
@@ -3,6 +3,7 @@
 use std::{num::NonZeroU32, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use futures_util::StreamExt;
 use governor::{
     Quota, RateLimiter,
     clock::DefaultClock,
@@ -15,15 +16,70 @@ use utils::url::normalize_base_url;
 
 use super::{
     config::OrchestratorConfig,
+    constants::{DEFAULT_LOCAL_LLM_TIMEOUT_SECS, LOCAL_API_TYPE},
     resilient_llm::{ProviderEvent, ProviderStatusReport},
-    types::{LLMMessage, LLMResponse, LLMUsage},
+    routing::ModelTier,
+    types::{
+        LLMMessage, LLMResponse, LLMStreamEvent, LLMToolCall, LLMToolDefinition, LLMUsage,
+    },
 };
 
+/// Channel receiving incremental output from [`LLMClient::chat_stream`].
+pub type LLMStreamSender = tokio::sync::mpsc::UnboundedSender<LLMStreamEvent>;
+
 /// Defines the LLM client interface used by the orchestrator.
 #[async_trait]
 pub trait LLMClient: Send + Sync {
     async fn chat(&self, messages: Vec<LLMMessage>) -> anyhow::Result<LLMResponse>;
 
+    /// Chat with native tool definitions attached.
+    ///
+    /// Clients without tool support fall back to plain `chat`, which leaves
+    /// `LLMResponse::tool_calls` empty so callers parse the text instead.
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<LLMMessage>,
+        _tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat(messages).await
+    }
+
+    /// Chat with incremental output: text deltas are sent on `events` as they
+    /// arrive and the assembled response is returned once the stream ends.
+    ///
+    /// Clients without streaming support send the whole reply as a single delta.
+    async fn chat_stream(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: LLMStreamSender,
+    ) -> anyhow::Result<LLMResponse> {
+        let response = self.chat_with_tools(messages, tools).await?;
+        if !response.content.is_empty() {
+            let _ = events.send(LLMStreamEvent::Delta {
+                text: response.content.clone(),
+            });
+        }
+        Ok(response)
+    }
+
+    /// Chat tagged with the capability tier picked by the call site.
+    ///
+    /// Multi-provider clients route to the providers serving `tier`; other
+    /// clients ignore it. Streams deltas when `events` is set.
+    async fn chat_routed(
+        &self,
+        _tier: ModelTier,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: Option<LLMStreamSender>,
+    ) -> anyhow::Result<LLMResponse> {
+        match events {
+            Some(events) => self.chat_stream(messages, tools, events).await,
+            None => self.chat_with_tools(messages, tools).await,
+        }
+    }
+
     /// Returns provider status reports. Default returns empty (single-provider clients).
     async fn provider_status(&self) -> Vec<ProviderStatusReport> {
         Vec::new()
@@ -74,6 +130,25 @@ where
         self.inner.chat(messages).await
     }
 
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        self.rate_limiter.until_ready().await;
+        self.inner.chat_with_tools(messages, tools).await
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: LLMStreamSender,
+    ) -> anyhow::Result<LLMResponse> {
+        self.rate_limiter.until_ready().await;
+        self.inner.chat_stream(messages, tools, events).await
+    }
+
     /// [G24-001] Transparent forwarding to inner client.
     async fn provider_status(&self) -> Vec<ProviderStatusReport> {
         self.inner.provider_status().await
@@ -151,6 +226,9 @@ impl LLMClient for MockLLMClient {
                 completion_tokens: 20,
                 total_tokens: 30,
             }),
+            tool_calls: Vec::new(),
+            provider: None,
+            model: None,
         })
     }
 }
@@ -161,6 +239,18 @@ struct ChatRequest {
     messages: Vec<ChatMessage>,
     temperature: Option<f32>,
     max_tokens: Option<i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<ChatTool>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<ChatStreamOptions>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatStreamOptions {
+    /// Ask for a final chunk carrying token usage
+    include_usage: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -169,6 +259,20 @@ struct ChatMessage {
     content: String,
 }
 
+#[derive(Debug, Serialize)]
+struct ChatTool {
+    #[serde(rename = "type")]
+    tool_type: &'static str,
+    function: ChatToolFunction,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatToolFunction {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
 #[derive(Debug, Deserialize)]
 struct ChatResponse {
     choices: Vec<ChatChoice>,
@@ -177,7 +281,30 @@ struct ChatResponse {
 
 #[derive(Debug, Deserialize)]
 struct ChatChoice {
-    message: ChatMessage,
+    message: ChatResponseMessage,
+}
+
+/// Assistant message; `content` is null when the model only returns tool calls.
+#[derive(Debug, Deserialize)]
+struct ChatResponseMessage {
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ChatToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCall {
+    #[serde(default)]
+    id: String,
+    function: ChatToolCallFunction,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCallFunction {
+    name: String,
+    /// JSON-encoded argument object
+    #[serde(default)]
+    arguments: String,
 }
 
 #[allow(clippy::struct_field_names)]
@@ -188,6 +315,193 @@ struct UsageInfo {
     total_tokens: i32,
 }
 
+impl From<UsageInfo> for LLMUsage {
+    fn from(u: UsageInfo) -> Self {
+        Self {
+            prompt_tokens: u.prompt_tokens,
+            completion_tokens: u.completion_tokens,
+            total_tokens: u.total_tokens,
+        }
+    }
+}
+
+impl From<ChatToolCall> for LLMToolCall {
+    fn from(call: ChatToolCall) -> Self {
+        let arguments = if call.function.arguments.trim().is_empty() {
+            serde_json::Value::Object(serde_json::Map::new())
+        } else {
+            serde_json::from_str(&call.function.arguments).unwrap_or_else(|e| {
+                tracing::warn!(
+                    tool = %call.function.name,
+                    error = %e,
+                    "Tool call arguments are not valid JSON, keeping raw string"
+                );
+                serde_json::Value::String(call.function.arguments.clone())
+            })
+        };
+        Self {
+            id: call.id,
+            name: call.function.name,
+            arguments,
+        }
+    }
+}
+
+impl ChatResponse {
+    fn into_llm_response(self) -> anyhow::Result<LLMResponse> {
+        // [G24-005] Return an error when the API returns no choices instead of
+        // silently producing an empty string that downstream code cannot distinguish
+        // from a legitimate empty response.
+        let message = self
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message)
+            .ok_or_else(|| anyhow::anyhow!("LLM API returned empty choices array"))?;
+
+        Ok(LLMResponse {
+            content: message.content.unwrap_or_default(),
+            usage: self.usage.map(LLMUsage::from),
+            tool_calls: message.tool_calls.into_iter().map(LLMToolCall::from).collect(),
+            provider: None,
+            model: None,
+        })
+    }
+}
+
+/// One `data:` payload of a server-sent event stream.
+enum SseData {
+    Done,
+    Event(serde_json::Value),
+}
+
+fn parse_sse_line(line: &str) -> Option<SseData> {
+    let data = line.trim().strip_prefix("data:")?.trim_start();
+    if data == "[DONE]" {
+        return Some(SseData::Done);
+    }
+    serde_json::from_str(data).ok().map(SseData::Event)
+}
+
+fn is_event_stream(response: &reqwest::Response) -> bool {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("text/event-stream"))
+}
+
+/// Reads an SSE response body incrementally, handing each `data:` event to
+/// `on_event` as soon as its line is complete.
+///
+/// Returns the raw body read so far so callers can fall back to plain JSON.
+async fn read_sse_events(
+    response: reqwest::Response,
+    mut on_event: impl FnMut(serde_json::Value),
+) -> anyhow::Result<String> {
+    let mut raw = Vec::new();
+    let mut pending = Vec::new();
+    let mut body = response.bytes_stream();
+
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        raw.extend_from_slice(&chunk);
+        pending.extend_from_slice(&chunk);
+        // Split on the newline byte so multi-byte UTF-8 sequences are never cut.
+        while let Some(pos) = pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=pos).collect();
+            match parse_sse_line(&String::from_utf8_lossy(&line)) {
+                Some(SseData::Done) => return Ok(String::from_utf8_lossy(&raw).into_owned()),
+                Some(SseData::Event(event)) => on_event(event),
+                None => {}
+            }
+        }
+    }
+    if let Some(SseData::Event(event)) = parse_sse_line(&String::from_utf8_lossy(&pending)) {
+        on_event(event);
+    }
+
+    Ok(String::from_utf8_lossy(&raw).into_owned())
+}
+
+fn send_delta(events: &LLMStreamSender, text: String) {
+    if !text.is_empty() {
+        // Receiver gone means nobody is watching; keep assembling the response.
+        let _ = events.send(LLMStreamEvent::Delta { text });
+    }
+}
+
+/// Assembles an OpenAI `chat.completion.chunk` stream.
+#[derive(Debug, Default)]
+struct OpenAIStreamState {
+    content: String,
+    /// Tool call fragments keyed by `index`: (id, name, arguments)
+    tool_calls: std::collections::BTreeMap<u64, (String, String, String)>,
+    usage: Option<LLMUsage>,
+    received_chunk: bool,
+}
+
+impl OpenAIStreamState {
+    /// Applies one chunk and returns the text delta it carried, if any.
+    fn apply(&mut self, chunk: &serde_json::Value) -> Option<String> {
+        self.received_chunk = true;
+        if let Some(usage) = chunk.get("usage").filter(|u| !u.is_null())
+            && let Ok(usage) = serde_json::from_value::<UsageInfo>(usage.clone())
+        {
+            self.usage = Some(usage.into());
+        }
+
+        let delta = chunk.pointer("/choices/0/delta")?;
+        if let Some(calls) = delta.get("tool_calls").and_then(|c| c.as_array()) {
+            for call in calls {
+                let index = call
+                    .get("index")
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or_default();
+                let entry = self.tool_calls.entry(index).or_default();
+                if let Some(id) = call.get("id").and_then(|v| v.as_str()) {
+                    entry.0 = id.to_string();
+                }
+                if let Some(name) = call.pointer("/function/name").and_then(|v| v.as_str()) {
+                    entry.1.push_str(name);
+                }
+                if let Some(args) = call
+                    .pointer("/function/arguments")
+                    .and_then(|v| v.as_str())
+                {
+                    entry.2.push_str(args);
+                }
+            }
+        }
+
+        let text = delta.get("content").and_then(|c| c.as_str())?;
+        self.content.push_str(text);
+        Some(text.to_string())
+    }
+
+    fn finish(self) -> anyhow::Result<LLMResponse> {
+        if !self.received_chunk {
+            return Err(anyhow::anyhow!("LLM API stream ended without any chunk"));
+        }
+        Ok(LLMResponse {
+            content: self.content,
+            usage: self.usage,
+            tool_calls: self
+                .tool_calls
+                .into_values()
+                .map(|(id, name, arguments)| {
+                    LLMToolCall::from(ChatToolCall {
+                        id,
+                        function: ChatToolCallFunction { name, arguments },
+                    })
+                })
+                .collect(),
+            provider: None,
+            model: None,
+        })
+    }
+}
+
 impl OpenAICompatibleClient {
     pub fn new(config: &OrchestratorConfig) -> Self {
         let client = Client::builder()
@@ -213,10 +527,12 @@ impl OpenAICompatibleClient {
         }
     }
 
-    /// Perform a single chat request without retry logic
-    async fn chat_once(&self, messages: Vec<LLMMessage>) -> anyhow::Result<LLMResponse> {
-        let url = format!("{}/chat/completions", self.base_url);
-
+    fn build_request(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        stream: bool,
+    ) -> ChatRequest {
         let chat_messages: Vec<ChatMessage> = messages
             .into_iter()
             .map(|m| ChatMessage {
@@ -225,26 +541,52 @@ impl OpenAICompatibleClient {
             })
             .collect();
 
-        let request = ChatRequest {
+        let tools = tools
+            .into_iter()
+            .map(|t| ChatTool {
+                tool_type: "function",
+                function: ChatToolFunction {
+                    name: t.name,
+                    description: t.description,
+                    parameters: t.parameters,
+                },
+            })
+            .collect();
+
+        ChatRequest {
             model: self.model.clone(),
             messages: chat_messages,
             temperature: Some(0.7),
             max_tokens: Some(2048),
-        };
+            tools,
+            stream,
+            stream_options: stream.then_some(ChatStreamOptions {
+                include_usage: true,
+            }),
+        }
+    }
+
+    /// Sends a chat request and returns the response once the status is successful.
+    async fn send(&self, request: &ChatRequest) -> anyhow::Result<reqwest::Response> {
+        let url = format!("{}/chat/completions", self.base_url);
 
         tracing::info!(
             url = %url,
             model = %self.model,
             msg_count = request.messages.len(),
+            tool_count = request.tools.len(),
+            stream = request.stream,
             "OpenAI-compatible LLM request starting"
         );
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
+        let mut builder = self.client.post(&url);
+        // Local servers (api_type "local") usually run without an API key.
+        if !self.api_key.is_empty() {
+            builder = builder.header("Authorization", format!("Bearer {}", self.api_key));
+        }
+        let response = builder
             .header("Content-Type", "application/json")
-            .json(&request)
+            .json(request)
             .send()
             .await
             .map_err(|e| {
@@ -263,23 +605,47 @@ impl OpenAICompatibleClient {
             return Err(anyhow::anyhow!("LLM API error: {status} - {body}"));
         }
 
+        Ok(response)
+    }
+
+    /// Perform a single chat request without retry logic
+    async fn chat_once(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        let request = self.build_request(messages, tools, false);
+        let response = self.send(&request).await?;
         let chat_response: ChatResponse = response.json().await?;
-        // [G24-005] Return an error when the API returns no choices instead of
-        // silently producing an empty string that downstream code cannot distinguish
-        // from a legitimate empty response.
-        let content = chat_response
-            .choices
-            .first()
-            .map(|c| c.message.content.clone())
-            .ok_or_else(|| anyhow::anyhow!("LLM API returned empty choices array"))?;
+        chat_response.into_llm_response()
+    }
 
-        let usage = chat_response.usage.map(|u| LLMUsage {
-            prompt_tokens: u.prompt_tokens,
-            completion_tokens: u.completion_tokens,
-            total_tokens: u.total_tokens,
-        });
+    /// Perform a single streaming chat request without retry logic
+    async fn chat_stream_once(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: &LLMStreamSender,
+    ) -> anyhow::Result<LLMResponse> {
+        let request = self.build_request(messages, tools, true);
+        let response = self.send(&request).await?;
+
+        // Some OpenAI-compatible servers ignore `stream` and reply with plain JSON.
+        if !is_event_stream(&response) {
+            let chat_response: ChatResponse = response.json().await?;
+            let result = chat_response.into_llm_response()?;
+            send_delta(events, result.content.clone());
+            return Ok(result);
+        }
 
-        Ok(LLMResponse { content, usage })
+        let mut state = OpenAIStreamState::default();
+        read_sse_events(response, |chunk| {
+            if let Some(text) = state.apply(&chunk) {
+                send_delta(events, text);
+            }
+        })
+        .await?;
+        state.finish()
     }
 }
 
@@ -290,7 +656,30 @@ impl LLMClient for OpenAICompatibleClient {
         // ResilientLLMClient's cross-provider retry loop is the sole retry layer.
         // Stacking 3 inner retries × N providers leads to excessive backoff delays
         // and confusing failure counts in the circuit breaker.
-        self.chat_once(messages).await
+        self.chat_once(messages, Vec::new())
+            .await
+            .map(|r| r.served_by(&self.model))
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat_once(messages, tools)
+            .await
+            .map(|r| r.served_by(&self.model))
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: LLMStreamSender,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat_stream_once(messages, tools, &events)
+            .await
+            .map(|r| r.served_by(&self.model))
     }
 }
 
@@ -318,6 +707,8 @@ struct AnthropicRequest {
     system: Option<String>,
     /// Always true — some Anthropic-compatible proxies only support streaming.
     stream: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<AnthropicTool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -326,6 +717,209 @@ struct AnthropicMessage {
     content: String,
 }
 
+#[derive(Debug, Serialize)]
+struct AnthropicTool {
+    name: String,
+    description: String,
+    input_schema: serde_json::Value,
+}
+
+/// A `tool_use` content block being assembled from `input_json_delta` events.
+#[derive(Debug, Default)]
+struct PendingToolUse {
+    id: String,
+    name: String,
+    initial_input: serde_json::Value,
+    partial_json: String,
+}
+
+impl PendingToolUse {
+    fn finish(self) -> LLMToolCall {
+        let arguments = if self.partial_json.trim().is_empty() {
+            self.initial_input
+        } else {
+            serde_json::from_str(&self.partial_json)
+                .unwrap_or(serde_json::Value::String(self.partial_json))
+        };
+        LLMToolCall {
+            id: self.id,
+            name: self.name,
+            arguments,
+        }
+    }
+}
+
+/// Assembles an Anthropic `/messages` event stream.
+#[derive(Debug, Default)]
+struct AnthropicStreamState {
+    content: String,
+    input_tokens: i32,
+    output_tokens: i32,
+    pending_tools: std::collections::BTreeMap<u64, PendingToolUse>,
+}
+
+impl AnthropicStreamState {
+    /// Applies one event and returns the text delta it carried, if any.
+    fn apply(&mut self, event: &serde_json::Value) -> Option<String> {
+        let index = event
+            .get("index")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or_default();
+        match event.get("type").and_then(|t| t.as_str()) {
+            Some("content_block_start") => {
+                let block = event.get("content_block");
+                if block.and_then(|b| b.get("type")).and_then(|t| t.as_str()) == Some("tool_use") {
+                    let field = |name: &str| {
+                        block
+                            .and_then(|b| b.get(name))
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string()
+                    };
+                    self.pending_tools.insert(
+                        index,
+                        PendingToolUse {
+                            id: field("id"),
+                            name: field("name"),
+                            initial_input: block
+                                .and_then(|b| b.get("input"))
+                                .cloned()
+                                .unwrap_or(serde_json::Value::Null),
+                            partial_json: String::new(),
+                        },
+                    );
+                }
+            }
+            Some("content_block_delta") => {
+                if let Some(text) = event.pointer("/delta/text").and_then(|t| t.as_str()) {
+                    self.content.push_str(text);
+                    return Some(text.to_string());
+                } else if let Some(partial) = event
+                    .pointer("/delta/partial_json")
+                    .and_then(|t| t.as_str())
+                    && let Some(tool) = self.pending_tools.get_mut(&index)
+                {
+                    tool.partial_json.push_str(partial);
+                }
+            }
+            Some("message_start") => {
+                if let Some(u) = event.pointer("/message/usage/input_tokens").and_then(serde_json::Value::as_i64) {
+                    self.input_tokens = u as i32;
+                }
+            }
+            Some("message_delta") => {
+                if let Some(u) = event.pointer("/usage/output_tokens").and_then(serde_json::Value::as_i64) {
+                    self.output_tokens = u as i32;
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Builds the final response; `body` is the raw response text used for the
+    /// non-streaming JSON fallback.
+    fn finish(self, body: &str) -> anyhow::Result<LLMResponse> {
+        let Self {
+            mut content,
+            mut input_tokens,
+            mut output_tokens,
+            pending_tools,
+        } = self;
+        let mut tool_calls: Vec<LLMToolCall> =
+            pending_tools.into_values().map(PendingToolUse::finish).collect();
+
+        // Fallback: if SSE parsing yielded nothing, the provider may have
+        // returned a standard (non-streaming) JSON response despite stream=true.
+        // Try to extract content from the raw body as a regular Anthropic response.
+        if content.is_empty() && tool_calls.is_empty() {
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(body) {
+                // Standard Anthropic response: { "content": [{ "type": "text", "text": "..." }] }
+                if let Some(blocks) = json.get("content").and_then(|c| c.as_array()) {
+                    for block in blocks {
+                        match block.get("type").and_then(|t| t.as_str()) {
+                            Some("text") => {
+                                if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                                    content.push_str(text);
+                                }
+                            }
+                            Some("tool_use") => tool_calls.push(LLMToolCall {
+                                id: block
+                                    .get("id")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                name: block
+                                    .get("name")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                arguments: block
+                                    .get("input")
+                                    .cloned()
+                                    .unwrap_or(serde_json::Value::Null),
+                            }),
+                            _ => {}
+                        }
+                    }
+                }
+                // Also try extracting usage from the non-streaming response
+                if let Some(u) = json.pointer("/usage/input_tokens").and_then(serde_json::Value::as_i64) {
+                    input_tokens = u as i32;
+                }
+                if let Some(u) = json.pointer("/usage/output_tokens").and_then(serde_json::Value::as_i64) {
+                    output_tokens = u as i32;
+                }
+            }
+        }
+
+        if content.is_empty() && tool_calls.is_empty() {
+            tracing::warn!(
+                body_len = body.len(),
+                body_preview = %body.chars().take(500).collect::<String>(),
+                "Anthropic API returned empty content after SSE + JSON fallback parsing"
+            );
+            return Err(anyhow::anyhow!("Anthropic API returned empty content"));
+        }
+
+        let usage = if input_tokens > 0 || output_tokens > 0 {
+            Some(LLMUsage {
+                prompt_tokens: input_tokens,
+                completion_tokens: output_tokens,
+                total_tokens: input_tokens + output_tokens,
+            })
+        } else {
+            None
+        };
+
+        Ok(LLMResponse {
+            content,
+            usage,
+            tool_calls,
+            provider: None,
+            model: None,
+        })
+    }
+}
+
+/// Parse an Anthropic `/messages` response body into text, tool calls and usage.
+///
+/// Accepts both the SSE event stream and (as a fallback for proxies that ignore
+/// `stream=true`) the standard non-streaming JSON body.
+fn parse_anthropic_body(body: &str) -> anyhow::Result<LLMResponse> {
+    let mut state = AnthropicStreamState::default();
+    for line in body.lines() {
+        match parse_sse_line(line) {
+            Some(SseData::Done) => break,
+            Some(SseData::Event(event)) => {
+                state.apply(&event);
+            }
+            None => {}
+        }
+    }
+    state.finish(body)
+}
+
 impl AnthropicCompatibleClient {
     pub fn new(config: &OrchestratorConfig) -> Self {
         let client = Client::builder()
@@ -351,7 +945,12 @@ impl AnthropicCompatibleClient {
         }
     }
 
-    async fn chat_once(&self, messages: Vec<LLMMessage>) -> anyhow::Result<LLMResponse> {
+    /// Sends a streaming `/messages` request and returns the successful response.
+    async fn send(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<reqwest::Response> {
         let url = format!("{}/messages", self.base_url);
 
         // Extract system message and convert the rest
@@ -375,12 +974,21 @@ impl AnthropicCompatibleClient {
             max_tokens: 2048,
             system: system_prompt,
             stream: true,
+            tools: tools
+                .into_iter()
+                .map(|t| AnthropicTool {
+                    name: t.name,
+                    description: t.description,
+                    input_schema: t.parameters,
+                })
+                .collect(),
         };
 
         tracing::debug!(
             url = %url,
             model = %self.model,
             msg_count = msg_count,
+            tool_count = request.tools.len(),
             "Anthropic-compatible LLM request starting (streaming)"
         );
 
@@ -409,96 +1017,72 @@ impl AnthropicCompatibleClient {
             return Err(anyhow::anyhow!("LLM API error: {status} - {body}"));
         }
 
-        // Parse SSE stream and accumulate text content + usage
-        let body = response.text().await?;
-        let mut content = String::new();
-        let mut input_tokens: i32 = 0;
-        let mut output_tokens: i32 = 0;
-
-        for line in body.lines() {
-            let line = line.trim();
-            if let Some(data) = line.strip_prefix("data: ") {
-                if data == "[DONE]" {
-                    break;
-                }
-                if let Ok(event) = serde_json::from_str::<serde_json::Value>(data) {
-                    match event.get("type").and_then(|t| t.as_str()) {
-                        Some("content_block_delta") => {
-                            if let Some(text) = event
-                                .pointer("/delta/text")
-                                .and_then(|t| t.as_str())
-                            {
-                                content.push_str(text);
-                            }
-                        }
-                        Some("message_start") => {
-                            if let Some(u) = event.pointer("/message/usage/input_tokens").and_then(serde_json::Value::as_i64) {
-                                input_tokens = u as i32;
-                            }
-                        }
-                        Some("message_delta") => {
-                            if let Some(u) = event.pointer("/usage/output_tokens").and_then(serde_json::Value::as_i64) {
-                                output_tokens = u as i32;
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
+        Ok(response)
+    }
 
-        // Fallback: if SSE parsing yielded nothing, the provider may have
-        // returned a standard (non-streaming) JSON response despite stream=true.
-        // Try to extract content from the raw body as a regular Anthropic response.
-        if content.is_empty() {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
-                // Standard Anthropic response: { "content": [{ "type": "text", "text": "..." }] }
-                if let Some(blocks) = json.get("content").and_then(|c| c.as_array()) {
-                    for block in blocks {
-                        if block.get("type").and_then(|t| t.as_str()) == Some("text") {
-                            if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
-                                content.push_str(text);
-                            }
-                        }
-                    }
-                }
-                // Also try extracting usage from the non-streaming response
-                if let Some(u) = json.pointer("/usage/input_tokens").and_then(serde_json::Value::as_i64) {
-                    input_tokens = u as i32;
-                }
-                if let Some(u) = json.pointer("/usage/output_tokens").and_then(serde_json::Value::as_i64) {
-                    output_tokens = u as i32;
-                }
-            }
-        }
+    async fn chat_once(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        let response = self.send(messages, tools).await?;
+        let body = response.text().await?;
+        parse_anthropic_body(&body)
+    }
 
-        if content.is_empty() {
-            tracing::warn!(
-                body_len = body.len(),
-                body_preview = %body.chars().take(500).collect::<String>(),
-                "Anthropic API returned empty content after SSE + JSON fallback parsing"
-            );
-            return Err(anyhow::anyhow!("Anthropic API returned empty content"));
+    async fn chat_stream_once(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: &LLMStreamSender,
+    ) -> anyhow::Result<LLMResponse> {
+        let response = self.send(messages, tools).await?;
+
+        if !is_event_stream(&response) {
+            let body = response.text().await?;
+            let result = parse_anthropic_body(&body)?;
+            send_delta(events, result.content.clone());
+            return Ok(result);
         }
 
-        let usage = if input_tokens > 0 || output_tokens > 0 {
-            Some(LLMUsage {
-                prompt_tokens: input_tokens,
-                completion_tokens: output_tokens,
-                total_tokens: input_tokens + output_tokens,
-            })
-        } else {
-            None
-        };
-
-        Ok(LLMResponse { content, usage })
+        let mut state = AnthropicStreamState::default();
+        let body = read_sse_events(response, |event| {
+            if let Some(text) = state.apply(&event) {
+                send_delta(events, text);
+            }
+        })
+        .await?;
+        state.finish(&body)
     }
 }
 
 #[async_trait]
 impl LLMClient for AnthropicCompatibleClient {
     async fn chat(&self, messages: Vec<LLMMessage>) -> anyhow::Result<LLMResponse> {
-        self.chat_once(messages).await
+        self.chat_once(messages, Vec::new())
+            .await
+            .map(|r| r.served_by(&self.model))
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat_once(messages, tools)
+            .await
+            .map(|r| r.served_by(&self.model))
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: LLMStreamSender,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat_stream_once(messages, tools, &events)
+            .await
+            .map(|r| r.served_by(&self.model))
     }
 }
 
@@ -531,7 +1115,7 @@ fn should_use_anthropic_protocol(config: &OrchestratorConfig) -> bool {
     // Explicit api_type takes priority — user knows their endpoint best
     match config.api_type.as_str() {
         "anthropic" | "anthropic-compatible" => return true,
-        "openai" | "openai-compatible" | "google" => return false,
+        "openai" | "openai-compatible" | "google" | "local" => return false,
         _ => {}
     }
     // Auto-detect only when api_type is not explicitly set
@@ -564,8 +1148,8 @@ pub fn create_llm_client(config: &OrchestratorConfig) -> anyhow::Result<Box<dyn
         let rps = config.rate_limit_requests_per_second;
         let primary_client: Box<dyn LLMClient> = build_single_client(config)?;
 
-        let mut providers: Vec<(String, Box<dyn LLMClient>)> =
-            vec![(primary_name, primary_client)];
+        let mut providers: Vec<(String, Vec<ModelTier>, Box<dyn LLMClient>)> =
+            vec![(primary_name, config.tiers.clone(), primary_client)];
 
         let mut fallbacks: Vec<_> = config.fallback_providers.clone();
         fallbacks.sort_by_key(|p| p.priority);
@@ -576,22 +1160,26 @@ pub fn create_llm_client(config: &OrchestratorConfig) -> anyhow::Result<Box<dyn
                 base_url: fb.base_url.clone(),
                 api_key: fb.api_key.clone(),
                 model: fb.model.clone(),
-                timeout_secs: config.timeout_secs,
+                timeout_secs: if fb.api_type == LOCAL_API_TYPE {
+                    config.timeout_secs.max(DEFAULT_LOCAL_LLM_TIMEOUT_SECS)
+                } else {
+                    config.timeout_secs
+                },
                 rate_limit_requests_per_second: rps,
                 ..Default::default()
             };
             let fb_client = build_single_client(&fb_config)?;
-            providers.push((fb.name.clone(), fb_client));
+            providers.push((fb.name.clone(), fb.tiers.clone(), fb_client));
         }
 
         tracing::info!(
             "ResilientLLMClient created with {} providers: {:?}",
             providers.len(),
-            providers.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(),
+            providers.iter().map(|(n, _, _)| n.as_str()).collect::<Vec<_>>(),
         );
 
         Ok(Box::new(
-            super::resilient_llm::ResilientLLMClient::new(providers),
+            super::resilient_llm::ResilientLLMClient::with_tiers(providers),
         ))
     }
 }
@@ -645,6 +1233,112 @@ mod rate_limit_tests {
     }
 }
 
+#[cfg(test)]
+mod tool_call_parsing_tests {
+    use super::*;
+
+    #[test]
+    fn anthropic_stream_assembles_tool_use_blocks() {
+        let body = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":12}}}\n\n",
+            "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Planning.\"}}\n",
+            "data: {\"type\":\"content_block_start\",\"index\":1,\"content_block\":{\"type\":\"tool_use\",\"id\":\"toolu_1\",\"name\":\"complete_workflow\",\"input\":{}}}\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"{\\\"summary\\\":\"}}\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"\\\"done\\\"}\"}}\n",
+            "data: {\"type\":\"message_delta\",\"usage\":{\"output_tokens\":7}}\n",
+        );
+
+        let response = parse_anthropic_body(body).expect("stream should parse");
+        assert_eq!(response.content, "Planning.");
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].id, "toolu_1");
+        assert_eq!(response.tool_calls[0].name, "complete_workflow");
+        assert_eq!(response.tool_calls[0].arguments["summary"], "done");
+        assert_eq!(response.usage.map(|u| u.total_tokens), Some(19));
+    }
+
+    #[test]
+    fn anthropic_non_streaming_tool_use_is_accepted() {
+        let body = r#"{"content":[{"type":"tool_use","id":"toolu_2","name":"fail_workflow","input":{"reason":"x"}}],"usage":{"input_tokens":1,"output_tokens":2}}"#;
+        let response = parse_anthropic_body(body).expect("json body should parse");
+        assert!(response.content.is_empty());
+        assert_eq!(response.tool_calls[0].arguments["reason"], "x");
+    }
+
+    #[test]
+    fn anthropic_empty_body_is_an_error() {
+        assert!(parse_anthropic_body("data: [DONE]\n").is_err());
+    }
+
+    #[test]
+    fn openai_tool_call_arguments_are_decoded() {
+        let response: ChatResponse = serde_json::from_str(
+            r#"{"choices":[{"message":{"role":"assistant","content":null,"tool_calls":[{"id":"call_1","type":"function","function":{"name":"start_terminal","arguments":"{\"terminal_id\":\"tm1\",\"instruction\":\"go\"}"}}]}}]}"#,
+        )
+        .expect("openai tool-call response should deserialize");
+        let message = response.choices.into_iter().next().expect("one choice").message;
+        assert!(message.content.is_none());
+
+        let calls: Vec<LLMToolCall> = message.tool_calls.into_iter().map(LLMToolCall::from).collect();
+        assert_eq!(calls[0].name, "start_terminal");
+        assert_eq!(calls[0].arguments["terminal_id"], "tm1");
+    }
+
+    #[test]
+    fn openai_stream_chunks_assemble_text_and_tool_calls() {
+        let lines = [
+            r#"data: {"choices":[{"index":0,"delta":{"role":"assistant","content":"Hel"}}]}"#,
+            r#"data:{"choices":[{"index":0,"delta":{"content":"lo"}}]}"#,
+            r#"data: {"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"fail_workflow","arguments":"{\"rea"}}]}}]}"#,
+            r#"data: {"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"son\":\"x\"}"}}]}}]}"#,
+            r#"data: {"choices":[],"usage":{"prompt_tokens":3,"completion_tokens":4,"total_tokens":7}}"#,
+            "data: [DONE]",
+        ];
+
+        let mut state = OpenAIStreamState::default();
+        let mut deltas = Vec::new();
+        for line in lines {
+            match parse_sse_line(line) {
+                Some(SseData::Event(chunk)) => deltas.extend(state.apply(&chunk)),
+                Some(SseData::Done) => break,
+                None => {}
+            }
+        }
+
+        assert_eq!(deltas, vec!["Hel".to_string(), "lo".to_string()]);
+        let response = state.finish().expect("stream should assemble");
+        assert_eq!(response.content, "Hello");
+        assert_eq!(response.tool_calls[0].id, "call_1");
+        assert_eq!(response.tool_calls[0].arguments["reason"], "x");
+        assert_eq!(response.usage.map(|u| u.total_tokens), Some(7));
+    }
+
+    #[test]
+    fn openai_empty_stream_is_an_error() {
+        assert!(OpenAIStreamState::default().finish().is_err());
+    }
+
+    #[tokio::test]
+    async fn default_chat_stream_emits_single_delta() {
+        let client = MockLLMClient::with_response("whole reply");
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = client
+            .chat_stream(Vec::new(), Vec::new(), tx)
+            .await
+            .expect("mock stream");
+        assert_eq!(response.content, "whole reply");
+        assert_eq!(
+            rx.recv().await,
+            Some(LLMStreamEvent::Delta {
+                text: "whole reply".to_string()
+            })
+        );
+        assert_eq!(rx.recv().await, None);
+    }
+}
+
 #[cfg(test)]
 mod anthropic_protocol_tests {
     use super::*;
@@ -0,0 +1,422 @@
+//! Record/replay LLM clients for deterministic orchestrator regression tests.
+//!
+//! [`RecordingLLMClient`] wraps a real client and writes every request/response
+//! pair of a workflow run to a fixture file. [`ReplayLLMClient`] loads that
+//! fixture and answers requests by their normalized message hash, so a recorded
+//! production transcript can drive `OrchestratorAgent` without network access.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{
+    llm::{LLMClient, LLMStreamSender},
+    resilient_llm::{ProviderEvent, ProviderStatusReport},
+    routing::ModelTier,
+    types::{LLMMessage, LLMResponse, LLMToolDefinition},
+};
+
+/// Current fixture file format version.
+pub const LLM_FIXTURE_VERSION: u32 = 1;
+
+/// When set, `OrchestratorAgent::new` records each workflow's LLM traffic to
+/// `<dir>/<workflow_id>.json`; a resumed workflow appends to its existing fixture.
+pub const LLM_RECORD_DIR_ENV: &str = "SOLODAWN_LLM_RECORD_DIR";
+
+static UUID_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b")
+        .expect("uuid regex must be valid")
+});
+
+static TIMESTAMP_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:?\d{2})?")
+        .expect("timestamp regex must be valid")
+});
+
+/// One recorded request/response pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMExchange {
+    /// Hash of the normalized request, see [`request_hash`]
+    pub request_hash: String,
+    /// Original (un-normalized) messages, kept for debugging fixtures
+    pub messages: Vec<LLMMessage>,
+    /// Names of the tools offered with the request
+    #[serde(default)]
+    pub tools: Vec<String>,
+    pub response: LLMResponse,
+}
+
+/// Fixture file contents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMFixture {
+    pub version: u32,
+    #[serde(default)]
+    pub exchanges: Vec<LLMExchange>,
+}
+
+impl Default for LLMFixture {
+    fn default() -> Self {
+        Self {
+            version: LLM_FIXTURE_VERSION,
+            exchanges: Vec::new(),
+        }
+    }
+}
+
+impl LLMFixture {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read LLM fixture {}: {e}", path.display()))?;
+        let fixture: Self = serde_json::from_str(&raw)
+            .map_err(|e| anyhow::anyhow!("Invalid LLM fixture {}: {e}", path.display()))?;
+        if fixture.version != LLM_FIXTURE_VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported LLM fixture version {} (expected {LLM_FIXTURE_VERSION})",
+                fixture.version
+            ));
+        }
+        Ok(fixture)
+    }
+
+    pub async fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let data = serde_json::to_vec_pretty(self)?;
+        // Write then rename so an interrupted run never leaves a truncated fixture.
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, data).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+        Ok(())
+    }
+}
+
+/// Normalizes message content so that run-specific values do not change the hash:
+/// UUIDs and timestamps are replaced with placeholders and whitespace is collapsed.
+pub fn normalize_content(content: &str) -> String {
+    let content = UUID_RE.replace_all(content, "<uuid>");
+    let content = TIMESTAMP_RE.replace_all(&content, "<timestamp>");
+    content.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Stable hash of a request: normalized messages plus the sorted tool names.
+pub fn request_hash(messages: &[LLMMessage], tools: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for message in messages {
+        hasher.update(message.role.trim().as_bytes());
+        hasher.update([0u8]);
+        hasher.update(normalize_content(&message.content).as_bytes());
+        hasher.update([0u8]);
+    }
+    let mut tools = tools.to_vec();
+    tools.sort();
+    for tool in &tools {
+        hasher.update([1u8]);
+        hasher.update(tool.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn tool_names(tools: &[LLMToolDefinition]) -> Vec<String> {
+    tools.iter().map(|tool| tool.name.clone()).collect()
+}
+
+/// Wraps a client and appends every successful exchange to a fixture file.
+///
+/// The fixture is rewritten after each exchange so a crashed run still keeps
+/// everything captured up to that point.
+pub struct RecordingLLMClient {
+    inner: Box<dyn LLMClient>,
+    path: PathBuf,
+    fixture: tokio::sync::Mutex<LLMFixture>,
+}
+
+impl RecordingLLMClient {
+    pub fn new(inner: Box<dyn LLMClient>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            fixture: tokio::sync::Mutex::new(LLMFixture::default()),
+        }
+    }
+
+    /// Like [`Self::new`], but continues an existing fixture at `path` so that a
+    /// resumed run appends to the recording instead of replacing it.
+    pub fn open(inner: Box<dyn LLMClient>, path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let fixture = if path.exists() {
+            LLMFixture::load(&path)?
+        } else {
+            LLMFixture::default()
+        };
+        Ok(Self {
+            inner,
+            path,
+            fixture: tokio::sync::Mutex::new(fixture),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Snapshot of the exchanges recorded so far.
+    pub async fn fixture(&self) -> LLMFixture {
+        self.fixture.lock().await.clone()
+    }
+
+    async fn record(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<String>,
+        response: &anyhow::Result<LLMResponse>,
+    ) {
+        let Ok(response) = response else {
+            return;
+        };
+        let mut fixture = self.fixture.lock().await;
+        fixture.exchanges.push(LLMExchange {
+            request_hash: request_hash(&messages, &tools),
+            messages,
+            tools,
+            response: response.clone(),
+        });
+        // Recording must never break the workflow itself.
+        if let Err(e) = fixture.save(&self.path).await {
+            tracing::warn!(
+                path = %self.path.display(),
+                "Failed to write LLM fixture: {e}"
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for RecordingLLMClient {
+    async fn chat(&self, messages: Vec<LLMMessage>) -> anyhow::Result<LLMResponse> {
+        let result = self.inner.chat(messages.clone()).await;
+        self.record(messages, Vec::new(), &result).await;
+        result
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        let names = tool_names(&tools);
+        let result = self.inner.chat_with_tools(messages.clone(), tools).await;
+        self.record(messages, names, &result).await;
+        result
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: LLMStreamSender,
+    ) -> anyhow::Result<LLMResponse> {
+        let names = tool_names(&tools);
+        let result = self
+            .inner
+            .chat_stream(messages.clone(), tools, events)
+            .await;
+        self.record(messages, names, &result).await;
+        result
+    }
+
+    async fn chat_routed(
+        &self,
+        tier: ModelTier,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: Option<LLMStreamSender>,
+    ) -> anyhow::Result<LLMResponse> {
+        let names = tool_names(&tools);
+        let result = self
+            .inner
+            .chat_routed(tier, messages.clone(), tools, events)
+            .await;
+        self.record(messages, names, &result).await;
+        result
+    }
+
+    async fn provider_status(&self) -> Vec<ProviderStatusReport> {
+        self.inner.provider_status().await
+    }
+
+    async fn reset_provider(&self, provider_name: &str) -> bool {
+        self.inner.reset_provider(provider_name).await
+    }
+
+    async fn take_provider_events(&self) -> Vec<ProviderEvent> {
+        self.inner.take_provider_events().await
+    }
+}
+
+/// Answers requests from a recorded fixture.
+///
+/// Responses are matched by [`request_hash`]; identical requests recorded
+/// several times are replayed in their recorded order. A request with no
+/// remaining match fails instead of falling back to the network.
+pub struct ReplayLLMClient {
+    responses: Mutex<HashMap<String, VecDeque<LLMResponse>>>,
+}
+
+impl ReplayLLMClient {
+    pub fn new(fixture: LLMFixture) -> Self {
+        let mut responses: HashMap<String, VecDeque<LLMResponse>> = HashMap::new();
+        for exchange in fixture.exchanges {
+            responses
+                .entry(exchange.request_hash)
+                .or_default()
+                .push_back(exchange.response);
+        }
+        Self {
+            responses: Mutex::new(responses),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        Ok(Self::new(LLMFixture::load(path)?))
+    }
+
+    /// Number of recorded responses not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.responses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .values()
+            .map(VecDeque::len)
+            .sum()
+    }
+
+    fn replay(&self, messages: &[LLMMessage], tools: &[String]) -> anyhow::Result<LLMResponse> {
+        let hash = request_hash(messages, tools);
+        let mut responses = self
+            .responses
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        responses
+            .get_mut(&hash)
+            .and_then(VecDeque::pop_front)
+            .ok_or_else(|| {
+                let last: String = messages
+                    .last()
+                    .map(|m| normalize_content(&m.content).chars().take(120).collect())
+                    .unwrap_or_default();
+                anyhow::anyhow!(
+                    "No recorded LLM response for request {hash} ({} messages, last: {last:?})",
+                    messages.len()
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl LLMClient for ReplayLLMClient {
+    async fn chat(&self, messages: Vec<LLMMessage>) -> anyhow::Result<LLMResponse> {
+        self.replay(&messages, &[])
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        self.replay(&messages, &tool_names(&tools))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::orchestrator::llm::MockLLMClient;
+
+    fn message(role: &str, content: &str) -> LLMMessage {
+        LLMMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn request_hash_ignores_run_specific_values() {
+        let first = vec![message(
+            "user",
+            "Task 3f2b8c1e-0d4a-4a8e-9b55-6f1e2d3c4b5a finished at 2026-03-01T10:00:00Z",
+        )];
+        let second = vec![message(
+            "user",
+            "Task  a1b2c3d4-e5f6-4789-8abc-def012345678 finished at\n2026-04-02T11:30:15.123+08:00",
+        )];
+        let other = vec![message("user", "Task a1b2c3d4-e5f6-4789-8abc-def012345678 failed")];
+
+        assert_eq!(request_hash(&first, &[]), request_hash(&second, &[]));
+        assert_ne!(request_hash(&first, &[]), request_hash(&other, &[]));
+        assert_ne!(
+            request_hash(&first, &[]),
+            request_hash(&first, &["create_task".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn recorded_fixture_replays_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixtures").join("run.json");
+
+        let recorder = RecordingLLMClient::new(
+            Box::new(MockLLMClient::with_response("[]")),
+            &path,
+        );
+        let messages = vec![message("system", "plan"), message("user", "go")];
+        recorder.chat(messages.clone()).await.unwrap();
+        recorder.chat(messages.clone()).await.unwrap();
+        assert_eq!(recorder.fixture().await.exchanges.len(), 2);
+
+        let replay = ReplayLLMClient::from_file(&path).unwrap();
+        assert_eq!(replay.remaining(), 2);
+        assert_eq!(replay.chat(messages.clone()).await.unwrap().content, "[]");
+        assert_eq!(replay.chat(messages.clone()).await.unwrap().content, "[]");
+        assert_eq!(replay.remaining(), 0);
+
+        let err = replay.chat(messages).await.unwrap_err();
+        assert!(err.to_string().contains("No recorded LLM response"));
+    }
+
+    #[tokio::test]
+    async fn reopened_recording_appends_to_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.json");
+        let messages = vec![message("user", "go")];
+
+        let first =
+            RecordingLLMClient::open(Box::new(MockLLMClient::with_response("a")), &path).unwrap();
+        first.chat(messages.clone()).await.unwrap();
+
+        // A resumed workflow opens the same fixture again.
+        let resumed =
+            RecordingLLMClient::open(Box::new(MockLLMClient::with_response("b")), &path).unwrap();
+        resumed.chat(messages.clone()).await.unwrap();
+
+        let replay = ReplayLLMClient::from_file(&path).unwrap();
+        assert_eq!(replay.chat(messages.clone()).await.unwrap().content, "a");
+        assert_eq!(replay.chat(messages).await.unwrap().content, "b");
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(
+            RecordingLLMClient::open(Box::new(MockLLMClient::new()), &path).is_err(),
+            "an unreadable fixture must not be overwritten"
+        );
+    }
+}
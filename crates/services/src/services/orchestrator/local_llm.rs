@@ -0,0 +1,287 @@
+//! Local OpenAI-compatible servers (Ollama, llama.cpp): model discovery and
+//! context-window checks for the `local` API type.
+
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{config::OrchestratorConfig, constants::LOCAL_MIN_CONTEXT_TOKENS};
+
+/// Discovery requests hit localhost; anything slower means the server is down.
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Which endpoint reported a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LocalModelSource {
+    /// `GET /v1/models` (llama.cpp, Ollama, LM Studio, vLLM)
+    OpenAi,
+    /// `GET /api/tags` (Ollama)
+    Ollama,
+}
+
+/// A model served by a local server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalModelInfo {
+    pub id: String,
+    /// Context window in tokens, when the server reports it
+    pub context_length: Option<u32>,
+    /// Model file size in bytes (Ollama only)
+    pub size_bytes: Option<u64>,
+    pub source: LocalModelSource,
+}
+
+impl LocalModelInfo {
+    /// Whether the orchestrator prompt fits; unknown context windows are accepted.
+    pub fn fits_orchestrator(&self) -> bool {
+        !matches!(self.context_length, Some(tokens) if tokens < LOCAL_MIN_CONTEXT_TOKENS)
+    }
+}
+
+/// Server root without the OpenAI `/v1` suffix.
+pub fn server_root(base_url: &str) -> String {
+    let trimmed = base_url.trim().trim_end_matches('/');
+    trimmed.strip_suffix("/v1").unwrap_or(trimmed).to_string()
+}
+
+fn discovery_client() -> anyhow::Result<Client> {
+    Ok(Client::builder().timeout(DISCOVERY_TIMEOUT).build()?)
+}
+
+async fn get_json(client: &Client, url: &str) -> anyhow::Result<Value> {
+    let response = client.get(url).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("GET {url} returned {status}"));
+    }
+    Ok(response.json().await?)
+}
+
+fn as_u32(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|v| u32::try_from(v).ok())
+}
+
+/// Parses an OpenAI-style `/v1/models` response.
+///
+/// Context windows are read from the fields used by common servers:
+/// `max_model_len` (vLLM), `context_length` (LM Studio and others) and
+/// `meta.n_ctx_train` (llama.cpp).
+pub fn parse_openai_models(json: &Value) -> Vec<LocalModelInfo> {
+    json.get("data")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let id = item.get("id").and_then(Value::as_str)?;
+                    let context_length = item
+                        .get("max_model_len")
+                        .or_else(|| item.get("context_length"))
+                        .or_else(|| item.pointer("/meta/n_ctx_train"))
+                        .and_then(as_u32);
+                    Some(LocalModelInfo {
+                        id: id.to_string(),
+                        context_length,
+                        size_bytes: None,
+                        source: LocalModelSource::OpenAi,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parses an Ollama `/api/tags` response.
+pub fn parse_ollama_tags(json: &Value) -> Vec<LocalModelInfo> {
+    json.get("models")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let id = item
+                        .get("name")
+                        .or_else(|| item.get("model"))
+                        .and_then(Value::as_str)?;
+                    Some(LocalModelInfo {
+                        id: id.to_string(),
+                        context_length: None,
+                        size_bytes: item.get("size").and_then(Value::as_u64),
+                        source: LocalModelSource::Ollama,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Effective context window from an Ollama `/api/show` response.
+///
+/// An explicit `num_ctx` parameter wins over the architecture maximum
+/// (`model_info["<arch>.context_length"]`), since Ollama serves with `num_ctx`.
+pub fn parse_ollama_context(json: &Value) -> Option<u32> {
+    let configured = json
+        .get("parameters")
+        .and_then(Value::as_str)
+        .and_then(|params| {
+            params.lines().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                (parts.next() == Some("num_ctx"))
+                    .then(|| parts.next()?.parse::<u32>().ok())
+                    .flatten()
+            })
+        });
+    configured.or_else(|| {
+        json.get("model_info")
+            .and_then(Value::as_object)?
+            .iter()
+            .find(|(key, _)| key.ends_with(".context_length"))
+            .and_then(|(_, value)| as_u32(value))
+    })
+}
+
+async fn ollama_context_length(client: &Client, root: &str, model: &str) -> Option<u32> {
+    let response = client
+        .post(format!("{root}/api/show"))
+        .json(&serde_json::json!({ "model": model }))
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    parse_ollama_context(&response.json().await.ok()?)
+}
+
+/// Lists the models of a local server via `/v1/models` and, for Ollama,
+/// `/api/tags` (+ `/api/show` for context windows).
+///
+/// Fails only when neither endpoint answers.
+pub async fn discover_local_models(base_url: &str) -> anyhow::Result<Vec<LocalModelInfo>> {
+    let client = discovery_client()?;
+    let root = server_root(base_url);
+
+    let openai = get_json(&client, &format!("{root}/v1/models")).await;
+    let ollama = get_json(&client, &format!("{root}/api/tags")).await;
+    if let (Err(openai_err), Err(ollama_err)) = (&openai, &ollama) {
+        return Err(anyhow::anyhow!(
+            "No local model server at {root}: /v1/models: {openai_err}; /api/tags: {ollama_err}"
+        ));
+    }
+
+    let mut models = openai
+        .as_ref()
+        .map(parse_openai_models)
+        .unwrap_or_default();
+    if let Ok(tags) = &ollama {
+        for mut tagged in parse_ollama_tags(tags) {
+            tagged.context_length = ollama_context_length(&client, &root, &tagged.id).await;
+            match models.iter_mut().find(|m| m.id == tagged.id) {
+                Some(existing) => {
+                    existing.size_bytes = tagged.size_bytes;
+                    existing.context_length = existing.context_length.or(tagged.context_length);
+                }
+                None => models.push(tagged),
+            }
+        }
+    }
+    Ok(models)
+}
+
+/// Fits the orchestrator to the context window of a local model.
+///
+/// Rejects models below [`LOCAL_MIN_CONTEXT_TOKENS`] and lowers the history
+/// compaction trigger so the history stays well inside the window. Unknown
+/// context windows (or an unreachable server) leave the config unchanged;
+/// the first LLM call reports connection problems.
+pub async fn fit_to_context_window(config: &mut OrchestratorConfig) -> anyhow::Result<()> {
+    if !config.is_local() {
+        return Ok(());
+    }
+    let models = match discover_local_models(&config.base_url).await {
+        Ok(models) => models,
+        Err(e) => {
+            tracing::warn!(base_url = %config.base_url, "Local model discovery failed: {e}");
+            return Ok(());
+        }
+    };
+    let Some(model) = models.iter().find(|m| m.id == config.model) else {
+        tracing::warn!(
+            model = %config.model,
+            available = ?models.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            "Local model not listed by the server"
+        );
+        return Ok(());
+    };
+    let Some(context_length) = model.context_length else {
+        return Ok(());
+    };
+    if !model.fits_orchestrator() {
+        return Err(anyhow::anyhow!(
+            "Local model {} has a {context_length}-token context window; the orchestrator needs at least {LOCAL_MIN_CONTEXT_TOKENS}",
+            model.id
+        ));
+    }
+
+    // Leave ~40% of the window for the system prompt, tools and the reply.
+    let history_budget = context_length as usize * 3 / 5;
+    let compaction = &mut config.history_compaction;
+    if history_budget < compaction.trigger_tokens {
+        tracing::info!(
+            model = %model.id,
+            context_length,
+            trigger_tokens = history_budget,
+            "Lowering history compaction trigger for local model"
+        );
+        compaction.trigger_tokens = history_budget;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn server_root_strips_v1() {
+        assert_eq!(server_root("http://localhost:11434/v1/"), "http://localhost:11434");
+        assert_eq!(server_root("http://localhost:8080"), "http://localhost:8080");
+    }
+
+    #[test]
+    fn parses_openai_and_ollama_listings() {
+        let openai = parse_openai_models(&json!({
+            "data": [
+                {"id": "qwen2.5-coder-14b", "meta": {"n_ctx_train": 32768}},
+                {"id": "mistral", "max_model_len": 4096}
+            ]
+        }));
+        assert_eq!(openai[0].context_length, Some(32768));
+        assert!(openai[0].fits_orchestrator());
+        assert!(!openai[1].fits_orchestrator());
+
+        let ollama = parse_ollama_tags(&json!({
+            "models": [{"name": "llama3.1:8b", "size": 4920753328u64}]
+        }));
+        assert_eq!(ollama[0].id, "llama3.1:8b");
+        assert_eq!(ollama[0].size_bytes, Some(4_920_753_328));
+        assert_eq!(ollama[0].source, LocalModelSource::Ollama);
+    }
+
+    #[test]
+    fn ollama_num_ctx_overrides_model_maximum() {
+        let show = json!({
+            "parameters": "stop \"<|eot_id|>\"\nnum_ctx 16384",
+            "model_info": {"llama.context_length": 131072}
+        });
+        assert_eq!(parse_ollama_context(&show), Some(16384));
+
+        let show = json!({"model_info": {"qwen2.context_length": 32768}});
+        assert_eq!(parse_ollama_context(&show), Some(32768));
+    }
+}
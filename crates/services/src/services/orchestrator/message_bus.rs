@@ -10,8 +10,8 @@ use super::{
     constants::WORKFLOW_TOPIC_PREFIX,
     resilient_llm::ProviderEvent,
     types::{
-        OrchestratorInstruction, PromptDecision, QualityGateResultEvent,
-        TerminalCompletionEvent, TerminalPromptEvent,
+        LLMStreamEvent, OrchestratorInstruction, PromptDecision, QualityGateResultEvent,
+        TerminalActivityEvent, TerminalCompletionEvent, TerminalPromptEvent,
     },
 };
 
@@ -75,6 +75,15 @@ pub enum BusMessage {
     },
     /// Quality gate result for a terminal checkpoint
     TerminalQualityGateResult(QualityGateResultEvent),
+    /// Activity monitor escalation for a stalled or looping terminal
+    TerminalActivityEscalated(TerminalActivityEvent),
+    /// Incremental orchestrator LLM output - broadcast only, for UI rendering
+    LLMStream {
+        workflow_id: String,
+        /// Identifies one LLM call; all events of a call share it
+        stream_id: String,
+        event: LLMStreamEvent,
+    },
     Shutdown,
 }
 
@@ -817,6 +826,21 @@ impl MessageBus {
             );
         }
     }
+
+    /// Publishes a terminal activity escalation event.
+    pub async fn publish_terminal_activity(&self, event: TerminalActivityEvent) {
+        let workflow_id = event.workflow_id.clone();
+        if let Err(e) = self
+            .publish_workflow_event(&workflow_id, BusMessage::TerminalActivityEscalated(event))
+            .await
+        {
+            tracing::warn!(
+                workflow_id = %workflow_id,
+                error = %e,
+                "Failed to publish terminal activity event (non-fatal)"
+            );
+        }
+    }
 }
 
 #[async_trait]
@@ -3,27 +3,39 @@
 //! 负责协调多个 AI 编码代理完成软件开发任务。
 
 pub mod agent;
+pub mod budget;
 pub mod config;
 pub mod constants;
+pub mod decision_log;
+pub mod history;
+pub mod instruction_tools;
 pub mod llm;
+pub mod llm_replay;
+pub mod local_llm;
 pub mod message_bus;
 pub mod persistence;
 pub mod prompt_handler;
 pub mod resilient_llm;
+pub mod routing;
 pub mod runtime;
 pub mod runtime_actions;
 pub mod state;
+pub mod task_graph;
 pub mod terminal_coordinator;
 pub mod types;
 
 pub use agent::OrchestratorAgent;
+pub use budget::{WorkflowBudget, WorkflowUsage};
 pub use config::OrchestratorConfig;
 #[cfg(test)]
 pub use llm::MockLLMClient;
 pub use llm::{
-    LLMClient, OpenAICompatibleClient, build_terminal_completion_prompt, create_llm_client,
+    LLMClient, LLMStreamSender, OpenAICompatibleClient, build_terminal_completion_prompt,
+    create_llm_client,
 };
+pub use llm_replay::{LLMFixture, RecordingLLMClient, ReplayLLMClient};
 pub use resilient_llm::{ProviderEvent, ProviderStatusReport, ResilientLLMClient};
+pub use routing::{LLMRoute, ModelTier};
 pub use message_bus::{BusMessage, MessageBus, SharedMessageBus};
 pub use prompt_handler::PromptHandler;
 pub use runtime::{OrchestratorRuntime, RuntimeConfig};
@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use super::{
+    budget::WorkflowUsage,
     constants::WORKFLOW_STATUS_RUNNING,
     state::{OrchestratorState, TaskExecutionState},
     types::LLMMessage,
@@ -40,6 +41,10 @@ pub struct PersistedState {
     /// Total error count for this workflow run
     pub error_count: u32,
 
+    /// Accumulated LLM usage and spend (budget accounting)
+    #[serde(default)]
+    pub usage: WorkflowUsage,
+
     /// Commit hashes already processed (idempotency on recovery).
     #[serde(default)]
     pub processed_commits: Vec<String>,
@@ -114,6 +119,7 @@ impl From<OrchestratorState> for PersistedState {
             conversation_history: state.conversation_history,
             total_tokens_used: state.total_tokens_used,
             error_count: state.error_count,
+            usage: state.usage,
             processed_commits: state.processed_commits.into_iter().collect(),
             processed_checkpoints: state.processed_checkpoints.into_iter().collect(),
             updated_at: Utc::now(),
@@ -134,6 +140,7 @@ impl From<&OrchestratorState> for PersistedState {
             conversation_history: state.conversation_history.clone(),
             total_tokens_used: state.total_tokens_used,
             error_count: state.error_count,
+            usage: state.usage.clone(),
             processed_commits: state.processed_commits.iter().cloned().collect(),
             processed_checkpoints: state.processed_checkpoints.iter().cloned().collect(),
             updated_at: Utc::now(),
@@ -220,6 +227,7 @@ impl StatePersistence {
             state.conversation_history = persisted.conversation_history;
             state.total_tokens_used = persisted.total_tokens_used;
             state.error_count = persisted.error_count;
+            state.usage = persisted.usage;
             state.restore_processed_commits(persisted.processed_commits);
             state.processed_checkpoints = persisted.processed_checkpoints.into_iter().collect();
 
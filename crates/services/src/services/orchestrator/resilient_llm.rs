@@ -8,8 +8,9 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
-use super::llm::LLMClient;
-use super::types::{LLMMessage, LLMResponse};
+use super::llm::{LLMClient, LLMStreamSender};
+use super::routing::{ModelTier, provider_order};
+use super::types::{LLMMessage, LLMResponse, LLMStreamEvent, LLMToolDefinition};
 
 /// Number of consecutive failures before a provider is marked dead.
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
@@ -33,6 +34,8 @@ struct ProviderState {
 struct ProviderEntry {
     name: String,
     client: Box<dyn LLMClient>,
+    /// Capability tiers served (empty = all)
+    tiers: Vec<ModelTier>,
     state: Arc<RwLock<ProviderState>>,
 }
 
@@ -75,6 +78,9 @@ pub enum ProviderEvent {
 ///
 /// Dead providers are periodically probed (every [`PROBE_INTERVAL_SECS`]
 /// seconds) and revived on success.
+///
+/// Routed calls ([`LLMClient::chat_routed`]) try the providers serving the
+/// requested tier first; the rest remain available as a last resort.
 pub struct ResilientLLMClient {
     providers: Vec<ProviderEntry>,
     active_index: AtomicUsize,
@@ -88,13 +94,24 @@ impl ResilientLLMClient {
     /// The first entry is treated as the primary provider; subsequent entries
     /// are fallbacks tried in order.  Panics if `providers` is empty.
     pub fn new(providers: Vec<(String, Box<dyn LLMClient>)>) -> Self {
+        Self::with_tiers(
+            providers
+                .into_iter()
+                .map(|(name, client)| (name, Vec::new(), client))
+                .collect(),
+        )
+    }
+
+    /// Like [`Self::new`], with the capability tiers each provider serves.
+    pub fn with_tiers(providers: Vec<(String, Vec<ModelTier>, Box<dyn LLMClient>)>) -> Self {
         assert!(!providers.is_empty(), "ResilientLLMClient requires at least one provider");
 
         let entries = providers
             .into_iter()
-            .map(|(name, client)| ProviderEntry {
+            .map(|(name, tiers, client)| ProviderEntry {
                 name,
                 client,
+                tiers,
                 state: Arc::new(RwLock::new(ProviderState::default())),
             })
             .collect();
@@ -243,9 +260,17 @@ impl ResilientLLMClient {
     }
 }
 
-#[async_trait]
-impl LLMClient for ResilientLLMClient {
-    async fn chat(&self, messages: Vec<LLMMessage>) -> anyhow::Result<LLMResponse> {
+impl ResilientLLMClient {
+    /// Tries each provider at most once. When `stream` is set, deltas are
+    /// forwarded and a `Reset` is sent before falling over to the next provider.
+    /// With a `tier`, providers serving it are tried first.
+    async fn chat_with_failover(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        stream: Option<&LLMStreamSender>,
+        tier: Option<ModelTier>,
+    ) -> anyhow::Result<LLMResponse> {
         // Clear events from previous call.
         {
             let mut events = self.last_events.write().await;
@@ -254,10 +279,22 @@ impl LLMClient for ResilientLLMClient {
 
         let provider_count = self.providers.len();
         let start_index = self.active_index.load(Ordering::Relaxed);
+        let order: Vec<usize> = match tier {
+            Some(tier) => {
+                let tiers: Vec<&[ModelTier]> =
+                    self.providers.iter().map(|p| p.tiers.as_slice()).collect();
+                provider_order(&tiers, start_index, tier)
+            }
+            None => (0..provider_count)
+                .map(|offset| (start_index + offset) % provider_count)
+                .collect(),
+        };
+        // A tier may route away from the active provider; only skipped or
+        // failed providers move the active index.
+        let mut drifted = false;
 
         // Try each provider at most once per call.
-        for offset in 0..provider_count {
-            let idx = (start_index + offset) % provider_count;
+        for (position, &idx) in order.iter().enumerate() {
             let entry = &self.providers[idx];
 
             // Check circuit breaker and mark probe timestamp atomically
@@ -271,6 +308,7 @@ impl LLMClient for ResilientLLMClient {
                             idx,
                             entry.name,
                         );
+                        drifted = true;
                         continue;
                     }
                     tracing::info!(
@@ -282,11 +320,27 @@ impl LLMClient for ResilientLLMClient {
                 }
             }
 
-            match entry.client.chat(messages.clone()).await {
-                Ok(response) => {
+            let result = match stream {
+                Some(events) => {
+                    entry
+                        .client
+                        .chat_stream(messages.clone(), tools.clone(), events.clone())
+                        .await
+                }
+                None => {
+                    entry
+                        .client
+                        .chat_with_tools(messages.clone(), tools.clone())
+                        .await
+                }
+            };
+
+            match result {
+                Ok(mut response) => {
                     self.record_success(idx).await;
+                    response.provider.get_or_insert_with(|| entry.name.clone());
                     // If we drifted away from the active index, update it.
-                    if idx != start_index {
+                    if drifted && idx != start_index {
                         let _ = self.active_index.compare_exchange(
                             start_index,
                             idx,
@@ -303,9 +357,13 @@ impl LLMClient for ResilientLLMClient {
                         entry.name,
                         e,
                     );
+                    if let Some(events) = stream {
+                        // Discard any partial output from the failed provider.
+                        let _ = events.send(LLMStreamEvent::Reset);
+                    }
+                    drifted = true;
                     let just_died = self.record_failure(idx).await;
-                    if offset + 1 < provider_count {
-                        let next_idx = (idx + 1) % provider_count;
+                    if let Some(&next_idx) = order.get(position + 1) {
                         if just_died {
                             self.switch_to_next(idx);
                         }
@@ -332,6 +390,41 @@ impl LLMClient for ResilientLLMClient {
             "All LLM providers exhausted ({provider_count} providers tried)",
         ))
     }
+}
+
+#[async_trait]
+impl LLMClient for ResilientLLMClient {
+    async fn chat(&self, messages: Vec<LLMMessage>) -> anyhow::Result<LLMResponse> {
+        self.chat_with_failover(messages, Vec::new(), None, None).await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat_with_failover(messages, tools, None, None).await
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: LLMStreamSender,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat_with_failover(messages, tools, Some(&events), None).await
+    }
+
+    async fn chat_routed(
+        &self,
+        tier: ModelTier,
+        messages: Vec<LLMMessage>,
+        tools: Vec<LLMToolDefinition>,
+        events: Option<LLMStreamSender>,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat_with_failover(messages, tools, events.as_ref(), Some(tier))
+            .await
+    }
 
     async fn provider_status(&self) -> Vec<ProviderStatusReport> {
         // Delegate to the inherent method.
@@ -383,6 +476,24 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn stream_failover_resets_partial_output() {
+        let client = ResilientLLMClient::new(vec![
+            ("primary".into(), Box::new(MockLLMClient::that_fails())),
+            ("fallback".into(), Box::new(MockLLMClient::with_response("ok"))),
+        ]);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let result = client.chat_stream(msg(), Vec::new(), tx).await;
+        assert_eq!(result.unwrap().content, "ok");
+        assert_eq!(rx.recv().await, Some(LLMStreamEvent::Reset));
+        assert_eq!(
+            rx.recv().await,
+            Some(LLMStreamEvent::Delta {
+                text: "ok".to_string()
+            })
+        );
+    }
+
     #[tokio::test]
     async fn all_providers_fail_returns_error() {
         let client = ResilientLLMClient::new(vec![
@@ -428,4 +539,57 @@ mod tests {
         assert_eq!(status[0].total_failures, 0);
         assert!(status[0].is_active);
     }
+
+    #[tokio::test]
+    async fn routed_call_prefers_provider_serving_tier() {
+        let client = ResilientLLMClient::with_tiers(vec![
+            (
+                "strong".into(),
+                vec![ModelTier::Strong],
+                Box::new(MockLLMClient::with_response("strong")),
+            ),
+            (
+                "cheap".into(),
+                vec![ModelTier::Cheap],
+                Box::new(MockLLMClient::with_response("cheap")),
+            ),
+        ]);
+
+        let cheap = client
+            .chat_routed(ModelTier::Cheap, msg(), Vec::new(), None)
+            .await
+            .unwrap();
+        assert_eq!(cheap.content, "cheap");
+        assert_eq!(cheap.provider.as_deref(), Some("cheap"));
+        // Routing alone does not move the active provider.
+        assert_eq!(client.active_provider_name(), "strong");
+
+        let strong = client
+            .chat_routed(ModelTier::Strong, msg(), Vec::new(), None)
+            .await
+            .unwrap();
+        assert_eq!(strong.content, "strong");
+    }
+
+    #[tokio::test]
+    async fn routed_call_falls_back_outside_tier() {
+        let client = ResilientLLMClient::with_tiers(vec![
+            (
+                "strong".into(),
+                vec![ModelTier::Strong],
+                Box::new(MockLLMClient::with_response("strong")),
+            ),
+            (
+                "cheap".into(),
+                vec![ModelTier::Cheap],
+                Box::new(MockLLMClient::that_fails()),
+            ),
+        ]);
+
+        let response = client
+            .chat_routed(ModelTier::Cheap, msg(), Vec::new(), None)
+            .await
+            .unwrap();
+        assert_eq!(response.content, "strong");
+    }
 }
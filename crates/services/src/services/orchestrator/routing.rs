@@ -0,0 +1,138 @@
+//! Cost-aware model routing: call sites tag each LLM request with a route, the
+//! route maps to a capability tier, and providers declare the tiers they serve.
+
+use serde::{Deserialize, Serialize};
+
+use super::types::TerminalCompletionStatus;
+
+/// Capability tier of a provider/model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelTier {
+    /// Cheap and fast: completion and history summaries
+    Cheap,
+    /// Most capable: planning, reviews, orchestration decisions
+    Strong,
+}
+
+/// Call site of an LLM request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LLMRoute {
+    /// Initial workflow planning and slash-command planning
+    Planning,
+    /// Evaluating the completion of a review terminal
+    Review,
+    /// Orchestration decisions on user chat and events
+    Decision,
+    /// Evaluating any other terminal completion
+    CompletionSummary,
+    /// Folding old history into the memory message
+    HistorySummary,
+}
+
+impl LLMRoute {
+    /// Tier used when `OrchestratorConfig::routing` has no override.
+    pub fn default_tier(self) -> ModelTier {
+        match self {
+            Self::Planning | Self::Review | Self::Decision => ModelTier::Strong,
+            Self::CompletionSummary | Self::HistorySummary => ModelTier::Cheap,
+        }
+    }
+
+    /// Route for evaluating a terminal completion. Review verdicts and the
+    /// completions of reviewer terminals decide merges and workflow completion,
+    /// so they go to the strong tier.
+    pub fn for_completion(status: TerminalCompletionStatus, terminal_role: Option<&str>) -> Self {
+        let is_review = matches!(
+            status,
+            TerminalCompletionStatus::ReviewPass | TerminalCompletionStatus::ReviewReject
+        ) || terminal_role
+            .is_some_and(|role| role.to_ascii_lowercase().contains("review"));
+        if is_review {
+            Self::Review
+        } else {
+            Self::CompletionSummary
+        }
+    }
+}
+
+/// Whether a provider declaring `tiers` serves `tier`; no declaration serves all.
+pub fn serves_tier(tiers: &[ModelTier], tier: ModelTier) -> bool {
+    tiers.is_empty() || tiers.contains(&tier)
+}
+
+/// Provider indices to try for `tier`: rotation from `start` (the active
+/// provider), with providers serving the tier first. The others stay at the end
+/// as a last-resort fallback, so routing never reduces availability.
+pub fn provider_order(
+    provider_tiers: &[&[ModelTier]],
+    start: usize,
+    tier: ModelTier,
+) -> Vec<usize> {
+    let count = provider_tiers.len();
+    let rotation: Vec<usize> = (0..count).map(|offset| (start + offset) % count).collect();
+    let (mut order, rest): (Vec<usize>, Vec<usize>) = rotation
+        .into_iter()
+        .partition(|&idx| serves_tier(provider_tiers[idx], tier));
+    order.extend(rest);
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_map_to_expected_tiers() {
+        assert_eq!(LLMRoute::Planning.default_tier(), ModelTier::Strong);
+        assert_eq!(LLMRoute::Review.default_tier(), ModelTier::Strong);
+        assert_eq!(LLMRoute::Decision.default_tier(), ModelTier::Strong);
+        assert_eq!(LLMRoute::CompletionSummary.default_tier(), ModelTier::Cheap);
+    }
+
+    #[test]
+    fn review_completions_use_the_review_route() {
+        use TerminalCompletionStatus::{Completed, Failed, ReviewPass, ReviewReject};
+
+        assert_eq!(LLMRoute::for_completion(ReviewPass, None), LLMRoute::Review);
+        assert_eq!(
+            LLMRoute::for_completion(ReviewReject, Some("coder")),
+            LLMRoute::Review
+        );
+        assert_eq!(
+            LLMRoute::for_completion(Completed, Some("Code Reviewer")),
+            LLMRoute::Review
+        );
+        assert_eq!(
+            LLMRoute::for_completion(Completed, Some("coder")),
+            LLMRoute::CompletionSummary
+        );
+        assert_eq!(
+            LLMRoute::for_completion(Failed, None),
+            LLMRoute::CompletionSummary
+        );
+    }
+
+    #[test]
+    fn provider_order_prefers_matching_tier() {
+        let strong: &[ModelTier] = &[ModelTier::Strong];
+        let cheap: &[ModelTier] = &[ModelTier::Cheap];
+        let any: &[ModelTier] = &[];
+        let providers = [strong, cheap, any];
+
+        assert_eq!(
+            provider_order(&providers, 0, ModelTier::Cheap),
+            vec![1, 2, 0]
+        );
+        assert_eq!(
+            provider_order(&providers, 0, ModelTier::Strong),
+            vec![0, 2, 1]
+        );
+        // Rotation from the active provider is kept within each group.
+        assert_eq!(
+            provider_order(&providers, 2, ModelTier::Strong),
+            vec![2, 0, 1]
+        );
+    }
+}
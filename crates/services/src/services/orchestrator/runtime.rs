@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use db::models::WorkflowOrchestratorCommand;
 use db::DBService;
 use sqlx::Row;
@@ -22,7 +22,11 @@ use uuid::Uuid;
 
 use super::{
     OrchestratorAgent, OrchestratorConfig, SharedMessageBus,
-    constants::{WORKFLOW_STATUS_FAILED, WORKFLOW_STATUS_PAUSED, WORKFLOW_STATUS_READY},
+    budget::{WorkflowBudget, WorkflowUsage},
+    constants::{
+        LOCAL_API_TYPE, WORKFLOW_STATUS_FAILED, WORKFLOW_STATUS_PAUSED, WORKFLOW_STATUS_READY,
+    },
+    local_llm,
     persistence::StatePersistence,
     runtime_actions::RuntimeActionService,
     types::LLMMessage,
@@ -350,6 +354,11 @@ impl OrchestratorRuntime {
             // Decrypt API key if needed
             let api_key = workflow
                 .get_api_key()?
+                .or_else(|| {
+                    // Local servers run without an API key.
+                    (workflow.orchestrator_api_type.as_deref() == Some(LOCAL_API_TYPE))
+                        .then(String::new)
+                })
                 .ok_or_else(|| anyhow!("Orchestrator API key not configured"))?;
 
             Some(
@@ -366,7 +375,11 @@ impl OrchestratorRuntime {
         };
 
         // Create orchestrator agent FIRST before changing status
-        let config = orchestrator_config.unwrap_or_default();
+        let mut config = orchestrator_config.unwrap_or_default();
+        config.budget = WorkflowBudget::from_workflow(&workflow);
+        local_llm::fit_to_context_window(&mut config)
+            .await
+            .context("Local model cannot run the orchestrator")?;
         let mut agent = match OrchestratorAgent::new(
             config,
             workflow_id.to_string(),
@@ -390,6 +403,16 @@ impl OrchestratorRuntime {
         if let Some(ref broadcaster) = *self.concierge_broadcaster.read().await {
             agent.attach_concierge_broadcaster(broadcaster.clone());
         }
+        // Budget usage accumulates across pause/resume cycles.
+        match self.persistence.load_state(workflow_id).await {
+            Ok(Some(previous)) => agent.restore_usage(previous.usage).await,
+            Ok(None) => {}
+            Err(e) => warn!(
+                workflow_id = %workflow_id,
+                error = %e,
+                "Failed to load previous budget usage; starting from zero"
+            ),
+        }
         let agent = Arc::new(agent);
 
         // Update workflow status to running AFTER agent is successfully created
@@ -805,6 +828,26 @@ impl OrchestratorRuntime {
         running.contains_key(workflow_id)
     }
 
+    /// Get accumulated LLM usage for a workflow.
+    ///
+    /// Live numbers for a running workflow, otherwise the last persisted
+    /// snapshot (`None` if the workflow never ran).
+    pub async fn get_workflow_usage(&self, workflow_id: &str) -> Result<Option<WorkflowUsage>> {
+        let agent = {
+            let running = self.running_workflows.lock().await;
+            running.get(workflow_id).map(|rw| Arc::clone(&rw.agent))
+        };
+
+        if let Some(agent) = agent {
+            return Ok(Some(agent.usage_snapshot().await));
+        }
+        Ok(self
+            .persistence
+            .load_state(workflow_id)
+            .await?
+            .map(|state| state.usage))
+    }
+
     /// Get live provider status for a running workflow.
     ///
     /// Returns `None` if the workflow is not currently running.
@@ -888,7 +931,9 @@ impl OrchestratorRuntime {
     ///
     ///   - Workflows that had active terminals with uncommitted work will
     ///     lose that in-progress work.  The terminals' PTY processes are
-    ///     gone after restart, so there is no way to reconnect.
+    ///     gone after restart unless they run under PTY supervisors
+    ///     (`SOLODAWN_PTY_SUPERVISOR`), which the deployment reattaches
+    ///     before this runs.
     ///
     ///   - Workflows with persisted state may still fail to resume if the
     ///     API key has been rotated or the LLM provider is unreachable at
@@ -1025,6 +1070,11 @@ impl OrchestratorRuntime {
         let orchestrator_config = if workflow.orchestrator_enabled {
             let api_key = workflow
                 .get_api_key()?
+                .or_else(|| {
+                    // Local servers run without an API key.
+                    (workflow.orchestrator_api_type.as_deref() == Some(LOCAL_API_TYPE))
+                        .then(String::new)
+                })
                 .ok_or_else(|| anyhow!("Orchestrator API key not configured for recovery"))?;
 
             Some(
@@ -1040,7 +1090,11 @@ impl OrchestratorRuntime {
             None
         };
 
-        let config = orchestrator_config.unwrap_or_default();
+        let mut config = orchestrator_config.unwrap_or_default();
+        config.budget = WorkflowBudget::from_workflow(&workflow);
+        local_llm::fit_to_context_window(&mut config)
+            .await
+            .context("Local model cannot run the orchestrator during recovery")?;
         let mut agent = OrchestratorAgent::new(
             config,
             workflow_id.to_string(),
@@ -1213,7 +1267,13 @@ mod tests {
                 completed_at TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                pause_reason TEXT
+                pause_reason TEXT,
+                budget_max_tokens INTEGER,
+                budget_max_cost_usd REAL,
+                budget_max_duration_secs INTEGER,
+                activity_stall_timeout_secs INTEGER,
+                activity_loop_threshold INTEGER,
+                activity_escalation_interval_secs INTEGER
             )
             ",
         )
@@ -1253,6 +1313,12 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             pause_reason: None,
+            budget_max_tokens: None,
+            budget_max_cost_usd: None,
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
         };
         Workflow::create(&pool, &workflow).await.unwrap();
 
@@ -1270,7 +1336,13 @@ mod tests {
                 remote_project_id TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                pause_reason TEXT
+                pause_reason TEXT,
+                budget_max_tokens INTEGER,
+                budget_max_cost_usd REAL,
+                budget_max_duration_secs INTEGER,
+                activity_stall_timeout_secs INTEGER,
+                activity_loop_threshold INTEGER,
+                activity_escalation_interval_secs INTEGER
             )
             ",
         )
@@ -1292,7 +1364,13 @@ mod tests {
                 dev_server_script TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                pause_reason TEXT
+                pause_reason TEXT,
+                budget_max_tokens INTEGER,
+                budget_max_cost_usd REAL,
+                budget_max_duration_secs INTEGER,
+                activity_stall_timeout_secs INTEGER,
+                activity_loop_threshold INTEGER,
+                activity_escalation_interval_secs INTEGER
             )
             ",
         )
@@ -1419,6 +1497,12 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             pause_reason: None,
+            budget_max_tokens: None,
+            budget_max_cost_usd: None,
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
         }
     }
 
@@ -47,6 +47,7 @@ pub struct RuntimeTaskSpec {
     pub description: Option<String>,
     pub branch: Option<String>,
     pub order_index: Option<i32>,
+    pub depends_on: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -104,6 +105,15 @@ impl RuntimeActionService {
             .max()
             .unwrap_or(-1)
             + 1;
+        if let Some(unknown) = spec
+            .depends_on
+            .iter()
+            .find(|dep| !existing_tasks.iter().any(|task| &task.id == *dep))
+        {
+            return Err(anyhow!(
+                "Task dependency {unknown} not found in workflow {workflow_id}"
+            ));
+        }
         let branch = spec.branch.unwrap_or_else(|| {
             generate_task_branch_name(
                 workflow_id,
@@ -123,6 +133,7 @@ impl RuntimeActionService {
             branch,
             status: "pending".to_string(),
             order_index: spec.order_index.unwrap_or(next_order_index),
+            depends_on: spec.depends_on,
             started_at: None,
             completed_at: None,
             created_at: Utc::now(),
@@ -193,6 +204,9 @@ impl RuntimeActionService {
             execution_process_id: None,
             vk_session_id: None,
             auto_confirm: spec.auto_confirm.unwrap_or(true),
+            resource_limits: None,
+            resource_report: None,
+            runner_labels: None,
             last_commit_hash: None,
             last_commit_message: None,
             started_at: None,
@@ -37,7 +37,13 @@ async fn test_runtime_starts_and_stops_workflow() {
             completed_at TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
-            pause_reason TEXT
+            pause_reason TEXT,
+            budget_max_tokens INTEGER,
+            budget_max_cost_usd REAL,
+            budget_max_duration_secs INTEGER,
+            activity_stall_timeout_secs INTEGER,
+            activity_loop_threshold INTEGER,
+            activity_escalation_interval_secs INTEGER
         )
     ").execute(&pool).await.unwrap();
 
@@ -121,7 +127,13 @@ async fn test_runtime_prevents_duplicate_start() {
             completed_at TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
-            pause_reason TEXT
+            pause_reason TEXT,
+            budget_max_tokens INTEGER,
+            budget_max_cost_usd REAL,
+            budget_max_duration_secs INTEGER,
+            activity_stall_timeout_secs INTEGER,
+            activity_loop_threshold INTEGER,
+            activity_escalation_interval_secs INTEGER
         )
     ").execute(&pool).await.unwrap();
 
@@ -198,7 +210,13 @@ async fn test_runtime_validates_workflow_state() {
             completed_at TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
-            pause_reason TEXT
+            pause_reason TEXT,
+            budget_max_tokens INTEGER,
+            budget_max_cost_usd REAL,
+            budget_max_duration_secs INTEGER,
+            activity_stall_timeout_secs INTEGER,
+            activity_loop_threshold INTEGER,
+            activity_escalation_interval_secs INTEGER
         )
     ").execute(&pool).await.unwrap();
 
@@ -290,7 +308,13 @@ async fn test_runtime_recovers_running_workflows() {
             completed_at TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
-            pause_reason TEXT
+            pause_reason TEXT,
+            budget_max_tokens INTEGER,
+            budget_max_cost_usd REAL,
+            budget_max_duration_secs INTEGER,
+            activity_stall_timeout_secs INTEGER,
+            activity_loop_threshold INTEGER,
+            activity_escalation_interval_secs INTEGER
         )
     ").execute(&pool).await.unwrap();
 
@@ -368,7 +392,13 @@ async fn test_runtime_stop_all() {
             completed_at TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
-            pause_reason TEXT
+            pause_reason TEXT,
+            budget_max_tokens INTEGER,
+            budget_max_cost_usd REAL,
+            budget_max_duration_secs INTEGER,
+            activity_stall_timeout_secs INTEGER,
+            activity_loop_threshold INTEGER,
+            activity_escalation_interval_secs INTEGER
         )
     ").execute(&pool).await.unwrap();
 
@@ -457,7 +487,13 @@ async fn test_runtime_enforces_concurrent_limit() {
             completed_at TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
-            pause_reason TEXT
+            pause_reason TEXT,
+            budget_max_tokens INTEGER,
+            budget_max_cost_usd REAL,
+            budget_max_duration_secs INTEGER,
+            activity_stall_timeout_secs INTEGER,
+            activity_loop_threshold INTEGER,
+            activity_escalation_interval_secs INTEGER
         )
     ").execute(&pool).await.unwrap();
 
@@ -550,7 +586,13 @@ async fn test_start_workflow_rolls_back_on_agent_creation_failure() {
             completed_at TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
-            pause_reason TEXT
+            pause_reason TEXT,
+            budget_max_tokens INTEGER,
+            budget_max_cost_usd REAL,
+            budget_max_duration_secs INTEGER,
+            activity_stall_timeout_secs INTEGER,
+            activity_loop_threshold INTEGER,
+            activity_escalation_interval_secs INTEGER
         )
     ").execute(&pool).await.unwrap();
 
@@ -5,7 +5,9 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use tokio::sync::RwLock;
 
 use super::{
+    budget::WorkflowUsage,
     config::OrchestratorConfig,
+    history::{self, CompactionBatch},
     types::{LLMMessage, TerminalCompletionEvent},
 };
 
@@ -61,6 +63,9 @@ pub struct OrchestratorState {
     /// Total error count for this workflow run.
     pub error_count: u32,
 
+    /// Accumulated LLM usage, spend and active time checked against the budget.
+    pub usage: WorkflowUsage,
+
     /// Set of processed commit hashes for idempotency.
     ///
     /// Bounded to `MAX_PROCESSED_COMMITS` entries. When the limit is reached,
@@ -91,6 +96,7 @@ impl OrchestratorState {
             pending_events: Vec::new(),
             total_tokens_used: 0,
             error_count: 0,
+            usage: WorkflowUsage::default(),
             processed_commits: HashSet::new(),
             processed_commits_order: VecDeque::new(),
             pending_quiet_completion_checks: HashSet::new(),
@@ -293,6 +299,105 @@ impl OrchestratorState {
         }
     }
 
+    /// Pinned messages survive compaction: the leading system prompt and the
+    /// memory message.
+    fn is_pinned_message(index: usize, message: &LLMMessage) -> bool {
+        (index == 0 && message.role == "system") || history::is_memory_message(message)
+    }
+
+    /// Selects the oldest turns to fold into the memory message, or `None` while
+    /// the history is within the compaction policy.
+    ///
+    /// Compaction triggers on the token estimate, and also just before the next
+    /// prompt/reply pair would hit `max_conversation_history` and truncate.
+    pub fn compaction_batch(&self, config: &OrchestratorConfig) -> Option<CompactionBatch> {
+        let policy = &config.history_compaction;
+        if !policy.enabled {
+            return None;
+        }
+        let near_cap = self.conversation_history.len() + 2 > config.max_conversation_history;
+        if !near_cap && history::estimate_tokens(&self.conversation_history) < policy.trigger_tokens
+        {
+            return None;
+        }
+
+        let foldable: Vec<&LLMMessage> = self
+            .conversation_history
+            .iter()
+            .enumerate()
+            .filter(|(index, message)| !Self::is_pinned_message(*index, message))
+            .map(|(_, message)| message)
+            .collect();
+        let keep = policy
+            .keep_recent_messages
+            .min(config.max_conversation_history / 2);
+        let fold = foldable.len().saturating_sub(keep);
+        if fold == 0 {
+            return None;
+        }
+
+        Some(CompactionBatch {
+            previous_memory: self
+                .conversation_history
+                .iter()
+                .find_map(history::memory_summary)
+                .map(str::to_string),
+            messages: foldable[..fold].iter().map(|m| (*m).clone()).collect(),
+        })
+    }
+
+    /// Replaces the batch with a pinned memory message holding `summary`.
+    ///
+    /// Returns false (leaving the history untouched) if the history no longer
+    /// starts with the batch, e.g. because it was truncated meanwhile.
+    pub fn apply_compaction(&mut self, batch: &CompactionBatch, summary: &str) -> bool {
+        let matches = self
+            .conversation_history
+            .iter()
+            .enumerate()
+            .filter(|(index, message)| !Self::is_pinned_message(*index, message))
+            .map(|(_, message)| message)
+            .zip(&batch.messages)
+            .filter(|(a, b)| a.role == b.role && a.content == b.content)
+            .count()
+            == batch.messages.len();
+        if !matches {
+            return false;
+        }
+
+        let mut compacted = Vec::with_capacity(self.conversation_history.len());
+        let mut folded = 0;
+        for (index, message) in std::mem::take(&mut self.conversation_history)
+            .into_iter()
+            .enumerate()
+        {
+            if index == 0 && message.role == "system" && !history::is_memory_message(&message) {
+                compacted.push(message);
+                compacted.push(LLMMessage {
+                    role: "system".to_string(),
+                    content: history::memory_message_content(summary),
+                });
+            } else if history::is_memory_message(&message) {
+                // Superseded by the new memory message.
+            } else if folded < batch.messages.len() {
+                folded += 1;
+            } else {
+                compacted.push(message);
+            }
+        }
+        if !compacted.iter().any(history::is_memory_message) {
+            compacted.insert(
+                0,
+                LLMMessage {
+                    role: "system".to_string(),
+                    content: history::memory_message_content(summary),
+                },
+            );
+        }
+        self.conversation_history = compacted;
+        true
+    }
+
     /// Returns true if all tasks are completed.
     pub fn all_tasks_completed(&self) -> bool {
         self.workflow_planning_complete
@@ -0,0 +1,162 @@
+//! Task dependency graph (`workflow_task.depends_on`): readiness and cycle checks.
+
+use std::collections::HashMap;
+
+use db::models::WorkflowTask;
+
+use super::constants::{TASK_STATUS_CANCELLED, TASK_STATUS_COMPLETED, TASK_STATUS_FAILED};
+
+/// Whether a task's dependencies allow it to start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyState {
+    /// No dependencies, or all of them completed
+    Ready,
+    /// At least one dependency is still pending or running
+    Blocked,
+    /// A dependency failed or was cancelled, so the task can never start
+    Unsatisfiable,
+}
+
+/// Evaluates `task.depends_on` against the current statuses of `tasks`.
+///
+/// Ids that don't match any task are ignored so a stale reference can't
+/// block a task forever.
+pub fn dependency_state(task: &WorkflowTask, tasks: &[WorkflowTask]) -> DependencyState {
+    let mut state = DependencyState::Ready;
+    for dep_id in &task.depends_on {
+        let Some(dep) = tasks.iter().find(|candidate| &candidate.id == dep_id) else {
+            continue;
+        };
+        match dep.status.as_str() {
+            TASK_STATUS_COMPLETED => {}
+            TASK_STATUS_FAILED | TASK_STATUS_CANCELLED => return DependencyState::Unsatisfiable,
+            _ => state = DependencyState::Blocked,
+        }
+    }
+    state
+}
+
+/// Finds a dependency cycle among `(id, depends_on)` nodes.
+///
+/// Returns the ids along the cycle with the first id repeated at the end
+/// (e.g. `["a", "b", "a"]`). Edges to unknown ids are ignored.
+pub fn find_cycle<'a, I>(nodes: I) -> Option<Vec<&'a str>>
+where
+    I: IntoIterator<Item = (&'a str, &'a [String])>,
+{
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Mark {
+        Visiting,
+        Done,
+    }
+
+    fn visit<'a>(
+        node: &'a str,
+        graph: &HashMap<&'a str, &'a [String]>,
+        marks: &mut HashMap<&'a str, Mark>,
+        path: &mut Vec<&'a str>,
+    ) -> Option<Vec<&'a str>> {
+        marks.insert(node, Mark::Visiting);
+        path.push(node);
+        for dep in graph.get(node).copied().unwrap_or_default() {
+            let Some((&dep, _)) = graph.get_key_value(dep.as_str()) else {
+                continue;
+            };
+            match marks.get(dep) {
+                Some(Mark::Visiting) => {
+                    let start = path.iter().position(|id| *id == dep).unwrap_or(0);
+                    let mut cycle = path[start..].to_vec();
+                    cycle.push(dep);
+                    return Some(cycle);
+                }
+                Some(Mark::Done) => {}
+                None => {
+                    if let Some(cycle) = visit(dep, graph, marks, path) {
+                        return Some(cycle);
+                    }
+                }
+            }
+        }
+        path.pop();
+        marks.insert(node, Mark::Done);
+        None
+    }
+
+    let nodes: Vec<(&'a str, &'a [String])> = nodes.into_iter().collect();
+    let graph: HashMap<&'a str, &'a [String]> = nodes.iter().copied().collect();
+    let mut marks = HashMap::new();
+    for (id, _) in nodes {
+        if marks.contains_key(id) {
+            continue;
+        }
+        if let Some(cycle) = visit(id, &graph, &mut marks, &mut Vec::new()) {
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn task(id: &str, status: &str, depends_on: &[&str]) -> WorkflowTask {
+        WorkflowTask {
+            id: id.to_string(),
+            workflow_id: "wf".to_string(),
+            vk_task_id: None,
+            name: id.to_string(),
+            description: None,
+            branch: format!("workflow/wf/{id}"),
+            status: status.to_string(),
+            order_index: 0,
+            depends_on: depends_on.iter().map(ToString::to_string).collect(),
+            started_at: None,
+            completed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn dependency_state_follows_dependency_statuses() {
+        let tasks = vec![
+            task("a", TASK_STATUS_COMPLETED, &[]),
+            task("b", "running", &[]),
+            task("c", TASK_STATUS_FAILED, &[]),
+        ];
+
+        assert_eq!(dependency_state(&task("x", "pending", &[]), &tasks), DependencyState::Ready);
+        assert_eq!(
+            dependency_state(&task("x", "pending", &["a", "missing"]), &tasks),
+            DependencyState::Ready
+        );
+        assert_eq!(
+            dependency_state(&task("x", "pending", &["a", "b"]), &tasks),
+            DependencyState::Blocked
+        );
+        assert_eq!(
+            dependency_state(&task("x", "pending", &["b", "c"]), &tasks),
+            DependencyState::Unsatisfiable
+        );
+    }
+
+    #[test]
+    fn find_cycle_reports_cycle_path() {
+        let deps = |ids: &[&str]| ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let a = deps(&[]);
+        let b = deps(&["a"]);
+        let c = deps(&["a", "b"]);
+        let acyclic = [("a", a.as_slice()), ("b", b.as_slice()), ("c", c.as_slice())];
+        assert_eq!(find_cycle(acyclic), None);
+
+        let a = deps(&["c"]);
+        let cyclic = [("a", a.as_slice()), ("b", b.as_slice()), ("c", c.as_slice())];
+        assert_eq!(find_cycle(cyclic), Some(vec!["a", "c", "a"]));
+
+        let own = deps(&["solo"]);
+        assert_eq!(find_cycle([("solo", own.as_slice())]), Some(vec!["solo", "solo"]));
+    }
+}
@@ -8,14 +8,17 @@ mod orchestrator_tests {
 
     use wiremock::{
         Mock, MockServer, ResponseTemplate,
-        matchers::{method, path},
+        matchers::{body_partial_json, method, path},
     };
 
     use crate::services::orchestrator::{
-        BusMessage, CommitMetadata, LLMMessage, MessageBus, MockLLMClient, OrchestratorAgent,
+        BusMessage, CommitMetadata, LLMMessage, LLMStreamEvent, MessageBus, MockLLMClient,
+        OrchestratorAgent,
         OrchestratorConfig, OrchestratorInstruction, OrchestratorRunState, OrchestratorState,
         RuntimeActionService, TerminalCompletionEvent, TerminalCompletionStatus,
         constants::DEFAULT_LLM_RATE_LIMIT_PER_SECOND, create_llm_client,
+        history::HistoryCompactionConfig,
+        instruction_tools::{instruction_tool_definitions, instructions_from_tool_calls},
     };
 
     // Tests will be added in subsequent tasks
@@ -91,6 +94,7 @@ mod orchestrator_tests {
                 description: Some("Create work at runtime".to_string()),
                 branch: None,
                 order_index: Some(0),
+                depends_on: vec!["task-1".to_string()],
             },
             OrchestratorInstruction::CreateTerminal {
                 terminal_id: Some("term-runtime".to_string()),
@@ -339,6 +343,98 @@ mod orchestrator_tests {
         assert_eq!(state.conversation_history[0].role, "system");
     }
 
+    #[test]
+    fn test_conversation_history_compaction() {
+        let mut state = OrchestratorState::new("workflow-1".to_string());
+        let config = OrchestratorConfig {
+            max_conversation_history: 10,
+            history_compaction: HistoryCompactionConfig {
+                enabled: true,
+                keep_recent_messages: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        state.add_message("system", "System prompt", &config);
+        for i in 0..3 {
+            state.add_message("user", &format!("Message {i}"), &config);
+            state.add_message("assistant", &format!("Response {i}"), &config);
+        }
+        assert!(
+            state.compaction_batch(&config).is_none(),
+            "history below the cap and token estimate should not compact"
+        );
+
+        for i in 3..4 {
+            state.add_message("user", &format!("Message {i}"), &config);
+            state.add_message("assistant", &format!("Response {i}"), &config);
+        }
+        let batch = state
+            .compaction_batch(&config)
+            .expect("history near the cap should compact");
+        assert!(batch.previous_memory.is_none());
+        assert_eq!(batch.messages.len(), 6);
+        assert_eq!(batch.messages[0].content, "Message 0");
+
+        assert!(state.apply_compaction(&batch, "plan: ship login first"));
+        let contents: Vec<_> = state
+            .conversation_history
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(
+            contents,
+            vec![
+                "System prompt",
+                "[Workflow memory]\nplan: ship login first",
+                "Message 3",
+                "Response 3",
+            ]
+        );
+
+        // A stale batch no longer matches the history and is ignored.
+        assert!(!state.apply_compaction(&batch, "stale"));
+
+        for i in 4..7 {
+            state.add_message("user", &format!("Message {i}"), &config);
+            state.add_message("assistant", &format!("Response {i}"), &config);
+        }
+        let batch = state
+            .compaction_batch(&config)
+            .expect("history near the cap should compact again");
+        assert_eq!(
+            batch.previous_memory.as_deref(),
+            Some("plan: ship login first")
+        );
+        assert!(state.apply_compaction(&batch, "merged memory"));
+        let memories = state
+            .conversation_history
+            .iter()
+            .filter(|m| m.content.starts_with("[Workflow memory]"))
+            .count();
+        assert_eq!(memories, 1, "memory message should be replaced, not duplicated");
+        assert_eq!(state.conversation_history.len(), 4);
+    }
+
+    #[test]
+    fn test_conversation_history_compaction_disabled() {
+        let mut state = OrchestratorState::new("workflow-1".to_string());
+        let config = OrchestratorConfig {
+            max_conversation_history: 4,
+            history_compaction: HistoryCompactionConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        for i in 0..4 {
+            state.add_message("user", &format!("Message {i}"), &config);
+        }
+        assert!(state.compaction_batch(&config).is_none());
+    }
+
     #[tokio::test]
     async fn test_all_tasks_completed() {
         let mut state = OrchestratorState::new("workflow-1".to_string());
@@ -411,6 +507,176 @@ mod orchestrator_tests {
         assert_eq!(usage.total_tokens, 19);
     }
 
+    #[tokio::test]
+    async fn test_llm_client_native_tool_calls() {
+        // Install crypto provider for reqwest (ignore if already installed)
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "tools": [{ "type": "function", "function": { "name": "start_task" } }]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{
+                    "message": {
+                        "role": "assistant",
+                        "content": null,
+                        "tool_calls": [{
+                            "id": "call_1",
+                            "type": "function",
+                            "function": {
+                                "name": "complete_workflow",
+                                "arguments": "{\"summary\":\"all done\"}"
+                            }
+                        }]
+                    }
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let config = OrchestratorConfig {
+            base_url: mock_server.uri(),
+            api_key: "test-key".to_string(),
+            model: "gpt-4".to_string(),
+            ..Default::default()
+        };
+
+        let client = create_llm_client(&config).unwrap();
+        let messages = vec![LLMMessage {
+            role: "user".to_string(),
+            content: "Finish up".to_string(),
+        }];
+
+        let response = client
+            .chat_with_tools(messages, instruction_tool_definitions())
+            .await
+            .unwrap();
+
+        assert!(response.content.is_empty());
+        let instructions = instructions_from_tool_calls(&response.tool_calls);
+        assert_eq!(instructions.len(), 1);
+        match &instructions[0] {
+            OrchestratorInstruction::CompleteWorkflow { summary } => {
+                assert_eq!(summary, "all done");
+            }
+            other => panic!("Unexpected instruction: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_llm_client_streams_openai_deltas() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        let mock_server = MockServer::start().await;
+        let sse_body = concat!(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\" world\"}}]}\n\n",
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":5,\"completion_tokens\":2,\"total_tokens\":7}}\n\n",
+            "data: [DONE]\n\n",
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({ "stream": true })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let config = OrchestratorConfig {
+            base_url: mock_server.uri(),
+            api_key: "test-key".to_string(),
+            model: "gpt-4".to_string(),
+            ..Default::default()
+        };
+
+        let client = create_llm_client(&config).unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = client
+            .chat_stream(
+                vec![LLMMessage {
+                    role: "user".to_string(),
+                    content: "Hi".to_string(),
+                }],
+                Vec::new(),
+                tx,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.content, "Hello world");
+        assert_eq!(response.usage.map(|u| u.total_tokens), Some(7));
+
+        let mut deltas = Vec::new();
+        while let Some(event) = rx.recv().await {
+            if let LLMStreamEvent::Delta { text } = event {
+                deltas.push(text);
+            }
+        }
+        assert_eq!(deltas, vec!["Hello", " world"]);
+    }
+
+    #[tokio::test]
+    async fn test_llm_client_streams_anthropic_deltas() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        let mock_server = MockServer::start().await;
+        let sse_body = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":4}}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Plan\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\" ready\"}}\n\n",
+            "event: message_delta\n",
+            "data: {\"type\":\"message_delta\",\"usage\":{\"output_tokens\":3}}\n\n",
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let config = OrchestratorConfig {
+            api_type: "anthropic".to_string(),
+            base_url: mock_server.uri(),
+            api_key: "test-key".to_string(),
+            model: "claude-test".to_string(),
+            ..Default::default()
+        };
+
+        let client = create_llm_client(&config).unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = client
+            .chat_stream(
+                vec![LLMMessage {
+                    role: "user".to_string(),
+                    content: "Hi".to_string(),
+                }],
+                Vec::new(),
+                tx,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.content, "Plan ready");
+        assert_eq!(response.usage.map(|u| u.total_tokens), Some(7));
+        assert_eq!(
+            rx.recv().await,
+            Some(LLMStreamEvent::Delta {
+                text: "Plan".to_string()
+            })
+        );
+    }
+
     #[tokio::test]
     async fn test_llm_client_error_handling() {
         // Install crypto provider for reqwest (ignore if already installed)
@@ -447,6 +713,59 @@ mod orchestrator_tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_llm_client_reports_serving_model_after_failover() {
+        use crate::services::orchestrator::config::ProviderConfig;
+
+        // Install crypto provider for reqwest (ignore if already installed)
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        let primary_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&primary_server)
+            .await;
+
+        let fallback_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": "from fallback" } }],
+                "usage": { "prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2 }
+            })))
+            .mount(&fallback_server)
+            .await;
+
+        let config = OrchestratorConfig {
+            base_url: primary_server.uri(),
+            api_key: "test-key".to_string(),
+            model: "gpt-4o".to_string(),
+            fallback_providers: vec![ProviderConfig {
+                name: "cheap".to_string(),
+                api_type: "openai".to_string(),
+                base_url: fallback_server.uri(),
+                api_key: "test-key".to_string(),
+                model: "gpt-4o-mini".to_string(),
+                priority: 1,
+                tiers: Vec::new(),
+            }],
+            ..Default::default()
+        };
+
+        let client = create_llm_client(&config).unwrap();
+        let messages = vec![LLMMessage {
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+        }];
+
+        let response = client.chat(messages).await.unwrap();
+
+        assert_eq!(response.content, "from fallback");
+        assert_eq!(response.provider.as_deref(), Some("cheap"));
+        assert_eq!(response.model.as_deref(), Some("gpt-4o-mini"));
+    }
+
     #[tokio::test]
     async fn test_llm_client_empty_response() {
         // Install crypto provider for reqwest (ignore if already installed)
@@ -878,6 +1197,12 @@ mod orchestrator_tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             pause_reason: None,
+            budget_max_tokens: None,
+            budget_max_cost_usd: None,
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
         };
         Workflow::create(&pool, &workflow).await.unwrap();
 
@@ -929,6 +1254,230 @@ mod orchestrator_tests {
         assert_eq!(workflow.status, "completed");
     }
 
+    #[tokio::test]
+    async fn test_budget_exceeded_pauses_workflow() {
+        use std::path::PathBuf;
+
+        use chrono::Utc;
+        use db::{DBService, models::Workflow};
+        use sqlx::sqlite::SqlitePoolOptions;
+        use uuid::Uuid;
+
+        use crate::services::orchestrator::{WorkflowBudget, constants::PAUSE_REASON_TOKEN_BUDGET};
+
+        let pool = SqlitePoolOptions::new().connect(":memory:").await.unwrap();
+        let migration_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(1)
+            .unwrap()
+            .join("db")
+            .join("migrations");
+        let migrator = sqlx::migrate::Migrator::new(migration_dir).await.unwrap();
+        migrator.run(&pool).await.unwrap();
+
+        let db = Arc::new(DBService { pool: pool.clone() });
+        let message_bus = Arc::new(MessageBus::new(100));
+
+        let project_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO projects (id, name, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(project_id)
+        .bind("budget-project")
+        .bind(Utc::now())
+        .bind(Utc::now())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let workflow = Workflow {
+            id: Uuid::new_v4().to_string(),
+            project_id,
+            name: "Budget Workflow".to_string(),
+            description: None,
+            status: "running".to_string(),
+            execution_mode: "agent_planned".to_string(),
+            initial_goal: None,
+            use_slash_commands: false,
+            orchestrator_enabled: false,
+            orchestrator_api_type: None,
+            orchestrator_base_url: None,
+            orchestrator_api_key: None,
+            orchestrator_model: None,
+            error_terminal_enabled: false,
+            error_terminal_cli_id: None,
+            error_terminal_model_id: None,
+            merge_terminal_cli_id: "cli-claude-code".to_string(),
+            merge_terminal_model_id: "model-claude-sonnet".to_string(),
+            target_branch: "main".to_string(),
+            git_watcher_enabled: false,
+            ready_at: Some(Utc::now()),
+            started_at: Some(Utc::now()),
+            completed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            pause_reason: None,
+            budget_max_tokens: Some(25),
+            budget_max_cost_usd: None,
+            budget_max_duration_secs: None,
+            activity_stall_timeout_secs: None,
+            activity_loop_threshold: None,
+            activity_escalation_interval_secs: None,
+        };
+        Workflow::create(&pool, &workflow).await.unwrap();
+
+        // MockLLMClient reports 30 tokens per call.
+        let agent = OrchestratorAgent::with_llm_client(
+            OrchestratorConfig {
+                api_key: "test-key".to_string(),
+                budget: WorkflowBudget::from_workflow(&workflow),
+                ..Default::default()
+            },
+            workflow.id.clone(),
+            message_bus.clone(),
+            db.clone(),
+            Box::new(MockLLMClient::with_response("[]")),
+        )
+        .expect("agent should be created");
+
+        agent
+            .submit_orchestrator_chat_message("first")
+            .await
+            .expect("call within budget should succeed");
+        assert!(
+            agent.submit_orchestrator_chat_message("second").await.is_err(),
+            "call over budget should be refused"
+        );
+
+        let usage = agent.usage_snapshot().await;
+        assert_eq!(usage.llm_calls, 1);
+        assert_eq!(usage.total_tokens, 30);
+
+        let workflow = Workflow::find_by_id(&pool, &workflow.id)
+            .await
+            .unwrap()
+            .expect("workflow should still exist");
+        assert_eq!(workflow.status, "paused");
+        assert_eq!(workflow.pause_reason.as_deref(), Some(PAUSE_REASON_TOKEN_BUDGET));
+    }
+
+    #[tokio::test]
+    async fn test_recorded_llm_run_replays_through_agent() {
+        use std::path::PathBuf;
+
+        use db::DBService;
+        use sqlx::sqlite::SqlitePoolOptions;
+        use uuid::Uuid;
+
+        use crate::services::orchestrator::{RecordingLLMClient, ReplayLLMClient};
+
+        let pool = SqlitePoolOptions::new().connect(":memory:").await.unwrap();
+        let migration_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(1)
+            .unwrap()
+            .join("db")
+            .join("migrations");
+        let migrator = sqlx::migrate::Migrator::new(migration_dir).await.unwrap();
+        migrator.run(&pool).await.unwrap();
+        let db = Arc::new(DBService { pool: pool.clone() });
+
+        let project_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO projects (id, name, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(project_id)
+        .bind("replay-project")
+        .bind(chrono::Utc::now())
+        .bind(chrono::Utc::now())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let workflow_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            r"
+            INSERT INTO workflow (
+                id, project_id, name, target_branch,
+                merge_terminal_cli_id, merge_terminal_model_id,
+                created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ",
+        )
+        .bind(&workflow_id)
+        .bind(project_id)
+        .bind("replay-workflow")
+        .bind("main")
+        .bind("cli-claude-code")
+        .bind("model-claude-sonnet")
+        .bind(chrono::Utc::now())
+        .bind(chrono::Utc::now())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let fixture_dir = tempfile::tempdir().unwrap();
+        let fixture_path = fixture_dir.path().join(format!("{workflow_id}.json"));
+        let config = OrchestratorConfig {
+            api_key: "test-key".to_string(),
+            ..Default::default()
+        };
+        let prompts = ["Summarize progress so far", "Is anything blocked?"];
+
+        // Record a run against the mock provider.
+        let recorded = OrchestratorAgent::with_llm_client(
+            config.clone(),
+            workflow_id.clone(),
+            Arc::new(MessageBus::new(100)),
+            db.clone(),
+            Box::new(RecordingLLMClient::new(
+                Box::new(MockLLMClient::with_response("[]")),
+                &fixture_path,
+            )),
+        )
+        .expect("recording agent should be created");
+        for prompt in prompts {
+            recorded
+                .submit_orchestrator_chat_message(prompt)
+                .await
+                .expect("recorded call should succeed");
+        }
+
+        // Replay it through a fresh agent: every request must hit the fixture.
+        let replayed = OrchestratorAgent::with_llm_client(
+            config,
+            workflow_id.clone(),
+            Arc::new(MessageBus::new(100)),
+            db.clone(),
+            Box::new(ReplayLLMClient::from_file(&fixture_path).unwrap()),
+        )
+        .expect("replaying agent should be created");
+        for prompt in prompts {
+            replayed
+                .submit_orchestrator_chat_message(prompt)
+                .await
+                .expect("replayed call should match the fixture");
+        }
+
+        let contents = |history: Vec<LLMMessage>| {
+            history
+                .into_iter()
+                .map(|m| (m.role, m.content))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            contents(replayed.get_conversation_history().await),
+            contents(recorded.get_conversation_history().await)
+        );
+        assert!(
+            replayed
+                .submit_orchestrator_chat_message("A request that was never recorded")
+                .await
+                .is_err(),
+            "unrecorded requests must fail instead of reaching a provider"
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_instruction_send_to_terminal() {
         use std::{path::PathBuf, sync::Arc};
@@ -1504,6 +2053,86 @@ mod orchestrator_tests {
         assert_eq!(task.status, "running");
     }
 
+    #[tokio::test]
+    async fn test_execute_instruction_start_task_waits_for_dependencies() {
+        use db::models::{Terminal, WorkflowTask};
+
+        let (db, workflow_id, task_id, terminals) = setup_workflow_with_terminals(1, true).await;
+        let (terminal_id, _) = terminals.first().cloned().unwrap();
+
+        let dependency_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            r"
+            INSERT INTO workflow_task (
+                id, workflow_id, name, branch, order_index,
+                created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ",
+        )
+        .bind(&dependency_id)
+        .bind(&workflow_id)
+        .bind("dependency-task")
+        .bind("feature/dependency")
+        .bind(1)
+        .bind(chrono::Utc::now())
+        .bind(chrono::Utc::now())
+        .execute(&db.pool)
+        .await
+        .unwrap();
+        sqlx::query("UPDATE workflow_task SET depends_on = ? WHERE id = ?")
+            .bind(serde_json::json!([dependency_id]).to_string())
+            .bind(&task_id)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let task = WorkflowTask::find_by_id(&db.pool, &task_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task.depends_on, vec![dependency_id.clone()]);
+
+        let config = OrchestratorConfig {
+            api_type: "openai".to_string(),
+            base_url: "https://api.openai.com/v1".to_string(),
+            api_key: "sk-test".to_string(),
+            model: "gpt-4".to_string(),
+            ..Default::default()
+        };
+        let agent = OrchestratorAgent::with_llm_client(
+            config,
+            workflow_id.clone(),
+            Arc::new(MessageBus::new(100)),
+            db.clone(),
+            Box::new(MockLLMClient::new()),
+        )
+        .unwrap();
+
+        let instruction_json = format!(
+            r#"{{"type":"start_task","task_id":"{task_id}","instruction":"echo start"}}"#
+        );
+        // The failed instruction is logged and skipped; the terminal must stay idle.
+        agent.execute_instruction(&instruction_json).await.unwrap();
+        let terminal = Terminal::find_by_id(&db.pool, &terminal_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(terminal.status, "waiting");
+
+        WorkflowTask::update_status(&db.pool, &dependency_id, "completed")
+            .await
+            .unwrap();
+        agent
+            .execute_instruction(&instruction_json)
+            .await
+            .expect("StartTask should dispatch once dependencies completed");
+        let terminal = Terminal::find_by_id(&db.pool, &terminal_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(terminal.status, "working");
+    }
+
     #[tokio::test]
     async fn test_execute_instruction_start_task_skips_dispatch_when_terminal_not_waiting() {
         use db::models::{Terminal, WorkflowTask};
@@ -2232,6 +2861,7 @@ mod orchestrator_tests {
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -2324,6 +2954,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -2410,6 +3041,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -2512,6 +3144,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -2588,6 +3221,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -2675,6 +3309,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -2760,6 +3395,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -2835,6 +3471,7 @@ reviewed_terminal: {}",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -3011,6 +3648,7 @@ next_action: handoff";
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -3100,6 +3738,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -3246,6 +3885,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -3359,6 +3999,7 @@ next_action: handoff",
             auto_merge_on_completion: true,
             fallback_providers: Vec::new(),
             quality_gate_mode: "shadow".to_string(),
+            ..Default::default()
         };
 
         let message_bus = Arc::new(MessageBus::new(100));
@@ -26,6 +26,9 @@ pub enum OrchestratorInstruction {
         description: Option<String>,
         branch: Option<String>,
         order_index: Option<i32>,
+        /// 依赖的任务 ID，全部完成后才会派发
+        #[serde(default)]
+        depends_on: Vec<String>,
     },
     /// 创建运行时终端（仅 agent_planned 模式）
     CreateTerminal {
@@ -146,11 +149,58 @@ pub struct LLMMessage {
     pub content: String,
 }
 
+/// LLM 工具定义（函数名 + JSON Schema 参数）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMToolDefinition {
+    pub name: String,
+    pub description: String,
+    /// JSON Schema describing the tool arguments
+    pub parameters: serde_json::Value,
+}
+
+/// LLM 返回的结构化工具调用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMToolCall {
+    /// Provider-assigned call id (may be empty for providers that omit it)
+    pub id: String,
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
 /// LLM 响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMResponse {
     pub content: String,
     pub usage: Option<LLMUsage>,
+    /// Structured tool calls returned by the provider (empty for text-only replies)
+    #[serde(default)]
+    pub tool_calls: Vec<LLMToolCall>,
+    /// Provider that served the response (set by multi-provider clients)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Model that served the response (set by the provider client)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl LLMResponse {
+    /// Records `model` as the serving model unless an inner client already did.
+    pub fn served_by(mut self, model: &str) -> Self {
+        self.model.get_or_insert_with(|| model.to_string());
+        self
+    }
+}
+
+/// LLM 流式输出事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LLMStreamEvent {
+    /// Incremental assistant text
+    Delta { text: String },
+    /// Partial output so far is discarded (failover to another provider)
+    Reset,
+    /// Stream finished; emitted by the consumer after the response is assembled
+    Done,
 }
 
 /// LLM 使用量
@@ -478,6 +528,82 @@ pub struct QualityGateResultEvent {
     pub fix_instructions: Option<String>,
 }
 
+/// Terminal activity monitor escalation step, in ladder order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityEscalationLevel {
+    /// Remind the terminal to continue its task
+    Nudge,
+    /// Ask the orchestrator LLM to intervene
+    Notify,
+    /// Relaunch the terminal and re-dispatch its task
+    Restart,
+    /// Give up and fail the terminal
+    Fail,
+}
+
+impl ActivityEscalationLevel {
+    /// Step after `current`; `None` once the terminal has been failed.
+    pub fn next(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::Nudge),
+            Some(Self::Nudge) => Some(Self::Notify),
+            Some(Self::Notify) => Some(Self::Restart),
+            Some(Self::Restart) => Some(Self::Fail),
+            Some(Self::Fail) => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Nudge => "nudge",
+            Self::Notify => "notify",
+            Self::Restart => "restart",
+            Self::Fail => "fail",
+        }
+    }
+}
+
+/// Why the activity monitor escalated a terminal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ActivityStallReason {
+    /// No meaningful output or commits for `idle_secs`
+    Idle { idle_secs: u64 },
+    /// The same tool call was seen `repeats` times in the recent window
+    Loop { tool_call: String, repeats: usize },
+}
+
+impl ActivityStallReason {
+    /// One-line description for terminal nudges and LLM prompts.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Idle { idle_secs } => format!(
+                "no meaningful output or commits for {} minutes",
+                idle_secs.div_ceil(60)
+            ),
+            Self::Loop { tool_call, repeats } => {
+                format!("the same tool call `{tool_call}` was repeated {repeats} times")
+            }
+        }
+    }
+}
+
+/// Terminal activity escalation, published by `ActivityMonitor`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalActivityEvent {
+    pub terminal_id: String,
+    pub workflow_id: String,
+    pub task_id: String,
+    /// PTY session ID the monitor was watching
+    pub session_id: String,
+    pub level: ActivityEscalationLevel,
+    pub reason: ActivityStallReason,
+    /// Last output sequence number seen on the terminal's `OutputFanout`
+    pub last_output_seq: u64,
+    pub detected_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,18 +2,42 @@
 //!
 //! Provides a unified `RunnerClient` trait with two implementations:
 //! - `LocalRunner`: wraps an in-process `ProcessManager` for local mode
-//! - `RemoteRunner`: gRPC client stub for remote runner mode (placeholder)
+//! - `RemoteRunner`: gRPC client for a single remote runner
 //!
 //! The `RunnerClientImpl` enum delegates to the appropriate implementation
-//! based on the `SOLODAWN_RUNNER_MODE` environment variable.
+//! based on the `SOLODAWN_RUNNER_MODE` environment variable; with several
+//! runners configured it schedules terminals through a [`RunnerRegistry`].
+//! In remote mode the server's `ProcessManager` hosts every terminal on the
+//! runners through this client (see [`super::terminal::remote`]).
+//!
+//! Runners without a shared filesystem get their working copy through
+//! workspace provisioning (see [`super::workspace_sync`]).
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use async_trait::async_trait;
-
+use futures::stream::BoxStream;
+use tokio::io::AsyncWriteExt;
+use tokio_stream::StreamExt;
+use tonic::{
+    metadata::{Ascii, MetadataValue},
+    service::{Interceptor, interceptor::InterceptedService},
+    transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity},
+};
+
+use super::runner_registry::RunnerRegistry;
 use super::terminal::process::{ProcessManager, SpawnCommand, SpawnEnv};
+use super::workspace_sync::{
+    self, BUNDLE_CHUNK_SIZE, ProvisionedWorkspace, RunnerWorkspaces, WorkspaceProvision,
+    WorkspaceSource, WorkspaceSync,
+};
+
+/// How long to wait for a TCP/TLS connection to a remote runner
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 
 // ============================================================================
 // Data Types
@@ -36,8 +60,45 @@ pub struct TerminalSpawnConfig {
     pub env_unset: Vec<String>,
     pub cols: u32,
     pub rows: u32,
+    /// Labels the chosen runner must carry (e.g. `has-gpu`); ignored by `LocalRunner`
+    pub runner_labels: Vec<String>,
+    /// Provisioned workspace the terminal runs in; pins it to that workspace's runner
+    pub workspace_id: Option<String>,
 }
 
+impl TerminalSpawnConfig {
+    /// Spawn configuration for running `command` as `terminal_id`.
+    pub fn from_spawn_command(
+        terminal_id: &str,
+        command: &SpawnCommand,
+        cols: u16,
+        rows: u16,
+    ) -> Self {
+        Self {
+            terminal_id: terminal_id.to_string(),
+            command: command.command.clone(),
+            args: command.args.clone(),
+            working_dir: command.working_dir.to_string_lossy().into_owned(),
+            env_set: command.env.set.clone(),
+            env_unset: command.env.unset.clone(),
+            cols: cols.into(),
+            rows: rows.into(),
+            runner_labels: command.runner_labels.clone(),
+            workspace_id: None,
+        }
+    }
+}
+
+/// One chunk of terminal output streamed from a runner.
+#[derive(Debug, Clone)]
+pub struct RunnerOutput {
+    pub seq: u64,
+    pub data: Vec<u8>,
+}
+
+/// Terminal output stream; ends when the runner drops the terminal.
+pub type RunnerOutputStream = BoxStream<'static, Result<RunnerOutput>>;
+
 /// Health status of a runner.
 #[derive(Debug, Clone)]
 pub struct RunnerHealth {
@@ -58,7 +119,24 @@ pub trait RunnerClient: Send + Sync + 'static {
     async fn is_running(&self, terminal_id: &str) -> Result<bool>;
     async fn write_input(&self, terminal_id: &str, data: &[u8]) -> Result<()>;
     async fn resize_terminal(&self, terminal_id: &str, cols: u32, rows: u32) -> Result<()>;
+    /// Output of `terminal_id` after chunk `from_seq` (0 replays the retained window).
+    async fn stream_output(&self, terminal_id: &str, from_seq: u64) -> Result<RunnerOutputStream>;
     async fn health_check(&self) -> Result<RunnerHealth>;
+    /// Create the working copy terminals of `request.workspace_id` run in.
+    async fn provision_workspace(
+        &self,
+        request: WorkspaceProvision,
+    ) -> Result<ProvisionedWorkspace>;
+    /// Fast-forward `local_worktree` to the workspace's commits after
+    /// `since_commit`, the workspace commit it was last synced to, and report
+    /// the workspace's uncommitted changes.
+    async fn sync_workspace(
+        &self,
+        workspace_id: &str,
+        since_commit: &str,
+        local_worktree: &Path,
+    ) -> Result<WorkspaceSync>;
+    async fn release_workspace(&self, workspace_id: &str) -> Result<()>;
 }
 
 // ============================================================================
@@ -69,12 +147,14 @@ pub trait RunnerClient: Send + Sync + 'static {
 #[derive(Clone)]
 pub struct LocalRunner {
     process_manager: Arc<ProcessManager>,
+    workspaces: RunnerWorkspaces,
 }
 
 impl std::fmt::Debug for LocalRunner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LocalRunner")
             .field("process_manager", &"Arc<ProcessManager>")
+            .field("workspaces", &self.workspaces)
             .finish()
     }
 }
@@ -82,7 +162,17 @@ impl std::fmt::Debug for LocalRunner {
 impl LocalRunner {
     /// Creates a new `LocalRunner` wrapping the given `ProcessManager`.
     pub fn new(process_manager: Arc<ProcessManager>) -> Self {
-        Self { process_manager }
+        Self {
+            process_manager,
+            workspaces: RunnerWorkspaces::from_env(),
+        }
+    }
+
+    /// Clone URL-sourced workspaces under `workspaces` instead of the
+    /// directory from `SOLODAWN_RUNNER_WORKSPACE_DIR`.
+    pub fn with_workspaces(mut self, workspaces: RunnerWorkspaces) -> Self {
+        self.workspaces = workspaces;
+        self
     }
 }
 
@@ -152,6 +242,24 @@ impl RunnerClient for LocalRunner {
         self.process_manager.resize(terminal_id, cols, rows).await
     }
 
+    async fn stream_output(&self, terminal_id: &str, from_seq: u64) -> Result<RunnerOutputStream> {
+        let subscription = self
+            .process_manager
+            .subscribe_output(terminal_id, (from_seq > 0).then_some(from_seq))
+            .await?;
+        Ok(Box::pin(futures::stream::unfold(
+            subscription,
+            |mut subscription| async move {
+                let chunk = subscription.recv().await.ok()?;
+                let output = RunnerOutput {
+                    seq: chunk.seq,
+                    data: chunk.text.into_bytes(),
+                };
+                Some((Ok(output), subscription))
+            },
+        )))
+    }
+
     async fn health_check(&self) -> Result<RunnerHealth> {
         let running = self.process_manager.list_running().await;
         Ok(RunnerHealth {
@@ -159,29 +267,172 @@ impl RunnerClient for LocalRunner {
             active_terminals: u32::try_from(running.len()).unwrap_or(u32::MAX),
         })
     }
+
+    /// Local repositories are used in place; URLs are cloned under the workspace root.
+    async fn provision_workspace(
+        &self,
+        request: WorkspaceProvision,
+    ) -> Result<ProvisionedWorkspace> {
+        match request.source {
+            WorkspaceSource::LocalRepo(repo) => Ok(ProvisionedWorkspace {
+                head_commit: workspace_sync::head_commit(&repo).await?,
+                working_dir: repo.to_string_lossy().into_owned(),
+            }),
+            WorkspaceSource::RemoteUrl(url) => {
+                self.workspaces
+                    .provision(&request.workspace_id, &url, &request.branch)
+                    .await
+            }
+        }
+    }
+
+    async fn sync_workspace(
+        &self,
+        workspace_id: &str,
+        since_commit: &str,
+        local_worktree: &Path,
+    ) -> Result<WorkspaceSync> {
+        if !self.workspaces.workspace_dir(workspace_id)?.exists() {
+            // Used in place, commits already land in the local worktree.
+            return Ok(WorkspaceSync {
+                head_commit: workspace_sync::head_commit(local_worktree).await?,
+                ..WorkspaceSync::default()
+            });
+        }
+        let bundle = self.workspaces.temp_bundle()?;
+        let sync = self
+            .workspaces
+            .collect_changes(workspace_id, since_commit, &bundle)
+            .await?;
+        if !sync.commits.is_empty() {
+            workspace_sync::import_bundle(local_worktree, &bundle, &sync.head_commit).await?;
+        }
+        Ok(sync)
+    }
+
+    async fn release_workspace(&self, workspace_id: &str) -> Result<()> {
+        self.workspaces.release(workspace_id).await
+    }
 }
 
 // ============================================================================
 // RemoteRunner
 // ============================================================================
 
-mod runner_proto {
+pub(super) mod runner_proto {
     tonic::include_proto!("solodawn.runner");
 }
 
 use runner_proto::runner_service_client::RunnerServiceClient;
 
+type GrpcClient = RunnerServiceClient<InterceptedService<Channel, BearerToken>>;
+
+/// Credentials for reaching remote runners.
+///
+/// - `SOLODAWN_RUNNER_TOKEN`: bearer token sent with every request
+/// - `SOLODAWN_RUNNER_TLS_CA`: PEM CA bundle used to verify `https://` runners
+/// - `SOLODAWN_RUNNER_CLIENT_TLS_CERT` / `SOLODAWN_RUNNER_CLIENT_TLS_KEY`: client identity
+///   for mTLS (the runner's own identity is `SOLODAWN_RUNNER_SERVER_TLS_*`)
+/// - `SOLODAWN_RUNNER_TLS_DOMAIN`: expected server name when it differs from the host
+#[derive(Clone, Default)]
+pub struct RunnerAuth {
+    pub token: Option<String>,
+    pub tls: Option<ClientTlsConfig>,
+}
+
+impl std::fmt::Debug for RunnerAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunnerAuth")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("tls", &self.tls.is_some())
+            .finish()
+    }
+}
+
+impl RunnerAuth {
+    /// Load the token and TLS material from the environment.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let read = |name: &str, path: &str| {
+            std::fs::read(path).with_context(|| format!("Failed to read {name} at {path}"))
+        };
+
+        let ca = var("SOLODAWN_RUNNER_TLS_CA");
+        let identity = match (
+            var("SOLODAWN_RUNNER_CLIENT_TLS_CERT"),
+            var("SOLODAWN_RUNNER_CLIENT_TLS_KEY"),
+        ) {
+            (Some(cert), Some(key)) => Some(Identity::from_pem(
+                read("SOLODAWN_RUNNER_CLIENT_TLS_CERT", &cert)?,
+                read("SOLODAWN_RUNNER_CLIENT_TLS_KEY", &key)?,
+            )),
+            (None, None) => None,
+            _ => bail!(
+                "SOLODAWN_RUNNER_CLIENT_TLS_CERT and SOLODAWN_RUNNER_CLIENT_TLS_KEY must be set together"
+            ),
+        };
+        let domain = var("SOLODAWN_RUNNER_TLS_DOMAIN");
+
+        let tls = if ca.is_some() || identity.is_some() || domain.is_some() {
+            let mut tls = ClientTlsConfig::new();
+            if let Some(ca) = ca {
+                tls =
+                    tls.ca_certificate(Certificate::from_pem(read("SOLODAWN_RUNNER_TLS_CA", &ca)?));
+            }
+            if let Some(identity) = identity {
+                tls = tls.identity(identity);
+            }
+            if let Some(domain) = domain {
+                tls = tls.domain_name(domain);
+            }
+            Some(tls)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            token: var("SOLODAWN_RUNNER_TOKEN"),
+            tls,
+        })
+    }
+}
+
+/// Adds `authorization: Bearer <token>` to outgoing runner requests
+#[derive(Clone)]
+struct BearerToken(Option<MetadataValue<Ascii>>);
+
+impl Interceptor for BearerToken {
+    fn call(
+        &mut self,
+        mut request: tonic::Request<()>,
+    ) -> Result<tonic::Request<()>, tonic::Status> {
+        if let Some(value) = &self.0 {
+            request
+                .metadata_mut()
+                .insert("authorization", value.clone());
+        }
+        Ok(request)
+    }
+}
+
 /// gRPC client for a remote runner process.
 #[derive(Clone)]
 pub struct RemoteRunner {
     addr: String,
-    client: Arc<tokio::sync::RwLock<Option<RunnerServiceClient<tonic::transport::Channel>>>>,
+    auth: RunnerAuth,
+    client: Arc<tokio::sync::RwLock<Option<GrpcClient>>>,
 }
 
 impl std::fmt::Debug for RemoteRunner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RemoteRunner")
             .field("addr", &self.addr)
+            .field("auth", &self.auth)
             .finish_non_exhaustive()
     }
 }
@@ -192,18 +443,30 @@ impl RemoteRunner {
     pub fn new(addr: impl Into<String>) -> Self {
         Self {
             addr: addr.into(),
+            auth: RunnerAuth::default(),
             client: Arc::new(tokio::sync::RwLock::new(None)),
         }
     }
 
+    /// Authenticate to the runner with the given token and TLS settings.
+    pub fn with_auth(mut self, auth: RunnerAuth) -> Self {
+        if auth.token.is_some() && auth.tls.is_none() && self.addr.starts_with("http://") {
+            tracing::warn!(
+                addr = %self.addr,
+                "Runner token is sent over plaintext HTTP; use an https:// address with TLS"
+            );
+        }
+        self.auth = auth;
+        self
+    }
+
     /// Connects to the remote runner gRPC service.
     pub async fn connect(addr: &str) -> Result<Self> {
-        let client = RunnerServiceClient::connect(addr.to_string()).await?;
+        let runner = Self::new(addr);
+        let client = runner.open_client().await?;
+        *runner.client.write().await = Some(client);
         tracing::info!(addr = %addr, "RemoteRunner connected via gRPC");
-        Ok(Self {
-            addr: addr.to_string(),
-            client: Arc::new(tokio::sync::RwLock::new(Some(client))),
-        })
+        Ok(runner)
     }
 
     /// Returns the configured remote address.
@@ -211,8 +474,29 @@ impl RemoteRunner {
         &self.addr
     }
 
+    async fn open_client(&self) -> Result<GrpcClient> {
+        let mut endpoint = Endpoint::from_shared(self.addr.clone())
+            .with_context(|| format!("Invalid runner address {}", self.addr))?
+            .connect_timeout(CONNECT_TIMEOUT);
+        if let Some(tls) = &self.auth.tls {
+            endpoint = endpoint.tls_config(tls.clone())?;
+        }
+        let channel = endpoint.connect().await?;
+        let token = self
+            .auth
+            .token
+            .as_deref()
+            .map(|token| format!("Bearer {token}").parse())
+            .transpose()
+            .context("Runner token contains characters not allowed in gRPC metadata")?;
+        Ok(RunnerServiceClient::with_interceptor(
+            channel,
+            BearerToken(token),
+        ))
+    }
+
     /// Ensure the gRPC client is connected, lazily establishing the connection.
-    async fn get_client(&self) -> Result<RunnerServiceClient<tonic::transport::Channel>> {
+    async fn get_client(&self) -> Result<GrpcClient> {
         {
             let guard = self.client.read().await;
             if let Some(client) = guard.as_ref() {
@@ -223,7 +507,8 @@ impl RemoteRunner {
         if let Some(client) = guard.as_ref() {
             return Ok(client.clone());
         }
-        let client = RunnerServiceClient::connect(self.addr.clone())
+        let client = self
+            .open_client()
             .await
             .map_err(|e| anyhow::anyhow!("Failed to connect to remote runner at {}: {e}", self.addr))?;
         tracing::info!(addr = %self.addr, "RemoteRunner lazily connected via gRPC");
@@ -301,6 +586,23 @@ impl RunnerClient for RemoteRunner {
         Ok(())
     }
 
+    async fn stream_output(&self, terminal_id: &str, from_seq: u64) -> Result<RunnerOutputStream> {
+        let mut client = self.get_client().await?;
+        let request = tonic::Request::new(runner_proto::StreamOutputRequest {
+            terminal_id: terminal_id.to_string(),
+            from_seq,
+        });
+        let stream = client.stream_output(request).await?.into_inner();
+        Ok(Box::pin(stream.map(|chunk| {
+            chunk
+                .map(|chunk| RunnerOutput {
+                    seq: chunk.seq,
+                    data: chunk.data,
+                })
+                .map_err(Into::into)
+        })))
+    }
+
     async fn health_check(&self) -> Result<RunnerHealth> {
         let mut client = self.get_client().await?;
         let request = tonic::Request::new(runner_proto::HealthRequest {});
@@ -310,17 +612,133 @@ impl RunnerClient for RemoteRunner {
             active_terminals: response.active_terminals,
         })
     }
+
+    /// Uploads local repositories as a git bundle; the runner clones URLs itself.
+    async fn provision_workspace(
+        &self,
+        request: WorkspaceProvision,
+    ) -> Result<ProvisionedWorkspace> {
+        let mut client = self.get_client().await?;
+        let header = runner_proto::ProvisionWorkspaceRequest {
+            workspace_id: request.workspace_id.clone(),
+            branch: request.branch.clone(),
+            ..Default::default()
+        };
+        let response = match request.source {
+            WorkspaceSource::RemoteUrl(remote_url) => {
+                let header = runner_proto::ProvisionWorkspaceRequest {
+                    remote_url,
+                    ..header
+                };
+                client
+                    .provision_workspace(tokio_stream::once(header))
+                    .await?
+            }
+            WorkspaceSource::LocalRepo(repo) => {
+                let bundle = tempfile::NamedTempFile::new()?.into_temp_path();
+                workspace_sync::create_bundle(&repo, &request.branch, &bundle).await?;
+                let file = tokio::fs::File::open(&bundle).await?;
+                let workspace_id = request.workspace_id.clone();
+                let chunks = tokio_util::io::ReaderStream::with_capacity(file, BUNDLE_CHUNK_SIZE)
+                    .map_while(move |chunk| {
+                        chunk
+                            .inspect_err(|e| {
+                                tracing::warn!(
+                                    workspace_id = %workspace_id,
+                                    error = %e,
+                                    "Failed to read workspace bundle"
+                                );
+                            })
+                            .ok()
+                    })
+                    .map(|chunk| runner_proto::ProvisionWorkspaceRequest {
+                        bundle_chunk: chunk.to_vec(),
+                        ..Default::default()
+                    });
+                client
+                    .provision_workspace(tokio_stream::once(header).chain(chunks))
+                    .await?
+            }
+        }
+        .into_inner();
+
+        tracing::info!(
+            workspace_id = %request.workspace_id,
+            addr = %self.addr,
+            working_dir = %response.working_dir,
+            "Workspace provisioned on remote runner"
+        );
+        Ok(ProvisionedWorkspace {
+            working_dir: response.working_dir,
+            head_commit: response.head_commit,
+        })
+    }
+
+    async fn sync_workspace(
+        &self,
+        workspace_id: &str,
+        since_commit: &str,
+        local_worktree: &Path,
+    ) -> Result<WorkspaceSync> {
+        let mut client = self.get_client().await?;
+        let request = tonic::Request::new(runner_proto::FetchWorkspaceChangesRequest {
+            workspace_id: workspace_id.to_string(),
+            since_commit: since_commit.to_string(),
+        });
+        let mut stream = client.fetch_workspace_changes(request).await?.into_inner();
+        let summary = stream
+            .message()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Runner sent no changes for {workspace_id}"))?;
+
+        let bundle = tempfile::NamedTempFile::new()?.into_temp_path();
+        let mut file = tokio::fs::File::create(&bundle).await?;
+        file.write_all(&summary.bundle_chunk).await?;
+        while let Some(chunk) = stream.message().await? {
+            file.write_all(&chunk.bundle_chunk).await?;
+        }
+        file.flush().await?;
+
+        let sync = WorkspaceSync {
+            head_commit: summary.head_commit,
+            commits: summary.commits,
+            patch: summary.patch,
+        };
+        if !sync.commits.is_empty() {
+            workspace_sync::import_bundle(local_worktree, &bundle, &sync.head_commit).await?;
+            tracing::info!(
+                workspace_id,
+                commits = sync.commits.len(),
+                "Imported commits from remote runner"
+            );
+        }
+        Ok(sync)
+    }
+
+    async fn release_workspace(&self, workspace_id: &str) -> Result<()> {
+        let mut client = self.get_client().await?;
+        let request = tonic::Request::new(runner_proto::ReleaseWorkspaceRequest {
+            workspace_id: workspace_id.to_string(),
+        });
+        let response = client.release_workspace(request).await?.into_inner();
+        if !response.success {
+            bail!("Remote release_workspace failed for {workspace_id}");
+        }
+        Ok(())
+    }
 }
 
 // ============================================================================
 // RunnerClientImpl (Unified Enum)
 // ============================================================================
 
-/// Unified runner client that dispatches to either a local or remote implementation.
+/// Unified runner client that dispatches to a local runner, a single remote
+/// runner, or a registry that schedules terminals across several runners.
 #[derive(Debug, Clone)]
 pub enum RunnerClientImpl {
     Local(LocalRunner),
     Remote(RemoteRunner),
+    Pool(RunnerRegistry),
 }
 
 /// Shared reference alias for `RunnerClientImpl`.
@@ -332,6 +750,7 @@ impl RunnerClient for RunnerClientImpl {
         match self {
             Self::Local(inner) => inner.spawn_terminal(config).await,
             Self::Remote(inner) => inner.spawn_terminal(config).await,
+            Self::Pool(inner) => inner.spawn_terminal(config).await,
         }
     }
 
@@ -339,6 +758,7 @@ impl RunnerClient for RunnerClientImpl {
         match self {
             Self::Local(inner) => inner.kill_terminal(terminal_id).await,
             Self::Remote(inner) => inner.kill_terminal(terminal_id).await,
+            Self::Pool(inner) => inner.kill_terminal(terminal_id).await,
         }
     }
 
@@ -346,6 +766,7 @@ impl RunnerClient for RunnerClientImpl {
         match self {
             Self::Local(inner) => inner.is_running(terminal_id).await,
             Self::Remote(inner) => inner.is_running(terminal_id).await,
+            Self::Pool(inner) => inner.is_running(terminal_id).await,
         }
     }
 
@@ -353,6 +774,7 @@ impl RunnerClient for RunnerClientImpl {
         match self {
             Self::Local(inner) => inner.write_input(terminal_id, data).await,
             Self::Remote(inner) => inner.write_input(terminal_id, data).await,
+            Self::Pool(inner) => inner.write_input(terminal_id, data).await,
         }
     }
 
@@ -360,6 +782,15 @@ impl RunnerClient for RunnerClientImpl {
         match self {
             Self::Local(inner) => inner.resize_terminal(terminal_id, cols, rows).await,
             Self::Remote(inner) => inner.resize_terminal(terminal_id, cols, rows).await,
+            Self::Pool(inner) => inner.resize_terminal(terminal_id, cols, rows).await,
+        }
+    }
+
+    async fn stream_output(&self, terminal_id: &str, from_seq: u64) -> Result<RunnerOutputStream> {
+        match self {
+            Self::Local(inner) => inner.stream_output(terminal_id, from_seq).await,
+            Self::Remote(inner) => inner.stream_output(terminal_id, from_seq).await,
+            Self::Pool(inner) => inner.stream_output(terminal_id, from_seq).await,
         }
     }
 
@@ -367,25 +798,83 @@ impl RunnerClient for RunnerClientImpl {
         match self {
             Self::Local(inner) => inner.health_check().await,
             Self::Remote(inner) => inner.health_check().await,
+            Self::Pool(inner) => inner.health_check().await,
+        }
+    }
+
+    async fn provision_workspace(
+        &self,
+        request: WorkspaceProvision,
+    ) -> Result<ProvisionedWorkspace> {
+        match self {
+            Self::Local(inner) => inner.provision_workspace(request).await,
+            Self::Remote(inner) => inner.provision_workspace(request).await,
+            Self::Pool(inner) => inner.provision_workspace(request).await,
+        }
+    }
+
+    async fn sync_workspace(
+        &self,
+        workspace_id: &str,
+        since_commit: &str,
+        local_worktree: &Path,
+    ) -> Result<WorkspaceSync> {
+        match self {
+            Self::Local(inner) => {
+                inner
+                    .sync_workspace(workspace_id, since_commit, local_worktree)
+                    .await
+            }
+            Self::Remote(inner) => {
+                inner
+                    .sync_workspace(workspace_id, since_commit, local_worktree)
+                    .await
+            }
+            Self::Pool(inner) => {
+                inner
+                    .sync_workspace(workspace_id, since_commit, local_worktree)
+                    .await
+            }
+        }
+    }
+
+    async fn release_workspace(&self, workspace_id: &str) -> Result<()> {
+        match self {
+            Self::Local(inner) => inner.release_workspace(workspace_id).await,
+            Self::Remote(inner) => inner.release_workspace(workspace_id).await,
+            Self::Pool(inner) => inner.release_workspace(workspace_id).await,
         }
     }
 }
 
 impl RunnerClientImpl {
-    /// Creates a `RunnerClientImpl` based on environment variables.
+    /// Creates the remote runner client configured in the environment, or
+    /// `None` in local mode.
     ///
     /// - `SOLODAWN_RUNNER_MODE`: `"local"` (default) or `"remote"`
-    /// - `SOLODAWN_RUNNER_ADDR`: remote runner address (required when mode is `"remote"`)
-    pub fn from_env(process_manager: Arc<ProcessManager>) -> Result<Self> {
+    /// - `SOLODAWN_RUNNERS`: JSON list of labelled runners to schedule across in remote
+    ///   mode (see [`RunnerRegistry::from_json`])
+    /// - `SOLODAWN_RUNNER_ADDR`: single remote runner address when `SOLODAWN_RUNNERS` is unset
+    ///
+    /// Remote runners are reached with the credentials from [`RunnerAuth::from_env`].
+    pub fn remote_from_env() -> Result<Option<Self>> {
         let mode = utils::env_compat::var_with_compat("SOLODAWN_RUNNER_MODE", "GITCORTEX_RUNNER_MODE")
             .unwrap_or_else(|_| "local".to_string());
 
         match mode.to_lowercase().as_str() {
-            "local" => {
-                tracing::info!("Runner mode: local (in-process ProcessManager)");
-                Ok(Self::Local(LocalRunner::new(process_manager)))
-            }
+            "local" => Ok(None),
             "remote" => {
+                let auth = RunnerAuth::from_env()?;
+                if let Some(runners) =
+                    utils::env_compat::var_opt_with_compat("SOLODAWN_RUNNERS", "GITCORTEX_RUNNERS")
+                {
+                    let registry = RunnerRegistry::from_json(&runners, &auth)?;
+                    tracing::info!(
+                        runners = ?registry.runner_names(),
+                        "Runner mode: remote (scheduled across runners)"
+                    );
+                    return Ok(Some(Self::Pool(registry)));
+                }
                 let addr = utils::env_compat::var_with_compat("SOLODAWN_RUNNER_ADDR", "GITCORTEX_RUNNER_ADDR").unwrap_or_else(|_| {
                     tracing::warn!(
                         "SOLODAWN_RUNNER_ADDR not set, defaulting to http://runner:50051"
@@ -393,7 +882,7 @@ impl RunnerClientImpl {
                     "http://runner:50051".to_string()
                 });
                 tracing::info!(addr = %addr, "Runner mode: remote (gRPC)");
-                Ok(Self::Remote(RemoteRunner::new(addr)))
+                Ok(Some(Self::Remote(RemoteRunner::new(addr).with_auth(auth))))
             }
             other => {
                 bail!(
@@ -0,0 +1,766 @@
+//! Remote runner registry and terminal scheduling
+//!
+//! Keeps the configured remote runners with their labels and places each new
+//! terminal on a healthy runner carrying every requested label, preferring the
+//! one with the fewest active terminals (`HealthResponse.active_terminals` plus
+//! spawns still in flight). Later calls for a terminal go to the runner it was
+//! placed on. Workspaces are placed the same way, and terminals spawned in a
+//! workspace run on the runner holding it.
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::sync::{Mutex, RwLock};
+
+use super::{
+    runner_client::{
+        RemoteRunner, RunnerAuth, RunnerClient, RunnerHealth, RunnerOutputStream, SpawnResult,
+        TerminalSpawnConfig,
+    },
+    workspace_sync::{ProvisionedWorkspace, WorkspaceProvision, WorkspaceSync},
+};
+
+/// How long a runner may take to answer a health probe before it is skipped
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// One entry of `SOLODAWN_RUNNERS`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RunnerSpec {
+    pub name: String,
+    /// gRPC address, e.g. `https://gpu-box:50051`
+    pub addr: String,
+    /// Capabilities terminals can ask for, e.g. `has-gpu`, `has-node20`
+    #[serde(default)]
+    pub labels: BTreeSet<String>,
+    /// Stop placing terminals on the runner once it reports this many
+    pub max_terminals: Option<u32>,
+}
+
+/// Load of one candidate runner at placement time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunnerLoad {
+    pub healthy: bool,
+    pub active_terminals: u32,
+    pub max_terminals: Option<u32>,
+}
+
+/// Index of the least loaded healthy runner with spare capacity; ties go to
+/// the runner listed first.
+pub fn select_runner(loads: &[RunnerLoad]) -> Option<usize> {
+    loads
+        .iter()
+        .enumerate()
+        .filter(|(_, load)| {
+            load.healthy
+                && load
+                    .max_terminals
+                    .is_none_or(|max| load.active_terminals < max)
+        })
+        .min_by_key(|(_, load)| load.active_terminals)
+        .map(|(index, _)| index)
+}
+
+struct RegisteredRunner {
+    spec: RunnerSpec,
+    client: RemoteRunner,
+    /// Spawns placed on this runner that have not completed yet
+    in_flight: AtomicU32,
+}
+
+impl RegisteredRunner {
+    fn has_labels(&self, required: &BTreeSet<&str>) -> bool {
+        required
+            .iter()
+            .all(|label| self.spec.labels.contains(*label))
+    }
+
+    async fn probe(&self) -> RunnerLoad {
+        let in_flight = self.in_flight.load(Ordering::Relaxed);
+        let health = tokio::time::timeout(HEALTH_PROBE_TIMEOUT, self.client.health_check()).await;
+        let (healthy, active_terminals) = match health {
+            Ok(Ok(health)) => (health.healthy, health.active_terminals),
+            Ok(Err(e)) => {
+                tracing::warn!(runner = %self.spec.name, error = %e, "Runner health probe failed");
+                (false, 0)
+            }
+            Err(_) => {
+                tracing::warn!(runner = %self.spec.name, "Runner health probe timed out");
+                (false, 0)
+            }
+        };
+        RunnerLoad {
+            healthy,
+            active_terminals: active_terminals.saturating_add(in_flight),
+            max_terminals: self.spec.max_terminals,
+        }
+    }
+}
+
+/// Counts a spawn against its runner until dropped
+struct InFlight(Arc<RegisteredRunner>);
+
+impl InFlight {
+    fn new(runner: Arc<RegisteredRunner>) -> Self {
+        runner.in_flight.fetch_add(1, Ordering::Relaxed);
+        Self(runner)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+struct RegistryInner {
+    runners: Vec<Arc<RegisteredRunner>>,
+    /// terminal_id -> runner the terminal was placed on
+    placements: RwLock<HashMap<String, Arc<RegisteredRunner>>>,
+    /// workspace_id -> runner holding the workspace
+    workspaces: RwLock<HashMap<String, Arc<RegisteredRunner>>>,
+    /// Serializes probe + select so concurrent spawns see each other's load
+    placement_lock: Mutex<()>,
+}
+
+/// Registry of labelled remote runners that schedules terminals across them.
+#[derive(Clone)]
+pub struct RunnerRegistry {
+    inner: Arc<RegistryInner>,
+}
+
+impl std::fmt::Debug for RunnerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunnerRegistry")
+            .field("runners", &self.runner_names())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RunnerRegistry {
+    /// Registry over `specs`, all reached with `auth`.
+    pub fn new(specs: Vec<RunnerSpec>, auth: &RunnerAuth) -> Result<Self> {
+        if specs.is_empty() {
+            bail!("At least one runner must be configured");
+        }
+        let mut names = HashSet::new();
+        for spec in &specs {
+            if spec.name.trim().is_empty() || spec.addr.trim().is_empty() {
+                bail!("Runner entries need a non-empty name and addr");
+            }
+            if !names.insert(spec.name.as_str()) {
+                bail!("Duplicate runner name '{}'", spec.name);
+            }
+        }
+
+        let runners = specs
+            .into_iter()
+            .map(|spec| {
+                Arc::new(RegisteredRunner {
+                    client: RemoteRunner::new(spec.addr.clone()).with_auth(auth.clone()),
+                    spec,
+                    in_flight: AtomicU32::new(0),
+                })
+            })
+            .collect();
+        Ok(Self {
+            inner: Arc::new(RegistryInner {
+                runners,
+                placements: RwLock::new(HashMap::new()),
+                workspaces: RwLock::new(HashMap::new()),
+                placement_lock: Mutex::new(()),
+            }),
+        })
+    }
+
+    /// Registry from a JSON list such as
+    /// `[{"name": "gpu", "addr": "https://gpu:50051", "labels": ["has-gpu"], "maxTerminals": 4}]`.
+    pub fn from_json(json: &str, auth: &RunnerAuth) -> Result<Self> {
+        let specs: Vec<RunnerSpec> =
+            serde_json::from_str(json).context("Invalid SOLODAWN_RUNNERS runner list")?;
+        Self::new(specs, auth)
+    }
+
+    pub fn runner_names(&self) -> Vec<String> {
+        self.inner
+            .runners
+            .iter()
+            .map(|runner| runner.spec.name.clone())
+            .collect()
+    }
+
+    /// Name of the runner `terminal_id` was placed on.
+    pub async fn placement(&self, terminal_id: &str) -> Option<String> {
+        self.inner
+            .placements
+            .read()
+            .await
+            .get(terminal_id)
+            .map(|runner| runner.spec.name.clone())
+    }
+
+    async fn runner_for(&self, terminal_id: &str) -> Result<Arc<RegisteredRunner>> {
+        self.inner
+            .placements
+            .read()
+            .await
+            .get(terminal_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Terminal {terminal_id} is not placed on any runner"))
+    }
+
+    async fn runner_for_workspace(&self, workspace_id: &str) -> Result<Arc<RegisteredRunner>> {
+        self.inner
+            .workspaces
+            .read()
+            .await
+            .get(workspace_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Workspace {workspace_id} is not provisioned"))
+    }
+
+    /// Choose a runner for a terminal requiring `labels` and count the spawn
+    /// against it.
+    async fn place(&self, labels: &[String]) -> Result<InFlight> {
+        let required: BTreeSet<&str> = labels.iter().map(String::as_str).collect();
+        let candidates: Vec<_> = self
+            .inner
+            .runners
+            .iter()
+            .filter(|runner| runner.has_labels(&required))
+            .cloned()
+            .collect();
+        if candidates.is_empty() {
+            bail!("No runner has labels {required:?}");
+        }
+
+        let _guard = self.inner.placement_lock.lock().await;
+        let loads = futures::future::join_all(candidates.iter().map(|runner| runner.probe())).await;
+        let Some(index) = select_runner(&loads) else {
+            let names: Vec<&str> = candidates
+                .iter()
+                .map(|runner| runner.spec.name.as_str())
+                .collect();
+            bail!("No healthy runner with free capacity among {names:?}");
+        };
+        tracing::debug!(
+            runner = %candidates[index].spec.name,
+            load = loads[index].active_terminals,
+            "Selected runner"
+        );
+        Ok(InFlight::new(Arc::clone(&candidates[index])))
+    }
+}
+
+#[async_trait]
+impl RunnerClient for RunnerRegistry {
+    async fn spawn_terminal(&self, config: TerminalSpawnConfig) -> Result<SpawnResult> {
+        let in_flight = match &config.workspace_id {
+            Some(workspace_id) => InFlight::new(self.runner_for_workspace(workspace_id).await?),
+            None => self.place(&config.runner_labels).await?,
+        };
+        let runner = Arc::clone(&in_flight.0);
+        let terminal_id = config.terminal_id.clone();
+
+        let result = runner.client.spawn_terminal(config).await?;
+        self.inner
+            .placements
+            .write()
+            .await
+            .insert(terminal_id.clone(), Arc::clone(&runner));
+        drop(in_flight);
+
+        tracing::info!(
+            terminal_id = %terminal_id,
+            runner = %runner.spec.name,
+            pid = result.pid,
+            "Terminal placed on remote runner"
+        );
+        Ok(result)
+    }
+
+    async fn kill_terminal(&self, terminal_id: &str) -> Result<()> {
+        let runner = self.runner_for(terminal_id).await?;
+        runner.client.kill_terminal(terminal_id).await?;
+        self.inner.placements.write().await.remove(terminal_id);
+        Ok(())
+    }
+
+    async fn is_running(&self, terminal_id: &str) -> Result<bool> {
+        let Ok(runner) = self.runner_for(terminal_id).await else {
+            return Ok(false);
+        };
+        runner.client.is_running(terminal_id).await
+    }
+
+    async fn write_input(&self, terminal_id: &str, data: &[u8]) -> Result<()> {
+        self.runner_for(terminal_id)
+            .await?
+            .client
+            .write_input(terminal_id, data)
+            .await
+    }
+
+    async fn resize_terminal(&self, terminal_id: &str, cols: u32, rows: u32) -> Result<()> {
+        self.runner_for(terminal_id)
+            .await?
+            .client
+            .resize_terminal(terminal_id, cols, rows)
+            .await
+    }
+
+    async fn stream_output(&self, terminal_id: &str, from_seq: u64) -> Result<RunnerOutputStream> {
+        self.runner_for(terminal_id)
+            .await?
+            .client
+            .stream_output(terminal_id, from_seq)
+            .await
+    }
+
+    /// Healthy while any runner is; counts terminals across healthy runners.
+    async fn health_check(&self) -> Result<RunnerHealth> {
+        let loads =
+            futures::future::join_all(self.inner.runners.iter().map(|runner| runner.probe())).await;
+        Ok(RunnerHealth {
+            healthy: loads.iter().any(|load| load.healthy),
+            active_terminals: loads
+                .iter()
+                .filter(|load| load.healthy)
+                .map(|load| load.active_terminals)
+                .sum(),
+        })
+    }
+
+    /// Re-provisioning a known workspace goes back to the runner holding it.
+    async fn provision_workspace(
+        &self,
+        request: WorkspaceProvision,
+    ) -> Result<ProvisionedWorkspace> {
+        let workspace_id = request.workspace_id.clone();
+        let runner = match self.runner_for_workspace(&workspace_id).await {
+            Ok(runner) => runner,
+            Err(_) => Arc::clone(&self.place(&request.runner_labels).await?.0),
+        };
+        let provisioned = runner.client.provision_workspace(request).await?;
+        self.inner
+            .workspaces
+            .write()
+            .await
+            .insert(workspace_id.clone(), Arc::clone(&runner));
+
+        tracing::info!(
+            workspace_id = %workspace_id,
+            runner = %runner.spec.name,
+            "Workspace placed on remote runner"
+        );
+        Ok(provisioned)
+    }
+
+    async fn sync_workspace(
+        &self,
+        workspace_id: &str,
+        since_commit: &str,
+        local_worktree: &Path,
+    ) -> Result<WorkspaceSync> {
+        self.runner_for_workspace(workspace_id)
+            .await?
+            .client
+            .sync_workspace(workspace_id, since_commit, local_worktree)
+            .await
+    }
+
+    async fn release_workspace(&self, workspace_id: &str) -> Result<()> {
+        let runner = self.runner_for_workspace(workspace_id).await?;
+        runner.client.release_workspace(workspace_id).await?;
+        self.inner.workspaces.write().await.remove(workspace_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+
+    use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+    use tonic::{Request, Response, Status, transport::Server};
+
+    use super::*;
+    use crate::services::{
+        runner_client::runner_proto::{
+            self,
+            runner_service_server::{RunnerService, RunnerServiceServer},
+        },
+        workspace_sync::WorkspaceSource,
+    };
+
+    fn load(healthy: bool, active_terminals: u32, max_terminals: Option<u32>) -> RunnerLoad {
+        RunnerLoad {
+            healthy,
+            active_terminals,
+            max_terminals,
+        }
+    }
+
+    #[test]
+    fn select_runner_prefers_least_loaded_healthy_runner_with_capacity() {
+        assert_eq!(select_runner(&[]), None);
+        assert_eq!(
+            select_runner(&[
+                load(true, 4, None),
+                load(true, 2, None),
+                load(true, 2, None)
+            ]),
+            Some(1)
+        );
+        assert_eq!(
+            select_runner(&[
+                load(false, 0, None),
+                load(true, 3, Some(3)),
+                load(true, 5, None)
+            ]),
+            Some(2)
+        );
+        assert_eq!(
+            select_runner(&[load(false, 0, None), load(true, 1, Some(1))]),
+            None
+        );
+    }
+
+    #[test]
+    fn registry_rejects_invalid_runner_lists() {
+        let auth = RunnerAuth::default();
+        assert!(RunnerRegistry::from_json("[]", &auth).is_err());
+        assert!(RunnerRegistry::from_json(r#"[{"name": "a"}]"#, &auth).is_err());
+        assert!(
+            RunnerRegistry::from_json(
+                r#"[{"name": "a", "addr": "http://a:1"}, {"name": "a", "addr": "http://b:1"}]"#,
+                &auth
+            )
+            .is_err()
+        );
+        let registry = RunnerRegistry::from_json(
+            r#"[{"name": "gpu", "addr": "http://gpu:50051", "labels": ["has-gpu"], "maxTerminals": 2}]"#,
+            &auth,
+        )
+        .unwrap();
+        assert_eq!(registry.runner_names(), vec!["gpu".to_string()]);
+    }
+
+    /// Runner stub reporting a fixed load and recording spawned terminals
+    #[derive(Default)]
+    struct FakeRunner {
+        active_terminals: u32,
+        token: Option<String>,
+        spawned: Arc<StdMutex<Vec<String>>>,
+    }
+
+    impl FakeRunner {
+        fn authorized<T>(&self, request: &Request<T>) -> bool {
+            let Some(token) = &self.token else {
+                return true;
+            };
+            let expected = format!("Bearer {token}");
+            request
+                .metadata()
+                .get("authorization")
+                .is_some_and(|value| value.to_str().ok() == Some(expected.as_str()))
+        }
+    }
+
+    #[tonic::async_trait]
+    impl RunnerService for FakeRunner {
+        type StreamOutputStream = ReceiverStream<Result<runner_proto::TerminalOutputChunk, Status>>;
+        type FetchWorkspaceChangesStream =
+            ReceiverStream<Result<runner_proto::WorkspaceChangesChunk, Status>>;
+
+        async fn spawn_terminal(
+            &self,
+            request: Request<runner_proto::SpawnTerminalRequest>,
+        ) -> Result<Response<runner_proto::SpawnTerminalResponse>, Status> {
+            if !self.authorized(&request) {
+                return Err(Status::unauthenticated("bad token"));
+            }
+            self.spawned
+                .lock()
+                .unwrap()
+                .push(request.into_inner().terminal_id);
+            Ok(Response::new(runner_proto::SpawnTerminalResponse {
+                success: true,
+                error: String::new(),
+                pid: 42,
+            }))
+        }
+
+        async fn kill_terminal(
+            &self,
+            _request: Request<runner_proto::KillTerminalRequest>,
+        ) -> Result<Response<runner_proto::KillTerminalResponse>, Status> {
+            Ok(Response::new(runner_proto::KillTerminalResponse {
+                success: true,
+            }))
+        }
+
+        async fn is_running(
+            &self,
+            request: Request<runner_proto::IsRunningRequest>,
+        ) -> Result<Response<runner_proto::IsRunningResponse>, Status> {
+            let terminal_id = request.into_inner().terminal_id;
+            Ok(Response::new(runner_proto::IsRunningResponse {
+                running: self.spawned.lock().unwrap().contains(&terminal_id),
+            }))
+        }
+
+        async fn resize_terminal(
+            &self,
+            _request: Request<runner_proto::ResizeRequest>,
+        ) -> Result<Response<runner_proto::ResizeResponse>, Status> {
+            Err(Status::unimplemented("resize"))
+        }
+
+        async fn write_input(
+            &self,
+            _request: Request<runner_proto::WriteInputRequest>,
+        ) -> Result<Response<runner_proto::WriteInputResponse>, Status> {
+            Err(Status::unimplemented("write_input"))
+        }
+
+        async fn stream_output(
+            &self,
+            _request: Request<runner_proto::StreamOutputRequest>,
+        ) -> Result<Response<Self::StreamOutputStream>, Status> {
+            Err(Status::unimplemented("stream_output"))
+        }
+
+        async fn health(
+            &self,
+            request: Request<runner_proto::HealthRequest>,
+        ) -> Result<Response<runner_proto::HealthResponse>, Status> {
+            if !self.authorized(&request) {
+                return Err(Status::unauthenticated("bad token"));
+            }
+            Ok(Response::new(runner_proto::HealthResponse {
+                healthy: true,
+                active_terminals: self.active_terminals,
+            }))
+        }
+
+        async fn provision_workspace(
+            &self,
+            request: Request<tonic::Streaming<runner_proto::ProvisionWorkspaceRequest>>,
+        ) -> Result<Response<runner_proto::ProvisionWorkspaceResponse>, Status> {
+            let mut stream = request.into_inner();
+            let header = stream.message().await?.unwrap_or_default();
+            while stream.message().await?.is_some() {}
+            Ok(Response::new(runner_proto::ProvisionWorkspaceResponse {
+                working_dir: format!("/workspaces/{}", header.workspace_id),
+                head_commit: "abc123".to_string(),
+            }))
+        }
+
+        async fn fetch_workspace_changes(
+            &self,
+            _request: Request<runner_proto::FetchWorkspaceChangesRequest>,
+        ) -> Result<Response<Self::FetchWorkspaceChangesStream>, Status> {
+            Err(Status::unimplemented("fetch_workspace_changes"))
+        }
+
+        async fn release_workspace(
+            &self,
+            _request: Request<runner_proto::ReleaseWorkspaceRequest>,
+        ) -> Result<Response<runner_proto::ReleaseWorkspaceResponse>, Status> {
+            Ok(Response::new(runner_proto::ReleaseWorkspaceResponse {
+                success: true,
+            }))
+        }
+    }
+
+    async fn serve(runner: FakeRunner) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(RunnerServiceServer::new(runner))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        format!("http://{addr}")
+    }
+
+    fn spawn_config(terminal_id: &str, labels: &[&str]) -> TerminalSpawnConfig {
+        TerminalSpawnConfig {
+            terminal_id: terminal_id.to_string(),
+            command: "bash".to_string(),
+            args: Vec::new(),
+            working_dir: "/tmp".to_string(),
+            env_set: HashMap::new(),
+            env_unset: Vec::new(),
+            cols: 80,
+            rows: 24,
+            runner_labels: labels.iter().map(ToString::to_string).collect(),
+            workspace_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn registry_places_terminals_by_label_and_load() {
+        let busy_cpu = Arc::new(StdMutex::new(Vec::new()));
+        let idle_cpu = Arc::new(StdMutex::new(Vec::new()));
+        let gpu = Arc::new(StdMutex::new(Vec::new()));
+        let token = Some("s3cret".to_string());
+        let spec = |name: &str, addr: String, labels: &[&str]| RunnerSpec {
+            name: name.to_string(),
+            addr,
+            labels: labels.iter().map(ToString::to_string).collect(),
+            max_terminals: None,
+        };
+        let specs = vec![
+            spec(
+                "busy-cpu",
+                serve(FakeRunner {
+                    active_terminals: 5,
+                    token: token.clone(),
+                    spawned: Arc::clone(&busy_cpu),
+                })
+                .await,
+                &["linux"],
+            ),
+            spec(
+                "idle-cpu",
+                serve(FakeRunner {
+                    active_terminals: 1,
+                    token: token.clone(),
+                    spawned: Arc::clone(&idle_cpu),
+                })
+                .await,
+                &["linux"],
+            ),
+            spec(
+                "gpu",
+                serve(FakeRunner {
+                    active_terminals: 9,
+                    token: token.clone(),
+                    spawned: Arc::clone(&gpu),
+                })
+                .await,
+                &["linux", "has-gpu"],
+            ),
+        ];
+        let auth = RunnerAuth { token, tls: None };
+        let registry = RunnerRegistry::new(specs, &auth).unwrap();
+
+        registry
+            .spawn_terminal(spawn_config("t-any", &["linux"]))
+            .await
+            .unwrap();
+        registry
+            .spawn_terminal(spawn_config("t-gpu", &["has-gpu"]))
+            .await
+            .unwrap();
+        assert_eq!(*idle_cpu.lock().unwrap(), vec!["t-any".to_string()]);
+        assert_eq!(*gpu.lock().unwrap(), vec!["t-gpu".to_string()]);
+        assert!(busy_cpu.lock().unwrap().is_empty());
+        assert_eq!(registry.placement("t-gpu").await.as_deref(), Some("gpu"));
+        assert!(registry.is_running("t-gpu").await.unwrap());
+        assert!(!registry.is_running("t-unknown").await.unwrap());
+
+        let err = registry
+            .spawn_terminal(spawn_config("t-node", &["has-node20"]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No runner has labels"));
+
+        registry.kill_terminal("t-gpu").await.unwrap();
+        assert_eq!(registry.placement("t-gpu").await, None);
+
+        let health = registry.health_check().await.unwrap();
+        assert!(health.healthy);
+        assert_eq!(health.active_terminals, 15);
+
+        // Without the token every runner rejects the probe
+        let unauthenticated = RunnerRegistry::new(
+            vec![spec(
+                "gpu",
+                registry.inner.runners[2].spec.addr.clone(),
+                &[],
+            )],
+            &RunnerAuth::default(),
+        )
+        .unwrap();
+        let err = unauthenticated
+            .spawn_terminal(spawn_config("t-x", &[]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No healthy runner"));
+    }
+
+    #[tokio::test]
+    async fn terminals_follow_their_workspace_runner() {
+        let gpu = Arc::new(StdMutex::new(Vec::new()));
+        let cpu = Arc::new(StdMutex::new(Vec::new()));
+        let specs = vec![
+            RunnerSpec {
+                name: "gpu".to_string(),
+                addr: serve(FakeRunner {
+                    active_terminals: 7,
+                    spawned: Arc::clone(&gpu),
+                    ..Default::default()
+                })
+                .await,
+                labels: ["has-gpu".to_string()].into(),
+                max_terminals: None,
+            },
+            RunnerSpec {
+                name: "cpu".to_string(),
+                addr: serve(FakeRunner {
+                    spawned: Arc::clone(&cpu),
+                    ..Default::default()
+                })
+                .await,
+                labels: BTreeSet::new(),
+                max_terminals: None,
+            },
+        ];
+        let registry = RunnerRegistry::new(specs, &RunnerAuth::default()).unwrap();
+
+        let provisioned = registry
+            .provision_workspace(WorkspaceProvision {
+                workspace_id: "ws-1".to_string(),
+                source: WorkspaceSource::RemoteUrl("https://example.com/repo.git".to_string()),
+                branch: "main".to_string(),
+                runner_labels: vec!["has-gpu".to_string()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(provisioned.working_dir, "/workspaces/ws-1");
+
+        // The idle cpu runner would win on load, but the workspace lives on gpu
+        registry
+            .spawn_terminal(TerminalSpawnConfig {
+                workspace_id: Some("ws-1".to_string()),
+                ..spawn_config("t-1", &[])
+            })
+            .await
+            .unwrap();
+        assert_eq!(*gpu.lock().unwrap(), vec!["t-1".to_string()]);
+        assert!(cpu.lock().unwrap().is_empty());
+
+        registry.release_workspace("ws-1").await.unwrap();
+        let err = registry
+            .spawn_terminal(TerminalSpawnConfig {
+                workspace_id: Some("ws-1".to_string()),
+                ..spawn_config("t-2", &[])
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not provisioned"));
+    }
+}
@@ -0,0 +1,685 @@
+//! Terminal Activity Monitor
+//!
+//! Watches the working terminals of running orchestrated workflows for stalls
+//! (no meaningful output or commits for a while) and loops (the same tool call
+//! repeated). Output activity comes from `OutputFanout` sequence numbers, commit
+//! activity from git watcher events on the message bus.
+//!
+//! Each stall or loop moves a terminal one step up the escalation ladder —
+//! nudge → orchestrator notification → restart → fail — with at least the
+//! workflow's escalation interval between steps. Only a commit resets the
+//! ladder; a terminal that keeps stalling without committing ends up failed.
+//! The orchestrator agent carries out each step.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use db::DBService;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tokio::{
+    sync::{Mutex, broadcast::error::RecvError},
+    task::JoinHandle,
+    time::Instant,
+};
+
+use crate::services::{
+    orchestrator::{
+        message_bus::{BusMessage, SharedMessageBus},
+        types::{ActivityEscalationLevel, ActivityStallReason, TerminalActivityEvent},
+    },
+    terminal::{process::ProcessManager, prompt_detector::normalize_text_for_detection},
+};
+
+/// How often terminals are re-discovered and evaluated
+const ACTIVITY_MONITOR_TICK: Duration = Duration::from_secs(15);
+
+/// Default idle time before a terminal counts as stalled
+pub const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Default number of identical tool calls in the recent window that count as a loop
+pub const DEFAULT_LOOP_THRESHOLD: usize = 6;
+
+/// Default minimum time between two escalation steps
+pub const DEFAULT_ESCALATION_INTERVAL: Duration = Duration::from_secs(3 * 60);
+
+/// Number of recent tool calls considered for loop detection
+const LOOP_WINDOW: usize = 20;
+
+/// TUIs redraw the running tool call; sightings closer than this are one call
+const TOOL_CALL_REDRAW_WINDOW: Duration = Duration::from_secs(2);
+
+/// Ladder state of a terminal that stopped working (e.g. while restarting)
+/// is kept this long
+const DORMANT_RETENTION: Duration = Duration::from_secs(15 * 60);
+
+/// Tool call lines: Claude Code `⏺ Bash(npm test)`, Codex `• Ran npm test`,
+/// Gemini `✓ Shell npm test`.
+static TOOL_CALL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:[●⏺]\s*(?P<claude>[A-Z][A-Za-z]+\([^)]{0,200}\))|^[•●]?\s*Ran\s+(?P<codex>\S.{0,200})$|^[✓✔]\s+(?P<gemini>[A-Z][A-Za-z]+\s+\S.{0,200})$)",
+    )
+    .expect("TOOL_CALL_RE must compile")
+});
+
+/// Spinner / status lines that change while an agent is stuck thinking
+static STATUS_LINE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:esc|ctrl\+c)\s+to\s+(?:interrupt|cancel)\b")
+        .expect("STATUS_LINE_RE must compile")
+});
+
+/// Activity thresholds of one workflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivityPolicy {
+    /// `None` disables stall detection
+    pub stall_timeout: Option<Duration>,
+    /// `None` disables loop detection
+    pub loop_threshold: Option<usize>,
+    pub escalation_interval: Duration,
+}
+
+impl Default for ActivityPolicy {
+    fn default() -> Self {
+        Self {
+            stall_timeout: Some(DEFAULT_STALL_TIMEOUT),
+            loop_threshold: Some(DEFAULT_LOOP_THRESHOLD),
+            escalation_interval: DEFAULT_ESCALATION_INTERVAL,
+        }
+    }
+}
+
+impl ActivityPolicy {
+    /// Policy from the workflow `activity_*` columns (NULL = default, 0 = disabled).
+    pub fn from_settings(
+        stall_timeout_secs: Option<i64>,
+        loop_threshold: Option<i64>,
+        escalation_interval_secs: Option<i64>,
+    ) -> Self {
+        let defaults = Self::default();
+        let secs = |value: i64| Duration::from_secs(u64::try_from(value).unwrap_or(0));
+        Self {
+            stall_timeout: stall_timeout_secs.map_or(defaults.stall_timeout, |value| {
+                (value > 0).then(|| secs(value))
+            }),
+            loop_threshold: loop_threshold.map_or(defaults.loop_threshold, |value| {
+                usize::try_from(value)
+                    .ok()
+                    .filter(|threshold| *threshold > 1)
+            }),
+            escalation_interval: escalation_interval_secs
+                .filter(|value| *value > 0)
+                .map_or(defaults.escalation_interval, secs),
+        }
+    }
+
+    pub fn from_workflow(workflow: &db::models::Workflow) -> Self {
+        Self::from_settings(
+            workflow.activity_stall_timeout_secs,
+            workflow.activity_loop_threshold,
+            workflow.activity_escalation_interval_secs,
+        )
+    }
+}
+
+/// Tool calls shown in a chunk of PTY output, deduplicated.
+pub fn extract_tool_calls(text: &str) -> Vec<String> {
+    let mut calls: Vec<String> = Vec::new();
+    for line in text.split(['\n', '\r']) {
+        let line = normalize_text_for_detection(line);
+        let Some(captures) = TOOL_CALL_RE.captures(line.trim()) else {
+            continue;
+        };
+        let Some(call) = ["claude", "codex", "gemini"]
+            .iter()
+            .find_map(|name| captures.name(name))
+        else {
+            continue;
+        };
+        let call = call
+            .as_str()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !calls.contains(&call) {
+            calls.push(call);
+        }
+    }
+    calls
+}
+
+/// Whether output shows progress, as opposed to spinners and status lines.
+fn is_meaningful_output(text: &str) -> bool {
+    text.split(['\n', '\r']).any(|line| {
+        let line = normalize_text_for_detection(line);
+        line.chars().filter(|c| c.is_alphabetic()).count() >= 3 && !STATUS_LINE_RE.is_match(&line)
+    })
+}
+
+/// Activity and escalation state of one terminal.
+#[derive(Debug)]
+pub struct TerminalActivity {
+    last_seq: u64,
+    last_activity_at: Instant,
+    recent_calls: VecDeque<String>,
+    last_call: Option<(String, Instant)>,
+    level: Option<ActivityEscalationLevel>,
+    last_escalation_at: Option<Instant>,
+}
+
+impl TerminalActivity {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_seq: 0,
+            last_activity_at: now,
+            recent_calls: VecDeque::new(),
+            last_call: None,
+            level: None,
+            last_escalation_at: None,
+        }
+    }
+
+    /// Last output sequence number seen.
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+
+    /// Last escalation step taken.
+    pub fn level(&self) -> Option<ActivityEscalationLevel> {
+        self.level
+    }
+
+    /// Output that was already on the fanout before monitoring started.
+    pub fn skip_output(&mut self, seq: u64) {
+        self.last_seq = self.last_seq.max(seq);
+    }
+
+    pub fn record_output(&mut self, seq: u64, text: &str, now: Instant) {
+        self.last_seq = self.last_seq.max(seq);
+        if is_meaningful_output(text) {
+            self.last_activity_at = now;
+        }
+        for call in extract_tool_calls(text) {
+            if let Some((last, seen_at)) = self.last_call.as_mut()
+                && *last == call
+                && now.duration_since(*seen_at) < TOOL_CALL_REDRAW_WINDOW
+            {
+                *seen_at = now;
+                continue;
+            }
+            self.last_call = Some((call.clone(), now));
+            self.recent_calls.push_back(call);
+            if self.recent_calls.len() > LOOP_WINDOW {
+                self.recent_calls.pop_front();
+            }
+        }
+    }
+
+    /// Output was produced but skipped (subscriber lagged).
+    pub fn record_lag(&mut self, now: Instant) {
+        self.last_activity_at = now;
+    }
+
+    /// A commit is progress: resets the ladder.
+    pub fn record_commit(&mut self, now: Instant) {
+        self.last_activity_at = now;
+        self.recent_calls.clear();
+        self.last_call = None;
+        self.level = None;
+        self.last_escalation_at = None;
+    }
+
+    /// Takes the next escalation step if the terminal is stalled or looping.
+    pub fn evaluate(
+        &mut self,
+        policy: &ActivityPolicy,
+        now: Instant,
+    ) -> Option<(ActivityEscalationLevel, ActivityStallReason)> {
+        let reason = self.stall_reason(policy, now)?;
+        if self
+            .last_escalation_at
+            .is_some_and(|at| now.duration_since(at) < policy.escalation_interval)
+        {
+            return None;
+        }
+        let level = ActivityEscalationLevel::next(self.level)?;
+        self.level = Some(level);
+        self.last_escalation_at = Some(now);
+        if matches!(reason, ActivityStallReason::Loop { .. }) {
+            self.recent_calls.clear();
+        }
+        Some((level, reason))
+    }
+
+    fn stall_reason(&self, policy: &ActivityPolicy, now: Instant) -> Option<ActivityStallReason> {
+        if let Some(threshold) = policy.loop_threshold {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for call in &self.recent_calls {
+                *counts.entry(call.as_str()).or_default() += 1;
+            }
+            if let Some((tool_call, repeats)) = counts
+                .into_iter()
+                .filter(|(_, repeats)| *repeats >= threshold)
+                .max_by_key(|(_, repeats)| *repeats)
+            {
+                return Some(ActivityStallReason::Loop {
+                    tool_call: tool_call.to_string(),
+                    repeats,
+                });
+            }
+        }
+
+        let idle = now.duration_since(self.last_activity_at);
+        policy
+            .stall_timeout
+            .filter(|timeout| idle >= *timeout)
+            .map(|_| ActivityStallReason::Idle {
+                idle_secs: idle.as_secs(),
+            })
+    }
+}
+
+/// Working terminal of a running orchestrated workflow
+#[derive(Debug, sqlx::FromRow)]
+struct WatchedTerminalRow {
+    terminal_id: String,
+    task_id: String,
+    workflow_id: String,
+    branch: String,
+    pty_session_id: Option<String>,
+    activity_stall_timeout_secs: Option<i64>,
+    activity_loop_threshold: Option<i64>,
+    activity_escalation_interval_secs: Option<i64>,
+}
+
+struct MonitoredTerminal {
+    workflow_id: String,
+    task_id: String,
+    branch: String,
+    session_id: String,
+    policy: ActivityPolicy,
+    activity: TerminalActivity,
+    reader: Option<JoinHandle<()>>,
+    /// Set while the terminal is not working
+    dormant_since: Option<Instant>,
+}
+
+impl MonitoredTerminal {
+    fn stop_reader(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
+    }
+}
+
+/// Background stall and loop monitor for agent terminals.
+pub struct ActivityMonitor {
+    message_bus: SharedMessageBus,
+    process_manager: Arc<ProcessManager>,
+    terminals: Arc<Mutex<HashMap<String, MonitoredTerminal>>>,
+    tick: Duration,
+}
+
+impl ActivityMonitor {
+    pub fn new(message_bus: SharedMessageBus, process_manager: Arc<ProcessManager>) -> Self {
+        Self {
+            message_bus,
+            process_manager,
+            terminals: Arc::new(Mutex::new(HashMap::new())),
+            tick: ACTIVITY_MONITOR_TICK,
+        }
+    }
+
+    /// Override the evaluation interval.
+    pub fn with_tick(mut self, tick: Duration) -> Self {
+        self.tick = tick;
+        self
+    }
+
+    /// Spawn the commit listener and the evaluation loop.
+    pub fn start(self: &Arc<Self>, db: Arc<DBService>) {
+        let monitor = Arc::clone(self);
+        tokio::spawn(async move {
+            monitor.commit_loop().await;
+        });
+
+        let monitor = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(monitor.tick);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = monitor.run_cycle(&db).await {
+                    tracing::warn!(error = %e, "Terminal activity monitor cycle failed");
+                }
+            }
+        });
+    }
+
+    /// Escalation level reached by a terminal, if any.
+    pub async fn escalation_level(&self, terminal_id: &str) -> Option<ActivityEscalationLevel> {
+        let terminals = self.terminals.lock().await;
+        terminals
+            .get(terminal_id)
+            .and_then(|terminal| terminal.activity.level())
+    }
+
+    async fn run_cycle(&self, db: &DBService) -> anyhow::Result<()> {
+        let rows = sqlx::query_as::<_, WatchedTerminalRow>(
+            r"
+            SELECT t.id AS terminal_id, t.workflow_task_id AS task_id, wt.workflow_id, wt.branch,
+                   t.pty_session_id, w.activity_stall_timeout_secs, w.activity_loop_threshold,
+                   w.activity_escalation_interval_secs
+            FROM terminal t
+            INNER JOIN workflow_task wt ON t.workflow_task_id = wt.id
+            INNER JOIN workflow w ON wt.workflow_id = w.id
+            WHERE t.status = 'working' AND w.status = 'running' AND w.orchestrator_enabled = 1
+            ",
+        )
+        .fetch_all(&db.pool)
+        .await?;
+
+        let now = Instant::now();
+        self.sync_terminals(rows, now).await;
+        for event in self.evaluate(now).await {
+            tracing::warn!(
+                workflow_id = %event.workflow_id,
+                terminal_id = %event.terminal_id,
+                level = event.level.as_str(),
+                reason = %event.reason.describe(),
+                "Escalating inactive terminal"
+            );
+            self.message_bus.publish_terminal_activity(event).await;
+        }
+        Ok(())
+    }
+
+    /// Start watching new working terminals and park the ones that stopped.
+    async fn sync_terminals(&self, rows: Vec<WatchedTerminalRow>, now: Instant) {
+        let mut terminals = self.terminals.lock().await;
+        let mut working = HashSet::new();
+
+        for row in rows {
+            let Some(session_id) = row
+                .pty_session_id
+                .filter(|session_id| !session_id.trim().is_empty())
+            else {
+                continue;
+            };
+            working.insert(row.terminal_id.clone());
+
+            let terminal =
+                terminals
+                    .entry(row.terminal_id.clone())
+                    .or_insert_with(|| MonitoredTerminal {
+                        workflow_id: row.workflow_id.clone(),
+                        task_id: row.task_id.clone(),
+                        branch: row.branch.clone(),
+                        session_id: session_id.clone(),
+                        policy: ActivityPolicy::default(),
+                        activity: TerminalActivity::new(now),
+                        reader: None,
+                        dormant_since: None,
+                    });
+            terminal.branch = row.branch;
+            terminal.policy = ActivityPolicy::from_settings(
+                row.activity_stall_timeout_secs,
+                row.activity_loop_threshold,
+                row.activity_escalation_interval_secs,
+            );
+            if terminal.dormant_since.take().is_some() {
+                // Idle time while not working (e.g. restarting) does not count.
+                terminal.activity.record_lag(now);
+            }
+
+            let reader_alive = terminal
+                .reader
+                .as_ref()
+                .is_some_and(|reader| !reader.is_finished());
+            if terminal.session_id != session_id || !reader_alive {
+                terminal.stop_reader();
+                terminal.session_id = session_id;
+                terminal.reader = self
+                    .spawn_reader(&row.terminal_id, &terminal.session_id)
+                    .await;
+            }
+        }
+
+        terminals.retain(|terminal_id, terminal| {
+            if working.contains(terminal_id) {
+                return true;
+            }
+            terminal.stop_reader();
+            let dormant_since = *terminal.dormant_since.get_or_insert(now);
+            now.duration_since(dormant_since) < DORMANT_RETENTION
+        });
+    }
+
+    async fn spawn_reader(&self, terminal_id: &str, session_id: &str) -> Option<JoinHandle<()>> {
+        let mut subscription = match self
+            .process_manager
+            .subscribe_output(terminal_id, None)
+            .await
+        {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                tracing::debug!(
+                    terminal_id = %terminal_id,
+                    error = %e,
+                    "Activity monitor could not subscribe to terminal output"
+                );
+                return None;
+            }
+        };
+
+        let terminals = Arc::clone(&self.terminals);
+        let terminal_id = terminal_id.to_string();
+        let session_id = session_id.to_string();
+        let subscribed_at = SystemTime::now();
+        Some(tokio::spawn(async move {
+            loop {
+                let result = subscription.recv().await;
+                let mut terminals = terminals.lock().await;
+                let Some(terminal) = terminals
+                    .get_mut(&terminal_id)
+                    .filter(|terminal| terminal.session_id == session_id)
+                else {
+                    break;
+                };
+                match result {
+                    // Replayed history must not count as new activity or tool calls
+                    Ok(chunk) if chunk.timestamp < subscribed_at => {
+                        terminal.activity.skip_output(chunk.seq);
+                    }
+                    Ok(chunk) => {
+                        terminal
+                            .activity
+                            .record_output(chunk.seq, &chunk.text, Instant::now());
+                    }
+                    Err(RecvError::Lagged(_)) => terminal.activity.record_lag(Instant::now()),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }))
+    }
+
+    async fn evaluate(&self, now: Instant) -> Vec<TerminalActivityEvent> {
+        let mut terminals = self.terminals.lock().await;
+        terminals
+            .iter_mut()
+            .filter(|(_, terminal)| terminal.dormant_since.is_none())
+            .filter_map(|(terminal_id, terminal)| {
+                let (level, reason) = terminal.activity.evaluate(&terminal.policy, now)?;
+                Some(TerminalActivityEvent {
+                    terminal_id: terminal_id.clone(),
+                    workflow_id: terminal.workflow_id.clone(),
+                    task_id: terminal.task_id.clone(),
+                    session_id: terminal.session_id.clone(),
+                    level,
+                    reason,
+                    last_output_seq: terminal.activity.last_seq(),
+                    detected_at: chrono::Utc::now(),
+                })
+            })
+            .collect()
+    }
+
+    /// Commits from the git watcher reset the ladder of the committing terminal.
+    async fn commit_loop(&self) {
+        let mut rx = self.message_bus.subscribe_broadcast();
+        loop {
+            match rx.recv().await {
+                Ok(BusMessage::GitEvent {
+                    workflow_id,
+                    branch,
+                    ..
+                }) => {
+                    self.record_commit(&workflow_id, None, Some(&branch)).await;
+                }
+                Ok(BusMessage::TerminalCompleted(event)) if event.commit_hash.is_some() => {
+                    self.record_commit(&event.workflow_id, Some(&event.terminal_id), None)
+                        .await;
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "Activity monitor lagged behind the message bus");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    async fn record_commit(
+        &self,
+        workflow_id: &str,
+        terminal_id: Option<&str>,
+        branch: Option<&str>,
+    ) {
+        let now = Instant::now();
+        let mut terminals = self.terminals.lock().await;
+        for (id, terminal) in terminals.iter_mut() {
+            let matches = terminal.workflow_id == workflow_id
+                && (terminal_id == Some(id.as_str()) || branch == Some(terminal.branch.as_str()));
+            if matches {
+                terminal.activity.record_commit(now);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(stall_secs: u64, loop_threshold: usize, interval_secs: u64) -> ActivityPolicy {
+        ActivityPolicy {
+            stall_timeout: Some(Duration::from_secs(stall_secs)),
+            loop_threshold: Some(loop_threshold),
+            escalation_interval: Duration::from_secs(interval_secs),
+        }
+    }
+
+    #[test]
+    fn stalled_terminal_walks_the_ladder_until_failed() {
+        let start = Instant::now();
+        let policy = policy(600, 6, 120);
+        let mut activity = TerminalActivity::new(start);
+        activity.record_output(1, "\u{1b}[32mCompiling crate\u{1b}[0m\r\n", start);
+
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        assert_eq!(activity.evaluate(&policy, at(599)), None);
+        // Spinner updates are not progress
+        activity.record_output(2, "✻ Thinking… (12s · esc to interrupt)", at(300));
+
+        let (level, reason) = activity.evaluate(&policy, at(600)).unwrap();
+        assert_eq!(level, ActivityEscalationLevel::Nudge);
+        assert_eq!(reason, ActivityStallReason::Idle { idle_secs: 600 });
+        assert_eq!(activity.evaluate(&policy, at(700)), None);
+
+        let levels = [720, 840, 960, 1080]
+            .map(|secs| activity.evaluate(&policy, at(secs)).map(|(level, _)| level));
+        assert_eq!(
+            levels,
+            [
+                Some(ActivityEscalationLevel::Notify),
+                Some(ActivityEscalationLevel::Restart),
+                Some(ActivityEscalationLevel::Fail),
+                None,
+            ]
+        );
+        assert_eq!(activity.last_seq(), 2);
+    }
+
+    #[test]
+    fn repeated_tool_calls_are_a_loop_until_a_commit() {
+        let start = Instant::now();
+        let policy = policy(600, 3, 60);
+        let mut activity = TerminalActivity::new(start);
+        let call = "\u{1b}[1m⏺\u{1b}[0m Bash(npm test)\r\n  ⎿  1 failing\r\n";
+
+        for (seq, secs) in [(1, 10), (2, 11), (3, 40), (4, 70)] {
+            activity.record_output(seq, call, start + Duration::from_secs(secs));
+        }
+        // The redraw at 11s is the same call
+        let (level, reason) = activity
+            .evaluate(&policy, start + Duration::from_secs(71))
+            .unwrap();
+        assert_eq!(level, ActivityEscalationLevel::Nudge);
+        assert_eq!(
+            reason,
+            ActivityStallReason::Loop {
+                tool_call: "Bash(npm test)".to_string(),
+                repeats: 3,
+            }
+        );
+        assert_eq!(
+            activity.evaluate(&policy, start + Duration::from_secs(200)),
+            None
+        );
+
+        activity.record_commit(start + Duration::from_secs(210));
+        assert_eq!(activity.level(), None);
+        for (seq, secs) in [(5, 220), (6, 250), (7, 280)] {
+            activity.record_output(seq, call, start + Duration::from_secs(secs));
+        }
+        let (level, _) = activity
+            .evaluate(&policy, start + Duration::from_secs(281))
+            .unwrap();
+        assert_eq!(level, ActivityEscalationLevel::Nudge);
+    }
+
+    #[test]
+    fn extracts_tool_calls_from_agent_cli_output() {
+        let output = "⏺ Read(src/main.rs)\r\n• Ran cargo   test --workspace\n✓  Shell npm run lint\nplain text";
+        assert_eq!(
+            extract_tool_calls(output),
+            vec![
+                "Read(src/main.rs)",
+                "cargo test --workspace",
+                "Shell npm run lint"
+            ]
+        );
+        assert!(!is_meaningful_output(
+            "⠋ Working (3s • esc to interrupt)\r\n"
+        ));
+        assert!(is_meaningful_output("wrote 3 files\n"));
+    }
+
+    #[test]
+    fn policy_settings_use_defaults_and_zero_disables() {
+        assert_eq!(
+            ActivityPolicy::from_settings(None, None, None),
+            ActivityPolicy::default()
+        );
+        let policy = ActivityPolicy::from_settings(Some(0), Some(0), Some(30));
+        assert_eq!(policy.stall_timeout, None);
+        assert_eq!(policy.loop_threshold, None);
+        assert_eq!(policy.escalation_interval, Duration::from_secs(30));
+
+        let mut activity = TerminalActivity::new(Instant::now());
+        let later = Instant::now() + Duration::from_secs(24 * 60 * 60);
+        assert_eq!(activity.evaluate(&policy, later), None);
+    }
+}
@@ -2,8 +2,16 @@
 //!
 //! Subscribes to PTY session topics and forwards terminal messages to stdin.
 //! This enables the Orchestrator to send commands and confirmations to CLI tools.
-
-use std::{collections::HashMap, sync::Arc, time::Duration};
+//!
+//! While a client holds the terminal's input lease, prompt auto-responses are
+//! dropped (the client answers prompts itself) and orchestrator instructions
+//! are queued until the lease returns to the orchestrator.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
 
 use tokio::{
     sync::{RwLock, mpsc},
@@ -11,7 +19,10 @@ use tokio::{
     time::MissedTickBehavior,
 };
 
-use super::process::ProcessManager;
+use super::{
+    input_lease::{InputLease, InputOwner},
+    process::ProcessManager,
+};
 use crate::services::orchestrator::message_bus::{BusMessage, SharedMessageBus};
 
 // ============================================================================
@@ -21,6 +32,10 @@ use crate::services::orchestrator::message_bus::{BusMessage, SharedMessageBus};
 /// Channel capacity for PTY writer queue.
 const BRIDGE_CHANNEL_CAPACITY: usize = 100;
 
+/// Orchestrator instructions held while a client holds the input lease;
+/// the oldest is dropped beyond this.
+const BRIDGE_PENDING_CAPACITY: usize = 100;
+
 /// Health check interval for terminal liveness (seconds).
 const BRIDGE_HEALTH_INTERVAL_SECS: u64 = 5;
 
@@ -252,6 +267,8 @@ impl TerminalBridge {
     /// Helper method to forward a bus message to PTY stdin.
     async fn forward_bus_message(
         tx: &mpsc::Sender<Vec<u8>>,
+        lease: &InputLease,
+        pending: &mut VecDeque<Vec<u8>>,
         terminal_id: &str,
         pty_session_id: &str,
         msg: Option<BusMessage>,
@@ -268,16 +285,23 @@ impl TerminalBridge {
                     message_len = payload.len(),
                     "Forwarding legacy TerminalMessage to PTY stdin"
                 );
-                tx.send(payload.into_bytes())
-                    .await
-                    .map_err(|_| anyhow::anyhow!("PTY writer channel closed"))?;
+                Self::deliver(
+                    tx,
+                    lease,
+                    pending,
+                    terminal_id,
+                    pty_session_id,
+                    payload,
+                    false,
+                )
+                .await?;
                 Ok(false)
             }
             Some(BusMessage::TerminalInput {
                 terminal_id: message_terminal_id,
                 session_id: message_session_id,
                 input,
-                ..
+                decision,
             }) => {
                 // Strict routing:
                 // - if session_id is present, it must match current PTY session;
@@ -300,9 +324,18 @@ impl TerminalBridge {
                     message_len = payload.len(),
                     "Forwarding TerminalInput to PTY stdin"
                 );
-                tx.send(payload.into_bytes())
-                    .await
-                    .map_err(|_| anyhow::anyhow!("PTY writer channel closed"))?;
+                // Input carrying a prompt decision is an automatic prompt response
+                let prompt_response = decision.is_some();
+                Self::deliver(
+                    tx,
+                    lease,
+                    pending,
+                    terminal_id,
+                    pty_session_id,
+                    payload,
+                    prompt_response,
+                )
+                .await?;
                 Ok(false)
             }
             Some(BusMessage::Shutdown) => {
@@ -325,6 +358,79 @@ impl TerminalBridge {
         }
     }
 
+    /// Write orchestrator input, or hold it back while a client holds the
+    /// input lease: prompt responses are dropped, instructions are queued.
+    async fn deliver(
+        tx: &mpsc::Sender<Vec<u8>>,
+        lease: &InputLease,
+        pending: &mut VecDeque<Vec<u8>>,
+        terminal_id: &str,
+        pty_session_id: &str,
+        payload: String,
+        prompt_response: bool,
+    ) -> anyhow::Result<()> {
+        if lease.is_held_by(&InputOwner::Orchestrator) {
+            // Queued instructions go first to keep their order
+            Self::flush_pending(tx, pending, terminal_id, pty_session_id).await?;
+            return tx
+                .send(payload.into_bytes())
+                .await
+                .map_err(|_| anyhow::anyhow!("PTY writer channel closed"));
+        }
+
+        if prompt_response {
+            tracing::info!(
+                terminal_id = %terminal_id,
+                pty_session_id = %pty_session_id,
+                holder = %lease.owner(),
+                "Dropping prompt auto-response while a client holds the input lease"
+            );
+            return Ok(());
+        }
+
+        if pending.len() >= BRIDGE_PENDING_CAPACITY {
+            pending.pop_front();
+            tracing::warn!(
+                terminal_id = %terminal_id,
+                pty_session_id = %pty_session_id,
+                "Pending orchestrator input queue full; dropping oldest instruction"
+            );
+        }
+        pending.push_back(payload.into_bytes());
+        tracing::info!(
+            terminal_id = %terminal_id,
+            pty_session_id = %pty_session_id,
+            holder = %lease.owner(),
+            queued = pending.len(),
+            "Queued orchestrator input until the client releases the input lease"
+        );
+        Ok(())
+    }
+
+    /// Write instructions queued while a client held the input lease.
+    async fn flush_pending(
+        tx: &mpsc::Sender<Vec<u8>>,
+        pending: &mut VecDeque<Vec<u8>>,
+        terminal_id: &str,
+        pty_session_id: &str,
+    ) -> anyhow::Result<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+        tracing::info!(
+            terminal_id = %terminal_id,
+            pty_session_id = %pty_session_id,
+            count = pending.len(),
+            "Forwarding orchestrator input queued while a client held the input lease"
+        );
+        while let Some(payload) = pending.pop_front() {
+            tx.send(payload)
+                .await
+                .map_err(|_| anyhow::anyhow!("PTY writer channel closed"))?;
+        }
+        Ok(())
+    }
+
     /// Main bridge loop that forwards messages to PTY stdin.
     async fn run_bridge(
         process_manager: Arc<ProcessManager>,
@@ -352,6 +458,10 @@ impl TerminalBridge {
         let writer = handle.writer.ok_or_else(|| {
             anyhow::anyhow!("PTY writer unavailable for terminal {terminal_id}")
         })?;
+        let lease = process_manager
+            .input_lease(&terminal_id)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Terminal not running: {terminal_id}"))?;
 
         // Create channel for writer task
         let (tx, mut writer_rx) = mpsc::channel::<Vec<u8>>(BRIDGE_CHANNEL_CAPACITY);
@@ -382,6 +492,8 @@ impl TerminalBridge {
         });
 
         let mut writer_finished = false;
+        let mut pending = VecDeque::new();
+        let mut lease_changes = lease.subscribe();
         let mut health_interval =
             tokio::time::interval(Duration::from_secs(BRIDGE_HEALTH_INTERVAL_SECS));
         health_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
@@ -396,15 +508,21 @@ impl TerminalBridge {
         loop {
             tokio::select! {
                 msg = rx_terminal_input.recv() => {
-                    if Self::forward_bus_message(&tx, &terminal_id, &pty_session_id, msg).await? {
+                    if Self::forward_bus_message(&tx, &lease, &mut pending, &terminal_id, &pty_session_id, msg).await? {
                         break;
                     }
                 }
                 msg = rx_legacy.recv() => {
-                    if Self::forward_bus_message(&tx, &terminal_id, &pty_session_id, msg).await? {
+                    if Self::forward_bus_message(&tx, &lease, &mut pending, &terminal_id, &pty_session_id, msg).await? {
                         break;
                     }
                 }
+                // The bridge holds the lease, so the sender outlives this loop
+                Ok(()) = lease_changes.changed() => {
+                    if lease.is_held_by(&InputOwner::Orchestrator) {
+                        Self::flush_pending(&tx, &mut pending, &terminal_id, &pty_session_id).await?;
+                    }
+                }
                 _ = health_interval.tick() => {
                     // [G21-010] Removed process_manager.cleanup() here: global dead-process
                     // scanning belongs to ProcessManager's own periodic task, not to each
@@ -533,6 +651,7 @@ impl TerminalBridge {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::orchestrator::types::PromptDecision;
 
     #[test]
     fn test_normalize_message_adds_newline() {
@@ -566,6 +685,8 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<Vec<u8>>(1);
         let should_stop = TerminalBridge::forward_bus_message(
             &tx,
+            &InputLease::new(),
+            &mut VecDeque::new(),
             "term-1",
             "session-1",
             Some(BusMessage::TerminalInput {
@@ -590,6 +711,8 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<Vec<u8>>(1);
         let should_stop = TerminalBridge::forward_bus_message(
             &tx,
+            &InputLease::new(),
+            &mut VecDeque::new(),
             "term-1",
             "session-1",
             Some(BusMessage::TerminalInput {
@@ -606,4 +729,63 @@ mod tests {
         let forwarded = rx.recv().await.expect("expected forwarded payload");
         assert_eq!(forwarded, b"hello\r");
     }
+
+    #[tokio::test]
+    async fn test_client_lease_drops_prompt_responses_and_queues_instructions() {
+        let (tx, mut rx) = mpsc::channel::<Vec<u8>>(4);
+        let lease = InputLease::new();
+        let mut pending = VecDeque::new();
+        let alice = InputOwner::client("alice");
+        lease.acquire(alice.clone());
+        let input = |input: &str, decision: Option<PromptDecision>| {
+            Some(BusMessage::TerminalInput {
+                terminal_id: "term-1".to_string(),
+                session_id: "session-1".to_string(),
+                input: input.to_string(),
+                decision,
+            })
+        };
+
+        for msg in [
+            input("y", Some(PromptDecision::auto_enter())),
+            input("continue", None),
+            Some(BusMessage::TerminalMessage {
+                message: "next task".to_string(),
+            }),
+        ] {
+            TerminalBridge::forward_bus_message(
+                &tx,
+                &lease,
+                &mut pending,
+                "term-1",
+                "session-1",
+                msg,
+            )
+            .await
+            .expect("forward should not error");
+        }
+        assert!(
+            rx.try_recv().is_err(),
+            "orchestrator input must not reach a client-held terminal"
+        );
+        assert_eq!(pending.len(), 2, "only instructions are queued");
+
+        lease.release(&alice);
+        TerminalBridge::forward_bus_message(
+            &tx,
+            &lease,
+            &mut pending,
+            "term-1",
+            "session-1",
+            input("after", None),
+        )
+        .await
+        .expect("forward should not error");
+        let mut forwarded = Vec::new();
+        while let Ok(payload) = rx.try_recv() {
+            forwarded.push(String::from_utf8(payload).unwrap());
+        }
+        assert_eq!(forwarded, vec!["continue\r", "next task\r", "after\r"]);
+        assert!(pending.is_empty());
+    }
 }
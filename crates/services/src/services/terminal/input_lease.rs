@@ -0,0 +1,148 @@
+//! Terminal input ownership
+//!
+//! Exactly one party writes to a terminal at a time: the orchestrator (the
+//! default holder) or a single connected client. Other clients stay read-only
+//! until they take the lease over. A client connecting to a terminal whose
+//! workflow has no running orchestrator receives the lease right away. While a client holds it, automatic prompt
+//! responses are suppressed and orchestrator instructions are queued until the
+//! lease is released.
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use ts_rs::TS;
+
+/// Holder of a terminal's input lease
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InputOwner {
+    /// Orchestrator and prompt automation (message bus input)
+    Orchestrator,
+    /// A connected terminal client
+    Client { client_id: String },
+}
+
+impl InputOwner {
+    pub fn client(client_id: impl Into<String>) -> Self {
+        Self::Client {
+            client_id: client_id.into(),
+        }
+    }
+
+    pub fn is_client(&self) -> bool {
+        matches!(self, Self::Client { .. })
+    }
+}
+
+impl std::fmt::Display for InputOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Orchestrator => f.write_str("the orchestrator"),
+            Self::Client { client_id } => write!(f, "client {client_id}"),
+        }
+    }
+}
+
+/// Single-writer lease for one terminal session
+#[derive(Debug)]
+pub struct InputLease {
+    owner: watch::Sender<InputOwner>,
+}
+
+impl InputLease {
+    /// New lease held by the orchestrator.
+    pub fn new() -> Self {
+        Self {
+            owner: watch::Sender::new(InputOwner::Orchestrator),
+        }
+    }
+
+    pub fn owner(&self) -> InputOwner {
+        self.owner.borrow().clone()
+    }
+
+    pub fn is_held_by(&self, who: &InputOwner) -> bool {
+        *self.owner.borrow() == *who
+    }
+
+    /// Take the lease over. Returns false if `who` already held it.
+    pub fn acquire(&self, who: InputOwner) -> bool {
+        self.owner.send_if_modified(|owner| {
+            if *owner == who {
+                return false;
+            }
+            *owner = who;
+            true
+        })
+    }
+
+    /// Take the lease only while the orchestrator holds it.
+    pub fn claim_from_orchestrator(&self, who: InputOwner) -> bool {
+        self.owner.send_if_modified(|owner| {
+            if *owner != InputOwner::Orchestrator {
+                return false;
+            }
+            *owner = who;
+            true
+        })
+    }
+
+    /// Hand the lease back to the orchestrator if `who` holds it.
+    pub fn release(&self, who: &InputOwner) -> bool {
+        self.owner.send_if_modified(|owner| {
+            if owner != who || !owner.is_client() {
+                return false;
+            }
+            *owner = InputOwner::Orchestrator;
+            true
+        })
+    }
+
+    /// Observe ownership changes.
+    pub fn subscribe(&self) -> watch::Receiver<InputOwner> {
+        self.owner.subscribe()
+    }
+}
+
+impl Default for InputLease {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clients_take_over_and_release_to_orchestrator() {
+        let lease = InputLease::new();
+        let mut changes = lease.subscribe();
+        let alice = InputOwner::client("alice");
+        let bob = InputOwner::client("bob");
+
+        assert!(lease.is_held_by(&InputOwner::Orchestrator));
+        assert!(lease.acquire(alice.clone()));
+        assert!(!lease.acquire(alice.clone()));
+        assert!(changes.has_changed().unwrap());
+        assert_eq!(*changes.borrow_and_update(), alice);
+
+        assert!(lease.acquire(bob.clone()));
+        // Only the current holder can hand the lease back
+        assert!(!lease.release(&alice));
+        assert!(lease.is_held_by(&bob));
+        assert!(lease.release(&bob));
+        assert_eq!(lease.owner(), InputOwner::Orchestrator);
+        assert!(!lease.release(&InputOwner::Orchestrator));
+    }
+
+    #[test]
+    fn claim_only_succeeds_against_the_orchestrator() {
+        let lease = InputLease::new();
+        let alice = InputOwner::client("alice");
+        let bob = InputOwner::client("bob");
+
+        assert!(lease.claim_from_orchestrator(alice.clone()));
+        assert!(!lease.claim_from_orchestrator(bob));
+        assert!(lease.is_held_by(&alice));
+    }
+}
@@ -12,6 +12,7 @@ use db::{
         cli_type,
         execution_process::{CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason},
         session::Session,
+        terminal::TerminalResourceReport,
     },
 };
 use executors::{
@@ -55,6 +56,8 @@ pub struct LaunchResult {
     pub process_handle: Option<ProcessHandle>,
     pub success: bool,
     pub error: Option<String>,
+    /// How the terminal's resource limits were enforced (None when unlimited)
+    pub resource_report: Option<TerminalResourceReport>,
 }
 
 impl TerminalLauncher {
@@ -166,6 +169,7 @@ impl TerminalLauncher {
                         process_handle: None,
                         success: false,
                         error: Some("CLI type not found".to_string()),
+                        resource_report: None,
                     };
                 }
                 Err(e) => {
@@ -174,6 +178,7 @@ impl TerminalLauncher {
                         process_handle: None,
                         success: false,
                         error: Some(format!("Database error: {e}")),
+                        resource_report: None,
                     };
                 }
             };
@@ -322,6 +327,7 @@ impl TerminalLauncher {
                     process_handle: None,
                     success: false,
                     error: Some(format!("Config build failed: {e}")),
+                    resource_report: None,
                 };
             }
         };
@@ -360,6 +366,19 @@ impl TerminalLauncher {
                         )
                         .await;
                 }
+                if let Err(e) = Terminal::update_resource_report(
+                    &self.db.pool,
+                    &terminal_id,
+                    handle.resource_report.as_ref(),
+                )
+                .await
+                {
+                    tracing::warn!(
+                        terminal_id = %terminal_id,
+                        error = %e,
+                        "Failed to persist terminal resource report"
+                    );
+                }
 
                 // Update terminal with session and execution process binding
                 if session_id.is_some() || execution_process_id.is_some() {
@@ -433,6 +452,7 @@ impl TerminalLauncher {
                                     &workflow_id,
                                     &terminal.workflow_task_id,
                                     &handle.session_id,
+                                    &terminal.cli_type_id,
                                     terminal.auto_confirm,
                                 )
                                 .await
@@ -523,6 +543,7 @@ impl TerminalLauncher {
 
                 LaunchResult {
                     terminal_id,
+                    resource_report: handle.resource_report.clone(),
                     process_handle: Some(handle),
                     success: true,
                     error: None,
@@ -587,6 +608,7 @@ impl TerminalLauncher {
             process_handle: None,
             success: false,
             error: Some(reason),
+            resource_report: None,
         }
     }
 
@@ -948,6 +970,9 @@ mod tests {
             execution_process_id: None,
             vk_session_id: None,
             auto_confirm: false,
+            resource_limits: None,
+            resource_report: None,
+            runner_labels: None,
             last_commit_hash: None,
             last_commit_message: None,
             started_at: None,
@@ -969,6 +994,7 @@ mod tests {
             process_handle: None,
             success: true,
             error: None,
+            resource_report: None,
         };
 
         assert_eq!(result.terminal_id, "test");
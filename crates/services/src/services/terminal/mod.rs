@@ -5,21 +5,40 @@
 //! - CliDetector: CLI availability and version detection
 //! - TerminalLauncher: Serial terminal launcher with model switching
 //! - TerminalBridge: MessageBus -> PTY stdin bridge for Orchestrator communication
+//! - InputLease: Single-writer input ownership shared by clients and the orchestrator
 //! - PromptDetector: Interactive prompt detection and classification
 //! - PromptWatcher: PTY output monitoring and prompt event publishing
+//! - PromptRuleStore: Declarative per-CLI prompt rule packs with hot reload
 //! - OutputFanout: Single-reader PTY output fanout with replay support
+//! - TerminalScreen: Per-session VT100 grid for screen-based prompt detection
+//! - SessionRecorder: Asciicast v2 recording of PTY sessions
+//! - ActivityMonitor: Stall/loop detection with escalation for agent terminals
+//! - SandboxPlan: Per-terminal resource limits (cgroup v2, rlimits, namespaces)
+//! - supervisor: Detached PTY supervisors that keep terminals alive across restarts
+//! - remote: Terminals hosted on remote runners behind the PTY traits
 
+pub mod activity_monitor;
 pub mod bridge;
 pub mod detector;
+pub mod input_lease;
 pub mod launcher;
 pub mod output_fanout;
 pub mod process;
 pub mod prompt_detector;
+pub mod prompt_rules;
 pub mod prompt_watcher;
+pub mod recording;
+pub mod remote;
+pub mod sandbox;
+pub mod screen;
+#[cfg(unix)]
+pub mod supervisor;
 pub mod utf8_decoder;
 
+pub use activity_monitor::{ActivityMonitor, ActivityPolicy};
 pub use bridge::TerminalBridge;
 pub use detector::CliDetector;
+pub use input_lease::{InputLease, InputOwner};
 pub use launcher::{LaunchResult, TerminalLauncher};
 pub use output_fanout::{OutputChunk, OutputFanout, OutputFanoutConfig, OutputSubscription};
 pub use process::{ProcessHandle, ProcessManager};
@@ -27,5 +46,11 @@ pub use prompt_detector::{
     ARROW_DOWN, ARROW_UP, ArrowSelectOption, DetectedPrompt, PromptDetector, PromptKind,
     build_arrow_sequence,
 };
+pub use prompt_rules::{PromptRulePack, PromptRuleSet, PromptRuleStore};
 pub use prompt_watcher::PromptWatcher;
+pub use recording::{InputCapture, RecordingConfig, RecordingInfo, SessionRecorder};
+pub use sandbox::{SandboxCapabilities, SandboxPlan, TerminalCgroup};
+pub use screen::{ScreenSnapshot, SharedTerminalScreen, TerminalScreen};
+#[cfg(unix)]
+pub use supervisor::{SupervisedSessionInfo, SupervisorConfig};
 pub use utf8_decoder::{Utf8DecodeChunk, Utf8DecodeStats, Utf8StreamDecoder};
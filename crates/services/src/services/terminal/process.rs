@@ -10,22 +10,35 @@ use std::{
     sync::{
         Arc, Mutex,
         atomic::{AtomicBool, Ordering},
+        mpsc,
     },
     time::Duration,
 };
 
-use db::DBService;
+use db::{
+    DBService,
+    models::{TerminalResourceLimits, TerminalResourceReport},
+};
 use portable_pty::{Child, CommandBuilder, MasterPty, PtySize, native_pty_system};
+use serde::{Deserialize, Serialize};
 use tokio::{
     sync::{Mutex as AsyncMutex, RwLock, oneshot},
     task::JoinHandle,
 };
 use uuid::Uuid;
 
+#[cfg(unix)]
+use super::supervisor::{self, SupervisorConfig, SupervisorSpec};
 use super::{
+    input_lease::InputLease,
     output_fanout::{OutputFanout, OutputFanoutConfig, OutputSubscription},
+    recording::{RecordingConfig, SessionRecorder},
+    remote,
+    sandbox::{SandboxCapabilities, SandboxPlan, TerminalCgroup},
+    screen::{ScreenSnapshot, SharedTerminalScreen, TerminalScreen},
     utf8_decoder::Utf8StreamDecoder,
 };
+use crate::services::runner_client::{SharedRunnerClient, TerminalSpawnConfig};
 
 // ============================================================================
 // PTY Size Configuration
@@ -72,7 +85,7 @@ const SQLITE_FOREIGN_KEY_CONSTRAINT_CODE: &str = "787";
 ///
 /// Supports both setting new environment variables and removing inherited ones
 /// to prevent parent process pollution.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SpawnEnv {
     /// Environment variables to set on the child process.
     pub set: HashMap<String, String>,
@@ -115,7 +128,7 @@ impl SpawnEnv {
 ///
 /// Encapsulates all information needed to spawn a process with proper isolation:
 /// command, arguments, working directory, and environment configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpawnCommand {
     /// Command to execute (e.g., "claude", "codex", "gemini").
     pub command: String,
@@ -125,6 +138,11 @@ pub struct SpawnCommand {
     pub working_dir: PathBuf,
     /// Environment variable configuration for process isolation.
     pub env: SpawnEnv,
+    /// Resource limits applied to the process tree.
+    pub resource_limits: Option<TerminalResourceLimits>,
+    /// Labels the remote runner hosting the process must carry.
+    #[serde(default)]
+    pub runner_labels: Vec<String>,
 }
 
 impl SpawnCommand {
@@ -135,6 +153,8 @@ impl SpawnCommand {
             args: Vec::new(),
             working_dir: working_dir.into(),
             env: SpawnEnv::default(),
+            resource_limits: None,
+            runner_labels: Vec::new(),
         }
     }
 
@@ -155,6 +175,104 @@ impl SpawnCommand {
         self.env = env;
         self
     }
+
+    /// Sets the resource limits.
+    pub fn with_resource_limits(mut self, limits: TerminalResourceLimits) -> Self {
+        self.resource_limits = Some(limits);
+        self
+    }
+
+    /// Sets the labels required of the remote runner.
+    pub fn with_runner_labels(
+        mut self,
+        labels: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.runner_labels = labels.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Build the PTY command for `config`, run behind the sandbox `wrapper` argv.
+pub(crate) fn build_command(
+    terminal_id: &str,
+    config: &SpawnCommand,
+    wrapper: &[String],
+) -> CommandBuilder {
+    // On Windows, use cmd.exe /c to run commands so that .cmd/.bat files are found
+    #[cfg(windows)]
+    let mut cmd = {
+        // Sandbox wrappers are Linux-only
+        let _ = wrapper;
+        let mut c = CommandBuilder::new("cmd.exe");
+        c.arg("/c");
+        c.arg(&config.command);
+        for arg in &config.args {
+            c.arg(arg);
+        }
+        c
+    };
+    #[cfg(not(windows))]
+    let mut cmd = {
+        // Sandbox wrappers (cgroup join, unshare, prlimit) exec the command in place, keeping its PID
+        let mut c = match wrapper.split_first() {
+            Some((program, wrapper_args)) => {
+                let mut c = CommandBuilder::new(program);
+                c.args(wrapper_args);
+                c.arg(&config.command);
+                c
+            }
+            None => CommandBuilder::new(&config.command),
+        };
+        for arg in &config.args {
+            c.arg(arg);
+        }
+        c
+    };
+    cmd.cwd(&config.working_dir);
+
+    // Set environment variables for proper terminal behavior
+    cmd.env("TERM", "xterm-256color");
+    cmd.env("COLORTERM", "truecolor");
+
+    // UTF-8 encoding for Unix
+    #[cfg(unix)]
+    {
+        cmd.env("LANG", "C.UTF-8");
+        cmd.env("LC_ALL", "C.UTF-8");
+    }
+
+    // Remove inherited environment variables to prevent parent process pollution
+    // This must be done BEFORE setting new values to ensure clean isolation
+    for key in &config.env.unset {
+        cmd.env_remove(key);
+        tracing::debug!(
+            terminal_id = %terminal_id,
+            key = %key,
+            "Removed inherited env var"
+        );
+    }
+
+    // Inject custom environment variables for process-level isolation
+    for (key, value) in &config.env.set {
+        cmd.env(key, value);
+        // Redact sensitive values in logs
+        if SpawnEnv::is_sensitive_key(key) {
+            tracing::debug!(
+                terminal_id = %terminal_id,
+                key = %key,
+                "Injected env var [REDACTED]"
+            );
+        } else {
+            tracing::debug!(
+                terminal_id = %terminal_id,
+                key = %key,
+                value = %value,
+                "Injected env var"
+            );
+        }
+    }
+
+    cmd
 }
 
 // ============================================================================
@@ -171,18 +289,63 @@ impl PtyReader {
     }
 }
 
+/// PTY output of a session whose PTY lives elsewhere (supervisor or remote
+/// runner), delivered over a channel; EOF once the sender is dropped
+pub(crate) struct ChannelReader {
+    output: mpsc::Receiver<Vec<u8>>,
+    pending: Vec<u8>,
+    offset: usize,
+}
+
+impl ChannelReader {
+    /// Reader yielding `initial` before anything received on `output`.
+    pub(crate) fn new(output: mpsc::Receiver<Vec<u8>>, initial: Vec<u8>) -> Self {
+        Self {
+            output,
+            pending: initial,
+            offset: 0,
+        }
+    }
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.offset >= self.pending.len() {
+            match self.output.recv() {
+                Ok(chunk) => {
+                    self.pending = chunk;
+                    self.offset = 0;
+                }
+                Err(_) => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.pending.len() - self.offset);
+        buf[..n].copy_from_slice(&self.pending[self.offset..self.offset + n]);
+        self.offset += n;
+        Ok(n)
+    }
+}
+
 /// PTY writer wrapper for async writing
-pub struct PtyWriter(Box<dyn Write + Send>);
+pub struct PtyWriter {
+    inner: Box<dyn Write + Send>,
+    /// Session recorder; every input written to the PTY is recorded
+    recorder: Option<Arc<SessionRecorder>>,
+}
 
 impl PtyWriter {
     /// Write bytes to PTY (blocking)
     pub fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
-        self.0.write_all(buf)
+        self.inner.write_all(buf)?;
+        if let Some(recorder) = &self.recorder {
+            recorder.record_input(buf);
+        }
+        Ok(())
     }
 
     /// Flush PTY writer
     pub fn flush(&mut self) -> std::io::Result<()> {
-        self.0.flush()
+        self.inner.flush()
     }
 }
 
@@ -198,6 +361,8 @@ pub struct ProcessHandle {
     pub reader: Option<PtyReader>,
     /// Shared PTY writer (for WebSocket input) - wrapped in Arc<Mutex> for reconnection support
     pub writer: Option<Arc<Mutex<PtyWriter>>>,
+    /// How the terminal's resource limits were enforced (None when unlimited)
+    pub resource_report: Option<TerminalResourceReport>,
 }
 
 // Implement Debug manually since portable-pty types don't implement Debug
@@ -209,6 +374,7 @@ impl std::fmt::Debug for ProcessHandle {
             .field("terminal_id", &self.terminal_id)
             .field("reader", &self.reader.is_some())
             .field("writer", &self.writer.is_some())
+            .field("resource_report", &self.resource_report)
             .finish()
     }
 }
@@ -231,12 +397,24 @@ struct TrackedProcess {
     codex_home: Option<PathBuf>,
     /// Output fanout hub (single reader -> multi-subscriber)
     output_fanout: Arc<OutputFanout>,
+    /// Virtual screen rendered from the raw PTY byte stream
+    screen: SharedTerminalScreen,
+    /// Asciicast recording of this session (None when recording is disabled)
+    recorder: Option<Arc<SessionRecorder>>,
     /// Background PTY reader task
     reader_task: Option<JoinHandle<()>>,
     /// Background terminal log persistence task
     logger_task: Option<JoinHandle<()>>,
     /// Shutdown signal for graceful terminal log task stop
     logger_shutdown_tx: Option<oneshot::Sender<()>>,
+    /// cgroup enforcing CPU/memory/process limits
+    cgroup: Option<TerminalCgroup>,
+    /// Wall-clock timeout watchdog
+    watchdog_task: Option<JoinHandle<()>>,
+    /// How resource limits were enforced for this session
+    resource_report: Option<TerminalResourceReport>,
+    /// Who may currently write to the PTY
+    input_lease: Arc<InputLease>,
 }
 
 // ============================================================================
@@ -285,6 +463,13 @@ impl Drop for CodexHomeGuard {
 /// (task joins, CODEX_HOME cleanup) which is not safe in a synchronous destructor.
 pub struct ProcessManager {
     processes: Arc<RwLock<HashMap<String, TrackedProcess>>>,
+    /// Session recording settings (None disables recording)
+    recording: Option<RecordingConfig>,
+    /// Run PTYs under detached supervisors that survive server restarts
+    #[cfg(unix)]
+    supervisor: Option<SupervisorConfig>,
+    /// Run terminals on remote runners instead of local PTYs
+    remote_runner: Option<SharedRunnerClient>,
 }
 
 impl ProcessManager {
@@ -292,9 +477,44 @@ impl ProcessManager {
     pub fn new() -> Self {
         Self {
             processes: Arc::new(RwLock::new(HashMap::new())),
+            recording: None,
+            #[cfg(unix)]
+            supervisor: None,
+            remote_runner: None,
         }
     }
 
+    /// Record every spawned PTY session as asciicast.
+    pub fn with_recording(mut self, config: RecordingConfig) -> Self {
+        self.recording = Some(config);
+        self
+    }
+
+    pub fn recordings_dir(&self) -> Option<&Path> {
+        self.recording.as_ref().map(|config| config.dir.as_path())
+    }
+
+    /// Spawn terminals under detached PTY supervisors.
+    #[cfg(unix)]
+    pub fn with_supervisor(mut self, config: SupervisorConfig) -> Self {
+        self.supervisor = Some(config);
+        self
+    }
+
+    /// Spawn terminals on `runner` (a remote runner or registry); output,
+    /// input, resize and kill are relayed through its RPCs.
+    pub fn with_remote_runner(mut self, runner: SharedRunnerClient) -> Self {
+        self.remote_runner = Some(runner);
+        self
+    }
+
+    fn is_supervised(&self) -> bool {
+        #[cfg(unix)]
+        return self.supervisor.is_some();
+        #[cfg(not(unix))]
+        false
+    }
+
     /// Cleans up CODEX_HOME temporary directory for a terminated Codex terminal.
     ///
     /// Safety: Only removes directories under the solodawn temp directory to prevent
@@ -415,9 +635,87 @@ impl ProcessManager {
         )
         .await;
 
+        if let Some(recorder) = tracked.recorder.take() {
+            recorder.finish();
+        }
+
         if let Some(codex_home) = tracked.codex_home.take() {
             Self::cleanup_codex_home(terminal_id, &codex_home);
         }
+
+        if let Some(watchdog) = tracked.watchdog_task.take() {
+            watchdog.abort();
+        }
+        if let Some(cgroup) = tracked.cgroup.take() {
+            // Dropping kills stragglers and waits for the cgroup to empty
+            let _ = tokio::task::spawn_blocking(move || drop(cgroup)).await;
+        }
+    }
+
+    /// Kill the session once its wall-clock budget is spent.
+    fn spawn_wall_clock_watchdog(
+        &self,
+        terminal_id: &str,
+        session_id: &str,
+        timeout: Duration,
+    ) -> JoinHandle<()> {
+        let processes = Arc::clone(&self.processes);
+        let terminal_id = terminal_id.to_string();
+        let session_id = session_id.to_string();
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            let mut processes = processes.write().await;
+            let Some(tracked) = processes.get_mut(&terminal_id) else {
+                return;
+            };
+            if tracked.session_id != session_id {
+                return;
+            }
+            tracing::warn!(
+                terminal_id = %terminal_id,
+                timeout_secs = timeout.as_secs(),
+                "Terminal exceeded wall-clock timeout, killing"
+            );
+            if let Some(cgroup) = &tracked.cgroup {
+                cgroup.kill_all();
+            }
+            if let Err(e) = tracked.child.kill() {
+                tracing::warn!(
+                    terminal_id = %terminal_id,
+                    error = %e,
+                    "Failed to kill terminal after wall-clock timeout"
+                );
+            }
+        })
+    }
+
+    /// Start the asciicast recording of a session (best-effort); `resume`
+    /// appends to the recording of a reattached session.
+    fn start_recording(
+        &self,
+        terminal_id: &str,
+        session_id: &str,
+        cols: u16,
+        rows: u16,
+        resume: bool,
+    ) -> Option<Arc<SessionRecorder>> {
+        let config = self.recording.as_ref()?;
+        let recorder = if resume {
+            SessionRecorder::resume(config, terminal_id, session_id, cols, rows)
+        } else {
+            SessionRecorder::create(config, terminal_id, session_id, cols, rows)
+        };
+        match recorder {
+            Ok(recorder) => Some(Arc::new(recorder)),
+            Err(e) => {
+                tracing::warn!(
+                    terminal_id = %terminal_id,
+                    error = %e,
+                    "Failed to start terminal session recording"
+                );
+                None
+            }
+        }
     }
 
     async fn stop_reader_task_gracefully(terminal_id: &str, task: Option<JoinHandle<()>>) {
@@ -484,6 +782,8 @@ impl ProcessManager {
         terminal_id: &str,
         mut reader: PtyReader,
         output_fanout: Arc<OutputFanout>,
+        screen: SharedTerminalScreen,
+        recorder: Option<Arc<SessionRecorder>>,
     ) -> JoinHandle<()> {
         let terminal_id = terminal_id.to_string();
         tokio::task::spawn_blocking(move || {
@@ -495,6 +795,9 @@ impl ProcessManager {
                     Ok(0) => {
                         // EOF reached - flush any pending incomplete UTF-8 tail
                         if let Some(tail_text) = decoder.flush_lossy_tail() {
+                            if let Some(recorder) = &recorder {
+                                recorder.record_output(&tail_text);
+                            }
                             let _ = output_fanout.publish(tail_text, 0);
                         }
                         tracing::debug!(
@@ -504,7 +807,16 @@ impl ProcessManager {
                         break;
                     }
                     Ok(n) => {
+                        // Feed raw bytes so escape sequences split across reads
+                        // are reassembled by the VT parser.
+                        screen
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner())
+                            .process(&buf[..n]);
                         let decoded = decoder.decode_chunk(&buf[..n]);
+                        if let Some(recorder) = &recorder {
+                            recorder.record_output(&decoded.text);
+                        }
                         if !decoded.text.is_empty() || decoded.dropped_invalid_bytes > 0 {
                             let _ =
                                 output_fanout.publish(decoded.text, decoded.dropped_invalid_bytes);
@@ -535,6 +847,55 @@ impl ProcessManager {
         }))
     }
 
+    /// Start the command on a local PTY, or on the remote runner or under a
+    /// supervisor when configured.
+    async fn open_session(
+        &self,
+        terminal_id: &str,
+        session_id: &str,
+        config: &SpawnCommand,
+        wrapper: &[String],
+        size: PtySize,
+        resource_report: Option<&TerminalResourceReport>,
+    ) -> anyhow::Result<(Box<dyn MasterPty + Send>, Box<dyn Child + Send + Sync>)> {
+        if let Some(runner) = &self.remote_runner {
+            let spawn =
+                TerminalSpawnConfig::from_spawn_command(terminal_id, config, size.cols, size.rows);
+            let (master, child) = remote::launch(Arc::clone(runner), spawn).await?;
+            return Ok((Box::new(master), Box::new(child)));
+        }
+
+        #[cfg(unix)]
+        if let Some(supervisor) = &self.supervisor {
+            let spec = SupervisorSpec {
+                terminal_id: terminal_id.to_string(),
+                session_id: session_id.to_string(),
+                command: config.clone(),
+                wrapper: wrapper.to_vec(),
+                cols: size.cols,
+                rows: size.rows,
+                state_dir: supervisor.state_dir.clone(),
+                resource_report: resource_report.cloned(),
+            };
+            let session = supervisor::launch(supervisor, &spec).await?;
+            // Output produced before we connected is still new to every consumer
+            let master = session.master.with_initial_output(session.replay);
+            return Ok((Box::new(master), Box::new(session.child)));
+        }
+        #[cfg(not(unix))]
+        let _ = (session_id, resource_report);
+
+        // Open PTY pair (master + slave)
+        let pair = native_pty_system()
+            .openpty(size)
+            .map_err(|e| anyhow::anyhow!("Failed to open PTY: {e}"))?;
+        let child = pair
+            .slave
+            .spawn_command(build_command(terminal_id, config, wrapper))
+            .map_err(|e| anyhow::anyhow!("Failed to spawn terminal process: {e}"))?;
+        Ok((pair.master, child))
+    }
+
     /// Spawns a new terminal process with PTY using SpawnCommand configuration.
     ///
     /// This method provides process-level isolation by:
@@ -575,8 +936,44 @@ impl ProcessManager {
         });
         let mut codex_home_guard = CodexHomeGuard::new(terminal_id, codex_home.clone());
 
-        // Create PTY system
-        let pty_system = native_pty_system();
+        let session_id = Uuid::new_v4().to_string();
+
+        // Resolve resource limits up front: a limit that cannot be set up fails the spawn
+        let limits = config
+            .resource_limits
+            .as_ref()
+            .filter(|limits| !limits.is_empty());
+        let (sandbox, cgroup) = match limits {
+            // The local sandbox cannot confine a process on another host
+            Some(_) if self.remote_runner.is_some() => {
+                tracing::warn!(
+                    terminal_id = %terminal_id,
+                    "Resource limits other than the wall-clock timeout are not enforced on remote runners"
+                );
+                (None, None)
+            }
+            Some(limits) => {
+                let capabilities = SandboxCapabilities::detect();
+                let mut plan = SandboxPlan::build(limits, &capabilities)?;
+                let cgroup = match &capabilities.cgroup_root {
+                    Some(root) if !plan.cgroup_settings.is_empty() => {
+                        let cgroup = TerminalCgroup::create(
+                            root,
+                            terminal_id,
+                            &session_id,
+                            &plan.cgroup_settings,
+                        )?;
+                        plan.report.cgroup_path = Some(cgroup.path().display().to_string());
+                        // Join the cgroup first, before namespaces or rlimits are set up
+                        plan.wrapper.splice(0..0, cgroup.join_wrapper());
+                        Some(cgroup)
+                    }
+                    _ => None,
+                };
+                (Some(plan), cgroup)
+            }
+            None => (None, None),
+        };
 
         // Configure PTY size
         let size = PtySize {
@@ -586,87 +983,35 @@ impl ProcessManager {
             pixel_height: 0,
         };
 
-        // Open PTY pair (master + slave)
-        let pair = pty_system
-            .openpty(size)
-            .map_err(|e| anyhow::anyhow!("Failed to open PTY: {e}"))?;
-
-        // Build command
-        // On Windows, use cmd.exe /c to run commands so that .cmd/.bat files are found
-        #[cfg(windows)]
-        let mut cmd = {
-            let mut c = CommandBuilder::new("cmd.exe");
-            c.arg("/c");
-            c.arg(&config.command);
-            for arg in &config.args {
-                c.arg(arg);
-            }
-            c
-        };
-        #[cfg(not(windows))]
-        let mut cmd = {
-            let mut c = CommandBuilder::new(&config.command);
-            for arg in &config.args {
-                c.arg(arg);
-            }
-            c
-        };
-        cmd.cwd(&config.working_dir);
-
-        // Set environment variables for proper terminal behavior
-        cmd.env("TERM", "xterm-256color");
-        cmd.env("COLORTERM", "truecolor");
-
-        // UTF-8 encoding for Unix
-        #[cfg(unix)]
-        {
-            cmd.env("LANG", "C.UTF-8");
-            cmd.env("LC_ALL", "C.UTF-8");
-        }
-
-        // Remove inherited environment variables to prevent parent process pollution
-        // This must be done BEFORE setting new values to ensure clean isolation
-        for key in &config.env.unset {
-            cmd.env_remove(key);
-            tracing::debug!(
-                terminal_id = %terminal_id,
-                key = %key,
-                "Removed inherited env var"
-            );
-        }
-
-        // Inject custom environment variables for process-level isolation
-        for (key, value) in &config.env.set {
-            cmd.env(key, value);
-            // Redact sensitive values in logs
-            if SpawnEnv::is_sensitive_key(key) {
-                tracing::debug!(
-                    terminal_id = %terminal_id,
-                    key = %key,
-                    "Injected env var [REDACTED]"
-                );
-            } else {
-                tracing::debug!(
-                    terminal_id = %terminal_id,
-                    key = %key,
-                    value = %value,
-                    "Injected env var"
-                );
-            }
-        }
-
         // [G21-001] CODEX_HOME was already captured above (line ~558) and stored in
         // `codex_home_guard`. The duplicate parsing here previously overwrote the
         // guard-protected value. Removed to use the single source of truth.
 
         // Spawn child process on slave PTY
-        let mut child = pair
-            .slave
-            .spawn_command(cmd)
-            .map_err(|e| anyhow::anyhow!("Failed to spawn terminal process: {e}"))?;
+        let (master, mut child) = self
+            .open_session(
+                terminal_id,
+                &session_id,
+                config,
+                sandbox
+                    .as_ref()
+                    .map(|plan| plan.wrapper.as_slice())
+                    .unwrap_or_default(),
+                size,
+                sandbox.as_ref().map(|plan| &plan.report),
+            )
+            .await?;
 
         let pid = child.process_id().unwrap_or(0);
-        let session_id = Uuid::new_v4().to_string();
+
+        // A supervisor removes the cgroup itself once the command exits
+        let cgroup = match cgroup {
+            Some(cgroup) if self.is_supervised() => {
+                cgroup.detach();
+                None
+            }
+            cgroup => cgroup,
+        };
 
         // Wait a short time and check if the process is still alive
         // This catches cases where the command fails immediately (e.g., not found, permission denied)
@@ -689,11 +1034,15 @@ impl ProcessManager {
 
         // Initialize output fanout and background reader
         let output_fanout = Self::default_output_fanout();
-        let reader_task = match pair.master.try_clone_reader() {
+        let screen = TerminalScreen::shared(rows, cols);
+        let recorder = self.start_recording(terminal_id, &session_id, cols, rows, false);
+        let reader_task = match master.try_clone_reader() {
             Ok(reader) => Some(Self::spawn_output_reader_task(
                 terminal_id,
                 PtyReader(reader),
                 Arc::clone(&output_fanout),
+                Arc::clone(&screen),
+                recorder.clone(),
             )),
             Err(e) => {
                 tracing::warn!(
@@ -705,6 +1054,17 @@ impl ProcessManager {
             }
         };
 
+        let watchdog_task = limits
+            .and_then(|limits| limits.wall_clock_timeout_secs)
+            .map(|secs| {
+                self.spawn_wall_clock_watchdog(
+                    terminal_id,
+                    &session_id,
+                    Duration::from_secs(secs.into()),
+                )
+            });
+        let resource_report = sandbox.map(|plan| plan.report);
+
         // Store tracked process
         let mut processes = self.processes.write().await;
         processes.insert(
@@ -712,13 +1072,19 @@ impl ProcessManager {
             TrackedProcess {
                 session_id: session_id.clone(),
                 child,
-                master: Mutex::new(pair.master),
+                master: Mutex::new(master),
                 shared_writer: None,
                 codex_home,
                 output_fanout,
+                screen,
+                recorder,
                 reader_task,
                 logger_task: None,
                 logger_shutdown_tx: None,
+                cgroup,
+                watchdog_task,
+                resource_report: resource_report.clone(),
+                input_lease: Arc::new(InputLease::new()),
             },
         );
 
@@ -741,6 +1107,7 @@ impl ProcessManager {
             terminal_id: terminal_id.to_string(),
             reader: None,
             writer: None,
+            resource_report,
         })
     }
 
@@ -774,6 +1141,98 @@ impl ProcessManager {
             .await
     }
 
+    /// Track the terminals whose supervisors outlived the previous server.
+    ///
+    /// The supervisor's buffered output only seeds the screen model: it was
+    /// already logged and recorded before the restart. Recording continues in
+    /// the session's existing file.
+    #[cfg(unix)]
+    pub async fn reattach_supervised(&self) -> Vec<ProcessHandle> {
+        let Some(state_dir) = self.supervisor.as_ref().map(|s| s.state_dir.clone()) else {
+            return Vec::new();
+        };
+        let sessions = tokio::task::spawn_blocking(move || supervisor::reconnect_all(&state_dir))
+            .await
+            .unwrap_or_default();
+
+        let mut handles = Vec::with_capacity(sessions.len());
+        for session in sessions {
+            let info = session.info;
+            let terminal_id = info.terminal_id.clone();
+            let screen = TerminalScreen::shared(info.rows, info.cols);
+            screen
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .process(&session.replay);
+            let output_fanout = Self::default_output_fanout();
+            let recorder =
+                self.start_recording(&terminal_id, &info.session_id, info.cols, info.rows, true);
+            let reader_task = match session.master.try_clone_reader() {
+                Ok(reader) => Some(Self::spawn_output_reader_task(
+                    &terminal_id,
+                    PtyReader(reader),
+                    Arc::clone(&output_fanout),
+                    Arc::clone(&screen),
+                    recorder.clone(),
+                )),
+                Err(e) => {
+                    tracing::warn!(
+                        terminal_id = %terminal_id,
+                        error = %e,
+                        "Failed to initialize background PTY reader for fanout"
+                    );
+                    None
+                }
+            };
+            let watchdog_task = info.wall_clock_timeout_secs.map(|secs| {
+                let elapsed = (chrono::Utc::now() - info.started_at)
+                    .to_std()
+                    .unwrap_or_default();
+                self.spawn_wall_clock_watchdog(
+                    &terminal_id,
+                    &info.session_id,
+                    Duration::from_secs(secs.into()).saturating_sub(elapsed),
+                )
+            });
+
+            self.processes.write().await.insert(
+                terminal_id.clone(),
+                TrackedProcess {
+                    session_id: info.session_id.clone(),
+                    child: Box::new(session.child),
+                    master: Mutex::new(Box::new(session.master)),
+                    shared_writer: None,
+                    codex_home: info.codex_home,
+                    output_fanout,
+                    screen,
+                    recorder,
+                    reader_task,
+                    logger_task: None,
+                    logger_shutdown_tx: None,
+                    cgroup: None,
+                    watchdog_task,
+                    resource_report: info.resource_report.clone(),
+                    input_lease: Arc::new(InputLease::new()),
+                },
+            );
+            tracing::info!(
+                terminal_id = %terminal_id,
+                pid = info.pid,
+                supervisor_pid = info.supervisor_pid,
+                "Reattached supervised PTY session"
+            );
+            handles.push(ProcessHandle {
+                pid: info.pid,
+                session_id: info.session_id,
+                terminal_id,
+                reader: None,
+                writer: None,
+                resource_report: info.resource_report,
+            });
+        }
+        handles
+    }
+
     /// Resize terminal PTY
     ///
     /// # Arguments
@@ -800,6 +1259,14 @@ impl ProcessManager {
             master
                 .resize(size)
                 .map_err(|e| anyhow::anyhow!("Failed to resize PTY: {e}"))?;
+            tracked
+                .screen
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .resize(rows, cols);
+            if let Some(recorder) = &tracked.recorder {
+                recorder.record_resize(cols, rows);
+            }
 
             tracing::debug!(
                 terminal_id = %terminal_id,
@@ -994,7 +1461,10 @@ impl ProcessManager {
 
                 match master.take_writer() {
                     Ok(w) => {
-                        tracked.shared_writer = Some(Arc::new(Mutex::new(PtyWriter(w))));
+                        tracked.shared_writer = Some(Arc::new(Mutex::new(PtyWriter {
+                            inner: w,
+                            recorder: tracked.recorder.clone(),
+                        })));
                         tracing::debug!(
                             terminal_id = %terminal_id,
                             "Initialized shared PTY writer"
@@ -1023,6 +1493,7 @@ impl ProcessManager {
                 // Reader is now owned by background fanout task (single-reader constraint)
                 reader: None,
                 writer,
+                resource_report: tracked.resource_report.clone(),
             })
         } else {
             None
@@ -1048,6 +1519,29 @@ impl ProcessManager {
         Ok(tracked.output_fanout.subscribe(from_seq))
     }
 
+    /// Snapshot of the rendered terminal screen.
+    ///
+    /// Returns None if terminal doesn't exist.
+    pub async fn screen_snapshot(&self, terminal_id: &str) -> Option<ScreenSnapshot> {
+        let processes = self.processes.read().await;
+        let tracked = processes.get(terminal_id)?;
+        let screen = tracked
+            .screen
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Some(screen.snapshot())
+    }
+
+    /// Input lease of the terminal's current session.
+    ///
+    /// Returns None if terminal doesn't exist.
+    pub async fn input_lease(&self, terminal_id: &str) -> Option<Arc<InputLease>> {
+        let processes = self.processes.read().await;
+        processes
+            .get(terminal_id)
+            .map(|tracked| Arc::clone(&tracked.input_lease))
+    }
+
     /// Get latest emitted output sequence for a terminal.
     ///
     /// Returns None if terminal doesn't exist, or 0 if no output has been emitted yet.
@@ -1102,7 +1596,7 @@ impl ProcessManager {
                         match recv_result {
                             Ok(chunk) => {
                                 if !chunk.text.is_empty() {
-                                    logger.append(&chunk.text).await;
+                                    logger.append_output(chunk.seq, &chunk.text).await;
                                 }
                                 if chunk.dropped_invalid_bytes > 0 {
                                     logger
@@ -1257,8 +1751,14 @@ pub const DEFAULT_MAX_BUFFER_SIZE: usize = 1000;
 /// Prevents unbounded memory growth when the flush worker is slower than output.
 const MAX_BUFFER_BYTES: usize = 10 * 1024 * 1024;
 
+/// Buffered log line and the output chunk it came from
+struct LogEntry {
+    seq: Option<u64>,
+    content: String,
+}
+
 pub struct TerminalLogger {
-    buffer: Arc<RwLock<Vec<String>>>,
+    buffer: Arc<RwLock<Vec<LogEntry>>>,
     /// Approximate byte size of buffered entries (tracked to avoid O(n) recount).
     buffer_bytes: Arc<std::sync::atomic::AtomicUsize>,
     flush_lock: Arc<AsyncMutex<()>>,
@@ -1357,25 +1857,26 @@ impl TerminalLogger {
         db: &DBService,
         terminal_id: &str,
         log_type: &str,
-        entries: &[String],
+        entries: &[LogEntry],
     ) -> anyhow::Result<()> {
         if entries.is_empty() {
             return Ok(());
         }
 
         let mut tx = db.pool.begin().await?;
-        for line in entries {
+        for entry in entries {
             sqlx::query(
                 r"
-                INSERT INTO terminal_log (id, terminal_id, log_type, content, created_at)
-                VALUES (?1, ?2, ?3, ?4, ?5)
+                INSERT INTO terminal_log (id, terminal_id, log_type, content, created_at, seq)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
                 ",
             )
             .bind(Uuid::new_v4().to_string())
             .bind(terminal_id)
             .bind(log_type)
-            .bind(line)
+            .bind(&entry.content)
             .bind(chrono::Utc::now())
+            .bind(entry.seq.and_then(|seq| i64::try_from(seq).ok()))
             .execute(&mut *tx)
             .await?;
         }
@@ -1384,7 +1885,7 @@ impl TerminalLogger {
     }
 
     async fn flush_buffer(
-        buffer: &Arc<RwLock<Vec<String>>>,
+        buffer: &Arc<RwLock<Vec<LogEntry>>>,
         flush_lock: &Arc<AsyncMutex<()>>,
         db: &DBService,
         terminal_id: &str,
@@ -1565,6 +2066,16 @@ impl TerminalLogger {
     }
 
     pub async fn append(&self, line: &str) {
+        self.push_entry(None, line).await;
+    }
+
+    /// Append text decoded from the PTY output chunk `seq`, so log search hits
+    /// can point back into the output stream.
+    pub async fn append_output(&self, seq: u64, text: &str) {
+        self.push_entry(Some(seq), text).await;
+    }
+
+    async fn push_entry(&self, seq: Option<u64>, line: &str) {
         if self.persistence_disabled.load(Ordering::Relaxed) {
             return;
         }
@@ -1572,7 +2083,10 @@ impl TerminalLogger {
         let should_flush = {
             let mut buffer = self.buffer.write().await;
             let line_bytes = line.len();
-            buffer.push(line.to_string());
+            buffer.push(LogEntry {
+                seq,
+                content: line.to_string(),
+            });
             let current_bytes = self.buffer_bytes.fetch_add(line_bytes, Ordering::Relaxed) + line_bytes;
             // [G09-006] Flush when entry count OR byte size exceeds limits
             buffer.len() >= self.max_buffer_size || current_bytes >= MAX_BUFFER_BYTES
@@ -1638,6 +2152,7 @@ impl TerminalLogger {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::terminal::recording::{InputCapture, RecordingConfig, recording_path};
 
     fn build_test_spawn_command(working_dir: &Path) -> SpawnCommand {
         #[cfg(windows)]
@@ -1677,7 +2192,8 @@ mod tests {
                 terminal_id TEXT NOT NULL,
                 log_type TEXT NOT NULL,
                 content TEXT NOT NULL,
-                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                seq INTEGER
             )",
         )
         .execute(&db.pool)
@@ -1718,7 +2234,8 @@ mod tests {
                 terminal_id TEXT NOT NULL REFERENCES terminal(id) ON DELETE CASCADE,
                 log_type TEXT NOT NULL,
                 content TEXT NOT NULL,
-                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                seq INTEGER
             )",
         )
         .execute(&db.pool)
@@ -1987,6 +2504,176 @@ mod tests {
         .await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_is_recorded_and_rendered_on_screen() {
+        let recordings_dir = tempfile::tempdir().unwrap();
+        let manager = ProcessManager::new().with_recording(
+            RecordingConfig::new(recordings_dir.path().to_path_buf())
+                .with_input(InputCapture::Full),
+        );
+        let temp_dir = tempfile::tempdir().unwrap();
+        let spawn_config = SpawnCommand::new("cat", temp_dir.path());
+
+        let handle = tokio::time::timeout(
+            Duration::from_secs(10),
+            manager.spawn_pty_with_config("test-terminal", &spawn_config, 80, 24),
+        )
+        .await
+        .expect("spawn_pty_with_config should not hang")
+        .unwrap();
+
+        let writer = manager
+            .get_handle("test-terminal")
+            .await
+            .unwrap()
+            .writer
+            .unwrap();
+        {
+            let mut writer = writer.lock().unwrap();
+            writer.write_all(b"hello screen\n").unwrap();
+            writer.flush().unwrap();
+        }
+
+        // PTY echo is rendered on the virtual screen
+        let mut rendered = false;
+        for _ in 0..50 {
+            let snapshot = manager.screen_snapshot("test-terminal").await.unwrap();
+            if snapshot.text().contains("hello screen") {
+                rendered = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(rendered, "echoed input should appear on the screen");
+        manager.resize("test-terminal", 100, 30).await.unwrap();
+
+        let _ = tokio::time::timeout(
+            Duration::from_secs(10),
+            manager.kill_terminal("test-terminal"),
+        )
+        .await;
+
+        let path =
+            recording_path(recordings_dir.path(), "test-terminal", &handle.session_id).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        let events = content
+            .lines()
+            .skip(1)
+            .map(|line| serde_json::from_str::<(f64, String, String)>(line).unwrap())
+            .collect::<Vec<_>>();
+        let has_event = |code: &str, text: &str| {
+            events
+                .iter()
+                .any(|(_, event_code, data)| event_code == code && data.contains(text))
+        };
+        assert!(has_event("i", "hello screen\n"));
+        assert!(has_event("o", "hello screen"));
+        assert!(has_event("r", "100x30"));
+    }
+
+    #[tokio::test]
+    async fn test_wall_clock_timeout_kills_terminal() {
+        let manager = ProcessManager::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let spawn_config = build_test_spawn_command(temp_dir.path()).with_resource_limits(
+            TerminalResourceLimits {
+                wall_clock_timeout_secs: Some(1),
+                ..Default::default()
+            },
+        );
+
+        let handle = tokio::time::timeout(
+            Duration::from_secs(10),
+            manager.spawn_pty_with_config("test-terminal", &spawn_config, 80, 24),
+        )
+        .await
+        .expect("spawn_pty_with_config should not hang")
+        .unwrap();
+        assert!(handle.resource_report.is_some());
+        assert!(manager.is_running("test-terminal").await);
+
+        let mut killed = false;
+        for _ in 0..50 {
+            if !manager.is_running("test-terminal").await {
+                killed = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(
+            killed,
+            "terminal should be killed after its wall-clock timeout"
+        );
+        manager.cleanup().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_reattach_supervised_session_after_restart() {
+        let state_dir = tempfile::tempdir().unwrap();
+        let spec = SupervisorSpec {
+            terminal_id: "test-terminal".to_string(),
+            session_id: "session-1".to_string(),
+            command: SpawnCommand::new("cat", state_dir.path()),
+            wrapper: Vec::new(),
+            cols: 80,
+            rows: 24,
+            state_dir: state_dir.path().to_path_buf(),
+            resource_report: None,
+        };
+        // Stands in for a supervisor left running by the previous server
+        let supervisor_thread =
+            std::thread::spawn(move || supervisor::serve(spec, &mut Vec::new()));
+        let manifest = state_dir.path().join("test-terminal.json");
+        for _ in 0..100 {
+            if manifest.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let manager = ProcessManager::new().with_supervisor(SupervisorConfig {
+            program: PathBuf::from("/nonexistent"),
+            args: Vec::new(),
+            state_dir: state_dir.path().to_path_buf(),
+        });
+        let handles = manager.reattach_supervised().await;
+        assert_eq!(handles.len(), 1);
+        assert_eq!(handles[0].session_id, "session-1");
+        assert!(manager.is_running("test-terminal").await);
+
+        let writer = manager
+            .get_handle("test-terminal")
+            .await
+            .unwrap()
+            .writer
+            .unwrap();
+        {
+            let mut writer = writer.lock().unwrap();
+            writer.write_all(b"still alive\n").unwrap();
+            writer.flush().unwrap();
+        }
+        let mut rendered = false;
+        for _ in 0..50 {
+            let snapshot = manager.screen_snapshot("test-terminal").await.unwrap();
+            if snapshot.text().contains("still alive") {
+                rendered = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(rendered, "reattached terminal should echo input");
+
+        let _ = tokio::time::timeout(
+            Duration::from_secs(10),
+            manager.kill_terminal("test-terminal"),
+        )
+        .await;
+        assert!(supervisor_thread.join().unwrap().is_ok());
+        assert!(!manifest.exists());
+    }
+
     #[tokio::test]
     async fn test_get_handle_for_nonexistent_terminal() {
         let manager = ProcessManager::new();
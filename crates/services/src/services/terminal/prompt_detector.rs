@@ -18,6 +18,8 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use super::screen::{CellStyle, ScreenLine, ScreenSnapshot};
+
 fn compile_regex(pattern: &'static str, regex_name: &'static str) -> Option<Regex> {
     match Regex::new(pattern) {
         Ok(regex) => Some(regex),
@@ -243,6 +245,14 @@ static SELECT_MARKER_RE: Lazy<Option<Regex>> = Lazy::new(|| {
 static SELECTED_MARKER_RE: Lazy<Option<Regex>> =
     Lazy::new(|| compile_regex(r"^[\s]*(>|\*|\[x\]|\(x\)|•)", "SELECTED_MARKER_RE"));
 
+/// Menu option row on a rendered screen: optional marker, optional number, label
+static SCREEN_OPTION_RE: Lazy<Option<Regex>> = Lazy::new(|| {
+    compile_regex(
+        r"^\s*(?P<mark>[❯›>▸▶●◉○◯•*]|\[[xX ]\]|\([xX ]\))?\s*(?P<num>\d{1,2}[.)])?\s+(?P<label>\S.*)$",
+        "SCREEN_OPTION_RE",
+    )
+});
+
 /// Choice detection (single-line options with multiple choices)
 static CHOICE_RE: Lazy<Option<Regex>> = Lazy::new(|| {
     // Matches: "Choose:", "Select [", or patterns like [a/b/c], (1/2/3)
@@ -284,6 +294,12 @@ static ENTER_CONFIRM_RE: Lazy<Option<Regex>> = Lazy::new(|| {
     )
 });
 
+/// Footer rows (key hints, borders) allowed below a menu on screen
+const SCREEN_MENU_MAX_TRAILING_LINES: usize = 4;
+
+/// Question rows above a menu included in the prompt text
+const SCREEN_MENU_CONTEXT_LINES: usize = 2;
+
 /// Dangerous keywords that should trigger LLM decision or user confirmation
 static DANGEROUS_KEYWORDS_RE: Lazy<Option<Regex>> = Lazy::new(|| {
     compile_regex(
@@ -544,6 +560,194 @@ impl PromptDetector {
     pub fn has_dangerous_keywords(&self, text: &str) -> bool {
         regex_is_match(DANGEROUS_KEYWORDS_RE.as_ref(), text)
     }
+
+    /// Detect a prompt from a rendered screen instead of output lines.
+    ///
+    /// Menus are read from the rows near the bottom of the screen; the selected
+    /// option comes from cell attributes (inverse/background, or the one label
+    /// styled differently) before falling back to selection markers. Other
+    /// prompt kinds are detected on the cursor row.
+    pub fn detect_screen(&self, screen: &ScreenSnapshot) -> Option<DetectedPrompt> {
+        let lines = screen.active_lines();
+        let active_line = lines
+            .get(screen.cursor_row)
+            .filter(|line| !line.text.trim().is_empty())
+            .or_else(|| lines.iter().rfind(|line| !line.text.trim().is_empty()))?;
+        let active_text = normalize_text_for_detection(&active_line.text);
+
+        if self.detect_password(active_text.trim()) {
+            return Some(DetectedPrompt::new(
+                PromptKind::Password,
+                active_text.trim().to_string(),
+                0.95,
+            ));
+        }
+        if let Some(prompt) = detect_screen_menu(lines) {
+            return Some(prompt);
+        }
+        self.detect(&active_text)
+    }
+}
+
+// ============================================================================
+// Screen Menu Detection
+// ============================================================================
+
+struct ScreenOption<'a> {
+    label: String,
+    /// Marker or number in front of the label
+    explicit: bool,
+    /// Marker that denotes the current selection (`❯`, `>`, `[x]`, ...)
+    marker_selected: bool,
+    line: &'a ScreenLine,
+}
+
+fn parse_screen_option(line: &ScreenLine) -> Option<ScreenOption<'_>> {
+    let text = normalize_text_for_detection(&line.text);
+    let caps = regex_captures(SCREEN_OPTION_RE.as_ref(), &text)?;
+    let label = caps.name("label")?.as_str().trim().to_string();
+    let mark = caps.name("mark").map(|m| m.as_str());
+    let explicit = mark.is_some() || caps.name("num").is_some();
+    let indent = text.len() - text.trim_start().len();
+    if !explicit && (indent < 2 || label.ends_with('?') || label.contains(':')) {
+        return None;
+    }
+    Some(ScreenOption {
+        label,
+        explicit,
+        marker_selected: mark.is_some_and(|m| !matches!(m, "○" | "◯" | "[ ]" | "( )")),
+        line,
+    })
+}
+
+/// Index of the one option whose label is styled differently from the rest.
+fn distinct_style_index(options: &[ScreenOption<'_>]) -> Option<usize> {
+    let styles = options
+        .iter()
+        .map(|option| option.line.label_style.unwrap_or_default())
+        .collect::<Vec<_>>();
+    let candidates = (0..styles.len())
+        .filter(|&i| {
+            let mut others = styles
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, style)| style);
+            let Some(first) = others.next() else {
+                return false;
+            };
+            *first != styles[i] && others.all(|style| style == first)
+        })
+        .collect::<Vec<_>>();
+    match candidates.as_slice() {
+        [index] => Some(*index),
+        // Two options styled differently: the plain one is not the selection.
+        [a, b] => match (
+            styles[*a] == CellStyle::default(),
+            styles[*b] == CellStyle::default(),
+        ) {
+            (true, false) => Some(*b),
+            (false, true) => Some(*a),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn detect_screen_menu(lines: &[ScreenLine]) -> Option<DetectedPrompt> {
+    // Last block of option rows, allowing a few footer rows below it.
+    let mut end = lines.len();
+    let mut trailing = 0;
+    while end > 0 && parse_screen_option(&lines[end - 1]).is_none() {
+        if !lines[end - 1].text.trim().is_empty() {
+            trailing += 1;
+            if trailing > SCREEN_MENU_MAX_TRAILING_LINES {
+                return None;
+            }
+        }
+        end -= 1;
+    }
+    let mut start = end;
+    while start > 0 && parse_screen_option(&lines[start - 1]).is_some() {
+        start -= 1;
+    }
+    let options = lines[start..end]
+        .iter()
+        .filter_map(parse_screen_option)
+        .collect::<Vec<_>>();
+
+    let context_start = lines[..start]
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, line)| !line.text.trim().is_empty())
+        .take(SCREEN_MENU_CONTEXT_LINES)
+        .last()
+        .map_or(start, |(row, _)| row);
+    let raw_text = lines[context_start..end]
+        .iter()
+        .map(|line| {
+            normalize_text_for_detection(&line.text)
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let has_arrow_hint = lines[context_start..]
+        .iter()
+        .any(|line| regex_is_match(ARROW_HINT_RE.as_ref(), &line.text));
+
+    let unique_label_count = options
+        .iter()
+        .map(|option| option.label.to_ascii_lowercase())
+        .collect::<HashSet<_>>()
+        .len();
+    if options.len() < 2
+        || unique_label_count < 2
+        || !(has_arrow_hint || options.iter().any(|option| option.explicit))
+    {
+        return None;
+    }
+
+    let emphasized = options
+        .iter()
+        .enumerate()
+        .filter(|(_, option)| option.line.emphasized)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let marked = options
+        .iter()
+        .enumerate()
+        .filter(|(_, option)| option.marker_selected)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let (selected_index, confidence) = match (emphasized.as_slice(), marked.as_slice()) {
+        ([index], _) => (*index, 0.95),
+        _ => match (distinct_style_index(&options), marked.as_slice()) {
+            (Some(index), _) => (index, 0.95),
+            (None, [index]) => (*index, 0.9),
+            // Several "selected" markers: checkbox list without a cursor row.
+            (None, [_, _, ..]) if !has_arrow_hint => return None,
+            _ if has_arrow_hint => (0, 0.8),
+            _ => (0, 0.75),
+        },
+    };
+
+    let options = options
+        .into_iter()
+        .enumerate()
+        .map(|(index, option)| ArrowSelectOption {
+            index,
+            label: option.label,
+            selected: index == selected_index,
+        })
+        .collect();
+    Some(DetectedPrompt::arrow_select(
+        raw_text,
+        confidence,
+        options,
+        selected_index,
+    ))
 }
 
 // ============================================================================
@@ -587,6 +791,7 @@ pub fn build_arrow_sequence(current: usize, target: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::terminal::screen::TerminalScreen;
 
     #[test]
     fn test_detect_password() {
@@ -815,4 +1020,67 @@ mod tests {
             "streaming status-line bypass indicator should be ignored"
         );
     }
+
+    fn render_screen(bytes: &[u8]) -> ScreenSnapshot {
+        let mut screen = TerminalScreen::new(12, 60);
+        screen.process(bytes);
+        screen.snapshot()
+    }
+
+    #[test]
+    fn test_detect_screen_reads_selection_from_inverse_row_after_repaint() {
+        let detector = PromptDetector::new();
+        let mut screen = TerminalScreen::new(12, 60);
+        screen.process(
+            b"Select a model\r\n\x1b[7m  Sonnet\x1b[0m\r\n  Opus\r\n  Haiku\r\n\r\n\
+              Use arrow keys to navigate\r\n",
+        );
+        // Cursor moves down: the TUI repaints rows 2 and 3 in place.
+        screen.process(b"\x1b[2;1H\x1b[2K  Sonnet\x1b[3;1H\x1b[2K\x1b[7m  Opus\x1b[0m\x1b[6;1H");
+
+        let prompt = detector
+            .detect_screen(&screen.snapshot())
+            .expect("menu should be detected on screen");
+        assert_eq!(prompt.kind, PromptKind::ArrowSelect);
+        assert_eq!(prompt.selected_index, Some(1));
+        let labels = prompt
+            .options
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|option| option.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["Sonnet", "Opus", "Haiku"]);
+        assert!(prompt.raw_text.starts_with("Select a model"));
+        assert!(prompt.confidence >= 0.95);
+    }
+
+    #[test]
+    fn test_detect_screen_prefers_styled_label_over_marker_position() {
+        let detector = PromptDetector::new();
+        // Marker column is blank for every row; only the label color tells.
+        let snapshot = render_screen(
+            "Do you want to proceed?\r\n  1. Yes\r\n  \x1b[36m2. Yes, and don't ask again\x1b[0m\r\n  3. No\r\n"
+                .as_bytes(),
+        );
+
+        let prompt = detector.detect_screen(&snapshot).unwrap();
+        assert_eq!(prompt.kind, PromptKind::ArrowSelect);
+        assert_eq!(prompt.selected_index, Some(1));
+        assert!(prompt.options.unwrap()[1].selected);
+    }
+
+    #[test]
+    fn test_detect_screen_falls_back_to_cursor_row_prompt() {
+        let detector = PromptDetector::new();
+        let snapshot = render_screen(b"Building...\r\ndone\r\nContinue? [y/N] ");
+
+        let prompt = detector.detect_screen(&snapshot).unwrap();
+        assert_eq!(prompt.kind, PromptKind::YesNo);
+        assert!(
+            detector
+                .detect_screen(&render_screen(b"compiling crate\r\n"))
+                .is_none()
+        );
+    }
 }
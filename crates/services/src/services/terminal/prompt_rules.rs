@@ -0,0 +1,829 @@
+//! Prompt Rule Packs
+//!
+//! Declarative, CLI-specific prompt rules for [`PromptWatcher`]: regex patterns
+//! (optionally accumulated across output chunks within a context window),
+//! per-rule debounce windows and the input to send back.
+//!
+//! Packs are YAML (or JSON) documents. The built-in pack ships with the binary;
+//! packs in the rules directory are evaluated first, loaded at startup and
+//! hot-reloaded when the directory changes. Captured PTY transcripts (asciicast
+//! v2) can be replayed against a rule set with [`replay_transcript`].
+//!
+//! [`PromptWatcher`]: super::prompt_watcher::PromptWatcher
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use regex::Regex;
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+
+use super::prompt_detector::{PromptKind, normalize_text_for_detection};
+
+/// Built-in pack, evaluated after all directory packs
+const BUILTIN_PACK_YAML: &str = include_str!("../../../prompt_rules/builtin.yaml");
+
+/// File extensions picked up from the rules directory
+const PACK_EXTENSIONS: [&str; 3] = ["yaml", "yml", "json"];
+
+// ============================================================================
+// Pack Documents
+// ============================================================================
+
+/// A named set of prompt rules
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PromptRulePack {
+    pub name: String,
+    #[serde(default)]
+    pub rules: Vec<PromptRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PromptRule {
+    /// Unique id; a directory pack rule replaces a later rule with the same id
+    pub id: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// CLI types the rule applies to (`cli-codex` or `codex`); empty = all
+    #[serde(default)]
+    pub cli_types: Vec<String>,
+    #[serde(default)]
+    pub when: RuleConditions,
+    /// Minimum gap between two firings of this rule on one terminal
+    pub debounce_ms: Option<u64>,
+    /// Re-send the response while the prompt stays on screen
+    pub retry: Option<RuleRetry>,
+    /// Only fire when auto-confirm is on for the terminal; workflow
+    /// unblockers that must also run in manual mode turn this off
+    #[serde(default = "default_true")]
+    pub require_auto_confirm: bool,
+    /// Required unless the rule is disabled
+    pub response: Option<RuleResponse>,
+}
+
+/// Patterns are matched against ANSI-stripped output chunks.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RuleConditions {
+    /// Every pattern must match: in the same chunk, or within `contextSecs`
+    /// of each other when set
+    #[serde(default)]
+    pub all: Vec<String>,
+    /// At least one pattern must match the chunk
+    #[serde(default)]
+    pub any: Vec<String>,
+    /// No pattern may match the chunk
+    #[serde(default)]
+    pub none: Vec<String>,
+    pub context_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RuleResponse {
+    /// Prompt kind reported to the prompt state machine
+    pub kind: PromptKind,
+    /// Input to send; `{workflow_id}`, `{task_id}` and `{terminal_id}` are expanded
+    pub input: String,
+    pub reasoning: String,
+    pub target_index: Option<usize>,
+    /// Send a separate Enter after the input
+    #[serde(default)]
+    pub submit_enter: bool,
+    /// Write to the PTY directly, falling back to the message bus
+    #[serde(default)]
+    pub direct: bool,
+}
+
+/// Re-sends of a rule response after its first firing. Inside the window the
+/// rule only fires as a retry; `debounceMs` applies once the window has passed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RuleRetry {
+    /// Minimum gap between the previous response and a re-send
+    pub after_ms: u64,
+    /// Re-sends stop this long after the first response
+    pub within_secs: u64,
+    #[serde(default = "default_retry_times")]
+    pub times: u32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_retry_times() -> u32 {
+    1
+}
+
+impl PromptRulePack {
+    /// Parses a YAML or JSON pack document.
+    pub fn parse(content: &str) -> Result<Self> {
+        let pack: Self = if content.trim_start().starts_with('{') {
+            serde_json::from_str(content).map_err(|e| anyhow!("Invalid rule pack JSON: {e}"))?
+        } else {
+            serde_yaml::from_str(content).map_err(|e| anyhow!("Invalid rule pack YAML: {e}"))?
+        };
+        if pack.name.trim().is_empty() {
+            bail!("Rule pack name is required");
+        }
+        Ok(pack)
+    }
+
+    /// The pack compiled into the binary.
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_PACK_YAML).expect("built-in prompt rule pack must parse")
+    }
+}
+
+impl RuleResponse {
+    /// Input with terminal placeholders expanded.
+    pub fn render_input(&self, workflow_id: &str, task_id: &str, terminal_id: &str) -> String {
+        self.input
+            .replace("{workflow_id}", workflow_id)
+            .replace("{task_id}", task_id)
+            .replace("{terminal_id}", terminal_id)
+    }
+}
+
+// ============================================================================
+// Compiled Rules
+// ============================================================================
+
+#[derive(Debug)]
+struct CompiledRule {
+    id: String,
+    cli_types: Vec<String>,
+    all: Vec<Regex>,
+    any: Vec<Regex>,
+    none: Vec<Regex>,
+    context: Option<Duration>,
+    debounce: Option<Duration>,
+    retry: Option<CompiledRetry>,
+    require_auto_confirm: bool,
+    response: RuleResponse,
+}
+
+#[derive(Debug)]
+struct CompiledRetry {
+    after: Duration,
+    within: Duration,
+    times: u32,
+}
+
+impl CompiledRule {
+    fn compile(pack: &str, rule: &PromptRule) -> Result<Self> {
+        let context = || format!("rule pack '{pack}', rule '{}'", rule.id);
+        let response = rule
+            .response
+            .clone()
+            .ok_or_else(|| anyhow!("{}: response is required", context()))?;
+        if response.input.is_empty() {
+            bail!("{}: response.input must not be empty", context());
+        }
+        if rule.when.all.is_empty() && rule.when.any.is_empty() {
+            bail!("{}: when.all or when.any must list a pattern", context());
+        }
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    Regex::new(pattern)
+                        .with_context(|| format!("{}: invalid pattern '{pattern}'", context()))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(Self {
+            id: rule.id.clone(),
+            cli_types: rule.cli_types.clone(),
+            all: compile(&rule.when.all)?,
+            any: compile(&rule.when.any)?,
+            none: compile(&rule.when.none)?,
+            context: rule.when.context_secs.map(Duration::from_secs),
+            debounce: rule.debounce_ms.map(Duration::from_millis),
+            retry: rule.retry.as_ref().map(|retry| CompiledRetry {
+                after: Duration::from_millis(retry.after_ms),
+                within: Duration::from_secs(retry.within_secs),
+                times: retry.times,
+            }),
+            require_auto_confirm: rule.require_auto_confirm,
+            response,
+        })
+    }
+
+    fn applies_to(&self, cli_type: Option<&str>, auto_confirm: bool) -> bool {
+        if self.require_auto_confirm && !auto_confirm {
+            return false;
+        }
+        if self.cli_types.is_empty() {
+            return true;
+        }
+        let Some(cli_type) = cli_type else {
+            return false;
+        };
+        let short = cli_type.strip_prefix("cli-").unwrap_or(cli_type);
+        self.cli_types.iter().any(|t| t == cli_type || t == short)
+    }
+}
+
+/// Rules of all loaded packs in evaluation order
+#[derive(Debug)]
+pub struct PromptRuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl PromptRuleSet {
+    /// Compiles packs in priority order. The first rule with a given id wins,
+    /// so a disabled rule also hides same-id rules of later packs.
+    pub fn from_packs(packs: &[PromptRulePack]) -> Result<Self> {
+        let mut seen = HashSet::new();
+        let mut rules = Vec::new();
+        for pack in packs {
+            for rule in &pack.rules {
+                if rule.id.trim().is_empty() {
+                    bail!("rule pack '{}': rule id is required", pack.name);
+                }
+                if !seen.insert(rule.id.as_str()) || !rule.enabled {
+                    continue;
+                }
+                rules.push(CompiledRule::compile(&pack.name, rule)?);
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn builtin() -> Self {
+        Self::from_packs(&[PromptRulePack::builtin()])
+            .expect("built-in prompt rule pack must compile")
+    }
+
+    pub fn rule_ids(&self) -> Vec<&str> {
+        self.rules.iter().map(|rule| rule.id.as_str()).collect()
+    }
+}
+
+// ============================================================================
+// Per-terminal Matching
+// ============================================================================
+
+/// A rule that matched an output chunk
+#[derive(Debug, Clone)]
+pub struct RuleHit {
+    pub rule_id: String,
+    pub response: RuleResponse,
+    /// Re-send of a response while the prompt is still on screen
+    pub retry: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Firing {
+    first: Instant,
+    last: Instant,
+    retries: u32,
+}
+
+/// Per-terminal rule state: context-window markers and last firing times
+#[derive(Debug, Default)]
+pub struct PromptRuleMatcher {
+    /// rule id -> when each `all` pattern last matched
+    context: HashMap<String, Vec<Option<Instant>>>,
+    fired: HashMap<String, Firing>,
+    matched: bool,
+}
+
+impl PromptRuleMatcher {
+    /// Feeds a normalized output chunk and returns the first matching rule
+    /// that is not inside its debounce window.
+    ///
+    /// Every applicable rule sees the chunk so context windows stay up to date.
+    pub fn observe(
+        &mut self,
+        rules: &PromptRuleSet,
+        cli_type: Option<&str>,
+        auto_confirm: bool,
+        text: &str,
+        now: Instant,
+    ) -> Option<RuleHit> {
+        let mut hit = None;
+        self.matched = false;
+        for rule in rules
+            .rules
+            .iter()
+            .filter(|rule| rule.applies_to(cli_type, auto_confirm))
+        {
+            if !self.matches(rule, text, now) {
+                continue;
+            }
+            self.matched = true;
+            if hit.is_none()
+                && let Some(retry) = self.may_fire(rule, now)
+            {
+                hit = Some(RuleHit {
+                    rule_id: rule.id.clone(),
+                    response: rule.response.clone(),
+                    retry,
+                });
+            }
+        }
+        hit
+    }
+
+    /// Whether a rule matched the last observed chunk, including rules that
+    /// did not fire because of debounce or retry limits.
+    pub fn matched(&self) -> bool {
+        self.matched
+    }
+
+    /// Records that a rule's response was sent.
+    pub fn mark_fired(&mut self, hit: &RuleHit, now: Instant) {
+        self.context.remove(&hit.rule_id);
+        match self.fired.get_mut(&hit.rule_id) {
+            Some(firing) if hit.retry => {
+                firing.last = now;
+                firing.retries += 1;
+            }
+            _ => {
+                self.fired.insert(
+                    hit.rule_id.clone(),
+                    Firing {
+                        first: now,
+                        last: now,
+                        retries: 0,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Drops accumulated context markers.
+    pub fn clear(&mut self) {
+        self.context.clear();
+    }
+
+    fn matches(&mut self, rule: &CompiledRule, text: &str, now: Instant) -> bool {
+        let all_matched = match rule.context {
+            None => rule.all.iter().all(|re| re.is_match(text)),
+            Some(window) => {
+                let seen = self.context.entry(rule.id.clone()).or_default();
+                seen.resize(rule.all.len(), None);
+                for (slot, re) in seen.iter_mut().zip(&rule.all) {
+                    if re.is_match(text) {
+                        *slot = Some(now);
+                    }
+                }
+                seen.iter()
+                    .all(|ts| ts.is_some_and(|ts| now.duration_since(ts) <= window))
+            }
+        };
+
+        all_matched
+            && (rule.any.is_empty() || rule.any.iter().any(|re| re.is_match(text)))
+            && !rule.none.iter().any(|re| re.is_match(text))
+    }
+
+    /// `None` while the rule may not fire, otherwise whether firing is a retry.
+    fn may_fire(&self, rule: &CompiledRule, now: Instant) -> Option<bool> {
+        let Some(firing) = self.fired.get(&rule.id) else {
+            return Some(false);
+        };
+        if let Some(retry) = &rule.retry
+            && now.duration_since(firing.first) <= retry.within
+        {
+            let due =
+                firing.retries < retry.times && now.duration_since(firing.last) >= retry.after;
+            return due.then_some(true);
+        }
+        let debounced = rule
+            .debounce
+            .is_some_and(|window| now.duration_since(firing.last) < window);
+        (!debounced).then_some(false)
+    }
+
+    /// Moves all recorded match and firing times `by` into the past.
+    #[cfg(test)]
+    pub(crate) fn rewind(&mut self, by: Duration) {
+        for markers in self.context.values_mut() {
+            for ts in markers.iter_mut().flatten() {
+                *ts -= by;
+            }
+        }
+        for firing in self.fired.values_mut() {
+            firing.first -= by;
+            firing.last -= by;
+        }
+    }
+}
+
+// ============================================================================
+// Loading and Hot Reload
+// ============================================================================
+
+type DirFingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+/// Current rule set, reloaded from the rules directory when it changes
+pub struct PromptRuleStore {
+    dir: Option<PathBuf>,
+    current: RwLock<Arc<PromptRuleSet>>,
+    fingerprint: Mutex<DirFingerprint>,
+}
+
+impl PromptRuleStore {
+    /// Built-in rules only.
+    pub fn builtin() -> Self {
+        Self {
+            dir: None,
+            current: RwLock::new(Arc::new(PromptRuleSet::builtin())),
+            fingerprint: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Built-in rules plus the packs in `dir`. Invalid packs are logged and
+    /// the built-in rules are used until the directory is fixed.
+    pub fn load(dir: PathBuf) -> Self {
+        let store = Self {
+            dir: Some(dir),
+            ..Self::builtin()
+        };
+        if let Err(e) = store.reload() {
+            tracing::warn!(error = %e, "Failed to load prompt rule packs; using built-in rules");
+        }
+        store
+    }
+
+    /// `SOLODAWN_PROMPT_RULES_DIR`, or `prompt_rules` under the asset dir.
+    pub fn default_dir() -> Option<PathBuf> {
+        utils::env_compat::var_opt_with_compat(
+            "SOLODAWN_PROMPT_RULES_DIR",
+            "GITCORTEX_PROMPT_RULES_DIR",
+        )
+        .map(PathBuf::from)
+        .or_else(|| {
+            utils::assets::asset_dir()
+                .ok()
+                .map(|dir| dir.join("prompt_rules"))
+        })
+    }
+
+    pub fn current(&self) -> Arc<PromptRuleSet> {
+        match self.current.read() {
+            Ok(guard) => Arc::clone(&guard),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+        }
+    }
+
+    /// Reloads packs if the directory changed since the last attempt.
+    ///
+    /// Returns whether new rules were applied; on error the current rules stay.
+    pub fn reload(&self) -> Result<bool> {
+        let Some(dir) = &self.dir else {
+            return Ok(false);
+        };
+        let files = pack_files(dir)?;
+        let fingerprint = files
+            .iter()
+            .map(|path| {
+                let metadata = std::fs::metadata(path).ok();
+                let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+                (path.clone(), modified, metadata.map_or(0, |m| m.len()))
+            })
+            .collect::<DirFingerprint>();
+        {
+            let mut last = match self.fingerprint.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            if *last == fingerprint {
+                return Ok(false);
+            }
+            // Recorded before parsing so a broken pack is reported once per change.
+            *last = fingerprint;
+        }
+
+        let mut packs = files
+            .iter()
+            .map(|path| {
+                std::fs::read_to_string(path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|content| PromptRulePack::parse(&content))
+                    .with_context(|| format!("Failed to load rule pack {}", path.display()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        packs.push(PromptRulePack::builtin());
+        let rules = PromptRuleSet::from_packs(&packs)?;
+
+        tracing::info!(
+            dir = %dir.display(),
+            packs = packs.len(),
+            rules = rules.rules.len(),
+            "Loaded prompt rule packs"
+        );
+        match self.current.write() {
+            Ok(mut guard) => *guard = Arc::new(rules),
+            Err(poisoned) => *poisoned.into_inner() = Arc::new(rules),
+        }
+        Ok(true)
+    }
+
+    /// Polls the rules directory and reloads packs when it changes.
+    pub fn spawn_hot_reload(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Err(e) = self.reload() {
+                    tracing::warn!(error = %e, "Prompt rule pack reload failed; keeping current rules");
+                }
+            }
+        })
+    }
+}
+
+fn pack_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read rule pack dir {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| PACK_EXTENSIONS.contains(&ext))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+// ============================================================================
+// Transcript Replay
+// ============================================================================
+
+/// One output event of a captured PTY transcript
+#[derive(Debug, Clone)]
+pub struct TranscriptEvent {
+    pub offset: Duration,
+    pub data: String,
+}
+
+/// Parses the output (`"o"`) events of an asciicast v2 recording.
+pub fn parse_asciicast(content: &str) -> Result<Vec<TranscriptEvent>> {
+    let mut events = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        // Header object and blank lines
+        if line.is_empty() || line.starts_with('{') {
+            continue;
+        }
+        let (time, code, data): (f64, String, String) = serde_json::from_str(line)
+            .with_context(|| format!("Invalid asciicast event on line {}", index + 1))?;
+        if code == "o" {
+            events.push(TranscriptEvent {
+                offset: Duration::from_secs_f64(time.max(0.0)),
+                data,
+            });
+        }
+    }
+    Ok(events)
+}
+
+/// A rule firing during transcript replay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayHit {
+    pub offset: Duration,
+    pub rule_id: String,
+    pub input: String,
+}
+
+/// Replays transcript output through a rule set chunk by chunk, as
+/// `PromptWatcher` feeds it, on the transcript's own clock.
+///
+/// Only rule-level debounce and retries apply, with auto-confirm on; the
+/// watcher's global debounce and prompt state machine are not simulated.
+pub fn replay_transcript(
+    rules: &PromptRuleSet,
+    cli_type: Option<&str>,
+    events: &[TranscriptEvent],
+) -> Vec<ReplayHit> {
+    let start = Instant::now();
+    let mut matcher = PromptRuleMatcher::default();
+    events
+        .iter()
+        .filter_map(|event| {
+            let now = start + event.offset;
+            let text = normalize_text_for_detection(&event.data);
+            let hit = matcher.observe(rules, cli_type, true, &text, now)?;
+            matcher.mark_fired(&hit, now);
+            Some(ReplayHit {
+                offset: event.offset,
+                rule_id: hit.rule_id,
+                input: hit.response.input,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay_fixture(name: &str, content: &str, cli_type: Option<&str>) -> Vec<ReplayHit> {
+        let events =
+            parse_asciicast(content).unwrap_or_else(|e| panic!("fixture {name} must parse: {e}"));
+        replay_transcript(&PromptRuleSet::builtin(), cli_type, &events)
+    }
+
+    #[test]
+    fn builtin_pack_replays_captured_transcripts() {
+        let hits = replay_fixture(
+            "codex_apply_patch",
+            include_str!("../../../prompt_rules/transcripts/codex_apply_patch.cast"),
+            Some("cli-codex"),
+        );
+        // The re-rendered status line at 2.35s falls inside the 2s debounce.
+        assert_eq!(
+            hits,
+            vec![ReplayHit {
+                offset: Duration::from_millis(2010),
+                rule_id: "codex-apply-patch-confirm".to_string(),
+                input: "y".to_string(),
+            }]
+        );
+
+        let hits = replay_fixture(
+            "notepad_split",
+            include_str!("../../../prompt_rules/transcripts/notepad_split.cast"),
+            None,
+        );
+        // The Codex status line is answered first, the split prompt once its
+        // "?" arrives.
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].rule_id, "bypass-permissions-toggle");
+        assert_eq!(hits[1].rule_id, "notepad-decline-split");
+        assert_eq!(hits[1].offset, Duration::from_millis(900));
+        assert_eq!(hits[1].input, "n\n");
+    }
+
+    #[test]
+    fn context_window_rules_are_scoped_to_cli_type() {
+        let pack = PromptRulePack::parse(
+            r#"
+name: claude-extras
+rules:
+  - id: claude-resume-session
+    cliTypes: [claude-code]
+    when:
+      all: ['(?i)session expired', '(?i)press enter to resume']
+      contextSecs: 5
+    response:
+      kind: input
+      input: "resume {task_id}\n"
+      reasoning: Resume expired session
+      submitEnter: true
+"#,
+        )
+        .unwrap();
+        let rules = PromptRuleSet::from_packs(&[pack]).unwrap();
+        let events = |gap: f64| {
+            vec![
+                TranscriptEvent {
+                    offset: Duration::ZERO,
+                    data: "Session expired.".to_string(),
+                },
+                TranscriptEvent {
+                    offset: Duration::from_secs_f64(gap),
+                    data: "Press Enter to resume".to_string(),
+                },
+            ]
+        };
+
+        let hits = replay_transcript(&rules, Some("cli-claude-code"), &events(3.0));
+        assert_eq!(hits.len(), 1);
+        assert!(replay_transcript(&rules, Some("cli-claude-code"), &events(7.0)).is_empty());
+        assert!(replay_transcript(&rules, Some("cli-codex"), &events(3.0)).is_empty());
+        assert!(replay_transcript(&rules, None, &events(3.0)).is_empty());
+
+        let mut matcher = PromptRuleMatcher::default();
+        let now = Instant::now();
+        let hit = matcher
+            .observe(
+                &rules,
+                Some("claude-code"),
+                true,
+                "session expired, press enter to resume",
+                now,
+            )
+            .unwrap();
+        assert!(hit.response.submit_enter);
+        assert_eq!(
+            hit.response.render_input("wf-1", "task-1", "term-1"),
+            "resume task-1\n"
+        );
+    }
+
+    #[test]
+    fn retries_are_limited_and_manual_mode_rules_still_fire() {
+        let rules = PromptRuleSet::builtin();
+        let menu = "Bypass Permissions mode 1. No, exit 2. Yes, I accept";
+        let mut matcher = PromptRuleMatcher::default();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        let hit = matcher.observe(&rules, None, true, menu, at(0)).unwrap();
+        assert_eq!(hit.rule_id, "claude-bypass-accept");
+        assert!(!hit.retry);
+        matcher.mark_fired(&hit, at(0));
+
+        // Too early for a retry, but the menu is still recognized
+        assert!(matcher.observe(&rules, None, true, menu, at(500)).is_none());
+        assert!(matcher.matched());
+
+        let hit = matcher.observe(&rules, None, true, menu, at(1000)).unwrap();
+        assert!(hit.retry);
+        matcher.mark_fired(&hit, at(1000));
+        assert!(
+            matcher
+                .observe(&rules, None, true, menu, at(2500))
+                .is_none()
+        );
+
+        // After the retry window the menu counts as a new prompt
+        let hit = matcher.observe(&rules, None, true, menu, at(9000)).unwrap();
+        assert!(!hit.retry);
+
+        // Only rules that opt out of auto-confirm apply in manual mode
+        let mut matcher = PromptRuleMatcher::default();
+        assert!(matcher.observe(&rules, None, false, menu, at(0)).is_none());
+        assert!(!matcher.matched());
+        let hit = matcher
+            .observe(
+                &rules,
+                None,
+                false,
+                "Unexpected changes in the worktree. Should I continue?",
+                at(0),
+            )
+            .unwrap();
+        assert_eq!(hit.rule_id, "unexpected-changes-continue");
+    }
+
+    #[test]
+    fn directory_packs_override_builtin_and_reload_on_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack_path = dir.path().join("local.yaml");
+        std::fs::write(
+            &pack_path,
+            r#"
+name: local
+rules:
+  - id: notepad-decline-split
+    enabled: false
+  - id: gemini-trust-folder
+    cliTypes: [gemini]
+    when:
+      any: ['(?i)do you trust (this|the files in this) folder']
+    response:
+      kind: arrow_select
+      input: "\r"
+      reasoning: Trust the workspace folder
+      targetIndex: 0
+"#,
+        )
+        .unwrap();
+
+        let store = PromptRuleStore::load(dir.path().to_path_buf());
+        let ids = store.current().rule_ids().join(",");
+        assert!(ids.starts_with("gemini-trust-folder,"));
+        assert!(!ids.contains("notepad-decline-split"));
+        assert_eq!(
+            store.current().rule_ids().len(),
+            PromptRuleSet::builtin().rule_ids().len()
+        );
+        assert!(!store.reload().unwrap(), "unchanged dir must not reload");
+
+        std::fs::write(
+            dir.path().join("broken.yml"),
+            "name: broken\nrules: [{id: x}]\n",
+        )
+        .unwrap();
+        assert!(store.reload().is_err());
+        assert_eq!(store.current().rule_ids().join(","), ids);
+
+        std::fs::remove_file(dir.path().join("broken.yml")).unwrap();
+        std::fs::remove_file(&pack_path).unwrap();
+        assert!(store.reload().unwrap());
+        assert_eq!(
+            store.current().rule_ids(),
+            PromptRuleSet::builtin().rule_ids()
+        );
+    }
+}
@@ -12,10 +12,8 @@ use std::{
     time::Duration,
 };
 
-use once_cell::sync::Lazy;
-use regex::Regex;
 use tokio::{
-    sync::{RwLock, broadcast::error::RecvError, oneshot},
+    sync::{RwLock, RwLockWriteGuard, broadcast::error::RecvError, oneshot},
     task::JoinHandle,
     time::Instant,
 };
@@ -26,10 +24,13 @@ use crate::services::{
         types::{PromptDecision, PromptState, TerminalPromptEvent, TerminalPromptStateMachine},
     },
     terminal::{
+        input_lease::InputOwner,
         process::ProcessManager,
         prompt_detector::{
             DetectedPrompt, PromptDetector, PromptKind, normalize_text_for_detection,
         },
+        prompt_rules::{PromptRuleMatcher, PromptRuleStore, RuleResponse},
+        screen::ScreenSnapshot,
     },
 };
 
@@ -50,505 +51,15 @@ const WAITING_FOR_APPROVAL_TIMEOUT_SECS: i64 = 300; // 5 minutes
 /// Minimum confidence threshold for publishing prompt events
 const MIN_CONFIDENCE_THRESHOLD: f32 = 0.7;
 
-/// Auto-reply used when Codex asks whether to continue after detecting
-/// "unexpected changes I didn't make" in the working tree.
-const UNEXPECTED_CHANGES_CONTINUE_RESPONSE: &str = "Continue with the current workspace state and proceed to complete the task and commit; do not wait for additional confirmation.\n";
-
-/// Max age for line-by-line unexpected-changes follow-up context.
-const UNEXPECTED_CHANGES_CONTEXT_MAX_AGE_SECS: u64 = 12;
-
-/// Max age for line-by-line Claude bypass prompt context.
-const CLAUDE_BYPASS_CONTEXT_MAX_AGE_SECS: u64 = 8;
-
-/// Delay before re-sending Claude bypass accept when menu is still visible.
-const CLAUDE_BYPASS_ACCEPT_RETRY_DELAY_MS: u64 = 900;
-
-/// Max age for pending Claude bypass accept retry state.
-const CLAUDE_BYPASS_ACCEPT_RETRY_MAX_AGE_SECS: u64 = 8;
-
-/// Limit retry count to avoid repeated accidental injections.
-const CLAUDE_BYPASS_ACCEPT_MAX_RETRIES: u8 = 1;
-
-/// Max age for "clean repo but waiting for next instruction" context.
-const HANDOFF_STALL_CONTEXT_MAX_AGE_SECS: u64 = 20;
-
-/// Max age for handoff reminder submit-assist context.
-const HANDOFF_SUBMIT_CONTEXT_MAX_AGE_SECS: u64 = 12;
-
-/// Auto-reply used when CLI reports clean workspace and asks what to do next.
-/// This prevents orchestrated terminals from idling forever instead of creating
-/// the required completion/handoff commit.
-const HANDOFF_STALL_CONTINUE_RESPONSE: &str = "Do not wait for additional instructions. \
-You must finish your current scoped terminal now. \
-If there are no file changes, create an empty commit with --allow-empty and include the exact \
----METADATA--- block from your original instruction (workflow_id/task_id/terminal_id/terminal_order/status/next_action). \
-Then stop and hand off to the next terminal.\n";
-
-/// Auto-reply used when Claude reports the configured model is unavailable.
-/// This prompt is common on custom gateways when a model alias is invalid or
-/// the provided API key lacks access to that model.
-const CLAUDE_MODEL_UNAVAILABLE_RECOVERY_INPUT: &str = "/model";
-
-/// Decision rationale text for model-unavailable auto-recovery.
-const CLAUDE_MODEL_UNAVAILABLE_CONTINUE_RESPONSE: &str = "The selected model is unavailable for this endpoint. \
-Run /model now, pick an available model from the presented list (first/default option is acceptable), \
-then continue this same task immediately and complete the required handoff commit. \
-Do not wait for additional instructions.\n";
-
-/// Legacy bypass-permissions toggle prompt (Codex-style TUI).
-/// Example: "bypass permissions on (shift+tab to cycle)"
-static BYPASS_PERMISSIONS_PROMPT_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)\bbypass\s+permissions\s+(on|off)\b.*\(shift\+tab\s+to\s+cycle\)")
-        .expect("BYPASS_PERMISSIONS_PROMPT_RE must compile")
-});
-
-/// Codex interactive confirmation prompt (requires y/n)
-static CODEX_CONFIRM_APPLY_PATCH_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)\bconfirming\s+apply_patch\s+approach\b")
-        .expect("CODEX_CONFIRM_APPLY_PATCH_RE must compile")
-});
-
-/// Claude custom API key selection prompt.
-/// Example:
-/// "Detected a custom API key in your environment"
-/// "Do you want to use this API key?"
-static CLAUDE_CUSTOM_API_KEY_PROMPT_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r"(?i)(do\s+you\s+want\s+to\s+use\s+this\s+api\s+key\?|detected\s+a\s+custom\s+api\s+key\s+in\s+your\s+environment)",
-    )
-    .expect("CLAUDE_CUSTOM_API_KEY_PROMPT_RE must compile")
-});
-
-/// Claude bypass list item variants:
-/// - "No, exit"
-/// - "No,exit"
-/// - "No ,  exit"
-static CLAUDE_BYPASS_NO_EXIT_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)\bno\s*,?\s*exit\b").expect("CLAUDE_BYPASS_NO_EXIT_RE must compile")
-});
-
-/// Claude bypass acceptance list item variants:
-/// - "Yes, I accept"
-/// - "Yes,I accept"
-/// - "Yes I accept"
-static CLAUDE_BYPASS_YES_ACCEPT_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)\byes\s*,?\s*i\s*accept\b").expect("CLAUDE_BYPASS_YES_ACCEPT_RE must compile")
-});
-
-/// Notepad launch/open prompt seen in headless Codex flows on Windows.
-/// Example: "Open in Notepad? (y/N)"
-static NOTEPAD_PROMPT_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r"(?i)\bnotepad\b.{0,200}(\[[^\]]*y\s*/\s*n[^\]]*\]|\([^\)]*y\s*/\s*n[^\)]*\)|\by\s*/\s*n\b|\byes\s*/\s*no\b)",
-    )
-    .expect("NOTEPAD_PROMPT_RE must compile")
-});
-
-fn is_bypass_permissions_prompt(line: &str) -> bool {
-    BYPASS_PERMISSIONS_PROMPT_RE.is_match(line)
-}
-
-#[allow(dead_code)]
-fn is_bypass_permissions_enter_confirm_context(text: &str) -> bool {
-    let lower = text.to_ascii_lowercase();
-    lower.contains("interrupted")
-        || lower.contains("press ctrl-c again to exit")
-        || lower.contains("enter to confirm")
-        || (lower.contains("press enter") && lower.contains("confirm"))
-}
-
-fn is_codex_apply_patch_confirmation(text: &str) -> bool {
-    CODEX_CONFIRM_APPLY_PATCH_RE.is_match(text)
-}
-
-fn is_claude_custom_api_key_prompt(text: &str) -> bool {
-    CLAUDE_CUSTOM_API_KEY_PROMPT_RE.is_match(text)
-}
-
-fn is_claude_model_unavailable_prompt(text: &str) -> bool {
-    let lower = text.to_ascii_lowercase();
-    let has_issue_marker = lower.contains("issue with the selected model")
-        || (lower.contains("selected model")
-            && (lower.contains("may not exist")
-                || lower.contains("may not have access")
-                || lower.contains("does not exist")
-                || lower.contains("don't have access")));
-    let has_model_action_hint = lower.contains("run /model")
-        || lower.contains("use /model")
-        || lower.contains("pick a different model")
-        || lower.contains("choose a different model");
-    // OpenAI-compatible gateways may return Claude model outages as 503 with
-    // `model_not_found` / `No available channel for model ...`.
-    let has_gateway_model_not_found_marker =
-        lower.contains("model_not_found") || lower.contains("no available channel for model");
-
-    (has_issue_marker && has_model_action_hint) || has_gateway_model_not_found_marker
-}
-
-fn is_notepad_prompt(text: &str) -> bool {
-    if NOTEPAD_PROMPT_RE.is_match(text) {
-        return true;
-    }
-
-    let lower = text.to_ascii_lowercase();
-    let has_notepad = lower.contains("notepad");
-    if !has_notepad {
-        return false;
-    }
-
-    // Fallback for prompts split across lines/chunks where y/n tokens may
-    // arrive separately: "Open in Notepad?"
-    let has_action = lower.contains("open") || lower.contains("launch") || lower.contains("use");
-    has_action && lower.contains('?')
-}
-
-fn is_unexpected_changes_followup_prompt(text: &str) -> bool {
-    let lower = text.to_ascii_lowercase();
-
-    let has_cn_change_marker =
-        text.contains("未发起的变更") || text.contains("未执行的变更") || text.contains("外部变更");
-    let has_cn_followup = has_cn_change_marker
-        && (text.contains("请确认我是否可以")
-            || text.contains("请确认是否继续")
-            || text.contains("是否继续")
-            || text.contains("继续实现任务")
-            || text.contains("继续实现并提交")
-            || text.contains("你希望我"));
-
-    let has_en_change_marker = lower.contains("changes i didn't make")
-        || lower.contains("changes i did not make")
-        || lower.contains("unexpected changes");
-    let has_en_followup = has_en_change_marker
-        && (lower.contains("should i continue")
-            || lower.contains("continue implementing")
-            || lower.contains("wait for you")
-            || lower.contains("proceed"));
-
-    has_cn_followup || has_en_followup
-}
-
-fn normalize_handoff_marker_text(text: &str) -> String {
-    text.to_ascii_lowercase()
-        .replace(['\u{2019}', '\u{2018}'], "'")
-        .replace(['\u{2013}', '\u{2014}'], " ")
-}
-
-fn has_handoff_stall_clean_marker(lower: &str) -> bool {
-    lower.contains("nothing to commit, working tree clean")
-        || lower.contains("working tree clean")
-        || lower.contains("working tree is clean")
-        || lower.contains("repository is clean")
-        || lower.contains("checkout is clean")
-        || lower.contains("no outstanding changes")
-        || lower.contains("no staged/unstaged diffs")
-        || lower.contains("no diff to review right now")
-        || lower.contains("status clean")
-        || lower.contains("no diff")
-        || lower.contains("git status clean")
-}
-
-fn has_handoff_stall_wait_marker(lower: &str) -> bool {
-    let asks_what_like_me_to = lower.contains("what")
-        && lower.contains("like me to")
-        && (lower.contains("work on")
-            || lower.contains("do next")
-            || lower.contains("implement")
-            || lower.contains("change"));
-    let asks_let_me_know = lower.contains("let me know")
-        && (lower.contains("work on")
-            || lower.contains("do next")
-            || lower.contains("implement")
-            || lower.contains("change"));
-    let asks_share_what = lower.contains("share what")
-        && lower.contains("like me to")
-        && (lower.contains("work on") || lower.contains("implement") || lower.contains("change"));
-    let asks_describe_specific = lower.contains("could you describe")
-        && (lower.contains("specific change or feature")
-            || lower.contains("specific change")
-            || lower.contains("specific feature")
-            || lower.contains("like me to work on"));
-    let asks_proceed_next = lower.contains("how would you like to proceed next")
-        || (lower.contains("how would you like") && lower.contains("proceed next"))
-        || (lower.contains("would you like to proceed") && lower.contains("next"));
-
-    asks_what_like_me_to
-        || asks_let_me_know
-        || asks_share_what
-        || asks_describe_specific
-        || asks_proceed_next
-        || lower.contains("what would you like me to work on next")
-        || (lower.contains("could you clarify what") && lower.contains("work on next"))
-        || (lower.contains("could you clarify what") && lower.contains("work on first"))
-        || (lower.contains("what changes or task") && lower.contains("work on first"))
-        || (lower.contains("let me know what") && lower.contains("work on next"))
-        || (lower.contains("let me know what") && lower.contains("like me to do next"))
-        || (lower.contains("let me know") && lower.contains("work on"))
-        || (lower.contains("let me know") && lower.contains("do next"))
-        || (lower.contains("what") && lower.contains("like me to work on"))
-        || (lower.contains("what you")
-            && lower.contains("like me to")
-            && lower.contains("work on next"))
-        || lower.contains("share the changes you want me to make")
-        || lower.contains("what you'd like me to implement")
-        || lower.contains("what you would like me to implement")
-        || lower.contains("what changes you'd like me to implement")
-        || lower.contains("what changes you would like me to implement")
-        || lower.contains("what would you like me to do next")
-        || lower.contains("what you'd like me to do next")
-        || lower.contains("what you would like me to do next")
-        || lower.contains("you'd like me to work on")
-        || lower.contains("youd like me to work on")
-}
-
-fn has_handoff_stall_scope_gap_marker(lower: &str) -> bool {
-    (lower.contains("specific requirements") && lower.contains("files to modify"))
-        || lower.contains("don't have any specific requirements")
-        || lower.contains("do not have any specific requirements")
-        || lower.contains("don't have a specific task yet")
-        || lower.contains("do not have a specific task yet")
-        || lower.contains("don't have a task yet")
-        || lower.contains("do not have a task yet")
-        || lower.contains("no further instructions were provided")
-        || (lower.contains("ready to start implementing")
-            && lower.contains("specific change or feature"))
-        || lower.contains("no specific requirements")
-        || lower.contains("no actionable change")
-        || lower.contains("no actionable changes")
-}
-
-fn is_handoff_stall_prompt(text: &str) -> bool {
-    let lower = normalize_handoff_marker_text(text);
-    let has_wait = has_handoff_stall_wait_marker(&lower);
-    has_wait
-        && (has_handoff_stall_clean_marker(&lower) || has_handoff_stall_scope_gap_marker(&lower))
-}
-
-fn has_claude_bypass_mode_text(lower: &str) -> bool {
-    lower.contains("bypass permissions mode")
-}
-
-fn has_claude_bypass_no_exit_text(lower: &str) -> bool {
-    CLAUDE_BYPASS_NO_EXIT_RE.is_match(lower)
-}
-
-fn has_claude_bypass_yes_accept_text(lower: &str) -> bool {
-    CLAUDE_BYPASS_YES_ACCEPT_RE.is_match(lower)
-}
-
-fn has_claude_bypass_confirm_hint_text(lower: &str) -> bool {
-    lower.contains("enter to confirm") || (lower.contains("enter") && lower.contains("confirm"))
-}
-
-fn has_claude_bypass_accept_menu_text(lower: &str) -> bool {
-    has_claude_bypass_mode_text(lower)
-        && has_claude_bypass_no_exit_text(lower)
-        && has_claude_bypass_yes_accept_text(lower)
-}
-
-fn is_claude_bypass_accept_prompt(text: &str) -> bool {
-    let lower = text.to_ascii_lowercase();
-    has_claude_bypass_accept_menu_text(&lower)
-}
-
-/// [G19-010] The numeric shortcut "2\r" assumes "Yes, I accept" is always the
-/// second menu item. If Claude CLI reorders the menu in a future version this
-/// will break. TODO: implement regex-based matching that scans the rendered
-/// menu lines for the "Yes" option index instead of hardcoding the position.
-fn claude_bypass_accept_response(_text: &str) -> (&'static str, &'static str, &'static str) {
-    (
-        "2\r",
-        "select 'Yes, I accept' via numeric shortcut and confirm",
-        "Select 'Yes, I accept' via numeric shortcut (2) and confirm for Claude bypass permissions prompt",
-    )
-}
-
-fn is_claude_bypass_accept_context_line(lower: &str) -> bool {
-    has_claude_bypass_mode_text(lower)
-        || has_claude_bypass_no_exit_text(lower)
-        || has_claude_bypass_yes_accept_text(lower)
-        || has_claude_bypass_confirm_hint_text(lower)
-}
-
-fn has_any_claude_bypass_marker(lower: &str) -> bool {
-    has_claude_bypass_mode_text(lower)
-        || has_claude_bypass_no_exit_text(lower)
-        || has_claude_bypass_yes_accept_text(lower)
-        || has_claude_bypass_confirm_hint_text(lower)
-}
+/// Minimum time between two rendered-screen snapshots of one terminal. Chunks
+/// in between use line detection; the screen is checked again once output
+/// settles.
+const SCREEN_CHECK_INTERVAL_MS: u64 = 200;
 
 // ============================================================================
 // Terminal Watch State
 // ============================================================================
 
-#[derive(Debug, Default)]
-struct ClaudeBypassContext {
-    saw_mode: bool,
-    saw_no_exit: bool,
-    saw_yes_accept: bool,
-    saw_confirm_hint: bool,
-    last_updated: Option<Instant>,
-}
-
-impl ClaudeBypassContext {
-    fn observe(&mut self, lower_text: &str) {
-        let mut touched = false;
-
-        if has_claude_bypass_mode_text(lower_text) && !self.saw_mode {
-            self.saw_mode = true;
-            touched = true;
-        }
-
-        if has_claude_bypass_no_exit_text(lower_text) && !self.saw_no_exit {
-            self.saw_no_exit = true;
-            touched = true;
-        }
-
-        if has_claude_bypass_yes_accept_text(lower_text) && !self.saw_yes_accept {
-            self.saw_yes_accept = true;
-            touched = true;
-        }
-
-        if has_claude_bypass_confirm_hint_text(lower_text) && !self.saw_confirm_hint {
-            self.saw_confirm_hint = true;
-            touched = true;
-        }
-
-        if touched {
-            self.last_updated = Some(Instant::now());
-        }
-    }
-
-    fn is_complete_and_recent(&self) -> bool {
-        if !(self.saw_mode && self.saw_no_exit && self.saw_yes_accept) {
-            return false;
-        }
-
-        self.last_updated.is_some_and(|ts| {
-            ts.elapsed() <= Duration::from_secs(CLAUDE_BYPASS_CONTEXT_MAX_AGE_SECS)
-        })
-    }
-
-    fn clear(&mut self) {
-        *self = Self::default();
-    }
-}
-
-#[derive(Debug, Default)]
-struct UnexpectedChangesContext {
-    saw_change_marker: bool,
-    saw_pause_marker: bool,
-    saw_followup_marker: bool,
-    last_updated: Option<Instant>,
-}
-
-impl UnexpectedChangesContext {
-    fn observe(&mut self, text: &str) {
-        let lower = text.to_ascii_lowercase();
-        let mut touched = false;
-
-        let has_change_marker = text.contains("未发起的变更")
-            || text.contains("未执行的变更")
-            || text.contains("外部变更")
-            || lower.contains("changes i didn't make")
-            || lower.contains("changes i did not make")
-            || lower.contains("unexpected changes");
-        if has_change_marker && !self.saw_change_marker {
-            self.saw_change_marker = true;
-            touched = true;
-        }
-
-        let has_pause_marker = text.contains("需要先暂停")
-            || text.contains("先暂停")
-            || lower.contains("need to pause")
-            || lower.contains("must pause");
-        if has_pause_marker && !self.saw_pause_marker {
-            self.saw_pause_marker = true;
-            touched = true;
-        }
-
-        let has_followup_marker = text.contains("请确认我是否可以")
-            || text.contains("请确认是否继续")
-            || text.contains("是否继续")
-            || text.contains("继续实现任务")
-            || text.contains("继续实现并提交")
-            || text.contains("你希望我")
-            || lower.contains("should i continue")
-            || lower.contains("continue implementing")
-            || lower.contains("wait for you")
-            || lower.contains("proceed");
-        if has_followup_marker && !self.saw_followup_marker {
-            self.saw_followup_marker = true;
-            touched = true;
-        }
-
-        if touched {
-            self.last_updated = Some(Instant::now());
-        }
-    }
-
-    fn is_complete_and_recent(&self) -> bool {
-        if !(self.saw_change_marker && (self.saw_followup_marker || self.saw_pause_marker)) {
-            return false;
-        }
-
-        self.last_updated.is_some_and(|ts| {
-            ts.elapsed() <= Duration::from_secs(UNEXPECTED_CHANGES_CONTEXT_MAX_AGE_SECS)
-        })
-    }
-
-    fn clear(&mut self) {
-        *self = Self::default();
-    }
-}
-
-#[derive(Debug, Default)]
-struct HandoffStallContext {
-    saw_clean_marker: bool,
-    saw_wait_marker: bool,
-    saw_scope_gap_marker: bool,
-    last_updated: Option<Instant>,
-}
-
-impl HandoffStallContext {
-    fn observe(&mut self, text: &str) {
-        let lower = normalize_handoff_marker_text(text);
-        let mut touched = false;
-
-        if has_handoff_stall_clean_marker(&lower) && !self.saw_clean_marker {
-            self.saw_clean_marker = true;
-            touched = true;
-        }
-
-        if has_handoff_stall_wait_marker(&lower) && !self.saw_wait_marker {
-            self.saw_wait_marker = true;
-            touched = true;
-        }
-
-        if has_handoff_stall_scope_gap_marker(&lower) && !self.saw_scope_gap_marker {
-            self.saw_scope_gap_marker = true;
-            touched = true;
-        }
-
-        if touched {
-            self.last_updated = Some(Instant::now());
-        }
-    }
-
-    fn is_complete_and_recent(&self) -> bool {
-        if !((self.saw_clean_marker || self.saw_scope_gap_marker) && self.saw_wait_marker) {
-            return false;
-        }
-
-        self.last_updated.is_some_and(|ts| {
-            ts.elapsed() <= Duration::from_secs(HANDOFF_STALL_CONTEXT_MAX_AGE_SECS)
-        })
-    }
-
-    fn clear(&mut self) {
-        *self = Self::default();
-    }
-}
-
 /// State for a single watched terminal
 #[derive(Debug)]
 struct TerminalWatchState {
@@ -560,28 +71,25 @@ struct TerminalWatchState {
     task_id: String,
     /// PTY session ID
     session_id: String,
+    /// CLI type ID (`cli-codex`, ...) used to scope prompt rules
+    cli_type_id: Option<String>,
     /// Whether auto-confirm is enabled for this terminal
     auto_confirm: bool,
     /// Prompt detector instance
     detector: PromptDetector,
     /// Prompt state machine
     state_machine: TerminalPromptStateMachine,
+    /// Context and debounce state for declarative prompt rules
+    rule_matcher: PromptRuleMatcher,
     /// Last detection timestamp (for debouncing)
     last_detection: Option<Instant>,
-    /// Rolling context for line-by-line Claude bypass prompt rendering.
-    claude_bypass_context: ClaudeBypassContext,
-    /// Rolling context for line-by-line unexpected-changes follow-up prompts.
-    unexpected_changes_context: UnexpectedChangesContext,
-    /// Rolling context for "clean workspace + waiting for next instruction".
-    handoff_stall_context: HandoffStallContext,
-    /// If handoff reminder text was injected while renderer was busy, send one
-    /// extra Enter on next bypass status-line to force submission.
-    pending_handoff_submit_at: Option<Instant>,
-    /// Timestamp of initial Claude bypass auto-accept send, used for one retry
-    /// if the same menu remains visible.
-    pending_claude_bypass_retry_since: Option<Instant>,
-    /// Number of Claude bypass retries sent for current prompt.
-    claude_bypass_retry_count: u8,
+    /// Fingerprint of the last prompt detected on the rendered screen, so a
+    /// prompt that stays on screen after it was handled is not reported again
+    last_screen_prompt: Option<String>,
+    /// When the rendered screen was last checked for prompts
+    last_screen_check: Option<Instant>,
+    /// Output arrived while screen checks were throttled
+    screen_check_pending: bool,
 }
 
 impl TerminalWatchState {
@@ -597,16 +105,15 @@ impl TerminalWatchState {
             workflow_id,
             task_id,
             session_id,
+            cli_type_id: None,
             auto_confirm,
             detector: PromptDetector::new(),
             state_machine: TerminalPromptStateMachine::new(),
+            rule_matcher: PromptRuleMatcher::default(),
             last_detection: None,
-            claude_bypass_context: ClaudeBypassContext::default(),
-            unexpected_changes_context: UnexpectedChangesContext::default(),
-            handoff_stall_context: HandoffStallContext::default(),
-            pending_handoff_submit_at: None,
-            pending_claude_bypass_retry_since: None,
-            claude_bypass_retry_count: 0,
+            last_screen_prompt: None,
+            last_screen_check: None,
+            screen_check_pending: false,
         }
     }
 
@@ -646,6 +153,57 @@ impl TerminalWatchState {
         Some(prompt)
     }
 
+    /// When a throttled screen check may run
+    fn screen_check_due_at(&self) -> Option<Instant> {
+        self.last_screen_check
+            .map(|last| last + Duration::from_millis(SCREEN_CHECK_INTERVAL_MS))
+    }
+
+    /// Claims a screen check if the throttle interval has passed, otherwise
+    /// leaves one pending for when output settles.
+    fn claim_screen_check(&mut self) -> bool {
+        let now = Instant::now();
+        if self.screen_check_due_at().is_some_and(|due| now < due) {
+            self.screen_check_pending = true;
+            return false;
+        }
+        self.last_screen_check = Some(now);
+        self.screen_check_pending = false;
+        true
+    }
+
+    /// Detect a prompt on the rendered screen and return it if it is new
+    fn process_screen(&mut self, screen: &ScreenSnapshot) -> Option<DetectedPrompt> {
+        if self.should_debounce() {
+            return None;
+        }
+
+        let Some(prompt) = self.detector.detect_screen(screen) else {
+            self.last_screen_prompt = None;
+            return None;
+        };
+
+        // Same prompt still on screen (e.g. menu repainted while moving the cursor)
+        let fingerprint = format!("{:?}:{}", prompt.kind, prompt.raw_text);
+        if self.last_screen_prompt.as_deref() == Some(fingerprint.as_str()) {
+            return None;
+        }
+
+        if prompt.confidence < MIN_CONFIDENCE_THRESHOLD {
+            return None;
+        }
+
+        if !self.state_machine.should_process(&prompt) {
+            return None;
+        }
+
+        self.last_screen_prompt = Some(fingerprint);
+        self.last_detection = Some(Instant::now());
+        self.state_machine.on_prompt_detected(prompt.clone());
+
+        Some(prompt)
+    }
+
     /// Reset state machine if stale.
     ///
     /// Uses a longer timeout for WaitingForApproval (G07-008) since that state
@@ -670,90 +228,9 @@ impl TerminalWatchState {
             }
             self.state_machine.reset();
             self.detector.clear_buffer();
-            self.claude_bypass_context.clear();
-            self.unexpected_changes_context.clear();
-            self.handoff_stall_context.clear();
-            self.pending_handoff_submit_at = None;
-            self.clear_claude_bypass_retry_state();
-        }
-    }
-
-    fn observe_claude_bypass_context(&mut self, text: &str) {
-        self.claude_bypass_context.observe(text);
-    }
-
-    fn has_recent_claude_bypass_accept_context(&self) -> bool {
-        self.claude_bypass_context.is_complete_and_recent()
-    }
-
-    fn clear_claude_bypass_context(&mut self) {
-        self.claude_bypass_context.clear();
-    }
-
-    fn observe_unexpected_changes_context(&mut self, text: &str) {
-        self.unexpected_changes_context.observe(text);
-    }
-
-    fn has_recent_unexpected_changes_context(&self) -> bool {
-        self.unexpected_changes_context.is_complete_and_recent()
-    }
-
-    fn clear_unexpected_changes_context(&mut self) {
-        self.unexpected_changes_context.clear();
-    }
-
-    fn observe_handoff_stall_context(&mut self, text: &str) {
-        self.handoff_stall_context.observe(text);
-    }
-
-    fn has_recent_handoff_stall_context(&self) -> bool {
-        self.handoff_stall_context.is_complete_and_recent()
-    }
-
-    fn clear_handoff_stall_context(&mut self) {
-        self.handoff_stall_context.clear();
-    }
-
-    #[allow(dead_code)]
-    fn mark_pending_handoff_submit(&mut self) {
-        self.pending_handoff_submit_at = Some(Instant::now());
-    }
-
-    fn should_force_handoff_submit(&self) -> bool {
-        self.pending_handoff_submit_at.is_some_and(|ts| {
-            ts.elapsed() <= Duration::from_secs(HANDOFF_SUBMIT_CONTEXT_MAX_AGE_SECS)
-        })
-    }
-
-    fn clear_pending_handoff_submit(&mut self) {
-        self.pending_handoff_submit_at = None;
-    }
-
-    fn mark_claude_bypass_accept_sent(&mut self) {
-        self.pending_claude_bypass_retry_since = Some(Instant::now());
-        self.claude_bypass_retry_count = 0;
-    }
-
-    fn should_retry_claude_bypass_accept(&self) -> bool {
-        let Some(since) = self.pending_claude_bypass_retry_since else {
-            return false;
-        };
-        if self.claude_bypass_retry_count >= CLAUDE_BYPASS_ACCEPT_MAX_RETRIES {
-            return false;
+            self.rule_matcher.clear();
+            self.last_screen_prompt = None;
         }
-        let elapsed = since.elapsed();
-        elapsed >= Duration::from_millis(CLAUDE_BYPASS_ACCEPT_RETRY_DELAY_MS)
-            && elapsed <= Duration::from_secs(CLAUDE_BYPASS_ACCEPT_RETRY_MAX_AGE_SECS)
-    }
-
-    fn mark_claude_bypass_retry_sent(&mut self) {
-        self.claude_bypass_retry_count = self.claude_bypass_retry_count.saturating_add(1);
-        self.pending_claude_bypass_retry_since = None;
-    }
-
-    fn clear_claude_bypass_retry_state(&mut self) {
-        self.pending_claude_bypass_retry_since = None;
-        self.claude_bypass_retry_count = 0;
     }
 }
 
@@ -784,6 +261,8 @@ pub struct PromptWatcher {
     active_subscriptions: Arc<RwLock<HashMap<String, WatchTaskHandle>>>,
     /// Monotonic task ID for safe replacement/cleanup
     next_task_id: Arc<AtomicU64>,
+    /// Declarative prompt rule packs
+    rules: Arc<PromptRuleStore>,
 }
 
 impl PromptWatcher {
@@ -795,9 +274,16 @@ impl PromptWatcher {
             terminals: Arc::new(RwLock::new(HashMap::new())),
             active_subscriptions: Arc::new(RwLock::new(HashMap::new())),
             next_task_id: Arc::new(AtomicU64::new(1)),
+            rules: Arc::new(PromptRuleStore::builtin()),
         }
     }
 
+    /// Use a rule store loaded from the rules directory instead of the built-in pack.
+    pub fn with_rule_store(mut self, rules: Arc<PromptRuleStore>) -> Self {
+        self.rules = rules;
+        self
+    }
+
     /// Register a terminal for watching
     pub async fn register(
         &self,
@@ -805,15 +291,17 @@ impl PromptWatcher {
         workflow_id: &str,
         task_id: &str,
         session_id: &str,
+        cli_type_id: &str,
         auto_confirm: bool,
     ) -> anyhow::Result<()> {
-        let state = TerminalWatchState::new(
+        let mut state = TerminalWatchState::new(
             terminal_id.to_string(),
             workflow_id.to_string(),
             task_id.to_string(),
             session_id.to_string(),
             auto_confirm,
         );
+        state.cli_type_id = Some(cli_type_id.to_string());
 
         {
             let mut terminals = self.terminals.write().await;
@@ -903,7 +391,18 @@ impl PromptWatcher {
             };
 
             loop {
-                match subscription.recv().await {
+                // Check the screen skipped by throttling once output settles
+                let next = match watcher.pending_screen_check_at(&terminal_id_for_task).await {
+                    Some(due) => match tokio::time::timeout_at(due, subscription.recv()).await {
+                        Ok(next) => next,
+                        Err(_) => {
+                            watcher.process_pending_screen(&terminal_id_for_task).await;
+                            continue;
+                        }
+                    },
+                    None => subscription.recv().await,
+                };
+                match next {
                     Ok(chunk) => {
                         if !chunk.text.is_empty() {
                             watcher
@@ -982,6 +481,9 @@ impl PromptWatcher {
         }
     }
 
+    /// Write `input` straight to the PTY. Returns false if the message bus
+    /// should be used instead; a write skipped because a client took input
+    /// over counts as handled.
     async fn try_direct_terminal_input(
         &self,
         terminal_id: &str,
@@ -1010,12 +512,27 @@ impl PromptWatcher {
             );
             return false;
         };
+        let Some(lease) = self.process_manager.input_lease(terminal_id).await else {
+            return false;
+        };
 
         let mut guard = match writer.lock() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
         };
 
+        // A client may have taken input over since the prompt was detected;
+        // its writes also go through this writer, so the check holds until ours
+        // is flushed.
+        if !lease.is_held_by(&InputOwner::Orchestrator) {
+            tracing::info!(
+                terminal_id = %terminal_id,
+                holder = %lease.owner(),
+                "Skip direct PTY auto-input: a client holds the input lease"
+            );
+            return true;
+        }
+
         if let Err(e) = guard.write_all(input.as_bytes()) {
             tracing::warn!(
                 terminal_id = %terminal_id,
@@ -1056,24 +573,6 @@ impl PromptWatcher {
         payload
     }
 
-    fn build_handoff_stall_continue_response(
-        workflow_id: &str,
-        task_id: &str,
-        terminal_id: &str,
-    ) -> String {
-        format!(
-            "{HANDOFF_STALL_CONTINUE_RESPONSE}\n\
-Use this exact metadata mapping in the commit message (do not swap or leave blank):\n\
----METADATA---\n\
-workflow_id: {workflow_id}\n\
-task_id: {task_id}\n\
-terminal_id: {terminal_id}\n\
-terminal_order: <copy from your original terminal instruction>\n\
-status: completed\n\
-next_action: handoff\n"
-        )
-    }
-
     async fn resolve_terminal_input_session_id(
         &self,
         terminal_id: &str,
@@ -1120,32 +619,44 @@ next_action: handoff\n"
             .await;
     }
 
-    async fn send_claude_bypass_accept_with_fallback(
+    async fn send_rule_response(
         &self,
         terminal_id: &str,
         session_id: &str,
-        response: &str,
+        input: &str,
+        response: &RuleResponse,
         decision: PromptDecision,
-        mode: &'static str,
-        is_retry: bool,
     ) {
-        let direct_input = Self::normalize_input_for_direct_write(response);
-        let sent_direct = self
-            .try_direct_terminal_input(terminal_id, session_id, &direct_input)
-            .await;
-        if !sent_direct {
-            tracing::warn!(
-                terminal_id = %terminal_id,
-                session_id = %session_id,
-                mode,
-                is_retry,
-                "Direct PTY Claude bypass auto-accept failed; falling back to message bus"
-            );
+        if response.direct {
+            let direct_input = Self::normalize_input_for_direct_write(input);
+            let sent_direct = self
+                .try_direct_terminal_input(terminal_id, session_id, &direct_input)
+                .await;
+            if !sent_direct {
+                tracing::warn!(
+                    terminal_id = %terminal_id,
+                    session_id = %session_id,
+                    "Direct PTY prompt rule response failed; falling back to message bus"
+                );
+                self.publish_terminal_input_with_active_session(
+                    terminal_id,
+                    session_id,
+                    input,
+                    Some(decision),
+                )
+                .await;
+            }
+        } else {
+            self.message_bus
+                .publish_terminal_input(terminal_id, session_id, input, Some(decision))
+                .await;
+        }
+        if response.submit_enter {
             self.publish_terminal_input_with_active_session(
                 terminal_id,
                 session_id,
-                response,
-                Some(decision),
+                "\n",
+                Some(PromptDecision::auto_enter()),
             )
             .await;
         }
@@ -1156,1031 +667,93 @@ next_action: handoff\n"
     /// Call this method with each line of PTY output.
     /// If a prompt is detected, publishes a `TerminalPromptDetected` event.
     ///
-    /// [G07-003] NOTE: All chunk-level prompt paths (Claude bypass, unexpected-changes,
-    /// handoff-stall) now respect the `auto_confirm` guard. When `auto_confirm=false`,
-    /// these handlers are skipped, ensuring manual-mode users retain full control.
-    pub async fn process_output(&self, terminal_id: &str, output: &str) {
-        let mut terminals = self.terminals.write().await;
-
-        let state = if let Some(s) = terminals.get_mut(terminal_id) { s } else {
-            tracing::trace!(
-                terminal_id = %terminal_id,
-                "Terminal not registered for prompt watching, ignoring output"
-            );
-            return;
-        };
-
-        // Check and reset stale state
-        state.check_and_reset_stale();
-
-        // Chunk-level fallback: Claude custom API key prompt can appear inside
-        // heavily ANSI-rendered TUI frames without clean newline boundaries.
-        // Detect directly from normalized chunk and auto-reply with ArrowUp+Enter
-        // to choose "Yes".
-        let normalized_output = normalize_text_for_detection(output);
-        let normalized_output_lower = normalized_output.to_ascii_lowercase();
-        state.observe_claude_bypass_context(&normalized_output_lower);
-        state.observe_unexpected_changes_context(&normalized_output);
-        state.observe_handoff_stall_context(&normalized_output);
-        let bypass_needs_enter_context = normalized_output_lower.contains("interrupted")
-            || normalized_output_lower.contains("press ctrl-c again to exit");
-        let has_claude_bypass_accept_context = is_claude_bypass_accept_prompt(&normalized_output)
-            || state.has_recent_claude_bypass_accept_context();
-        let has_claude_custom_api_key_context = is_claude_custom_api_key_prompt(&normalized_output);
-        let has_unexpected_changes_followup_context =
-            is_unexpected_changes_followup_prompt(&normalized_output)
-                || state.has_recent_unexpected_changes_context();
-        let has_handoff_stall_context =
-            is_handoff_stall_prompt(&normalized_output) || state.has_recent_handoff_stall_context();
-
-        if !state.should_debounce()
-            && state.should_force_handoff_submit()
-            && is_bypass_permissions_prompt(&normalized_output)
-        {
-            let decision = PromptDecision::auto_enter();
-            state.last_detection = Some(Instant::now());
-            state.state_machine.on_response_sent(decision.clone());
-            state.detector.clear_buffer();
-            state.clear_pending_handoff_submit();
-
-            let response_terminal_id = state.terminal_id.clone();
-            let response_session_id = state.session_id.clone();
-
-            tracing::info!(
-                terminal_id = %response_terminal_id,
-                session_id = %response_session_id,
-                "Detected bypass status-line after handoff reminder (chunk); sending extra Enter to submit queued input"
-            );
-
-            drop(terminals);
-            self.publish_terminal_input_with_active_session(
-                &response_terminal_id,
-                &response_session_id,
-                "\n",
-                Some(decision),
-            )
-            .await;
-            return;
-        }
-
-        let has_any_claude_bypass_chunk_marker =
-            has_any_claude_bypass_marker(&normalized_output_lower);
-        if state.auto_confirm && has_any_claude_bypass_chunk_marker {
-            tracing::info!(
-                terminal_id = %state.terminal_id,
-                session_id = %state.session_id,
-                has_mode = has_claude_bypass_mode_text(&normalized_output_lower),
-                has_no_exit = has_claude_bypass_no_exit_text(&normalized_output_lower),
-                has_yes_accept = has_claude_bypass_yes_accept_text(&normalized_output_lower),
-                has_confirm_hint = has_claude_bypass_confirm_hint_text(&normalized_output_lower),
-                chunk_accept_context = has_claude_bypass_accept_context,
-                chunk_len = normalized_output.len(),
-                "Observed Claude bypass prompt markers in chunk"
-            );
-        }
-
-        if state.auto_confirm
-            && !state.should_debounce()
-            && has_any_claude_bypass_chunk_marker
-            && state.should_retry_claude_bypass_accept()
-        {
-            let (response, action, reasoning) = claude_bypass_accept_response(&normalized_output);
-            let decision = PromptDecision::LLMDecision {
-                response: response.to_string(),
-                reasoning: format!("{reasoning} (retry once because prompt is still visible)"),
-                target_index: Some(1),
-            };
-            state.last_detection = Some(Instant::now());
-            state.state_machine.on_response_sent(decision.clone());
-            state.detector.clear_buffer();
-            state.clear_claude_bypass_context();
-            state.mark_claude_bypass_retry_sent();
-
-            let response_terminal_id = state.terminal_id.clone();
-            let response_session_id = state.session_id.clone();
-
-            tracing::info!(
-                terminal_id = %response_terminal_id,
-                session_id = %response_session_id,
-                action = %action,
-                "Claude bypass prompt still visible after auto-accept; retrying once (chunk)"
-            );
-
-            drop(terminals);
-            self.send_claude_bypass_accept_with_fallback(
-                &response_terminal_id,
-                &response_session_id,
-                response,
-                decision,
-                "chunk",
-                true,
-            )
-            .await;
-            return;
-        }
-
-        // Chunk-level fallback: Claude bypass-permissions acceptance prompt.
-        // Select "Yes, I accept" via numeric shortcut "2" + Enter.
-        // This is more stable than ArrowDown in fast ANSI frame updates.
-        if state.auto_confirm && !state.should_debounce() && has_claude_bypass_accept_context {
-            let (response, action, reasoning) = claude_bypass_accept_response(&normalized_output);
-            let decision = PromptDecision::LLMDecision {
-                response: response.to_string(),
-                reasoning: reasoning.to_string(),
-                target_index: Some(1),
-            };
-            let detected_prompt =
-                DetectedPrompt::new(PromptKind::ArrowSelect, normalized_output.clone(), 0.95);
-
-            if state.state_machine.should_process(&detected_prompt) {
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-                state.clear_claude_bypass_context();
-                state.mark_claude_bypass_accept_sent();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    action = %action,
-                    "Detected Claude bypass permissions prompt (chunk); sending auto-accept sequence"
-                );
-
-                drop(terminals);
-                self.send_claude_bypass_accept_with_fallback(
-                    &response_terminal_id,
-                    &response_session_id,
-                    response,
-                    decision,
-                    "chunk",
-                    false,
-                )
-                .await;
-                return;
-            }
-            tracing::debug!(
-                terminal_id = %state.terminal_id,
-                "Skipping duplicate chunk-level Claude bypass accept fallback injection"
-            );
-        }
-
-        if state.auto_confirm && !state.should_debounce() && has_claude_custom_api_key_context {
-            let decision = PromptDecision::LLMDecision {
-                response: "\u{1b}[A\n".to_string(),
-                reasoning: "Auto-select 'Yes' for custom API key prompt via ArrowUp + Enter"
-                    .to_string(),
-                target_index: Some(0),
-            };
-            let detected_prompt =
-                DetectedPrompt::new(PromptKind::ArrowSelect, normalized_output.clone(), 0.95);
-
-            if state.state_machine.should_process(&detected_prompt) {
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected custom API key selection prompt (chunk); sending ArrowUp + Enter to choose Yes"
-                );
-
-                drop(terminals);
-                self.message_bus
-                    .publish_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        "\u{1b}[A\n",
-                        Some(decision),
-                    )
-                    .await;
-                return;
-            }
-            tracing::debug!(
-                terminal_id = %state.terminal_id,
-                "Skipping duplicate chunk-level custom API key fallback injection"
-            );
-        }
-
-        // Chunk-level fallback: Claude can report the configured model as
-        // unavailable on custom endpoints and then idle forever. Auto-instruct
-        // the agent to switch model via /model and continue.
-        if state.auto_confirm
-            && !state.should_debounce()
-            && is_claude_model_unavailable_prompt(&normalized_output)
-        {
-            let decision = PromptDecision::LLMDecision {
-                response: CLAUDE_MODEL_UNAVAILABLE_CONTINUE_RESPONSE.to_string(),
-                reasoning:
-                    "Auto-recover Claude terminal when configured model is unavailable on current endpoint"
-                        .to_string(),
-                target_index: None,
-            };
-            let detected_prompt =
-                DetectedPrompt::new(PromptKind::Input, normalized_output.clone(), 0.95);
-
-            if state.state_machine.should_process(&detected_prompt) {
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected Claude model-unavailable prompt (chunk); sending /model recovery command"
-                );
-
-                drop(terminals);
-                let direct_input = Self::normalize_input_for_direct_write(
-                    CLAUDE_MODEL_UNAVAILABLE_RECOVERY_INPUT,
-                );
-                let sent_direct = self
-                    .try_direct_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        &direct_input,
-                    )
-                    .await;
-                if !sent_direct {
-                    tracing::warn!(
-                        terminal_id = %response_terminal_id,
-                        session_id = %response_session_id,
-                        "Direct PTY model-unavailable recovery failed (chunk mode); falling back to message bus"
-                    );
-                    self.publish_terminal_input_with_active_session(
-                        &response_terminal_id,
-                        &response_session_id,
-                        CLAUDE_MODEL_UNAVAILABLE_RECOVERY_INPUT,
-                        Some(decision),
-                    )
-                    .await;
-                }
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Model-unavailable recovery injected (chunk); sending immediate Enter to submit"
-                );
-                self.publish_terminal_input_with_active_session(
-                    &response_terminal_id,
-                    &response_session_id,
-                    "\n",
-                    Some(PromptDecision::auto_enter()),
-                )
-                .await;
-                return;
-            }
-            tracing::debug!(
-                terminal_id = %state.terminal_id,
-                "Skipping duplicate chunk-level Claude model-unavailable fallback injection"
-            );
-        }
-
-        // Chunk-level fallback for bypass-permissions toggle prompt.
-        // Some terminal frames are emitted as dense ANSI chunks without stable
-        // line boundaries, so line-based detection can miss this interaction.
-        // When auto_confirm is enabled, the bypass prompt alone is sufficient
-        // (no need for explicit "enter to confirm" context text).
-        // Skip if the chunk also contains a handoff stall prompt — let the
-        // line-level processor handle the stall first (it sends the continue
-        // instruction which takes priority over a bare Enter).
-        if state.auto_confirm
-            && !state.should_debounce()
-            && is_bypass_permissions_prompt(&normalized_output)
-            && !has_handoff_stall_context
-        {
-            let decision = PromptDecision::auto_enter();
-            let detected_prompt =
-                DetectedPrompt::new(PromptKind::EnterConfirm, normalized_output.clone(), 0.95);
-
-            if state.state_machine.should_process(&detected_prompt) {
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected bypass permissions prompt (chunk); sending direct auto-enter fallback"
-                );
-
-                drop(terminals);
-                self.message_bus
-                    .publish_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        "\n",
-                        Some(decision),
-                    )
-                    .await;
-                return;
-            }
-            tracing::debug!(
-                terminal_id = %state.terminal_id,
-                "Skipping duplicate chunk-level bypass-permissions fallback injection"
-            );
-        }
-
-        // Chunk-level fallback: decline Notepad prompts to avoid blocking
-        // headless workflow execution in Windows environments.
-        if state.auto_confirm && !state.should_debounce() && is_notepad_prompt(&normalized_output) {
-            let decision = PromptDecision::llm_yes_no(
-                false,
-                "Auto-decline Notepad prompt to keep workflow non-blocking".to_string(),
-            );
-            let detected_prompt =
-                DetectedPrompt::new(PromptKind::YesNo, normalized_output.clone(), 0.95);
-
-            if state.state_machine.should_process(&detected_prompt) {
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected Notepad prompt (chunk); sending 'n'+Enter fallback"
-                );
-
-                drop(terminals);
-                self.message_bus
-                    .publish_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        "n\n",
-                        Some(decision),
-                    )
-                    .await;
-                return;
-            }
-            tracing::debug!(
-                terminal_id = %state.terminal_id,
-                "Skipping duplicate chunk-level Notepad fallback injection"
-            );
-        }
-
-        // Chunk-level fallback: Codex can ask whether it should continue after
-        // seeing "unexpected changes I didn't make". Auto-answer this to keep
-        // orchestrated workflows non-blocking.
-        if state.auto_confirm && !state.should_debounce() && has_unexpected_changes_followup_context {
-            let decision = PromptDecision::LLMDecision {
-                response: UNEXPECTED_CHANGES_CONTINUE_RESPONSE.to_string(),
-                reasoning:
-                    "Auto-continue when Codex asks for confirmation about unexpected workspace changes"
-                        .to_string(),
-                target_index: None,
-            };
-            let detected_prompt =
-                DetectedPrompt::new(PromptKind::Input, normalized_output.clone(), 0.95);
-
-            if state.state_machine.should_process(&detected_prompt) {
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-                state.clear_unexpected_changes_context();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected unexpected-changes follow-up prompt (chunk); sending auto-continue response"
-                );
-
-                drop(terminals);
-                let direct_input =
-                    Self::normalize_input_for_direct_write(UNEXPECTED_CHANGES_CONTINUE_RESPONSE);
-                let sent_direct = self
-                    .try_direct_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        &direct_input,
-                    )
-                    .await;
-                if !sent_direct {
-                    tracing::warn!(
-                        terminal_id = %response_terminal_id,
-                        session_id = %response_session_id,
-                        "Direct PTY unexpected-changes auto-continue failed; falling back to message bus"
-                    );
-                    self.publish_terminal_input_with_active_session(
-                        &response_terminal_id,
-                        &response_session_id,
-                        UNEXPECTED_CHANGES_CONTINUE_RESPONSE,
-                        Some(decision),
-                    )
-                    .await;
-                }
-                return;
-            }
-            tracing::debug!(
-                terminal_id = %state.terminal_id,
-                "Skipping duplicate chunk-level unexpected-changes fallback injection"
-            );
-        }
-
-        // Chunk-level fallback: some agents finish their checks and then ask for
-        // "what next?" instead of creating the required handoff commit.
-        // Auto-instruct them to execute the completion contract immediately.
-        if state.auto_confirm && has_handoff_stall_context {
-            let handoff_continue_response = Self::build_handoff_stall_continue_response(
-                &state.workflow_id,
-                &state.task_id,
-                &state.terminal_id,
-            );
-            let decision = PromptDecision::LLMDecision {
-                response: handoff_continue_response.clone(),
-                reasoning:
-                    "Auto-continue terminal when it waits for next instruction after reporting clean workspace"
-                        .to_string(),
-                target_index: None,
-            };
-            let detected_prompt =
-                DetectedPrompt::new(PromptKind::Input, normalized_output.clone(), 0.95);
-
-            if state.state_machine.should_process(&detected_prompt) {
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-                state.clear_handoff_stall_context();
-                // Always follow handoff reminder injection with one submit Enter.
-                // Some Claude TUI frames keep injected text in composer without
-                // committing unless Enter is sent explicitly.
-                state.clear_pending_handoff_submit();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected handoff-stall prompt (chunk); sending completion-contract reminder"
-                );
-
-                drop(terminals);
-                let direct_input =
-                    Self::normalize_input_for_direct_write(&handoff_continue_response);
-                let sent_direct = self
-                    .try_direct_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        &direct_input,
-                    )
-                    .await;
-                if !sent_direct {
-                    tracing::warn!(
-                        terminal_id = %response_terminal_id,
-                        session_id = %response_session_id,
-                        "Direct PTY handoff-stall reminder failed (chunk mode); falling back to message bus"
-                    );
-                    self.publish_terminal_input_with_active_session(
-                        &response_terminal_id,
-                        &response_session_id,
-                        &handoff_continue_response,
-                        Some(decision),
-                    )
-                    .await;
-                }
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Handoff reminder injected (chunk); sending immediate Enter to submit"
-                );
-                self.publish_terminal_input_with_active_session(
-                    &response_terminal_id,
-                    &response_session_id,
-                    "\n",
-                    Some(PromptDecision::auto_enter()),
-                )
-                .await;
-                return;
-            }
-            tracing::debug!(
-                terminal_id = %state.terminal_id,
-                "Skipping duplicate chunk-level handoff-stall fallback injection"
-            );
-        }
-
-        // Chunk-level fallback: Codex confirmation prompt can appear inside
-        // heavily ANSI-rendered TUI frames without clean newline boundaries.
-        // Detect directly from normalized chunk and auto-reply with "y".
-        if state.auto_confirm
-            && !state.should_debounce()
-            && is_codex_apply_patch_confirmation(&normalized_output)
-        {
-            let decision = PromptDecision::llm_yes_no(
-                true,
-                "Auto-approve Codex apply_patch confirmation".to_string(),
-            );
-            let detected_prompt = DetectedPrompt::new(PromptKind::YesNo, normalized_output, 0.95);
-
-            if state.state_machine.should_process(&detected_prompt) {
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected Codex apply_patch confirmation; sending direct auto-yes fallback"
-                );
-
-                drop(terminals);
-                self.message_bus
-                    .publish_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        "y",
-                        Some(decision),
-                    )
-                    .await;
-                return;
-            }
-            tracing::debug!(
-                terminal_id = %state.terminal_id,
-                "Skipping duplicate chunk-level Codex confirmation fallback injection"
-            );
-        }
-
-        // Process each line
-        for line in output.lines() {
-            let normalized_line = normalize_text_for_detection(line);
-            let normalized_line_lower = normalized_line.to_ascii_lowercase();
-            let has_any_claude_bypass_line_marker =
-                has_any_claude_bypass_marker(&normalized_line_lower);
-            state.observe_claude_bypass_context(&normalized_line_lower);
-            state.observe_unexpected_changes_context(&normalized_line);
-            state.observe_handoff_stall_context(&normalized_line);
-
-            // Claude custom API key prompt fallback:
-            // force "Yes" selection via ArrowUp + Enter.
-            // This avoids defaulting to "No (recommended)" which can block model access.
-            if state.auto_confirm
-                && !state.should_debounce()
-                && is_claude_custom_api_key_prompt(&normalized_line)
-            {
-                let decision = PromptDecision::LLMDecision {
-                    response: "\u{1b}[A\n".to_string(),
-                    reasoning: "Auto-select 'Yes' for custom API key prompt via ArrowUp + Enter"
-                        .to_string(),
-                    target_index: Some(0),
-                };
-                let detected_prompt =
-                    DetectedPrompt::new(PromptKind::ArrowSelect, normalized_line.clone(), 0.95);
-
-                if !state.state_machine.should_process(&detected_prompt) {
-                    tracing::debug!(
-                        terminal_id = %state.terminal_id,
-                        "Skipping duplicate line-level custom API key fallback injection"
-                    );
-                    continue;
-                }
-
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected custom API key selection prompt; sending ArrowUp + Enter to choose Yes"
-                );
-
-                // Publish input (drop lock first to avoid deadlock)
-                drop(terminals);
-                self.message_bus
-                    .publish_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        "\u{1b}[A\n",
-                        Some(decision),
-                    )
-                    .await;
-                return;
-            }
-
-            // Line-level fallback for model-unavailable prompts.
-            if state.auto_confirm
-                && !state.should_debounce()
-                && is_claude_model_unavailable_prompt(&normalized_line)
-            {
-                let decision = PromptDecision::LLMDecision {
-                    response: CLAUDE_MODEL_UNAVAILABLE_CONTINUE_RESPONSE.to_string(),
-                    reasoning:
-                        "Auto-recover Claude terminal when configured model is unavailable on current endpoint"
-                            .to_string(),
-                    target_index: None,
-                };
-                let detected_prompt =
-                    DetectedPrompt::new(PromptKind::Input, normalized_line.clone(), 0.95);
-
-                if !state.state_machine.should_process(&detected_prompt) {
-                    tracing::debug!(
-                        terminal_id = %state.terminal_id,
-                        "Skipping duplicate line-level Claude model-unavailable fallback injection"
-                    );
-                    continue;
-                }
-
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected Claude model-unavailable prompt (line); sending /model recovery command"
-                );
-
-                drop(terminals);
-                let direct_input = Self::normalize_input_for_direct_write(
-                    CLAUDE_MODEL_UNAVAILABLE_RECOVERY_INPUT,
-                );
-                let sent_direct = self
-                    .try_direct_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        &direct_input,
-                    )
-                    .await;
-                if !sent_direct {
-                    tracing::warn!(
-                        terminal_id = %response_terminal_id,
-                        session_id = %response_session_id,
-                        "Direct PTY model-unavailable recovery failed (line mode); falling back to message bus"
-                    );
-                    self.publish_terminal_input_with_active_session(
-                        &response_terminal_id,
-                        &response_session_id,
-                        CLAUDE_MODEL_UNAVAILABLE_RECOVERY_INPUT,
-                        Some(decision),
-                    )
-                    .await;
-                }
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Model-unavailable recovery injected (line); sending immediate Enter to submit"
-                );
-                self.publish_terminal_input_with_active_session(
-                    &response_terminal_id,
-                    &response_session_id,
-                    "\n",
-                    Some(PromptDecision::auto_enter()),
-                )
-                .await;
-                return;
-            }
-
-            if state.auto_confirm
-                && !state.should_debounce()
-                && has_any_claude_bypass_line_marker
-                && state.should_retry_claude_bypass_accept()
-            {
-                let (response, action, reasoning) = claude_bypass_accept_response(&normalized_line);
-                let decision = PromptDecision::LLMDecision {
-                    response: response.to_string(),
-                    reasoning: format!("{reasoning} (retry once because prompt is still visible)"),
-                    target_index: Some(1),
-                };
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-                state.clear_claude_bypass_context();
-                state.mark_claude_bypass_retry_sent();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    action = %action,
-                    "Claude bypass prompt still visible after auto-accept; retrying once (line)"
-                );
-
-                drop(terminals);
-                self.send_claude_bypass_accept_with_fallback(
-                    &response_terminal_id,
-                    &response_session_id,
-                    response,
-                    decision,
-                    "line",
-                    true,
-                )
-                .await;
-                return;
-            }
-
-            // Line-level fallback for Claude bypass-permissions acceptance prompt.
-            // This handles frames where each render only emits a single line.
-            let has_claude_bypass_line_context = is_claude_bypass_accept_prompt(&normalized_line)
-                || (state.has_recent_claude_bypass_accept_context()
-                    && is_claude_bypass_accept_context_line(&normalized_line_lower));
-            if state.auto_confirm && !state.should_debounce() && has_claude_bypass_line_context {
-                let (response, action, reasoning) = claude_bypass_accept_response(&normalized_line);
-                let decision = PromptDecision::LLMDecision {
-                    response: response.to_string(),
-                    reasoning: reasoning.to_string(),
-                    target_index: Some(1),
-                };
-                let detected_prompt =
-                    DetectedPrompt::new(PromptKind::ArrowSelect, normalized_line.clone(), 0.95);
-
-                if !state.state_machine.should_process(&detected_prompt) {
-                    tracing::debug!(
-                        terminal_id = %state.terminal_id,
-                        "Skipping duplicate line-level Claude bypass accept fallback injection"
-                    );
-                    continue;
-                }
-
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-                state.clear_claude_bypass_context();
-                state.mark_claude_bypass_accept_sent();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    action = %action,
-                    "Detected Claude bypass permissions prompt (line); sending auto-accept sequence"
-                );
-
-                drop(terminals);
-                self.send_claude_bypass_accept_with_fallback(
-                    &response_terminal_id,
-                    &response_session_id,
-                    response,
-                    decision,
-                    "line",
-                    false,
-                )
-                .await;
-                return;
-            }
-
-            // Fallback for bypass-permissions prompts — auto-enter when auto_confirm is on.
-            // Skip when handoff stall context is present (handled above with higher priority).
-            if state.auto_confirm
-                && !state.should_debounce()
-                && is_bypass_permissions_prompt(&normalized_line)
-                && !has_handoff_stall_context
-            {
-                let decision = PromptDecision::auto_enter();
-                let detected_prompt =
-                    DetectedPrompt::new(PromptKind::EnterConfirm, normalized_line.clone(), 0.95);
-
-                if !state.state_machine.should_process(&detected_prompt) {
-                    tracing::debug!(
-                        terminal_id = %state.terminal_id,
-                        "Skipping duplicate bypass-permissions fallback injection"
-                    );
-                    continue;
-                }
-
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected bypass permissions prompt; sending direct auto-enter fallback"
-                );
-
-                // Publish input (drop lock first to avoid deadlock)
-                drop(terminals);
-                self.message_bus
-                    .publish_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        "\n",
-                        Some(decision),
-                    )
-                    .await;
-                return;
-            }
-
-            if !state.should_debounce()
-                && state.should_force_handoff_submit()
-                && is_bypass_permissions_prompt(&normalized_line)
-            {
-                let decision = PromptDecision::auto_enter();
-
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-                state.clear_pending_handoff_submit();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected bypass status-line after handoff reminder; sending extra Enter to submit queued input"
-                );
-
-                drop(terminals);
-                self.publish_terminal_input_with_active_session(
-                    &response_terminal_id,
-                    &response_session_id,
-                    "\n",
-                    Some(decision),
-                )
-                .await;
-                return;
-            }
-
-            // Notepad fallback in line-by-line mode.
-            if state.auto_confirm && !state.should_debounce() && is_notepad_prompt(&normalized_line)
-            {
-                let decision = PromptDecision::llm_yes_no(
-                    false,
-                    "Auto-decline Notepad prompt to keep workflow non-blocking".to_string(),
-                );
-                let detected_prompt =
-                    DetectedPrompt::new(PromptKind::YesNo, normalized_line.clone(), 0.95);
-
-                if !state.state_machine.should_process(&detected_prompt) {
-                    tracing::debug!(
-                        terminal_id = %state.terminal_id,
-                        "Skipping duplicate line-level Notepad fallback injection"
-                    );
-                    continue;
-                }
-
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
-
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected Notepad prompt; sending 'n'+Enter fallback"
-                );
-
-                // Publish input (drop lock first to avoid deadlock)
-                drop(terminals);
-                self.message_bus
-                    .publish_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        "n\n",
-                        Some(decision),
-                    )
-                    .await;
-                return;
-            }
-
-            // Line-level fallback for Codex unexpected-changes follow-up prompt.
-            let has_unexpected_changes_line_context =
-                is_unexpected_changes_followup_prompt(&normalized_line)
-                    || state.has_recent_unexpected_changes_context();
-            if !state.should_debounce() && has_unexpected_changes_line_context {
-                let decision = PromptDecision::LLMDecision {
-                    response: UNEXPECTED_CHANGES_CONTINUE_RESPONSE.to_string(),
-                    reasoning:
-                        "Auto-continue when Codex asks for confirmation about unexpected workspace changes"
-                            .to_string(),
-                    target_index: None,
-                };
-                let detected_prompt =
-                    DetectedPrompt::new(PromptKind::Input, normalized_line.clone(), 0.95);
-
-                if !state.state_machine.should_process(&detected_prompt) {
-                    tracing::debug!(
-                        terminal_id = %state.terminal_id,
-                        "Skipping duplicate line-level unexpected-changes fallback injection"
-                    );
-                    continue;
-                }
-
-                state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
-                state.state_machine.on_response_sent(decision.clone());
-                state.detector.clear_buffer();
-                state.clear_unexpected_changes_context();
+    /// [G07-003] NOTE: Prompt rules respect the `auto_confirm` guard unless they
+    /// set `requireAutoConfirm: false` (workflow unblockers such as the
+    /// handoff-stall reminder). When `auto_confirm=false`, the other rules are
+    /// skipped, ensuring manual-mode users retain full control.
+    /// Output is ignored entirely while a client holds the terminal's input lease.
+    pub async fn process_output(&self, terminal_id: &str, output: &str) {
+        // A client holding the input lease answers prompts itself
+        if let Some(lease) = self.process_manager.input_lease(terminal_id).await
+            && lease.owner().is_client()
+        {
+            tracing::trace!(
+                terminal_id = %terminal_id,
+                "Client holds terminal input; skipping prompt automation"
+            );
+            return;
+        }
 
-                let response_terminal_id = state.terminal_id.clone();
-                let response_session_id = state.session_id.clone();
+        // Rendered screen of the PTY session; None when the terminal is not
+        // managed by ProcessManager or the check is throttled.
+        let screen = if self.claim_screen_check(terminal_id).await {
+            self.process_manager.screen_snapshot(terminal_id).await
+        } else {
+            None
+        };
+        let mut terminals = self.terminals.write().await;
 
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Detected unexpected-changes follow-up prompt; sending auto-continue response"
-                );
+        let state = if let Some(s) = terminals.get_mut(terminal_id) { s } else {
+            tracing::trace!(
+                terminal_id = %terminal_id,
+                "Terminal not registered for prompt watching, ignoring output"
+            );
+            return;
+        };
 
-                drop(terminals);
-                let direct_input =
-                    Self::normalize_input_for_direct_write(UNEXPECTED_CHANGES_CONTINUE_RESPONSE);
-                let sent_direct = self
-                    .try_direct_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        &direct_input,
-                    )
-                    .await;
-                if !sent_direct {
-                    tracing::warn!(
-                        terminal_id = %response_terminal_id,
-                        session_id = %response_session_id,
-                        "Direct PTY unexpected-changes auto-continue failed (line mode); falling back to message bus"
-                    );
-                    self.publish_terminal_input_with_active_session(
-                        &response_terminal_id,
-                        &response_session_id,
-                        UNEXPECTED_CHANGES_CONTINUE_RESPONSE,
-                        Some(decision),
-                    )
-                    .await;
-                }
-                return;
-            }
+        // Check and reset stale state
+        state.check_and_reset_stale();
 
-            // Line-level fallback for clean-workspace "what next?" stalls.
-            let has_handoff_stall_line_context = is_handoff_stall_prompt(&normalized_line)
-                || state.has_recent_handoff_stall_context();
-            if has_handoff_stall_line_context {
-                let handoff_continue_response = Self::build_handoff_stall_continue_response(
-                    &state.workflow_id,
-                    &state.task_id,
-                    &state.terminal_id,
-                );
-                let decision = PromptDecision::LLMDecision {
-                    response: handoff_continue_response.clone(),
-                    reasoning:
-                        "Auto-continue terminal when it waits for next instruction after reporting clean workspace"
-                            .to_string(),
-                    target_index: None,
-                };
-                let detected_prompt =
-                    DetectedPrompt::new(PromptKind::Input, normalized_line.clone(), 0.95);
+        // Chunk-level detection: CLI-specific prompts can arrive inside heavily
+        // ANSI-rendered TUI frames without clean newline boundaries, so prompt
+        // rules match the normalized chunk.
+        let normalized_output = normalize_text_for_detection(output);
 
-                if !state.state_machine.should_process(&detected_prompt) {
-                    tracing::debug!(
-                        terminal_id = %state.terminal_id,
-                        "Skipping duplicate line-level handoff-stall fallback injection"
-                    );
-                    continue;
+        // Declarative prompt rules (built-in pack plus rules directory packs).
+        // Every chunk is observed so multi-chunk context windows stay current.
+        let rule_hit = state.rule_matcher.observe(
+            &self.rules.current(),
+            state.cli_type_id.as_deref(),
+            state.auto_confirm,
+            &normalized_output,
+            std::time::Instant::now(),
+        );
+        if !state.should_debounce()
+            && let Some(hit) = rule_hit
+        {
+            let input =
+                hit.response
+                    .render_input(&state.workflow_id, &state.task_id, &state.terminal_id);
+            let decision = if hit.response.kind == PromptKind::EnterConfirm && input == "\n" {
+                PromptDecision::auto_enter()
+            } else {
+                PromptDecision::LLMDecision {
+                    response: input.clone(),
+                    reasoning: if hit.retry {
+                        format!(
+                            "{} (retry because prompt is still visible)",
+                            hit.response.reasoning
+                        )
+                    } else {
+                        hit.response.reasoning.clone()
+                    },
+                    target_index: hit.response.target_index,
                 }
+            };
+            let detected_prompt =
+                DetectedPrompt::new(hit.response.kind, normalized_output.clone(), 0.95);
 
+            // A retry re-answers the prompt that is already being handled
+            if hit.retry || state.state_machine.should_process(&detected_prompt) {
                 state.last_detection = Some(Instant::now());
-                state.state_machine.on_prompt_detected(detected_prompt);
+                if !hit.retry {
+                    state.state_machine.on_prompt_detected(detected_prompt);
+                }
                 state.state_machine.on_response_sent(decision.clone());
                 state.detector.clear_buffer();
-                state.clear_handoff_stall_context();
-                // Always submit after injecting handoff reminder to avoid
-                // composer-only text that never executes.
-                state.clear_pending_handoff_submit();
+                state
+                    .rule_matcher
+                    .mark_fired(&hit, std::time::Instant::now());
 
                 let response_terminal_id = state.terminal_id.clone();
                 let response_session_id = state.session_id.clone();
@@ -2188,149 +761,170 @@ next_action: handoff\n"
                 tracing::info!(
                     terminal_id = %response_terminal_id,
                     session_id = %response_session_id,
-                    "Detected handoff-stall prompt (line); sending completion-contract reminder"
+                    rule_id = %hit.rule_id,
+                    retry = hit.retry,
+                    "Prompt rule matched (chunk); sending rule response"
                 );
 
                 drop(terminals);
-                let direct_input =
-                    Self::normalize_input_for_direct_write(&handoff_continue_response);
-                let sent_direct = self
-                    .try_direct_terminal_input(
-                        &response_terminal_id,
-                        &response_session_id,
-                        &direct_input,
-                    )
-                    .await;
-                if !sent_direct {
-                    tracing::warn!(
-                        terminal_id = %response_terminal_id,
-                        session_id = %response_session_id,
-                        "Direct PTY handoff-stall reminder failed (line mode); falling back to message bus"
-                    );
-                    self.publish_terminal_input_with_active_session(
-                        &response_terminal_id,
-                        &response_session_id,
-                        &handoff_continue_response,
-                        Some(decision),
-                    )
-                    .await;
-                }
-                tracing::info!(
-                    terminal_id = %response_terminal_id,
-                    session_id = %response_session_id,
-                    "Handoff reminder injected (line); sending immediate Enter to submit"
-                );
-                self.publish_terminal_input_with_active_session(
+                self.send_rule_response(
                     &response_terminal_id,
                     &response_session_id,
-                    "\n",
-                    Some(PromptDecision::auto_enter()),
+                    &input,
+                    &hit.response,
+                    decision,
                 )
                 .await;
                 return;
             }
+            tracing::debug!(
+                terminal_id = %state.terminal_id,
+                rule_id = %hit.rule_id,
+                "Skipping duplicate prompt rule injection"
+            );
+        }
+        // Output recognized by a rule is answered by that rule, never by the
+        // generic detector (e.g. Enter on the Claude bypass menu picks "No, exit").
+        if state.rule_matcher.matched() {
+            return;
+        }
 
-            if let Some(prompt) = state.process_line(line) {
-                // Direct fallback for EnterConfirm prompts when auto_confirm is enabled.
-                // This keeps terminals responsive even when Orchestrator is not running
-                // (e.g. workflow in ready/prepare stage).
-                let skip_enter_confirm_fallback = has_claude_bypass_accept_context
-                    || bypass_needs_enter_context
-                    || normalized_output_lower.contains("interrupted")
-                    || normalized_output_lower.contains("custom api key");
-
-                if state.auto_confirm
-                    && prompt.kind == PromptKind::EnterConfirm
-                    && !prompt.has_dangerous_keywords
-                    && !skip_enter_confirm_fallback
-                {
-                    if has_claude_custom_api_key_context {
-                        let decision = PromptDecision::LLMDecision {
-                            response: "\u{1b}[A\n".to_string(),
-                            reasoning:
-                                "Auto-select 'Yes' for custom API key prompt via ArrowUp + Enter"
-                                    .to_string(),
-                            target_index: Some(0),
-                        };
-
-                        state.state_machine.on_response_sent(decision.clone());
-                        state.detector.clear_buffer();
-
-                        let response_terminal_id = state.terminal_id.clone();
-                        let response_session_id = state.session_id.clone();
-
-                        tracing::info!(
-                            terminal_id = %response_terminal_id,
-                            session_id = %response_session_id,
-                            confidence = prompt.confidence,
-                            "Detected EnterConfirm under custom API key context; sending ArrowUp + Enter fallback"
-                        );
+        // Generic detection on the rendered screen, falling back to output
+        // lines when the screen shows no new prompt or was not checked.
+        let mut detected = screen
+            .as_ref()
+            .and_then(|screen| state.process_screen(screen));
+        if detected.is_none() {
+            detected = output.lines().find_map(|line| state.process_line(line));
+        }
+        let Some(prompt) = detected else {
+            return;
+        };
 
-                        drop(terminals);
-                        self.message_bus
-                            .publish_terminal_input(
-                                &response_terminal_id,
-                                &response_session_id,
-                                "\u{1b}[A\n",
-                                Some(decision),
-                            )
-                            .await;
-                        return;
-                    }
+        let context = normalized_output.to_ascii_lowercase();
+        self.dispatch_prompt(terminals, terminal_id, prompt, &context)
+            .await;
+    }
 
-                    let decision = PromptDecision::auto_enter();
+    async fn claim_screen_check(&self, terminal_id: &str) -> bool {
+        let mut terminals = self.terminals.write().await;
+        terminals
+            .get_mut(terminal_id)
+            .is_some_and(TerminalWatchState::claim_screen_check)
+    }
 
-                    state.state_machine.on_response_sent(decision.clone());
-                    state.detector.clear_buffer();
+    async fn pending_screen_check_at(&self, terminal_id: &str) -> Option<Instant> {
+        let terminals = self.terminals.read().await;
+        let state = terminals.get(terminal_id)?;
+        if !state.screen_check_pending {
+            return None;
+        }
+        state.screen_check_due_at()
+    }
 
-                    let response_terminal_id = state.terminal_id.clone();
-                    let response_session_id = state.session_id.clone();
+    /// Check the rendered screen once output settled after throttled chunks.
+    async fn process_pending_screen(&self, terminal_id: &str) {
+        if let Some(lease) = self.process_manager.input_lease(terminal_id).await
+            && lease.owner().is_client()
+        {
+            return;
+        }
+        if !self.claim_screen_check(terminal_id).await {
+            return;
+        }
+        let Some(screen) = self.process_manager.screen_snapshot(terminal_id).await else {
+            return;
+        };
+        let mut terminals = self.terminals.write().await;
+        let Some(state) = terminals.get_mut(terminal_id) else {
+            return;
+        };
+        state.check_and_reset_stale();
+        let Some(prompt) = state.process_screen(&screen) else {
+            return;
+        };
 
-                    tracing::info!(
-                        terminal_id = %response_terminal_id,
-                        session_id = %response_session_id,
-                        confidence = prompt.confidence,
-                        "Detected EnterConfirm prompt; sending direct auto-enter fallback"
-                    );
+        let context = screen.text().to_ascii_lowercase();
+        self.dispatch_prompt(terminals, terminal_id, prompt, &context)
+            .await;
+    }
 
-                    drop(terminals);
-                    self.message_bus
-                        .publish_terminal_input(
-                            &response_terminal_id,
-                            &response_session_id,
-                            "\n",
-                            Some(decision),
-                        )
-                        .await;
-                    return;
-                }
+    /// Auto-confirm a detected prompt or publish it for the Orchestrator.
+    ///
+    /// `context` is the lowercased output the prompt was detected in.
+    async fn dispatch_prompt(
+        &self,
+        mut terminals: RwLockWriteGuard<'_, HashMap<String, TerminalWatchState>>,
+        terminal_id: &str,
+        prompt: DetectedPrompt,
+        context: &str,
+    ) {
+        let Some(state) = terminals.get_mut(terminal_id) else {
+            return;
+        };
 
-                let event = TerminalPromptEvent {
-                    terminal_id: state.terminal_id.clone(),
-                    workflow_id: state.workflow_id.clone(),
-                    task_id: state.task_id.clone(),
-                    session_id: state.session_id.clone(),
-                    auto_confirm: state.auto_confirm,
-                    prompt: prompt.clone(),
-                    detected_at: chrono::Utc::now(),
-                };
+        // Direct fallback for EnterConfirm prompts when auto_confirm is enabled.
+        // This keeps terminals responsive even when Orchestrator is not running
+        // (e.g. workflow in ready/prepare stage).
+        let skip_enter_confirm_fallback = context.contains("interrupted")
+            || context.contains("press ctrl-c again to exit")
+            || context.contains("custom api key");
 
-                tracing::info!(
-                    terminal_id = %state.terminal_id,
-                    prompt_kind = ?prompt.kind,
-                    confidence = prompt.confidence,
-                    has_dangerous_keywords = prompt.has_dangerous_keywords,
-                    "Detected interactive prompt"
-                );
+        if state.auto_confirm
+            && prompt.kind == PromptKind::EnterConfirm
+            && !prompt.has_dangerous_keywords
+            && !skip_enter_confirm_fallback
+        {
+            let decision = PromptDecision::auto_enter();
 
-                // Publish event (drop lock first to avoid deadlock)
-                drop(terminals);
-                self.message_bus
-                    .publish_terminal_prompt_detected(event)
-                    .await;
-                return;
-            }
+            state.state_machine.on_response_sent(decision.clone());
+            state.detector.clear_buffer();
+
+            let response_terminal_id = state.terminal_id.clone();
+            let response_session_id = state.session_id.clone();
+
+            tracing::info!(
+                terminal_id = %response_terminal_id,
+                session_id = %response_session_id,
+                confidence = prompt.confidence,
+                "Detected EnterConfirm prompt; sending direct auto-enter fallback"
+            );
+
+            drop(terminals);
+            self.message_bus
+                .publish_terminal_input(
+                    &response_terminal_id,
+                    &response_session_id,
+                    "\n",
+                    Some(decision),
+                )
+                .await;
+            return;
         }
+
+        let event = TerminalPromptEvent {
+            terminal_id: state.terminal_id.clone(),
+            workflow_id: state.workflow_id.clone(),
+            task_id: state.task_id.clone(),
+            session_id: state.session_id.clone(),
+            auto_confirm: state.auto_confirm,
+            prompt: prompt.clone(),
+            detected_at: chrono::Utc::now(),
+        };
+
+        tracing::info!(
+            terminal_id = %state.terminal_id,
+            prompt_kind = ?prompt.kind,
+            confidence = prompt.confidence,
+            has_dangerous_keywords = prompt.has_dangerous_keywords,
+            "Detected interactive prompt"
+        );
+
+        // Publish event (drop lock first to avoid deadlock)
+        drop(terminals);
+        self.message_bus
+            .publish_terminal_prompt_detected(event)
+            .await;
     }
 
     /// Update terminal state after response is sent
@@ -2343,9 +937,7 @@ next_action: handoff\n"
         if let Some(state) = terminals.get_mut(terminal_id) {
             state.state_machine.on_response_sent(decision);
             state.detector.clear_buffer();
-            state.clear_claude_bypass_context();
-            state.clear_handoff_stall_context();
-            state.clear_pending_handoff_submit();
+            state.rule_matcher.clear();
         }
     }
 
@@ -2367,9 +959,8 @@ next_action: handoff\n"
         if let Some(state) = terminals.get_mut(terminal_id) {
             state.state_machine.reset();
             state.detector.clear_buffer();
-            state.clear_claude_bypass_context();
-            state.clear_handoff_stall_context();
-            state.clear_pending_handoff_submit();
+            state.last_screen_prompt = None;
+            state.rule_matcher.clear();
         }
     }
 
@@ -2395,7 +986,21 @@ next_action: handoff\n"
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::services::orchestrator::message_bus::{BusMessage, MessageBus};
+    use crate::services::{
+        orchestrator::message_bus::{BusMessage, MessageBus},
+        terminal::{prompt_rules::PromptRulePack, screen::TerminalScreen},
+    };
+
+    /// Rendered input of a built-in rule for the standard test terminal
+    fn builtin_rule_input(rule_id: &str) -> String {
+        PromptRulePack::builtin()
+            .rules
+            .into_iter()
+            .find(|rule| rule.id == rule_id)
+            .and_then(|rule| rule.response)
+            .expect("built-in rule must exist")
+            .render_input("workflow-1", "task-1", "term-1")
+    }
 
     fn create_test_watcher() -> PromptWatcher {
         let message_bus = Arc::new(MessageBus::new(100));
@@ -2410,7 +1015,14 @@ mod tests {
         // Registration will fail because no terminal exists in ProcessManager
         // This is expected in unit tests - we're testing state management, not integration
         let result = watcher
-            .register("term-1", "workflow-1", "task-1", "session-1", true)
+            .register(
+                "term-1",
+                "workflow-1",
+                "task-1",
+                "session-1",
+                "cli-codex",
+                true,
+            )
             .await;
 
         // Registration should fail with terminal not found
@@ -2431,7 +1043,14 @@ mod tests {
         let watcher = create_test_watcher();
 
         let result = watcher
-            .register("term-1", "workflow-1", "task-1", "session-1", false)
+            .register(
+                "term-1",
+                "workflow-1",
+                "task-1",
+                "session-1",
+                "cli-codex",
+                false,
+            )
             .await;
 
         assert!(result.is_err());
@@ -2861,9 +1480,8 @@ WARNING: Claude Code running in Bypass Permissions mode
                 .expect("terminal state should exist");
             state.last_detection =
                 Some(Instant::now() - Duration::from_millis(PROMPT_DEBOUNCE_MS + 1));
-            state.pending_claude_bypass_retry_since = Some(
-                Instant::now() - Duration::from_millis(CLAUDE_BYPASS_ACCEPT_RETRY_DELAY_MS + 1),
-            );
+            // Past the rule's retry delay
+            state.rule_matcher.rewind(Duration::from_secs(1));
         }
 
         watcher.process_output("term-1", prompt_single_line).await;
@@ -2884,9 +1502,8 @@ WARNING: Claude Code running in Bypass Permissions mode
                 .expect("terminal state should exist");
             state.last_detection =
                 Some(Instant::now() - Duration::from_millis(PROMPT_DEBOUNCE_MS + 1));
-            state.pending_claude_bypass_retry_since = Some(
-                Instant::now() - Duration::from_millis(CLAUDE_BYPASS_ACCEPT_RETRY_DELAY_MS + 1),
-            );
+            // Past the rule's retry delay
+            state.rule_matcher.rewind(Duration::from_secs(1));
         }
 
         watcher.process_output("term-1", prompt_single_line).await;
@@ -3039,7 +1656,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert_eq!(input, UNEXPECTED_CHANGES_CONTINUE_RESPONSE);
+                assert_eq!(input, builtin_rule_input("unexpected-changes-continue"));
                 assert!(matches!(
                     decision,
                     Some(crate::services::orchestrator::types::PromptDecision::LLMDecision { .. })
@@ -3092,7 +1709,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert_eq!(input, UNEXPECTED_CHANGES_CONTINUE_RESPONSE);
+                assert_eq!(input, builtin_rule_input("unexpected-changes-continue"));
                 assert!(matches!(
                     decision,
                     Some(crate::services::orchestrator::types::PromptDecision::LLMDecision { .. })
@@ -3157,7 +1774,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert_eq!(input, UNEXPECTED_CHANGES_CONTINUE_RESPONSE);
+                assert_eq!(input, builtin_rule_input("unexpected-changes-continue"));
                 assert!(matches!(
                     decision,
                     Some(crate::services::orchestrator::types::PromptDecision::LLMDecision { .. })
@@ -3209,7 +1826,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3264,7 +1881,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3319,7 +1936,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3374,7 +1991,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3429,7 +2046,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3485,7 +2102,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3544,7 +2161,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3630,7 +2247,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3685,7 +2302,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3740,7 +2357,7 @@ WARNING: Claude Code running in Bypass Permissions mode
             } => {
                 assert_eq!(terminal_id, "term-1");
                 assert_eq!(session_id, "session-1");
-                assert!(input.contains(HANDOFF_STALL_CONTINUE_RESPONSE));
+                assert_eq!(input, builtin_rule_input("handoff-stall"));
                 assert!(input.contains("workflow_id: workflow-1"));
                 assert!(input.contains("task_id: task-1"));
                 assert!(input.contains("terminal_id: term-1"));
@@ -3949,57 +2566,6 @@ WARNING: Claude Code running in Bypass Permissions mode
         );
     }
 
-    #[tokio::test]
-    async fn test_process_output_bypass_status_line_auto_enters_when_handoff_submit_pending() {
-        let message_bus = Arc::new(MessageBus::new(100));
-        let process_manager = Arc::new(ProcessManager::new());
-        let watcher = PromptWatcher::new(message_bus.clone(), process_manager);
-        let mut broadcast_rx = message_bus.subscribe_broadcast();
-
-        {
-            let mut terminals = watcher.terminals.write().await;
-            let mut state = TerminalWatchState::new(
-                "term-1".to_string(),
-                "workflow-1".to_string(),
-                "task-1".to_string(),
-                "session-1".to_string(),
-                true,
-            );
-            state.mark_pending_handoff_submit();
-            terminals.insert("term-1".to_string(), state);
-        }
-
-        watcher
-            .process_output(
-                "term-1",
-                "bypass permissions on (shift+tab to cycle) ctrl+g to edit in Notepad",
-            )
-            .await;
-
-        let event = tokio::time::timeout(Duration::from_millis(200), broadcast_rx.recv())
-            .await
-            .expect("expected terminal input broadcast")
-            .expect("broadcast channel should be open");
-
-        match event {
-            BusMessage::TerminalInput {
-                terminal_id,
-                session_id,
-                input,
-                decision,
-            } => {
-                assert_eq!(terminal_id, "term-1");
-                assert_eq!(session_id, "session-1");
-                assert_eq!(input, "\n");
-                assert!(matches!(
-                    decision,
-                    Some(crate::services::orchestrator::types::PromptDecision::AutoConfirm { .. })
-                ));
-            }
-            other => panic!("expected TerminalInput event, got: {other:?}"),
-        }
-    }
-
     #[tokio::test]
     async fn test_process_output_claude_custom_api_key_prompt_auto_select_yes() {
         let message_bus = Arc::new(MessageBus::new(100));
@@ -4323,6 +2889,80 @@ WARNING: Claude Code running in Bypass Permissions mode
         }
     }
 
+    #[tokio::test]
+    async fn test_process_output_applies_cli_scoped_rule_pack_across_chunks() {
+        let rules_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            rules_dir.path().join("claude.yaml"),
+            r#"
+name: claude
+rules:
+  - id: claude-resume-session
+    cliTypes: [claude-code]
+    when:
+      all: ['(?i)session expired', '(?i)waiting for resume']
+      contextSecs: 10
+    response:
+      kind: input
+      input: "resume {task_id}"
+      reasoning: Resume expired session
+      submitEnter: true
+"#,
+        )
+        .unwrap();
+        let message_bus = Arc::new(MessageBus::new(100));
+        let process_manager = Arc::new(ProcessManager::new());
+        let watcher = PromptWatcher::new(message_bus.clone(), process_manager).with_rule_store(
+            Arc::new(PromptRuleStore::load(rules_dir.path().to_path_buf())),
+        );
+        let mut broadcast_rx = message_bus.subscribe_broadcast();
+
+        {
+            let mut terminals = watcher.terminals.write().await;
+            for (terminal_id, cli_type_id) in
+                [("term-1", "cli-claude-code"), ("term-2", "cli-codex")]
+            {
+                let mut state = TerminalWatchState::new(
+                    terminal_id.to_string(),
+                    "workflow-1".to_string(),
+                    "task-1".to_string(),
+                    format!("session-{terminal_id}"),
+                    true,
+                );
+                state.cli_type_id = Some(cli_type_id.to_string());
+                terminals.insert(terminal_id.to_string(), state);
+            }
+        }
+
+        for terminal_id in ["term-2", "term-1"] {
+            watcher
+                .process_output(terminal_id, "\u{1b}[31mSession expired.\u{1b}[0m")
+                .await;
+            watcher
+                .process_output(terminal_id, "Waiting for resume...")
+                .await;
+        }
+
+        let mut inputs = Vec::new();
+        while let Ok(Ok(event)) =
+            tokio::time::timeout(Duration::from_millis(200), broadcast_rx.recv()).await
+        {
+            if let BusMessage::TerminalInput {
+                terminal_id, input, ..
+            } = event
+            {
+                inputs.push((terminal_id, input));
+            }
+        }
+        assert_eq!(
+            inputs,
+            vec![
+                ("term-1".to_string(), "resume task-1".to_string()),
+                ("term-1".to_string(), "\n".to_string()),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_get_state() {
         let watcher = create_test_watcher();
@@ -4338,4 +2978,121 @@ WARNING: Claude Code running in Bypass Permissions mode
         // Reset on unregistered terminal should not panic
         watcher.reset_state("term-1").await;
     }
+
+    /// Spawns `sh -c script` on a real PTY and watches it without auto-confirm.
+    #[cfg(unix)]
+    async fn watch_shell_script(
+        script: &str,
+    ) -> (
+        PromptWatcher,
+        Arc<ProcessManager>,
+        tokio::sync::broadcast::Receiver<BusMessage>,
+    ) {
+        let message_bus = Arc::new(MessageBus::new(100));
+        let process_manager = Arc::new(ProcessManager::new());
+        let watcher = PromptWatcher::new(message_bus.clone(), Arc::clone(&process_manager));
+        let broadcast_rx = message_bus.subscribe_broadcast();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let spawn_config =
+            crate::services::terminal::process::SpawnCommand::new("sh", temp_dir.path())
+                .with_args(["-c", script]);
+        let handle = process_manager
+            .spawn_pty_with_config("term-1", &spawn_config, 80, 24)
+            .await
+            .unwrap();
+        watcher
+            .register(
+                "term-1",
+                "workflow-1",
+                "task-1",
+                &handle.session_id,
+                "cli-claude-code",
+                false,
+            )
+            .await
+            .unwrap();
+        (watcher, process_manager, broadcast_rx)
+    }
+
+    #[cfg(unix)]
+    async fn next_prompt_event(
+        broadcast_rx: &mut tokio::sync::broadcast::Receiver<BusMessage>,
+    ) -> TerminalPromptEvent {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let BusMessage::TerminalPromptDetected(event) =
+                    broadcast_rx.recv().await.unwrap()
+                {
+                    return event;
+                }
+            }
+        })
+        .await
+        .expect("expected prompt event broadcast")
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_process_output_detects_menu_on_real_screen_after_throttled_chunk() {
+        // The menu is painted bottom-up with cursor moves, so only the rendered
+        // screen shows it as a menu. It follows the first chunk within the
+        // screen check interval and is picked up once output settles.
+        let (_watcher, process_manager, mut broadcast_rx) = watch_shell_script(
+            "sleep 0.5; printf 'Working...\\r\\n'; sleep 0.05; \
+             printf '\\033[4;1H  Opus\\033[3;1H\\033[7m❯ Sonnet\\033[0m\\033[2;1HSelect a model'; \
+             sleep 10",
+        )
+        .await;
+
+        let event = next_prompt_event(&mut broadcast_rx).await;
+        assert_eq!(event.terminal_id, "term-1");
+        assert_eq!(event.prompt.kind, PromptKind::ArrowSelect);
+        assert_eq!(event.prompt.selected_index, Some(0));
+
+        let _ = process_manager.kill_terminal("term-1").await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_process_output_falls_back_to_lines_when_screen_shows_no_prompt() {
+        // The prompt is cleared from the screen in the same write
+        let (_watcher, process_manager, mut broadcast_rx) =
+            watch_shell_script("printf 'Press Enter to continue\\r\\n\\033[2J\\033[H'; sleep 10")
+                .await;
+
+        let event = next_prompt_event(&mut broadcast_rx).await;
+        assert_eq!(event.prompt.kind, PromptKind::EnterConfirm);
+
+        let _ = process_manager.kill_terminal("term-1").await;
+    }
+
+    #[test]
+    fn test_process_screen_ignores_menu_still_on_screen_after_response() {
+        let mut state = TerminalWatchState::new(
+            "term-1".to_string(),
+            "workflow-1".to_string(),
+            "task-1".to_string(),
+            "session-1".to_string(),
+            true,
+        );
+        let mut screen = TerminalScreen::new(10, 60);
+        let menu = "Select a model\r\n\x1b[7m❯ Sonnet\x1b[0m\r\n  Opus\r\n";
+        screen.process(menu.as_bytes());
+
+        let prompt = state.process_screen(&screen.snapshot()).unwrap();
+        assert_eq!(prompt.kind, PromptKind::ArrowSelect);
+        assert_eq!(prompt.selected_index, Some(0));
+
+        // Response handled, but the CLI has not repainted yet.
+        state.state_machine.reset();
+        state.last_detection = None;
+        assert!(state.process_screen(&screen.snapshot()).is_none());
+
+        // Menu cleared, then shown again: a new prompt.
+        screen.process(b"\x1b[2J\x1b[HThinking...\r\n");
+        assert!(state.process_screen(&screen.snapshot()).is_none());
+        screen.process(format!("\x1b[2J\x1b[H{menu}").as_bytes());
+        assert!(state.process_screen(&screen.snapshot()).is_some());
+    }
 }
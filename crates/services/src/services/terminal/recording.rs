@@ -0,0 +1,669 @@
+//! Terminal Session Recording
+//!
+//! Every PTY session is recorded as an asciicast v2 file with output, input
+//! and resize events, stored as `<dir>/<terminal_id>/<session_id>.cast`, so a
+//! failed run can be reviewed afterwards. Input covers user keystrokes and
+//! orchestrator-injected `TerminalInput`, since both go through the shared
+//! `PtyWriter`; typed text is masked unless configured otherwise, as it may
+//! contain secrets.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::prompt_rules::{TranscriptEvent, parse_asciicast};
+
+/// Recording file extension
+pub const RECORDING_EXTENSION: &str = "cast";
+
+/// Buffered events are flushed to disk at least this often
+const RECORDING_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default size cap of one session recording
+const DEFAULT_MAX_SESSION_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Default number of recordings kept per terminal
+const DEFAULT_KEEP_SESSIONS: usize = 20;
+
+/// How bytes written to the PTY are recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputCapture {
+    /// No input events
+    Exclude,
+    /// Input events with printable characters masked
+    Redact,
+    /// Input events verbatim
+    Full,
+}
+
+impl InputCapture {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "exclude" | "none" | "off" => Some(Self::Exclude),
+            "redact" => Some(Self::Redact),
+            "full" | "on" => Some(Self::Full),
+            _ => None,
+        }
+    }
+}
+
+/// Session recording settings
+#[derive(Debug, Clone)]
+pub struct RecordingConfig {
+    /// Root directory for recordings
+    pub dir: PathBuf,
+    pub input: InputCapture,
+    /// Events beyond this size are dropped and the recording is closed
+    pub max_session_bytes: u64,
+    /// Older recordings of a terminal are deleted when a new session starts
+    pub keep_sessions: usize,
+}
+
+impl RecordingConfig {
+    /// Recording into `dir` with input redacted and the default limits.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            input: InputCapture::Redact,
+            max_session_bytes: DEFAULT_MAX_SESSION_BYTES,
+            keep_sessions: DEFAULT_KEEP_SESSIONS,
+        }
+    }
+
+    pub fn with_input(mut self, input: InputCapture) -> Self {
+        self.input = input;
+        self
+    }
+
+    /// Recording is on unless `SOLODAWN_RECORD_SESSIONS` is `0`/`false`/`no`/`off`.
+    ///
+    /// Stored in `SOLODAWN_RECORDINGS_DIR`, or `recordings` under the asset
+    /// dir. `SOLODAWN_RECORDING_INPUT` (`exclude`/`redact`/`full`),
+    /// `SOLODAWN_RECORDING_MAX_MB` and `SOLODAWN_RECORDING_KEEP` override the
+    /// defaults.
+    pub fn from_env() -> Option<Self> {
+        use utils::env_compat::var_opt_with_compat;
+
+        let disabled = var_opt_with_compat("SOLODAWN_RECORD_SESSIONS", "GITCORTEX_RECORD_SESSIONS")
+            .is_some_and(|value| {
+                matches!(
+                    value.trim().to_ascii_lowercase().as_str(),
+                    "0" | "false" | "no" | "off"
+                )
+            });
+        if disabled {
+            tracing::info!("Session recording disabled via SOLODAWN_RECORD_SESSIONS");
+            return None;
+        }
+        let dir = var_opt_with_compat("SOLODAWN_RECORDINGS_DIR", "GITCORTEX_RECORDINGS_DIR")
+            .map(PathBuf::from)
+            .or_else(|| {
+                utils::assets::asset_dir()
+                    .ok()
+                    .map(|dir| dir.join("recordings"))
+            })?;
+
+        let mut config = Self::new(dir);
+        if let Some(value) =
+            var_opt_with_compat("SOLODAWN_RECORDING_INPUT", "GITCORTEX_RECORDING_INPUT")
+        {
+            match InputCapture::parse(&value) {
+                Some(input) => config.input = input,
+                None => tracing::warn!(
+                    value = %value,
+                    "Unknown SOLODAWN_RECORDING_INPUT; input is redacted"
+                ),
+            }
+        }
+        if let Some(mb) =
+            var_opt_with_compat("SOLODAWN_RECORDING_MAX_MB", "GITCORTEX_RECORDING_MAX_MB")
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        {
+            config.max_session_bytes = mb.saturating_mul(1024 * 1024);
+        }
+        if let Some(keep) =
+            var_opt_with_compat("SOLODAWN_RECORDING_KEEP", "GITCORTEX_RECORDING_KEEP")
+                .and_then(|value| value.trim().parse::<usize>().ok())
+        {
+            config.keep_sessions = keep.max(1);
+        }
+        Some(config)
+    }
+}
+
+/// Masks typed text; escape sequences (arrow keys, bracketed paste markers
+/// etc.) and control characters are kept so the recording still shows how the
+/// terminal was driven. Text between escape sequences, such as the body of a
+/// paste, is masked.
+fn redact_input(data: &str) -> String {
+    let mut redacted = String::with_capacity(data.len());
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            redacted.push(if c.is_control() { c } else { '*' });
+            continue;
+        }
+        redacted.push(c);
+        if chars.next_if_eq(&'[').is_some() {
+            // CSI: parameter and intermediate bytes up to the final byte
+            redacted.push('[');
+            for c in chars.by_ref() {
+                redacted.push(c);
+                if !matches!(c, '\u{20}'..='\u{3f}') {
+                    break;
+                }
+            }
+        } else if chars.next_if_eq(&'O').is_some() {
+            // SS3: a single function or keypad key
+            redacted.push('O');
+            redacted.extend(chars.next());
+        }
+    }
+    redacted
+}
+
+/// Ids are used as path segments; reject anything that could escape the dir.
+fn validate_path_segment(value: &str) -> Result<()> {
+    if value.is_empty()
+        || !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Invalid recording id: {value}");
+    }
+    Ok(())
+}
+
+/// Path of the recording of one PTY session.
+pub fn recording_path(dir: &Path, terminal_id: &str, session_id: &str) -> Result<PathBuf> {
+    validate_path_segment(terminal_id)?;
+    validate_path_segment(session_id)?;
+    Ok(dir
+        .join(terminal_id)
+        .join(format!("{session_id}.{RECORDING_EXTENSION}")))
+}
+
+/// Stored recording of one PTY session
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RecordingInfo {
+    pub session_id: String,
+    pub size_bytes: u64,
+    pub modified_at: DateTime<Utc>,
+}
+
+/// Recordings of a terminal, newest first.
+pub fn list_recordings(dir: &Path, terminal_id: &str) -> Result<Vec<RecordingInfo>> {
+    validate_path_segment(terminal_id)?;
+    let terminal_dir = dir.join(terminal_id);
+    if !terminal_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut recordings = Vec::new();
+    for entry in std::fs::read_dir(&terminal_dir)
+        .with_context(|| format!("Failed to read {}", terminal_dir.display()))?
+    {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(RECORDING_EXTENSION) {
+            continue;
+        }
+        let Some(session_id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let metadata = std::fs::metadata(&path)?;
+        recordings.push(RecordingInfo {
+            session_id: session_id.to_string(),
+            size_bytes: metadata.len(),
+            modified_at: metadata
+                .modified()
+                .map(DateTime::from)
+                .unwrap_or_else(|_| Utc::now()),
+        });
+    }
+    recordings.sort_by_key(|recording| std::cmp::Reverse(recording.modified_at));
+    Ok(recordings)
+}
+
+/// Parsed recording for replay
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub width: u16,
+    pub height: u16,
+    /// Output events in recording order
+    pub events: Vec<TranscriptEvent>,
+}
+
+/// Parses an asciicast v2 recording; only output events are kept.
+///
+/// A trailing partial line (session still being recorded) is ignored.
+pub fn parse_recording(content: &str) -> Result<Recording> {
+    let content = &content[..content.rfind('\n').map_or(content.len(), |end| end + 1)];
+    let header = content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .context("Recording is empty")?;
+    let header: serde_json::Value =
+        serde_json::from_str(header).context("Invalid asciicast header")?;
+    let dimension = |key: &str| {
+        header
+            .get(key)
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|value| u16::try_from(value).ok())
+            .with_context(|| format!("Asciicast header is missing `{key}`"))
+    };
+    Ok(Recording {
+        width: dimension("width")?,
+        height: dimension("height")?,
+        events: parse_asciicast(content)?,
+    })
+}
+
+/// Delete the oldest recordings of a terminal so at most `keep` remain.
+fn prune_recordings(dir: &Path, terminal_id: &str, keep: usize) -> Result<()> {
+    for stale in list_recordings(dir, terminal_id)?.into_iter().skip(keep) {
+        let path = recording_path(dir, terminal_id, &stale.session_id)?;
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+struct RecorderState {
+    writer: BufWriter<File>,
+    last_flush: Instant,
+    bytes_written: u64,
+}
+
+/// Asciicast v2 writer for one PTY session.
+///
+/// Write failures are logged once and stop the recording; they never affect
+/// the terminal itself.
+pub struct SessionRecorder {
+    terminal_id: String,
+    input: InputCapture,
+    max_bytes: u64,
+    started: Instant,
+    state: Mutex<Option<RecorderState>>,
+}
+
+impl SessionRecorder {
+    /// Create the recording file and write the asciicast header, pruning the
+    /// terminal's older recordings first.
+    pub fn create(
+        config: &RecordingConfig,
+        terminal_id: &str,
+        session_id: &str,
+        cols: u16,
+        rows: u16,
+    ) -> Result<Self> {
+        let path = recording_path(&config.dir, terminal_id, session_id)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        if let Err(e) = prune_recordings(
+            &config.dir,
+            terminal_id,
+            config.keep_sessions.saturating_sub(1),
+        ) {
+            tracing::warn!(
+                terminal_id = %terminal_id,
+                error = %e,
+                "Failed to prune old terminal recordings"
+            );
+        }
+        let file =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+
+        let header = serde_json::json!({
+            "version": 2,
+            "width": cols,
+            "height": rows,
+            "timestamp": Utc::now().timestamp(),
+            "title": terminal_id,
+            "env": { "TERM": "xterm-256color" },
+        });
+        let header = format!("{header}\n");
+        writer.write_all(header.as_bytes())?;
+        writer.flush()?;
+
+        Ok(Self::with_writer(
+            config,
+            terminal_id,
+            Instant::now(),
+            writer,
+            header.len() as u64,
+        ))
+    }
+
+    /// Continue the recording of a session that outlived the server.
+    ///
+    /// Events are appended with offsets relative to the original header, after
+    /// dropping a partial line left by the previous writer. Falls back to a
+    /// new recording when the file is missing.
+    pub fn resume(
+        config: &RecordingConfig,
+        terminal_id: &str,
+        session_id: &str,
+        cols: u16,
+        rows: u16,
+    ) -> Result<Self> {
+        let path = recording_path(&config.dir, terminal_id, session_id)?;
+        let content = match std::fs::read(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Self::create(config, terminal_id, session_id, cols, rows);
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        let complete_len = content
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |end| end + 1);
+        let header: serde_json::Value = content[..complete_len]
+            .split(|&byte| byte == b'\n')
+            .next()
+            .and_then(|line| serde_json::from_slice(line).ok())
+            .with_context(|| format!("Invalid asciicast header in {}", path.display()))?;
+        let recorded_secs = header
+            .get("timestamp")
+            .and_then(serde_json::Value::as_i64)
+            .map_or(0, |timestamp| (Utc::now().timestamp() - timestamp).max(0));
+        let started = Instant::now()
+            .checked_sub(Duration::from_secs(recorded_secs as u64))
+            .unwrap_or_else(Instant::now);
+
+        let file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        file.set_len(complete_len as u64)
+            .with_context(|| format!("Failed to truncate {}", path.display()))?;
+
+        Ok(Self::with_writer(
+            config,
+            terminal_id,
+            started,
+            BufWriter::new(file),
+            complete_len as u64,
+        ))
+    }
+
+    fn with_writer(
+        config: &RecordingConfig,
+        terminal_id: &str,
+        started: Instant,
+        writer: BufWriter<File>,
+        bytes_written: u64,
+    ) -> Self {
+        Self {
+            terminal_id: terminal_id.to_string(),
+            input: config.input,
+            max_bytes: config.max_session_bytes,
+            started,
+            state: Mutex::new(Some(RecorderState {
+                writer,
+                last_flush: Instant::now(),
+                bytes_written,
+            })),
+        }
+    }
+
+    /// Record PTY output (`"o"` event).
+    pub fn record_output(&self, data: &str) {
+        self.write_event("o", data, false);
+    }
+
+    /// Record bytes written to the PTY (`"i"` event), per the input capture.
+    pub fn record_input(&self, data: &[u8]) {
+        let data = String::from_utf8_lossy(data);
+        match self.input {
+            InputCapture::Exclude => {}
+            InputCapture::Redact => self.write_event("i", &redact_input(&data), true),
+            InputCapture::Full => self.write_event("i", &data, true),
+        }
+    }
+
+    /// Record a terminal resize (`"r"` event).
+    pub fn record_resize(&self, cols: u16, rows: u16) {
+        self.write_event("r", &format!("{cols}x{rows}"), true);
+    }
+
+    /// Flush buffered events and close the file.
+    pub fn finish(&self) {
+        let mut guard = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(mut state) = guard.take()
+            && let Err(e) = state.writer.flush()
+        {
+            tracing::warn!(
+                terminal_id = %self.terminal_id,
+                error = %e,
+                "Failed to flush terminal recording"
+            );
+        }
+    }
+
+    fn write_event(&self, code: &str, data: &str, flush: bool) {
+        if data.is_empty() {
+            return;
+        }
+        let mut guard = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Some(state) = guard.as_mut() else {
+            return;
+        };
+
+        let offset = self.started.elapsed().as_secs_f64();
+        let event = serde_json::json!([(offset * 1_000_000.0).round() / 1_000_000.0, code, data]);
+        let line = format!("{event}\n");
+        if state.bytes_written + line.len() as u64 > self.max_bytes {
+            tracing::info!(
+                terminal_id = %self.terminal_id,
+                max_bytes = self.max_bytes,
+                "Terminal recording reached its size limit; recording stopped"
+            );
+            if let Err(e) = state.writer.flush() {
+                tracing::warn!(
+                    terminal_id = %self.terminal_id,
+                    error = %e,
+                    "Failed to flush terminal recording"
+                );
+            }
+            *guard = None;
+            return;
+        }
+
+        let mut result = state.writer.write_all(line.as_bytes());
+        state.bytes_written += line.len() as u64;
+        if result.is_ok() && (flush || state.last_flush.elapsed() >= RECORDING_FLUSH_INTERVAL) {
+            result = state.writer.flush();
+            state.last_flush = Instant::now();
+        }
+
+        if let Err(e) = result {
+            tracing::warn!(
+                terminal_id = %self.terminal_id,
+                error = %e,
+                "Terminal recording write failed; recording stopped"
+            );
+            *guard = None;
+        }
+    }
+}
+
+impl Drop for SessionRecorder {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_output_input_and_resize_as_asciicast() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = RecordingConfig::new(dir.path().to_path_buf()).with_input(InputCapture::Full);
+        let recorder = SessionRecorder::create(&config, "term-1", "session-1", 100, 30).unwrap();
+        recorder.record_output("\u{1b}[1mhello\u{1b}[0m\r\n");
+        recorder.record_input(b"yes\n");
+        recorder.record_resize(120, 40);
+        recorder.record_output("");
+        recorder.finish();
+        // Events after finish are dropped
+        recorder.record_output("late");
+
+        let path = recording_path(dir.path(), "term-1", "session-1").unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        let codes = lines[1..]
+            .iter()
+            .map(|line| serde_json::from_str::<(f64, String, String)>(line).unwrap())
+            .map(|(_, code, data)| format!("{code}:{data}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec!["o:\u{1b}[1mhello\u{1b}[0m\r\n", "i:yes\n", "r:120x40"]
+        );
+
+        let recording = parse_recording(&content).unwrap();
+        assert_eq!((recording.width, recording.height), (100, 30));
+        assert_eq!(recording.events.len(), 1);
+        let partial = format!("{content}[1.5, \"o\", \"trunc");
+        assert_eq!(parse_recording(&partial).unwrap().events.len(), 1);
+
+        let listed = list_recordings(dir.path(), "term-1").unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].session_id, "session-1");
+        assert!(list_recordings(dir.path(), "term-2").unwrap().is_empty());
+    }
+
+    #[test]
+    fn input_is_excluded_or_redacted_unless_full_capture() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_events = |input: InputCapture, session_id: &str| {
+            let config = RecordingConfig::new(dir.path().to_path_buf()).with_input(input);
+            let recorder = SessionRecorder::create(&config, "term-1", session_id, 80, 24).unwrap();
+            recorder.record_input(b"hunter2\r");
+            recorder.record_input(b"\x1b[A");
+            recorder.record_input(b"\x1b[200~sk-secret\x1b[201~");
+            recorder.finish();
+            let path = recording_path(dir.path(), "term-1", session_id).unwrap();
+            std::fs::read_to_string(path)
+                .unwrap()
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    serde_json::from_str::<(f64, String, String)>(line)
+                        .unwrap()
+                        .2
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert!(input_events(InputCapture::Exclude, "excluded").is_empty());
+        assert_eq!(
+            input_events(InputCapture::Redact, "redacted"),
+            vec!["*******\r", "\u{1b}[A", "\u{1b}[200~*********\u{1b}[201~"]
+        );
+        assert_eq!(
+            input_events(InputCapture::Full, "full"),
+            vec!["hunter2\r", "\u{1b}[A", "\u{1b}[200~sk-secret\u{1b}[201~"]
+        );
+    }
+
+    #[test]
+    fn recordings_are_size_capped_and_rotated() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = RecordingConfig::new(dir.path().to_path_buf());
+        config.max_session_bytes = 200;
+        config.keep_sessions = 2;
+
+        let recorder = SessionRecorder::create(&config, "term-1", "session-1", 80, 24).unwrap();
+        for _ in 0..20 {
+            recorder.record_output("0123456789");
+        }
+        recorder.finish();
+        let path = recording_path(dir.path(), "term-1", "session-1").unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.len() <= 200);
+        assert!(!parse_recording(&content).unwrap().events.is_empty());
+
+        for session_id in ["session-2", "session-3"] {
+            std::thread::sleep(Duration::from_millis(20));
+            SessionRecorder::create(&config, "term-1", session_id, 80, 24)
+                .unwrap()
+                .finish();
+        }
+        let mut kept = list_recordings(dir.path(), "term-1")
+            .unwrap()
+            .into_iter()
+            .map(|recording| recording.session_id)
+            .collect::<Vec<_>>();
+        kept.sort();
+        assert_eq!(kept, vec!["session-2", "session-3"]);
+    }
+
+    #[test]
+    fn resumed_recording_appends_after_partial_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = RecordingConfig::new(dir.path().to_path_buf());
+        let recorder = SessionRecorder::create(&config, "term-1", "session-1", 80, 24).unwrap();
+        recorder.record_output("before restart\r\n");
+        recorder.finish();
+        let path = recording_path(dir.path(), "term-1", "session-1").unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(b"[1.5, \"o\", \"trunc").unwrap();
+        drop(file);
+
+        let recorder = SessionRecorder::resume(&config, "term-1", "session-1", 80, 24).unwrap();
+        recorder.record_output("after restart\r\n");
+        recorder.finish();
+
+        let recording = parse_recording(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let outputs = recording
+            .events
+            .iter()
+            .map(|event| event.data.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(outputs, vec!["before restart\r\n", "after restart\r\n"]);
+
+        // A session whose recording is gone starts a new one
+        SessionRecorder::resume(&config, "term-1", "session-2", 80, 24)
+            .unwrap()
+            .finish();
+        assert_eq!(list_recordings(dir.path(), "term-1").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejects_ids_that_escape_the_recordings_dir() {
+        let dir = Path::new("/tmp/recordings");
+        assert!(recording_path(dir, "term-1", "../secrets").is_err());
+        assert!(recording_path(dir, "..", "session").is_err());
+        assert!(list_recordings(dir, "a/b").is_err());
+    }
+}
@@ -0,0 +1,480 @@
+//! Runner-hosted terminal sessions
+//!
+//! With a remote runner configured, `ProcessManager` starts terminals on it
+//! instead of on a local PTY. [`RemoteMaster`] and [`RemoteChild`] implement
+//! the portable-pty traits over the runner RPCs, so output fanout, screen,
+//! recording and prompt watching work as for local terminals: output is
+//! streamed from the runner (resubscribing when a stream drops while the
+//! terminal still runs), input, resizes and kills are relayed in order, and
+//! the session exits once the runner no longer runs the terminal. Runners do
+//! not report exit codes, so a finished terminal exits with code 0.
+//!
+//! A terminal started in a branch checkout gets its own workspace on the
+//! runner, cloned from that branch. While the terminal runs, a
+//! [`WorkspaceMirror`] fast-forwards the local worktree to the runner's
+//! commits; once it exits the last changes are synced and the workspace is
+//! released.
+
+use std::{
+    io::{Read, Write},
+    path::Path,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, Weak, mpsc},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use futures::StreamExt;
+use portable_pty::{Child, ChildKiller, ExitStatus, MasterPty, PtySize};
+use tokio::sync::mpsc as async_mpsc;
+
+use super::process::{ChannelReader, DEFAULT_COLS, DEFAULT_ROWS};
+use crate::services::{
+    runner_client::{RunnerClient, RunnerOutputStream, SharedRunnerClient, TerminalSpawnConfig},
+    workspace_sync::{self, WorkspaceMirror, WorkspaceProvision, WorkspaceSource},
+};
+
+/// Pause before resubscribing to the output of a terminal that still runs
+const RESUBSCRIBE_DELAY: Duration = Duration::from_millis(500);
+
+/// How often runner commits are pulled into the local worktree
+const WORKSPACE_SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Requests relayed to the runner
+enum Command {
+    Input(Vec<u8>),
+    Resize(PtySize),
+    Kill,
+}
+
+/// State shared by the PTY proxies of one runner-hosted terminal
+struct Session {
+    terminal_id: String,
+    /// Process ID on the runner host
+    pid: u32,
+    commands: async_mpsc::UnboundedSender<Command>,
+    size: Mutex<PtySize>,
+    exit: Mutex<Option<ExitStatus>>,
+    exited: Condvar,
+    output: Mutex<Option<mpsc::Receiver<Vec<u8>>>>,
+}
+
+impl Session {
+    fn send(&self, command: Command) -> std::io::Result<()> {
+        self.commands.send(command).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                format!("Runner session of terminal {} is closed", self.terminal_id),
+            )
+        })
+    }
+
+    fn set_exit(&self, status: ExitStatus) {
+        lock(&self.exit).get_or_insert(status);
+        self.exited.notify_all();
+    }
+}
+
+/// Spawn `config` on `runner` and connect to the terminal's output.
+pub async fn launch(
+    runner: SharedRunnerClient,
+    mut config: TerminalSpawnConfig,
+) -> anyhow::Result<(RemoteMaster, RemoteChild)> {
+    let terminal_id = config.terminal_id.clone();
+    let size = PtySize {
+        rows: u16::try_from(config.rows).unwrap_or(DEFAULT_ROWS),
+        cols: u16::try_from(config.cols).unwrap_or(DEFAULT_COLS),
+        pixel_width: 0,
+        pixel_height: 0,
+    };
+    let workspace = provision_workspace(&runner, &mut config).await?;
+    let spawned = match runner.spawn_terminal(config).await {
+        Ok(spawned) => spawned,
+        Err(e) => {
+            if let Some(mirror) = &workspace {
+                let _ = runner.release_workspace(mirror.workspace_id()).await;
+            }
+            return Err(e);
+        }
+    };
+    let output = match runner.stream_output(&terminal_id, 0).await {
+        Ok(output) => output,
+        Err(e) => {
+            let _ = runner.kill_terminal(&terminal_id).await;
+            if let Some(mirror) = &workspace {
+                let _ = runner.release_workspace(mirror.workspace_id()).await;
+            }
+            return Err(e.context(format!("Failed to stream output of terminal {terminal_id}")));
+        }
+    };
+    if let Some(mirror) = &workspace {
+        let mirror = Arc::clone(mirror);
+        tokio::spawn(async move { mirror.run().await });
+    }
+
+    let (output_tx, output_rx) = mpsc::channel();
+    let (command_tx, command_rx) = async_mpsc::unbounded_channel();
+    let session = Arc::new(Session {
+        terminal_id: terminal_id.clone(),
+        pid: spawned.pid,
+        commands: command_tx,
+        size: Mutex::new(size),
+        exit: Mutex::new(None),
+        exited: Condvar::new(),
+        output: Mutex::new(Some(output_rx)),
+    });
+    tokio::spawn(forward_commands(
+        Arc::clone(&runner),
+        terminal_id.clone(),
+        command_rx,
+    ));
+    tokio::spawn(pump_output(
+        runner,
+        terminal_id,
+        output,
+        output_tx,
+        Arc::downgrade(&session),
+        workspace,
+    ));
+
+    Ok((
+        RemoteMaster {
+            session: Arc::clone(&session),
+        },
+        RemoteChild { session },
+    ))
+}
+
+/// Give the terminal a runner workspace cloned from the branch its working
+/// directory has checked out, and start it there. Directories outside a
+/// branch checkout are used as they are.
+async fn provision_workspace(
+    runner: &SharedRunnerClient,
+    config: &mut TerminalSpawnConfig,
+) -> anyhow::Result<Option<Arc<WorkspaceMirror>>> {
+    let Some(checkout) = workspace_sync::local_checkout(Path::new(&config.working_dir)).await
+    else {
+        return Ok(None);
+    };
+    let workspace_id = config.terminal_id.clone();
+    let provisioned = runner
+        .provision_workspace(WorkspaceProvision {
+            workspace_id: workspace_id.clone(),
+            source: WorkspaceSource::LocalRepo(checkout.worktree.clone()),
+            branch: checkout.branch,
+            runner_labels: config.runner_labels.clone(),
+        })
+        .await?;
+
+    config.working_dir = if checkout.prefix.is_empty() {
+        provisioned.working_dir
+    } else {
+        format!(
+            "{}/{}",
+            provisioned.working_dir.trim_end_matches('/'),
+            checkout.prefix.trim_end_matches('/')
+        )
+    };
+    config.workspace_id = Some(workspace_id.clone());
+    Ok(Some(Arc::new(WorkspaceMirror::new(
+        Arc::clone(runner),
+        workspace_id,
+        checkout.worktree,
+        provisioned.head_commit,
+        WORKSPACE_SYNC_INTERVAL,
+    ))))
+}
+
+/// Take over the last changes of an exited terminal's workspace and release
+/// it. A workspace that fails to sync stays on the runner.
+async fn finish_workspace(runner: &SharedRunnerClient, mirror: &WorkspaceMirror) {
+    mirror.stop();
+    if let Err(e) = mirror.sync_once().await {
+        tracing::warn!(
+            workspace_id = %mirror.workspace_id(),
+            error = %e,
+            "Failed to sync the runner workspace of an exited terminal, keeping it"
+        );
+        return;
+    }
+    if let Err(e) = runner.release_workspace(mirror.workspace_id()).await {
+        tracing::warn!(
+            workspace_id = %mirror.workspace_id(),
+            error = %e,
+            "Failed to release runner workspace"
+        );
+    }
+}
+
+/// Relay requests to the runner in the order they were made.
+async fn forward_commands(
+    runner: SharedRunnerClient,
+    terminal_id: String,
+    mut commands: async_mpsc::UnboundedReceiver<Command>,
+) {
+    while let Some(command) = commands.recv().await {
+        let (request, result) = match command {
+            Command::Input(data) => ("input", runner.write_input(&terminal_id, &data).await),
+            Command::Resize(size) => (
+                "resize",
+                runner
+                    .resize_terminal(&terminal_id, size.cols.into(), size.rows.into())
+                    .await,
+            ),
+            Command::Kill => ("kill", runner.kill_terminal(&terminal_id).await),
+        };
+        if let Err(e) = result {
+            tracing::warn!(
+                terminal_id = %terminal_id,
+                request,
+                error = %e,
+                "Runner rejected terminal request"
+            );
+        }
+    }
+}
+
+/// Feed the runner's output to the session reader until the terminal is gone.
+async fn pump_output(
+    runner: SharedRunnerClient,
+    terminal_id: String,
+    mut output: RunnerOutputStream,
+    reader: mpsc::Sender<Vec<u8>>,
+    session: Weak<Session>,
+    workspace: Option<Arc<WorkspaceMirror>>,
+) {
+    let mut last_seq = 0;
+    let status = loop {
+        while let Some(chunk) = output.next().await {
+            match chunk {
+                Ok(chunk) => {
+                    last_seq = chunk.seq;
+                    let _ = reader.send(chunk.data);
+                }
+                Err(e) => {
+                    tracing::debug!(
+                        terminal_id = %terminal_id,
+                        error = %e,
+                        "Runner output stream failed"
+                    );
+                    break;
+                }
+            }
+        }
+
+        // Runners also end the stream of a subscriber that fell behind
+        match runner.is_running(&terminal_id).await {
+            Ok(false) => break ExitStatus::with_exit_code(0),
+            Ok(true) if session.strong_count() == 0 => {
+                if let Some(mirror) = &workspace {
+                    mirror.stop();
+                }
+                return;
+            }
+            Ok(true) => {}
+            Err(e) => {
+                tracing::warn!(
+                    terminal_id = %terminal_id,
+                    error = %e,
+                    "Lost contact with the terminal's runner"
+                );
+                break ExitStatus::with_signal("runner connection lost");
+            }
+        }
+        tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        output = match runner.stream_output(&terminal_id, last_seq).await {
+            Ok(output) => output,
+            Err(e) => {
+                tracing::warn!(
+                    terminal_id = %terminal_id,
+                    error = %e,
+                    "Failed to resume the terminal's runner output"
+                );
+                break ExitStatus::with_signal("runner connection lost");
+            }
+        };
+    };
+    // Commits land in the local worktree before the terminal counts as exited
+    if let Some(mirror) = &workspace {
+        finish_workspace(&runner, mirror).await;
+    }
+    if let Some(session) = session.upgrade() {
+        session.set_exit(status);
+    }
+}
+
+/// Runner-backed stand-in for a PTY master
+pub struct RemoteMaster {
+    session: Arc<Session>,
+}
+
+impl MasterPty for RemoteMaster {
+    fn resize(&self, size: PtySize) -> anyhow::Result<()> {
+        self.session.send(Command::Resize(size))?;
+        *lock(&self.session.size) = size;
+        Ok(())
+    }
+
+    fn get_size(&self) -> anyhow::Result<PtySize> {
+        Ok(*lock(&self.session.size))
+    }
+
+    fn try_clone_reader(&self) -> anyhow::Result<Box<dyn Read + Send>> {
+        let output = lock(&self.session.output)
+            .take()
+            .ok_or_else(|| anyhow!("Runner terminal output has a single reader"))?;
+        Ok(Box::new(ChannelReader::new(output, Vec::new())))
+    }
+
+    fn take_writer(&self) -> anyhow::Result<Box<dyn Write + Send>> {
+        Ok(Box::new(RemoteWriter {
+            session: Arc::clone(&self.session),
+        }))
+    }
+
+    #[cfg(unix)]
+    fn process_group_leader(&self) -> Option<nix::libc::pid_t> {
+        None
+    }
+
+    #[cfg(unix)]
+    fn as_raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        None
+    }
+}
+
+struct RemoteWriter {
+    session: Arc<Session>,
+}
+
+impl Write for RemoteWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.session.send(Command::Input(buf.to_vec()))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runner-backed stand-in for the terminal's child process.
+///
+/// Reports no local process ID: the PID belongs to the runner host, and
+/// `ProcessManager` signals local PIDs directly.
+pub struct RemoteChild {
+    session: Arc<Session>,
+}
+
+impl std::fmt::Debug for RemoteChild {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteChild")
+            .field("terminal_id", &self.session.terminal_id)
+            .field("runner_pid", &self.session.pid)
+            .finish()
+    }
+}
+
+impl ChildKiller for RemoteChild {
+    fn kill(&mut self) -> std::io::Result<()> {
+        self.session.send(Command::Kill)
+    }
+
+    fn clone_killer(&self) -> Box<dyn ChildKiller + Send + Sync> {
+        Box::new(Self {
+            session: Arc::clone(&self.session),
+        })
+    }
+}
+
+impl Child for RemoteChild {
+    fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        Ok(lock(&self.session.exit).clone())
+    }
+
+    fn wait(&mut self) -> std::io::Result<ExitStatus> {
+        let mut exit = lock(&self.session.exit);
+        loop {
+            if let Some(status) = exit.clone() {
+                return Ok(status);
+            }
+            exit = self
+                .session
+                .exited
+                .wait(exit)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    fn process_id(&self) -> Option<u32> {
+        None
+    }
+
+    #[cfg(windows)]
+    fn as_raw_handle(&self) -> Option<std::os::windows::io::RawHandle> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::services::{
+        runner_client::{LocalRunner, RunnerClientImpl},
+        terminal::process::{ProcessManager, SpawnCommand},
+    };
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn terminal_runs_on_the_runner() {
+        let runner_manager = Arc::new(ProcessManager::new());
+        let runner = Arc::new(RunnerClientImpl::Local(LocalRunner::new(Arc::clone(
+            &runner_manager,
+        ))));
+        let manager = ProcessManager::new().with_remote_runner(runner);
+        let dir = tempfile::tempdir().unwrap();
+        let command = SpawnCommand::new("/bin/sh", dir.path())
+            .with_args(["-c", "echo ready; read line; echo got:$line; sleep 30"])
+            .with_runner_labels(["has-sh"]);
+
+        let handle = manager
+            .spawn_pty_with_config("t-remote", &command, 80, 24)
+            .await
+            .unwrap();
+        assert_eq!(handle.pid, 0, "runner PIDs are not local processes");
+        assert!(runner_manager.is_running("t-remote").await);
+
+        let mut output = manager.subscribe_output("t-remote", None).await.unwrap();
+        let writer = manager
+            .get_handle("t-remote")
+            .await
+            .unwrap()
+            .writer
+            .unwrap();
+        writer.lock().unwrap().write_all(b"hello\n").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut seen = String::new();
+        while !seen.contains("got:hello") {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let chunk = tokio::time::timeout(remaining, output.recv())
+                .await
+                .expect("runner output")
+                .unwrap();
+            seen.push_str(&chunk.text);
+        }
+        assert!(seen.contains("ready"));
+
+        manager.kill_terminal("t-remote").await.unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while runner_manager.is_running("t-remote").await {
+            assert!(Instant::now() < deadline, "runner terminal was not killed");
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+}
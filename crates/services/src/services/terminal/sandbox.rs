@@ -0,0 +1,402 @@
+//! Terminal resource limits
+//!
+//! Applies a terminal's [`TerminalResourceLimits`] to its PTY process. On Linux:
+//! - CPU, memory and process caps go into a per-session cgroup v2 leaf under
+//!   `$SOLODAWN_CGROUP_ROOT` (default: the server's own cgroup). The command
+//!   joins the cgroup before it is exec'd, so no child escapes the limits.
+//!   Without a writable cgroup v2 hierarchy, the memory cap falls back to an
+//!   rlimit set by `prlimit`; CPU and process caps have no per-session rlimit
+//!   equivalent (`RLIMIT_NPROC` counts every process of the user) and are
+//!   reported unenforced.
+//! - `network_disabled` runs the command in fresh user + network namespaces via
+//!   `unshare`. Network isolation fails closed: if it cannot be set up the
+//!   terminal is not spawned.
+//!
+//! The wall-clock timeout is enforced by `ProcessManager` on every platform.
+
+use std::path::{Path, PathBuf};
+
+use db::models::{
+    AppliedResourceLimit, ResourceEnforcement, TerminalResource, TerminalResourceLimits,
+    TerminalResourceReport,
+};
+
+/// Overrides the cgroup v2 directory under which terminal cgroups are created
+pub const CGROUP_ROOT_ENV: &str = "SOLODAWN_CGROUP_ROOT";
+
+/// cgroup v2 `cpu.max` period in microseconds
+const CPU_MAX_PERIOD_US: u64 = 100_000;
+
+/// Host facilities available for enforcing limits
+#[derive(Debug, Clone, Default)]
+pub struct SandboxCapabilities {
+    /// cgroup v2 directory with cpu/memory/pids enabled for its children
+    pub cgroup_root: Option<PathBuf>,
+    pub prlimit: Option<PathBuf>,
+    pub unshare: Option<PathBuf>,
+}
+
+impl SandboxCapabilities {
+    /// Probe the current host.
+    pub fn detect() -> Self {
+        if !cfg!(target_os = "linux") {
+            return Self::default();
+        }
+        Self {
+            cgroup_root: detect_cgroup_root(),
+            prlimit: find_executable("prlimit"),
+            unshare: find_executable("unshare"),
+        }
+    }
+}
+
+/// Command wrappers and cgroup settings for one spawn
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SandboxPlan {
+    /// Program and arguments run in front of the terminal command
+    pub wrapper: Vec<String>,
+    /// cgroup v2 interface files to write (`cpu.max`, `memory.max`, `pids.max`)
+    pub cgroup_settings: Vec<(&'static str, String)>,
+    pub report: TerminalResourceReport,
+}
+
+impl SandboxPlan {
+    /// Decide how each requested limit is enforced.
+    pub fn build(
+        limits: &TerminalResourceLimits,
+        capabilities: &SandboxCapabilities,
+    ) -> anyhow::Result<Self> {
+        let mut plan = Self::default();
+        let cgroup = capabilities.cgroup_root.is_some();
+        let mut rlimits: Vec<String> = Vec::new();
+
+        if let Some(percent) = limits.cpu_percent {
+            if cgroup {
+                let quota = u64::from(percent) * CPU_MAX_PERIOD_US / 100;
+                plan.cgroup_settings
+                    .push(("cpu.max", format!("{quota} {CPU_MAX_PERIOD_US}")));
+                plan.apply(TerminalResource::Cpu, ResourceEnforcement::Cgroup);
+            } else {
+                plan.apply(TerminalResource::Cpu, ResourceEnforcement::Unenforced);
+            }
+        }
+
+        if let Some(memory_mb) = limits.memory_mb {
+            let bytes = u64::from(memory_mb) * 1024 * 1024;
+            if cgroup {
+                plan.cgroup_settings.push(("memory.max", bytes.to_string()));
+                plan.apply(TerminalResource::Memory, ResourceEnforcement::Cgroup);
+            } else if capabilities.prlimit.is_some() {
+                // RLIMIT_AS would break runtimes that reserve large virtual ranges (V8).
+                rlimits.push(format!("--data={bytes}"));
+                plan.apply(TerminalResource::Memory, ResourceEnforcement::Rlimit);
+            } else {
+                plan.apply(TerminalResource::Memory, ResourceEnforcement::Unenforced);
+            }
+        }
+
+        if let Some(max_processes) = limits.max_processes {
+            if cgroup {
+                plan.cgroup_settings
+                    .push(("pids.max", max_processes.to_string()));
+                plan.apply(TerminalResource::Processes, ResourceEnforcement::Cgroup);
+            } else {
+                plan.apply(TerminalResource::Processes, ResourceEnforcement::Unenforced);
+            }
+        }
+
+        if limits.wall_clock_timeout_secs.is_some() {
+            plan.apply(TerminalResource::WallClock, ResourceEnforcement::Supervisor);
+        }
+
+        if limits.network_disabled {
+            let unshare = capabilities.unshare.as_ref().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Network-disabled terminals require Linux with `unshare` (util-linux) in PATH"
+                )
+            })?;
+            plan.wrapper.extend([
+                unshare.display().to_string(),
+                "--user".to_string(),
+                "--map-current-user".to_string(),
+                "--net".to_string(),
+                "--".to_string(),
+            ]);
+            plan.apply(TerminalResource::Network, ResourceEnforcement::Namespace);
+        }
+
+        if let (false, Some(prlimit)) = (rlimits.is_empty(), &capabilities.prlimit) {
+            plan.wrapper.push(prlimit.display().to_string());
+            plan.wrapper.extend(rlimits);
+            plan.wrapper.push("--".to_string());
+        }
+
+        Ok(plan)
+    }
+
+    fn apply(&mut self, resource: TerminalResource, enforcement: ResourceEnforcement) {
+        self.report.applied.push(AppliedResourceLimit {
+            resource,
+            enforcement,
+        });
+    }
+}
+
+/// cgroup v2 leaf holding one terminal session; removed on drop
+#[derive(Debug)]
+pub struct TerminalCgroup {
+    path: PathBuf,
+}
+
+impl TerminalCgroup {
+    /// Create the session cgroup and write its limits.
+    pub fn create(
+        root: &Path,
+        terminal_id: &str,
+        session_id: &str,
+        settings: &[(&'static str, String)],
+    ) -> anyhow::Result<Self> {
+        let path = root.join(format!("solodawn-{terminal_id}-{session_id}"));
+        std::fs::create_dir(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to create cgroup {}: {e}", path.display()))?;
+        let cgroup = Self { path };
+        for (file, value) in settings {
+            std::fs::write(cgroup.path.join(file), value).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to set {file}={value} on {}: {e}",
+                    cgroup.path.display()
+                )
+            })?;
+        }
+        Ok(cgroup)
+    }
+
+    /// Take ownership of a session cgroup created by another process.
+    pub fn adopt(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Give up ownership, leaving the cgroup in place.
+    pub fn detach(mut self) {
+        self.path = PathBuf::new();
+    }
+
+    /// Wrapper argv that moves itself into the cgroup and execs the rest of
+    /// the command line, so the command starts inside the cgroup. The command
+    /// is not run if the cgroup cannot be joined.
+    pub fn join_wrapper(&self) -> Vec<String> {
+        vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            r#"echo $$ > "$0/cgroup.procs" || exit 126; exec "$@""#.to_string(),
+            self.path.display().to_string(),
+        ]
+    }
+
+    /// Kill every process in the cgroup.
+    pub fn kill_all(&self) {
+        // cgroup.kill needs Linux 5.14+; older kernels leave stragglers to the PTY hangup.
+        let _ = std::fs::write(self.path.join("cgroup.kill"), "1");
+    }
+}
+
+impl Drop for TerminalCgroup {
+    fn drop(&mut self) {
+        if self.path.as_os_str().is_empty() {
+            return;
+        }
+        self.kill_all();
+        // rmdir fails with EBUSY until the killed processes are gone
+        for _ in 0..10 {
+            match std::fs::remove_dir(&self.path) {
+                Ok(()) => return,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+                Err(_) => std::thread::sleep(std::time::Duration::from_millis(20)),
+            }
+        }
+        tracing::warn!(cgroup = %self.path.display(), "Failed to remove terminal cgroup");
+    }
+}
+
+/// Locate a cgroup v2 directory and enable cpu/memory/pids for its children.
+fn detect_cgroup_root() -> Option<PathBuf> {
+    let root = match std::env::var_os(CGROUP_ROOT_ENV) {
+        Some(root) => PathBuf::from(root),
+        None => {
+            // cgroup v2 lists the unified hierarchy as `0::/path`
+            let own = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+            let relative = own.lines().find_map(|line| line.strip_prefix("0::"))?;
+            Path::new("/sys/fs/cgroup").join(relative.trim_start_matches('/'))
+        }
+    };
+    if !root.join("cgroup.controllers").is_file() {
+        return None;
+    }
+    // Fails for a non-root cgroup that still holds processes ("no internal
+    // processes" rule); the server must then run in a delegated subtree.
+    match std::fs::write(root.join("cgroup.subtree_control"), "+cpu +memory +pids") {
+        Ok(()) => Some(root),
+        Err(e) => {
+            tracing::debug!(
+                cgroup = %root.display(),
+                error = %e,
+                "cgroup v2 controllers not delegable; falling back to rlimits"
+            );
+            None
+        }
+    }
+}
+
+fn find_executable(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .chain(["/usr/bin", "/bin", "/usr/sbin"].map(PathBuf::from))
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enforcement(plan: &SandboxPlan, resource: TerminalResource) -> Option<ResourceEnforcement> {
+        plan.report
+            .applied
+            .iter()
+            .find(|limit| limit.resource == resource)
+            .map(|limit| limit.enforcement)
+    }
+
+    fn limits() -> TerminalResourceLimits {
+        TerminalResourceLimits {
+            cpu_percent: Some(150),
+            memory_mb: Some(512),
+            max_processes: Some(64),
+            wall_clock_timeout_secs: Some(600),
+            network_disabled: false,
+        }
+    }
+
+    #[test]
+    fn cgroup_enforces_cpu_memory_and_processes() {
+        let capabilities = SandboxCapabilities {
+            cgroup_root: Some(PathBuf::from("/sys/fs/cgroup/solodawn")),
+            prlimit: Some(PathBuf::from("/usr/bin/prlimit")),
+            unshare: None,
+        };
+        let plan = SandboxPlan::build(&limits(), &capabilities).unwrap();
+
+        assert!(plan.wrapper.is_empty());
+        assert_eq!(
+            plan.cgroup_settings,
+            vec![
+                ("cpu.max", "150000 100000".to_string()),
+                ("memory.max", (512 * 1024 * 1024).to_string()),
+                ("pids.max", "64".to_string()),
+            ]
+        );
+        assert_eq!(
+            enforcement(&plan, TerminalResource::WallClock),
+            Some(ResourceEnforcement::Supervisor)
+        );
+        assert!(plan.report.unenforced().is_empty());
+    }
+
+    #[test]
+    fn falls_back_to_rlimits_without_cgroups() {
+        let capabilities = SandboxCapabilities {
+            cgroup_root: None,
+            prlimit: Some(PathBuf::from("/usr/bin/prlimit")),
+            unshare: Some(PathBuf::from("/usr/bin/unshare")),
+        };
+        let plan = SandboxPlan::build(
+            &TerminalResourceLimits {
+                network_disabled: true,
+                ..limits()
+            },
+            &capabilities,
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.wrapper,
+            [
+                "/usr/bin/unshare",
+                "--user",
+                "--map-current-user",
+                "--net",
+                "--",
+                "/usr/bin/prlimit",
+                "--data=536870912",
+                "--",
+            ]
+        );
+        assert!(plan.cgroup_settings.is_empty());
+        assert_eq!(
+            plan.report.unenforced(),
+            vec![TerminalResource::Cpu, TerminalResource::Processes]
+        );
+        assert_eq!(
+            enforcement(&plan, TerminalResource::Network),
+            Some(ResourceEnforcement::Namespace)
+        );
+    }
+
+    #[test]
+    fn network_isolation_fails_closed() {
+        let limits = TerminalResourceLimits {
+            network_disabled: true,
+            ..Default::default()
+        };
+        assert!(SandboxPlan::build(&limits, &SandboxCapabilities::default()).is_err());
+
+        let plan = SandboxPlan::build(
+            &TerminalResourceLimits {
+                memory_mb: Some(256),
+                ..Default::default()
+            },
+            &SandboxCapabilities::default(),
+        )
+        .unwrap();
+        assert!(plan.wrapper.is_empty());
+        assert_eq!(plan.report.unenforced(), vec![TerminalResource::Memory]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn join_wrapper_adds_the_command_pid_before_exec() {
+        // A plain directory stands in for the cgroup; cgroup.procs becomes a file
+        let dir = tempfile::tempdir().unwrap();
+        let cgroup = TerminalCgroup::adopt(dir.path());
+        let mut wrapper = cgroup.join_wrapper();
+        cgroup.detach();
+
+        let program = wrapper.remove(0);
+        let output = std::process::Command::new(program)
+            .args(wrapper)
+            .args(["/bin/sh", "-c", "echo $$"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let command_pid = String::from_utf8(output.stdout).unwrap();
+        let joined_pid = std::fs::read_to_string(dir.path().join("cgroup.procs")).unwrap();
+        assert_eq!(joined_pid.trim(), command_pid.trim());
+
+        let missing = TerminalCgroup::adopt(&dir.path().join("missing"));
+        let mut wrapper = missing.join_wrapper();
+        missing.detach();
+        let program = wrapper.remove(0);
+        let status = std::process::Command::new(program)
+            .args(wrapper)
+            .args(["/bin/sh", "-c", "exit 0"])
+            .status()
+            .unwrap();
+        assert_eq!(status.code(), Some(126));
+    }
+}
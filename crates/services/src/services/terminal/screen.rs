@@ -0,0 +1,178 @@
+//! Virtual Terminal Screen
+//!
+//! Per-session VT100 grid fed from the raw PTY byte stream. Full-screen TUIs
+//! redraw with cursor movement, so prompt detection reads the rendered screen
+//! (text plus cell attributes) instead of decoded output lines.
+
+use std::sync::{Arc, Mutex};
+
+use vt100::Color;
+
+/// Shared screen updated by the PTY reader task
+pub type SharedTerminalScreen = Arc<Mutex<TerminalScreen>>;
+
+/// VT100 grid of one PTY session
+pub struct TerminalScreen {
+    parser: vt100::Parser,
+}
+
+impl TerminalScreen {
+    /// Create an empty screen; scrollback is not kept.
+    pub fn new(rows: u16, cols: u16) -> Self {
+        Self {
+            parser: vt100::Parser::new(rows.max(1), cols.max(1), 0),
+        }
+    }
+
+    pub fn shared(rows: u16, cols: u16) -> SharedTerminalScreen {
+        Arc::new(Mutex::new(Self::new(rows, cols)))
+    }
+
+    /// Feed raw PTY output bytes.
+    pub fn process(&mut self, bytes: &[u8]) {
+        self.parser.process(bytes);
+    }
+
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        self.parser.screen_mut().set_size(rows.max(1), cols.max(1));
+    }
+
+    /// Rendered rows with the attributes prompt detection needs.
+    pub fn snapshot(&self) -> ScreenSnapshot {
+        let screen = self.parser.screen();
+        let (rows, cols) = screen.size();
+        let lines = (0..rows)
+            .map(|row| {
+                let mut text = String::with_capacity(usize::from(cols));
+                let mut label_style = None;
+                let mut emphasized = false;
+                for col in 0..cols {
+                    let Some(cell) = screen.cell(row, col) else {
+                        continue;
+                    };
+                    if cell.is_wide_continuation() {
+                        continue;
+                    }
+                    let contents = cell.contents();
+                    if contents.trim().is_empty() {
+                        text.push(' ');
+                        continue;
+                    }
+                    text.push_str(contents);
+
+                    let style = CellStyle::of(cell);
+                    emphasized |= style.inverse || style.bg != Color::Default;
+                    if label_style.is_none() && contents.chars().any(char::is_alphanumeric) {
+                        label_style = Some(style);
+                    }
+                }
+                ScreenLine {
+                    text: text.trim_end().to_string(),
+                    emphasized,
+                    label_style,
+                }
+            })
+            .collect();
+
+        ScreenSnapshot {
+            lines,
+            cursor_row: usize::from(screen.cursor_position().0),
+        }
+    }
+}
+
+/// Display attributes of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CellStyle {
+    pub fg: Color,
+    pub bg: Color,
+    pub bold: bool,
+    pub inverse: bool,
+}
+
+impl CellStyle {
+    fn of(cell: &vt100::Cell) -> Self {
+        Self {
+            fg: cell.fgcolor(),
+            bg: cell.bgcolor(),
+            bold: cell.bold(),
+            inverse: cell.inverse(),
+        }
+    }
+}
+
+/// One rendered screen row
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScreenLine {
+    /// Row text without trailing blanks
+    pub text: String,
+    /// A visible cell is drawn inverse or on a non-default background
+    pub emphasized: bool,
+    /// Style of the first alphanumeric cell (the label of a menu option)
+    pub label_style: Option<CellStyle>,
+}
+
+/// Rendered screen at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct ScreenSnapshot {
+    pub lines: Vec<ScreenLine>,
+    pub cursor_row: usize,
+}
+
+impl ScreenSnapshot {
+    /// Rows up to the cursor or the last non-blank row, whichever is lower.
+    pub fn active_lines(&self) -> &[ScreenLine] {
+        let last_content = self
+            .lines
+            .iter()
+            .rposition(|line| !line.text.trim().is_empty())
+            .map_or(0, |row| row + 1);
+        let end = last_content.max(self.cursor_row + 1).min(self.lines.len());
+        &self.lines[..end]
+    }
+
+    /// Plain text of the whole screen.
+    pub fn text(&self) -> String {
+        self.active_lines()
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repaints_overwrite_rows_and_keep_highlight_attributes() {
+        let mut screen = TerminalScreen::new(6, 40);
+        screen.process(b"Pick a model\r\n\x1b[7m> Sonnet\x1b[0m\r\n  Opus\r\n");
+        // Move the highlight down in place, the way TUIs repaint a menu.
+        screen.process(b"\x1b[2;1H\x1b[2K  Sonnet\x1b[3;1H\x1b[2K\x1b[7m> Opus\x1b[0m\x1b[4;1H");
+
+        let snapshot = screen.snapshot();
+        let active = snapshot.active_lines();
+        assert_eq!(active.len(), 4);
+        assert_eq!(active[1].text, "  Sonnet");
+        assert!(!active[1].emphasized);
+        assert_eq!(active[2].text, "> Opus");
+        assert!(active[2].emphasized);
+        assert_eq!(snapshot.text(), "Pick a model\n  Sonnet\n> Opus\n");
+    }
+
+    #[test]
+    fn label_style_tracks_first_alphanumeric_cell() {
+        let mut screen = TerminalScreen::new(3, 30);
+        screen.process("\x1b[36m❯\x1b[0m \x1b[1;36m1. Yes\x1b[0m\r\n  2. No".as_bytes());
+
+        let snapshot = screen.snapshot();
+        let selected = snapshot.lines[0].label_style.unwrap();
+        let other = snapshot.lines[1].label_style.unwrap();
+        assert_eq!(selected.fg, Color::Idx(6));
+        assert!(selected.bold);
+        assert_eq!(other, CellStyle::default());
+        assert!(!snapshot.lines[0].emphasized);
+    }
+}
@@ -0,0 +1,853 @@
+//! Detached PTY supervisor
+//!
+//! When enabled, every terminal runs under a small detached process
+//! (`solodawn-server pty-supervisor`) that owns the PTY and serves it on a Unix
+//! socket, so agents survive server restarts and upgrades. `ProcessManager`
+//! drives the session through [`SupervisedMaster`] and [`SupervisedChild`],
+//! which implement the portable-pty traits, and reattaches to live sessions on
+//! startup using the manifests supervisors leave in the state directory.
+//!
+//! Socket frames are `tag (u8) | length (u32 BE) | payload`. On connect the
+//! supervisor sends a hello (session info) and a replay of recent output; the
+//! newest connection replaces the previous client.
+
+use std::{
+    collections::VecDeque,
+    fs::Permissions,
+    io::{BufRead, BufReader, Read, Write},
+    net::Shutdown,
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, mpsc},
+    time::Duration,
+};
+
+use anyhow::{Context, anyhow, bail};
+use chrono::{DateTime, Utc};
+use db::models::TerminalResourceReport;
+use portable_pty::{Child, ChildKiller, ExitStatus, MasterPty, PtySize, native_pty_system};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    process::{
+        ChannelReader, PROCESS_PTY_READ_BUFFER_SIZE, PROCESS_REPLAY_MAX_BYTES, SpawnCommand,
+        build_command,
+    },
+    sandbox::TerminalCgroup,
+};
+
+/// Hidden server subcommand running [`run_from_stdio`]
+pub const SUPERVISOR_SUBCOMMAND: &str = "pty-supervisor";
+
+const FRAME_HELLO: u8 = b'H';
+const FRAME_REPLAY: u8 = b'P';
+const FRAME_OUTPUT: u8 = b'O';
+const FRAME_EXIT: u8 = b'X';
+const FRAME_INPUT: u8 = b'I';
+const FRAME_RESIZE: u8 = b'R';
+const FRAME_KILL: u8 = b'K';
+
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+/// How long a supervisor may take to spawn the command and greet
+const READY_TIMEOUT_SECS: u64 = 10;
+/// A server that stops reading is dropped instead of stalling the PTY
+const CLIENT_WRITE_TIMEOUT_SECS: u64 = 5;
+/// Time to drain the last output after the command exits
+const FINAL_OUTPUT_TIMEOUT_SECS: u64 = 1;
+
+/// How `ProcessManager` launches supervisors
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Executable that runs [`run_from_stdio`] when invoked with `args`
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    /// Directory holding supervisor sockets and session manifests
+    pub state_dir: PathBuf,
+}
+
+impl SupervisorConfig {
+    /// Supervise terminals with the running server binary when
+    /// `SOLODAWN_PTY_SUPERVISOR` is set.
+    pub fn from_env() -> Option<Self> {
+        if !utils::env_compat::var_is_set("SOLODAWN_PTY_SUPERVISOR", "GITCORTEX_PTY_SUPERVISOR") {
+            return None;
+        }
+        let program = std::env::current_exe().ok()?;
+        let state_dir = utils::assets::asset_dir().ok()?.join("pty-sessions");
+        Some(Self {
+            program,
+            args: vec![SUPERVISOR_SUBCOMMAND.to_string()],
+            state_dir,
+        })
+    }
+}
+
+/// Everything a supervisor needs to start a terminal (sent on stdin)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorSpec {
+    pub terminal_id: String,
+    pub session_id: String,
+    pub command: SpawnCommand,
+    /// Sandbox wrapper argv run in front of the command
+    pub wrapper: Vec<String>,
+    pub cols: u16,
+    pub rows: u16,
+    pub state_dir: PathBuf,
+    pub resource_report: Option<TerminalResourceReport>,
+}
+
+/// Session manifest, also sent as the hello frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisedSessionInfo {
+    pub terminal_id: String,
+    pub session_id: String,
+    /// PID of the terminal command
+    pub pid: u32,
+    pub supervisor_pid: u32,
+    pub cols: u16,
+    pub rows: u16,
+    pub started_at: DateTime<Utc>,
+    pub wall_clock_timeout_secs: Option<u32>,
+    pub resource_report: Option<TerminalResourceReport>,
+    /// Isolated CODEX_HOME to clean up when the session ends
+    pub codex_home: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum ReadyMessage {
+    Ready(Box<SupervisedSessionInfo>),
+    Failed(String),
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn socket_path(state_dir: &Path, terminal_id: &str) -> PathBuf {
+    state_dir.join(format!("{terminal_id}.sock"))
+}
+
+fn manifest_path(state_dir: &Path, terminal_id: &str) -> PathBuf {
+    state_dir.join(format!("{terminal_id}.json"))
+}
+
+/// Ids are used as file names; reject anything that could escape the dir.
+fn validate_terminal_id(terminal_id: &str) -> anyhow::Result<()> {
+    if terminal_id.is_empty()
+        || !terminal_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        bail!("Invalid terminal id for supervisor: {terminal_id}");
+    }
+    Ok(())
+}
+
+fn write_frame(writer: &mut impl Write, tag: u8, payload: &[u8]) -> std::io::Result<()> {
+    let len = u32::try_from(payload.len())
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "frame too large"))?;
+    let mut frame = Vec::with_capacity(payload.len() + 5);
+    frame.push(tag);
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame)
+}
+
+/// Read one frame; `None` on a clean end of stream.
+fn read_frame(reader: &mut impl Read) -> std::io::Result<Option<(u8, Vec<u8>)>> {
+    let mut header = [0u8; 5];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("frame of {len} bytes exceeds limit"),
+        ));
+    }
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload)?;
+    Ok(Some((header[0], payload)))
+}
+
+fn encode_size(size: PtySize) -> [u8; 4] {
+    let [c0, c1] = size.cols.to_be_bytes();
+    let [r0, r1] = size.rows.to_be_bytes();
+    [c0, c1, r0, r1]
+}
+
+fn decode_size(payload: &[u8]) -> Option<PtySize> {
+    let [c0, c1, r0, r1] = payload.try_into().ok()?;
+    Some(PtySize {
+        cols: u16::from_be_bytes([c0, c1]),
+        rows: u16::from_be_bytes([r0, r1]),
+        pixel_width: 0,
+        pixel_height: 0,
+    })
+}
+
+// ============================================================================
+// Supervisor side
+// ============================================================================
+
+/// Entry point of the supervisor subcommand: reads a [`SupervisorSpec`] from
+/// stdin, reports readiness on stdout and serves the PTY until the command
+/// exits. Returns the command's exit code.
+pub fn run_from_stdio() -> i32 {
+    // Leave the server's session so terminal hangups and Ctrl-C don't reach us
+    let _ = nix::unistd::setsid();
+
+    let mut stdout = std::io::stdout();
+    let mut spec = String::new();
+    let result = std::io::stdin()
+        .read_to_string(&mut spec)
+        .context("Failed to read supervisor spec")
+        .and_then(|_| serde_json::from_str(&spec).context("Invalid supervisor spec"))
+        .and_then(|spec| serve(spec, &mut stdout));
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            if let Ok(message) = serde_json::to_string(&ReadyMessage::Failed(format!("{e:#}"))) {
+                let _ = writeln!(stdout, "{message}");
+            }
+            1
+        }
+    }
+}
+
+struct ServeState {
+    info: SupervisedSessionInfo,
+    client: Option<UnixStream>,
+    replay: VecDeque<u8>,
+}
+
+/// Handles shared by the client input threads
+struct ServeHandles {
+    state: Arc<Mutex<ServeState>>,
+    master: Mutex<Box<dyn MasterPty + Send>>,
+    writer: Mutex<Box<dyn Write + Send>>,
+    killer: Mutex<Box<dyn ChildKiller + Send + Sync>>,
+}
+
+/// Spawn the command on a fresh PTY and serve it until it exits.
+pub fn serve(spec: SupervisorSpec, ready: &mut impl Write) -> anyhow::Result<i32> {
+    validate_terminal_id(&spec.terminal_id)?;
+    // The server created the session cgroup; remove it once the command is gone
+    let _cgroup = spec
+        .resource_report
+        .as_ref()
+        .and_then(|report| report.cgroup_path.as_deref())
+        .map(|path| TerminalCgroup::adopt(Path::new(path)));
+
+    let pair = native_pty_system()
+        .openpty(PtySize {
+            rows: spec.rows,
+            cols: spec.cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| anyhow!("Failed to open PTY: {e}"))?;
+    let cmd = build_command(&spec.terminal_id, &spec.command, &spec.wrapper);
+    let mut child = pair
+        .slave
+        .spawn_command(cmd)
+        .map_err(|e| anyhow!("Failed to spawn terminal process: {e}"))?;
+    drop(pair.slave);
+    let reader = pair
+        .master
+        .try_clone_reader()
+        .map_err(|e| anyhow!("Failed to clone PTY reader: {e}"))?;
+    let writer = pair
+        .master
+        .take_writer()
+        .map_err(|e| anyhow!("Failed to take PTY writer: {e}"))?;
+
+    let socket = socket_path(&spec.state_dir, &spec.terminal_id);
+    let _ = std::fs::remove_file(&socket);
+    let listener = UnixListener::bind(&socket)
+        .with_context(|| format!("Failed to bind {}", socket.display()))?;
+    std::fs::set_permissions(&socket, Permissions::from_mode(0o600))?;
+
+    let info = SupervisedSessionInfo {
+        terminal_id: spec.terminal_id.clone(),
+        session_id: spec.session_id.clone(),
+        pid: child.process_id().unwrap_or(0),
+        supervisor_pid: std::process::id(),
+        cols: spec.cols,
+        rows: spec.rows,
+        started_at: Utc::now(),
+        wall_clock_timeout_secs: spec
+            .command
+            .resource_limits
+            .as_ref()
+            .and_then(|limits| limits.wall_clock_timeout_secs),
+        resource_report: spec.resource_report.clone(),
+        codex_home: spec
+            .command
+            .env
+            .set
+            .get("CODEX_HOME")
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from),
+    };
+    let manifest = manifest_path(&spec.state_dir, &spec.terminal_id);
+    write_manifest(&manifest, &info)?;
+    writeln!(
+        ready,
+        "{}",
+        serde_json::to_string(&ReadyMessage::Ready(Box::new(info.clone())))?
+    )?;
+    ready.flush()?;
+
+    let handles = Arc::new(ServeHandles {
+        state: Arc::new(Mutex::new(ServeState {
+            info,
+            client: None,
+            replay: VecDeque::new(),
+        })),
+        master: Mutex::new(pair.master),
+        writer: Mutex::new(writer),
+        killer: Mutex::new(child.clone_killer()),
+    });
+
+    let (drained_tx, drained_rx) = mpsc::channel();
+    {
+        let state = Arc::clone(&handles.state);
+        std::thread::spawn(move || {
+            pump_output(reader, &state);
+            let _ = drained_tx.send(());
+        });
+    }
+    {
+        let handles = Arc::clone(&handles);
+        std::thread::spawn(move || accept_clients(&listener, &handles));
+    }
+
+    let status = child
+        .wait()
+        .context("Failed to wait for terminal process")?;
+    // Orphaned grandchildren can keep the PTY open; don't wait on them forever
+    let _ = drained_rx.recv_timeout(Duration::from_secs(FINAL_OUTPUT_TIMEOUT_SECS));
+
+    let code = status.exit_code();
+    if let Some(client) = lock(&handles.state).client.as_mut() {
+        let _ = write_frame(client, FRAME_EXIT, &code.to_be_bytes());
+    }
+    let _ = std::fs::remove_file(&socket);
+    let _ = std::fs::remove_file(&manifest);
+    Ok(i32::try_from(code).unwrap_or(1))
+}
+
+fn write_manifest(path: &Path, info: &SupervisedSessionInfo) -> anyhow::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(info)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::set_permissions(&tmp, Permissions::from_mode(0o600))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn pump_output(mut reader: Box<dyn Read + Send>, state: &Mutex<ServeState>) {
+    let mut buf = [0u8; PROCESS_PTY_READ_BUFFER_SIZE];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+        let chunk = &buf[..n];
+        let mut state = lock(state);
+        state.replay.extend(chunk);
+        let excess = state.replay.len().saturating_sub(PROCESS_REPLAY_MAX_BYTES);
+        state.replay.drain(..excess);
+        if let Some(client) = state.client.as_mut()
+            && write_frame(client, FRAME_OUTPUT, chunk).is_err()
+        {
+            state.client = None;
+        }
+    }
+}
+
+fn accept_clients(listener: &UnixListener, handles: &Arc<ServeHandles>) {
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else {
+            continue;
+        };
+        let _ = stream.set_write_timeout(Some(Duration::from_secs(CLIENT_WRITE_TIMEOUT_SECS)));
+        let Ok(input) = stream.try_clone() else {
+            continue;
+        };
+        {
+            let mut state = lock(&handles.state);
+            let Ok(hello) = serde_json::to_vec(&state.info) else {
+                continue;
+            };
+            let replay: Vec<u8> = state.replay.iter().copied().collect();
+            if write_frame(&mut stream, FRAME_HELLO, &hello)
+                .and_then(|()| write_frame(&mut stream, FRAME_REPLAY, &replay))
+                .is_err()
+            {
+                continue;
+            }
+            if let Some(previous) = state.client.replace(stream) {
+                let _ = previous.shutdown(Shutdown::Both);
+            }
+        }
+        let handles = Arc::clone(handles);
+        std::thread::spawn(move || handle_client_input(input, &handles));
+    }
+}
+
+fn handle_client_input(mut input: UnixStream, handles: &ServeHandles) {
+    while let Ok(Some((tag, payload))) = read_frame(&mut input) {
+        match tag {
+            FRAME_INPUT => {
+                let mut writer = lock(&handles.writer);
+                let _ = writer.write_all(&payload).and_then(|()| writer.flush());
+            }
+            FRAME_RESIZE => {
+                if let Some(size) = decode_size(&payload)
+                    && lock(&handles.master).resize(size).is_ok()
+                {
+                    let mut state = lock(&handles.state);
+                    state.info.cols = size.cols;
+                    state.info.rows = size.rows;
+                }
+            }
+            FRAME_KILL => {
+                let _ = lock(&handles.killer).kill();
+            }
+            _ => {}
+        }
+    }
+}
+
+// ============================================================================
+// Server side
+// ============================================================================
+
+/// Connection to a supervisor, shared by the PTY proxies
+struct Connection {
+    stream: Mutex<UnixStream>,
+    pid: u32,
+    size: Mutex<PtySize>,
+    exit: Mutex<Option<ExitStatus>>,
+    exited: Condvar,
+    output: Mutex<Option<mpsc::Receiver<Vec<u8>>>>,
+}
+
+impl Connection {
+    fn send(&self, tag: u8, payload: &[u8]) -> std::io::Result<()> {
+        write_frame(&mut *lock(&self.stream), tag, payload)
+    }
+
+    fn set_exit(&self, status: ExitStatus) {
+        lock(&self.exit).get_or_insert(status);
+        self.exited.notify_all();
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // Ends the demux thread; the supervisor keeps running without a client
+        let _ = lock(&self.stream).shutdown(Shutdown::Both);
+    }
+}
+
+/// A live session served by a supervisor
+pub struct SupervisedSession {
+    pub info: SupervisedSessionInfo,
+    /// Recent output buffered by the supervisor before this connection
+    pub replay: Vec<u8>,
+    pub master: SupervisedMaster,
+    pub child: SupervisedChild,
+}
+
+/// Start a detached supervisor for `spec` and connect to it.
+pub async fn launch(
+    config: &SupervisorConfig,
+    spec: &SupervisorSpec,
+) -> anyhow::Result<SupervisedSession> {
+    validate_terminal_id(&spec.terminal_id)?;
+    std::fs::create_dir_all(&config.state_dir)
+        .with_context(|| format!("Failed to create {}", config.state_dir.display()))?;
+    std::fs::set_permissions(&config.state_dir, Permissions::from_mode(0o700))?;
+
+    let payload = serde_json::to_vec(spec)?;
+    let config = config.clone();
+    tokio::task::spawn_blocking(move || launch_blocking(&config, &payload))
+        .await
+        .map_err(|e| anyhow!("Supervisor launch task failed: {e}"))?
+}
+
+fn launch_blocking(config: &SupervisorConfig, spec: &[u8]) -> anyhow::Result<SupervisedSession> {
+    let mut process = std::process::Command::new(&config.program)
+        .args(&config.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| {
+            format!(
+                "Failed to start PTY supervisor {}",
+                config.program.display()
+            )
+        })?;
+
+    let mut stdin = process
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow!("PTY supervisor stdin unavailable"))?;
+    stdin.write_all(spec)?;
+    drop(stdin);
+
+    let stdout = process
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("PTY supervisor stdout unavailable"))?;
+    let (line_tx, line_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        let _ = BufReader::new(stdout).read_line(&mut line);
+        let _ = line_tx.send(line);
+    });
+    let Ok(line) = line_rx.recv_timeout(Duration::from_secs(READY_TIMEOUT_SECS)) else {
+        let _ = process.kill();
+        let _ = process.wait();
+        bail!("PTY supervisor did not become ready within {READY_TIMEOUT_SECS}s");
+    };
+    // The supervisor outlives this server; reap it if it exits first
+    std::thread::spawn(move || {
+        let _ = process.wait();
+    });
+
+    let info = match serde_json::from_str::<ReadyMessage>(&line) {
+        Ok(ReadyMessage::Ready(info)) => info,
+        Ok(ReadyMessage::Failed(error)) => bail!("PTY supervisor failed: {error}"),
+        Err(_) => bail!("PTY supervisor exited before becoming ready"),
+    };
+    connect(&socket_path(&config.state_dir, &info.terminal_id)).inspect_err(|_| {
+        // Nobody can reach the session anymore
+        let _ = nix::sys::signal::kill(
+            nix::unistd::Pid::from_raw(info.pid as i32),
+            nix::sys::signal::Signal::SIGKILL,
+        );
+    })
+}
+
+/// Connect to the supervisor listening on `socket`.
+pub fn connect(socket: &Path) -> anyhow::Result<SupervisedSession> {
+    let mut stream = UnixStream::connect(socket)
+        .with_context(|| format!("Failed to connect to {}", socket.display()))?;
+    stream.set_read_timeout(Some(Duration::from_secs(READY_TIMEOUT_SECS)))?;
+    let info: SupervisedSessionInfo = match read_frame(&mut stream)? {
+        Some((FRAME_HELLO, payload)) => serde_json::from_slice(&payload)?,
+        _ => bail!("Supervisor at {} sent no hello", socket.display()),
+    };
+    let replay = match read_frame(&mut stream)? {
+        Some((FRAME_REPLAY, payload)) => payload,
+        _ => bail!("Supervisor at {} sent no replay", socket.display()),
+    };
+    stream.set_read_timeout(None)?;
+
+    let (output_tx, output_rx) = mpsc::channel();
+    let connection = Arc::new(Connection {
+        stream: Mutex::new(stream.try_clone()?),
+        pid: info.pid,
+        size: Mutex::new(PtySize {
+            rows: info.rows,
+            cols: info.cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        }),
+        exit: Mutex::new(None),
+        exited: Condvar::new(),
+        output: Mutex::new(Some(output_rx)),
+    });
+    let weak = Arc::downgrade(&connection);
+    std::thread::spawn(move || demux(stream, &output_tx, &weak));
+
+    Ok(SupervisedSession {
+        info,
+        replay,
+        master: SupervisedMaster {
+            connection: Arc::clone(&connection),
+            initial_output: Mutex::new(Vec::new()),
+        },
+        child: SupervisedChild { connection },
+    })
+}
+
+fn demux(
+    mut stream: UnixStream,
+    output: &mpsc::Sender<Vec<u8>>,
+    connection: &std::sync::Weak<Connection>,
+) {
+    let status = loop {
+        match read_frame(&mut stream) {
+            Ok(Some((FRAME_OUTPUT, payload))) => {
+                let _ = output.send(payload);
+            }
+            Ok(Some((FRAME_EXIT, payload))) => {
+                let code = payload.try_into().map_or(1, u32::from_be_bytes);
+                break ExitStatus::with_exit_code(code);
+            }
+            Ok(Some(_)) => {}
+            Ok(None) | Err(_) => break ExitStatus::with_signal("supervisor connection lost"),
+        }
+    };
+    if let Some(connection) = connection.upgrade() {
+        connection.set_exit(status);
+    }
+}
+
+/// Reconnect to every session in `state_dir`; manifests of dead supervisors
+/// are removed.
+pub fn reconnect_all(state_dir: &Path) -> Vec<SupervisedSession> {
+    let Ok(entries) = std::fs::read_dir(state_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|manifest| {
+            let terminal_id = manifest.file_stem()?.to_str()?.to_string();
+            let socket = socket_path(state_dir, &terminal_id);
+            match connect(&socket) {
+                Ok(session) => Some(session),
+                Err(e) => {
+                    tracing::warn!(
+                        terminal_id = %terminal_id,
+                        error = %e,
+                        "Removing stale PTY supervisor session"
+                    );
+                    let _ = std::fs::remove_file(&manifest);
+                    let _ = std::fs::remove_file(&socket);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Supervisor-backed stand-in for a PTY master
+pub struct SupervisedMaster {
+    connection: Arc<Connection>,
+    initial_output: Mutex<Vec<u8>>,
+}
+
+impl SupervisedMaster {
+    /// Output the reader yields before anything received from the supervisor.
+    pub fn with_initial_output(self, output: Vec<u8>) -> Self {
+        *lock(&self.initial_output) = output;
+        self
+    }
+}
+
+impl MasterPty for SupervisedMaster {
+    fn resize(&self, size: PtySize) -> anyhow::Result<()> {
+        self.connection.send(FRAME_RESIZE, &encode_size(size))?;
+        *lock(&self.connection.size) = size;
+        Ok(())
+    }
+
+    fn get_size(&self) -> anyhow::Result<PtySize> {
+        Ok(*lock(&self.connection.size))
+    }
+
+    fn try_clone_reader(&self) -> anyhow::Result<Box<dyn Read + Send>> {
+        let output = lock(&self.connection.output)
+            .take()
+            .ok_or_else(|| anyhow!("Supervised PTY output has a single reader"))?;
+        Ok(Box::new(ChannelReader::new(
+            output,
+            std::mem::take(&mut *lock(&self.initial_output)),
+        )))
+    }
+
+    fn take_writer(&self) -> anyhow::Result<Box<dyn Write + Send>> {
+        Ok(Box::new(SupervisedWriter {
+            connection: Arc::clone(&self.connection),
+        }))
+    }
+
+    fn process_group_leader(&self) -> Option<nix::libc::pid_t> {
+        None
+    }
+
+    fn as_raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        None
+    }
+}
+
+struct SupervisedWriter {
+    connection: Arc<Connection>,
+}
+
+impl Write for SupervisedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.connection.send(FRAME_INPUT, buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Supervisor-backed stand-in for the terminal's child process
+pub struct SupervisedChild {
+    connection: Arc<Connection>,
+}
+
+impl std::fmt::Debug for SupervisedChild {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SupervisedChild")
+            .field("pid", &self.connection.pid)
+            .finish()
+    }
+}
+
+impl ChildKiller for SupervisedChild {
+    fn kill(&mut self) -> std::io::Result<()> {
+        self.connection.send(FRAME_KILL, &[])
+    }
+
+    fn clone_killer(&self) -> Box<dyn ChildKiller + Send + Sync> {
+        Box::new(Self {
+            connection: Arc::clone(&self.connection),
+        })
+    }
+}
+
+impl Child for SupervisedChild {
+    fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        Ok(lock(&self.connection.exit).clone())
+    }
+
+    fn wait(&mut self) -> std::io::Result<ExitStatus> {
+        let mut exit = lock(&self.connection.exit);
+        loop {
+            if let Some(status) = exit.clone() {
+                return Ok(status);
+            }
+            exit = self
+                .connection
+                .exited
+                .wait(exit)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    fn process_id(&self) -> Option<u32> {
+        Some(self.connection.pid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(state_dir: &Path, command: SpawnCommand) -> SupervisorSpec {
+        SupervisorSpec {
+            terminal_id: "term-1".to_string(),
+            session_id: "session-1".to_string(),
+            command,
+            wrapper: Vec::new(),
+            cols: 80,
+            rows: 24,
+            state_dir: state_dir.to_path_buf(),
+            resource_report: None,
+        }
+    }
+
+    fn read_until(reader: &mut dyn Read, needle: &str) -> String {
+        let mut seen = String::new();
+        let mut buf = [0u8; 256];
+        while !seen.contains(needle) {
+            let n = reader.read(&mut buf).unwrap();
+            assert!(n > 0, "output ended before {needle:?}: {seen:?}");
+            seen.push_str(&String::from_utf8_lossy(&buf[..n]));
+        }
+        seen
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let mut wire = Vec::new();
+        write_frame(&mut wire, FRAME_OUTPUT, b"hello").unwrap();
+        write_frame(&mut wire, FRAME_KILL, &[]).unwrap();
+        let mut reader = wire.as_slice();
+        assert_eq!(
+            read_frame(&mut reader).unwrap(),
+            Some((FRAME_OUTPUT, b"hello".to_vec()))
+        );
+        assert_eq!(
+            read_frame(&mut reader).unwrap(),
+            Some((FRAME_KILL, Vec::new()))
+        );
+        assert_eq!(read_frame(&mut reader).unwrap(), None);
+
+        let size = decode_size(&encode_size(PtySize {
+            rows: 40,
+            cols: 120,
+            pixel_width: 0,
+            pixel_height: 0,
+        }))
+        .unwrap();
+        assert_eq!((size.cols, size.rows), (120, 40));
+    }
+
+    #[test]
+    fn session_survives_client_reconnect() {
+        let state_dir = tempfile::tempdir().unwrap();
+        let spec = spec(state_dir.path(), SpawnCommand::new("cat", state_dir.path()));
+        let server = std::thread::spawn(move || {
+            let mut ready = Vec::new();
+            let code = serve(spec, &mut ready);
+            (code.unwrap(), ready)
+        });
+
+        // Wait for the manifest the supervisor publishes once it is ready
+        let manifest = manifest_path(state_dir.path(), "term-1");
+        for _ in 0..100 {
+            if manifest.exists() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let first = reconnect_all(state_dir.path()).pop().unwrap();
+        assert_eq!(first.info.session_id, "session-1");
+        let mut reader = first.master.try_clone_reader().unwrap();
+        let mut writer = first.master.take_writer().unwrap();
+        writer.write_all(b"before restart\n").unwrap();
+        read_until(reader.as_mut(), "before restart");
+
+        // A server restart drops the old connection; the command keeps running
+        drop((reader, writer, first));
+        let second = reconnect_all(state_dir.path()).pop().unwrap();
+        assert!(String::from_utf8_lossy(&second.replay).contains("before restart"));
+        let mut child = second.child;
+        assert!(child.try_wait().unwrap().is_none());
+        let mut reader = second.master.try_clone_reader().unwrap();
+        let mut writer = second.master.take_writer().unwrap();
+        writer.write_all(b"after restart\n").unwrap();
+        read_until(reader.as_mut(), "after restart");
+
+        child.kill().unwrap();
+        assert!(!child.wait().unwrap().success());
+        let (_, ready) = server.join().unwrap();
+        assert!(String::from_utf8_lossy(&ready).contains("Ready"));
+        assert!(!manifest.exists());
+    }
+}
@@ -0,0 +1,651 @@
+//! Workflow templates
+//!
+//! A template is a versioned JSON/YAML document describing a workflow's
+//! task/terminal graph (roles, CLI types, model configs, slash commands).
+//! Templates are exported from existing workflows and instantiated into
+//! `CreateWorkflowRequest`s; every text field is rendered with
+//! [`TemplateRenderer`] so it can use `{{param}}` and `{{workflow.*}}`.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, anyhow, bail};
+use db::models::{
+    CreateTerminalRequest, CreateWorkflowRequest, CreateWorkflowTaskRequest, OrchestratorConfig,
+    Terminal, TerminalConfig, TerminalResourceLimits, Workflow, WorkflowActivityPolicyRequest,
+    WorkflowBudgetRequest, WorkflowCommand, WorkflowCommandRequest, WorkflowTask,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use ts_rs::TS;
+use utils::text::git_branch_id;
+
+use super::{
+    orchestrator::{constants::LOCAL_API_TYPE, task_graph},
+    template_renderer::{TemplateRenderer, WorkflowContext},
+};
+
+/// Template document schema produced and accepted by this version.
+pub const WORKFLOW_TEMPLATE_SCHEMA_VERSION: u32 = 1;
+
+/// Serialization format of a template document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum TemplateFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+/// Workflow template document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WorkflowTemplateDocument {
+    pub schema_version: u32,
+    /// Template name (not the name of instantiated workflows)
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Parameters that can be referenced as `{{name}}`
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+    pub workflow: TemplateWorkflow,
+    /// Slash commands, in execution order
+    #[serde(default)]
+    pub commands: Vec<TemplateCommand>,
+    /// Tasks, in order
+    #[serde(default)]
+    pub tasks: Vec<TemplateTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateParameter {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Used when the parameter is not supplied; without it the parameter is required
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateWorkflow {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default = "default_execution_mode")]
+    pub execution_mode: String,
+    #[serde(default)]
+    pub initial_goal: Option<String>,
+    #[serde(default)]
+    pub use_slash_commands: bool,
+    #[serde(default)]
+    pub target_branch: Option<String>,
+    #[serde(default = "default_true")]
+    pub git_watcher_enabled: bool,
+    /// Main Agent settings; the API key is supplied at instantiation
+    #[serde(default)]
+    pub orchestrator: Option<TemplateOrchestrator>,
+    #[serde(default)]
+    pub error_terminal: Option<TemplateTerminalConfig>,
+    pub merge_terminal: TemplateTerminalConfig,
+    #[serde(default)]
+    pub budget: Option<TemplateBudget>,
+    #[serde(default)]
+    pub activity_policy: Option<TemplateActivityPolicy>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateOrchestrator {
+    pub api_type: String,
+    pub base_url: String,
+    pub model: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateTerminalConfig {
+    pub cli_type_id: String,
+    pub model_config_id: String,
+    #[serde(default)]
+    pub custom_base_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateBudget {
+    #[serde(default)]
+    pub max_tokens: Option<i64>,
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+    #[serde(default)]
+    pub max_duration_secs: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateActivityPolicy {
+    #[serde(default)]
+    pub stall_timeout_secs: Option<i64>,
+    #[serde(default)]
+    pub loop_threshold: Option<i64>,
+    #[serde(default)]
+    pub escalation_interval_secs: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateCommand {
+    pub preset_id: String,
+    /// JSON string passed to the preset as-is (not rendered)
+    #[serde(default)]
+    pub custom_params: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateTask {
+    /// Identifier used by `dependsOn`, unique within the template
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Custom branch; auto-generated when omitted
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Keys of tasks that must complete first
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    pub terminals: Vec<TemplateTerminal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TemplateTerminal {
+    pub cli_type_id: String,
+    pub model_config_id: String,
+    #[serde(default)]
+    pub custom_base_url: Option<String>,
+    #[serde(default)]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub role_description: Option<String>,
+    #[serde(default = "default_true")]
+    pub auto_confirm: bool,
+    #[serde(default)]
+    pub resource_limits: Option<TerminalResourceLimits>,
+    #[serde(default)]
+    pub runner_labels: Option<Vec<String>>,
+}
+
+fn default_execution_mode() -> String {
+    "diy".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl WorkflowTemplateDocument {
+    /// Builds a template from an existing workflow.
+    ///
+    /// API keys are never exported, and auto-generated branch names (which
+    /// embed the source workflow id) are dropped so new ones get generated.
+    pub fn from_workflow(
+        workflow: &Workflow,
+        tasks: &[(WorkflowTask, Vec<Terminal>)],
+        commands: &[WorkflowCommand],
+    ) -> Self {
+        let auto_branch_prefix = format!("workflow/{}/", workflow.id);
+
+        let mut keys_by_task_id: HashMap<&str, String> = HashMap::new();
+        let mut used_keys = HashSet::new();
+        for (index, (task, _)) in tasks.iter().enumerate() {
+            let base = match git_branch_id(&task.name) {
+                slug if slug.is_empty() => format!("task-{}", index + 1),
+                slug => slug,
+            };
+            let mut key = base.clone();
+            let mut counter = 2;
+            while !used_keys.insert(key.clone()) {
+                key = format!("{base}-{counter}");
+                counter += 1;
+            }
+            keys_by_task_id.insert(task.id.as_str(), key);
+        }
+
+        let template_tasks = tasks
+            .iter()
+            .map(|(task, terminals)| TemplateTask {
+                key: keys_by_task_id[task.id.as_str()].clone(),
+                name: task.name.clone(),
+                description: task.description.clone(),
+                branch: (!task.branch.starts_with(&auto_branch_prefix))
+                    .then(|| task.branch.clone()),
+                depends_on: task
+                    .depends_on
+                    .iter()
+                    .filter_map(|dep| keys_by_task_id.get(dep.as_str()).cloned())
+                    .collect(),
+                terminals: terminals
+                    .iter()
+                    .map(|terminal| TemplateTerminal {
+                        cli_type_id: terminal.cli_type_id.clone(),
+                        model_config_id: terminal.model_config_id.clone(),
+                        custom_base_url: terminal.custom_base_url.clone(),
+                        role: terminal.role.clone(),
+                        role_description: terminal.role_description.clone(),
+                        auto_confirm: terminal.auto_confirm,
+                        resource_limits: terminal.resource_limits.clone(),
+                        runner_labels: terminal.runner_labels.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let orchestrator = match (
+            workflow.orchestrator_enabled,
+            &workflow.orchestrator_api_type,
+            &workflow.orchestrator_base_url,
+            &workflow.orchestrator_model,
+        ) {
+            (true, Some(api_type), Some(base_url), Some(model)) => Some(TemplateOrchestrator {
+                api_type: api_type.clone(),
+                base_url: base_url.clone(),
+                model: model.clone(),
+            }),
+            _ => None,
+        };
+        let error_terminal = match (
+            workflow.error_terminal_enabled,
+            &workflow.error_terminal_cli_id,
+            &workflow.error_terminal_model_id,
+        ) {
+            (true, Some(cli_type_id), Some(model_config_id)) => Some(TemplateTerminalConfig {
+                cli_type_id: cli_type_id.clone(),
+                model_config_id: model_config_id.clone(),
+                custom_base_url: None,
+            }),
+            _ => None,
+        };
+        let budget = (workflow.budget_max_tokens.is_some()
+            || workflow.budget_max_cost_usd.is_some()
+            || workflow.budget_max_duration_secs.is_some())
+        .then(|| TemplateBudget {
+            max_tokens: workflow.budget_max_tokens,
+            max_cost_usd: workflow.budget_max_cost_usd,
+            max_duration_secs: workflow.budget_max_duration_secs,
+        });
+        let activity_policy = (workflow.activity_stall_timeout_secs.is_some()
+            || workflow.activity_loop_threshold.is_some()
+            || workflow.activity_escalation_interval_secs.is_some())
+        .then(|| TemplateActivityPolicy {
+            stall_timeout_secs: workflow.activity_stall_timeout_secs,
+            loop_threshold: workflow.activity_loop_threshold,
+            escalation_interval_secs: workflow.activity_escalation_interval_secs,
+        });
+
+        Self {
+            schema_version: WORKFLOW_TEMPLATE_SCHEMA_VERSION,
+            name: workflow.name.clone(),
+            description: workflow.description.clone(),
+            parameters: Vec::new(),
+            workflow: TemplateWorkflow {
+                name: workflow.name.clone(),
+                description: workflow.description.clone(),
+                execution_mode: workflow.execution_mode.clone(),
+                initial_goal: workflow.initial_goal.clone(),
+                use_slash_commands: workflow.use_slash_commands,
+                target_branch: Some(workflow.target_branch.clone()),
+                git_watcher_enabled: workflow.git_watcher_enabled,
+                orchestrator,
+                error_terminal,
+                merge_terminal: TemplateTerminalConfig {
+                    cli_type_id: workflow.merge_terminal_cli_id.clone(),
+                    model_config_id: workflow.merge_terminal_model_id.clone(),
+                    custom_base_url: None,
+                },
+                budget,
+                activity_policy,
+            },
+            commands: commands
+                .iter()
+                .map(|command| TemplateCommand {
+                    preset_id: command.preset_id.clone(),
+                    custom_params: command.custom_params.clone(),
+                })
+                .collect(),
+            tasks: template_tasks,
+        }
+    }
+
+    /// Parses a JSON or YAML document and validates it.
+    pub fn parse(content: &str) -> Result<Self> {
+        // YAML is a superset of JSON, but JSON errors are clearer for JSON input.
+        let document: Self = if content.trim_start().starts_with('{') {
+            serde_json::from_str(content).map_err(|e| anyhow!("Invalid template JSON: {e}"))?
+        } else {
+            serde_yaml::from_str(content).map_err(|e| anyhow!("Invalid template YAML: {e}"))?
+        };
+        document.validate()?;
+        Ok(document)
+    }
+
+    pub fn serialize(&self, format: TemplateFormat) -> Result<String> {
+        Ok(match format {
+            TemplateFormat::Json => serde_json::to_string_pretty(self)?,
+            TemplateFormat::Yaml => serde_yaml::to_string(self)?,
+        })
+    }
+
+    /// Checks structure, parameter names and the task dependency graph.
+    pub fn validate(&self) -> Result<()> {
+        if self.schema_version == 0 || self.schema_version > WORKFLOW_TEMPLATE_SCHEMA_VERSION {
+            bail!(
+                "Unsupported template schemaVersion {} (supported: 1..={WORKFLOW_TEMPLATE_SCHEMA_VERSION})",
+                self.schema_version
+            );
+        }
+        if self.name.trim().is_empty() {
+            bail!("Template name is required");
+        }
+        if self.workflow.name.trim().is_empty() {
+            bail!("workflow.name is required");
+        }
+
+        let mut parameter_names = HashSet::new();
+        for parameter in &self.parameters {
+            if parameter.name.trim().is_empty() || parameter.name == "workflow" {
+                bail!("Invalid parameter name '{}'", parameter.name);
+            }
+            if !parameter_names.insert(parameter.name.as_str()) {
+                bail!("Duplicate parameter '{}'", parameter.name);
+            }
+        }
+
+        let mut task_keys = HashSet::new();
+        for (index, task) in self.tasks.iter().enumerate() {
+            if task.key.trim().is_empty() {
+                bail!("tasks[{index}].key is required");
+            }
+            if !task_keys.insert(task.key.as_str()) {
+                bail!("Duplicate task key '{}'", task.key);
+            }
+            if task.name.trim().is_empty() {
+                bail!("tasks[{index}].name is required");
+            }
+            if task.terminals.is_empty() {
+                bail!("tasks[{index}].terminals must not be empty");
+            }
+        }
+        for task in &self.tasks {
+            if let Some(unknown) = task.depends_on.iter().find(|dep| !task_keys.contains(dep.as_str()))
+            {
+                bail!("Task '{}' depends on unknown task '{unknown}'", task.key);
+            }
+        }
+        if let Some(cycle) = task_graph::find_cycle(
+            self.tasks
+                .iter()
+                .map(|task| (task.key.as_str(), task.depends_on.as_slice())),
+        ) {
+            bail!("Task dependencies contain a cycle: {}", cycle.join(" -> "));
+        }
+        Ok(())
+    }
+
+    /// Resolves declared parameters against supplied values and defaults.
+    fn resolve_parameters(&self, supplied: &HashMap<String, String>) -> Result<Map<String, Value>> {
+        if let Some(unknown) = supplied
+            .keys()
+            .find(|name| !self.parameters.iter().any(|p| &p.name == *name))
+        {
+            bail!("Unknown template parameter '{unknown}'");
+        }
+
+        let mut resolved = Map::new();
+        for parameter in &self.parameters {
+            let value = supplied
+                .get(&parameter.name)
+                .or(parameter.default.as_ref())
+                .ok_or_else(|| anyhow!("Missing required template parameter '{}'", parameter.name))?;
+            resolved.insert(parameter.name.clone(), Value::String(value.clone()));
+        }
+        Ok(resolved)
+    }
+
+    /// Renders the template into a create request for `project_id`.
+    ///
+    /// `orchestrator_api_key` is required when the template configures a Main Agent
+    /// (except for the `local` API type).
+    pub fn instantiate(
+        &self,
+        project_id: &str,
+        params: &HashMap<String, String>,
+        orchestrator_api_key: Option<&str>,
+    ) -> Result<CreateWorkflowRequest> {
+        self.validate()?;
+        let params_json = Value::Object(self.resolve_parameters(params)?).to_string();
+        let renderer = TemplateRenderer::new();
+        let render = |field: &str, text: &str, ctx: Option<&WorkflowContext>| {
+            renderer
+                .render(text, Some(&params_json), ctx)
+                .map_err(|e| anyhow!("{field}: {e}"))
+        };
+        let render_opt = |field: &str, text: &Option<String>, ctx: Option<&WorkflowContext>| {
+            text.as_deref().map(|text| render(field, text, ctx)).transpose()
+        };
+
+        let spec = &self.workflow;
+        let name = render("workflow.name", &spec.name, None)?;
+        let description = render_opt("workflow.description", &spec.description, None)?;
+        let target_branch = render_opt("workflow.targetBranch", &spec.target_branch, None)?;
+        let ctx = WorkflowContext::new(
+            name.clone(),
+            description.clone(),
+            target_branch.clone().unwrap_or_else(|| "main".to_string()),
+        );
+        let ctx = Some(&ctx);
+
+        let orchestrator_config = match &spec.orchestrator {
+            Some(orchestrator) => {
+                let api_key = orchestrator_api_key
+                    .filter(|key| !key.trim().is_empty())
+                    .or_else(|| (orchestrator.api_type == LOCAL_API_TYPE).then_some(""))
+                    .ok_or_else(|| {
+                        anyhow!("orchestratorApiKey is required: the template configures a Main Agent")
+                    })?;
+                Some(OrchestratorConfig {
+                    api_type: orchestrator.api_type.clone(),
+                    base_url: orchestrator.base_url.clone(),
+                    api_key: api_key.to_string(),
+                    model: render("workflow.orchestrator.model", &orchestrator.model, ctx)?,
+                })
+            }
+            None => None,
+        };
+        let terminal_config = |field: &str, config: &TemplateTerminalConfig| -> Result<TerminalConfig> {
+            Ok(TerminalConfig {
+                cli_type_id: render(field, &config.cli_type_id, ctx)?,
+                model_config_id: render(field, &config.model_config_id, ctx)?,
+                model_config: None,
+                custom_base_url: config.custom_base_url.clone(),
+                custom_api_key: None,
+            })
+        };
+
+        let mut tasks = Vec::with_capacity(self.tasks.len());
+        for (task_index, task) in self.tasks.iter().enumerate() {
+            let field = |name: &str| format!("tasks[{task_index}].{name}");
+            let mut terminals = Vec::with_capacity(task.terminals.len());
+            for (terminal_index, terminal) in task.terminals.iter().enumerate() {
+                let field = |name: &str| format!("tasks[{task_index}].terminals[{terminal_index}].{name}");
+                terminals.push(CreateTerminalRequest {
+                    id: None,
+                    cli_type_id: render(&field("cliTypeId"), &terminal.cli_type_id, ctx)?,
+                    model_config_id: render(&field("modelConfigId"), &terminal.model_config_id, ctx)?,
+                    model_config: None,
+                    custom_base_url: terminal.custom_base_url.clone(),
+                    custom_api_key: None,
+                    role: render_opt(&field("role"), &terminal.role, ctx)?,
+                    role_description: render_opt(
+                        &field("roleDescription"),
+                        &terminal.role_description,
+                        ctx,
+                    )?,
+                    order_index: i32::try_from(terminal_index)?,
+                    auto_confirm: terminal.auto_confirm,
+                    resource_limits: terminal.resource_limits.clone(),
+                    runner_labels: terminal.runner_labels.clone(),
+                });
+            }
+            tasks.push(CreateWorkflowTaskRequest {
+                id: Some(task.key.clone()),
+                name: render(&field("name"), &task.name, ctx)?,
+                description: render_opt(&field("description"), &task.description, ctx)?,
+                branch: render_opt(&field("branch"), &task.branch, ctx)?,
+                order_index: i32::try_from(task_index)?,
+                depends_on: task.depends_on.clone(),
+                terminals,
+            });
+        }
+
+        Ok(CreateWorkflowRequest {
+            project_id: project_id.to_string(),
+            name,
+            description,
+            execution_mode: spec.execution_mode.clone(),
+            initial_goal: render_opt("workflow.initialGoal", &spec.initial_goal, ctx)?,
+            use_slash_commands: spec.use_slash_commands,
+            commands: (!self.commands.is_empty()).then(|| {
+                self.commands
+                    .iter()
+                    .map(|command| WorkflowCommandRequest {
+                        preset_id: command.preset_id.clone(),
+                        custom_params: command.custom_params.clone(),
+                    })
+                    .collect()
+            }),
+            orchestrator_config,
+            error_terminal_config: spec
+                .error_terminal
+                .as_ref()
+                .map(|config| terminal_config("workflow.errorTerminal", config))
+                .transpose()?,
+            merge_terminal_config: terminal_config("workflow.mergeTerminal", &spec.merge_terminal)?,
+            target_branch,
+            git_watcher_enabled: Some(spec.git_watcher_enabled),
+            budget: spec.budget.as_ref().map(|budget| WorkflowBudgetRequest {
+                max_tokens: budget.max_tokens,
+                max_cost_usd: budget.max_cost_usd,
+                max_duration_secs: budget.max_duration_secs,
+            }),
+            activity_policy: spec.activity_policy.as_ref().map(|policy| {
+                WorkflowActivityPolicyRequest {
+                    stall_timeout_secs: policy.stall_timeout_secs,
+                    loop_threshold: policy.loop_threshold,
+                    escalation_interval_secs: policy.escalation_interval_secs,
+                }
+            }),
+            tasks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE_YAML: &str = r"
+schemaVersion: 1
+name: Fullstack feature
+parameters:
+  - name: feature
+  - name: model
+    default: model-sonnet
+workflow:
+  name: '{{feature}}'
+  targetBranch: main
+  mergeTerminal:
+    cliTypeId: cli-claude-code
+    modelConfigId: '{{model}}'
+tasks:
+  - key: api
+    name: '{{feature}} API'
+    terminals:
+      - cliTypeId: cli-claude-code
+        modelConfigId: '{{model}}'
+        role: coder
+  - key: ui
+    name: '{{feature}} UI'
+    description: 'Build the UI for {{workflow.name}}'
+    dependsOn: [api]
+    terminals:
+      - cliTypeId: cli-codex
+        modelConfigId: model-codex
+";
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn instantiate_renders_parameters_and_workflow_context() {
+        let document = WorkflowTemplateDocument::parse(TEMPLATE_YAML).unwrap();
+        let request = document
+            .instantiate("project-1", &params(&[("feature", "Login")]), None)
+            .unwrap();
+
+        assert_eq!(request.name, "Login");
+        assert_eq!(request.merge_terminal_config.model_config_id, "model-sonnet");
+        assert_eq!(request.tasks[0].name, "Login API");
+        assert_eq!(request.tasks[0].terminals[0].model_config_id, "model-sonnet");
+        assert_eq!(
+            request.tasks[1].description.as_deref(),
+            Some("Build the UI for Login")
+        );
+        assert_eq!(request.tasks[1].id.as_deref(), Some("ui"));
+        assert_eq!(request.tasks[1].depends_on, vec!["api".to_string()]);
+        assert_eq!(request.tasks[1].order_index, 1);
+    }
+
+    #[test]
+    fn instantiate_rejects_missing_and_unknown_parameters() {
+        let document = WorkflowTemplateDocument::parse(TEMPLATE_YAML).unwrap();
+        let missing = document.instantiate("project-1", &HashMap::new(), None);
+        assert!(missing.unwrap_err().to_string().contains("'feature'"));
+
+        let unknown =
+            document.instantiate("project-1", &params(&[("feature", "x"), ("typo", "y")]), None);
+        assert!(unknown.unwrap_err().to_string().contains("'typo'"));
+    }
+
+    #[test]
+    fn parse_rejects_cycles_and_round_trips_formats() {
+        let cyclic = TEMPLATE_YAML.replace("  - key: api\n", "  - key: api\n    dependsOn: [ui]\n");
+        let error = WorkflowTemplateDocument::parse(&cyclic).unwrap_err();
+        assert!(error.to_string().contains("cycle"));
+
+        let document = WorkflowTemplateDocument::parse(TEMPLATE_YAML).unwrap();
+        for format in [TemplateFormat::Json, TemplateFormat::Yaml] {
+            let text = document.serialize(format).unwrap();
+            assert_eq!(WorkflowTemplateDocument::parse(&text).unwrap(), document);
+        }
+    }
+}
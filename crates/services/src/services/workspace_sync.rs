@@ -0,0 +1,634 @@
+//! Workspace synchronization for runners without a shared filesystem
+//!
+//! A runner keeps one git clone per workspace under its workspace root, cloned
+//! from a bundle the server uploads or directly from a remote URL. The server
+//! pulls new commits back as a bundle and fast-forwards its local worktree, so
+//! the `GitWatcher` sees them exactly like commits made by a local terminal.
+//! Uncommitted runner changes are mirrored as a patch on top of those commits.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+use tokio::sync::Mutex;
+
+use super::{
+    git::GitCli,
+    runner_client::{RunnerClient, SharedRunnerClient},
+};
+
+/// Directory a runner keeps its workspaces in
+pub const WORKSPACE_DIR_ENV: &str = "SOLODAWN_RUNNER_WORKSPACE_DIR";
+
+/// Size of the bundle chunks streamed between server and runner
+pub const BUNDLE_CHUNK_SIZE: usize = 64 * 1024;
+
+const MAX_WORKSPACE_ID_LEN: usize = 128;
+
+/// Where a runner clones a workspace from
+#[derive(Debug, Clone)]
+pub enum WorkspaceSource {
+    /// Repository on the server; remote runners receive the branch as a git bundle
+    LocalRepo(PathBuf),
+    /// URL the runner clones from itself
+    RemoteUrl(String),
+}
+
+/// Request to provision a workspace on a runner
+#[derive(Debug, Clone)]
+pub struct WorkspaceProvision {
+    pub workspace_id: String,
+    pub source: WorkspaceSource,
+    /// Branch to clone and check out
+    pub branch: String,
+    /// Labels the chosen runner must carry; ignored by single runners
+    pub runner_labels: Vec<String>,
+}
+
+/// A workspace ready for terminals
+#[derive(Debug, Clone)]
+pub struct ProvisionedWorkspace {
+    /// Directory on the runner, for `TerminalSpawnConfig.working_dir`
+    pub working_dir: String,
+    pub head_commit: String,
+}
+
+/// Changes in a runner workspace since a given commit
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceSync {
+    pub head_commit: String,
+    /// New commits, oldest first
+    pub commits: Vec<String>,
+    /// `git diff --binary` of uncommitted changes, untracked files included
+    pub patch: String,
+}
+
+/// Git clones a runner provisions for workspaces.
+#[derive(Debug, Clone)]
+pub struct RunnerWorkspaces {
+    root: PathBuf,
+}
+
+impl RunnerWorkspaces {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Root from `SOLODAWN_RUNNER_WORKSPACE_DIR`, defaulting to a directory
+    /// under the system temp dir.
+    pub fn from_env() -> Self {
+        let root = std::env::var_os(WORKSPACE_DIR_ENV)
+            .filter(|value| !value.is_empty())
+            .map_or_else(
+                || std::env::temp_dir().join("solodawn-runner-workspaces"),
+                PathBuf::from,
+            );
+        Self::new(root)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory of `workspace_id`; rejects ids that are not a plain file name.
+    pub fn workspace_dir(&self, workspace_id: &str) -> Result<PathBuf> {
+        let valid = !workspace_id.is_empty()
+            && workspace_id.len() <= MAX_WORKSPACE_ID_LEN
+            && !workspace_id.starts_with('.')
+            && workspace_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            bail!("Invalid workspace id '{workspace_id}'");
+        }
+        Ok(self.root.join(workspace_id))
+    }
+
+    /// Temporary file under the root for an incoming or outgoing bundle.
+    pub fn temp_bundle(&self) -> Result<tempfile::TempPath> {
+        std::fs::create_dir_all(&self.root)
+            .with_context(|| format!("Failed to create {}", self.root.display()))?;
+        Ok(tempfile::Builder::new()
+            .prefix(".bundle-")
+            .tempfile_in(&self.root)?
+            .into_temp_path())
+    }
+
+    /// Clone `branch` from `source` (a bundle path or a URL). A workspace that
+    /// already exists is returned as is, so provisioning is idempotent.
+    pub async fn provision(
+        &self,
+        workspace_id: &str,
+        source: &str,
+        branch: &str,
+    ) -> Result<ProvisionedWorkspace> {
+        let dir = self.workspace_dir(workspace_id)?;
+        if branch.is_empty() || branch.starts_with('-') {
+            bail!("Invalid branch '{branch}'");
+        }
+
+        if dir.join(".git").exists() {
+            tracing::info!(workspace_id, "Workspace already provisioned, reusing it");
+        } else {
+            tokio::fs::create_dir_all(&self.root)
+                .await
+                .with_context(|| format!("Failed to create {}", self.root.display()))?;
+            // Clone next to the target and rename, so a failed clone never
+            // leaves a half-populated workspace behind.
+            let partial = self.root.join(format!(".{workspace_id}.partial"));
+            if partial.exists() {
+                tokio::fs::remove_dir_all(&partial).await?;
+            }
+            git(
+                &self.root,
+                [
+                    "clone".into(),
+                    "--branch".into(),
+                    branch.into(),
+                    "--".into(),
+                    source.into(),
+                    partial.clone().into_os_string(),
+                ],
+            )
+            .await
+            .with_context(|| format!("Failed to clone workspace {workspace_id}"))?;
+            tokio::fs::rename(&partial, &dir).await?;
+            tracing::info!(workspace_id, branch, "Workspace provisioned");
+        }
+
+        Ok(ProvisionedWorkspace {
+            working_dir: dir.to_string_lossy().into_owned(),
+            head_commit: head_commit(&dir).await?,
+        })
+    }
+
+    /// Commits after `since_commit` and uncommitted changes. When there are new
+    /// commits they are written to `bundle_out` as a git bundle.
+    pub async fn collect_changes(
+        &self,
+        workspace_id: &str,
+        since_commit: &str,
+        bundle_out: &Path,
+    ) -> Result<WorkspaceSync> {
+        let dir = self.workspace_dir(workspace_id)?;
+        if !dir.join(".git").exists() {
+            bail!("Workspace {workspace_id} is not provisioned");
+        }
+        validate_commit(since_commit)?;
+
+        let head_commit = head_commit(&dir).await?;
+        let commits: Vec<String> = git(
+            &dir,
+            ["rev-list", "--reverse", &format!("{since_commit}..HEAD")],
+        )
+        .await
+        .with_context(|| format!("Commit {since_commit} is not in workspace {workspace_id}"))?
+        .lines()
+        .map(ToString::to_string)
+        .collect();
+        if !commits.is_empty() {
+            git(
+                &dir,
+                [
+                    OsString::from("bundle"),
+                    "create".into(),
+                    bundle_out.as_os_str().to_owned(),
+                    "HEAD".into(),
+                    format!("^{since_commit}").into(),
+                ],
+            )
+            .await?;
+        }
+
+        // Stage intent-to-add entries in a temporary index, so untracked files
+        // show up in the diff without touching the index terminals work with.
+        let index = tempfile::TempDir::new()?;
+        let envs = [(
+            OsString::from("GIT_INDEX_FILE"),
+            index.path().join("index").into_os_string(),
+        )];
+        git_with_env(&dir, ["read-tree", "HEAD"], &envs).await?;
+        git_with_env(&dir, ["add", "--all", "--intent-to-add"], &envs).await?;
+        let patch = git_with_env(&dir, ["diff", "--binary", "HEAD"], &envs).await?;
+
+        Ok(WorkspaceSync {
+            head_commit,
+            commits,
+            patch,
+        })
+    }
+
+    /// Delete a workspace; missing workspaces are not an error.
+    pub async fn release(&self, workspace_id: &str) -> Result<()> {
+        let dir = self.workspace_dir(workspace_id)?;
+        match tokio::fs::remove_dir_all(&dir).await {
+            Ok(()) => {
+                tracing::info!(workspace_id, "Workspace released");
+                Ok(())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e).with_context(|| format!("Failed to remove {}", dir.display())),
+        }
+    }
+}
+
+/// Commit `HEAD` points at in `repo`.
+pub async fn head_commit(repo: &Path) -> Result<String> {
+    Ok(git(repo, ["rev-parse", "HEAD"]).await?.trim().to_string())
+}
+
+/// Write `branch` of `repo` to `out` as a git bundle.
+pub async fn create_bundle(repo: &Path, branch: &str, out: &Path) -> Result<()> {
+    if branch.is_empty() || branch.starts_with('-') {
+        bail!("Invalid branch '{branch}'");
+    }
+    git(
+        repo,
+        [
+            OsString::from("bundle"),
+            "create".into(),
+            out.as_os_str().to_owned(),
+            branch.into(),
+        ],
+    )
+    .await
+    .with_context(|| format!("Failed to bundle {branch} of {}", repo.display()))?;
+    Ok(())
+}
+
+/// Branch checkout a terminal works in
+#[derive(Debug, Clone)]
+pub struct LocalCheckout {
+    /// Top-level directory of the worktree
+    pub worktree: PathBuf,
+    /// Path of the terminal's directory below `worktree`, empty or ending in `/`
+    pub prefix: String,
+    pub branch: String,
+}
+
+/// Checkout `dir` belongs to; `None` outside git or on a detached or unborn `HEAD`.
+pub async fn local_checkout(dir: &Path) -> Option<LocalCheckout> {
+    let output = git(
+        dir,
+        [
+            "rev-parse",
+            "--show-toplevel",
+            "--show-prefix",
+            "--abbrev-ref",
+            "HEAD",
+        ],
+    )
+    .await
+    .ok()?;
+    let mut lines = output.lines();
+    let worktree = PathBuf::from(lines.next()?);
+    let prefix = lines.next()?.to_string();
+    let branch = lines.next()?.to_string();
+    (branch != "HEAD").then_some(LocalCheckout {
+        worktree,
+        prefix,
+        branch,
+    })
+}
+
+/// Fetch a bundle made by [`RunnerWorkspaces::collect_changes`] into
+/// `worktree` and fast-forward its checked-out branch to `head_commit`.
+pub async fn import_bundle(worktree: &Path, bundle: &Path, head_commit: &str) -> Result<()> {
+    validate_commit(head_commit)?;
+    git(
+        worktree,
+        [
+            OsString::from("fetch"),
+            "--no-tags".into(),
+            bundle.as_os_str().to_owned(),
+            "HEAD".into(),
+        ],
+    )
+    .await
+    .context("Failed to fetch runner bundle")?;
+    git(worktree, ["merge", "--ff-only", head_commit])
+        .await
+        .with_context(|| {
+            format!(
+                "Cannot fast-forward {} to runner commit {head_commit}",
+                worktree.display()
+            )
+        })?;
+    Ok(())
+}
+
+/// Apply a [`WorkspaceSync::patch`] to the files of `worktree`, or take it
+/// back out with `reverse`.
+pub async fn apply_patch(worktree: &Path, patch: &str, reverse: bool) -> Result<()> {
+    let file = tempfile::NamedTempFile::new()?.into_temp_path();
+    tokio::fs::write(&file, patch).await?;
+    let mut args = vec![OsString::from("apply"), "--binary".into()];
+    if reverse {
+        args.push("--reverse".into());
+    }
+    args.push(file.to_path_buf().into_os_string());
+    git(worktree, args)
+        .await
+        .with_context(|| format!("Failed to apply runner changes to {}", worktree.display()))?;
+    Ok(())
+}
+
+fn validate_commit(commit: &str) -> Result<()> {
+    if !(4..=64).contains(&commit.len()) || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("Invalid commit id '{commit}'");
+    }
+    Ok(())
+}
+
+async fn git<I, S>(dir: &Path, args: I) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    git_with_env(dir, args, &[]).await
+}
+
+async fn git_with_env<I, S>(dir: &Path, args: I, envs: &[(OsString, OsString)]) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let dir = dir.to_path_buf();
+    let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    let envs = envs.to_vec();
+    let output = tokio::task::spawn_blocking(move || GitCli::git_with_env(&dir, args, &envs))
+        .await
+        .context("git task panicked")??;
+    Ok(output)
+}
+
+/// What a [`WorkspaceMirror`] last took over from the runner
+#[derive(Debug, Default)]
+struct MirrorState {
+    /// Runner commit the local worktree was last synced to
+    synced_commit: String,
+    /// Uncommitted runner changes currently applied to the local worktree
+    applied_patch: String,
+}
+
+/// Mirrors a runner workspace into a local worktree.
+///
+/// Polls the runner for new commits and fast-forwards `local_worktree`, where
+/// a `GitWatcher` publishes them to the orchestrator. The runner's uncommitted
+/// changes are applied on top and replaced on every sync.
+pub struct WorkspaceMirror {
+    runner: SharedRunnerClient,
+    workspace_id: String,
+    local_worktree: PathBuf,
+    poll_interval: Duration,
+    is_stopped: AtomicBool,
+    state: Mutex<MirrorState>,
+}
+
+impl WorkspaceMirror {
+    /// Mirror of a workspace provisioned at `synced_commit`
+    /// ([`ProvisionedWorkspace::head_commit`]).
+    pub fn new(
+        runner: SharedRunnerClient,
+        workspace_id: impl Into<String>,
+        local_worktree: impl Into<PathBuf>,
+        synced_commit: impl Into<String>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            runner,
+            workspace_id: workspace_id.into(),
+            local_worktree: local_worktree.into(),
+            poll_interval,
+            is_stopped: AtomicBool::new(false),
+            state: Mutex::new(MirrorState {
+                synced_commit: synced_commit.into(),
+                applied_patch: String::new(),
+            }),
+        }
+    }
+
+    pub fn workspace_id(&self) -> &str {
+        &self.workspace_id
+    }
+
+    /// Pull the runner's commits and uncommitted changes once.
+    pub async fn sync_once(&self) -> Result<WorkspaceSync> {
+        let mut state = self.state.lock().await;
+        // Take the previous changes back out so the fast-forward starts clean
+        if !state.applied_patch.is_empty() {
+            apply_patch(&self.local_worktree, &state.applied_patch, true).await?;
+            state.applied_patch.clear();
+        }
+        let sync = self
+            .runner
+            .sync_workspace(
+                &self.workspace_id,
+                &state.synced_commit,
+                &self.local_worktree,
+            )
+            .await?;
+        state.synced_commit.clone_from(&sync.head_commit);
+        if !sync.patch.is_empty() {
+            apply_patch(&self.local_worktree, &sync.patch, false).await?;
+            state.applied_patch.clone_from(&sync.patch);
+        }
+        Ok(sync)
+    }
+
+    /// Poll until [`stop`](Self::stop) is called.
+    pub async fn run(&self) {
+        loop {
+            tokio::time::sleep(self.poll_interval).await;
+            if self.is_stopped.load(Ordering::SeqCst) {
+                break;
+            }
+            match self.sync_once().await {
+                Ok(sync) if !sync.commits.is_empty() => tracing::info!(
+                    workspace_id = %self.workspace_id,
+                    commits = sync.commits.len(),
+                    head = %sync.head_commit,
+                    "Imported runner commits"
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    workspace_id = %self.workspace_id,
+                    error = %e,
+                    "Failed to sync runner workspace"
+                ),
+            }
+        }
+    }
+
+    pub fn stop(&self) {
+        self.is_stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::services::{
+        runner_client::{LocalRunner, RunnerClientImpl},
+        terminal::process::ProcessManager,
+    };
+
+    fn git_sync(dir: &Path, args: &[&str]) -> String {
+        GitCli::new().git(dir, args).unwrap()
+    }
+
+    fn init_repo(dir: &Path) {
+        std::fs::create_dir_all(dir).unwrap();
+        git_sync(dir, &["init", "-q", "-b", "main"]);
+        git_sync(dir, &["config", "user.email", "test@example.com"]);
+        git_sync(dir, &["config", "user.name", "Test"]);
+        std::fs::write(dir.join("README.md"), "hello\n").unwrap();
+        git_sync(dir, &["add", "."]);
+        git_sync(dir, &["commit", "-q", "-m", "initial"]);
+    }
+
+    #[test]
+    fn workspace_ids_must_be_plain_names() {
+        let workspaces = RunnerWorkspaces::new("/srv/workspaces");
+        assert_eq!(
+            workspaces.workspace_dir("task-1.a_b").unwrap(),
+            PathBuf::from("/srv/workspaces/task-1.a_b")
+        );
+        for id in ["", "..", ".hidden", "a/b", "a b"] {
+            assert!(workspaces.workspace_dir(id).is_err(), "{id:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn runner_commits_round_trip_through_bundles() {
+        let temp = tempfile::tempdir().unwrap();
+        let local = temp.path().join("local");
+        init_repo(&local);
+        let workspaces = RunnerWorkspaces::new(temp.path().join("runner"));
+
+        let upload = workspaces.temp_bundle().unwrap();
+        create_bundle(&local, "main", &upload).await.unwrap();
+        let provisioned = workspaces
+            .provision("ws-1", upload.to_str().unwrap(), "main")
+            .await
+            .unwrap();
+        assert_eq!(provisioned.head_commit, head_commit(&local).await.unwrap());
+        let again = workspaces
+            .provision("ws-1", upload.to_str().unwrap(), "main")
+            .await
+            .unwrap();
+        assert_eq!(again.working_dir, provisioned.working_dir);
+
+        let remote = PathBuf::from(&provisioned.working_dir);
+        git_sync(&remote, &["config", "user.email", "runner@example.com"]);
+        git_sync(&remote, &["config", "user.name", "Runner"]);
+        std::fs::write(remote.join("feature.txt"), "done\n").unwrap();
+        git_sync(&remote, &["add", "."]);
+        git_sync(&remote, &["commit", "-q", "-m", "feature"]);
+        std::fs::write(remote.join("wip.txt"), "draft\n").unwrap();
+
+        let since = head_commit(&local).await.unwrap();
+        let download = workspaces.temp_bundle().unwrap();
+        let sync = workspaces
+            .collect_changes("ws-1", &since, &download)
+            .await
+            .unwrap();
+        assert_eq!(sync.commits.len(), 1);
+        assert!(sync.patch.contains("wip.txt"));
+        assert_eq!(
+            git_sync(&remote, &["status", "--porcelain"]),
+            "?? wip.txt\n",
+            "the runner's index is left alone"
+        );
+
+        import_bundle(&local, &download, &sync.head_commit)
+            .await
+            .unwrap();
+        assert_eq!(head_commit(&local).await.unwrap(), sync.head_commit);
+        assert!(local.join("feature.txt").exists());
+
+        let unchanged = workspaces
+            .collect_changes("ws-1", &sync.head_commit, &download)
+            .await
+            .unwrap();
+        assert!(unchanged.commits.is_empty());
+
+        workspaces.release("ws-1").await.unwrap();
+        assert!(!remote.exists());
+        workspaces.release("ws-1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn mirror_follows_runner_commits_and_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let local = temp.path().join("local");
+        init_repo(&local);
+        let runner = Arc::new(RunnerClientImpl::Local(
+            LocalRunner::new(Arc::new(ProcessManager::new()))
+                .with_workspaces(RunnerWorkspaces::new(temp.path().join("runner"))),
+        ));
+        let provisioned = runner
+            .provision_workspace(WorkspaceProvision {
+                workspace_id: "ws-1".to_string(),
+                source: WorkspaceSource::RemoteUrl(local.to_string_lossy().into_owned()),
+                branch: "main".to_string(),
+                runner_labels: Vec::new(),
+            })
+            .await
+            .unwrap();
+        let mirror = WorkspaceMirror::new(
+            Arc::clone(&runner),
+            "ws-1",
+            &local,
+            provisioned.head_commit,
+            Duration::from_secs(30),
+        );
+
+        let remote = PathBuf::from(&provisioned.working_dir);
+        git_sync(&remote, &["config", "user.email", "runner@example.com"]);
+        git_sync(&remote, &["config", "user.name", "Runner"]);
+        std::fs::write(remote.join("feature.txt"), "one\n").unwrap();
+        git_sync(&remote, &["add", "."]);
+        git_sync(&remote, &["commit", "-q", "-m", "feature"]);
+        std::fs::write(remote.join("wip.txt"), "draft\n").unwrap();
+
+        let first = mirror.sync_once().await.unwrap();
+        assert_eq!(first.commits.len(), 1);
+        assert_eq!(head_commit(&local).await.unwrap(), first.head_commit);
+        assert_eq!(
+            std::fs::read_to_string(local.join("wip.txt")).unwrap(),
+            "draft\n"
+        );
+
+        // The mirrored draft is replaced by the commit that took it over
+        std::fs::write(remote.join("wip.txt"), "final\n").unwrap();
+        git_sync(&remote, &["add", "."]);
+        git_sync(&remote, &["commit", "-q", "-m", "wip"]);
+        std::fs::write(remote.join("feature.txt"), "two\n").unwrap();
+
+        let second = mirror.sync_once().await.unwrap();
+        assert_eq!(second.commits.len(), 1);
+        assert_eq!(head_commit(&local).await.unwrap(), second.head_commit);
+        assert_eq!(
+            std::fs::read_to_string(local.join("wip.txt")).unwrap(),
+            "final\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(local.join("feature.txt")).unwrap(),
+            "two\n"
+        );
+        assert_eq!(
+            git_sync(&local, &["status", "--porcelain"]),
+            " M feature.txt\n"
+        );
+
+        runner.release_workspace("ws-1").await.unwrap();
+        assert!(!remote.exists());
+    }
+}
@@ -201,6 +201,9 @@ async fn create_terminal(db: &DBService, task_id: &str, order_index: i32) -> Str
         order_index,
         status: "not_started".to_string(),
         auto_confirm: true,
+        resource_limits: None,
+        resource_report: None,
+        runner_labels: None,
         process_id: None,
         pty_session_id: None,
         session_id: None,
@@ -136,6 +136,9 @@ async fn test_terminal_launch_creates_session() {
         order_index: 0,
         status: "not_started".to_string(),
         auto_confirm: true,
+        resource_limits: None,
+        resource_report: None,
+        runner_labels: None,
         process_id: None,
         pty_session_id: None,
         vk_session_id: None,
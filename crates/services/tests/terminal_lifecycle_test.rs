@@ -155,6 +155,9 @@ async fn create_terminal(
         created_at: Utc::now(),
         updated_at: Utc::now(),
         auto_confirm: true,
+        resource_limits: None,
+        resource_report: None,
+        runner_labels: None,
     };
 
     Terminal::create(&db.pool, &terminal).await.unwrap();
@@ -3,7 +3,11 @@ use std::sync::Arc;
 use chrono::Utc;
 use db::{
     DBService,
-    models::{Terminal, terminal::TerminalLog},
+    models::{
+        Terminal,
+        log_search::{LogSearchFilter, LogSearchHit},
+        terminal::TerminalLog,
+    },
 };
 use services::services::terminal::process::TerminalLogger;
 
@@ -91,6 +95,9 @@ async fn create_terminal(db: &DBService) -> String {
         order_index: 0,
         status: "running".to_string(),
         auto_confirm: true,
+        resource_limits: None,
+        resource_report: None,
+        runner_labels: None,
         process_id: None,
         pty_session_id: None,
         vk_session_id: None,
@@ -150,3 +157,47 @@ async fn test_terminal_logger_flushes_on_full_buffer() {
     assert!(contents.contains(&"line 2"));
     assert!(contents.contains(&"line 3"));
 }
+
+#[tokio::test]
+async fn test_terminal_output_is_searchable_with_seq() {
+    let db = setup_db().await;
+    let terminal_id = create_terminal(&db).await;
+
+    let logger = TerminalLogger::with_max_buffer_size(
+        Arc::clone(&db),
+        terminal_id.clone(),
+        "stdout",
+        60,
+        10,
+    );
+    logger
+        .append_output(7, "\x1b[31merror: linker failed\x1b[0m")
+        .await;
+    logger.append_output(8, "retrying").await;
+    logger.append("[stdout] system note about the error").await;
+    logger.flush().await.unwrap();
+
+    let filter = LogSearchFilter {
+        text: Some("ERROR linker".to_string()),
+        terminal_id: Some(terminal_id.clone()),
+        ..Default::default()
+    };
+    let hits = LogSearchHit::search(&db.pool, &filter, 50, 0)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].seq, Some(7));
+    assert_eq!(hits[0].terminal_id.as_deref(), Some(terminal_id.as_str()));
+    assert!(hits[0].workflow_id.is_some());
+
+    let filter = LogSearchFilter {
+        text: Some("error".to_string()),
+        terminal_id: Some(terminal_id),
+        ..Default::default()
+    };
+    let hits = LogSearchHit::search(&db.pool, &filter, 50, 0)
+        .await
+        .unwrap();
+    let seqs: Vec<Option<i64>> = hits.iter().map(|hit| hit.seq).collect();
+    assert_eq!(seqs, vec![Some(7), None]);
+}
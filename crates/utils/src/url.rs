@@ -9,7 +9,8 @@
 pub fn normalize_base_url(api_type: &str, raw_url: &str) -> String {
     let trimmed = raw_url.trim_end_matches('/');
     match api_type {
-        "openai" | "anthropic" => {
+        // "local" servers (Ollama, llama.cpp) expose the OpenAI API under /v1
+        "openai" | "anthropic" | "local" => {
             if trimmed.ends_with("/v1") {
                 trimmed.to_string()
             } else {
@@ -69,6 +70,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn local_server_gets_v1() {
+        assert_eq!(
+            normalize_base_url("local", "http://localhost:11434/"),
+            "http://localhost:11434/v1"
+        );
+        assert_eq!(
+            normalize_base_url("local", "http://127.0.0.1:8080/v1"),
+            "http://127.0.0.1:8080/v1"
+        );
+    }
+
     #[test]
     fn anthropic_compatible_no_v1() {
         assert_eq!(
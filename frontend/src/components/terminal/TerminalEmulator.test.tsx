@@ -1,5 +1,5 @@
 import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
-import { act, render, screen, waitFor } from '@testing-library/react';
+import { act, fireEvent, render, screen, waitFor } from '@testing-library/react';
 import { createRef } from 'react';
 import { TerminalEmulator, TerminalEmulatorRef } from './TerminalEmulator';
 
@@ -73,6 +73,10 @@ class MockWebSocket {
     this.onopen?.();
   }
 
+  simulateMessage(message: unknown) {
+    this.onmessage?.({ data: JSON.stringify(message) } as MessageEvent<string>);
+  }
+
   simulateUnexpectedClose(reason = 'network drop') {
     this.readyState = MockWebSocket.CLOSED;
     this.onclose?.({ code: 1006, reason } as CloseEvent);
@@ -352,6 +356,47 @@ describe('TerminalEmulator', () => {
     });
   });
 
+  describe('Input Lease', () => {
+    it('should offer take over and release controls for the input lease', async () => {
+      render(
+        <TerminalEmulator
+          terminalId={VALID_TERMINAL_ID}
+          wsUrl="ws://localhost:8080"
+        />
+      );
+
+      await waitFor(() => {
+        expect(MockWebSocket.instances.length).toBeGreaterThan(0);
+      });
+
+      const socket = MockWebSocket.instances[0];
+      act(() => {
+        socket.simulateOpen();
+        socket.simulateMessage({
+          type: 'input_lease',
+          owner: { kind: 'orchestrator' },
+          client_id: 'viewer:1',
+        });
+      });
+
+      expect(screen.getByText('Input held by the orchestrator')).toBeInTheDocument();
+      fireEvent.click(screen.getByRole('button', { name: 'Take over' }));
+      expect(socket.lastSent).toBe(JSON.stringify({ type: 'take_input' }));
+
+      act(() => {
+        socket.simulateMessage({
+          type: 'input_lease',
+          owner: { kind: 'client', client_id: 'viewer:1' },
+          client_id: 'viewer:1',
+        });
+      });
+
+      expect(screen.getByText('You are typing in this terminal')).toBeInTheDocument();
+      fireEvent.click(screen.getByRole('button', { name: 'Release' }));
+      expect(socket.lastSent).toBe(JSON.stringify({ type: 'release_input' }));
+    });
+  });
+
   describe('Error Handling', () => {
     it('should show disconnected hint when stream closes unexpectedly', async () => {
       render(
@@ -2,7 +2,12 @@ import { useEffect, useRef, useCallback, forwardRef, useImperativeHandle, useSta
 import { Terminal } from '@xterm/xterm';
 import { FitAddon } from '@xterm/addon-fit';
 import '@xterm/xterm/css/xterm.css';
-import { isWsOutputMessage, isWsErrorMessage } from '@/types/websocket';
+import {
+  isWsOutputMessage,
+  isWsErrorMessage,
+  isWsInputLeaseMessage,
+  type WsInputLeaseMessage,
+} from '@/types/websocket';
 
 const TERMINAL_ID_REGEX =
   /^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$/i;
@@ -23,6 +28,9 @@ const PENDING_INPUT_MAX_SIZE = 1000;
 
 type ConnectionState = 'idle' | 'connecting' | 'connected' | 'disconnected';
 
+const holdsInputLease = (lease: WsInputLeaseMessage | null) =>
+  lease?.owner.kind === 'client' && lease.owner.client_id === lease.client_id;
+
 interface Props {
   terminalId: string;
   wsUrl?: string;
@@ -56,6 +64,10 @@ export const TerminalEmulator = forwardRef<TerminalEmulatorRef, Props>(
     const [wsKey, setWsKey] = useState(0);
     const [connectionState, setConnectionState] = useState<ConnectionState>('idle');
     const [disconnectHint, setDisconnectHint] = useState<string | null>(null);
+    // Only the lease holder's keystrokes reach the PTY; others stay read-only
+    const [inputLease, setInputLease] = useState<WsInputLeaseMessage | null>(null);
+    const inputLeaseRef = useRef<WsInputLeaseMessage | null>(null);
+    inputLeaseRef.current = inputLease;
 
     const markConnecting = useCallback(() => {
       setConnectionState('connecting');
@@ -140,6 +152,10 @@ export const TerminalEmulator = forwardRef<TerminalEmulatorRef, Props>(
     // Stable handlers
     const handleData = useCallback((data: string) => {
       onData?.(data);
+      const lease = inputLeaseRef.current;
+      if (lease && !holdsInputLease(lease)) {
+        return;
+      }
       if (wsRef.current?.readyState === WebSocket.OPEN) {
         wsRef.current.send(JSON.stringify({ type: 'input', data }));
       } else if (pendingInputRef.current.length < PENDING_INPUT_MAX_SIZE) {
@@ -147,6 +163,12 @@ export const TerminalEmulator = forwardRef<TerminalEmulatorRef, Props>(
       }
     }, [onData]);
 
+    const sendLeaseRequest = useCallback((type: 'take_input' | 'release_input') => {
+      if (wsRef.current?.readyState === WebSocket.OPEN) {
+        wsRef.current.send(JSON.stringify({ type }));
+      }
+    }, []);
+
     const handleResize = useCallback((cols: number, rows: number) => {
       onResize?.(cols, rows);
       if (wsRef.current?.readyState === WebSocket.OPEN) {
@@ -234,6 +256,7 @@ export const TerminalEmulator = forwardRef<TerminalEmulatorRef, Props>(
       markConnecting();
       clearKeepAliveTimer();
       skipNextAutoReconnectRef.current = false;
+      setInputLease(null);
 
       // Basic validation
       if (!terminalId || !TERMINAL_ID_REGEX.test(terminalId)) {
@@ -289,6 +312,8 @@ export const TerminalEmulator = forwardRef<TerminalEmulatorRef, Props>(
           // Use type guards for safe message handling
           if (isWsOutputMessage(message)) {
             terminalRef.current?.write(message.data);
+          } else if (isWsInputLeaseMessage(message)) {
+            setInputLease(message);
           } else if (isWsErrorMessage(message)) {
             // Handle error message properly
             notifyError('Terminal WebSocket error message', new Error(message.message));
@@ -317,6 +342,7 @@ export const TerminalEmulator = forwardRef<TerminalEmulatorRef, Props>(
         clearKeepAliveTimer();
 
         if (isActive) {
+          setInputLease(null);
           const reason = event?.reason?.trim();
           const showCode = typeof code === 'number' && code !== 1000 && code !== 1005;
           const detail = reason || (showCode ? `code ${code}` : '');
@@ -353,6 +379,15 @@ export const TerminalEmulator = forwardRef<TerminalEmulatorRef, Props>(
     ]);
 
     const showConnectingHint = Boolean(wsUrl && connectionState === 'connecting');
+    const holdsInput = holdsInputLease(inputLease);
+    const showInputLease = connectionState === 'connected' && inputLease !== null;
+    let inputLeaseHint = 'You are typing in this terminal';
+    if (!holdsInput) {
+      inputLeaseHint =
+        inputLease?.owner.kind === 'orchestrator'
+          ? 'Input held by the orchestrator'
+          : 'Input held by another viewer';
+    }
 
     return (
       <div className="relative w-full h-full min-h-[300px]">
@@ -380,6 +415,22 @@ export const TerminalEmulator = forwardRef<TerminalEmulatorRef, Props>(
             {disconnectHint ?? DISCONNECTED_HINT}
           </div>
         ) : null}
+        {showInputLease ? (
+          <div
+            role="status"
+            aria-live="polite"
+            className="absolute bottom-3 right-3 flex items-center gap-2 rounded-md bg-black/70 px-2 py-1 text-xs text-gray-200"
+          >
+            <span>{inputLeaseHint}</span>
+            <button
+              type="button"
+              className="rounded border border-gray-500 px-2 py-0.5 hover:bg-white/10"
+              onClick={() => sendLeaseRequest(holdsInput ? 'release_input' : 'take_input')}
+            >
+              {holdsInput ? 'Release' : 'Take over'}
+            </button>
+          </div>
+        ) : null}
       </div>
     );
   }
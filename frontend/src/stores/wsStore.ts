@@ -31,7 +31,9 @@ export type WsEventType =
   | 'quality.gate_result'
   | 'provider.switched'
   | 'provider.exhausted'
-  | 'provider.recovered';
+  | 'provider.recovered'
+  | 'orchestrator.stream'
+  | 'terminal.activity_escalated';
 
 type ConnectionStatus =
   | 'disconnected'
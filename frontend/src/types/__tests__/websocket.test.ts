@@ -1,4 +1,4 @@
-import { isWsOutputMessage, isWsErrorMessage } from '../websocket';
+import { isWsOutputMessage, isWsErrorMessage, isWsInputLeaseMessage } from '../websocket';
 
 describe('WebSocket Type Guards', () => {
     describe('isWsOutputMessage', () => {
@@ -61,4 +61,37 @@ describe('WebSocket Type Guards', () => {
             expect(isWsErrorMessage(123)).toBe(false);
         });
     });
+
+    describe('isWsInputLeaseMessage', () => {
+        it('should return true for client and orchestrator owners', () => {
+            const held = {
+                type: 'input_lease',
+                owner: { kind: 'client', client_id: 'viewer:1' },
+                client_id: 'viewer:1',
+            };
+            const orchestrated = {
+                type: 'input_lease',
+                owner: { kind: 'orchestrator' },
+                client_id: 'viewer:1',
+            };
+            expect(isWsInputLeaseMessage(held)).toBe(true);
+            expect(isWsInputLeaseMessage(orchestrated)).toBe(true);
+        });
+
+        it('should return false for malformed owners', () => {
+            const noOwner = { type: 'input_lease', client_id: 'viewer:1' };
+            const badOwner = {
+                type: 'input_lease',
+                owner: { kind: 'client' },
+                client_id: 'viewer:1',
+            };
+            expect(isWsInputLeaseMessage(noOwner)).toBe(false);
+            expect(isWsInputLeaseMessage(badOwner)).toBe(false);
+        });
+
+        it('should return false for other message types', () => {
+            expect(isWsInputLeaseMessage({ type: 'output', data: 'x' })).toBe(false);
+            expect(isWsInputLeaseMessage(null)).toBe(false);
+        });
+    });
 });
@@ -56,3 +56,37 @@ export function isWsErrorMessage(msg: unknown): msg is WsErrorMessage {
         typeof (msg as WsErrorMessage).message === 'string'
     );
 }
+
+/**
+ * Input lease update from server to client (current holder of terminal input)
+ */
+export type WsInputLeaseMessage = Extract<WsMessage, { type: 'input_lease' }>;
+
+/**
+ * Type guard for WsInputLeaseMessage
+ * @param msg - Unknown message to check
+ * @returns True if msg is a valid WsInputLeaseMessage
+ */
+export function isWsInputLeaseMessage(msg: unknown): msg is WsInputLeaseMessage {
+    if (
+        typeof msg !== 'object' ||
+        msg === null ||
+        !('type' in msg) ||
+        (msg as WsMessage).type !== 'input_lease' ||
+        !('client_id' in msg) ||
+        typeof (msg as WsInputLeaseMessage).client_id !== 'string' ||
+        !('owner' in msg)
+    ) {
+        return false;
+    }
+    const owner: unknown = (msg as WsInputLeaseMessage).owner;
+    if (typeof owner !== 'object' || owner === null || !('kind' in owner)) {
+        return false;
+    }
+    const { kind } = owner as { kind: unknown };
+    return (
+        kind === 'orchestrator' ||
+        (kind === 'client' &&
+            typeof (owner as { client_id?: unknown }).client_id === 'string')
+    );
+}
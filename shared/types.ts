@@ -13,11 +13,15 @@ mergeTerminalCliId: string | null,
 /**
  * See `merge_terminal_cli_id` — same backward-compat rationale.
  */
-mergeTerminalModelId: string | null, targetBranch: string, gitWatcherEnabled: boolean, readyAt: string | null, startedAt: string | null, completedAt: string | null, createdAt: string, updatedAt: string, tasks: Array<WorkflowTaskDto>, commands: Array<WorkflowCommandDto>, };
+mergeTerminalModelId: string | null, targetBranch: string, gitWatcherEnabled: boolean, 
+/**
+ * Why the workflow is paused (e.g. "user_requested", "budget_cost_exceeded")
+ */
+pauseReason: string | null, budget: WorkflowBudgetDto, activityPolicy: WorkflowActivityPolicyDto, readyAt: string | null, startedAt: string | null, completedAt: string | null, createdAt: string, updatedAt: string, tasks: Array<WorkflowTaskDto>, commands: Array<WorkflowCommandDto>, };
 
-export type WorkflowTaskDto = { id: string, workflowId: string, vkTaskId: string | null, name: string, description: string | null, branch: string, status: string, orderIndex: number, startedAt: string | null, completedAt: string | null, createdAt: string, updatedAt: string, terminals: Array<TerminalDto>, };
+export type WorkflowTaskDto = { id: string, workflowId: string, vkTaskId: string | null, name: string, description: string | null, branch: string, status: string, orderIndex: number, dependsOn: Array<string>, startedAt: string | null, completedAt: string | null, createdAt: string, updatedAt: string, terminals: Array<TerminalDto>, };
 
-export type TerminalDto = { id: string, workflowTaskId: string, cliTypeId: string, modelConfigId: string, customBaseUrl: string | null, customApiKey?: string, role: string | null, roleDescription: string | null, orderIndex: number, status: string, autoConfirm: boolean, lastCommitHash: string | null, lastCommitMessage: string | null, startedAt: string | null, completedAt: string | null, createdAt: string, updatedAt: string, };
+export type TerminalDto = { id: string, workflowTaskId: string, cliTypeId: string, modelConfigId: string, customBaseUrl: string | null, customApiKey?: string, role: string | null, roleDescription: string | null, orderIndex: number, status: string, autoConfirm: boolean, resourceLimits: TerminalResourceLimits | null, resourceReport: TerminalResourceReport | null, runnerLabels: Array<string> | null, lastCommitHash: string | null, lastCommitMessage: string | null, startedAt: string | null, completedAt: string | null, createdAt: string, updatedAt: string, };
 
 export type WorkflowCommandDto = { id: string, workflowId: string, presetId: string, orderIndex: number, customParams: string | null, createdAt: string, preset: SlashCommandPresetDto, };
 
@@ -25,6 +29,29 @@ export type SlashCommandPresetDto = { id: string, command: string, description:
 
 export type WorkflowListItemDto = { id: string, projectId: string, name: string, description: string | null, status: string, executionMode: string, createdAt: string, updatedAt: string, tasksCount: number, terminalsCount: number, };
 
+/**
+ * Orchestrator budget limits (`None` = unlimited)
+ */
+export type WorkflowBudgetDto = { maxTokens: bigint | null, maxCostUsd: number | null, maxDurationSecs: bigint | null, };
+
+/**
+ * Terminal activity monitor thresholds (`None` = default, 0 = disabled)
+ */
+export type WorkflowActivityPolicyDto = { stallTimeoutSecs: bigint | null, loopThreshold: bigint | null, escalationIntervalSecs: bigint | null, };
+
+/**
+ * Orchestrator LLM usage and spend for a workflow
+ */
+export type WorkflowUsageDto = { workflowId: string, status: string, pauseReason: string | null, budget: WorkflowBudgetDto, promptTokens: bigint, completionTokens: bigint, totalTokens: bigint, 
+/**
+ * Spend in USD for models with a known price
+ */
+costUsd: number, llmCalls: bigint, 
+/**
+ * Calls whose model has no known price (not included in `cost_usd`)
+ */
+unpricedCalls: bigint, activeSecs: bigint, };
+
 export type SharedTaskResponse = { task: SharedTask, user: UserData | null, };
 
 export type AssigneesQuery = { project_id: string, };
@@ -241,7 +268,9 @@ export type CheckEditorAvailabilityResponse = { available: boolean, };
 
 export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
 
-export type WsMessage = { "type": "input", data: string, } | { "type": "output", data: string, } | { "type": "resize", cols: number, rows: number, } | { "type": "heartbeat" } | { "type": "error", message: string, };
+export type InputOwner = { "kind": "orchestrator" } | { "kind": "client", client_id: string, };
+
+export type WsMessage = { "type": "input", data: string, } | { "type": "output", data: string, } | { "type": "resize", cols: number, rows: number, } | { "type": "heartbeat" } | { "type": "error", message: string, } | { "type": "take_input" } | { "type": "release_input" } | { "type": "input_lease", owner: InputOwner, client_id: string, };
 
 export type CurrentUserResponse = { user_id: string, };
 
@@ -636,6 +665,10 @@ reportJson: string | null,
  * Serialized QualityGateDecision
  */
 decisionJson: string | null, 
+/**
+ * SARIF 2.1.0 export of the report
+ */
+sarifJson: string | null, 
 /**
  * Error message if the run itself failed
  */
@@ -661,6 +694,14 @@ export type QualityRunSummary = { id: string, workflowId: string, taskId: string
 
 export type QualityRunDetail = { providersRun: JsonValue | null, reportJson: JsonValue | null, decisionJson: JsonValue | null, id: string, workflowId: string, taskId: string | null, terminalId: string | null, commitHash: string | null, gateLevel: string, gateStatus: string, mode: string, totalIssues: number, blockingIssues: number, newIssues: number, durationMs: number, errorMessage: string | null, createdAt: string, completedAt: string | null, };
 
+export type CreateQualityBaselineRequest = { 
+/**
+ * Replace an existing baseline instead of failing.
+ */
+refresh: boolean, };
+
+export type QualityBaselineInfo = { path: string, createdAt: string, totalIssues: number, fingerprints: number, };
+
 export type WsEvent = { 
 /**
  * Event type (e.g., "workflow.status_changed")
@@ -679,7 +720,7 @@ timestamp: string,
  */
 id: string, };
 
-export type WsEventType = "workflow.status_changed" | "terminal.status_changed" | "task.status_changed" | "terminal.completed" | "git.commit_detected" | "orchestrator.awakened" | "orchestrator.decision" | "system.heartbeat" | "system.lagged" | "system.error" | "terminal.prompt_detected" | "terminal.prompt_decision" | "provider.switched" | "provider.exhausted" | "provider.recovered" | "quality.gate_result";
+export type WsEventType = "workflow.status_changed" | "terminal.status_changed" | "task.status_changed" | "terminal.completed" | "git.commit_detected" | "orchestrator.awakened" | "orchestrator.decision" | "system.heartbeat" | "system.lagged" | "system.error" | "terminal.prompt_detected" | "terminal.prompt_decision" | "provider.switched" | "provider.exhausted" | "provider.recovered" | "quality.gate_result" | "orchestrator.stream" | "terminal.activity_escalated";
 
 export type WorkflowStatus = "created" | "starting" | "ready" | "running" | "paused" | "merging" | "completed" | "failed" | "cancelled";
 
@@ -687,6 +728,58 @@ export type WorkflowTaskStatus = "pending" | "running" | "review_pending" | "com
 
 export type TerminalStatus = "not_started" | "starting" | "waiting" | "working" | "completed" | "failed" | "cancelled" | "review_passed" | "review_rejected" | "quality_pending";
 
+/**
+ * Terminal resource limits
+ *
+ * Every limit is optional; limits are enforced on Linux (see
+ * `services::terminal::sandbox`), other platforms only get the wall-clock timeout.
+ */
+export type TerminalResourceLimits = { 
+/**
+ * CPU cap in percent of one core (200 = two cores)
+ */
+cpuPercent: number | null, 
+/**
+ * Memory cap in MiB
+ */
+memoryMb: number | null, 
+/**
+ * Maximum number of processes (threads included)
+ */
+maxProcesses: number | null, 
+/**
+ * Kill the terminal process after this many seconds
+ */
+wallClockTimeoutSecs: number | null, 
+/**
+ * Run without network access
+ */
+networkDisabled: boolean, };
+
+/**
+ * Limited resource
+ */
+export type TerminalResource = "cpu" | "memory" | "processes" | "wall_clock" | "network";
+
+/**
+ * Mechanism enforcing a resource limit
+ */
+export type ResourceEnforcement = "cgroup" | "rlimit" | "namespace" | "supervisor" | "unenforced";
+
+/**
+ * Enforcement of one requested limit
+ */
+export type AppliedResourceLimit = { resource: TerminalResource, enforcement: ResourceEnforcement, };
+
+/**
+ * Resource limits in effect for a PTY session
+ */
+export type TerminalResourceReport = { applied: Array<AppliedResourceLimit>, 
+/**
+ * cgroup v2 directory of the session, when cgroups are used
+ */
+cgroupPath: string | null, };
+
 export type CliType = { 
 /**
  * Primary key ID, format: cli-{name}
@@ -805,6 +898,146 @@ executablePath: string | null,
  */
 installGuideUrl: string | null, };
 
+export type WorkflowTemplate = { id: string, name: string, description: string | null, 
+/**
+ * Schema version of the template document
+ */
+schemaVersion: number, 
+/**
+ * Revision of this template, starting at 1
+ */
+version: number, content: string, 
+/**
+ * Workflow the template was exported from (if any)
+ */
+sourceWorkflowId: string | null, createdAt: string, updatedAt: string, };
+
+export type TemplateFormat = "json" | "yaml";
+
+export type WorkflowTemplateDocument = { schemaVersion: number, 
+/**
+ * Template name (not the name of instantiated workflows)
+ */
+name: string, description: string | null, 
+/**
+ * Parameters that can be referenced as `{{name}}`
+ */
+parameters: Array<TemplateParameter>, workflow: TemplateWorkflow, 
+/**
+ * Slash commands, in execution order
+ */
+commands: Array<TemplateCommand>, 
+/**
+ * Tasks, in order
+ */
+tasks: Array<TemplateTask>, };
+
+export type TemplateParameter = { name: string, description: string | null, 
+/**
+ * Used when the parameter is not supplied; without it the parameter is required
+ */
+default: string | null, };
+
+export type TemplateWorkflow = { name: string, description: string | null, executionMode: string, initialGoal: string | null, useSlashCommands: boolean, targetBranch: string | null, gitWatcherEnabled: boolean, 
+/**
+ * Main Agent settings; the API key is supplied at instantiation
+ */
+orchestrator: TemplateOrchestrator | null, errorTerminal: TemplateTerminalConfig | null, mergeTerminal: TemplateTerminalConfig, budget: TemplateBudget | null, activityPolicy: TemplateActivityPolicy | null, };
+
+export type TemplateOrchestrator = { apiType: string, baseUrl: string, model: string, };
+
+export type TemplateTerminalConfig = { cliTypeId: string, modelConfigId: string, customBaseUrl: string | null, };
+
+export type TemplateBudget = { maxTokens: bigint | null, maxCostUsd: number | null, maxDurationSecs: bigint | null, };
+
+export type TemplateActivityPolicy = { stallTimeoutSecs: bigint | null, loopThreshold: bigint | null, escalationIntervalSecs: bigint | null, };
+
+export type TemplateCommand = { presetId: string, 
+/**
+ * JSON string passed to the preset as-is (not rendered)
+ */
+customParams: string | null, };
+
+export type TemplateTask = { 
+/**
+ * Identifier used by `dependsOn`, unique within the template
+ */
+key: string, name: string, description: string | null, 
+/**
+ * Custom branch; auto-generated when omitted
+ */
+branch: string | null, 
+/**
+ * Keys of tasks that must complete first
+ */
+dependsOn: Array<string>, terminals: Array<TemplateTerminal>, };
+
+export type TemplateTerminal = { cliTypeId: string, modelConfigId: string, customBaseUrl: string | null, role: string | null, roleDescription: string | null, autoConfirm: boolean, resourceLimits: TerminalResourceLimits | null, runnerLabels: Array<string> | null, };
+
+export type OrchestratorDecision = { id: string, workflowId: string, 
+/**
+ * Linked timeline entry
+ */
+workflowEventId: string | null, 
+/**
+ * instruction | prompt_decision | merge
+ */
+decisionType: string, 
+/**
+ * Instruction type (`send_to_terminal`, ...), prompt decision action
+ * (`auto_confirm`, `llm_decision`, ...) or merge kind
+ */
+action: string, 
+/**
+ * applied | failed | timed_out
+ */
+status: string, terminalId: string | null, taskId: string | null, 
+/**
+ * Why the decision was made (LLM reply text, prompt decision reason)
+ */
+reasoning: string | null, 
+/**
+ * Full decision payload as JSON
+ */
+detail: string | null, error: string | null, 
+/**
+ * LLM provider that produced the decision
+ */
+provider: string | null, promptTokens: bigint | null, completionTokens: bigint | null, 
+/**
+ * Bus message variant that triggered the decision (or `user_chat`)
+ */
+triggerType: string | null, 
+/**
+ * Triggering bus message as JSON
+ */
+triggerMessage: string | null, createdAt: string, };
+
+export type DecisionPage = { items: Array<OrchestratorDecision>, 
+/**
+ * Number of decisions matching the filters
+ */
+total: bigint, limit: bigint, offset: bigint, };
+
+export type RecordingInfo = { sessionId: string, sizeBytes: bigint, modifiedAt: string, };
+
+export type LogSearchHit = { 
+/**
+ * terminal | execution
+ */
+source: string, terminalId: string | null, workflowId: string | null, executionId: string | null, 
+/**
+ * Position to jump to: the PTY output chunk seq for terminal logs, the
+ * zero-based message index for execution logs
+ */
+seq: bigint | null, content: string, createdAt: string, };
+
+export type LogSearchPage = { items: Array<LogSearchHit>, limit: bigint, offset: bigint, 
+/**
+ * True when a regex search stopped at the scan limit before filling the page
+ */
+truncated: boolean, };
+
 export const DEFAULT_PR_DESCRIPTION_PROMPT = `Update the PR that was just created with a better title and description.
 The PR number is #{pr_number} and the URL is {pr_url}.
 